@@ -152,6 +152,36 @@ if [ ! -x "$GRADLEW_PATH" ]; then
   chmod +x "$GRADLEW_PATH"
 fi
 
+echo "Adding the hardware signing foreground service..."
+# Keeps the USB link to the hardware wallet alive while it waits for approval
+SERVICE_SRC="$PROJECT_ROOT/scripts/android/HardwareSigningService.java"
+SERVICE_DIR="app/src/main/java/com/unruggable/usb"
+MANIFEST="app/src/main/AndroidManifest.xml"
+mkdir -p "$SERVICE_DIR"
+cp "$SERVICE_SRC" "$SERVICE_DIR/"
+if [ -f "$MANIFEST" ]; then
+  grep -q "FOREGROUND_SERVICE_CONNECTED_DEVICE" "$MANIFEST" || sed -i '' -E 's#(<application)#<uses-permission android:name="android.permission.FOREGROUND_SERVICE" />\
+    <uses-permission android:name="android.permission.FOREGROUND_SERVICE_CONNECTED_DEVICE" />\
+    <uses-permission android:name="android.permission.POST_NOTIFICATIONS" />\
+    <uses-feature android:name="android.hardware.usb.host" android:required="false" />\
+    \1#' "$MANIFEST"
+  grep -q "HardwareSigningService" "$MANIFEST" || sed -i '' -E 's#(</application>)#    <service android:name="com.unruggable.usb.HardwareSigningService" android:exported="false" android:foregroundServiceType="connectedDevice" />\
+    \1#' "$MANIFEST"
+else
+  echo "Warning: $MANIFEST not found; the signing service will not be registered."
+fi
+
+echo "Adding the Rust bridge classes..."
+# Static helpers the Rust code calls over JNI (see the BRIDGE_CLASS constants)
+BRIDGE_DIR="app/src/main/java/com/unruggable/app"
+mkdir -p "$BRIDGE_DIR"
+cp "$PROJECT_ROOT/scripts/android/SecretStoreBridge.java" "$BRIDGE_DIR/"
+cp "$PROJECT_ROOT/scripts/android/PasskeyBridge.java" "$BRIDGE_DIR/"
+if [ -f "$MANIFEST" ]; then
+  grep -q "USE_BIOMETRIC" "$MANIFEST" || sed -i '' -E 's#(<application)#<uses-permission android:name="android.permission.USE_BIOMETRIC" />\
+    \1#' "$MANIFEST"
+fi
+
 echo "Cleaning build artifacts..."
 "$GRADLEW_PATH" clean
 
@@ -0,0 +1,51 @@
+package com.unruggable.usb;
+
+import android.app.Notification;
+import android.app.NotificationChannel;
+import android.app.NotificationManager;
+import android.app.Service;
+import android.content.Intent;
+import android.content.pm.ServiceInfo;
+import android.os.Build;
+import android.os.IBinder;
+
+/**
+ * Foreground service started from Rust while the hardware wallet is waiting
+ * for the user to approve a signature. It keeps the process (and its USB
+ * connection) alive if the app is backgrounded mid-approval.
+ */
+public class HardwareSigningService extends Service {
+    private static final String CHANNEL_ID = "hardware_signing";
+    private static final int NOTIFICATION_ID = 4201;
+
+    @Override
+    public int onStartCommand(Intent intent, int flags, int startId) {
+        Notification.Builder builder;
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
+            NotificationChannel channel = new NotificationChannel(
+                    CHANNEL_ID, "Hardware wallet signing", NotificationManager.IMPORTANCE_LOW);
+            getSystemService(NotificationManager.class).createNotificationChannel(channel);
+            builder = new Notification.Builder(this, CHANNEL_ID);
+        } else {
+            builder = new Notification.Builder(this);
+        }
+        Notification notification = builder
+                .setContentTitle("Waiting for your hardware wallet")
+                .setContentText("Approve or reject the request on your device.")
+                .setSmallIcon(getApplicationInfo().icon)
+                .setOngoing(true)
+                .build();
+
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.Q) {
+            startForeground(NOTIFICATION_ID, notification, ServiceInfo.FOREGROUND_SERVICE_TYPE_CONNECTED_DEVICE);
+        } else {
+            startForeground(NOTIFICATION_ID, notification);
+        }
+        return START_NOT_STICKY;
+    }
+
+    @Override
+    public IBinder onBind(Intent intent) {
+        return null;
+    }
+}
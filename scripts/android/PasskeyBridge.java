@@ -0,0 +1,134 @@
+package com.unruggable.app;
+
+import android.app.Activity;
+import android.hardware.biometrics.BiometricManager;
+import android.hardware.biometrics.BiometricPrompt;
+import android.os.Build;
+import android.os.CancellationSignal;
+import android.security.keystore.KeyGenParameterSpec;
+import android.security.keystore.KeyProperties;
+import android.util.Log;
+
+import java.security.KeyStore;
+import java.util.UUID;
+import java.util.concurrent.CountDownLatch;
+import java.util.concurrent.Executor;
+import java.util.concurrent.TimeUnit;
+import java.util.concurrent.atomic.AtomicReference;
+
+import javax.crypto.KeyGenerator;
+import javax.crypto.Mac;
+import javax.crypto.SecretKey;
+
+/**
+ * Biometric authenticator called from Rust (src/signing/passkey.rs). Each
+ * credential is an HMAC-SHA256 key in the Android Keystore that can only be
+ * used right after a strong biometric check; the secret for a salt is
+ * HMAC(salt), computed once BiometricPrompt succeeds. deriveSecret blocks
+ * until the prompt completes, so it must not be called on the UI thread.
+ * Failures and cancellations are reported as null rather than thrown.
+ */
+public final class PasskeyBridge {
+    private static final String TAG = "PasskeyBridge";
+    private static final String KEYSTORE = "AndroidKeyStore";
+    private static final String ALIAS_PREFIX = "unruggable_passkey_";
+    private static final long PROMPT_TIMEOUT_SECONDS = 120;
+
+    private PasskeyBridge() {}
+
+    public static boolean isAvailable(Activity activity) {
+        if (Build.VERSION.SDK_INT < Build.VERSION_CODES.R) {
+            return false;
+        }
+        BiometricManager manager = activity.getSystemService(BiometricManager.class);
+        return manager != null
+                && manager.canAuthenticate(BiometricManager.Authenticators.BIOMETRIC_STRONG)
+                        == BiometricManager.BIOMETRIC_SUCCESS;
+    }
+
+    /** Create a biometric-bound key and return its alias as the credential id */
+    public static String createCredential(Activity activity, String userName) {
+        if (!isAvailable(activity)) {
+            return null;
+        }
+        String alias = ALIAS_PREFIX + UUID.randomUUID();
+        try {
+            KeyGenerator generator = KeyGenerator.getInstance(KeyProperties.KEY_ALGORITHM_HMAC_SHA256, KEYSTORE);
+            generator.init(new KeyGenParameterSpec.Builder(alias, KeyProperties.PURPOSE_SIGN)
+                    .setUserAuthenticationRequired(true)
+                    .setUserAuthenticationParameters(0, KeyProperties.AUTH_BIOMETRIC_STRONG)
+                    .setInvalidatedByBiometricEnrollment(true)
+                    .build());
+            generator.generateKey();
+            return alias;
+        } catch (Exception e) {
+            Log.e(TAG, "Failed to create a credential for " + userName, e);
+            return null;
+        }
+    }
+
+    /** Prompt for biometrics and return HMAC(salt) under the credential's key */
+    public static byte[] deriveSecret(Activity activity, String credentialId, byte[] salt) {
+        if (Build.VERSION.SDK_INT < Build.VERSION_CODES.R) {
+            return null;
+        }
+        Mac mac;
+        try {
+            KeyStore keyStore = KeyStore.getInstance(KEYSTORE);
+            keyStore.load(null);
+            SecretKey key = (SecretKey) keyStore.getKey(credentialId, null);
+            if (key == null) {
+                Log.e(TAG, "No key for credential " + credentialId);
+                return null;
+            }
+            mac = Mac.getInstance("HmacSHA256");
+            // Throws KeyPermanentlyInvalidatedException once biometrics change
+            mac.init(key);
+        } catch (Exception e) {
+            Log.e(TAG, "Failed to load credential " + credentialId, e);
+            return null;
+        }
+
+        AtomicReference<byte[]> secret = new AtomicReference<>();
+        CountDownLatch done = new CountDownLatch(1);
+        CancellationSignal cancel = new CancellationSignal();
+        Executor executor = activity.getMainExecutor();
+
+        activity.runOnUiThread(() -> {
+            BiometricPrompt prompt = new BiometricPrompt.Builder(activity)
+                    .setTitle("Unruggable")
+                    .setSubtitle("Confirm it's you")
+                    .setAllowedAuthenticators(BiometricManager.Authenticators.BIOMETRIC_STRONG)
+                    .setNegativeButton("Cancel", executor, (dialog, which) -> done.countDown())
+                    .build();
+            prompt.authenticate(new BiometricPrompt.CryptoObject(mac), cancel, executor,
+                    new BiometricPrompt.AuthenticationCallback() {
+                        @Override
+                        public void onAuthenticationSucceeded(BiometricPrompt.AuthenticationResult result) {
+                            try {
+                                secret.set(result.getCryptoObject().getMac().doFinal(salt));
+                            } catch (Exception e) {
+                                Log.e(TAG, "Failed to derive the secret", e);
+                            }
+                            done.countDown();
+                        }
+
+                        @Override
+                        public void onAuthenticationError(int errorCode, CharSequence message) {
+                            Log.w(TAG, "Biometric prompt ended: " + message);
+                            done.countDown();
+                        }
+                    });
+        });
+
+        try {
+            if (!done.await(PROMPT_TIMEOUT_SECONDS, TimeUnit.SECONDS)) {
+                cancel.cancel();
+            }
+        } catch (InterruptedException e) {
+            cancel.cancel();
+            Thread.currentThread().interrupt();
+        }
+        return secret.get();
+    }
+}
@@ -0,0 +1,98 @@
+package com.unruggable.app;
+
+import android.app.Activity;
+import android.content.Context;
+import android.content.SharedPreferences;
+import android.security.keystore.KeyGenParameterSpec;
+import android.security.keystore.KeyProperties;
+import android.util.Base64;
+import android.util.Log;
+
+import java.nio.ByteBuffer;
+import java.nio.charset.StandardCharsets;
+import java.security.KeyStore;
+
+import javax.crypto.Cipher;
+import javax.crypto.KeyGenerator;
+import javax.crypto.SecretKey;
+import javax.crypto.spec.GCMParameterSpec;
+
+/**
+ * Secret store called from Rust (src/storage/secrets.rs). Values are
+ * encrypted with AES-256-GCM under a key that never leaves the Android
+ * Keystore and kept in private shared preferences. Failures are reported
+ * as null / false rather than thrown, so no Java exception is left pending
+ * on the calling native thread.
+ */
+public final class SecretStoreBridge {
+    private static final String TAG = "SecretStoreBridge";
+    private static final String KEYSTORE = "AndroidKeyStore";
+    private static final String KEY_ALIAS = "unruggable_secret_store";
+    private static final String PREFS = "unruggable_secrets";
+    private static final String TRANSFORMATION = "AES/GCM/NoPadding";
+    private static final int IV_LENGTH = 12;
+    private static final int TAG_BITS = 128;
+
+    private SecretStoreBridge() {}
+
+    /** The stored value, or null if there is none or it can't be decrypted */
+    public static synchronized String get(Activity activity, String name) {
+        String stored = prefs(activity).getString(name, null);
+        if (stored == null) {
+            return null;
+        }
+        try {
+            ByteBuffer sealed = ByteBuffer.wrap(Base64.decode(stored, Base64.NO_WRAP));
+            byte[] iv = new byte[IV_LENGTH];
+            sealed.get(iv);
+            byte[] ciphertext = new byte[sealed.remaining()];
+            sealed.get(ciphertext);
+
+            Cipher cipher = Cipher.getInstance(TRANSFORMATION);
+            cipher.init(Cipher.DECRYPT_MODE, key(), new GCMParameterSpec(TAG_BITS, iv));
+            return new String(cipher.doFinal(ciphertext), StandardCharsets.UTF_8);
+        } catch (Exception e) {
+            Log.e(TAG, "Failed to read " + name, e);
+            return null;
+        }
+    }
+
+    public static synchronized boolean set(Activity activity, String name, String value) {
+        try {
+            Cipher cipher = Cipher.getInstance(TRANSFORMATION);
+            cipher.init(Cipher.ENCRYPT_MODE, key());
+            byte[] iv = cipher.getIV();
+            byte[] ciphertext = cipher.doFinal(value.getBytes(StandardCharsets.UTF_8));
+            byte[] sealed = ByteBuffer.allocate(iv.length + ciphertext.length).put(iv).put(ciphertext).array();
+            return prefs(activity).edit().putString(name, Base64.encodeToString(sealed, Base64.NO_WRAP)).commit();
+        } catch (Exception e) {
+            Log.e(TAG, "Failed to store " + name, e);
+            return false;
+        }
+    }
+
+    public static synchronized void delete(Activity activity, String name) {
+        prefs(activity).edit().remove(name).commit();
+    }
+
+    private static SharedPreferences prefs(Activity activity) {
+        return activity.getSharedPreferences(PREFS, Context.MODE_PRIVATE);
+    }
+
+    /** The store's Keystore key, created on first use */
+    private static SecretKey key() throws Exception {
+        KeyStore keyStore = KeyStore.getInstance(KEYSTORE);
+        keyStore.load(null);
+        if (keyStore.containsAlias(KEY_ALIAS)) {
+            return (SecretKey) keyStore.getKey(KEY_ALIAS, null);
+        }
+        KeyGenerator generator = KeyGenerator.getInstance(KeyProperties.KEY_ALGORITHM_AES, KEYSTORE);
+        generator.init(new KeyGenParameterSpec.Builder(
+                KEY_ALIAS, KeyProperties.PURPOSE_ENCRYPT | KeyProperties.PURPOSE_DECRYPT)
+                .setKeySize(256)
+                .setBlockModes(KeyProperties.BLOCK_MODE_GCM)
+                .setEncryptionPaddings(KeyProperties.ENCRYPTION_PADDING_NONE)
+                .build());
+        return generator.generateKey();
+    }
+}
@@ -0,0 +1,117 @@
+// src/auto_lock.rs
+//! Locking the app again after inactivity or when it goes to the background
+//!
+//! The webview reports input and visibility changes (see `ACTIVITY_JS`).
+//! Once the idle period passes, or the app is hidden with locking on
+//! background turned on, the vault key is dropped and the PIN screen comes
+//! back. Timers may not run while a phone app is in the background, so the
+//! idle period is checked again when the app becomes visible.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Idle periods offered in settings, in minutes; `None` never locks on idle
+pub const IDLE_OPTIONS: [Option<u32>; 6] = [Some(1), Some(5), Some(15), Some(30), Some(60), None];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AutoLockSettings {
+    /// Minutes without input before locking; `None` never locks on idle
+    pub idle_minutes: Option<u32>,
+    /// Lock as soon as the app is hidden or sent to the background
+    pub lock_on_background: bool,
+}
+
+impl Default for AutoLockSettings {
+    fn default() -> Self {
+        Self { idle_minutes: Some(5), lock_on_background: true }
+    }
+}
+
+impl AutoLockSettings {
+    /// Whether the idle period has passed since the last input
+    pub fn idle_expired(&self, last_activity: u64, now: u64) -> bool {
+        self.idle_minutes
+            .is_some_and(|minutes| now.saturating_sub(last_activity) >= u64::from(minutes) * 60)
+    }
+}
+
+/// "5 min", "1 h", "Never"
+pub fn idle_label(minutes: Option<u32>) -> String {
+    match minutes {
+        None => "Never".to_string(),
+        Some(minutes) if minutes % 60 == 0 => format!("{} h", minutes / 60),
+        Some(minutes) => format!("{} min", minutes),
+    }
+}
+
+/// Unix time of the last input, or of the last unlock
+static LAST_ACTIVITY: AtomicU64 = AtomicU64::new(0);
+
+pub fn record_activity(now: u64) {
+    LAST_ACTIVITY.store(now, Ordering::SeqCst);
+}
+
+pub fn last_activity() -> u64 {
+    LAST_ACTIVITY.load(Ordering::SeqCst)
+}
+
+/// Settings in force, so the idle checks don't read storage, with whether
+/// they belong to the duress profile
+static SETTINGS: Mutex<Option<(bool, AutoLockSettings)>> = Mutex::new(None);
+
+/// Settings for the open profile, loaded from storage once per profile
+pub fn current() -> AutoLockSettings {
+    let duress = crate::pin::is_duress_session();
+    if let Some((cached_duress, settings)) = *SETTINGS.lock().unwrap() {
+        if cached_duress == duress {
+            return settings;
+        }
+    }
+    let settings = crate::storage::load_auto_lock_settings();
+    set_current(settings);
+    settings
+}
+
+/// Use newly saved settings for the open profile
+pub fn set_current(settings: AutoLockSettings) {
+    *SETTINGS.lock().unwrap() = Some((crate::pin::is_duress_session(), settings));
+}
+
+/// Reports "active" on input, at most every few seconds, and "hidden" /
+/// "visible" when the app goes to or comes back from the background
+pub const ACTIVITY_JS: &str = r#"
+    let lastReport = 0;
+    const report = () => {
+        const now = Date.now();
+        if (now - lastReport > 5000) {
+            lastReport = now;
+            dioxus.send('active');
+        }
+    };
+    for (const type of ['pointerdown', 'keydown', 'touchstart', 'wheel']) {
+        document.addEventListener(type, report, { capture: true, passive: true });
+    }
+    document.addEventListener('visibilitychange', () => {
+        dioxus.send(document.visibilityState === 'visible' ? 'visible' : 'hidden');
+    });
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_expired() {
+        let settings = AutoLockSettings::default();
+        assert!(!settings.idle_expired(1_000, 1_000 + 299));
+        assert!(settings.idle_expired(1_000, 1_000 + 300));
+        // A clock that went backwards never locks
+        assert!(!settings.idle_expired(1_000, 900));
+
+        let never = AutoLockSettings { idle_minutes: None, ..settings };
+        assert!(!never.idle_expired(0, u64::MAX));
+        assert_eq!(idle_label(Some(60)), "1 h");
+        assert_eq!(idle_label(Some(15)), "15 min");
+    }
+}
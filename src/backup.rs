@@ -0,0 +1,232 @@
+// src/backup.rs
+//! Encrypted backups
+//!
+//! One file holding the software wallets, the app's settings and the labels
+//! and notes kept on wallets and transactions, sealed under a password with
+//! Argon2id and AES-256-GCM (see `pin`). Keys go into the sealed payload raw,
+//! so a backup restores on a machine with a different PIN or none, where they
+//! are sealed again under the new PIN.
+//!
+//! Wallets whose key isn't on this device (passkey, shared or remote ones)
+//! are left out, and the hardware wallet's address book stays on the device.
+
+use crate::pin::{open_wallet_key, open_with_password, seal_with_password, KdfParams};
+use crate::storage::{
+    load_address_labels, load_dataset_value, load_jito_settings_from_storage, load_rpc_from_storage, load_wallets_from_storage,
+    save_address_labels, save_dataset_value, save_export_file, save_jito_settings_to_storage, save_rpc_to_storage,
+    save_wallets_to_storage, JitoSettings,
+};
+use crate::wallet::{Wallet, WalletInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const BACKUP_FORMAT: &str = "unruggable-backup";
+const BACKUP_VERSION: u32 = 1;
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Stored datasets carried in a backup: settings, wallet notes, and
+/// transaction and address labels
+const BACKUP_DATASETS: &[&str] = &[
+    "network",
+    "rpc_fallbacks",
+    "rpc_headers",
+    "rpc_auto_select",
+    "app_tip",
+    "fee_bump_settings",
+    "fee_sponsor",
+    "slippage",
+    "swap_providers",
+    "mev_protection",
+    "price_providers",
+    "budgets",
+    "wallet_profiles",
+    "transaction_labels",
+    "transaction_notes",
+];
+
+/// Carried alongside `BACKUP_DATASETS` but read and written through its own
+/// accessors, as it isn't a JSON dataset
+const ADDRESS_LABELS: &str = "address_labels";
+
+/// The file as written, with the sealed payload base64-encoded
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupFile {
+    format: String,
+    version: u32,
+    created_at: i64,
+    sealed: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BackupContents {
+    /// Wallets with their raw base58 keys
+    pub wallets: Vec<WalletInfo>,
+    #[serde(default)]
+    pub custom_rpc: Option<String>,
+    #[serde(default)]
+    pub jito_settings: Option<JitoSettings>,
+    #[serde(default)]
+    pub datasets: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestoreSummary {
+    pub wallets_added: usize,
+    /// Wallets already on this device
+    pub wallets_skipped: usize,
+    pub settings_restored: usize,
+}
+
+/// Gather what goes into a backup. Fails if any wallet key is locked.
+pub fn collect() -> Result<BackupContents, String> {
+    let mut wallets = Vec::new();
+    for wallet in load_wallets_from_storage() {
+        if wallet.encrypted_key.is_empty() {
+            continue;
+        }
+        let encrypted_key = open_wallet_key(&wallet.encrypted_key)?;
+        wallets.push(WalletInfo { encrypted_key, ..wallet });
+    }
+    let mut datasets: BTreeMap<String, serde_json::Value> = BACKUP_DATASETS
+        .iter()
+        .filter_map(|key| load_dataset_value(key).map(|value| (key.to_string(), value)))
+        .collect();
+    // Address names live in the database on desktop and mobile
+    let address_labels = load_address_labels();
+    if !address_labels.is_empty() {
+        let value = serde_json::to_value(&address_labels).map_err(|e| format!("Failed to serialize backup: {}", e))?;
+        datasets.insert(ADDRESS_LABELS.to_string(), value);
+    }
+    Ok(BackupContents {
+        wallets,
+        custom_rpc: load_rpc_from_storage(),
+        jito_settings: Some(load_jito_settings_from_storage()),
+        datasets,
+    })
+}
+
+/// Seal a backup under a password into the text of the backup file
+pub fn seal(contents: &BackupContents, password: &str) -> Result<String, String> {
+    seal_with_params(contents, password, KdfParams::DEFAULT)
+}
+
+fn seal_with_params(contents: &BackupContents, password: &str, params: KdfParams) -> Result<String, String> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(format!("Use a password of at least {} characters", MIN_PASSWORD_LENGTH));
+    }
+    let payload = serde_json::to_vec(contents).map_err(|e| format!("Failed to serialize backup: {}", e))?;
+    let file = BackupFile {
+        format: BACKUP_FORMAT.to_string(),
+        version: BACKUP_VERSION,
+        created_at: chrono::Utc::now().timestamp(),
+        sealed: base64::encode(seal_with_password(&payload, password, params)?),
+    };
+    serde_json::to_string_pretty(&file).map_err(|e| format!("Failed to serialize backup: {}", e))
+}
+
+/// Open the text of a backup file
+pub fn open(text: &str, password: &str) -> Result<BackupContents, String> {
+    let file: BackupFile =
+        serde_json::from_str(text.trim()).map_err(|_| "This is not an Unruggable backup file".to_string())?;
+    if file.format != BACKUP_FORMAT {
+        return Err("This is not an Unruggable backup file".to_string());
+    }
+    if file.version > BACKUP_VERSION {
+        return Err(format!(
+            "This backup was made by a newer version of the app (format {}), update to restore it",
+            file.version
+        ));
+    }
+    let sealed = base64::decode(&file.sealed).map_err(|e| format!("Corrupted backup: {}", e))?;
+    let payload = open_with_password(&sealed, password)?;
+    let contents: BackupContents =
+        serde_json::from_slice(&payload).map_err(|e| format!("Corrupted backup: {}", e))?;
+    for wallet in &contents.wallets {
+        let restored = Wallet::from_wallet_info(wallet)
+            .map_err(|e| format!("Corrupted key for {} in backup: {}", wallet.name, e))?;
+        if restored.get_public_key() != wallet.address {
+            return Err(format!("Key for {} in backup doesn't match its address", wallet.name));
+        }
+    }
+    Ok(contents)
+}
+
+/// Write the backup's wallets and settings to this device. Wallets already
+/// here are kept as they are.
+pub fn restore(contents: &BackupContents) -> Result<RestoreSummary, String> {
+    let mut wallets = load_wallets_from_storage();
+    let mut wallets_added = 0;
+    for wallet in &contents.wallets {
+        if wallets.iter().any(|w| w.address == wallet.address) {
+            continue;
+        }
+        wallets.push(wallet.clone());
+        wallets_added += 1;
+    }
+    if wallets_added > 0 {
+        save_wallets_to_storage(&wallets);
+    }
+
+    let mut settings_restored = 0;
+    for (key, value) in contents.datasets.iter().filter(|(key, _)| BACKUP_DATASETS.contains(&key.as_str())) {
+        save_dataset_value(key, value)?;
+        settings_restored += 1;
+    }
+    if let Some(value) = contents.datasets.get(ADDRESS_LABELS) {
+        let labels = serde_json::from_value(value.clone()).map_err(|e| format!("Corrupted backup: {}", e))?;
+        save_address_labels(&labels)?;
+        settings_restored += 1;
+    }
+    if let Some(rpc) = &contents.custom_rpc {
+        save_rpc_to_storage(rpc);
+        settings_restored += 1;
+    }
+    if let Some(jito_settings) = &contents.jito_settings {
+        save_jito_settings_to_storage(jito_settings);
+        settings_restored += 1;
+    }
+
+    log::info!("✅ Restored backup: {} wallet(s) added, {} setting(s)", wallets_added, settings_restored);
+    Ok(RestoreSummary {
+        wallets_added,
+        wallets_skipped: contents.wallets.len() - wallets_added,
+        settings_restored,
+    })
+}
+
+/// Collect, seal and write a backup to the exports folder, returning its path
+pub fn export_backup(password: &str) -> Result<String, String> {
+    let text = seal(&collect()?, password)?;
+    let file_name = format!("unruggable_backup_{}.json", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+    save_export_file(&file_name, &text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pin::TEST_PARAMS;
+
+    #[test]
+    fn test_backup_round_trip() {
+        let wallet = Wallet::new("Main".to_string()).to_wallet_info();
+        let mut datasets = BTreeMap::new();
+        datasets.insert("slippage".to_string(), serde_json::json!({ "bps": 50 }));
+        let contents = BackupContents {
+            wallets: vec![wallet.clone()],
+            custom_rpc: Some("https://rpc.example.com".to_string()),
+            jito_settings: None,
+            datasets,
+        };
+
+        assert!(seal_with_params(&contents, "short", TEST_PARAMS).is_err());
+        let text = seal_with_params(&contents, "correct horse", TEST_PARAMS).unwrap();
+        assert!(!text.contains(&wallet.encrypted_key));
+        assert_eq!(open(&text, "correct horse").unwrap(), contents);
+        assert!(open(&text, "battery staple").is_err());
+        assert!(open("{}", "correct horse").is_err());
+
+        let mut newer: serde_json::Value = serde_json::from_str(&text).unwrap();
+        newer["version"] = serde_json::json!(BACKUP_VERSION + 1);
+        assert!(open(&newer.to_string(), "correct horse").unwrap_err().contains("newer version"));
+    }
+}
@@ -0,0 +1,487 @@
+// src/balance_history.rs
+//! Balances as of a past date, for tax and accounting
+//!
+//! The RPC can't be asked for a balance at an old date, so it is rebuilt from
+//! history: starting from today's balances, the SOL and token changes of
+//! every transaction after the chosen date are undone using the pre/post
+//! balances the RPC records for each transaction. Incoming token transfers
+//! often don't mention the wallet itself, so each current token account's
+//! history is walked as well.
+//!
+//! When there is too much activity since the date to replay, the post
+//! balances of the last transaction at or before the date are used as a
+//! snapshot instead. That snapshot only covers the tokens the transaction
+//! moved.
+
+use crate::prices::{get_historical_close, get_token_metadata};
+use crate::rpc::{self, TransactionHistoryItem};
+use crate::storage::{load_historical_price, save_historical_price};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+const SIGNATURE_PAGE_SIZE: usize = 1000;
+const MAX_SIGNATURE_PAGES: usize = 20;
+/// Transactions are fetched one by one to replay; past this the snapshot is used
+const MAX_REPLAY_TRANSACTIONS: usize = 300;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenBalance {
+    pub mint: String,
+    /// Base units
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+impl TokenBalance {
+    pub fn ui_amount(&self) -> f64 {
+        self.amount as f64 / 10_f64.powi(self.decimals as i32)
+    }
+}
+
+/// What one transaction did to a wallet's balances
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BalanceChange {
+    pub signature: String,
+    pub block_time: Option<i64>,
+    /// Lamports, including the fee when the wallet paid it
+    pub sol_delta: i128,
+    /// The wallet's lamports after the transaction, if it was an account of it
+    pub post_sol: Option<u64>,
+    /// Per mint: change in base units and decimals
+    pub token_deltas: Vec<(String, i128, u8)>,
+    /// Token balances after the transaction, for the mints it touched
+    pub post_tokens: Vec<TokenBalance>,
+}
+
+fn account_key(entry: &Value) -> Option<&str> {
+    // jsonParsed keys are objects; plain encodings are strings
+    entry.get("pubkey").and_then(|k| k.as_str()).or_else(|| entry.as_str())
+}
+
+fn raw_token_amount(entry: &Value) -> Option<(usize, String, u64, u8)> {
+    let index = entry.get("accountIndex")?.as_u64()? as usize;
+    let mint = entry.get("mint")?.as_str()?.to_string();
+    let ui = entry.get("uiTokenAmount")?;
+    let amount = ui.get("amount")?.as_str()?.parse().ok()?;
+    let decimals = ui.get("decimals")?.as_u64()? as u8;
+    Some((index, mint, amount, decimals))
+}
+
+/// Read `wallet`'s balance changes from a jsonParsed transaction's message and meta
+pub fn parse_balance_change(
+    wallet: &str,
+    signature: &str,
+    block_time: Option<i64>,
+    message: &Value,
+    meta: &Value,
+) -> Option<BalanceChange> {
+    let keys = message.get("accountKeys")?.as_array()?;
+    let mut change = BalanceChange {
+        signature: signature.to_string(),
+        block_time,
+        ..Default::default()
+    };
+
+    if let Some(index) = keys.iter().position(|k| account_key(k) == Some(wallet)) {
+        let balance = |field: &str| meta.get(field)?.as_array()?.get(index)?.as_u64();
+        if let (Some(pre), Some(post)) = (balance("preBalances"), balance("postBalances")) {
+            change.sol_delta = post as i128 - pre as i128;
+            change.post_sol = Some(post);
+        }
+    }
+
+    // Token accounts owned by the wallet, by account index: (mint, decimals, pre, post)
+    let mut accounts: HashMap<usize, (String, u8, u64, u64)> = HashMap::new();
+    for (field, is_post) in [("preTokenBalances", false), ("postTokenBalances", true)] {
+        let entries = meta.get(field).and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        for entry in entries.iter().filter(|e| e.get("owner").and_then(|o| o.as_str()) == Some(wallet)) {
+            let Some((index, mint, amount, decimals)) = raw_token_amount(entry) else { continue };
+            let slot = accounts.entry(index).or_insert((mint, decimals, 0, 0));
+            if is_post {
+                slot.3 = amount;
+            } else {
+                slot.2 = amount;
+            }
+        }
+    }
+
+    let mut by_mint: HashMap<String, (i128, u64, u8)> = HashMap::new();
+    for (mint, decimals, pre, post) in accounts.into_values() {
+        let totals = by_mint.entry(mint).or_insert((0, 0, decimals));
+        totals.0 += post as i128 - pre as i128;
+        totals.1 += post;
+    }
+    let mut mints: Vec<_> = by_mint.into_iter().collect();
+    mints.sort_by(|a, b| a.0.cmp(&b.0));
+    for (mint, (delta, post, decimals)) in mints {
+        if delta != 0 {
+            change.token_deltas.push((mint.clone(), delta, decimals));
+        }
+        change.post_tokens.push(TokenBalance { mint, amount: post, decimals });
+    }
+
+    Some(change)
+}
+
+/// Undo `changes` (every transaction after the target date) from the current balances
+pub fn replay(current_sol: u64, current_tokens: &[TokenBalance], changes: &[BalanceChange]) -> (u64, Vec<TokenBalance>) {
+    let sol = changes.iter().fold(current_sol as i128, |sol, c| sol - c.sol_delta);
+
+    let mut tokens: HashMap<String, (i128, u8)> = HashMap::new();
+    for token in current_tokens {
+        let entry = tokens.entry(token.mint.clone()).or_insert((0, token.decimals));
+        entry.0 += token.amount as i128;
+    }
+    for change in changes {
+        for (mint, delta, decimals) in &change.token_deltas {
+            let entry = tokens.entry(mint.clone()).or_insert((0, *decimals));
+            entry.0 -= delta;
+        }
+    }
+
+    let mut tokens: Vec<TokenBalance> = tokens
+        .into_iter()
+        .filter(|(_, (amount, _))| *amount > 0)
+        .map(|(mint, (amount, decimals))| TokenBalance { mint, amount: amount.min(u64::MAX as i128) as u64, decimals })
+        .collect();
+    tokens.sort_by(|a, b| a.mint.cmp(&b.mint));
+    (sol.max(0) as u64, tokens)
+}
+
+/// Balances right after the last transaction at or before the target date
+pub fn snapshot(change: &BalanceChange) -> (u64, Vec<TokenBalance>) {
+    let tokens = change.post_tokens.iter().filter(|t| t.amount > 0).cloned().collect();
+    (change.post_sol.unwrap_or(0), tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BalanceSource {
+    /// Rebuilt by undoing this many later transactions
+    Replayed { transactions: usize },
+    /// Taken from the balances recorded after this transaction
+    Snapshot { signature: String, block_time: Option<i64> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoricalBalances {
+    pub wallet: String,
+    /// Unix timestamp the balances are for
+    pub as_of: i64,
+    pub sol_lamports: u64,
+    pub tokens: Vec<TokenBalance>,
+    pub source: BalanceSource,
+}
+
+impl HistoricalBalances {
+    pub fn source_note(&self) -> String {
+        match &self.source {
+            BalanceSource::Replayed { transactions } => {
+                format!("Rebuilt from today's balances by undoing {} later transaction(s).", transactions)
+            }
+            BalanceSource::Snapshot { signature, block_time } => format!(
+                "Too much activity since this date to replay. Taken from the balances after transaction {} ({}); tokens that transaction didn't move are not shown.",
+                signature,
+                block_time.map(rpc::format_timestamp).unwrap_or_else(|| "unknown time".to_string())
+            ),
+        }
+    }
+}
+
+/// A cached USD daily close
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoricalPrice {
+    pub symbol: String,
+    /// "YYYY-MM-DD" (UTC)
+    pub day: String,
+    pub usd: f64,
+}
+
+fn day_key(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// USD close of `symbol` on the day of `timestamp`, from the local cache when possible
+pub async fn historical_price(symbol: &str, timestamp: i64) -> Option<f64> {
+    let day = day_key(timestamp);
+    if let Some(usd) = load_historical_price(symbol, &day) {
+        return Some(usd);
+    }
+    match get_historical_close(symbol, timestamp).await {
+        Ok(Some(usd)) => {
+            let price = HistoricalPrice { symbol: symbol.to_string(), day, usd };
+            if let Err(e) = save_historical_price(&price) {
+                println!("⚠️ Failed to cache historical price: {}", e);
+            }
+            Some(usd)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            println!("⚠️ No historical price for {} on {}: {}", symbol, day, e);
+            None
+        }
+    }
+}
+
+/// One line of a valued balance sheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValuedBalance {
+    pub label: String,
+    pub amount: f64,
+    pub usd_price: Option<f64>,
+}
+
+impl ValuedBalance {
+    pub fn usd_value(&self) -> Option<f64> {
+        self.usd_price.map(|p| p * self.amount)
+    }
+}
+
+/// Price every balance at its close on the target date
+pub async fn value_balances(balances: &HistoricalBalances) -> Vec<ValuedBalance> {
+    let sol = balances.sol_lamports as f64 / 1_000_000_000.0;
+    let mut lines = vec![ValuedBalance {
+        label: "SOL".to_string(),
+        amount: sol,
+        usd_price: historical_price("SOL", balances.as_of).await,
+    }];
+
+    let mints: Vec<String> = balances.tokens.iter().map(|t| t.mint.clone()).collect();
+    let metadata = get_token_metadata(mints).await.unwrap_or_else(|e| {
+        println!("⚠️ Token metadata unavailable: {}", e);
+        HashMap::new()
+    });
+    for token in &balances.tokens {
+        let symbol = metadata.get(&token.mint).map(|m| m.symbol.clone());
+        let usd_price = match &symbol {
+            Some(symbol) => historical_price(symbol, balances.as_of).await,
+            None => None,
+        };
+        lines.push(ValuedBalance {
+            label: symbol.unwrap_or_else(|| format!("{}...", &token.mint[..token.mint.len().min(8)])),
+            amount: token.ui_amount(),
+            usd_price,
+        });
+    }
+    lines
+}
+
+/// Signatures newer than `as_of`, the newest one at or before it, and
+/// whether the walk reached `as_of` (or the start of the history)
+async fn signatures_since(
+    address: &str,
+    as_of: i64,
+    rpc_url: Option<&str>,
+) -> Result<(Vec<TransactionHistoryItem>, Option<TransactionHistoryItem>, bool), String> {
+    let mut newer = Vec::new();
+    let mut before: Option<String> = None;
+    for _ in 0..MAX_SIGNATURE_PAGES {
+        let page = rpc::get_signatures_for_address(address, before.as_deref(), SIGNATURE_PAGE_SIZE, rpc_url).await?;
+        let exhausted = page.len() < SIGNATURE_PAGE_SIZE;
+        before = page.last().map(|item| item.signature.clone());
+        for item in page {
+            if item.block_time.map_or(false, |t| t <= as_of) {
+                return Ok((newer, Some(item), true));
+            }
+            newer.push(item);
+        }
+        if exhausted || before.is_none() {
+            return Ok((newer, None, true));
+        }
+    }
+    Ok((newer, None, false))
+}
+
+async fn fetch_change(wallet: &str, item: &TransactionHistoryItem, rpc_url: Option<&str>) -> Result<Option<BalanceChange>, String> {
+    let details = rpc::get_transaction_details(&item.signature, rpc_url).await?;
+    let (Some(message), Some(meta)) = (details.get("message"), details.get("meta")) else {
+        return Ok(None);
+    };
+    Ok(parse_balance_change(wallet, &item.signature, item.block_time, message, meta))
+}
+
+async fn current_token_balances(wallet: &str, rpc_url: Option<&str>) -> Result<Vec<(String, TokenBalance)>, String> {
+    let accounts = rpc::get_all_token_accounts(wallet, rpc_url).await?;
+    Ok(accounts
+        .into_iter()
+        .map(|account| {
+            (
+                account.pubkey,
+                TokenBalance {
+                    mint: account.mint,
+                    amount: (account.amount * 10_f64.powi(account.decimals as i32)).round() as u64,
+                    decimals: account.decimals,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Signatures after `since` of the wallet and its token accounts (see
+/// `signatures_since`). Stops walking once there are more than can be replayed.
+async fn signatures_touching(
+    wallet: &str,
+    since: i64,
+    current_tokens: &[(String, TokenBalance)],
+    rpc_url: Option<&str>,
+) -> Result<(Vec<TransactionHistoryItem>, Option<TransactionHistoryItem>, bool), String> {
+    let (mut newer, at_or_before, mut complete) = signatures_since(wallet, since, rpc_url).await?;
+    let mut seen: HashSet<String> = newer.iter().map(|item| item.signature.clone()).collect();
+    for (token_account, _) in current_tokens {
+        if newer.len() > MAX_REPLAY_TRANSACTIONS {
+            break;
+        }
+        let (account_newer, _, account_complete) = signatures_since(token_account, since, rpc_url).await?;
+        complete &= account_complete;
+        newer.extend(account_newer.into_iter().filter(|item| seen.insert(item.signature.clone())));
+    }
+    Ok((newer, at_or_before, complete))
+}
+
+/// Every balance change of `wallet` between `from` (exclusive) and `to`
+/// (inclusive). The flag is false when there was more activity than could
+/// be fetched and only the newest changes are included.
+pub async fn balance_changes_between(
+    wallet: &str,
+    from: i64,
+    to: i64,
+    rpc_url: Option<&str>,
+) -> Result<(Vec<BalanceChange>, bool), String> {
+    let current_tokens = current_token_balances(wallet, rpc_url).await?;
+    let (mut items, _, mut complete) = signatures_touching(wallet, from, &current_tokens, rpc_url).await?;
+    items.retain(|item| item.block_time.map_or(false, |t| t <= to));
+    items.sort_by_key(|item| std::cmp::Reverse(item.block_time));
+    if items.len() > MAX_REPLAY_TRANSACTIONS {
+        items.truncate(MAX_REPLAY_TRANSACTIONS);
+        complete = false;
+    }
+
+    let mut changes = Vec::new();
+    for item in &items {
+        if let Some(change) = fetch_change(wallet, item, rpc_url).await? {
+            changes.push(change);
+        }
+    }
+    Ok((changes, complete))
+}
+
+/// Rebuild `wallet`'s SOL and token balances as they were at `as_of`
+pub async fn reconstruct_balances(wallet: &str, as_of: i64, rpc_url: Option<&str>) -> Result<HistoricalBalances, String> {
+    let current_sol = (rpc::get_balance(wallet, rpc_url).await? * 1_000_000_000.0).round() as u64;
+    let current_tokens = current_token_balances(wallet, rpc_url).await?;
+
+    let (newer, at_or_before, complete) = signatures_touching(wallet, as_of, &current_tokens, rpc_url).await?;
+
+    if complete && newer.len() <= MAX_REPLAY_TRANSACTIONS {
+        println!("🕰️ Replaying {} transaction(s) for {}", newer.len(), wallet);
+        let mut changes = Vec::new();
+        for item in &newer {
+            if let Some(change) = fetch_change(wallet, item, rpc_url).await? {
+                changes.push(change);
+            }
+        }
+        let tokens: Vec<TokenBalance> = current_tokens.into_iter().map(|(_, t)| t).collect();
+        let (sol_lamports, tokens) = replay(current_sol, &tokens, &changes);
+        return Ok(HistoricalBalances {
+            wallet: wallet.to_string(),
+            as_of,
+            sol_lamports,
+            tokens,
+            source: BalanceSource::Replayed { transactions: newer.len() },
+        });
+    }
+
+    let item = at_or_before.ok_or("Too much activity since this date to reconstruct balances")?;
+    println!("🕰️ Using the balances after {} for {}", item.signature, wallet);
+    let change = fetch_change(wallet, &item, rpc_url)
+        .await?
+        .ok_or("Failed to read balances from the snapshot transaction")?;
+    let (sol_lamports, tokens) = snapshot(&change);
+    Ok(HistoricalBalances {
+        wallet: wallet.to_string(),
+        as_of,
+        sol_lamports,
+        tokens,
+        source: BalanceSource::Snapshot { signature: item.signature, block_time: item.block_time },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const WALLET: &str = "Wa11et1111111111111111111111111111111111111";
+    const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn token_entry(index: u64, owner: &str, amount: &str) -> Value {
+        json!({
+            "accountIndex": index,
+            "mint": MINT,
+            "owner": owner,
+            "uiTokenAmount": { "amount": amount, "decimals": 6 }
+        })
+    }
+
+    #[test]
+    fn test_parse_balance_change() {
+        let message = json!({ "accountKeys": [
+            { "pubkey": "Payer111111111111111111111111111111111111111" },
+            { "pubkey": WALLET },
+            { "pubkey": "Ata1111111111111111111111111111111111111111" },
+        ]});
+        let meta = json!({
+            "preBalances": [10_000, 5_000_000, 2_039_280],
+            "postBalances": [5_000, 4_000_000, 2_039_280],
+            "preTokenBalances": [token_entry(2, WALLET, "1000000"), token_entry(3, "Other", "5")],
+            "postTokenBalances": [token_entry(2, WALLET, "3500000"), token_entry(3, "Other", "0")],
+        });
+
+        let change = parse_balance_change(WALLET, "sig", Some(100), &message, &meta).unwrap();
+        assert_eq!(change.sol_delta, -1_000_000);
+        assert_eq!(change.post_sol, Some(4_000_000));
+        assert_eq!(change.token_deltas, vec![(MINT.to_string(), 2_500_000, 6)]);
+        assert_eq!(change.post_tokens, vec![TokenBalance { mint: MINT.to_string(), amount: 3_500_000, decimals: 6 }]);
+    }
+
+    #[test]
+    fn test_closed_token_account_counts_as_zero() {
+        let message = json!({ "accountKeys": [WALLET, "Ata1111111111111111111111111111111111111111"] });
+        let meta = json!({
+            "preBalances": [1_000, 2_039_280],
+            "postBalances": [2_040_280, 0],
+            "preTokenBalances": [token_entry(1, WALLET, "42")],
+            "postTokenBalances": [],
+        });
+
+        let change = parse_balance_change(WALLET, "sig", None, &message, &meta).unwrap();
+        assert_eq!(change.sol_delta, 2_039_280);
+        assert_eq!(change.token_deltas, vec![(MINT.to_string(), -42, 6)]);
+        assert!(snapshot(&change).1.is_empty());
+    }
+
+    #[test]
+    fn test_replay_undoes_later_changes() {
+        let current = vec![TokenBalance { mint: MINT.to_string(), amount: 3_000_000, decimals: 6 }];
+        let changes = vec![
+            BalanceChange { sol_delta: 2_000_000_000, token_deltas: vec![(MINT.to_string(), 3_000_000, 6)], ..Default::default() },
+            BalanceChange {
+                sol_delta: -5_000,
+                token_deltas: vec![("Gone111111111111111111111111111111111111111".to_string(), -7, 0)],
+                ..Default::default()
+            },
+        ];
+
+        let (sol, tokens) = replay(3_000_000_000, &current, &changes);
+        assert_eq!(sol, 1_000_005_000);
+        // The USDC arrived later; the closed token existed back then
+        assert_eq!(tokens, vec![TokenBalance { mint: "Gone111111111111111111111111111111111111111".to_string(), amount: 7, decimals: 0 }]);
+    }
+
+    #[test]
+    fn test_day_key_is_utc() {
+        assert_eq!(day_key(1_700_000_000), "2023-11-14");
+    }
+}
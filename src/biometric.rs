@@ -0,0 +1,114 @@
+// src/biometric.rs
+//! Biometric unlock in place of the PIN
+//!
+//! The session's vault key (see `pin`) is encrypted under a secret that only
+//! the platform authenticator releases after Face ID / Touch ID, Android
+//! BiometricPrompt or Windows Hello. Unlocking asks the authenticator for the
+//! secret, decrypts the vault key and opens the session with it, so the PIN
+//! itself is never stored. Setting or removing the PIN drops the wrapped key.
+
+use crate::pin::{decrypt_with_key, encrypt_with_key, generate_salt, vault_key, VaultKey};
+use crate::signing::passkey::{platform_authenticator, PasskeyAuthenticator};
+use crate::storage::{load_biometric_unlock, save_biometric_unlock, unlock_with_vault_key};
+use serde::{Deserialize, Serialize};
+
+/// Vault key wrapped under a platform authenticator secret, safe to persist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BiometricUnlock {
+    pub credential_id: String,
+    /// Base64 salt fed to the authenticator secret derivation
+    pub salt: String,
+    /// Base64 nonce + AES-256-GCM ciphertext of the vault key
+    pub wrapped_key: String,
+}
+
+/// Name of the platform authenticator, e.g. "Android Biometric", if there is one
+pub async fn authenticator_name() -> Option<String> {
+    let authenticator = platform_authenticator()?;
+    authenticator.is_available().await.then(|| authenticator.get_name())
+}
+
+pub fn is_enabled() -> bool {
+    load_biometric_unlock().is_some()
+}
+
+/// Wrap the session's vault key under a new platform credential. Needs the
+/// session unlocked with the PIN.
+pub async fn enable() -> Result<(), String> {
+    let authenticator = platform_authenticator().ok_or("Biometric unlock is not supported on this device")?;
+    let key = vault_key().ok_or("Enter your PIN before turning on biometric unlock")?;
+    let unlock = wrap_vault_key(&key, authenticator.as_ref()).await?;
+
+    // Check the authenticator releases the key before relying on it
+    unwrap_vault_key(&unlock, authenticator.as_ref()).await?;
+    save_biometric_unlock(Some(&unlock))
+}
+
+pub fn disable() -> Result<(), String> {
+    save_biometric_unlock(None)
+}
+
+/// Prompt for biometrics and unlock the session with the released vault key
+pub async fn unlock() -> Result<(), String> {
+    let unlock = load_biometric_unlock().ok_or("Biometric unlock is not turned on")?;
+    let authenticator = platform_authenticator().ok_or("Biometric unlock is not supported on this device")?;
+    unlock_with_vault_key(unwrap_vault_key(&unlock, authenticator.as_ref()).await?)
+}
+
+async fn wrap_vault_key(key: &VaultKey, authenticator: &dyn PasskeyAuthenticator) -> Result<BiometricUnlock, String> {
+    if !authenticator.is_available().await {
+        return Err(format!("{} is not available", authenticator.get_name()));
+    }
+    let credential_id = authenticator
+        .create_credential("Unruggable unlock")
+        .await
+        .map_err(|e| e.to_string())?;
+    let salt = generate_salt();
+    let mut secret = authenticator
+        .derive_secret(&credential_id, &salt)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut bytes = key.to_bytes();
+    let wrapped = encrypt_with_key(&bytes, &secret);
+    bytes.fill(0);
+    secret.fill(0);
+
+    Ok(BiometricUnlock {
+        credential_id,
+        salt: base64::encode(salt),
+        wrapped_key: base64::encode(wrapped?),
+    })
+}
+
+async fn unwrap_vault_key(unlock: &BiometricUnlock, authenticator: &dyn PasskeyAuthenticator) -> Result<VaultKey, String> {
+    let salt = base64::decode(&unlock.salt).map_err(|e| format!("Invalid biometric unlock: {}", e))?;
+    let wrapped = base64::decode(&unlock.wrapped_key).map_err(|e| format!("Invalid biometric unlock: {}", e))?;
+    let mut secret = authenticator
+        .derive_secret(&unlock.credential_id, &salt)
+        .await
+        .map_err(|e| e.to_string())?;
+    let decrypted = decrypt_with_key(&wrapped, &secret);
+    secret.fill(0);
+
+    let mut bytes = decrypted.map_err(|_| "Biometrics did not release the vault key - enter your PIN".to_string())?;
+    let key = VaultKey::from_bytes(&bytes);
+    bytes.fill(0);
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pin::TEST_PARAMS;
+    use crate::signing::passkey::FakeAuthenticator;
+
+    #[tokio::test]
+    async fn test_wrap_vault_key() {
+        let key = VaultKey::derive("123456", &generate_salt(), TEST_PARAMS).unwrap();
+        let unlock = wrap_vault_key(&key, &FakeAuthenticator("enrolled")).await.unwrap();
+
+        let released = unwrap_vault_key(&unlock, &FakeAuthenticator("enrolled")).await.unwrap();
+        assert_eq!(released.to_bytes(), key.to_bytes());
+        assert!(unwrap_vault_key(&unlock, &FakeAuthenticator("other")).await.is_err());
+    }
+}
@@ -6,7 +6,6 @@ use solana_sdk::{
     signature::Signature as SolanaSignature,
     transaction::VersionedTransaction,
     message::VersionedMessage,
-    system_instruction,
 };
 use std::error::Error as StdError;
 use std::str::FromStr;
@@ -16,6 +15,7 @@ use reqwest::Client as HttpClient;
 use crate::signing::TransactionSigner;
 use crate::bonk_staking::types::StakeResult;
 use crate::storage::get_current_jito_settings;
+use crate::transaction::TransactionClient;
 
 type Result<T> = std::result::Result<T, Box<dyn StdError>>;
 
@@ -52,13 +52,7 @@ impl BonkStakingClient {
             "params": [ata.to_string()]
         });
 
-        let response = self.http_client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
 
         if let Some(amount_str) = json["result"]["value"]["amount"].as_str() {
             let lamports = amount_str.parse::<u64>()?;
@@ -126,14 +120,13 @@ impl BonkStakingClient {
         );
         instructions.push(stake_ix);
 
-        // Check Jito settings and add tip if enabled
-        let jito_settings = get_current_jito_settings();
-        if jito_settings.jito_tx {
-            let jito_tip_address = Pubkey::from_str("juLesoSmdTcRtzjCzYzRoHrnF8GhVu6KCV7uxq7nJGp")?;
-            let tip_ix = system_instruction::transfer(&user_pubkey, &jito_tip_address, 100_000);
-            instructions.push(tip_ix);
+        // App tip and Jito tip when JitoTx is enabled
+        if get_current_jito_settings().jito_tx {
+            instructions.extend(crate::tips::tip_instructions(&user_pubkey, Some(crate::tips::DEFAULT_JITO_TIP_LAMPORTS))?);
         }
 
+        TransactionClient::new(Some(&self.rpc_url)).size_compute_budget(&mut instructions, &user_pubkey).await;
+
         // Get recent blockhash
         let recent_blockhash = self.get_recent_blockhash().await?;
 
@@ -200,13 +193,7 @@ impl BonkStakingClient {
             ]
         });
 
-        let response = self.http_client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
 
         if let Some(data) = json["result"]["value"]["data"][0].as_str() {
             let decoded = base64::decode(data)?;
@@ -225,13 +212,7 @@ impl BonkStakingClient {
             "params": [{ "commitment": "finalized" }]
         });
 
-        let response = self.http_client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
 
         if let Some(blockhash_str) = json["result"]["value"]["blockhash"].as_str() {
             Ok(solana_sdk::hash::Hash::from_str(blockhash_str)?)
@@ -256,13 +237,7 @@ impl BonkStakingClient {
             ]
         });
 
-        let response = self.http_client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
 
         if let Some(error) = json.get("error") {
             Err(format!("Transaction error: {:?}", error).into())
@@ -0,0 +1,41 @@
+//! Device bridge
+//!
+//! Encrypted peer-to-peer link between two of the user's own devices, e.g.
+//! the desktop app and the phone on the same network. The desktop listens,
+//! the phone connects, and both sides derive the session key from a pairing
+//! code shown on the listening device.
+
+mod protocol;
+mod transport;
+
+pub use protocol::*;
+pub use transport::{generate_pairing_code, BridgeConnection, DEFAULT_BRIDGE_PORT};
+
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use tokio::sync::Mutex;
+
+/// Shared handle to a live bridge connection
+pub type SharedBridge = Arc<Mutex<BridgeConnection>>;
+
+static ACTIVE_BRIDGE: StdMutex<Option<SharedBridge>> = StdMutex::new(None);
+
+/// Make `connection` the bridge used by signers that need the paired device
+pub fn set_active_bridge(connection: BridgeConnection) -> SharedBridge {
+    crate::notifications::notify(crate::notifications::CriticalEvent::NewConnection {
+        peer: connection.peer_name().to_string(),
+    });
+    let shared = Arc::new(Mutex::new(connection));
+    *ACTIVE_BRIDGE.lock().unwrap() = Some(shared.clone());
+    shared
+}
+
+/// The currently paired device, if any
+pub fn active_bridge() -> Option<SharedBridge> {
+    ACTIVE_BRIDGE.lock().unwrap().clone()
+}
+
+/// Drop the active connection
+pub fn clear_active_bridge() {
+    *ACTIVE_BRIDGE.lock().unwrap() = None;
+}
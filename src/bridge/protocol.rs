@@ -0,0 +1,95 @@
+// src/bridge/protocol.rs
+//! Messages exchanged between paired devices
+//!
+//! Binary values (points, scalars, messages) are base64 encoded so the
+//! messages stay plain JSON.
+
+use serde::{Deserialize, Serialize};
+
+/// A message sent over the bridge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BridgeMessage {
+    /// First message in each direction after the key exchange
+    Hello { device_name: String },
+
+    /// Commitment to a public key share during shared wallet creation
+    MpcKeygenCommit { commitment: String },
+    /// Public key share, revealed after the peer's commitment was received
+    MpcKeygenReveal { public_share: String },
+
+    /// Ask the peer to co-sign `message` for the shared wallet
+    MpcSignRequest {
+        session_id: String,
+        group_address: String,
+        /// Base64 serialized transaction message
+        message: String,
+        /// Commitment to the requester's nonce point
+        nonce_commitment: String,
+    },
+    /// Nonce point for a signing session
+    MpcNonce { session_id: String, nonce_point: String },
+    /// The co-signer's half of the signature
+    MpcPartialSignature { session_id: String, partial: String },
+
+    /// Ask the peer which of its wallets it can sign with
+    ListWallets,
+    /// Wallets the peer offers for remote signing
+    Wallets { wallets: Vec<RemoteWallet> },
+    /// Ask the peer to sign a transaction message with one of its own wallets
+    SignRequest {
+        request_id: String,
+        signer_address: String,
+        /// Base64 serialized transaction message
+        message: String,
+    },
+    /// Signature produced on the peer after the user approved
+    /// Ask the peer to sign text as an off-chain message (never a transaction)
+    SignMessageRequest {
+        request_id: String,
+        signer_address: String,
+        message: String,
+    },
+    SignResponse { request_id: String, signature: String },
+
+    /// Refuse a request
+    Reject { session_id: Option<String>, reason: String },
+}
+
+/// A wallet held on the paired device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteWallet {
+    pub name: String,
+    pub address: String,
+    /// Name of the device that holds the key
+    #[serde(default)]
+    pub device: String,
+}
+
+/// Encrypted frame payload, numbered to reject replayed frames
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Envelope {
+    pub seq: u64,
+    /// Sender role, so a frame cannot be reflected back to its author
+    pub from_listener: bool,
+    pub message: BridgeMessage,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let message = BridgeMessage::MpcNonce {
+            session_id: "abc".to_string(),
+            nonce_point: "AAAA".to_string(),
+        };
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.contains("\"type\":\"mpc_nonce\""));
+        assert_eq!(serde_json::from_str::<BridgeMessage>(&json).unwrap(), message);
+
+        let json = serde_json::to_string(&BridgeMessage::ListWallets).unwrap();
+        assert_eq!(json, "{\"type\":\"list_wallets\"}");
+    }
+}
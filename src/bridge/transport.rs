@@ -0,0 +1,235 @@
+// src/bridge/transport.rs
+//! WebSocket transport for the device bridge
+//!
+//! The listening device sends a random salt in the clear, then both ends
+//! derive an AES-256-GCM key from the pairing code. Every later frame is an
+//! encrypted, sequence-numbered `Envelope`, so a peer without the pairing code
+//! cannot read, inject or replay messages.
+
+use super::protocol::{BridgeMessage, Envelope};
+use crate::pin::{decrypt_with_key, derive_key_from_pin, encrypt_with_key, generate_salt};
+use futures_util::{SinkExt, StreamExt};
+use rand::Rng;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{accept_async, connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+/// Port the desktop app listens on for the phone
+pub const DEFAULT_BRIDGE_PORT: u16 = 47_820;
+
+/// Unambiguous characters used for pairing codes (no 0/O, 1/I)
+const PAIRING_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const PAIRING_CODE_LEN: usize = 12;
+
+/// Random pairing code shown on the listening device, e.g. `K7QF-2MZX-9PLA`
+pub fn generate_pairing_code() -> String {
+    let mut rng = rand::thread_rng();
+    let chars: Vec<char> = (0..PAIRING_CODE_LEN)
+        .map(|_| PAIRING_ALPHABET[rng.gen_range(0..PAIRING_ALPHABET.len())] as char)
+        .collect();
+    chars
+        .chunks(4)
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Uppercase the code and drop separators so typing variations still match
+fn normalize_pairing_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// An authenticated, encrypted connection to the paired device
+pub struct BridgeConnection {
+    ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    key: [u8; 32],
+    is_listener: bool,
+    send_seq: u64,
+    recv_seq: u64,
+    peer_name: String,
+}
+
+impl BridgeConnection {
+    /// Wait for the other device to connect on `port`
+    pub async fn listen(port: u16, pairing_code: &str, device_name: &str) -> Result<Self, String> {
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .await
+            .map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+        println!("🔗 Bridge listening on port {}", port);
+
+        let (stream, peer) = listener
+            .accept()
+            .await
+            .map_err(|e| format!("Failed to accept connection: {}", e))?;
+        println!("🔗 Bridge connection from {}", peer);
+
+        let mut ws = accept_async(MaybeTlsStream::Plain(stream))
+            .await
+            .map_err(|e| format!("WebSocket handshake failed: {}", e))?;
+
+        let salt = generate_salt();
+        ws.send(Message::Text(base64::encode(salt)))
+            .await
+            .map_err(|e| format!("Failed to send salt: {}", e))?;
+
+        let key = derive_key_from_pin(&normalize_pairing_code(pairing_code), &salt);
+        let mut connection = Self::new(ws, key, true);
+
+        // The phone speaks first; a wrong pairing code fails to decrypt here
+        let peer_name = match connection.recv().await? {
+            BridgeMessage::Hello { device_name } => device_name,
+            other => return Err(format!("Unexpected first message: {:?}", other)),
+        };
+        connection.send(&BridgeMessage::Hello { device_name: device_name.to_string() }).await?;
+        connection.peer_name = peer_name;
+
+        println!("✅ Bridge paired with {}", connection.peer_name);
+        Ok(connection)
+    }
+
+    /// Connect to a listening device at `address` (`host:port`)
+    pub async fn connect(address: &str, pairing_code: &str, device_name: &str) -> Result<Self, String> {
+        let url = if address.contains(':') {
+            format!("ws://{}", address)
+        } else {
+            format!("ws://{}:{}", address, DEFAULT_BRIDGE_PORT)
+        };
+        println!("🔗 Connecting to bridge at {}", url);
+
+        let (mut ws, _) = connect_async(&url)
+            .await
+            .map_err(|e| format!("Failed to connect to {}: {}", url, e))?;
+
+        let salt = match ws.next().await {
+            Some(Ok(Message::Text(text))) => base64::decode(text.trim())
+                .map_err(|e| format!("Invalid salt from peer: {}", e))?,
+            Some(Ok(other)) => return Err(format!("Unexpected handshake frame: {:?}", other)),
+            Some(Err(e)) => return Err(format!("Bridge handshake failed: {}", e)),
+            None => return Err("Bridge closed during handshake".to_string()),
+        };
+
+        let key = derive_key_from_pin(&normalize_pairing_code(pairing_code), &salt);
+        let mut connection = Self::new(ws, key, false);
+
+        connection.send(&BridgeMessage::Hello { device_name: device_name.to_string() }).await?;
+        connection.peer_name = match connection.recv().await {
+            Ok(BridgeMessage::Hello { device_name }) => device_name,
+            Ok(other) => return Err(format!("Unexpected first message: {:?}", other)),
+            // The listener drops the socket when our Hello fails to decrypt
+            Err(_) => return Err("Pairing failed - check the pairing code".to_string()),
+        };
+
+        println!("✅ Bridge paired with {}", connection.peer_name);
+        Ok(connection)
+    }
+
+    fn new(ws: WebSocketStream<MaybeTlsStream<TcpStream>>, key: [u8; 32], is_listener: bool) -> Self {
+        Self {
+            ws,
+            key,
+            is_listener,
+            send_seq: 0,
+            recv_seq: 0,
+            peer_name: String::new(),
+        }
+    }
+
+    /// Name the other device announced during pairing
+    pub fn peer_name(&self) -> &str {
+        &self.peer_name
+    }
+
+    /// Encrypt and send a message
+    pub async fn send(&mut self, message: &BridgeMessage) -> Result<(), String> {
+        let envelope = Envelope {
+            seq: self.send_seq,
+            from_listener: self.is_listener,
+            message: message.clone(),
+        };
+        let plaintext = serde_json::to_vec(&envelope)
+            .map_err(|e| format!("Failed to serialize bridge message: {}", e))?;
+        let frame = encrypt_with_key(&plaintext, &self.key)?;
+
+        self.ws
+            .send(Message::Text(base64::encode(frame)))
+            .await
+            .map_err(|e| format!("Failed to send bridge message: {}", e))?;
+        self.send_seq += 1;
+        Ok(())
+    }
+
+    /// Wait for the next message from the peer
+    pub async fn recv(&mut self) -> Result<BridgeMessage, String> {
+        loop {
+            let frame = match self.ws.next().await {
+                Some(Ok(Message::Text(text))) => text,
+                Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) => continue,
+                Some(Ok(Message::Close(_))) | None => return Err("Bridge connection closed".to_string()),
+                Some(Ok(other)) => return Err(format!("Unexpected bridge frame: {:?}", other)),
+                Some(Err(e)) => return Err(format!("Bridge connection error: {}", e)),
+            };
+
+            let ciphertext = base64::decode(frame.trim())
+                .map_err(|e| format!("Invalid bridge frame: {}", e))?;
+            let plaintext = decrypt_with_key(&ciphertext, &self.key)
+                .map_err(|_| "Failed to decrypt bridge message - pairing code mismatch".to_string())?;
+            let envelope: Envelope = serde_json::from_slice(&plaintext)
+                .map_err(|e| format!("Invalid bridge message: {}", e))?;
+
+            // Reject our own frames reflected back and anything out of order
+            if envelope.from_listener == self.is_listener {
+                return Err("Received a reflected bridge message".to_string());
+            }
+            if envelope.seq != self.recv_seq {
+                return Err(format!(
+                    "Out of order bridge message (expected {}, got {})",
+                    self.recv_seq, envelope.seq
+                ));
+            }
+            self.recv_seq += 1;
+            return Ok(envelope.message);
+        }
+    }
+
+    /// Close the connection
+    pub async fn close(&mut self) {
+        let _ = self.ws.close(None).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pairing_code_format() {
+        let code = generate_pairing_code();
+        assert_eq!(code.len(), PAIRING_CODE_LEN + 2);
+        assert_eq!(normalize_pairing_code(&code).len(), PAIRING_CODE_LEN);
+        assert_eq!(normalize_pairing_code(" k7qf-2mzx 9pla "), "K7QF2MZX9PLA");
+    }
+
+    #[tokio::test]
+    async fn test_pair_and_exchange() {
+        let port = 47_900;
+        let server = tokio::spawn(async move {
+            let mut conn = BridgeConnection::listen(port, "ABCD-EFGH-JKLM", "Desktop").await.unwrap();
+            let message = conn.recv().await.unwrap();
+            conn.send(&message).await.unwrap();
+            conn.peer_name().to_string()
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut client = BridgeConnection::connect(&format!("127.0.0.1:{}", port), "abcd efgh jklm", "Phone")
+            .await
+            .unwrap();
+        assert_eq!(client.peer_name(), "Desktop");
+
+        let message = BridgeMessage::Reject { session_id: None, reason: "echo".to_string() };
+        client.send(&message).await.unwrap();
+        assert_eq!(client.recv().await.unwrap(), message);
+        assert_eq!(server.await.unwrap(), "Phone");
+    }
+}
@@ -0,0 +1,239 @@
+// src/budget.rs
+//! Savings goals and monthly spend budgets
+//!
+//! Purely local analytics: a goal tracks the SOL balance of one wallet
+//! against a target, and a budget caps the SOL spent each calendar month
+//! (UTC) on transactions tagged with a category in the history index.
+//! Nothing here is sent anywhere.
+
+use crate::balance_history::parse_balance_change;
+use crate::history;
+use crate::storage::{load_budget_settings, load_history_index, save_budget_settings};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavingsGoal {
+    pub name: String,
+    /// Wallet whose SOL balance counts towards the goal
+    pub address: String,
+    pub target_sol: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpendBudget {
+    pub category: String,
+    pub monthly_limit_sol: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetSettings {
+    #[serde(default)]
+    pub goals: Vec<SavingsGoal>,
+    #[serde(default)]
+    pub budgets: Vec<SpendBudget>,
+    /// "category:YYYY-MM" of budgets already reported as exceeded
+    #[serde(default)]
+    pub alerted: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgress {
+    pub goal: SavingsGoal,
+    pub balance_sol: f64,
+}
+
+impl GoalProgress {
+    /// 0.0 - 1.0
+    pub fn fraction(&self) -> f64 {
+        if self.goal.target_sol <= 0.0 {
+            return 1.0;
+        }
+        (self.balance_sol / self.goal.target_sol).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetUsage {
+    pub budget: SpendBudget,
+    pub spent_sol: f64,
+}
+
+impl BudgetUsage {
+    /// 0.0 - 1.0, capped for display
+    pub fn fraction(&self) -> f64 {
+        if self.budget.monthly_limit_sol <= 0.0 {
+            return 1.0;
+        }
+        (self.spent_sol / self.budget.monthly_limit_sol).clamp(0.0, 1.0)
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.spent_sol > self.budget.monthly_limit_sol
+    }
+}
+
+/// "2026-10" for any time in October 2026 (UTC)
+pub fn month_key(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|d| d.format("%Y-%m").to_string())
+        .unwrap_or_default()
+}
+
+/// First second of the month `timestamp` falls in (UTC)
+pub fn month_start(timestamp: i64) -> i64 {
+    use chrono::Datelike;
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .and_then(|d| chrono::NaiveDate::from_ymd_opt(d.year(), d.month(), 1))
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc().timestamp())
+        .unwrap_or(timestamp)
+}
+
+/// Total spent per category since `since`, from `(category, block_time, sol_spent)`
+pub fn spending_by_category(spends: &[(String, i64, f64)], since: i64) -> HashMap<String, f64> {
+    let mut totals = HashMap::new();
+    for (category, block_time, spent) in spends {
+        if *block_time >= since {
+            *totals.entry(category.clone()).or_insert(0.0) += spent;
+        }
+    }
+    totals
+}
+
+pub fn budget_usage(budgets: &[SpendBudget], spending: &HashMap<String, f64>) -> Vec<BudgetUsage> {
+    budgets
+        .iter()
+        .map(|budget| BudgetUsage {
+            budget: budget.clone(),
+            spent_sol: spending.get(&budget.category).copied().unwrap_or(0.0),
+        })
+        .collect()
+}
+
+/// Categories exceeded in `month` that haven't been reported yet. They are
+/// recorded in `settings` so each is only reported once a month.
+pub fn new_alerts(settings: &mut BudgetSettings, usage: &[BudgetUsage], month: &str) -> Vec<String> {
+    let mut alerts = Vec::new();
+    for line in usage.iter().filter(|u| u.is_exceeded()) {
+        let key = format!("{}:{}", line.budget.category, month);
+        if !settings.alerted.contains(&key) {
+            settings.alerted.push(key);
+            alerts.push(line.budget.category.clone());
+        }
+    }
+    // Only this month's entries matter
+    settings.alerted.retain(|key| key.ends_with(month));
+    alerts
+}
+
+/// SOL spent this month per category by tagged transactions of `address`.
+/// Parsed transactions come from the history cache where possible.
+pub async fn month_spending(address: &str, rpc_url: Option<&str>) -> HashMap<String, f64> {
+    let since = month_start(chrono::Utc::now().timestamp());
+    let index = load_history_index(address);
+    let mut spends = Vec::new();
+    for entry in &index.entries {
+        let (Some(category), Some(block_time)) = (index.category(&entry.signature), entry.block_time) else { continue };
+        if block_time < since {
+            continue;
+        }
+        let details = match history::transaction_details(address, &entry.signature, rpc_url).await {
+            Ok(details) => details,
+            Err(e) => {
+                println!("⚠️ Skipping {} in budget: {}", entry.signature, e);
+                continue;
+            }
+        };
+        let (Some(message), Some(meta)) = (details.get("message"), details.get("meta")) else { continue };
+        if let Some(change) = parse_balance_change(address, &entry.signature, entry.block_time, message, meta) {
+            if change.sol_delta < 0 {
+                spends.push((category.to_string(), block_time, change.sol_delta.unsigned_abs() as f64 / 1_000_000_000.0));
+            }
+        }
+    }
+    spending_by_category(&spends, since)
+}
+
+/// Current progress of every goal
+pub async fn goal_progress(goals: &[SavingsGoal], rpc_url: Option<&str>) -> Vec<GoalProgress> {
+    let mut progress = Vec::new();
+    for goal in goals {
+        match crate::rpc::get_balance(&goal.address, rpc_url).await {
+            Ok(balance_sol) => progress.push(GoalProgress { goal: goal.clone(), balance_sol }),
+            Err(e) => println!("⚠️ Balance for goal {} unavailable: {}", goal.name, e),
+        }
+    }
+    progress
+}
+
+/// Budget usage for `address` this month, plus the budgets newly exceeded
+pub async fn check_budgets(address: &str, rpc_url: Option<&str>) -> (Vec<BudgetUsage>, Vec<String>) {
+    let mut settings = load_budget_settings();
+    if settings.budgets.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+    let usage = budget_usage(&settings.budgets, &month_spending(address, rpc_url).await);
+    let alerts = new_alerts(&mut settings, &usage, &month_key(chrono::Utc::now().timestamp()));
+    if !alerts.is_empty() {
+        if let Err(e) = save_budget_settings(&settings) {
+            println!("⚠️ Budget alerts not saved: {}", e);
+        }
+    }
+    (usage, alerts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_boundaries() {
+        // 2026-10-16 13:00:00 UTC
+        let now = 1_792_155_600;
+        assert_eq!(month_key(now), "2026-10");
+        assert_eq!(month_key(month_start(now)), "2026-10");
+        assert_eq!(month_key(month_start(now) - 1), "2026-09");
+    }
+
+    #[test]
+    fn test_spending_and_usage() {
+        let spends = vec![
+            ("Food".to_string(), 100, 1.5),
+            ("Food".to_string(), 200, 1.0),
+            ("Bills".to_string(), 200, 0.5),
+            ("Food".to_string(), 50, 9.0),
+        ];
+        let spending = spending_by_category(&spends, 100);
+        assert_eq!(spending["Food"], 2.5);
+
+        let budgets = vec![
+            SpendBudget { category: "Food".to_string(), monthly_limit_sol: 2.0 },
+            SpendBudget { category: "Fun".to_string(), monthly_limit_sol: 1.0 },
+        ];
+        let usage = budget_usage(&budgets, &spending);
+        assert!(usage[0].is_exceeded());
+        assert_eq!(usage[0].fraction(), 1.0);
+        assert_eq!(usage[1].spent_sol, 0.0);
+        assert!(!usage[1].is_exceeded());
+    }
+
+    #[test]
+    fn test_alerts_once_per_month() {
+        let usage = vec![BudgetUsage {
+            budget: SpendBudget { category: "Food".to_string(), monthly_limit_sol: 1.0 },
+            spent_sol: 2.0,
+        }];
+        let mut settings = BudgetSettings { alerted: vec!["Food:2026-09".to_string()], ..Default::default() };
+        assert_eq!(new_alerts(&mut settings, &usage, "2026-10"), vec!["Food".to_string()]);
+        assert!(new_alerts(&mut settings, &usage, "2026-10").is_empty());
+        assert_eq!(settings.alerted, vec!["Food:2026-10".to_string()]);
+    }
+
+    #[test]
+    fn test_goal_fraction() {
+        let goal = SavingsGoal { name: "Cold storage".to_string(), address: String::new(), target_sol: 100.0 };
+        assert_eq!(GoalProgress { goal: goal.clone(), balance_sol: 25.0 }.fraction(), 0.25);
+        assert_eq!(GoalProgress { goal, balance_sol: 150.0 }.fraction(), 1.0);
+    }
+}
@@ -4,7 +4,6 @@ use solana_sdk::{
     transaction::VersionedTransaction,
     message::VersionedMessage,
     instruction::Instruction,
-    system_instruction,
 };
 use std::error::Error as StdError;
 use std::str::FromStr;
@@ -14,6 +13,7 @@ use reqwest::Client as HttpClient;
 use crate::signing::TransactionSigner;
 use crate::carrot::types::{CarrotBalances, DepositResult, WithdrawResult};
 use crate::storage::get_current_jito_settings;
+use crate::transaction::TransactionClient;
 
 type Result<T> = std::result::Result<T, Box<dyn StdError>>;
 
@@ -59,13 +59,7 @@ impl CarrotClient {
             ]
         });
 
-        let response = self.http_client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
 
         if let Some(amount_str) = json["result"]["value"]["amount"].as_str() {
             let amount = amount_str.parse::<u64>()
@@ -159,15 +153,14 @@ impl CarrotClient {
         )?;
         instructions.push(issue_ix);
         
-        // Check Jito settings and add tip if enabled
-        let jito_settings = get_current_jito_settings();
-        if jito_settings.jito_tx {
-            let jito_tip_address = Pubkey::from_str("juLesoSmdTcRtzjCzYzRoHrnF8GhVu6KCV7uxq7nJGp")?;
-            let tip_ix = system_instruction::transfer(&member_pubkey, &jito_tip_address, 100_000);
-            instructions.push(tip_ix);
+        // App tip and Jito tip when JitoTx is enabled
+        if get_current_jito_settings().jito_tx {
+            instructions.extend(crate::tips::tip_instructions(&member_pubkey, Some(crate::tips::DEFAULT_JITO_TIP_LAMPORTS))?);
             println!("[Carrot] Added Jito tip to deposit transaction");
         }
         
+        TransactionClient::new(Some(&self.rpc_url)).size_compute_budget(&mut instructions, &member_pubkey).await;
+
         // Get recent blockhash
         println!("[Carrot] Getting recent blockhash...");
         let recent_blockhash = self.get_recent_blockhash().await?;
@@ -272,15 +265,14 @@ impl CarrotClient {
         )?;
         instructions.push(redeem_ix);
         
-        // Check Jito settings and add tip if enabled
-        let jito_settings = get_current_jito_settings();
-        if jito_settings.jito_tx {
-            let jito_tip_address = Pubkey::from_str("juLesoSmdTcRtzjCzYzRoHrnF8GhVu6KCV7uxq7nJGp")?;
-            let tip_ix = system_instruction::transfer(&member_pubkey, &jito_tip_address, 100_000);
-            instructions.push(tip_ix);
+        // App tip and Jito tip when JitoTx is enabled
+        if get_current_jito_settings().jito_tx {
+            instructions.extend(crate::tips::tip_instructions(&member_pubkey, Some(crate::tips::DEFAULT_JITO_TIP_LAMPORTS))?);
             println!("[Carrot] Added Jito tip to withdraw transaction");
         }
         
+        TransactionClient::new(Some(&self.rpc_url)).size_compute_budget(&mut instructions, &member_pubkey).await;
+
         // Get recent blockhash
         println!("[Carrot] Getting recent blockhash...");
         let recent_blockhash = self.get_recent_blockhash().await?;
@@ -342,13 +334,7 @@ impl CarrotClient {
             ]
         });
 
-        let response = self.http_client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
 
         if let Some(data) = json["result"]["value"]["data"][0].as_str() {
             let decoded = base64::decode(data)
@@ -372,13 +358,7 @@ impl CarrotClient {
             ]
         });
 
-        let response = self.http_client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
 
         if let Some(blockhash_str) = json["result"]["value"]["blockhash"].as_str() {
             let blockhash = solana_sdk::hash::Hash::from_str(blockhash_str)
@@ -403,13 +383,7 @@ impl CarrotClient {
             ]
         });
 
-        let response = self.http_client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
         
         if let Some(owner_str) = json["result"]["value"]["owner"].as_str() {
             let owner = Pubkey::from_str(owner_str)
@@ -450,13 +424,7 @@ impl CarrotClient {
             ]
         });
 
-        let response = self.http_client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
 
         if let Some(error) = json.get("error") {
             Err(format!("Transaction error: {:?}", error).into())
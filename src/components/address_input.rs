@@ -1,8 +1,7 @@
 // src/components/address_input.rs
 use dioxus::prelude::*;
 use solana_sdk::pubkey::Pubkey;
-use crate::domain_resolver::DomainResolver;
-use std::sync::Arc;
+use crate::domain_resolver::resolve_recipient;
 
 #[derive(Props, Clone, PartialEq)]
 pub struct AddressInputProps {
@@ -27,7 +26,8 @@ pub enum ValidationState {
 #[component]
 pub fn AddressInput(props: AddressInputProps) -> Element {
     let mut validation_state = use_signal(|| ValidationState::Empty);
-    let domain_resolver = use_context::<Arc<DomainResolver>>();
+    // Latest input sent for resolution, so slow lookups can't overwrite newer ones
+    let mut pending_input = use_signal(String::new);
     
     let show_validation = props.show_validation.unwrap_or(true);
     let auto_resolve = props.auto_resolve.unwrap_or(false);
@@ -40,7 +40,6 @@ pub fn AddressInput(props: AddressInputProps) -> Element {
 
     let resolve_address_handler = {
         let mut validation_state = validation_state.clone();
-        let domain_resolver = domain_resolver.clone();
         let on_resolved = props_on_resolved.clone();
         
         move |input: String| {
@@ -51,18 +50,25 @@ pub fn AddressInput(props: AddressInputProps) -> Element {
             }
 
             validation_state.set(ValidationState::Resolving);
+            pending_input.set(input.clone());
             
-            // Use the detailed resolver for better UX (now supports SNS + ANS)
-            match domain_resolver.resolve_address_with_details(&input) {
-                Ok((pubkey, description)) => {
-                    validation_state.set(ValidationState::Success(pubkey, description));
-                    on_resolved.call(Some(pubkey));
-                },
-                Err(error) => {
-                    validation_state.set(ValidationState::Error(error));
-                    on_resolved.call(None);
+            // Same resolution as every other address field (address, SNS or ANS)
+            spawn(async move {
+                let result = resolve_recipient(&input).await;
+                if *pending_input.peek() != input {
+                    return;
                 }
-            }
+                match result {
+                    Ok(recipient) => {
+                        validation_state.set(ValidationState::Success(recipient.pubkey, recipient.description()));
+                        on_resolved.call(Some(recipient.pubkey));
+                    },
+                    Err(error) => {
+                        validation_state.set(ValidationState::Error(error));
+                        on_resolved.call(None);
+                    }
+                }
+            });
         }
     };
 
@@ -0,0 +1,87 @@
+// src/components/budget_overlay.rs
+use dioxus::prelude::*;
+use crate::budget::{self, BudgetUsage, GoalProgress};
+use crate::storage::load_budget_settings;
+
+fn bar_style(fraction: f64, exceeded: bool) -> String {
+    let color = if exceeded { "#ef4444" } else { "#22c55e" };
+    format!(
+        "height: 6px; border-radius: 3px; width: {:.0}%; background: {};",
+        fraction * 100.0,
+        color
+    )
+}
+
+/// Savings goal and monthly budget progress under the portfolio balance.
+/// Renders nothing until a goal or budget has been set up.
+#[component]
+pub fn BudgetOverlay(
+    address: ReadOnlySignal<String>,
+    custom_rpc: Option<String>,
+    /// Bumped by the parent to recompute, e.g. after a refresh
+    refresh: ReadOnlySignal<u32>,
+) -> Element {
+    let mut goals = use_signal(Vec::<GoalProgress>::new);
+    let mut usage = use_signal(Vec::<BudgetUsage>::new);
+    let mut alerts = use_signal(Vec::<String>::new);
+
+    use_effect(move || {
+        let _ = refresh();
+        let addr = address();
+        let rpc_url = custom_rpc.clone();
+        spawn(async move {
+            let settings = load_budget_settings();
+            goals.set(budget::goal_progress(&settings.goals, rpc_url.as_deref()).await);
+            let (month_usage, exceeded) = budget::check_budgets(&addr, rpc_url.as_deref()).await;
+            usage.set(month_usage);
+            if !exceeded.is_empty() {
+                alerts.set(exceeded);
+            }
+        });
+    });
+
+    if goals().is_empty() && usage().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "info-message",
+            style: "margin: 8px 16px;",
+
+            if !alerts().is_empty() {
+                div {
+                    class: "error-message",
+                    "⚠️ Monthly budget exceeded: {alerts().join(\", \")}"
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| alerts.set(Vec::new()),
+                        "Dismiss"
+                    }
+                }
+            }
+
+            for progress in goals() {
+                div {
+                    key: "goal-{progress.goal.name}-{progress.goal.address}",
+                    div {
+                        class: "help-text",
+                        "🎯 {progress.goal.name}: {progress.balance_sol:.2} / {progress.goal.target_sol} SOL"
+                    }
+                    div { style: "{bar_style(progress.fraction(), false)}" }
+                }
+            }
+
+            for line in usage() {
+                div {
+                    key: "budget-{line.budget.category}",
+                    div {
+                        class: "help-text",
+                        "💰 {line.budget.category} this month: {line.spent_sol:.2} / {line.budget.monthly_limit_sol} SOL"
+                    }
+                    div { style: "{bar_style(line.fraction(), line.is_exceeded())}" }
+                }
+            }
+        }
+    }
+}
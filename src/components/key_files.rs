@@ -0,0 +1,91 @@
+// src/components/key_files.rs
+use dioxus::prelude::*;
+use crate::backup::MIN_PASSWORD_LENGTH;
+use crate::keystore::{export_cli_keypair, export_keystore};
+use crate::wallet::Wallet;
+
+/// Save a wallet key as a Solana CLI keypair file or an encrypted keystore
+#[component]
+pub fn KeyFilesPanel(private_key: String) -> Element {
+    let mut password = use_signal(String::new);
+    let mut confirm_password = use_signal(String::new);
+    let mut saved_path = use_signal(|| None as Option<String>);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    let wallet = bs58::decode(&private_key)
+        .into_vec()
+        .map_err(|e| format!("Decode error: {}", e))
+        .and_then(|bytes| Wallet::from_private_key(&bytes, String::new()));
+    let cli_wallet = wallet.clone();
+
+    let mut show_result = move |result: Result<String, String>| match result {
+        Ok(path) => {
+            error_message.set(None);
+            saved_path.set(Some(path));
+        }
+        Err(e) => error_message.set(Some(e)),
+    };
+
+    let save_keystore = move |_| {
+        if password() != confirm_password() {
+            error_message.set(Some("Passwords don't match".to_string()));
+            return;
+        }
+        show_result(wallet.clone().and_then(|wallet| export_keystore(&wallet, &password())));
+        password.set(String::new());
+        confirm_password.set(String::new());
+    };
+
+    let password_hint = format!("At least {} characters", MIN_PASSWORD_LENGTH);
+
+    rsx! {
+        div {
+            class: "wallet-field",
+            label { "Key Files:" }
+            if let Some(error) = error_message() {
+                div { class: "error-message", "{error}" }
+            }
+            if let Some(path) = saved_path() {
+                div { class: "info-message", "Saved to {path}" }
+            }
+            div {
+                class: "help-text",
+                "id.json is the Solana CLI keypair file, unencrypted. Use it with solana --keypair or solana-keygen."
+            }
+            button {
+                class: "show-key-button",
+                onclick: move |_| show_result(cli_wallet.clone().and_then(|wallet| export_cli_keypair(&wallet))),
+                "Save id.json"
+            }
+            div {
+                class: "help-text",
+                "The encrypted keystore opens with its password in this app's Import Wallet, or any tool with Argon2id and AES-GCM."
+            }
+            div {
+                class: "wallet-field",
+                label { "Keystore password:" }
+                input {
+                    r#type: "password",
+                    value: "{password}",
+                    placeholder: "{password_hint}",
+                    oninput: move |e| password.set(e.value()),
+                }
+            }
+            div {
+                class: "wallet-field",
+                label { "Confirm password:" }
+                input {
+                    r#type: "password",
+                    value: "{confirm_password}",
+                    oninput: move |e| confirm_password.set(e.value()),
+                }
+            }
+            button {
+                class: "show-key-button",
+                disabled: password().is_empty(),
+                onclick: save_keystore,
+                "Save Encrypted Keystore"
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// src/components/key_shares.rs
+use dioxus::prelude::*;
+use crate::shamir::{split_wallet_key, MAX_SHARES};
+
+/// Split a wallet key into SLIP-39 shares to write down and keep apart
+#[component]
+pub fn KeySharesPanel(private_key: String) -> Element {
+    let mut threshold = use_signal(|| 2u8);
+    let mut count = use_signal(|| 3u8);
+    let mut shares = use_signal(Vec::<String>::new);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    let split = move |_| match split_wallet_key(&private_key, threshold(), count()) {
+        Ok(split) => {
+            error_message.set(None);
+            shares.set(split);
+        }
+        Err(e) => error_message.set(Some(e)),
+    };
+
+    let summary = format!("Any {} of these {} shares rebuild the wallet", threshold(), count());
+
+    rsx! {
+        div {
+            class: "wallet-field",
+            label { "Split into Shares:" }
+            div {
+                class: "help-text",
+                "Keep each share in a different place. Fewer than the threshold reveal nothing about the key. Shares rebuild this wallet's key, not its recovery phrase."
+            }
+            div {
+                class: "wallet-field",
+                label { "Shares needed:" }
+                select {
+                    onchange: move |e| {
+                        threshold.set(e.value().parse().unwrap_or(2));
+                        shares.set(Vec::new());
+                    },
+                    for n in 2..=MAX_SHARES {
+                        option { key: "{n}", value: "{n}", selected: n == threshold(), "{n}" }
+                    }
+                }
+                label { "Total shares:" }
+                select {
+                    onchange: move |e| {
+                        count.set(e.value().parse().unwrap_or(3));
+                        shares.set(Vec::new());
+                    },
+                    for n in 2..=MAX_SHARES {
+                        option { key: "{n}", value: "{n}", selected: n == count(), "{n}" }
+                    }
+                }
+            }
+            if let Some(error) = error_message() {
+                div { class: "error-message", "{error}" }
+            }
+            if shares().is_empty() {
+                button {
+                    class: "show-key-button",
+                    onclick: split,
+                    "Create Shares"
+                }
+            } else {
+                div { class: "info-message", "{summary}" }
+                for (i, share) in shares().into_iter().enumerate() {
+                    div {
+                        key: "{i}",
+                        class: "wallet-field",
+                        label { "Share {i + 1}:" }
+                        div { class: "private-key-display", "{share}" }
+                    }
+                }
+                div {
+                    class: "copy-hint",
+                    "To restore, choose Import Wallet and enter the shares one per line."
+                }
+            }
+        }
+    }
+}
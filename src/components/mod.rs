@@ -6,8 +6,34 @@ pub mod address_input;
 pub mod onboarding;
 pub mod pin_input;
 pub mod pin_unlock;
+pub mod operation_progress;
+pub mod simulation_preview;
+pub mod stuck_transaction;
+pub mod update_banner;
+pub mod transaction_status;
+pub mod budget_overlay;
+pub mod transaction_export;
+pub mod tip_picker;
+pub mod token_search;
+pub mod wsol_cleanup;
+pub mod key_shares;
+pub mod paper_backup;
+pub mod key_files;
 
 pub use wallet_view::*;
 pub use onboarding::OnboardingFlow;
 pub use pin_input::PinInput;
-pub use pin_unlock::PinUnlock;
\ No newline at end of file
+pub use pin_unlock::PinUnlock;
+pub use operation_progress::OperationProgress;
+pub use simulation_preview::{SimulationPreviewPanel, PreviewState, DrainWarning};
+pub use stuck_transaction::StuckTransactionMonitor;
+pub use update_banner::UpdateBanner;
+pub use transaction_status::{use_transaction_status, TransactionStatusView};
+pub use budget_overlay::BudgetOverlay;
+pub use transaction_export::TransactionExportPanel;
+pub use tip_picker::TipPicker;
+pub use token_search::TokenSearch;
+pub use wsol_cleanup::WrappedSolCleanup;
+pub use key_shares::KeySharesPanel;
+pub use paper_backup::PaperBackupPanel;
+pub use key_files::KeyFilesPanel;
\ No newline at end of file
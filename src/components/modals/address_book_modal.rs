@@ -0,0 +1,164 @@
+use dioxus::prelude::*;
+use crate::hardware::HardwareWallet;
+use crate::hardware::protocol::{TrustedAddress, MAX_TRUSTED_LABEL_LEN};
+use crate::domain_resolver::resolve_recipient;
+use std::sync::Arc;
+
+/// Manage the trusted recipient list stored on the hardware wallet
+#[component]
+pub fn AddressBookModal(hardware_wallet: Arc<HardwareWallet>, onclose: EventHandler<()>) -> Element {
+    let mut entries = use_signal(|| Vec::<TrustedAddress>::new());
+    let mut loading = use_signal(|| true);
+    let mut busy = use_signal(|| false);
+    let mut refresh = use_signal(|| 0u32);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut new_address = use_signal(|| String::new());
+    let mut new_label = use_signal(|| String::new());
+
+    let hw_for_effect = hardware_wallet.clone();
+    use_effect(move || {
+        let _ = refresh();
+        let hw = hw_for_effect.clone();
+        loading.set(true);
+        spawn(async move {
+            match hw.list_trusted_addresses().await {
+                Ok(list) => entries.set(list),
+                Err(e) => error_message.set(Some(format!("Failed to read address book: {}", e))),
+            }
+            loading.set(false);
+        });
+    });
+
+    let hw_for_add = hardware_wallet.clone();
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Device Address Book" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Recipients saved here are shown as trusted on your device while signing. Unknown recipients trigger a warning."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                if loading() {
+                    div { class: "info-message", "Reading address book from device..." }
+                } else if entries().is_empty() {
+                    div { class: "info-message", "No trusted addresses on this device" }
+                }
+
+                for entry in entries() {
+                    {
+                        let hw = hardware_wallet.clone();
+                        let address = entry.address.clone();
+                        rsx! {
+                            div {
+                                key: "{entry.address}",
+                                class: "wallet-field",
+                                label { "{entry.label}" }
+                                div { class: "address-display", "{entry.address}" }
+                                button {
+                                    class: "button-standard secondary",
+                                    disabled: busy(),
+                                    onclick: move |_| {
+                                        let hw = hw.clone();
+                                        let address = address.clone();
+                                        busy.set(true);
+                                        error_message.set(None);
+                                        spawn(async move {
+                                            if let Err(e) = hw.remove_trusted_address(&address).await {
+                                                error_message.set(Some(format!("Failed to remove address: {}", e)));
+                                            }
+                                            busy.set(false);
+                                            refresh.set(refresh() + 1);
+                                        });
+                                    },
+                                    "Remove"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Add trusted address:" }
+                    input {
+                        value: "{new_address}",
+                        oninput: move |e| new_address.set(e.value()),
+                        placeholder: "Address or domain (e.g. recipient.sol)"
+                    }
+                    input {
+                        value: "{new_label}",
+                        maxlength: "{MAX_TRUSTED_LABEL_LEN}",
+                        oninput: move |e| new_label.set(e.value()),
+                        placeholder: "Label (e.g. Exchange)"
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: busy() || new_address().trim().is_empty(),
+                        onclick: move |_| {
+                            let hw = hw_for_add.clone();
+                            let address = new_address().trim().to_string();
+                            let label = new_label().trim().to_string();
+                            busy.set(true);
+                            error_message.set(None);
+                            spawn(async move {
+                                let resolved = match resolve_recipient(&address).await {
+                                    Ok(resolved) => resolved,
+                                    Err(e) => {
+                                        error_message.set(Some(e));
+                                        busy.set(false);
+                                        return;
+                                    }
+                                };
+                                // Name domain entries after the domain unless a label was given
+                                let label = match (&resolved.domain, label.is_empty()) {
+                                    (Some(domain), true) => domain.chars().take(MAX_TRUSTED_LABEL_LEN).collect(),
+                                    _ => label,
+                                };
+                                // The device asks for confirmation before storing the entry
+                                match hw.add_trusted_address(&resolved.pubkey.to_string(), &label).await {
+                                    Ok(()) => {
+                                        new_address.set(String::new());
+                                        new_label.set(String::new());
+                                    }
+                                    Err(e) => error_message.set(Some(format!("Failed to add address: {}", e))),
+                                }
+                                busy.set(false);
+                                refresh.set(refresh() + 1);
+                            });
+                        },
+                        if busy() { "Confirm on device..." } else { "Add to Device" }
+                    }
+                }
+            }
+        }
+    }
+}
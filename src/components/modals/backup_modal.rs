@@ -0,0 +1,102 @@
+use dioxus::prelude::*;
+use crate::backup::{export_backup, MIN_PASSWORD_LENGTH};
+
+/// Write every software wallet and the app's settings to one password
+/// protected file, restorable from onboarding on another machine
+#[component]
+pub fn BackupModal(onclose: EventHandler<()>) -> Element {
+    let mut password = use_signal(String::new);
+    let mut confirm_password = use_signal(String::new);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut saved_path = use_signal(|| None as Option<String>);
+
+    let create = move |_| {
+        if password() != confirm_password() {
+            error_message.set(Some("Passwords don't match".to_string()));
+            return;
+        }
+        error_message.set(None);
+        match export_backup(&password()) {
+            Ok(path) => {
+                saved_path.set(Some(path));
+                password.set(String::new());
+                confirm_password.set(String::new());
+            }
+            Err(e) => error_message.set(Some(e)),
+        }
+    };
+
+    let password_hint = format!("At least {} characters", MIN_PASSWORD_LENGTH);
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Backup" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Saves your software wallets, settings and labels to one encrypted file. Copy it to the new machine and choose Restore from Backup when setting up the app there."
+                }
+                div {
+                    class: "warning-message",
+                    "⚠️ Anyone with the file and its password controls your wallets. The password can't be recovered."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(path) = saved_path() {
+                    div { class: "info-message", "Backup saved to {path}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Password:" }
+                    input {
+                        r#type: "password",
+                        value: "{password}",
+                        placeholder: "{password_hint}",
+                        oninput: move |e| password.set(e.value()),
+                    }
+                }
+                div {
+                    class: "wallet-field",
+                    label { "Confirm password:" }
+                    input {
+                        r#type: "password",
+                        value: "{confirm_password}",
+                        oninput: move |e| confirm_password.set(e.value()),
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: password().is_empty(),
+                        onclick: create,
+                        "Create Backup"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -30,7 +30,7 @@ fn HardwareApprovalOverlay(oncancel: EventHandler<()>) -> Element {
 
 #[component]
 fn TransactionSuccessModal(signature: String, operation: String, amount: f64, onclose: EventHandler<()>) -> Element {
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     let orb_url = format!("https://orb.helius.dev/tx/{}?cluster=mainnet-beta&tab=summary", signature);
     
     rsx! {
@@ -0,0 +1,250 @@
+use dioxus::prelude::*;
+use crate::components::modals::device_transfer_modal::CAMERA_SCANNER_JS;
+use crate::components::simulation_preview::{SimulationPreviewPanel, PreviewState, DrainWarning};
+use crate::components::TransactionStatusView;
+use crate::qr_transfer::FrameAssembler;
+use crate::transaction::{RawTransactionCheck, TransactionClient};
+
+/// Paste or scan a transaction signed elsewhere, check it, and broadcast it
+#[component]
+pub fn BroadcastModal(custom_rpc: Option<String>, onclose: EventHandler<()>) -> Element {
+    let mut encoded = use_signal(String::new);
+    let mut check = use_signal(|| None as Option<RawTransactionCheck>);
+    let mut preview = use_signal(|| PreviewState::Idle);
+    let mut drain_confirmed = use_signal(|| false);
+    let mut checking = use_signal(|| false);
+    let mut sending = use_signal(|| false);
+    let mut scanning = use_signal(|| false);
+    let mut stop_camera = use_signal(|| None as Option<document::Eval>);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut signature = use_signal(|| None as Option<String>);
+
+    let rpc_for_check = custom_rpc.clone();
+    let mut run_check = move |text: String| {
+        let rpc = rpc_for_check.clone();
+        check.set(None);
+        preview.set(PreviewState::Idle);
+        drain_confirmed.set(false);
+        signature.set(None);
+        error_message.set(None);
+        checking.set(true);
+        spawn(async move {
+            let client = TransactionClient::new(rpc.as_deref());
+            match client.check_raw_transaction(&text).await {
+                Ok(checked) => {
+                    preview.set(PreviewState::Loading);
+                    let fee_payer = checked.fee_payer();
+                    let transaction = checked.transaction.clone();
+                    check.set(Some(checked));
+                    if let Some(fee_payer) = fee_payer {
+                        let result = client.simulate_balance_diff(&transaction, &fee_payer).await.map_err(|e| e.to_string());
+                        match result {
+                            Ok(simulated) => preview.set(PreviewState::Ready(simulated)),
+                            Err(e) => preview.set(PreviewState::Unavailable(e)),
+                        }
+                    }
+                }
+                Err(e) => error_message.set(Some(e)),
+            }
+            checking.set(false);
+        });
+    };
+
+    let mut stop_scanning = move || {
+        if let Some(eval) = stop_camera.write().take() {
+            let _ = eval.send(true);
+        }
+        scanning.set(false);
+    };
+
+    let run_check_for_scan = run_check.clone();
+    let start_scanning = move |_| {
+        scanning.set(true);
+        error_message.set(None);
+        let mut eval = document::eval(CAMERA_SCANNER_JS);
+        stop_camera.set(Some(eval));
+        let mut run_check = run_check_for_scan.clone();
+        spawn(async move {
+            let mut assembler = FrameAssembler::new();
+            while let Ok(code) = eval.recv::<String>().await {
+                if let Some(reason) = code.strip_prefix("!error:") {
+                    error_message.set(Some(reason.to_string()));
+                    break;
+                }
+                // Animated codes from Device Transfer or an export, or one plain code
+                if !code.starts_with("UNRUG:") {
+                    encoded.set(code.clone());
+                    run_check(code);
+                    let _ = eval.send(true);
+                    break;
+                }
+                if let Err(e) = assembler.add(&code) {
+                    error_message.set(Some(e));
+                    continue;
+                }
+                match assembler.finish() {
+                    Some(Ok((_, payload))) => {
+                        encoded.set(payload.clone());
+                        run_check(payload);
+                        let _ = eval.send(true);
+                        break;
+                    }
+                    Some(Err(e)) => {
+                        error_message.set(Some(e));
+                        let _ = eval.send(true);
+                        break;
+                    }
+                    None => {}
+                }
+            }
+            stop_camera.set(None);
+            scanning.set(false);
+        });
+    };
+
+    let rpc_for_send = custom_rpc.clone();
+    let broadcast = move |_| {
+        let Some(checked) = check() else { return };
+        let rpc = rpc_for_send.clone();
+        sending.set(true);
+        error_message.set(None);
+        spawn(async move {
+            let client = TransactionClient::new(rpc.as_deref());
+            let result = client.broadcast_raw(&checked).await.map_err(|e| e.to_string());
+            match result {
+                Ok(sig) => signature.set(Some(sig)),
+                Err(e) => error_message.set(Some(format!("Broadcast failed: {}", e))),
+            }
+            sending.set(false);
+        });
+    };
+
+    let problems = check().map(|c| c.problems()).unwrap_or_default();
+    let blockhash_note = match check().map(|c| (c.uses_durable_nonce, c.blockhash_valid)) {
+        Some((true, _)) => "Uses a durable nonce, so it doesn't expire",
+        Some((false, Some(true))) => "Blockhash is recent",
+        Some((false, Some(false))) => "Blockhash has expired",
+        _ => "Blockhash age couldn't be checked",
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| {
+                stop_scanning();
+                onclose.call(());
+            },
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Broadcast Transaction" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| {
+                            stop_scanning();
+                            onclose.call(());
+                        },
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Submit a transaction that was signed elsewhere. It is decoded and checked before anything is sent."
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Signed transaction (base58 or base64):" }
+                    textarea {
+                        rows: "4",
+                        value: "{encoded}",
+                        oninput: move |e| {
+                            encoded.set(e.value());
+                            check.set(None);
+                            preview.set(PreviewState::Idle);
+                        },
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        disabled: scanning(),
+                        onclick: start_scanning,
+                        if scanning() { "Scanning..." } else { "Scan QR" }
+                    }
+                    button {
+                        class: "button-standard secondary",
+                        disabled: checking() || encoded().trim().is_empty(),
+                        onclick: move |_| run_check(encoded()),
+                        if checking() { "Checking..." } else { "Check" }
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                if let Some(checked) = check() {
+                    div {
+                        class: "wallet-field",
+                        label { "Signers:" }
+                        for status in checked.signers.iter() {
+                            div {
+                                key: "{status.pubkey}",
+                                class: "help-text",
+                                if status.signed { "✅ {status.pubkey}" } else { "❌ {status.pubkey}" }
+                            }
+                        }
+                        label { "Instructions:" }
+                        for (index, line) in checked.instruction_summary().into_iter().enumerate() {
+                            div { key: "{index}", class: "help-text", "{line}" }
+                        }
+                        div { class: "help-text", "{blockhash_note}" }
+                    }
+                    for (index, problem) in problems.iter().enumerate() {
+                        div { key: "problem-{index}", class: "error-message", "{problem}" }
+                    }
+                }
+
+                SimulationPreviewPanel { state: preview() }
+                DrainWarning {
+                    state: preview(),
+                    confirmed: drain_confirmed(),
+                    onchange: move |confirmed| drain_confirmed.set(confirmed),
+                }
+
+                if let Some(sig) = signature() {
+                    div {
+                        class: "wallet-field",
+                        label { "Sent" }
+                        div { class: "address-display", "{sig}" }
+                        TransactionStatusView { signature: sig.clone(), custom_rpc: custom_rpc.clone() }
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| {
+                            stop_scanning();
+                            onclose.call(());
+                        },
+                        "Close"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: sending() || check().is_none() || !problems.is_empty() || signature().is_some() || (preview.read().needs_confirmation() && !drain_confirmed()),
+                        onclick: broadcast,
+                        if sending() { "Broadcasting..." } else { "Broadcast" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use dioxus::prelude::*;
+use crate::budget::{BudgetSettings, SavingsGoal, SpendBudget};
+use crate::storage::{load_budget_settings, load_history_index, save_budget_settings};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+fn parse_sol(amount: &str) -> Result<f64, String> {
+    amount
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| *v > 0.0)
+        .ok_or_else(|| "Enter a valid SOL amount".to_string())
+}
+
+/// Set up savings goals and monthly spend budgets per history category
+#[component]
+pub fn BudgetModal(
+    /// Prefilled as the goal wallet and used for category suggestions
+    address: String,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut settings = use_signal(load_budget_settings);
+    let mut goal_name = use_signal(String::new);
+    let mut goal_address = use_signal({
+        let address = address.clone();
+        move || address
+    });
+    let mut goal_target = use_signal(String::new);
+    let mut budget_category = use_signal(String::new);
+    let mut budget_limit = use_signal(String::new);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    let known_categories = load_history_index(&address).known_categories();
+
+    let mut save = move |updated: BudgetSettings| match save_budget_settings(&updated) {
+        Ok(()) => {
+            settings.set(updated);
+            error_message.set(None);
+        }
+        Err(e) => error_message.set(Some(e)),
+    };
+
+    let add_goal = move |_| {
+        let name = goal_name().trim().to_string();
+        let wallet = goal_address().trim().to_string();
+        if name.is_empty() {
+            error_message.set(Some("Give the goal a name".to_string()));
+            return;
+        }
+        if Pubkey::from_str(&wallet).is_err() {
+            error_message.set(Some("Enter a valid wallet address".to_string()));
+            return;
+        }
+        let target_sol = match parse_sol(&goal_target()) {
+            Ok(target) => target,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        let mut updated = settings();
+        updated.goals.push(SavingsGoal { name, address: wallet, target_sol });
+        save(updated);
+        goal_name.set(String::new());
+        goal_target.set(String::new());
+    };
+
+    let add_budget = move |_| {
+        let category = budget_category().trim().to_string();
+        if category.is_empty() {
+            error_message.set(Some("Choose a category".to_string()));
+            return;
+        }
+        let monthly_limit_sol = match parse_sol(&budget_limit()) {
+            Ok(limit) => limit,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        let mut updated = settings();
+        // One budget per category
+        updated.budgets.retain(|b| b.category != category);
+        updated.budgets.push(SpendBudget { category, monthly_limit_sol });
+        save(updated);
+        budget_category.set(String::new());
+        budget_limit.set(String::new());
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Goals & Budgets" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Progress shows under your balance. Budgets count SOL spent this month by transactions you tagged with the category in Transaction History. Everything stays on this device."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Savings goals:" }
+                    if settings().goals.is_empty() {
+                        div { class: "help-text", "No goals yet" }
+                    }
+                    for (index, goal) in settings().goals.into_iter().enumerate() {
+                        div {
+                            key: "goal-{index}",
+                            class: "info-message",
+                            "🎯 {goal.name}: {goal.target_sol} SOL in {goal.address}"
+                            button {
+                                class: "button-standard secondary",
+                                onclick: move |_| {
+                                    let mut updated = settings();
+                                    updated.goals.remove(index);
+                                    save(updated);
+                                },
+                                "Remove"
+                            }
+                        }
+                    }
+                    input {
+                        value: "{goal_name}",
+                        oninput: move |e| goal_name.set(e.value()),
+                        placeholder: "Goal name, e.g. Cold storage"
+                    }
+                    input {
+                        value: "{goal_address}",
+                        oninput: move |e| goal_address.set(e.value()),
+                        placeholder: "Wallet address"
+                    }
+                    input {
+                        r#type: "number",
+                        value: "{goal_target}",
+                        oninput: move |e| goal_target.set(e.value()),
+                        placeholder: "Target (SOL)"
+                    }
+                    button {
+                        class: "button-standard secondary",
+                        onclick: add_goal,
+                        "Add Goal"
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Monthly budgets:" }
+                    if settings().budgets.is_empty() {
+                        div { class: "help-text", "No budgets yet" }
+                    }
+                    for (index, spend_budget) in settings().budgets.into_iter().enumerate() {
+                        div {
+                            key: "budget-{index}",
+                            class: "info-message",
+                            "💰 {spend_budget.category}: {spend_budget.monthly_limit_sol} SOL per month"
+                            button {
+                                class: "button-standard secondary",
+                                onclick: move |_| {
+                                    let mut updated = settings();
+                                    updated.budgets.remove(index);
+                                    save(updated);
+                                },
+                                "Remove"
+                            }
+                        }
+                    }
+                    input {
+                        value: "{budget_category}",
+                        list: "budget-categories",
+                        oninput: move |e| budget_category.set(e.value()),
+                        placeholder: "Category"
+                    }
+                    datalist {
+                        id: "budget-categories",
+                        for category in known_categories {
+                            option { key: "{category}", value: "{category}" }
+                        }
+                    }
+                    input {
+                        r#type: "number",
+                        value: "{budget_limit}",
+                        oninput: move |e| budget_limit.set(e.value()),
+                        placeholder: "Monthly limit (SOL)"
+                    }
+                    button {
+                        class: "button-standard secondary",
+                        onclick: add_budget,
+                        "Add Budget"
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard primary",
+                        onclick: move |_| onclose.call(()),
+                        "Done"
+                    }
+                }
+            }
+        }
+    }
+}
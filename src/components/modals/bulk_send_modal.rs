@@ -2,20 +2,51 @@
 
 use dioxus::prelude::*;
 use crate::components::common::Token;
-use crate::wallet::{Wallet, WalletInfo};
+use crate::wallet::WalletInfo;
 use crate::hardware::HardwareWallet;
 use crate::components::modals::send_modal::HardwareWalletEvent;
-use crate::transaction::TransactionClient;
-use crate::signing::{SignerType, hardware::HardwareSigner};
+use crate::transaction::{RecipientTransfer, TransactionClient, MAX_MEMO_BYTES, WRAPPED_SOL_MINT};
+use crate::history;
+use crate::operations::{self, Operation, OperationRunner, OperationState, PauseHandle};
+use crate::signing::{active_wallet_signer, TransactionSigner};
 use crate::components::address_input::AddressInput; // ← ADD THIS IMPORT
+use crate::components::OperationProgress;
 use solana_sdk::pubkey::Pubkey; // ← ADD THIS IMPORT
+use std::str::FromStr;
 use std::sync::Arc;
 use std::collections::HashSet;
 
-#[derive(Debug, Clone)]
-pub struct SelectedTokenForBulkSend {
-    pub token: Token,
-    pub amount: f64,
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// Operation kinds this modal runs and resumes
+const BULK_SEND_KINDS: &[&str] = &["bulk_send", "sweep"];
+
+/// Run (or resume) a bulk send or sweep, mirroring its progress into
+/// `progress`, and label every transaction it sent
+async fn run_bulk_operation(
+    mut operation: Operation,
+    signer: Box<dyn TransactionSigner>,
+    rpc_url: Option<String>,
+    pause: PauseHandle,
+    label_text: String,
+    mut progress: Signal<Option<Operation>>,
+) -> Result<(), String> {
+    let client = TransactionClient::new(rpc_url.as_deref());
+    let runner = OperationRunner::new(pause);
+    runner.pause.resume();
+    let result = operations::run_transfer_operation(&runner, &mut operation, &client, signer.as_ref(), |snapshot| {
+        progress.set(Some(snapshot.clone()));
+    })
+    .await;
+
+    if !label_text.trim().is_empty() {
+        for signature in operation.steps.iter().filter_map(|step| step.result.as_deref()) {
+            if let Err(e) = history::set_transaction_label(signature, Some(&label_text)) {
+                println!("⚠️ Failed to save transaction label: {}", e);
+            }
+        }
+    }
+    result
 }
 
 /// Hardware wallet approval overlay component for bulk send
@@ -87,7 +118,7 @@ pub fn BulkSendSuccessModal(
     onclose: EventHandler<()>,
 ) -> Element {
     // Explorer links - Solscan and Orb
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     let orb_url = format!("https://orb.helius.dev/tx/{}?cluster=mainnet-beta&tab=summary", signature);
     
     rsx! {
@@ -189,6 +220,10 @@ pub fn BulkSendModal(
     let mut checking_balance = use_signal(|| false);
     let mut token_amounts = use_signal(|| std::collections::HashMap::<String, String>::new());
     let mut token_amount_errors = use_signal(|| std::collections::HashMap::<String, String>::new());
+    // Sent on-chain with the transfers
+    let mut memo = use_signal(String::new);
+    // Kept on this device only, shown in the history
+    let mut label = use_signal(String::new);
     
     // Success modal state
     let mut show_success_modal = use_signal(|| false);
@@ -197,6 +232,56 @@ pub fn BulkSendModal(
     
     // Hardware approval overlay state
     let mut show_hardware_approval = use_signal(|| false);
+
+    // Bulk send or sweep in progress, one step per transaction
+    let mut operation = use_signal(|| None as Option<Operation>);
+    let pause = use_hook(PauseHandle::new);
+
+    // Offer to finish a bulk send or sweep that was interrupted
+    let hardware_for_resume = hardware_wallet.clone();
+    let wallet_for_resume = wallet.clone();
+    use_effect(move || {
+        let Ok(signer) = active_wallet_signer(hardware_for_resume.clone(), wallet_for_resume.clone()) else { return };
+        spawn(async move {
+            let Ok(address) = signer.get_public_key().await else { return };
+            let interrupted = operations::load_resumable_operations()
+                .into_iter()
+                .find(|o| o.wallet_address == address && BULK_SEND_KINDS.contains(&o.kind.as_str()));
+            if let Some(mut interrupted) = interrupted {
+                // Whatever was running stopped with the app
+                interrupted.state = OperationState::Paused;
+                operation.set(Some(interrupted));
+            }
+        });
+    });
+
+    let hardware_for_run = hardware_wallet.clone();
+    let wallet_for_run = wallet.clone();
+    let rpc_for_run = custom_rpc.clone();
+    let pause_for_run = pause.clone();
+    let resume = move |_: ()| {
+        let Some(interrupted) = operation() else { return };
+        let signer = match active_wallet_signer(hardware_for_run.clone(), wallet_for_run.clone()) {
+            Ok(signer) => signer,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        let rpc_url = rpc_for_run.clone();
+        let pause = pause_for_run.clone();
+        let label_text = label();
+        sending.set(true);
+        error_message.set(None);
+        show_hardware_approval.set(hardware_for_run.is_some());
+        spawn(async move {
+            if let Err(e) = run_bulk_operation(interrupted, signer, rpc_url, pause, label_text, operation).await {
+                error_message.set(Some(e));
+            }
+            sending.set(false);
+            show_hardware_approval.set(false);
+        });
+    };
     
     // Filter tokens to only selected ones using use_memo for reactivity
     let selected_tokens = use_memo(move || {
@@ -303,6 +388,59 @@ pub fn BulkSendModal(
         };
     }
 
+    if let Some(current) = operation() {
+        let pause_for_cancel = pause.clone();
+        let pause_for_progress = pause.clone();
+        return rsx! {
+            div {
+                class: "modal-backdrop",
+                onclick: move |_| onclose.call(()),
+
+                div {
+                    class: "modal-content bulk-send-modal",
+                    onclick: move |e| e.stop_propagation(),
+                    style: "position: relative;",
+
+                    if show_hardware_approval() {
+                        BulkSendHardwareApprovalOverlay {
+                            selected_count: current.steps.iter()
+                                .map(|step| step.payload["transfers"].as_array().map_or(0, Vec::len))
+                                .sum::<usize>(),
+                            oncancel: move |_| {
+                                pause_for_cancel.pause();
+                                show_hardware_approval.set(false);
+                            }
+                        }
+                    }
+
+                    if let Some(error) = error_message() {
+                        div {
+                            class: "error-message",
+                            "{error}"
+                        }
+                    }
+
+                    OperationProgress {
+                        operation: current,
+                        on_pause: move |_| pause_for_progress.pause(),
+                        on_resume: resume,
+                        on_dismiss: move |_| {
+                            let finished = operation.peek().as_ref()
+                                .filter(|o| o.state == OperationState::Completed)
+                                .and_then(|o| o.steps.last()?.result.clone());
+                            // An unfinished operation stays stored and is offered again
+                            operation.set(None);
+                            if let Some(signature) = finished {
+                                transaction_signature.set(signature);
+                                show_success_modal.set(true);
+                            }
+                        },
+                    }
+                }
+            }
+        };
+    }
+
     // Determine which address to show based on wallet type
     let display_address = if let Some(hw) = &hardware_wallet {
         let mut hw_address = use_signal(|| None as Option<String>);
@@ -518,6 +656,29 @@ pub fn BulkSendModal(
                     }
                 }
 
+                div {
+                    class: "wallet-field",
+                    label { "Memo (optional, public):" }
+                    input {
+                        value: "{memo}",
+                        oninput: move |e| memo.set(e.value()),
+                        placeholder: "Attached to the transaction on-chain"
+                    }
+                    if memo().trim().len() > MAX_MEMO_BYTES {
+                        div { class: "error-message", "Memo is too long ({memo().trim().len()} of at most {MAX_MEMO_BYTES} bytes)" }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Label (optional, private):" }
+                    input {
+                        value: "{label}",
+                        oninput: move |e| label.set(e.value()),
+                        placeholder: "e.g. Rent — March, shown in your history"
+                    }
+                }
+
                 if hardware_wallet.is_some() {
                     div {
                         class: "info-message",
@@ -529,7 +690,7 @@ pub fn BulkSendModal(
                     class: "modal-buttons",
                     button {
                         class: "modal-button primary",
-                        disabled: sending() || !all_amounts_valid() || resolved_recipient.read().is_none(), // ← UPDATED VALIDATION
+                        disabled: sending() || !all_amounts_valid() || resolved_recipient.read().is_none() || memo().trim().len() > MAX_MEMO_BYTES, // ← UPDATED VALIDATION
                         onclick: move |_| {
                             // ← VALIDATE RESOLVED RECIPIENT FIRST
                             let recipient_pubkey = match resolved_recipient.read().as_ref() {
@@ -540,97 +701,108 @@ pub fn BulkSendModal(
                                 }
                             };
 
-                            if !sending() {
-                                sending.set(true);
-                                error_message.set(None);
-                                
-                                // Show hardware approval overlay if using hardware wallet
-                                if hardware_wallet.is_some() {
-                                    show_hardware_approval.set(true);
-                                    was_hardware_transaction.set(true);
-                                } else {
-                                    was_hardware_transaction.set(false);
+                            if let Some(w) = &wallet {
+                                if let Err(e) = crate::policy::check_transfer(&w.address, &recipient_pubkey.to_string()) {
+                                    error_message.set(Some(e));
+                                    return;
                                 }
-                                
-                                // Clone values for async task
-                                let hardware_wallet_clone = hardware_wallet.clone();
-                                let wallet_info = wallet.clone();
-                                let recipient_address = recipient_pubkey.to_string(); // ← USE RESOLVED PUBKEY
-                                let rpc_url = custom_rpc.clone();
-                                let selected_for_send: Vec<SelectedTokenForBulkSend> = selected_tokens()
-                                    .iter()
-                                    .filter_map(|token| {
-                                        token_amounts().get(&token.mint)
-                                            .and_then(|amount_str| amount_str.parse::<f64>().ok())
-                                            .map(|amount| SelectedTokenForBulkSend { token: token.clone(), amount })
-                                    })
-                                    .collect();
-                                
-                                spawn(async move {
-                                    // ← NO NEED TO VALIDATE recipient_address anymore since it's already a valid pubkey!
-                                
-                                    println!("Sending bulk transaction with {} tokens to {}", selected_for_send.len(), recipient_address);
-                                    for item in &selected_for_send {
-                                        println!("  {} {} ({})", item.amount, item.token.symbol, item.token.mint);
-                                    }
-                                    
-                                    let client = TransactionClient::new(rpc_url.as_deref());
-                                
-                                    // Determine signer type based on available wallet
-                                    let result = if let Some(ref hw) = hardware_wallet_clone {
-                                        // Use hardware wallet signer
-                                        let hw_signer = HardwareSigner::from_wallet(hw.clone());
-                                        client.send_bulk_tokens_with_signer(&hw_signer, &recipient_address, selected_for_send).await
-                                    } else if let Some(wallet_info) = wallet_info {
-                                        // Use software wallet signer
-                                        match Wallet::from_wallet_info(&wallet_info) {
-                                            Ok(wallet) => {
-                                                let signer = SignerType::from_wallet(wallet);
-                                                client.send_bulk_tokens_with_signer(&signer, &recipient_address, selected_for_send).await
-                                            }
-                                            Err(e) => {
-                                                error_message.set(Some(format!("Failed to load wallet: {}", e)));
-                                                sending.set(false);
-                                                show_hardware_approval.set(false);
-                                                return;
-                                            }
-                                        }
-                                    } else {
-                                        error_message.set(Some("No wallet available".to_string()));
-                                        sending.set(false);
-                                        show_hardware_approval.set(false);
-                                        return;
-                                    };
-                                
-                                    // Handle the transaction result
-                                    match result {
-                                        Ok(signature) => {
-                                            println!("Bulk transaction sent successfully: {}", signature);
-
-                                            // Hide hardware approval overlay
-                                            show_hardware_approval.set(false);
-
-                                            // Set the transaction signature and show success modal
-                                            transaction_signature.set(signature);
-                                            sending.set(false);
-                                            show_success_modal.set(true);
-                                        }
+                            }
+
+                            if sending() {
+                                return;
+                            }
+                            let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+                                Ok(signer) => signer,
+                                Err(e) => {
+                                    error_message.set(Some(e));
+                                    return;
+                                }
+                            };
+
+                            let amounts = token_amounts();
+                            let mut transfers = Vec::new();
+                            let mut symbols = Vec::new();
+                            for token in selected_tokens().iter() {
+                                let Some(amount) = amounts.get(&token.mint).and_then(|a| a.parse::<f64>().ok()) else { continue };
+                                let mint = if token.mint == SOL_MINT || token.symbol.eq_ignore_ascii_case("SOL") {
+                                    None
+                                } else {
+                                    match Pubkey::from_str(&token.mint) {
+                                        Ok(mint) => Some(mint),
                                         Err(e) => {
-                                            let error_msg = if e.to_string().contains("too large") {
-                                                format!("Transaction too large. Please reduce the number of tokens or send in smaller batches. Error: {}", e)
-                                            } else if e.to_string().contains("Insufficient") {
-                                                format!("Insufficient balance for transaction fees or token amounts. Error: {}", e)
-                                            } else {
-                                                format!("Transaction failed: {}", e)
-                                            };
-                                            
-                                            error_message.set(Some(error_msg));
-                                            sending.set(false);
-                                            show_hardware_approval.set(false);
+                                            error_message.set(Some(format!("Invalid mint for {}: {}", token.symbol, e)));
+                                            return;
                                         }
                                     }
-                                });
+                                };
+                                transfers.push(RecipientTransfer { recipient: recipient_pubkey, mint, amount });
+                                symbols.push(token.symbol.clone());
                             }
+                            // Sending the whole balance of every selected token sweeps them
+                            let sweep = selected_tokens().iter().all(|token| {
+                                amounts.get(&token.mint).and_then(|a| a.parse::<f64>().ok()) == Some(token.balance)
+                            });
+
+                            sending.set(true);
+                            error_message.set(None);
+                            show_hardware_approval.set(hardware_wallet.is_some());
+                            was_hardware_transaction.set(hardware_wallet.is_some());
+
+                            let rpc_url = custom_rpc.clone();
+                            let memo_text = memo();
+                            let label_text = label();
+                            let pause = pause.clone();
+                            spawn(async move {
+                                println!("Sending {} tokens to {} in a bulk operation", transfers.len(), recipient_pubkey);
+
+                                let planned = async {
+                                    let payer = signer.get_public_key().await.map_err(|e| e.to_string())?;
+                                    let payer_pubkey = Pubkey::from_str(&payer).map_err(|e| e.to_string())?;
+                                    let memo = Some(memo_text.trim()).filter(|m| !m.is_empty());
+                                    let batches = TransactionClient::new(rpc_url.as_deref())
+                                        .plan_transfer_batches(&payer_pubkey, &transfers, memo)
+                                        .await
+                                        .map_err(|e| e.to_string())?;
+
+                                    let mut sent = 0;
+                                    let steps = batches
+                                        .iter()
+                                        .enumerate()
+                                        .map(|(index, batch)| {
+                                            let label = batch
+                                                .iter()
+                                                .zip(&symbols[sent..])
+                                                .map(|(transfer, symbol)| format!("{} {}", transfer.amount, symbol))
+                                                .collect::<Vec<_>>()
+                                                .join(", ");
+                                            sent += batch.len();
+                                            operations::transfer_step(&label, batch, memo.filter(|_| index == 0))
+                                        })
+                                        .collect();
+                                    let (kind, verb) = if sweep { ("sweep", "Sweep") } else { ("bulk_send", "Bulk send") };
+                                    let title = format!("{} to {}", verb, recipient_pubkey);
+                                    Ok::<_, String>(Operation::new(kind, &title, &payer, steps))
+                                };
+
+                                let planned = planned.await;
+                                let result = match planned {
+                                    Ok(planned) => {
+                                        operation.set(Some(planned.clone()));
+                                        run_bulk_operation(planned, signer, rpc_url, pause, label_text, operation).await
+                                    }
+                                    Err(e) => Err(e),
+                                };
+                                if let Err(e) = result {
+                                    let error_msg = if e.contains("Insufficient") {
+                                        format!("Insufficient balance for transaction fees or token amounts. Error: {}", e)
+                                    } else {
+                                        format!("Transaction failed: {}", e)
+                                    };
+                                    error_message.set(Some(error_msg));
+                                }
+                                sending.set(false);
+                                show_hardware_approval.set(false);
+                            });
                         },
                         if sending() {
                             "Sending..."
@@ -77,7 +77,7 @@ fn TransactionSuccessModal(
     asset_symbol: String,
     onclose: EventHandler<()>,
 ) -> Element {
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     let orb_url = format!("https://orb.helius.dev/tx/{}?cluster=mainnet-beta&tab=summary", signature);
     
     rsx! {
@@ -0,0 +1,308 @@
+use dioxus::prelude::*;
+use crate::wallet::WalletInfo;
+use crate::hardware::HardwareWallet;
+use crate::composer::{self, ComposerAction, STAKE_ACCOUNT_SIZE};
+use crate::components::TransactionStatusView;
+use crate::components::simulation_preview::{SimulationPreviewPanel, PreviewState, DrainWarning};
+use crate::domain_resolver::resolve_recipient;
+use crate::signing::active_wallet_signer;
+use crate::transaction::{TransactionClient, MAX_TRANSACTION_ACCOUNTS, PACKET_DATA_SIZE};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Rent exemption of a stake account, used until the RPC answers
+const DEFAULT_STAKE_RENT: u64 = 2_282_880;
+
+fn parse_lamports(amount: &str) -> Result<u64, String> {
+    amount
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| *v > 0.0)
+        .map(|v| (v * 1_000_000_000.0).round() as u64)
+        .ok_or_else(|| "Enter a valid SOL amount".to_string())
+}
+
+/// Stack transfers, memos, stake delegations and tips into one transaction
+#[component]
+pub fn ComposerModal(
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    owner_address: String,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut actions = use_signal(Vec::<ComposerAction>::new);
+    let mut kind = use_signal(|| "transfer".to_string());
+    let mut target = use_signal(String::new);
+    let mut amount = use_signal(String::new);
+    let mut memo = use_signal(String::new);
+    let mut stake_rent = use_signal(|| DEFAULT_STAKE_RENT);
+    let mut adding = use_signal(|| false);
+    let mut sending = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut signature = use_signal(|| None as Option<String>);
+
+    let rpc_for_rent = custom_rpc.clone();
+    use_future(move || {
+        let rpc = rpc_for_rent.clone();
+        async move {
+            if let Ok(rent) = crate::rpc::get_minimum_balance_for_rent_exemption(STAKE_ACCOUNT_SIZE, rpc.as_deref()).await {
+                stake_rent.set(rent);
+            }
+        }
+    });
+
+    let payer = Pubkey::from_str(&owner_address).ok();
+
+    // Simulate the stack before any signature is requested
+    let mut preview_state = use_signal(|| PreviewState::Idle);
+    let mut drain_confirmed = use_signal(|| false);
+    let rpc_for_preview = custom_rpc.clone();
+    use_effect(move || {
+        let stack = actions();
+        let rent = stake_rent();
+        let builder = match payer.map(|payer| composer::compose(&payer, &stack, rent)) {
+            Some(Ok(builder)) if !stack.is_empty() && builder.limits().check().is_ok() => builder,
+            _ => {
+                preview_state.set(PreviewState::Idle);
+                return;
+            }
+        };
+        let rpc = rpc_for_preview.clone();
+        preview_state.set(PreviewState::Loading);
+        drain_confirmed.set(false);
+
+        spawn(async move {
+            let result = TransactionClient::new(rpc.as_deref()).preview_built(&builder).await;
+
+            // Ignore results for a stack the user has since changed
+            if *actions.peek() != stack {
+                return;
+            }
+            match result {
+                Ok(preview) => preview_state.set(PreviewState::Ready(preview)),
+                Err(e) => preview_state.set(PreviewState::Unavailable(e.to_string())),
+            }
+        });
+    });
+    let limits = payer.map(|payer| composer::compose(&payer, &actions(), stake_rent()).map(|b| b.limits()));
+    let total_sol = actions().iter().map(|a| a.outgoing_lamports(stake_rent())).sum::<u64>() as f64 / 1_000_000_000.0;
+    let limit_error = match &limits {
+        Some(Ok(limits)) => limits.check().err(),
+        Some(Err(e)) => Some(e.clone()),
+        None => Some("Invalid wallet address".to_string()),
+    };
+
+    let add_action = move |_| {
+        error_message.set(None);
+        let chosen = kind();
+        adding.set(true);
+        spawn(async move {
+            let action = match chosen.as_str() {
+                "memo" => Ok(ComposerAction::Memo { text: memo().trim().to_string() }),
+                "tip" => parse_lamports(&amount()).map(|lamports| ComposerAction::Tip { lamports }),
+                "stake" => match (Pubkey::from_str(target().trim()), parse_lamports(&amount())) {
+                    (Ok(vote_account), Ok(lamports)) => Ok(ComposerAction::Stake { vote_account, lamports }),
+                    (Err(_), _) => Err("Enter a valid validator vote account".to_string()),
+                    (_, Err(e)) => Err(e),
+                },
+                _ => match parse_lamports(&amount()) {
+                    Ok(lamports) => resolve_recipient(&target())
+                        .await
+                        .map(|resolved| ComposerAction::Transfer { to: resolved.pubkey, lamports }),
+                    Err(e) => Err(e),
+                },
+            };
+            match action {
+                Ok(action) => {
+                    actions.write().push(action);
+                    target.set(String::new());
+                    amount.set(String::new());
+                    memo.set(String::new());
+                }
+                Err(e) => error_message.set(Some(e)),
+            }
+            adding.set(false);
+        });
+    };
+
+    let rpc_for_send = custom_rpc.clone();
+    let send = move |_| {
+        let Some(payer) = payer else { return };
+        let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+            Ok(signer) => signer,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        let builder = match composer::compose(&payer, &actions(), stake_rent()) {
+            Ok(builder) => builder,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        let rpc = rpc_for_send.clone();
+        sending.set(true);
+        error_message.set(None);
+        spawn(async move {
+            let client = TransactionClient::new(rpc.as_deref());
+            match client.send_built(builder, signer.as_ref()).await {
+                Ok(sig) => {
+                    signature.set(Some(sig));
+                    actions.set(Vec::new());
+                }
+                Err(e) => error_message.set(Some(format!("Transaction failed: {}", e))),
+            }
+            sending.set(false);
+        });
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Transaction Composer" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Stack several actions into one transaction. They run top to bottom, and either all of them succeed or none do."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                if let Some(sig) = signature() {
+                    div {
+                        class: "wallet-field",
+                        label { "Sent" }
+                        div { class: "address-display", "{sig}" }
+                        TransactionStatusView { signature: sig.clone(), custom_rpc: custom_rpc.clone() }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Instruction stack:" }
+                    if actions().is_empty() {
+                        div { class: "help-text", "Add an action below to start" }
+                    }
+                    for (index, action) in actions().into_iter().enumerate() {
+                        div {
+                            key: "{index}",
+                            class: "info-message",
+                            "{index + 1}. {action.icon()} {action.description()}"
+                            div { class: "modal-buttons",
+                                button {
+                                    class: "button-standard secondary",
+                                    disabled: index == 0,
+                                    onclick: move |_| composer::move_action(&mut actions.write(), index, true),
+                                    "↑"
+                                }
+                                button {
+                                    class: "button-standard secondary",
+                                    disabled: index + 1 == actions().len(),
+                                    onclick: move |_| composer::move_action(&mut actions.write(), index, false),
+                                    "↓"
+                                }
+                                button {
+                                    class: "button-standard secondary",
+                                    onclick: move |_| {
+                                        actions.write().remove(index);
+                                    },
+                                    "Remove"
+                                }
+                            }
+                        }
+                    }
+                    if let Some(Ok(limits)) = &limits {
+                        div {
+                            class: "help-text",
+                            "Size: {limits.size} / {PACKET_DATA_SIZE} bytes • Accounts: {limits.accounts} / {MAX_TRANSACTION_ACCOUNTS} • Sends {total_sol:.6} SOL plus fees"
+                        }
+                    }
+                    if let Some(error) = &limit_error {
+                        div { class: "error-message", "{error}" }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Add action:" }
+                    select {
+                        onchange: move |e| kind.set(e.value()),
+                        option { value: "transfer", selected: kind() == "transfer", "Transfer SOL" }
+                        option { value: "memo", selected: kind() == "memo", "Memo" }
+                        option { value: "stake", selected: kind() == "stake", "Stake SOL" }
+                        option { value: "tip", selected: kind() == "tip", "Jito tip" }
+                    }
+                    if kind() == "memo" {
+                        input {
+                            value: "{memo}",
+                            oninput: move |e| memo.set(e.value()),
+                            placeholder: "Memo text"
+                        }
+                    } else {
+                        if kind() == "transfer" || kind() == "stake" {
+                            input {
+                                value: "{target}",
+                                oninput: move |e| target.set(e.value()),
+                                placeholder: if kind() == "stake" { "Validator vote account" } else { "Recipient address or .sol domain" }
+                            }
+                        }
+                        input {
+                            r#type: "number",
+                            value: "{amount}",
+                            oninput: move |e| amount.set(e.value()),
+                            placeholder: "Amount (SOL)"
+                        }
+                    }
+                    button {
+                        class: "button-standard secondary",
+                        disabled: adding(),
+                        onclick: add_action,
+                        if adding() { "Resolving..." } else { "Add to Stack" }
+                    }
+                }
+
+                SimulationPreviewPanel { state: preview_state() }
+                DrainWarning {
+                    state: preview_state(),
+                    confirmed: drain_confirmed(),
+                    onchange: move |confirmed| drain_confirmed.set(confirmed),
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: sending() || actions().is_empty() || limit_error.is_some() || (preview_state.read().needs_confirmation() && !drain_confirmed()),
+                        onclick: send,
+                        if sending() { "Sending..." } else { "Sign & Send" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use dioxus::prelude::*;
+use crate::cost_basis::{self, CostBasisReport};
+use crate::storage::{load_cost_basis, save_export_file};
+use std::collections::HashMap;
+
+fn signed_usd(value: f64) -> String {
+    if value >= 0.0 {
+        format!("+${:.2}", value)
+    } else {
+        format!("-${:.2}", -value)
+    }
+}
+
+/// Average cost and realized/unrealized P&L per asset, rebuilt from the
+/// wallet's history, with CSV export
+#[component]
+pub fn CostBasisModal(
+    address: String,
+    custom_rpc: Option<String>,
+    /// Current USD prices by mint
+    usd_prices: HashMap<String, f64>,
+    oncomputed: EventHandler<CostBasisReport>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let stored = load_cost_basis(&address);
+    let mut report = use_signal(move || stored);
+    let mut working = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status = use_signal(|| None as Option<String>);
+
+    let compute = {
+        let address = address.clone();
+        move |_| {
+            let address = address.clone();
+            let rpc = custom_rpc.clone();
+            working.set(true);
+            error_message.set(None);
+            status.set(None);
+            spawn(async move {
+                match cost_basis::compute_cost_basis(&address, rpc.as_deref()).await {
+                    Ok(computed) => {
+                        oncomputed.call(computed.clone());
+                        report.set(Some(computed));
+                    }
+                    Err(e) => error_message.set(Some(e)),
+                }
+                working.set(false);
+            });
+        }
+    };
+
+    let export = {
+        let usd_prices = usd_prices.clone();
+        move |_| {
+            let Some(current) = report() else { return };
+            let file_name = format!(
+                "cost_basis_{}_{}.csv",
+                &current.wallet[..current.wallet.len().min(8)],
+                chrono::Utc::now().format("%Y%m%d")
+            );
+            match save_export_file(&file_name, &current.to_csv(&usd_prices)) {
+                Ok(path) => status.set(Some(format!("Saved to {}", path))),
+                Err(e) => error_message.set(Some(e)),
+            }
+        }
+    };
+
+    let summary = report().map(|current| {
+        let computed = chrono::DateTime::from_timestamp(current.computed_at, 0)
+            .map(|d| d.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_default();
+        let coverage = if current.complete { "full history" } else { "most recent history only" };
+        format!(
+            "{} transaction(s), {}, computed {}. Realized: {}",
+            current.transactions,
+            coverage,
+            computed,
+            signed_usd(current.total_realized_usd())
+        )
+    });
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Cost Basis & P&L" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Average cost per asset from this wallet's transaction history, priced at each day's USD close. Unrealized P&L uses current prices."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(message) = status() {
+                    div { class: "info-message", "{message}" }
+                }
+
+                if let Some(current) = report() {
+                    for position in current.positions.iter().filter(|p| p.quantity > 0.0 || p.realized_usd != 0.0) {
+                        {
+                            let average = position
+                                .average_cost()
+                                .map(|cost| format!("avg ${:.4}", cost))
+                                .unwrap_or_else(|| "closed".to_string());
+                            let unrealized = usd_prices
+                                .get(&position.asset)
+                                .map(|price| format!("unrealized {}", signed_usd(position.unrealized_usd(*price))))
+                                .unwrap_or_else(|| "no current price".to_string());
+                            let realized = signed_usd(position.realized_usd);
+                            rsx! {
+                                div {
+                                    key: "{position.asset}",
+                                    class: "wallet-field",
+                                    label { "{position.symbol}" }
+                                    div { class: "info-message", "{position.quantity:.6} held, {average}, {unrealized}, realized {realized}" }
+                                    if position.is_partial() {
+                                        div { class: "help-text", "Partial: some of this asset predates the known history or couldn't be priced" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if let Some(summary) = summary {
+                        div { class: "help-text", "{summary}" }
+                    }
+                }
+
+                if working() {
+                    div { class: "help-text", "Reading history and prices... this can take a while for busy wallets." }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        disabled: report().is_none(),
+                        onclick: export,
+                        "Export CSV"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: working(),
+                        onclick: compute,
+                        if working() { "Computing..." } else if report().is_some() { "Recompute" } else { "Compute" }
+                    }
+                }
+            }
+        }
+    }
+}
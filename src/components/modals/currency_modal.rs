@@ -6,6 +6,7 @@ use crate::currency::{
     EXCHANGE_RATES,
     save_currency_to_storage,
     fetch_exchange_rates,
+    is_crypto_currency,
     CurrencyInfo
 };
 
@@ -96,6 +97,12 @@ pub fn CurrencyModal(onclose: EventHandler<()>) -> Element {
                         {
                             let is_selected = currency.code == current_currency;
                             let rate = exchange_rates.get(&currency.code).unwrap_or(&1.0);
+                            // Crypto rates read better as the asset's USD price
+                            let rate_label = if is_crypto_currency(&currency.code) {
+                                format!("1 {} = ${:.2}", currency.code, 1.0 / rate)
+                            } else {
+                                format!("1 USD = {:.4} {}", rate, currency.code)
+                            };
                             let currency_code = currency.code.clone();
                             
                             rsx! {
@@ -129,7 +136,7 @@ pub fn CurrencyModal(onclose: EventHandler<()>) -> Element {
                                         } else {
                                             span { 
                                                 class: "rate-value",
-                                                "1 USD = {rate:.4} {currency.code}"
+                                                "{rate_label}"
                                             }
                                         }
                                     }
@@ -0,0 +1,343 @@
+use dioxus::prelude::*;
+use crate::components::common::Token;
+use crate::hardware::HardwareWallet;
+use crate::signing::session::{SessionAction, SessionSigner};
+use crate::signing::active_wallet_signer;
+use crate::storage::{load_dca_schedules, save_dca_schedules};
+use crate::swap::dca::{self, DCA_INTERVALS};
+use crate::swap::DcaSchedule;
+use crate::wallet::WalletInfo;
+use std::sync::Arc;
+
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|d| d.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default()
+}
+
+fn interval_label(seconds: i64) -> String {
+    DCA_INTERVALS
+        .iter()
+        .find(|(_, interval)| *interval == seconds)
+        .map(|(label, _)| label.to_string())
+        .unwrap_or_else(|| format!("Every {} s", seconds))
+}
+
+/// Create and manage DCA schedules: a fixed amount of one token swapped into
+/// another at a fixed interval, with the history of executed buys
+#[component]
+pub fn DcaModal(
+    tokens: Vec<Token>,
+    address: String,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let first_symbol = tokens.iter().find(|t| t.symbol == "SOL").or(tokens.first()).map(|t| t.symbol.clone()).unwrap_or_default();
+    let mut selling = use_signal(move || first_symbol);
+    let mut buying = use_signal(String::new);
+    let mut amount = use_signal(String::new);
+    let mut interval = use_signal(|| 1usize);
+    let mut buy_count = use_signal(|| "10".to_string());
+    let mut schedules = use_signal({
+        let address = address.clone();
+        move || load_dca_schedules(&address)
+    });
+    let mut working = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status = use_signal(|| None as Option<String>);
+
+    let session_active = SessionSigner::for_action(&address, SessionAction::Dca).is_some();
+    let session_note = if session_active {
+        "Your session key allows DCA executions: buys paid in SOL run on their own while the app is open. Other buys ask you to confirm."
+    } else {
+        "Due buys ask you to confirm them. Start a session key that allows DCA executions to run buys paid in SOL on their own."
+    };
+
+    // Store a changed schedule list
+    let persist = {
+        let address = address.clone();
+        move |updated: Vec<DcaSchedule>| match save_dca_schedules(&address, &updated) {
+            Ok(()) => schedules.set(updated),
+            Err(e) => error_message.set(Some(e)),
+        }
+    };
+
+    let create = {
+        let tokens = tokens.clone();
+        let address = address.clone();
+        let mut persist = persist.clone();
+        move |_| {
+            error_message.set(None);
+            status.set(None);
+            let sell = tokens.iter().find(|t| t.symbol == selling());
+            let buy = tokens.iter().find(|t| t.symbol == buying());
+            let (Some(sell), Some(buy)) = (sell, buy) else {
+                error_message.set(Some("Choose the tokens to spend and buy".to_string()));
+                return;
+            };
+            if sell.mint == buy.mint {
+                error_message.set(Some("Choose two different tokens".to_string()));
+                return;
+            }
+            let per_buy = match amount().trim().parse::<f64>() {
+                Ok(value) if value > 0.0 => value,
+                _ => {
+                    error_message.set(Some("Enter the amount to spend per buy".to_string()));
+                    return;
+                }
+            };
+            let total_buys = match buy_count().trim().parse::<u32>() {
+                Ok(count) if count > 0 => count,
+                _ => {
+                    error_message.set(Some("Enter the number of buys".to_string()));
+                    return;
+                }
+            };
+            let now = chrono::Utc::now().timestamp();
+            let schedule = DcaSchedule {
+                id: format!("dca-{}-{}", now, rand::random::<u32>()),
+                owner: address.clone(),
+                input_mint: sell.mint.clone(),
+                output_mint: buy.mint.clone(),
+                input_symbol: sell.symbol.clone(),
+                output_symbol: buy.symbol.clone(),
+                input_decimals: sell.decimals,
+                output_decimals: buy.decimals,
+                amount_per_buy: (per_buy * 10_f64.powi(sell.decimals as i32)).round() as u64,
+                interval_secs: DCA_INTERVALS[interval()].1,
+                total_buys,
+                next_buy_at: now,
+                paused: false,
+                created_at: now,
+                buys: Vec::new(),
+            };
+            let mut updated = schedules();
+            updated.insert(0, schedule);
+            persist(updated);
+            amount.set(String::new());
+            status.set(Some(format!("Scheduled {} buys of {} with {} {} each", total_buys, buy.symbol, per_buy, sell.symbol)));
+        }
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    let symbols: Vec<String> = tokens.iter().map(|t| t.symbol.clone()).collect();
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "DCA" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div { class: "help-text", "{session_note}" }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(message) = status() {
+                    div { class: "info-message", "{message}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Spend per buy:" }
+                    select {
+                        onchange: move |e| selling.set(e.value()),
+                        for symbol in symbols.iter() {
+                            option { key: "{symbol}", value: "{symbol}", selected: *symbol == selling(), "{symbol}" }
+                        }
+                    }
+                    input {
+                        r#type: "text",
+                        value: "{amount}",
+                        placeholder: "Amount",
+                        oninput: move |e| amount.set(e.value()),
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Buy:" }
+                    select {
+                        onchange: move |e| buying.set(e.value()),
+                        option { value: "", selected: buying().is_empty(), "Select token" }
+                        for symbol in symbols.iter() {
+                            option { key: "{symbol}", value: "{symbol}", selected: *symbol == buying(), "{symbol}" }
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Every:" }
+                    select {
+                        onchange: move |e| interval.set(e.value().parse().unwrap_or(1)),
+                        for (index, (label, _)) in DCA_INTERVALS.iter().enumerate() {
+                            option { key: "{index}", value: "{index}", selected: index == interval(), "{label}" }
+                        }
+                    }
+                    label { "Number of buys:" }
+                    input {
+                        r#type: "number",
+                        value: "{buy_count}",
+                        oninput: move |e| buy_count.set(e.value()),
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        onclick: create,
+                        "Create Schedule"
+                    }
+                }
+
+                for (index, schedule) in schedules().into_iter().enumerate() {
+                    {
+                        let scale = 10_f64.powi(schedule.input_decimals as i32);
+                        let title = format!(
+                            "{} {} → {}, {}",
+                            schedule.amount_per_buy as f64 / scale,
+                            schedule.input_symbol,
+                            schedule.output_symbol,
+                            interval_label(schedule.interval_secs).to_lowercase()
+                        );
+                        let progress = if schedule.is_complete() {
+                            format!("Completed {} buys", schedule.total_buys)
+                        } else if schedule.paused {
+                            format!("{} of {} buys, paused", schedule.buys.len(), schedule.total_buys)
+                        } else if schedule.is_due(now) {
+                            format!("{} of {} buys, next buy is due", schedule.buys.len(), schedule.total_buys)
+                        } else {
+                            format!(
+                                "{} of {} buys, next {}",
+                                schedule.buys.len(),
+                                schedule.total_buys,
+                                format_time(schedule.next_buy_at)
+                            )
+                        };
+                        let average = schedule
+                            .average_price()
+                            .map(|price| format!("Average price: {:.6} {} per {}", price, schedule.input_symbol, schedule.output_symbol))
+                            .unwrap_or_default();
+                        let history: Vec<String> = schedule
+                            .buys
+                            .iter()
+                            .rev()
+                            .map(|buy| {
+                                let by = if buy.by_session_key { " (session key)" } else { "" };
+                                format!(
+                                    "{}: {} {} → {:.6} {}{}",
+                                    format_time(buy.at),
+                                    buy.spent,
+                                    schedule.input_symbol,
+                                    buy.received,
+                                    schedule.output_symbol,
+                                    by
+                                )
+                            })
+                            .collect();
+                        let complete = schedule.is_complete();
+                        let paused = schedule.paused;
+                        let hardware_wallet = hardware_wallet.clone();
+                        let wallet = wallet.clone();
+                        let custom_rpc = custom_rpc.clone();
+                        let address = address.clone();
+                        let mut pause_persist = persist.clone();
+                        let mut delete_persist = persist.clone();
+                        rsx! {
+                            div {
+                                key: "{schedule.id}",
+                                class: "wallet-field",
+                                div { class: "info-message", "{title}" }
+                                div { class: "help-text", "{progress}" }
+                                if !average.is_empty() {
+                                    div { class: "help-text", "{average}" }
+                                }
+                                div { class: "modal-buttons",
+                                    if !complete {
+                                        button {
+                                            class: "button-standard primary",
+                                            disabled: working(),
+                                            onclick: {
+                                                let schedule = schedule.clone();
+                                                move |_| {
+                                                    let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+                                                        Ok(signer) => signer,
+                                                        Err(e) => {
+                                                            error_message.set(Some(e));
+                                                            return;
+                                                        }
+                                                    };
+                                                    let schedule = schedule.clone();
+                                                    let custom_rpc = custom_rpc.clone();
+                                                    let address = address.clone();
+                                                    working.set(true);
+                                                    error_message.set(None);
+                                                    spawn(async move {
+                                                        match dca::execute_buy(&schedule, &*signer, false, custom_rpc.as_deref()).await {
+                                                            Ok(buy) => {
+                                                                status.set(Some(format!("Bought {:.6} {}", buy.received, schedule.output_symbol)));
+                                                                match dca::record_buy(&address, &schedule.id, buy) {
+                                                                    Ok(()) => schedules.set(load_dca_schedules(&address)),
+                                                                    Err(e) => error_message.set(Some(e)),
+                                                                }
+                                                            }
+                                                            Err(e) => error_message.set(Some(format!("DCA buy failed: {}", e))),
+                                                        }
+                                                        working.set(false);
+                                                    });
+                                                }
+                                            },
+                                            "Buy Now"
+                                        }
+                                        button {
+                                            class: "button-standard secondary",
+                                            onclick: move |_| {
+                                                let mut updated = schedules();
+                                                updated[index].paused = !paused;
+                                                pause_persist(updated);
+                                            },
+                                            if paused { "Resume" } else { "Pause" }
+                                        }
+                                    }
+                                    button {
+                                        class: "button-standard secondary",
+                                        onclick: move |_| {
+                                            let mut updated = schedules();
+                                            updated.remove(index);
+                                            delete_persist(updated);
+                                        },
+                                        "Delete"
+                                    }
+                                }
+                                for (line_index, line) in history.into_iter().enumerate() {
+                                    div { key: "{line_index}", class: "help-text", "{line}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
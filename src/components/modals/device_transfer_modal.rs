@@ -0,0 +1,338 @@
+use dioxus::prelude::*;
+use crate::qr_transfer::{encode_frames, FrameAssembler, TransferKind};
+use crate::signing::partial::{decode_transaction, signer_statuses};
+use crate::wallet::WalletInfo;
+use qrcode::{QrCode, render::svg};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Time each frame of an animated code stays on screen
+const FRAME_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Opens the camera inside the webview and reports every QR code it sees.
+/// Relies on the built-in BarcodeDetector; sends "!error" when unavailable.
+pub const CAMERA_SCANNER_JS: &str = r#"
+    if (!('BarcodeDetector' in window) || !navigator.mediaDevices) {
+        dioxus.send("!error:Camera scanning is not supported on this device. Paste the codes instead.");
+        return;
+    }
+    const video = document.createElement('video');
+    video.setAttribute('playsinline', '');
+    video.style.cssText = 'position:fixed;bottom:16px;right:16px;width:200px;border-radius:12px;z-index:10000;';
+    document.body.appendChild(video);
+    let stream;
+    try {
+        stream = await navigator.mediaDevices.getUserMedia({ video: { facingMode: 'environment' } });
+    } catch (e) {
+        video.remove();
+        dioxus.send("!error:Camera access was denied.");
+        return;
+    }
+    video.srcObject = stream;
+    await video.play();
+    const detector = new BarcodeDetector({ formats: ['qr_code'] });
+    let running = true;
+    (async () => { await dioxus.recv(); running = false; })();
+    let last = '';
+    while (running) {
+        try {
+            for (const code of await detector.detect(video)) {
+                if (code.rawValue !== last) {
+                    last = code.rawValue;
+                    dioxus.send(code.rawValue);
+                }
+            }
+        } catch (e) {}
+        await new Promise(r => setTimeout(r, 150));
+    }
+    stream.getTracks().forEach(t => t.stop());
+    video.remove();
+"#;
+
+fn qr_code_svg(data: &str) -> Option<String> {
+    QrCode::new(data).ok().map(|qr| {
+        qr.render()
+            .min_dimensions(260, 260)
+            .dark_color(svg::Color("#000000"))
+            .light_color(svg::Color("#ffffff"))
+            .build()
+    })
+}
+
+/// Check that a received payload is what it claims to be
+fn validate_payload(kind: TransferKind, payload: &str) -> Result<Vec<String>, String> {
+    match kind {
+        TransferKind::Address => Pubkey::from_str(payload)
+            .map(|_| vec![])
+            .map_err(|_| "Received an invalid address".to_string()),
+        TransferKind::Transaction => decode_transaction(payload)
+            .map(|tx| {
+                signer_statuses(&tx)
+                    .iter()
+                    .map(|s| format!("{} {}", if s.signed { "✅" } else { "⏳" }, s.pubkey))
+                    .collect()
+            })
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Show an address or unsigned transaction as (animated) QR codes, or scan
+/// them from another of my devices
+#[component]
+pub fn DeviceTransferModal(wallet: Option<WalletInfo>, onclose: EventHandler<()>) -> Element {
+    let mut scan_mode = use_signal(|| false);
+    let mut send_kind = use_signal(|| TransferKind::Address);
+    let mut payload = use_signal(|| wallet.as_ref().map(|w| w.address.clone()).unwrap_or_default());
+    let mut frames = use_signal(|| Vec::<String>::new());
+    let mut frame_index = use_signal(|| 0usize);
+    let mut assembler = use_signal(FrameAssembler::new);
+    let mut manual_frame = use_signal(|| String::new());
+    let mut scanning = use_signal(|| false);
+    let mut stop_camera = use_signal(|| None as Option<document::Eval>);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    // Cycle through the frames of a multi-part code
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(FRAME_INTERVAL).await;
+            let count = frames.peek().len();
+            if count > 1 {
+                frame_index.set((*frame_index.peek() + 1) % count);
+            }
+        }
+    });
+
+    let mut add_frame = move |frame: String| {
+        let result = assembler.write().add(&frame);
+        match result {
+            Ok(()) => error_message.set(None),
+            Err(e) => error_message.set(Some(e)),
+        }
+    };
+
+    let mut stop_scanning = move || {
+        if let Some(eval) = stop_camera.write().take() {
+            let _ = eval.send(true);
+        }
+        scanning.set(false);
+    };
+
+    let (received, total) = assembler().progress();
+    let finished = assembler().finish();
+    let wallet_address = wallet.as_ref().map(|w| w.address.clone());
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| {
+                stop_scanning();
+                onclose.call(());
+            },
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Device Transfer" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| {
+                            stop_scanning();
+                            onclose.call(());
+                        },
+                        "×"
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: if !scan_mode() { "button-standard primary" } else { "button-standard secondary" },
+                        onclick: move |_| {
+                            stop_scanning();
+                            scan_mode.set(false);
+                        },
+                        "Show"
+                    }
+                    button {
+                        class: if scan_mode() { "button-standard primary" } else { "button-standard secondary" },
+                        onclick: move |_| {
+                            frames.set(Vec::new());
+                            scan_mode.set(true);
+                        },
+                        "Scan"
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                if !scan_mode() {
+                    if frames().is_empty() {
+                        div { class: "modal-buttons",
+                            button {
+                                class: if send_kind() == TransferKind::Address { "button-standard primary" } else { "button-standard secondary" },
+                                onclick: move |_| {
+                                    send_kind.set(TransferKind::Address);
+                                    payload.set(wallet_address.clone().unwrap_or_default());
+                                },
+                                "Address"
+                            }
+                            button {
+                                class: if send_kind() == TransferKind::Transaction { "button-standard primary" } else { "button-standard secondary" },
+                                onclick: move |_| {
+                                    send_kind.set(TransferKind::Transaction);
+                                    payload.set(String::new());
+                                },
+                                "Transaction"
+                            }
+                        }
+                        div {
+                            class: "wallet-field",
+                            label { "{send_kind().label()}:" }
+                            textarea {
+                                value: "{payload}",
+                                rows: if send_kind() == TransferKind::Address { "2" } else { "5" },
+                                oninput: move |e| payload.set(e.value()),
+                                placeholder: if send_kind() == TransferKind::Address { "Address to share" } else { "Unsigned transaction (base64 or base58)" }
+                            }
+                        }
+                        div { class: "modal-buttons",
+                            button {
+                                class: "button-standard primary",
+                                disabled: payload().trim().is_empty(),
+                                onclick: move |_| {
+                                    let text: String = payload().chars().filter(|c| !c.is_whitespace()).collect();
+                                    match validate_payload(send_kind(), &text) {
+                                        Ok(_) => {
+                                            error_message.set(None);
+                                            frame_index.set(0);
+                                            frames.set(encode_frames(send_kind(), &text));
+                                        }
+                                        Err(e) => error_message.set(Some(e)),
+                                    }
+                                },
+                                "Show QR"
+                            }
+                        }
+                    } else {
+                        if let Some(svg) = frames().get(frame_index()).and_then(|frame| qr_code_svg(frame)) {
+                            div {
+                                class: "qr-code-container",
+                                dangerous_inner_html: "{svg}"
+                            }
+                        }
+                        if frames().len() > 1 {
+                            div { class: "help-text", "Frame {frame_index() + 1} of {frames().len()}. Keep the other device pointed at the screen until it has every frame." }
+                        } else {
+                            div { class: "help-text", "Scan this code from the Device Transfer screen on your other device." }
+                        }
+                        div { class: "modal-buttons",
+                            button {
+                                class: "button-standard secondary",
+                                onclick: move |_| frames.set(Vec::new()),
+                                "Done"
+                            }
+                        }
+                    }
+                } else {
+                    match finished.clone() {
+                        Some(Ok((kind, data))) => {
+                            let details = validate_payload(kind, &data);
+                            rsx! {
+                                div { class: "info-message", "✅ Received {kind.label().to_lowercase()}" }
+                                div {
+                                    class: "wallet-field",
+                                    textarea {
+                                        value: "{data}",
+                                        rows: if kind == TransferKind::Address { "2" } else { "5" },
+                                        readonly: true,
+                                    }
+                                }
+                                match details {
+                                    Ok(lines) => rsx! {
+                                        if kind == TransferKind::Transaction {
+                                            div { class: "help-text", "Required signers (sign it from Co-sign Transaction):" }
+                                        }
+                                        for line in lines {
+                                            div { class: "help-text", "{line}" }
+                                        }
+                                    },
+                                    Err(e) => rsx! { div { class: "error-message", "{e}" } },
+                                }
+                            }
+                        }
+                        Some(Err(e)) => rsx! { div { class: "error-message", "{e}" } },
+                        None => rsx! {
+                            if total > 0 {
+                                div { class: "info-message", "Received {received} of {total} frames" }
+                            }
+                            div { class: "modal-buttons",
+                                button {
+                                    class: "button-standard primary",
+                                    disabled: scanning(),
+                                    onclick: move |_| {
+                                        scanning.set(true);
+                                        error_message.set(None);
+                                        let mut eval = document::eval(CAMERA_SCANNER_JS);
+                                        stop_camera.set(Some(eval));
+                                        spawn(async move {
+                                            while let Ok(frame) = eval.recv::<String>().await {
+                                                if let Some(reason) = frame.strip_prefix("!error:") {
+                                                    error_message.set(Some(reason.to_string()));
+                                                    break;
+                                                }
+                                                add_frame(frame);
+                                                if assembler.peek().finish().is_some() {
+                                                    let _ = eval.send(true);
+                                                    break;
+                                                }
+                                            }
+                                            stop_camera.set(None);
+                                            scanning.set(false);
+                                        });
+                                    },
+                                    if scanning() { "Scanning..." } else { "Start Camera" }
+                                }
+                            }
+                            div {
+                                class: "wallet-field",
+                                label { "Or enter a scanned code:" }
+                                input {
+                                    value: "{manual_frame}",
+                                    oninput: move |e| manual_frame.set(e.value()),
+                                    placeholder: "UNRUG:..."
+                                }
+                            }
+                            div { class: "modal-buttons",
+                                button {
+                                    class: "button-standard secondary",
+                                    disabled: manual_frame().trim().is_empty(),
+                                    onclick: move |_| {
+                                        add_frame(manual_frame());
+                                        manual_frame.set(String::new());
+                                    },
+                                    "Add Code"
+                                }
+                            }
+                        },
+                    }
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| {
+                                stop_scanning();
+                                assembler.set(FrameAssembler::new());
+                                error_message.set(None);
+                            },
+                            "Start Over"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use dioxus::prelude::*;
+use crate::storage::{has_duress_pin, load_duress_wallet_addresses, remove_duress_pin, save_duress_pin};
+use crate::wallet::WalletInfo;
+
+/// Set a second PIN that unlocks a decoy profile holding only the picked
+/// wallets, for handing over under coercion
+#[component]
+pub fn DuressPinModal(wallets: Vec<WalletInfo>, onclose: EventHandler<()>) -> Element {
+    let mut is_set = use_signal(has_duress_pin);
+    let mut decoys = use_signal(load_duress_wallet_addresses);
+    let mut pin = use_signal(String::new);
+    let mut confirm_pin = use_signal(String::new);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status_message = use_signal(|| None as Option<String>);
+
+    // Only wallets with a key on this device can go in the decoy profile
+    let software_wallets: Vec<WalletInfo> = wallets.into_iter().filter(|w| !w.encrypted_key.is_empty()).collect();
+
+    let save = move |_| {
+        let entered = pin();
+        if entered.len() != 6 || !entered.chars().all(|c| c.is_ascii_digit()) {
+            error_message.set(Some("The duress PIN must be 6 digits".to_string()));
+            return;
+        }
+        if entered != confirm_pin() {
+            error_message.set(Some("PINs don't match".to_string()));
+            return;
+        }
+        match save_duress_pin(&entered, &decoys()) {
+            Ok(()) => {
+                is_set.set(true);
+                error_message.set(None);
+                status_message.set(Some("Duress PIN saved".to_string()));
+            }
+            Err(e) => error_message.set(Some(e)),
+        }
+        pin.set(String::new());
+        confirm_pin.set(String::new());
+    };
+
+    let remove = move |_| match remove_duress_pin() {
+        Ok(()) => {
+            is_set.set(false);
+            decoys.set(Vec::new());
+            error_message.set(None);
+            status_message.set(Some("Duress PIN removed".to_string()));
+        }
+        Err(e) => error_message.set(Some(e)),
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Duress PIN" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(status) = status_message() {
+                    div { class: "info-message", "{status}" }
+                }
+
+                div {
+                    class: "help-text",
+                    "Entering the duress PIN at the lock screen opens a decoy profile with only the wallets picked here. Keep small amounts in them so handing the app over looks real."
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Decoy wallets:" }
+                    for wallet in software_wallets {
+                        {
+                            let address = wallet.address.clone();
+                            let checked = decoys().contains(&address);
+                            rsx! {
+                                label {
+                                    key: "{wallet.address}",
+                                    class: "help-text",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: checked,
+                                        oninput: move |_| decoys.with_mut(|list| {
+                                            if let Some(at) = list.iter().position(|a| *a == address) {
+                                                list.remove(at);
+                                            } else {
+                                                list.push(address.clone());
+                                            }
+                                        }),
+                                    }
+                                    " {wallet.name}"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { if is_set() { "New duress PIN:" } else { "Duress PIN:" } }
+                    input {
+                        r#type: "password",
+                        inputmode: "numeric",
+                        maxlength: "6",
+                        value: "{pin}",
+                        placeholder: "6 digits, different from your PIN",
+                        oninput: move |e| pin.set(e.value()),
+                    }
+                }
+                div {
+                    class: "wallet-field",
+                    label { "Confirm duress PIN:" }
+                    input {
+                        r#type: "password",
+                        inputmode: "numeric",
+                        maxlength: "6",
+                        value: "{confirm_pin}",
+                        oninput: move |e| confirm_pin.set(e.value()),
+                    }
+                }
+                div {
+                    class: "help-text",
+                    "Saving copies the picked wallets' current keys into the decoy profile. Save again after changing which wallets it holds."
+                }
+
+                div { class: "modal-buttons",
+                    if is_set() {
+                        button {
+                            class: "button-standard secondary",
+                            onclick: remove,
+                            "Remove Duress PIN"
+                        }
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: pin().is_empty() || decoys().is_empty(),
+                        onclick: save,
+                        "Save"
+                    }
+                }
+            }
+        }
+    }
+}
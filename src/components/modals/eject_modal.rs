@@ -749,7 +749,7 @@ fn EjectProcessingModal(
                 div {
                     style: "display: flex; gap: 8px;",
                     a {
-                        href: format!("https://solscan.io/tx/{}", final_signature),
+                        href: crate::config::network::explorer_tx_url(&final_signature),
                         target: "_blank",
                         style: "
                             flex: 1;
@@ -912,7 +912,7 @@ pub fn EjectSuccessModal(
     was_hardware_wallet: bool,
     onclose: EventHandler<()>,
 ) -> Element {
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     let orb_url = format!("https://orb.helius.dev/tx/{}?cluster=mainnet-beta&tab=summary", signature);
 
     rsx! {
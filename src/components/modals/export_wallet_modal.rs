@@ -2,7 +2,8 @@
 // Replace the class name to match your existing modals
 
 use dioxus::prelude::*;
-use crate::wallet::WalletInfo;
+use crate::wallet::{Wallet, WalletInfo};
+use crate::components::{KeyFilesPanel, KeySharesPanel, PaperBackupPanel};
 
 #[component]
 pub fn ExportWalletModal(
@@ -10,6 +11,10 @@ pub fn ExportWalletModal(
     onclose: EventHandler<()>
 ) -> Element {
     let mut show_private_key = use_signal(|| false);
+    // Stored keys may be sealed under the PIN
+    let private_key = wallet
+        .as_ref()
+        .map(|info| Wallet::from_wallet_info(info).map(|w| w.get_private_key()));
     
     rsx! {
         div { class: "modal-backdrop",  // CHANGED: from "modal-overlay" to "modal-backdrop"
@@ -52,8 +57,14 @@ pub fn ExportWalletModal(
                                         "Show Private Key"
                                     }
                                 } else {
-                                    div { class: "private-key-display", 
-                                        "{wallet_info.encrypted_key}"
+                                    match &private_key {
+                                        Some(Ok(key)) => rsx! {
+                                            div { class: "private-key-display", "{key}" }
+                                        },
+                                        Some(Err(e)) => rsx! {
+                                            div { class: "error-message", "{e}" }
+                                        },
+                                        None => rsx! {},
                                     }
                                     div { 
                                         class: "key-format-info",
@@ -63,6 +74,11 @@ pub fn ExportWalletModal(
                                         class: "copy-hint",
                                         "Make sure to copy this key to a secure location!"
                                     }
+                                    if let Some(Ok(key)) = private_key.clone() {
+                                        KeyFilesPanel { private_key: key.clone() }
+                                        KeySharesPanel { private_key: key.clone() }
+                                        PaperBackupPanel { private_key: key, name: wallet_info.name.clone() }
+                                    }
                                 }
                             }
                         }
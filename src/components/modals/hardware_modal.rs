@@ -1,6 +1,7 @@
 // src/components/modals/hardware_modal.rs
 use dioxus::prelude::*;
 use crate::hardware::{HardwareWallet, HardwareDeviceInfo, HardwareDeviceType};
+use crate::storage::{load_usb_device_grants, remove_usb_device_grant};
 use std::sync::Arc;
 
 // Define the assets for device icons - local assets
@@ -26,6 +27,7 @@ pub fn HardwareWalletModal(
     let mut device_type = use_signal(|| None as Option<HardwareDeviceType>);
     let mut available_devices = use_signal(|| Vec::<HardwareDeviceInfo>::new());
     let mut scanning = use_signal(|| false);
+    let mut remembered_devices = use_signal(load_usb_device_grants);
     
     // Store if we have an existing wallet
     let has_existing_wallet = existing_wallet.is_some();
@@ -259,6 +261,38 @@ pub fn HardwareWalletModal(
                                 }
                             }
                         }
+
+
+                        // USB devices granted before are reattached automatically
+                        if !remembered_devices().is_empty() {
+                            div {
+                                class: "devices-section",
+                                h4 { class: "devices-title", "Remembered Devices" }
+                                for grant in remembered_devices() {
+                                    div {
+                                        key: "{grant.label()}",
+                                        class: "wallet-field",
+                                        div { class: "device-name", "{grant.label()}" }
+                                        if let Some(key) = grant.public_key.clone() {
+                                            div { class: "help-text", "{key}" }
+                                        }
+                                        button {
+                                            class: "button-standard secondary",
+                                            onclick: {
+                                                let grant = grant.clone();
+                                                move |_| {
+                                                    if let Err(e) = remove_usb_device_grant(&grant) {
+                                                        error_message.set(Some(e));
+                                                    }
+                                                    remembered_devices.set(load_usb_device_grants());
+                                                }
+                                            },
+                                            "Forget"
+                                        }
+                                    }
+                                }
+                            }
+                        }
                         
                     } else {
                         // Connected state - show wallet info and options
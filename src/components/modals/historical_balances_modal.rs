@@ -0,0 +1,175 @@
+use dioxus::prelude::*;
+use crate::balance_history::{self, HistoricalBalances, ValuedBalance};
+use crate::wallet::WalletInfo;
+
+#[derive(Debug, Clone, PartialEq)]
+struct WalletReport {
+    name: String,
+    address: String,
+    result: Result<(HistoricalBalances, Vec<ValuedBalance>), String>,
+}
+
+impl WalletReport {
+    fn lines(&self) -> Vec<String> {
+        let Ok((_, valued)) = &self.result else { return Vec::new() };
+        valued
+            .iter()
+            .map(|line| match (line.usd_price, line.usd_value()) {
+                (Some(price), Some(value)) => format!("{:.6} {} × ${:.4} = ${:.2}", line.amount, line.label, price, value),
+                _ => format!("{:.6} {} (no price for that day)", line.amount, line.label),
+            })
+            .collect()
+    }
+
+    fn total_usd(&self) -> Option<f64> {
+        let Ok((_, valued)) = &self.result else { return None };
+        Some(valued.iter().filter_map(|line| line.usd_value()).sum())
+    }
+}
+
+/// End of the chosen UTC day, as long as it's in the past
+fn parse_date(date: &str) -> Result<i64, String> {
+    let day = chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| "Choose a date".to_string())?;
+    let as_of = day
+        .and_hms_opt(23, 59, 59)
+        .map(|d| d.and_utc().timestamp())
+        .ok_or("Invalid date")?;
+    if as_of >= chrono::Utc::now().timestamp() {
+        return Err("Choose a day before today".to_string());
+    }
+    Ok(as_of)
+}
+
+/// Rebuild every wallet's balances as of a past date, valued at that day's
+/// closing prices
+#[component]
+pub fn HistoricalBalancesModal(
+    wallets: Vec<WalletInfo>,
+    hardware_address: Option<String>,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let yesterday = (chrono::Utc::now() - chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+    let mut date = use_signal(move || yesterday);
+    let mut reports = use_signal(Vec::<WalletReport>::new);
+    let mut working = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    let mut accounts: Vec<(String, String)> = wallets.iter().map(|w| (w.name.clone(), w.address.clone())).collect();
+    if let Some(address) = hardware_address {
+        if !accounts.iter().any(|(_, a)| *a == address) {
+            accounts.push(("Hardware Wallet".to_string(), address));
+        }
+    }
+
+    let reconstruct = move |_| {
+        let as_of = match parse_date(&date()) {
+            Ok(as_of) => as_of,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        let accounts = accounts.clone();
+        let rpc = custom_rpc.clone();
+        working.set(true);
+        error_message.set(None);
+        reports.set(Vec::new());
+        spawn(async move {
+            for (name, address) in accounts {
+                let result = match balance_history::reconstruct_balances(&address, as_of, rpc.as_deref()).await {
+                    Ok(balances) => {
+                        let valued = balance_history::value_balances(&balances).await;
+                        Ok((balances, valued))
+                    }
+                    Err(e) => Err(e),
+                };
+                reports.write().push(WalletReport { name, address, result });
+            }
+            working.set(false);
+        });
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Balances on Date" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Rebuilds each wallet's balances at the end of the chosen day (UTC) from its transaction history, valued at that day's closing USD prices."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Date:" }
+                    input {
+                        r#type: "date",
+                        value: "{date}",
+                        oninput: move |e| date.set(e.value()),
+                    }
+                }
+
+                for report in reports() {
+                    div {
+                        key: "{report.address}",
+                        class: "wallet-field",
+                        label { "{report.name}" }
+                        div { class: "address-display", "{report.address}" }
+                        match &report.result {
+                            Ok((balances, _)) => rsx! {
+                                for (index, line) in report.lines().into_iter().enumerate() {
+                                    div { key: "{index}", class: "info-message", "{line}" }
+                                }
+                                if let Some(total) = report.total_usd() {
+                                    div { class: "info-message", "Total: ${total:.2}" }
+                                }
+                                div { class: "help-text", "{balances.source_note()}" }
+                            },
+                            Err(e) => rsx! {
+                                div { class: "error-message", "{e}" }
+                            },
+                        }
+                    }
+                }
+
+                if working() {
+                    div { class: "help-text", "Replaying history... this can take a while for busy wallets." }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: working(),
+                        onclick: reconstruct,
+                        if working() { "Reconstructing..." } else { "Reconstruct" }
+                    }
+                }
+            }
+        }
+    }
+}
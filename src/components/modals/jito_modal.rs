@@ -1,10 +1,18 @@
 use dioxus::prelude::*;
-use crate::storage::{save_jito_settings_to_storage, load_jito_settings_from_storage, JitoSettings};
+use crate::storage::{save_jito_settings_to_storage, load_jito_settings_from_storage, load_fee_sponsor_settings, save_fee_sponsor_settings, JitoSettings};
+use crate::signing::fee_payer::FeeSponsorSettings;
+use crate::components::tip_picker::TipPicker;
 
 #[component]
 pub fn JitoModal(current_settings: JitoSettings, onclose: EventHandler<()>, onsave: EventHandler<JitoSettings>) -> Element {
     let mut jito_tx = use_signal(|| current_settings.jito_tx);
     let mut jito_bundles = use_signal(|| current_settings.jito_bundles);
+    let mut tip_level = use_signal(|| current_settings.tip_level);
+    let saved_sponsor = use_hook(load_fee_sponsor_settings);
+    let mut sponsor_enabled = use_signal(|| saved_sponsor.enabled);
+    let mut sponsor_url = use_signal(|| saved_sponsor.url.clone());
+    let mut sponsor_pubkey = use_signal(|| saved_sponsor.payer_pubkey.clone());
+    let mut error_message = use_signal(|| None as Option<String>);
     
     rsx! {
         div {
@@ -91,7 +99,76 @@ pub fn JitoModal(current_settings: JitoSettings, onclose: EventHandler<()>, onsa
                         }
                     }
                 }
+
+                if jito_tx() {
+                    TipPicker {
+                        selected: tip_level(),
+                        onchange: move |level| tip_level.set(level),
+                    }
+                    div {
+                        class: "help-text",
+                        "Default tip for sends and swaps. Percentile tips follow what recently landed transactions paid and can be changed per transaction."
+                    }
+                }
                 
+                div {
+                    class: "toggle-section",
+                    div {
+                        class: "toggle-item",
+                        div {
+                            class: "toggle-item-content",
+                            div {
+                                class: "toggle-label",
+                                "Fee sponsor"
+                            }
+                            div {
+                                class: "toggle-description",
+                                "Have a sponsor service pay network fees and token account rent for sends, so a wallet with no SOL can still move tokens"
+                            }
+                        }
+                        label {
+                            class: "toggle-switch",
+                            input {
+                                r#type: "checkbox",
+                                checked: sponsor_enabled(),
+                                oninput: move |_| sponsor_enabled.set(!sponsor_enabled()),
+                            }
+                            span { class: "toggle-slider" }
+                        }
+                    }
+                }
+
+                if sponsor_enabled() {
+                    div {
+                        class: "wallet-field",
+                        label { "Sponsor URL:" }
+                        input {
+                            value: "{sponsor_url}",
+                            oninput: move |e| sponsor_url.set(e.value()),
+                            placeholder: "https://sponsor.example.com/sign"
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Sponsor fee payer address:" }
+                        input {
+                            value: "{sponsor_pubkey}",
+                            oninput: move |e| sponsor_pubkey.set(e.value()),
+                        }
+                        div {
+                            class: "help-text",
+                            "The sponsor only sees a send after you have signed it. Jito tips are paid by the sponsor too."
+                        }
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div {
+                        class: "error-message",
+                        "{error}"
+                    }
+                }
+
                 div { class: "modal-buttons",
                     button {
                         class: "modal-button cancel",
@@ -101,9 +178,19 @@ pub fn JitoModal(current_settings: JitoSettings, onclose: EventHandler<()>, onsa
                     button {
                         class: "modal-button primary",
                         onclick: move |_| {
+                            let sponsor = FeeSponsorSettings {
+                                enabled: sponsor_enabled(),
+                                url: sponsor_url().trim().to_string(),
+                                payer_pubkey: sponsor_pubkey().trim().to_string(),
+                            };
+                            if let Err(e) = sponsor.fee_payer().and_then(|_| save_fee_sponsor_settings(&sponsor)) {
+                                error_message.set(Some(e));
+                                return;
+                            }
                             let settings = JitoSettings {
                                 jito_tx: jito_tx(),
                                 jito_bundles: jito_bundles(),
+                                tip_level: tip_level(),
                             };
                             onsave.call(settings);
                         },
@@ -152,27 +152,14 @@ async fn execute_jupiter_lend_transaction(
         "method": "sendTransaction",
         "params": [signed_transaction_b64, { "encoding": "base64", "skipPreflight": true, "preflightCommitment": "finalized" }]
     });
-    let response = client
-        .post(&rpc_url)
-        .json(&send_body)
-        .send()
-        .await;
+    let json = crate::rpc_pool::post_json(&client, &rpc_url, &send_body)
+        .await
+        .map_err(|e| format!("Failed to send to RPC: {}", e))?;
 
-    match response {
-        Ok(res) if res.status().is_success() => {
-            if let Ok(json) = res.json::<serde_json::Value>().await {
-                if let Some(sig) = json.get("result").and_then(|v| v.as_str()) {
-                    Ok(sig.to_string())
-                } else {
-                    Err("Failed to get signature from RPC response".to_string())
-                }
-            } else {
-                Err("Failed to parse RPC response".to_string())
-            }
-        }
-        Ok(res) => Err(format!("RPC request failed with status: {}", res.status())),
-        Err(e) => Err(format!("Failed to send to RPC: {}", e)),
-    }
+    json.get("result")
+        .and_then(|v| v.as_str())
+        .map(|sig| sig.to_string())
+        .ok_or_else(|| "Failed to get signature from RPC response".to_string())
 }
 
 #[component]
@@ -1290,7 +1277,7 @@ pub fn LendTransactionSuccessModal(
     was_hardware_wallet: bool,
     onclose: EventHandler<()>,
 ) -> Element {
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     let orb_url = format!("https://orb.helius.dev/tx/{}?cluster=mainnet-beta&tab=summary", signature);
     rsx! {
         div {
@@ -0,0 +1,323 @@
+use dioxus::prelude::*;
+use crate::components::common::Token;
+use crate::hardware::HardwareWallet;
+use crate::signing::active_wallet_signer;
+use crate::swap::{CreateOrderParams, LimitOrder, OrderStatus, TriggerClient};
+use crate::wallet::WalletInfo;
+use std::sync::Arc;
+
+/// Expiry choices: (label, seconds from now)
+const EXPIRY_OPTIONS: &[(&str, Option<i64>)] = &[
+    ("Never", None),
+    ("1 day", Some(86_400)),
+    ("7 days", Some(7 * 86_400)),
+    ("30 days", Some(30 * 86_400)),
+];
+
+fn to_base_units(amount: f64, decimals: u8) -> u64 {
+    (amount * 10_f64.powi(decimals as i32)).round() as u64
+}
+
+/// Place, list and cancel limit orders (sell X when its price reaches Y),
+/// filled by Jupiter's keepers while the app is closed
+#[component]
+pub fn LimitOrdersModal(
+    tokens: Vec<Token>,
+    address: String,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let first_symbol = tokens.first().map(|t| t.symbol.clone()).unwrap_or_default();
+    let second_symbol = tokens.iter().find(|t| t.symbol == "USDC").or(tokens.get(1)).map(|t| t.symbol.clone()).unwrap_or_default();
+    let mut selling = use_signal(move || first_symbol);
+    let mut buying = use_signal(move || second_symbol);
+    let mut amount = use_signal(String::new);
+    let mut limit_price = use_signal(String::new);
+    let mut expiry = use_signal(|| 0usize);
+    let mut orders = use_signal(Vec::<LimitOrder>::new);
+    let mut working = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status = use_signal(|| None as Option<String>);
+
+    let refresh = {
+        let address = address.clone();
+        move || {
+            let address = address.clone();
+            spawn(async move {
+                let (loaded, error) = TriggerClient::new().get_orders(&address).await;
+                orders.set(loaded);
+                if let Some(e) = error {
+                    status.set(Some(format!("Showing saved orders, Jupiter is unreachable: {}", e)));
+                }
+            });
+        }
+    };
+    let refresh_on_mount = refresh.clone();
+    use_hook(move || refresh_on_mount());
+
+    let find = {
+        let tokens = tokens.clone();
+        move |symbol: &str| tokens.iter().find(|t| t.symbol == symbol).cloned()
+    };
+    let sell_token = find(&selling());
+    let buy_token = find(&buying());
+    let market_price = match (&sell_token, &buy_token) {
+        (Some(sell), Some(buy)) if buy.price > 0.0 => Some(sell.price / buy.price),
+        _ => None,
+    };
+    let market_label = match (market_price, &sell_token, &buy_token) {
+        (Some(price), Some(sell), Some(buy)) => format!("Market: 1 {} ≈ {:.6} {}", sell.symbol, price, buy.symbol),
+        _ => String::new(),
+    };
+    let receive_label = match (amount().parse::<f64>(), limit_price().parse::<f64>(), &buy_token) {
+        (Ok(amount), Ok(price), Some(buy)) if amount > 0.0 && price > 0.0 => {
+            format!("You receive at least {:.6} {} when filled", amount * price, buy.symbol)
+        }
+        _ => String::new(),
+    };
+
+    let place_order = {
+        let hardware_wallet = hardware_wallet.clone();
+        let wallet = wallet.clone();
+        let refresh = refresh.clone();
+        let sell_token = sell_token.clone();
+        let buy_token = buy_token.clone();
+        move |_| {
+            error_message.set(None);
+            status.set(None);
+            let (Some(sell), Some(buy)) = (sell_token.clone(), buy_token.clone()) else {
+                error_message.set(Some("Choose the tokens to sell and buy".to_string()));
+                return;
+            };
+            if sell.mint == buy.mint {
+                error_message.set(Some("Choose two different tokens".to_string()));
+                return;
+            }
+            let making = match amount().trim().parse::<f64>() {
+                Ok(value) if value > 0.0 && value <= sell.balance => value,
+                Ok(value) if value > sell.balance => {
+                    error_message.set(Some(format!("You only have {} {}", sell.balance, sell.symbol)));
+                    return;
+                }
+                _ => {
+                    error_message.set(Some("Enter the amount to sell".to_string()));
+                    return;
+                }
+            };
+            let price = match limit_price().trim().parse::<f64>() {
+                Ok(value) if value > 0.0 => value,
+                _ => {
+                    error_message.set(Some("Enter the price to sell at".to_string()));
+                    return;
+                }
+            };
+            let params = CreateOrderParams {
+                input_mint: sell.mint.clone(),
+                output_mint: buy.mint.clone(),
+                input_symbol: sell.symbol.clone(),
+                output_symbol: buy.symbol.clone(),
+                input_decimals: sell.decimals,
+                output_decimals: buy.decimals,
+                making_amount: to_base_units(making, sell.decimals),
+                taking_amount: to_base_units(making * price, buy.decimals),
+                expires_at: EXPIRY_OPTIONS[expiry()].1.map(|seconds| chrono::Utc::now().timestamp() + seconds),
+            };
+            let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+                Ok(signer) => signer,
+                Err(e) => {
+                    error_message.set(Some(e));
+                    return;
+                }
+            };
+            let refresh = refresh.clone();
+            working.set(true);
+            spawn(async move {
+                match TriggerClient::new().create_order_with_signer(&*signer, &params).await {
+                    Ok(order) => {
+                        status.set(Some(format!("Order placed: sell {} {} at {:.6}", order.making_amount, order.input_symbol, order.limit_price())));
+                        amount.set(String::new());
+                        refresh();
+                    }
+                    Err(e) => error_message.set(Some(format!("Failed to place order: {}", e))),
+                }
+                working.set(false);
+            });
+        }
+    };
+
+    let symbols: Vec<String> = tokens.iter().map(|t| t.symbol.clone()).collect();
+    let (open, past): (Vec<LimitOrder>, Vec<LimitOrder>) = orders().into_iter().partition(|o| o.status == OrderStatus::Open);
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Limit Orders" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Sell a token automatically once its price reaches your limit. The tokens stay in the order until it fills, expires or you cancel it."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(message) = status() {
+                    div { class: "info-message", "{message}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Sell:" }
+                    select {
+                        value: "{selling}",
+                        onchange: move |e| selling.set(e.value()),
+                        for symbol in symbols.iter() {
+                            option { key: "{symbol}", value: "{symbol}", selected: *symbol == selling(), "{symbol}" }
+                        }
+                    }
+                    input {
+                        r#type: "text",
+                        value: "{amount}",
+                        placeholder: "Amount",
+                        oninput: move |e| amount.set(e.value()),
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "For:" }
+                    select {
+                        value: "{buying}",
+                        onchange: move |e| buying.set(e.value()),
+                        for symbol in symbols.iter() {
+                            option { key: "{symbol}", value: "{symbol}", selected: *symbol == buying(), "{symbol}" }
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Limit price ({buying} per {selling}):" }
+                    input {
+                        r#type: "text",
+                        value: "{limit_price}",
+                        placeholder: "Price",
+                        oninput: move |e| limit_price.set(e.value()),
+                    }
+                    if !market_label.is_empty() {
+                        div { class: "help-text", "{market_label}" }
+                    }
+                    if !receive_label.is_empty() {
+                        div { class: "help-text", "{receive_label}" }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Expires:" }
+                    select {
+                        onchange: move |e| expiry.set(e.value().parse().unwrap_or(0)),
+                        for (index, (label, _)) in EXPIRY_OPTIONS.iter().enumerate() {
+                            option { key: "{index}", value: "{index}", selected: index == expiry(), "{label}" }
+                        }
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: working(),
+                        onclick: place_order,
+                        if working() { "Placing..." } else { "Place Order" }
+                    }
+                }
+
+                if !open.is_empty() {
+                    h3 { "Open orders" }
+                }
+                for order in open {
+                    {
+                        let line = format!(
+                            "Sell {} {} at {:.6} {} each",
+                            order.making_amount, order.input_symbol, order.limit_price(), order.output_symbol
+                        );
+                        let order_key = order.order.clone();
+                        let hardware_wallet = hardware_wallet.clone();
+                        let wallet = wallet.clone();
+                        let refresh = refresh.clone();
+                        rsx! {
+                            div {
+                                key: "{order.order}",
+                                class: "wallet-field",
+                                div { class: "info-message", "{line}" }
+                                button {
+                                    class: "button-standard secondary",
+                                    disabled: working(),
+                                    onclick: move |_| {
+                                        let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+                                            Ok(signer) => signer,
+                                            Err(e) => {
+                                                error_message.set(Some(e));
+                                                return;
+                                            }
+                                        };
+                                        let order_key = order_key.clone();
+                                        let refresh = refresh.clone();
+                                        working.set(true);
+                                        error_message.set(None);
+                                        spawn(async move {
+                                            match TriggerClient::new().cancel_order_with_signer(&*signer, &order_key).await {
+                                                Ok(_) => {
+                                                    status.set(Some("Order cancelled".to_string()));
+                                                    refresh();
+                                                }
+                                                Err(e) => error_message.set(Some(format!("Failed to cancel order: {}", e))),
+                                            }
+                                            working.set(false);
+                                        });
+                                    },
+                                    "Cancel"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !past.is_empty() {
+                    h3 { "History" }
+                }
+                for order in past {
+                    {
+                        let line = format!(
+                            "{}: {} {} → {} {}",
+                            order.status.label(), order.making_amount, order.input_symbol, order.taking_amount, order.output_symbol
+                        );
+                        rsx! {
+                            div { key: "{order.order}", class: "help-text", "{line}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
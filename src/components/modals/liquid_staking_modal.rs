@@ -0,0 +1,346 @@
+use dioxus::prelude::*;
+use crate::components::common::Token;
+use crate::hardware::HardwareWallet;
+use crate::liquid_staking::{
+    lst_by_symbol, ExchangeRate, LiquidStakingClient, LstProtocol, StakePoolInfo, LIQUID_STAKING_TOKENS,
+};
+use crate::signing::active_wallet_signer;
+use crate::wallet::WalletInfo;
+use std::sync::Arc;
+
+/// `selected` value for a stake pool entered by address
+const CUSTOM_POOL: &str = "custom";
+
+/// How the conversion back to SOL works, shown under the form
+fn unstake_note(protocol: LstProtocol) -> &'static str {
+    match protocol {
+        LstProtocol::StakePool(_) => {
+            "Unstaking withdraws SOL from the pool's reserve right away. The pool charges a small withdrawal fee, and large amounts can exceed what the reserve holds."
+        }
+        LstProtocol::Marinade => {
+            "Unstaking sells mSOL to Marinade's liquidity pool for immediate SOL, minus the pool's liquid unstake fee."
+        }
+        LstProtocol::Jupiter => "Converted through a Jupiter swap into or out of the Infinity pool, within your slippage setting.",
+    }
+}
+
+/// Convert SOL into a liquid staking token, or any SPL stake pool's token,
+/// and back in one transaction
+#[component]
+pub fn LiquidStakingModal(
+    tokens: Vec<Token>,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut selected = use_signal(|| LIQUID_STAKING_TOKENS[0].symbol.to_string());
+    let mut staking = use_signal(|| true);
+    let mut amount = use_signal(String::new);
+    // Unstake by selling through Sanctum's router instead of the protocol
+    let mut via_router = use_signal(|| false);
+    let mut rate = use_signal(|| None as Option<ExchangeRate>);
+    // A stake pool entered by address
+    let mut pool_address = use_signal(String::new);
+    let mut pool = use_signal(|| None as Option<StakePoolInfo>);
+    let mut loading_pool = use_signal(|| false);
+    let mut working = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status = use_signal(|| None as Option<String>);
+
+    use_effect({
+        let custom_rpc = custom_rpc.clone();
+        move || {
+            rate.set(None);
+            let Some(token) = lst_by_symbol(&selected()) else { return };
+            let custom_rpc = custom_rpc.clone();
+            spawn(async move {
+                match LiquidStakingClient::new(custom_rpc.as_deref()).exchange_rate(token).await {
+                    Ok(found) => rate.set(Some(found)),
+                    Err(e) => error_message.set(Some(format!("Failed to load the {} rate: {}", token.symbol, e))),
+                }
+            });
+        }
+    });
+
+    let load_pool = {
+        let custom_rpc = custom_rpc.clone();
+        move |_| {
+            let address = pool_address().trim().to_string();
+            if address.is_empty() {
+                error_message.set(Some("Enter a stake pool address".to_string()));
+                return;
+            }
+            let custom_rpc = custom_rpc.clone();
+            loading_pool.set(true);
+            error_message.set(None);
+            pool.set(None);
+            spawn(async move {
+                match LiquidStakingClient::new(custom_rpc.as_deref()).stake_pool(&address).await {
+                    Ok(info) => pool.set(Some(info)),
+                    Err(e) => error_message.set(Some(format!("Failed to load the stake pool: {}", e))),
+                }
+                loading_pool.set(false);
+            });
+        }
+    };
+
+    let custom = selected() == CUSTOM_POOL;
+    let token = lst_by_symbol(&selected()).unwrap_or(&LIQUID_STAKING_TOKENS[0]);
+    let (token_symbol, token_mint, token_decimals) = match (custom, pool()) {
+        (true, Some(info)) => ("pool tokens".to_string(), info.state.pool_mint.to_string(), info.decimals),
+        (true, None) => ("pool tokens".to_string(), String::new(), 9),
+        (false, _) => (token.symbol.to_string(), token.mint.to_string(), token.decimals),
+    };
+    let balance_of = |mint: &str| tokens.iter().find(|t| t.mint == mint).map(|t| t.balance).unwrap_or(0.0);
+    let sol_balance = balance_of("So11111111111111111111111111111111111111112");
+    let token_balance = balance_of(&token_mint);
+    let (input_symbol, output_symbol, available) = if staking() {
+        ("SOL".to_string(), token_symbol.clone(), sol_balance)
+    } else {
+        (token_symbol.clone(), "SOL".to_string(), token_balance)
+    };
+    let available_label = format!("Available: {:.6} {}", available, input_symbol);
+    let shown_rate = if custom { pool().map(|info| info.rate()) } else { rate() };
+    let rate_label = match shown_rate {
+        Some(r) => r.label(&token_symbol),
+        None if custom => "Load a pool to see its rate and fees".to_string(),
+        None => "Loading rate...".to_string(),
+    };
+    let fee_label = pool().filter(|_| custom).map(|info| info.fee_summary());
+    let expected = match (amount().trim().parse::<f64>(), shown_rate, pool().filter(|_| custom)) {
+        // A pool's own state gives the exact amount after its fees
+        (Ok(value), _, Some(info)) if value > 0.0 => {
+            let out = if staking() {
+                info.state.deposit_quote((value * 1e9) as u64) as f64 / 10_f64.powi(info.decimals as i32)
+            } else {
+                info.state.withdraw_quote((value * 10_f64.powi(info.decimals as i32)) as u64) as f64 / 1e9
+            };
+            format!("You receive about {:.6} {} after pool fees", out, output_symbol)
+        }
+        (Ok(value), Some(r), None) if value > 0.0 => {
+            let out = if staking() { r.tokens_for_sol(value) } else { r.sol_for_tokens(value) };
+            format!("You receive about {:.6} {} before fees", out, output_symbol)
+        }
+        _ => String::new(),
+    };
+    let router = !staking() && via_router();
+    let note = if staking() {
+        "Staked SOL keeps earning rewards inside the token, whose SOL value grows every epoch."
+    } else if router {
+        "Sells the tokens to SOL through Sanctum's router at its best price right now, which can beat the protocol's own unstake for large amounts."
+    } else if custom {
+        unstake_note(LstProtocol::StakePool(""))
+    } else {
+        unstake_note(token.protocol)
+    };
+    let action_label = if working() {
+        "Converting...".to_string()
+    } else {
+        format!("Convert {} to {}", input_symbol, output_symbol)
+    };
+
+    let convert = {
+        let input_symbol = input_symbol.clone();
+        let token_mint = token_mint.clone();
+        move |_| {
+            let token = lst_by_symbol(&selected());
+            let custom_pool = pool().filter(|_| custom);
+            if token.is_none() && custom_pool.is_none() {
+                error_message.set(Some("Load a stake pool first".to_string()));
+                return;
+            }
+            let value = match amount().trim().parse::<f64>() {
+                Ok(value) if value > 0.0 => value,
+                _ => {
+                    error_message.set(Some("Enter an amount".to_string()));
+                    return;
+                }
+            };
+            if value > available {
+                error_message.set(Some(format!("Not enough {}", input_symbol)));
+                return;
+            }
+            let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+                Ok(signer) => signer,
+                Err(e) => {
+                    error_message.set(Some(e));
+                    return;
+                }
+            };
+            let custom_rpc = custom_rpc.clone();
+            let stake = staking();
+            let mint = token_mint.clone();
+            let spent = format!("{} {}", value, input_symbol);
+            working.set(true);
+            error_message.set(None);
+            status.set(if hardware_wallet.is_some() {
+                Some("Confirm the transaction on your hardware wallet".to_string())
+            } else {
+                None
+            });
+            spawn(async move {
+                let client = LiquidStakingClient::new(custom_rpc.as_deref());
+                let base_units = if stake {
+                    (value * 1e9) as u64
+                } else {
+                    (value * 10_f64.powi(token_decimals as i32)) as u64
+                };
+                let result = match (token, custom_pool) {
+                    _ if router => match client.quote_sale(&mint, base_units).await {
+                        Ok((_, quote)) => client.sell_quoted(quote, signer.as_ref()).await,
+                        Err(e) => Err(e),
+                    },
+                    (Some(token), _) if stake => client.stake(token, signer.as_ref(), base_units).await,
+                    (Some(token), _) => client.unstake(token, signer.as_ref(), base_units).await,
+                    (None, Some(info)) if stake => client.deposit_to_pool(&info, signer.as_ref(), base_units).await,
+                    (None, Some(info)) => client.withdraw_from_pool(&info, signer.as_ref(), base_units).await,
+                    (None, None) => Err("No stake pool loaded".into()),
+                };
+                match result {
+                    Ok(signature) => {
+                        status.set(Some(format!("Converted {}: {}", spent, signature)));
+                        amount.set(String::new());
+                    }
+                    Err(e) => {
+                        status.set(None);
+                        error_message.set(Some(e.to_string()));
+                    }
+                }
+                working.set(false);
+            });
+        }
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Liquid Staking" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(message) = status() {
+                    div { class: "info-message", "{message}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Token:" }
+                    select {
+                        onchange: move |e| {
+                            selected.set(e.value());
+                            error_message.set(None);
+                        },
+                        for lst in LIQUID_STAKING_TOKENS.iter() {
+                            option {
+                                key: "{lst.symbol}",
+                                value: "{lst.symbol}",
+                                selected: lst.symbol == selected(),
+                                "{lst.symbol} · {lst.name}"
+                            }
+                        }
+                        option { value: CUSTOM_POOL, selected: custom, "Other stake pool..." }
+                    }
+                }
+
+                if custom {
+                    div {
+                        class: "wallet-field",
+                        label { "Stake pool address:" }
+                        input {
+                            r#type: "text",
+                            value: "{pool_address}",
+                            placeholder: "SPL stake pool account",
+                            oninput: move |e| pool_address.set(e.value()),
+                        }
+                        button {
+                            class: "button-standard secondary",
+                            disabled: loading_pool(),
+                            onclick: load_pool,
+                            if loading_pool() { "Loading..." } else { "Load Pool" }
+                        }
+                    }
+                }
+
+                if let Some(fees) = fee_label {
+                    div { class: "help-text", "{fees}" }
+                }
+
+                div { class: "help-text", "{rate_label}" }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: if staking() { "button-standard primary" } else { "button-standard secondary" },
+                        onclick: move |_| staking.set(true),
+                        "Stake"
+                    }
+                    button {
+                        class: if staking() { "button-standard secondary" } else { "button-standard primary" },
+                        onclick: move |_| staking.set(false),
+                        "Unstake"
+                    }
+                }
+
+                if !staking() {
+                    div {
+                        class: "wallet-field",
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: via_router(),
+                                onchange: move |e| via_router.set(e.checked()),
+                            }
+                            " Sell instantly through Sanctum's router"
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Amount ({input_symbol}):" }
+                    input {
+                        r#type: "text",
+                        value: "{amount}",
+                        placeholder: "0.0",
+                        oninput: move |e| amount.set(e.value()),
+                    }
+                    div { class: "help-text", "{available_label}" }
+                }
+
+                if !expected.is_empty() {
+                    div { class: "info-message", "{expected}" }
+                }
+                div { class: "help-text", "{note}" }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: working(),
+                        onclick: convert,
+                        "{action_label}"
+                    }
+                }
+            }
+        }
+    }
+}
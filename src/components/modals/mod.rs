@@ -19,6 +19,36 @@ pub mod squads_modal;
 pub mod carrot_modal;
 pub mod bonk_staking_modal;
 pub mod quantum_vault_modal;
+pub mod streaming_modal;
+pub mod address_book_modal;
+pub mod shared_wallet_modal;
+pub mod partial_sign_modal;
+pub mod wallet_profile_modal;
+pub mod sign_message_modal;
+pub mod device_transfer_modal;
+pub mod session_key_modal;
+pub mod historical_balances_modal;
+pub mod cost_basis_modal;
+pub mod price_providers_modal;
+pub mod swap_providers_modal;
+pub mod limit_orders_modal;
+pub mod dca_modal;
+pub mod swap_history_modal;
+pub mod stake_positions_modal;
+pub mod liquid_staking_modal;
+pub mod spread_stake_modal;
+pub mod backup_modal;
+pub mod staking_rewards_modal;
+pub mod notifications_modal;
+pub mod outbox_modal;
+pub mod treasury_report_modal;
+pub mod composer_modal;
+pub mod budget_modal;
+pub mod broadcast_modal;
+pub mod multi_transfer_modal;
+pub mod organize_wallets_modal;
+pub mod pin_security_modal;
+pub mod duress_pin_modal;
 
 pub use wallet_modal::WalletModal;
 pub use rpc_modal::RpcModal;
@@ -40,4 +70,34 @@ pub use delete_wallet_modal::DeleteWalletModal;
 pub use carrot_modal::CarrotModal;
 pub use squads_modal::SquadsModal;
 pub use bonk_staking_modal::BonkStakingModal;
-pub use quantum_vault_modal::QuantumVaultModal;
\ No newline at end of file
+pub use quantum_vault_modal::QuantumVaultModal;
+pub use streaming_modal::StreamingModal;
+pub use address_book_modal::AddressBookModal;
+pub use shared_wallet_modal::SharedWalletModal;
+pub use partial_sign_modal::PartialSignModal;
+pub use wallet_profile_modal::WalletProfileModal;
+pub use sign_message_modal::SignMessageModal;
+pub use device_transfer_modal::DeviceTransferModal;
+pub use session_key_modal::SessionKeyModal;
+pub use historical_balances_modal::HistoricalBalancesModal;
+pub use cost_basis_modal::CostBasisModal;
+pub use price_providers_modal::PriceProvidersModal;
+pub use swap_providers_modal::SwapProvidersModal;
+pub use limit_orders_modal::LimitOrdersModal;
+pub use dca_modal::DcaModal;
+pub use swap_history_modal::SwapHistoryModal;
+pub use stake_positions_modal::StakePositionsModal;
+pub use liquid_staking_modal::LiquidStakingModal;
+pub use spread_stake_modal::SpreadStakeModal;
+pub use backup_modal::BackupModal;
+pub use staking_rewards_modal::StakingRewardsModal;
+pub use notifications_modal::NotificationsModal;
+pub use outbox_modal::OutboxModal;
+pub use treasury_report_modal::TreasuryReportModal;
+pub use composer_modal::ComposerModal;
+pub use budget_modal::BudgetModal;
+pub use broadcast_modal::BroadcastModal;
+pub use multi_transfer_modal::MultiTransferModal;
+pub use organize_wallets_modal::OrganizeWalletsModal;
+pub use pin_security_modal::PinSecurityModal;
+pub use duress_pin_modal::DuressPinModal;
@@ -0,0 +1,308 @@
+use dioxus::prelude::*;
+use crate::wallet::WalletInfo;
+use crate::hardware::HardwareWallet;
+use crate::components::common::Token;
+use crate::domain_resolver::resolve_recipient;
+use crate::signing::active_wallet_signer;
+use crate::transaction::{RecipientTransfer, TransactionClient};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// A transfer line as shown in the list
+#[derive(Debug, Clone, PartialEq)]
+struct TransferLine {
+    transfer: RecipientTransfer,
+    symbol: String,
+    /// What was typed, e.g. a .sol domain
+    label: String,
+}
+
+fn mint_for(symbol_or_mint: &str, tokens: &[Token]) -> Result<(Option<Pubkey>, String), String> {
+    let wanted = symbol_or_mint.trim();
+    if wanted.is_empty() || wanted.eq_ignore_ascii_case("SOL") || wanted == SOL_MINT {
+        return Ok((None, "SOL".to_string()));
+    }
+    let token = tokens
+        .iter()
+        .find(|t| t.mint == wanted || t.symbol.eq_ignore_ascii_case(wanted))
+        .ok_or_else(|| format!("You don't hold a token called {}", wanted))?;
+    let mint = Pubkey::from_str(&token.mint).map_err(|e| e.to_string())?;
+    Ok((Some(mint), token.symbol.clone()))
+}
+
+/// Send SOL and tokens to many recipients with one signature, e.g. payroll
+#[component]
+pub fn MultiTransferModal(
+    tokens: Vec<Token>,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    owner_address: String,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut lines = use_signal(Vec::<TransferLine>::new);
+    let mut csv = use_signal(String::new);
+    let mut recipient = use_signal(String::new);
+    let mut amount = use_signal(String::new);
+    let mut token = use_signal(|| "SOL".to_string());
+    let mut adding = use_signal(|| false);
+    let mut batch_count = use_signal(|| None as Option<usize>);
+    let mut planning = use_signal(|| false);
+    let mut sending = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut signatures = use_signal(Vec::<String>::new);
+
+    let tokens_for_add = tokens.clone();
+    // Lines of "recipient,amount[,token]"; recipients may be domains
+    let add_lines = move |text: String| {
+        let tokens = tokens_for_add.clone();
+        error_message.set(None);
+        adding.set(true);
+        spawn(async move {
+            for (row, raw) in text.lines().enumerate() {
+                let fields: Vec<&str> = raw.split(',').map(str::trim).collect();
+                if fields.iter().all(|f| f.is_empty()) {
+                    continue;
+                }
+                let parsed = match fields.as_slice() {
+                    [to, value] | [to, value, _] => value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|v| *v > 0.0)
+                        .ok_or_else(|| format!("Line {}: invalid amount", row + 1))
+                        .and_then(|value| mint_for(fields.get(2).copied().unwrap_or("SOL"), &tokens).map(|m| (to.to_string(), value, m))),
+                    _ => Err(format!("Line {}: expected recipient,amount[,token]", row + 1)),
+                };
+                let (to, value, (mint, symbol)) = match parsed {
+                    Ok(parsed) => parsed,
+                    Err(e) => {
+                        error_message.set(Some(e));
+                        break;
+                    }
+                };
+                match resolve_recipient(&to).await {
+                    Ok(resolved) => {
+                        lines.write().push(TransferLine {
+                            transfer: RecipientTransfer { recipient: resolved.pubkey, mint, amount: value },
+                            symbol,
+                            label: to,
+                        });
+                        batch_count.set(None);
+                    }
+                    Err(e) => {
+                        error_message.set(Some(format!("Line {}: {}", row + 1, e)));
+                        break;
+                    }
+                }
+            }
+            adding.set(false);
+        });
+    };
+
+    let rpc_for_plan = custom_rpc.clone();
+    let owner_for_plan = owner_address.clone();
+    let review = move |_| {
+        let Ok(payer) = Pubkey::from_str(&owner_for_plan) else {
+            error_message.set(Some("Invalid wallet address".to_string()));
+            return;
+        };
+        let transfers: Vec<RecipientTransfer> = lines().into_iter().map(|l| l.transfer).collect();
+        let rpc = rpc_for_plan.clone();
+        planning.set(true);
+        error_message.set(None);
+        spawn(async move {
+            let client = TransactionClient::new(rpc.as_deref());
+            let result = client.plan_multi_transfer(&payer, &transfers).await.map(|b| b.len()).map_err(|e| e.to_string());
+            match result {
+                Ok(count) => batch_count.set(Some(count)),
+                Err(e) => error_message.set(Some(e)),
+            }
+            planning.set(false);
+        });
+    };
+
+    let rpc_for_send = custom_rpc.clone();
+    let send = move |_| {
+        let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+            Ok(signer) => signer,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        let transfers: Vec<RecipientTransfer> = lines().into_iter().map(|l| l.transfer).collect();
+        let rpc = rpc_for_send.clone();
+        sending.set(true);
+        error_message.set(None);
+        spawn(async move {
+            let client = TransactionClient::new(rpc.as_deref());
+            let result = client.send_multi_transfer_with_signer(signer.as_ref(), &transfers).await.map_err(|e| e.to_string());
+            match result {
+                Ok(sent) => {
+                    signatures.set(sent);
+                    lines.set(Vec::new());
+                    batch_count.set(None);
+                }
+                Err(e) => error_message.set(Some(format!("Send failed: {}", e))),
+            }
+            sending.set(false);
+        });
+    };
+
+    let totals: Vec<(String, f64)> = {
+        let mut totals: Vec<(String, f64)> = Vec::new();
+        for line in lines() {
+            match totals.iter_mut().find(|(symbol, _)| *symbol == line.symbol) {
+                Some((_, total)) => *total += line.transfer.amount,
+                None => totals.push((line.symbol.clone(), line.transfer.amount)),
+            }
+        }
+        totals
+    };
+    let mut add_csv = add_lines.clone();
+    let mut add_single = add_lines;
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Multi-Recipient Send" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Pay many recipients at once. Transfers are packed into as few transactions as fit, usually one signature and one fee. Missing token accounts are created for the recipients."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                if !signatures().is_empty() {
+                    div {
+                        class: "success-message",
+                        "Sent in {signatures().len()} transaction(s):"
+                        for sig in signatures() {
+                            div { key: "{sig}", class: "address-display", "{sig}" }
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Transfers:" }
+                    if lines().is_empty() {
+                        div { class: "help-text", "Add recipients below" }
+                    }
+                    for (index, line) in lines().into_iter().enumerate() {
+                        div {
+                            key: "{index}",
+                            class: "info-message",
+                            "{line.transfer.amount} {line.symbol} → {line.label}"
+                            button {
+                                class: "button-standard secondary",
+                                onclick: move |_| {
+                                    lines.write().remove(index);
+                                    batch_count.set(None);
+                                },
+                                "Remove"
+                            }
+                        }
+                    }
+                    for (symbol, total) in totals {
+                        div { key: "total-{symbol}", class: "help-text", "Total: {total} {symbol}" }
+                    }
+                    if let Some(count) = batch_count() {
+                        div {
+                            class: "help-text",
+                            if count == 1 { "Fits in one transaction" } else { "Needs {count} transactions, one signature each" }
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Add recipient:" }
+                    input {
+                        value: "{recipient}",
+                        oninput: move |e| recipient.set(e.value()),
+                        placeholder: "Address or .sol domain"
+                    }
+                    input {
+                        r#type: "number",
+                        value: "{amount}",
+                        oninput: move |e| amount.set(e.value()),
+                        placeholder: "Amount"
+                    }
+                    select {
+                        onchange: move |e| token.set(e.value()),
+                        option { value: "SOL", selected: token() == "SOL", "SOL" }
+                        for t in tokens.iter().filter(|t| t.mint != SOL_MINT) {
+                            option { key: "{t.mint}", value: "{t.mint}", selected: token() == t.mint, "{t.symbol}" }
+                        }
+                    }
+                    button {
+                        class: "button-standard secondary",
+                        disabled: adding() || recipient().trim().is_empty() || amount().trim().is_empty(),
+                        onclick: move |_| {
+                            add_single(format!("{},{},{}", recipient().trim(), amount().trim(), token()));
+                            recipient.set(String::new());
+                            amount.set(String::new());
+                        },
+                        if adding() { "Resolving..." } else { "Add" }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Or paste CSV (recipient,amount[,token]):" }
+                    textarea {
+                        rows: "4",
+                        value: "{csv}",
+                        oninput: move |e| csv.set(e.value()),
+                        placeholder: "alice.sol,1.5\nBob1111...,20,USDC"
+                    }
+                    button {
+                        class: "button-standard secondary",
+                        disabled: adding() || csv().trim().is_empty(),
+                        onclick: move |_| {
+                            add_csv(csv());
+                            csv.set(String::new());
+                        },
+                        "Add Lines"
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        disabled: planning() || lines().is_empty(),
+                        onclick: review,
+                        if planning() { "Checking..." } else { "Review" }
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: sending() || lines().is_empty(),
+                        onclick: send,
+                        if sending() { "Sending..." } else { "Sign & Send" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use dioxus::prelude::*;
+use crate::notifications::{self, CriticalEvent, EmailChannel, NotificationSettings, TelegramChannel, DEFAULT_EMAIL_API_URL};
+use crate::storage::{load_notification_settings, save_notification_settings};
+
+/// Keep the stored sealed secret when the field is left blank
+fn sealed_or_existing(input: &str, existing: Option<&str>, field: &str) -> Result<String, String> {
+    if !input.trim().is_empty() {
+        notifications::seal_secret(input)
+    } else {
+        existing.map(str::to_string).ok_or_else(|| format!("Enter the {}", field))
+    }
+}
+
+/// What was typed into the form
+struct NotificationForm {
+    chat_id: String,
+    bot_token: String,
+    email_to: String,
+    email_from: String,
+    email_api_url: String,
+    email_api_key: String,
+    threshold: String,
+}
+
+impl NotificationForm {
+    /// Apply the form to `current`; empty channel fields remove the channel
+    fn apply(&self, current: &NotificationSettings) -> Result<NotificationSettings, String> {
+        let mut settings = current.clone();
+        settings.large_transfer_sol = self.threshold.trim().parse::<f64>()
+            .ok()
+            .filter(|v| *v > 0.0)
+            .ok_or("Enter a valid SOL threshold")?;
+
+        settings.telegram = if self.chat_id.trim().is_empty() {
+            None
+        } else {
+            let existing = current.telegram.as_ref().map(|t| t.bot_token.as_str());
+            Some(TelegramChannel {
+                chat_id: self.chat_id.trim().to_string(),
+                bot_token: sealed_or_existing(&self.bot_token, existing, "Telegram bot token")?,
+            })
+        };
+
+        settings.email = if self.email_to.trim().is_empty() {
+            None
+        } else {
+            if !self.email_to.contains('@') || !self.email_from.contains('@') {
+                return Err("Enter valid sender and recipient email addresses".to_string());
+            }
+            let api_url = match self.email_api_url.trim() {
+                "" => DEFAULT_EMAIL_API_URL,
+                url => url,
+            };
+            if !api_url.starts_with("https://") {
+                return Err("The email API must use https".to_string());
+            }
+            let existing = current.email.as_ref().map(|e| e.api_key.as_str());
+            Some(EmailChannel {
+                api_url: api_url.to_string(),
+                from: self.email_from.trim().to_string(),
+                to: self.email_to.trim().to_string(),
+                api_key: sealed_or_existing(&self.email_api_key, existing, "email API key")?,
+            })
+        };
+        Ok(settings)
+    }
+}
+
+/// Configure Telegram and email alerts for critical events
+#[component]
+pub fn NotificationsModal(onclose: EventHandler<()>) -> Element {
+    let initial = load_notification_settings();
+    let mut settings = use_signal(|| initial.clone());
+    let mut chat_id = use_signal(|| initial.telegram.as_ref().map(|t| t.chat_id.clone()).unwrap_or_default());
+    let mut bot_token = use_signal(String::new);
+    let mut email_to = use_signal(|| initial.email.as_ref().map(|e| e.to.clone()).unwrap_or_default());
+    let mut email_from = use_signal(|| initial.email.as_ref().map(|e| e.from.clone()).unwrap_or_default());
+    let mut email_api_url = use_signal(|| initial.email.as_ref().map(|e| e.api_url.clone()).unwrap_or_else(|| DEFAULT_EMAIL_API_URL.to_string()));
+    let mut email_api_key = use_signal(String::new);
+    let mut threshold = use_signal(|| initial.large_transfer_sol.to_string());
+    let mut working = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status_message = use_signal(|| None as Option<String>);
+
+    let mut save = move || -> Option<NotificationSettings> {
+        error_message.set(None);
+        status_message.set(None);
+        let form = NotificationForm {
+            chat_id: chat_id(),
+            bot_token: bot_token(),
+            email_to: email_to(),
+            email_from: email_from(),
+            email_api_url: email_api_url(),
+            email_api_key: email_api_key(),
+            threshold: threshold(),
+        };
+        let updated = form.apply(&settings());
+        match updated.and_then(|updated| save_notification_settings(&updated).map(|_| updated)) {
+            Ok(updated) => {
+                // Secrets are never shown again once sealed
+                bot_token.set(String::new());
+                email_api_key.set(String::new());
+                settings.set(updated.clone());
+                Some(updated)
+            }
+            Err(e) => {
+                error_message.set(Some(e));
+                None
+            }
+        }
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Critical Alerts" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Get a Telegram message or email for critical events only. Alerts never include keys, seed phrases or full addresses, and the bot token and API key are stored sealed on this device."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(status) = status_message() {
+                    div { class: "info-message", "{status}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Telegram chat ID:" }
+                    input {
+                        value: "{chat_id}",
+                        oninput: move |e| chat_id.set(e.value()),
+                        placeholder: "Leave empty to turn off Telegram"
+                    }
+                    input {
+                        r#type: "password",
+                        value: "{bot_token}",
+                        oninput: move |e| bot_token.set(e.value()),
+                        placeholder: if settings().telegram.is_some() { "Bot token (saved, leave empty to keep)" } else { "Bot token from @BotFather" }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Email:" }
+                    input {
+                        value: "{email_to}",
+                        oninput: move |e| email_to.set(e.value()),
+                        placeholder: "Send alerts to (leave empty to turn off email)"
+                    }
+                    input {
+                        value: "{email_from}",
+                        oninput: move |e| email_from.set(e.value()),
+                        placeholder: "Sender address"
+                    }
+                    input {
+                        value: "{email_api_url}",
+                        oninput: move |e| email_api_url.set(e.value()),
+                        placeholder: "Email API URL"
+                    }
+                    input {
+                        r#type: "password",
+                        value: "{email_api_key}",
+                        oninput: move |e| email_api_key.set(e.value()),
+                        placeholder: if settings().email.is_some() { "API key (saved, leave empty to keep)" } else { "Email API key" }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Alert me about:" }
+                    label {
+                        class: "help-text",
+                        input {
+                            r#type: "checkbox",
+                            checked: settings().large_transfers,
+                            oninput: move |_| settings.with_mut(|s| s.large_transfers = !s.large_transfers),
+                        }
+                        " Outgoing transfers of at least"
+                    }
+                    input {
+                        r#type: "number",
+                        value: "{threshold}",
+                        oninput: move |e| threshold.set(e.value()),
+                        placeholder: "SOL"
+                    }
+                    label {
+                        class: "help-text",
+                        input {
+                            r#type: "checkbox",
+                            checked: settings().new_connections,
+                            oninput: move |_| settings.with_mut(|s| s.new_connections = !s.new_connections),
+                        }
+                        " New bridge connections"
+                    }
+                    label {
+                        class: "help-text",
+                        input {
+                            r#type: "checkbox",
+                            checked: settings().failed_logins,
+                            oninput: move |_| settings.with_mut(|s| s.failed_logins = !s.failed_logins),
+                        }
+                        " Wrong PIN attempts"
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        disabled: working(),
+                        onclick: move |_| {
+                            let Some(saved) = save() else { return };
+                            if !saved.has_channel() {
+                                error_message.set(Some("Set up Telegram or email first".to_string()));
+                                return;
+                            }
+                            working.set(true);
+                            spawn(async move {
+                                match notifications::send_event(&saved, &CriticalEvent::Test).await {
+                                    Ok(()) => status_message.set(Some("Test alert sent".to_string())),
+                                    Err(e) => error_message.set(Some(e)),
+                                }
+                                working.set(false);
+                            });
+                        },
+                        if working() { "Sending..." } else { "Send Test" }
+                    }
+                    button {
+                        class: "button-standard primary",
+                        onclick: move |_| {
+                            if save().is_some() {
+                                status_message.set(Some("Alert settings saved".to_string()));
+                            }
+                        },
+                        "Save"
+                    }
+                }
+            }
+        }
+    }
+}
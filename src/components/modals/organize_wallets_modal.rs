@@ -0,0 +1,205 @@
+use dioxus::prelude::*;
+use crate::storage::{load_wallet_profile, load_wallet_profiles, save_wallet_order, save_wallet_profile};
+use crate::wallet::WalletInfo;
+use crate::wallet_groups::{folder_names, organize, reordered, WALLET_COLORS};
+
+/// Reorder stored wallets, file them into folders and give them a color or
+/// emoji for the wallet list
+#[component]
+pub fn OrganizeWalletsModal(wallets: Vec<WalletInfo>, onclose: EventHandler<()>) -> Element {
+    let mut profiles = use_signal(load_wallet_profiles);
+    let mut editing = use_signal(|| None as Option<usize>);
+    let mut folder = use_signal(String::new);
+    let mut emoji = use_signal(String::new);
+    let mut color = use_signal(|| None as Option<String>);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    let folders = organize(&wallets, &profiles());
+    let suggestions = folder_names(&profiles());
+
+    let move_wallet = {
+        let wallets = wallets.clone();
+        let folders = folders.clone();
+        move |index: usize, up: bool| {
+            match save_wallet_order(&reordered(&wallets, &folders, index, up)) {
+                Ok(()) => profiles.set(load_wallet_profiles()),
+                Err(e) => error_message.set(Some(e)),
+            }
+        }
+    };
+
+    let start_editing = {
+        let wallets = wallets.clone();
+        move |index: usize| {
+            let profile = load_wallet_profile(&wallets[index].address);
+            folder.set(profile.folder);
+            emoji.set(profile.emoji);
+            color.set(profile.color);
+            editing.set(Some(index));
+        }
+    };
+
+    let save_appearance = {
+        let wallets = wallets.clone();
+        move |_| {
+            let Some(index) = editing() else { return };
+            let mut profile = load_wallet_profile(&wallets[index].address);
+            profile.folder = folder().trim().to_string();
+            profile.emoji = emoji().trim().chars().take(2).collect();
+            profile.color = color();
+            match save_wallet_profile(&profile) {
+                Ok(()) => {
+                    profiles.set(load_wallet_profiles());
+                    editing.set(None);
+                }
+                Err(e) => error_message.set(Some(e)),
+            }
+        }
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Organize Wallets" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                for group in folders {
+                    div {
+                        key: "{group.name}",
+                        class: "wallet-field",
+                        label { if group.name.is_empty() { "No folder" } else { "📁 {group.name}" } }
+                        for (position, index) in group.indices.iter().copied().enumerate() {
+                            {
+                                let wallet = &wallets[index];
+                                let profile = profiles().into_iter().find(|p| p.address == wallet.address).unwrap_or_default();
+                                let marker = if profile.emoji.is_empty() { "•".to_string() } else { profile.emoji.clone() };
+                                let accent = profile.color.map(|c| format!("border-left: 3px solid {};", c)).unwrap_or_default();
+                                let last = position + 1 == group.indices.len();
+                                let mut move_wallet = move_wallet.clone();
+                                let mut move_down = move_wallet.clone();
+                                let mut start_editing = start_editing.clone();
+                                rsx! {
+                                    div {
+                                        key: "{wallet.address}",
+                                        class: "transaction-item",
+                                        style: "{accent}",
+                                        div {
+                                            class: "transaction-item-content",
+                                            div { "{marker} {wallet.name}" }
+                                        }
+                                        button {
+                                            class: "button-standard secondary",
+                                            disabled: position == 0,
+                                            onclick: move |_| move_wallet(index, true),
+                                            "▲"
+                                        }
+                                        button {
+                                            class: "button-standard secondary",
+                                            disabled: last,
+                                            onclick: move |_| move_down(index, false),
+                                            "▼"
+                                        }
+                                        button {
+                                            class: "button-standard secondary",
+                                            onclick: move |_| start_editing(index),
+                                            "Edit"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if let Some(index) = editing() {
+                    div {
+                        class: "wallet-field",
+                        label { "Appearance of {wallets[index].name}:" }
+                        div {
+                            class: "wallet-field",
+                            label { "Folder:" }
+                            input {
+                                value: "{folder}",
+                                list: "wallet-folders",
+                                placeholder: "None",
+                                oninput: move |e| folder.set(e.value()),
+                            }
+                            datalist {
+                                id: "wallet-folders",
+                                for name in suggestions {
+                                    option { key: "{name}", value: "{name}" }
+                                }
+                            }
+                        }
+                        div {
+                            class: "wallet-field",
+                            label { "Emoji:" }
+                            input {
+                                value: "{emoji}",
+                                placeholder: "e.g. 🏦",
+                                oninput: move |e| emoji.set(e.value()),
+                            }
+                        }
+                        div {
+                            class: "wallet-field",
+                            label { "Color:" }
+                            div { class: "modal-buttons",
+                                button {
+                                    class: if color().is_none() { "button-standard primary" } else { "button-standard secondary" },
+                                    onclick: move |_| color.set(None),
+                                    "None"
+                                }
+                                for swatch in WALLET_COLORS {
+                                    button {
+                                        key: "{swatch}",
+                                        class: if color().as_deref() == Some(swatch) { "button-standard primary" } else { "button-standard secondary" },
+                                        style: "color: {swatch};",
+                                        onclick: move |_| color.set(Some(swatch.to_string())),
+                                        "●"
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "modal-buttons",
+                            button {
+                                class: "button-standard secondary",
+                                onclick: move |_| editing.set(None),
+                                "Cancel"
+                            }
+                            button {
+                                class: "button-standard primary",
+                                onclick: save_appearance,
+                                "Save"
+                            }
+                        }
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Done"
+                    }
+                }
+            }
+        }
+    }
+}
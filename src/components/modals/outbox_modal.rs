@@ -0,0 +1,113 @@
+use dioxus::prelude::*;
+use crate::storage::{cancel_outbox_item, clear_finished_outbox_items, load_outbox};
+use crate::transaction::TransactionClient;
+
+/// Signed transactions waiting for the network, with their status
+#[component]
+pub fn OutboxModal(custom_rpc: Option<String>, onclose: EventHandler<()>) -> Element {
+    let mut items = use_signal(load_outbox);
+    let mut working = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status_message = use_signal(|| None as Option<String>);
+
+    let pending = items().iter().filter(|i| i.is_pending()).count();
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Outbox" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Durable-nonce transactions signed while offline wait here and are broadcast automatically once the network is reachable. Cancelling only stops this device from sending one; the signed transaction stays valid until its nonce is advanced."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(status) = status_message() {
+                    div { class: "info-message", "{status}" }
+                }
+
+                if items().is_empty() {
+                    div { class: "help-text", "Nothing queued." }
+                }
+
+                for item in items() {
+                    div {
+                        key: "{item.signature}",
+                        class: "wallet-field",
+                        label { "{item.description}" }
+                        div { class: "address-display", "{item.signature}" }
+                        div {
+                            class: if item.is_pending() { "help-text" } else { "info-message" },
+                            "{item.status_label()}"
+                        }
+                        if item.is_pending() {
+                            button {
+                                class: "button-standard secondary",
+                                onclick: {
+                                    let signature = item.signature.clone();
+                                    move |_| match cancel_outbox_item(&signature) {
+                                        Ok(()) => items.set(load_outbox()),
+                                        Err(e) => error_message.set(Some(e)),
+                                    }
+                                },
+                                "Cancel"
+                            }
+                        }
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        disabled: items().iter().all(|i| i.is_pending()),
+                        onclick: move |_| {
+                            match clear_finished_outbox_items() {
+                                Ok(()) => items.set(load_outbox()),
+                                Err(e) => error_message.set(Some(e)),
+                            }
+                        },
+                        "Clear Finished"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: working() || pending == 0,
+                        onclick: move |_| {
+                            let client = TransactionClient::new(custom_rpc.as_deref());
+                            working.set(true);
+                            error_message.set(None);
+                            status_message.set(None);
+                            spawn(async move {
+                                let sent = client.flush_outbox().await;
+                                items.set(load_outbox());
+                                if items.peek().iter().any(|i| i.is_pending()) {
+                                    status_message.set(Some(format!("Sent {}; the network still looks unreachable", sent)));
+                                } else {
+                                    status_message.set(Some(format!("Sent {}", sent)));
+                                }
+                                working.set(false);
+                            });
+                        },
+                        if working() { "Sending..." } else { "Send Now" }
+                    }
+                }
+            }
+        }
+    }
+}
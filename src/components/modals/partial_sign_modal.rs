@@ -0,0 +1,295 @@
+use dioxus::prelude::*;
+use crate::wallet::WalletInfo;
+use crate::hardware::HardwareWallet;
+use crate::signing::active_wallet_signer;
+use crate::signing::partial::{decode_transaction, encode_transaction, is_fully_signed, merge_signatures, partially_sign, signer_statuses};
+use crate::storage::{read_export_file, save_export_file};
+use crate::transaction::{SendOutcome, TransactionClient};
+use qrcode::{QrCode, render::svg};
+use solana_sdk::transaction::VersionedTransaction;
+use std::sync::Arc;
+
+fn qr_code_svg(data: &str) -> Option<String> {
+    QrCode::new(data).ok().map(|qr| {
+        qr.render()
+            .min_dimensions(240, 240)
+            .dark_color(svg::Color("#000000"))
+            .light_color(svg::Color("#ffffff"))
+            .build()
+    })
+}
+
+/// Sign a multisig / shared-custody transaction, exchange it with co-signers and broadcast it
+#[component]
+pub fn PartialSignModal(
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut input = use_signal(|| String::new());
+    let mut file_path = use_signal(|| String::new());
+    let mut cosigner_input = use_signal(|| String::new());
+    let mut transaction = use_signal(|| None as Option<VersionedTransaction>);
+    let mut our_address = use_signal(|| None as Option<String>);
+    let mut show_qr = use_signal(|| false);
+    let mut busy = use_signal(|| false);
+    let mut status = use_signal(|| None as Option<String>);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    let hw_for_address = hardware_wallet.clone();
+    let wallet_for_address = wallet.clone();
+    use_effect(move || {
+        let hw = hw_for_address.clone();
+        let wallet = wallet_for_address.clone();
+        spawn(async move {
+            let address = match hw {
+                Some(hw) => hw.get_public_key().await.ok(),
+                None => wallet.map(|w| w.address),
+            };
+            our_address.set(address);
+        });
+    });
+
+    let mut load = move |encoded: String| {
+        error_message.set(None);
+        status.set(None);
+        show_qr.set(false);
+        match decode_transaction(&encoded) {
+            Ok(tx) => transaction.set(Some(tx)),
+            Err(e) => error_message.set(Some(e.to_string())),
+        }
+    };
+
+    let exported = transaction().and_then(|tx| encode_transaction(&tx).ok());
+    let statuses = transaction().map(|tx| signer_statuses(&tx)).unwrap_or_default();
+    let we_can_sign = statuses.iter().any(|s| !s.signed && Some(s.pubkey.to_string()) == our_address());
+    let complete = transaction().map(|tx| is_fully_signed(&tx)).unwrap_or(false);
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Co-sign Transaction" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(message) = status() {
+                    div { class: "info-message", "{message}" }
+                }
+
+                if transaction().is_none() {
+                    div {
+                        class: "wallet-field",
+                        label { "Transaction (base64 or base58):" }
+                        textarea {
+                            value: "{input}",
+                            rows: "5",
+                            oninput: move |e| input.set(e.value()),
+                            placeholder: "Paste the transaction to sign"
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Or load from file:" }
+                        input {
+                            value: "{file_path}",
+                            oninput: move |e| file_path.set(e.value()),
+                            placeholder: "/path/to/transaction.txt"
+                        }
+                    }
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard secondary",
+                            disabled: file_path().trim().is_empty(),
+                            onclick: move |_| {
+                                match read_export_file(file_path().trim()) {
+                                    Ok(contents) => load(contents),
+                                    Err(e) => error_message.set(Some(e)),
+                                }
+                            },
+                            "Load File"
+                        }
+                        button {
+                            class: "button-standard primary",
+                            disabled: input().trim().is_empty(),
+                            onclick: move |_| load(input()),
+                            "Load"
+                        }
+                    }
+                } else {
+                    div {
+                        class: "wallet-field",
+                        label { "Required signers:" }
+                        for signer in statuses.iter() {
+                            div {
+                                key: "{signer.pubkey}",
+                                class: "help-text",
+                                if signer.signed { "✅ " } else { "⏳ " }
+                                "{signer.pubkey}"
+                                if Some(signer.pubkey.to_string()) == our_address() { " (you)" }
+                            }
+                        }
+                    }
+
+                    if we_can_sign {
+                        div { class: "modal-buttons",
+                            button {
+                                class: "button-standard primary",
+                                disabled: busy(),
+                                onclick: move |_| {
+                                    let Some(mut tx) = transaction() else { return; };
+                                    let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+                                        Ok(signer) => signer,
+                                        Err(e) => {
+                                            error_message.set(Some(e));
+                                            return;
+                                        }
+                                    };
+                                    busy.set(true);
+                                    error_message.set(None);
+                                    spawn(async move {
+                                        match partially_sign(&mut tx, signer.as_ref()).await {
+                                            Ok(()) => {
+                                                transaction.set(Some(tx));
+                                                status.set(Some("Signed. Share the transaction with the remaining signers.".to_string()));
+                                            }
+                                            Err(e) => error_message.set(Some(format!("Signing failed: {}", e))),
+                                        }
+                                        busy.set(false);
+                                    });
+                                },
+                                if busy() { "Signing..." } else { "Add My Signature" }
+                            }
+                        }
+                    }
+
+                    if !complete {
+                        div {
+                            class: "wallet-field",
+                            label { "Co-signer copy:" }
+                            textarea {
+                                value: "{cosigner_input}",
+                                rows: "3",
+                                oninput: move |e| cosigner_input.set(e.value()),
+                                placeholder: "Paste a copy signed by another signer"
+                            }
+                        }
+                        div { class: "modal-buttons",
+                            button {
+                                class: "button-standard secondary",
+                                disabled: cosigner_input().trim().is_empty(),
+                                onclick: move |_| {
+                                    let Some(mut tx) = transaction() else { return; };
+                                    error_message.set(None);
+                                    let merged = decode_transaction(&cosigner_input())
+                                        .and_then(|other| merge_signatures(&mut tx, &other));
+                                    match merged {
+                                        Ok(count) => {
+                                            transaction.set(Some(tx));
+                                            cosigner_input.set(String::new());
+                                            status.set(Some(format!("Merged {} signature(s)", count)));
+                                        }
+                                        Err(e) => error_message.set(Some(e.to_string())),
+                                    }
+                                },
+                                "Merge Signatures"
+                            }
+                        }
+                    }
+
+                    if let Some(encoded) = exported.clone() {
+                        div {
+                            class: "wallet-field",
+                            label { "Export:" }
+                            textarea {
+                                value: "{encoded}",
+                                rows: "4",
+                                readonly: true,
+                            }
+                        }
+                        if show_qr() {
+                            if let Some(svg) = qr_code_svg(&encoded) {
+                                div {
+                                    class: "qr-code-container",
+                                    dangerous_inner_html: "{svg}"
+                                }
+                            } else {
+                                div { class: "error-message", "Transaction is too large for a QR code" }
+                            }
+                        }
+                        div { class: "modal-buttons",
+                            button {
+                                class: "button-standard secondary",
+                                onclick: move |_| show_qr.set(!show_qr()),
+                                if show_qr() { "Hide QR" } else { "Show QR" }
+                            }
+                            button {
+                                class: "button-standard secondary",
+                                onclick: move |_| {
+                                    let Some(encoded) = transaction().and_then(|tx| encode_transaction(&tx).ok()) else { return; };
+                                    let file_name = format!("partial_tx_{}.txt", chrono::Utc::now().timestamp());
+                                    match save_export_file(&file_name, &encoded) {
+                                        Ok(path) => status.set(Some(format!("Saved to {}", path))),
+                                        Err(e) => error_message.set(Some(e)),
+                                    }
+                                },
+                                "Save to File"
+                            }
+                        }
+                    }
+
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| {
+                                transaction.set(None);
+                                status.set(None);
+                                error_message.set(None);
+                            },
+                            "Start Over"
+                        }
+                        button {
+                            class: "button-standard primary",
+                            disabled: busy() || !complete,
+                            onclick: move |_| {
+                                let Some(tx) = transaction() else { return; };
+                                let client = TransactionClient::new(custom_rpc.as_deref());
+                                busy.set(true);
+                                error_message.set(None);
+                                spawn(async move {
+                                    let result = match bincode::serialize(&tx) {
+                                        Ok(bytes) => client.send_or_queue(&bs58::encode(bytes).into_string(), "Multisig transaction").await
+                                            .map_err(|e| e.to_string()),
+                                        Err(e) => Err(format!("Failed to serialize transaction: {}", e)),
+                                    };
+                                    match result {
+                                        Ok(SendOutcome::Sent(signature)) => status.set(Some(format!("Broadcast: {}", signature))),
+                                        Ok(SendOutcome::Queued(_)) => status.set(Some("Offline: queued in the Outbox and will be broadcast when the network is back".to_string())),
+                                        Err(e) => error_message.set(Some(format!("Broadcast failed: {}", e))),
+                                    }
+                                    busy.set(false);
+                                });
+                            },
+                            if complete { "Broadcast" } else { "Waiting for Signatures" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
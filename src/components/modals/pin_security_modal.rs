@@ -0,0 +1,130 @@
+use dioxus::prelude::*;
+use crate::auto_lock::{idle_label, AutoLockSettings, IDLE_OPTIONS};
+use crate::pin::{format_lockout, lockout_secs, FREE_PIN_ATTEMPTS, MAX_PIN_ATTEMPTS};
+use crate::storage::{load_auto_lock_settings, save_auto_lock_settings, set_wipe_after_max_attempts, wipes_after_max_attempts};
+
+/// When the app locks itself, how wrong PINs are handled, and the opt-in
+/// wipe for high-threat users
+#[component]
+pub fn PinSecurityModal(onclose: EventHandler<()>) -> Element {
+    let mut wipe = use_signal(wipes_after_max_attempts);
+    let mut confirming = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut auto_lock = use_signal(load_auto_lock_settings);
+
+    let mut save_auto_lock = move |settings: AutoLockSettings| match save_auto_lock_settings(&settings) {
+        Ok(()) => auto_lock.set(settings),
+        Err(e) => error_message.set(Some(e)),
+    };
+
+    let mut save = move |enabled: bool| match set_wipe_after_max_attempts(enabled) {
+        Ok(()) => {
+            wipe.set(enabled);
+            confirming.set(false);
+        }
+        Err(e) => error_message.set(Some(e)),
+    };
+
+    let first_lockout = format_lockout(lockout_secs(FREE_PIN_ATTEMPTS + 1));
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "PIN Security" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Lock after inactivity:" }
+                    div { class: "modal-buttons",
+                        for minutes in IDLE_OPTIONS {
+                            button {
+                                key: "{idle_label(minutes)}",
+                                class: if auto_lock().idle_minutes == minutes { "button-standard primary" } else { "button-standard secondary" },
+                                onclick: move |_| save_auto_lock(AutoLockSettings { idle_minutes: minutes, ..auto_lock() }),
+                                "{idle_label(minutes)}"
+                            }
+                        }
+                    }
+                    label {
+                        class: "help-text",
+                        input {
+                            r#type: "checkbox",
+                            checked: auto_lock().lock_on_background,
+                            oninput: move |_| save_auto_lock(AutoLockSettings { lock_on_background: !auto_lock().lock_on_background, ..auto_lock() }),
+                        }
+                        " Lock when the app goes to the background"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "After {FREE_PIN_ATTEMPTS} wrong PINs in a row, PIN entry locks for {first_lockout}, doubling with each further wrong PIN up to an hour."
+                }
+
+                div {
+                    class: "wallet-field",
+                    label {
+                        class: "help-text",
+                        input {
+                            r#type: "checkbox",
+                            checked: wipe() || confirming(),
+                            oninput: move |_| {
+                                if wipe() {
+                                    save(false);
+                                } else {
+                                    confirming.set(!confirming());
+                                }
+                            },
+                        }
+                        " Wipe wallets after {MAX_PIN_ATTEMPTS} wrong PINs"
+                    }
+                }
+
+                if confirming() {
+                    div {
+                        class: "warning-message danger",
+                        "Every wallet key on this device will be deleted after {MAX_PIN_ATTEMPTS} wrong PINs in a row. Only turn this on with a backup of every wallet you can restore from."
+                    }
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| confirming.set(false),
+                            "Cancel"
+                        }
+                        button {
+                            class: "button-standard primary",
+                            onclick: move |_| save(true),
+                            "Turn On Wipe"
+                        }
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Done"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use dioxus::prelude::*;
+use crate::prices::{self, PriceProviderSettings};
+use std::collections::HashMap;
+
+/// Enable, order and health-check the price providers. Prices are asked from
+/// the enabled providers top to bottom, each one only for the tokens the
+/// ones above couldn't price.
+#[component]
+pub fn PriceProvidersModal(onclose: EventHandler<()>) -> Element {
+    let mut settings = use_signal(prices::price_provider_settings);
+    let mut health = use_signal(HashMap::<String, Result<u128, String>>::new);
+    let mut checking = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    let check_health = move |_| {
+        let current = settings();
+        checking.set(true);
+        health.set(HashMap::new());
+        spawn(async move {
+            for entry in &current.providers {
+                let Some(provider) = prices::price_provider(&entry.id, &current) else { continue };
+                let result = provider.health_check().await.map(|elapsed| elapsed.as_millis());
+                health.write().insert(entry.id.clone(), result);
+            }
+            checking.set(false);
+        });
+    };
+
+    let save = move |_| {
+        let current: PriceProviderSettings = settings();
+        if !current.providers.iter().any(|entry| entry.enabled) {
+            error_message.set(Some("Enable at least one provider".to_string()));
+            return;
+        }
+        match prices::save_price_provider_settings(current) {
+            Ok(()) => onclose.call(()),
+            Err(e) => error_message.set(Some(e)),
+        }
+    };
+
+    let entries = settings().providers;
+    let provider_count = entries.len();
+    let birdeye_key = settings().birdeye_api_key.unwrap_or_default();
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Price Providers" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Enabled providers are asked top to bottom; each one only prices the tokens the ones above it couldn't."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                for (index, entry) in entries.into_iter().enumerate() {
+                    {
+                        let name = prices::price_provider(&entry.id, &settings())
+                            .map(|provider| provider.name())
+                            .unwrap_or("Unknown");
+                        let status = match health().get(&entry.id) {
+                            Some(Ok(ms)) => format!("✅ {} ms", ms),
+                            Some(Err(e)) => format!("❌ {}", e),
+                            None => String::new(),
+                        };
+                        rsx! {
+                            div {
+                                key: "{entry.id}",
+                                class: "wallet-field price-provider-row",
+                                label {
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: entry.enabled,
+                                        onchange: move |e| {
+                                            settings.write().providers[index].enabled = e.checked();
+                                        },
+                                    }
+                                    " {index + 1}. {name}"
+                                }
+                                button {
+                                    class: "button-standard secondary",
+                                    disabled: index == 0,
+                                    onclick: move |_| settings.write().move_provider(index, -1),
+                                    "↑"
+                                }
+                                button {
+                                    class: "button-standard secondary",
+                                    disabled: index + 1 == provider_count,
+                                    onclick: move |_| settings.write().move_provider(index, 1),
+                                    "↓"
+                                }
+                                if !status.is_empty() {
+                                    div { class: "help-text", "{status}" }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Birdeye API key:" }
+                    input {
+                        r#type: "password",
+                        value: "{birdeye_key}",
+                        placeholder: "Only needed when Birdeye is enabled",
+                        oninput: move |e| {
+                            let key = e.value();
+                            settings.write().birdeye_api_key = (!key.trim().is_empty()).then(|| key.trim().to_string());
+                        },
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        disabled: checking(),
+                        onclick: check_health,
+                        if checking() { "Checking..." } else { "Check Health" }
+                    }
+                    button {
+                        class: "button-standard primary",
+                        onclick: save,
+                        "Save"
+                    }
+                }
+            }
+        }
+    }
+}
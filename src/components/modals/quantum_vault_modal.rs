@@ -44,7 +44,7 @@ fn QuantumVaultSuccessModal(
     vault_address: Option<String>,
     onclose: EventHandler<()>,
 ) -> Element {
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     
     rsx! {
         div {
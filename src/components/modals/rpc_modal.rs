@@ -1,11 +1,73 @@
 use dioxus::prelude::*;
-use crate::rpc;
+use crate::config::network::{self, Network};
+use crate::config::providers::{self, PROVIDERS};
+use crate::rpc_pool;
+use crate::storage::{load_rpc_auto_select, load_rpc_fallbacks, save_rpc_auto_select, save_rpc_fallbacks};
 
 #[component]
 pub fn RpcModal(current_rpc: Option<String>, onclose: EventHandler<()>, onsave: EventHandler<String>) -> Element {
     let mut rpc_url = use_signal(|| current_rpc.clone().unwrap_or_default());
+    let mut fallbacks = use_signal(|| load_rpc_fallbacks().join("\n"));
+    let mut headers = use_signal(|| rpc_pool::format_header_lines(&rpc_pool::endpoint_headers()));
     let mut error_message = use_signal(|| None as Option<String>);
     let mut testing = use_signal(|| false);
+    let mut auto_select = use_signal(load_rpc_auto_select);
+    let mut benchmarks = use_signal(rpc_pool::last_benchmark);
+    let mut benchmarking = use_signal(|| false);
+    let mut network_choice = use_signal(|| network::active().label().to_lowercase());
+    let mut custom_network_url = use_signal(|| match network::active() {
+        Network::Custom { rpc_url } => rpc_url,
+        _ => String::new(),
+    });
+    let mut metrics = use_signal(rpc_pool::metrics_snapshot);
+    let mut setup_hint = use_signal(|| None as Option<&'static str>);
+    let detected = {
+        let url = rpc_url();
+        let url = if url.trim().is_empty() { rpc_pool::DEFAULT_RPC_URL.to_string() } else { url };
+        let capabilities = providers::capabilities(&url);
+        let mark = |supported: bool| if supported { "✓" } else { "✗" };
+        format!(
+            "{}: DAS {} · WebSockets {} · Priority fees {}",
+            providers::provider_for_url(&url).map(|p| p.name).unwrap_or("Unknown provider"),
+            mark(capabilities.das),
+            mark(capabilities.websockets),
+            mark(capabilities.priority_fee_api)
+        )
+    };
+    let health = rpc_pool::health_snapshot();
+    let diagnostic_rows: Vec<(String, String, bool)> = metrics()
+        .into_iter()
+        .map(|(url, m)| {
+            let status = health.iter().find(|(u, _)| *u == url).map(|(_, h)| h.clone()).unwrap_or_default();
+            let p95 = m.p95_ms().map(|ms| format!("{} ms", ms)).unwrap_or_else(|| "–".to_string());
+            let mut text = format!(
+                "{} requests, {} errors ({:.0}%), p95 {}, {:.1} KB sent, {:.1} KB received",
+                m.requests,
+                m.errors,
+                m.error_rate() * 100.0,
+                p95,
+                m.bytes_sent as f64 / 1024.0,
+                m.bytes_received as f64 / 1024.0
+            );
+            if status.cooling_down() {
+                text.push_str(&format!(", cooling down after {} failures", status.consecutive_failures));
+            }
+            (url, text, status.cooling_down())
+        })
+        .collect();
+    let benchmark_rows: Vec<(String, String, bool)> = benchmarks()
+        .into_iter()
+        .map(|result| {
+            let text = match (result.latency_ms, result.slots_behind, &result.error) {
+                (Some(latency), Some(behind), _) => format!("{} ms, {} slots behind", latency, behind),
+                (_, _, Some(error)) => format!("failed: {}", error),
+                _ => "no answer".to_string(),
+            };
+            let usable = result.usable();
+            (result.url, text, usable)
+        })
+        .collect();
+    let fastest = benchmarks().into_iter().find(|r| r.usable()).map(|r| r.url);
     
     rsx! {
         div {
@@ -34,6 +96,57 @@ pub fn RpcModal(current_rpc: Option<String>, onclose: EventHandler<()>, onsave:
                     }
                 }
                 
+                div {
+                    class: "wallet-field",
+                    label { "Network:" }
+                    select {
+                        onchange: move |e| network_choice.set(e.value()),
+                        option { value: "mainnet", selected: network_choice() == "mainnet", "Mainnet" }
+                        option { value: "devnet", selected: network_choice() == "devnet", "Devnet" }
+                        option { value: "testnet", selected: network_choice() == "testnet", "Testnet" }
+                        // Local test validator, for development
+                        if cfg!(debug_assertions) || network_choice() == "localnet" {
+                            option { value: "localnet", selected: network_choice() == "localnet", "Localnet (solana-test-validator)" }
+                        }
+                        option { value: "custom", selected: network_choice() == "custom", "Custom" }
+                    }
+                    if network_choice() == "custom" {
+                        input {
+                            value: "{custom_network_url}",
+                            oninput: move |e| custom_network_url.set(e.value()),
+                            placeholder: "http://127.0.0.1:8899"
+                        }
+                    }
+                    if network_choice() != "mainnet" {
+                        div {
+                            class: "warning-message",
+                            "All requests go to this network's RPC. The RPC settings below only apply on mainnet."
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Provider presets:" }
+                    div {
+                        class: "modal-buttons",
+                        for provider in PROVIDERS.iter() {
+                            button {
+                                key: "{provider.id}",
+                                class: "button-standard secondary",
+                                onclick: move |_| {
+                                    rpc_url.set(provider.url_template.to_string());
+                                    setup_hint.set(Some(provider.setup_hint));
+                                },
+                                "{provider.name}"
+                            }
+                        }
+                    }
+                    if let Some(hint) = setup_hint() {
+                        div { class: "info-message", "{hint}" }
+                    }
+                }
+
                 div {
                     class: "wallet-field",
                     label { "RPC URL:" }
@@ -46,14 +159,126 @@ pub fn RpcModal(current_rpc: Option<String>, onclose: EventHandler<()>, onsave:
                         class: "help-text",
                         "Leave empty to use default RPC"
                     }
+                    div {
+                        class: "help-text",
+                        "{detected}"
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Fallback RPCs:" }
+                    textarea {
+                        value: "{fallbacks}",
+                        oninput: move |e| fallbacks.set(e.value()),
+                        placeholder: "One URL per line",
+                        rows: "3",
+                    }
+                    div {
+                        class: "help-text",
+                        "Used in order when the RPC above times out, rate limits or errors"
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Request headers:" }
+                    textarea {
+                        value: "{headers}",
+                        oninput: move |e| headers.set(e.value()),
+                        placeholder: "https://your-rpc-url.com Authorization: Bearer <key>",
+                        rows: "2",
+                    }
+                    div {
+                        class: "help-text",
+                        "One header per line, prefixed with the endpoint it's sent to"
+                    }
                 }
                 
-                if let Some(current) = current_rpc {
+                div {
+                    class: "wallet-field",
+                    label {
+                        input {
+                            r#type: "checkbox",
+                            checked: auto_select(),
+                            onchange: move |e| auto_select.set(e.checked()),
+                        }
+                        " Auto-select the fastest endpoint"
+                    }
+                    div {
+                        class: "help-text",
+                        "Endpoints are benchmarked on latency and slot freshness every 10 minutes"
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Benchmark:" }
+                    if benchmark_rows.is_empty() {
+                        div { class: "help-text", "Not run yet" }
+                    }
+                    for (url, text, usable) in benchmark_rows {
+                        div {
+                            key: "bench-{url}",
+                            class: if usable { "help-text" } else { "warning-message" },
+                            if fastest.as_ref() == Some(&url) { "⚡ {url}: {text}" } else { "{url}: {text}" }
+                        }
+                    }
+                    button {
+                        class: "button-standard secondary",
+                        disabled: benchmarking(),
+                        onclick: {
+                            let current = current_rpc.clone();
+                            move |_| {
+                                let primary = current.clone().unwrap_or_else(|| rpc_pool::DEFAULT_RPC_URL.to_string());
+                                benchmarking.set(true);
+                                spawn(async move {
+                                    benchmarks.set(rpc_pool::benchmark(&primary).await);
+                                    benchmarking.set(false);
+                                });
+                            }
+                        },
+                        if benchmarking() { "Benchmarking..." } else { "Run Benchmark" }
+                    }
+                }
+
+                if let Some(current) = current_rpc.clone() {
                     div {
                         class: "info-message",
                         "Current RPC: {current}"
                     }
                 }
+
+                div {
+                    class: "wallet-field",
+                    label { "Diagnostics:" }
+                    if diagnostic_rows.is_empty() {
+                        div { class: "help-text", "No requests yet" }
+                    }
+                    for (url, text, cooling_down) in diagnostic_rows {
+                        div {
+                            key: "diag-{url}",
+                            class: if cooling_down { "warning-message" } else { "help-text" },
+                            "{url}: {text}"
+                        }
+                    }
+                    div {
+                        class: "modal-buttons",
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| metrics.set(rpc_pool::metrics_snapshot()),
+                            "Refresh"
+                        }
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| {
+                                rpc_pool::reset_metrics();
+                                metrics.set(rpc_pool::metrics_snapshot());
+                            },
+                            "Reset"
+                        }
+                    }
+                }
                 
                 div { class: "modal-buttons",
                     button {
@@ -69,9 +294,9 @@ pub fn RpcModal(current_rpc: Option<String>, onclose: EventHandler<()>, onsave:
                             let test_rpc = rpc_url();
                             
                             spawn(async move {
-                                // Test the RPC with a known address
-                                match rpc::get_balance("11111111111111111111111111111111", 
-                                    if test_rpc.is_empty() { None } else { Some(&test_rpc) }).await {
+                                // Test this endpoint alone, without failing over
+                                let url = if test_rpc.is_empty() { rpc_pool::DEFAULT_RPC_URL.to_string() } else { test_rpc };
+                                match rpc_pool::check_endpoint(&url).await {
                                     Ok(_) => {
                                         error_message.set(None);
                                         testing.set(false);
@@ -89,6 +314,44 @@ pub fn RpcModal(current_rpc: Option<String>, onclose: EventHandler<()>, onsave:
                     button {
                         class: "button-standard primary",
                         onclick: move |_| {
+                            let endpoints: Vec<String> = fallbacks()
+                                .lines()
+                                .map(|line| line.trim().to_string())
+                                .filter(|line| !line.is_empty())
+                                .collect();
+                            if rpc_url().contains('<') {
+                                error_message.set(Some("Replace the <placeholders> in the RPC URL first".to_string()));
+                                return;
+                            }
+                            let endpoint_headers = match rpc_pool::parse_header_lines(&headers()) {
+                                Ok(endpoint_headers) => endpoint_headers,
+                                Err(e) => {
+                                    error_message.set(Some(e));
+                                    return;
+                                }
+                            };
+                            let selected_network = match network_choice().as_str() {
+                                "devnet" => Network::Devnet,
+                                "testnet" => Network::Testnet,
+                                "localnet" => Network::Localnet,
+                                "custom" => {
+                                    let url = custom_network_url().trim().to_string();
+                                    if url.is_empty() {
+                                        error_message.set(Some("Enter the custom network's RPC URL".to_string()));
+                                        return;
+                                    }
+                                    Network::Custom { rpc_url: url }
+                                }
+                                _ => Network::Mainnet,
+                            };
+                            if let Err(e) = save_rpc_fallbacks(&endpoints)
+                                .and_then(|_| save_rpc_auto_select(auto_select()))
+                                .and_then(|_| rpc_pool::save_endpoint_headers(endpoint_headers))
+                                .and_then(|_| network::set_active(selected_network))
+                            {
+                                error_message.set(Some(e));
+                                return;
+                            }
                             onsave.call(rpc_url());
                         },
                         "Save"
@@ -97,4 +360,4 @@ pub fn RpcModal(current_rpc: Option<String>, onclose: EventHandler<()>, onsave:
             }
         }
     }
-}
\ No newline at end of file
+}
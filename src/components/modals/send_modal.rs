@@ -1,10 +1,21 @@
 use dioxus::prelude::*;
-use crate::wallet::{Wallet, WalletInfo};
+use crate::wallet::WalletInfo;
 use crate::hardware::HardwareWallet;
 use crate::transaction::TransactionClient;
 use crate::signing::hardware::HardwareSigner;
+use crate::signing::SignerType;
 use crate::rpc;
+use crate::policy::{self, RiskTier};
+use crate::storage::{get_current_jito_settings, load_fee_sponsor_settings, load_wallet_profile};
+use crate::tips::tip_for;
+use crate::history;
+use crate::transaction::MAX_MEMO_BYTES;
 use crate::components::address_input::AddressInput; // ← ADD THIS IMPORT
+use crate::components::simulation_preview::{SimulationPreviewPanel, PreviewState, DrainWarning};
+use crate::components::stuck_transaction::StuckTransactionMonitor;
+use crate::components::transaction_export::TransactionExportPanel;
+use crate::components::tip_picker::TipPicker;
+use solana_sdk::transaction::VersionedTransaction;
 use solana_sdk::pubkey::Pubkey; // ← ADD THIS IMPORT
 use std::sync::Arc;
 
@@ -68,11 +79,16 @@ fn HardwareApprovalOverlay(oncancel: EventHandler<()>) -> Element {
 pub fn TransactionSuccessModal(
     signature: String,
     was_hardware_wallet: bool,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    onreplaced: EventHandler<String>,
     onclose: EventHandler<()>,
 ) -> Element {
     // Explorer links - Solscan and Orb
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     let orb_url = format!("https://orb.helius.dev/tx/{}?cluster=mainnet-beta&tab=summary", signature);
+    let monitored_signature = signature.clone();
     
     rsx! {
         div {
@@ -119,6 +135,15 @@ pub fn TransactionSuccessModal(
                         }
                     }
                     
+                    StuckTransactionMonitor {
+                        key: "{monitored_signature}",
+                        signature: monitored_signature.clone(),
+                        wallet: wallet.clone(),
+                        hardware_wallet: hardware_wallet.clone(),
+                        custom_rpc: custom_rpc.clone(),
+                        onreplaced: move |replacement| onreplaced.call(replacement),
+                    }
+
                     div {
                         class: "explorer-links",
                         p { "View transaction in explorer:" }
@@ -189,6 +214,68 @@ pub fn SendModalWithHardware(
     // Add state for hardware wallet approval overlay - always declared
     let mut show_hardware_approval = use_signal(|| false);
 
+    // Simulate the transfer before any signature is requested
+    let mut preview_state = use_signal(|| PreviewState::Idle);
+    let mut drain_confirmed = use_signal(|| false);
+    // Built but not sent, for export to another tool or machine
+    let mut export_tx = use_signal(|| None as Option<VersionedTransaction>);
+    let mut exporting = use_signal(|| false);
+    // Jito tip for this transaction, defaulting to the one in Jito settings
+    let jito_enabled = get_current_jito_settings().jito_tx;
+    let mut tip_level = use_signal(|| get_current_jito_settings().tip_level);
+    // Sent on-chain with the transfer
+    let mut memo = use_signal(String::new);
+    // Kept on this device only, shown in the history
+    let mut label = use_signal(String::new);
+    let wallet_for_preview = wallet.clone();
+    let hardware_for_preview = hardware_wallet.clone();
+    let custom_rpc_for_preview = custom_rpc.clone();
+    use_effect(move || {
+        let recipient_pubkey = *resolved_recipient.read();
+        let amount_str = amount.read().clone();
+        let amount_value = amount_str.parse::<f64>().ok().filter(|a| *a > 0.0);
+        let level = tip_level();
+
+        let (Some(recipient_pubkey), Some(amount_value)) = (recipient_pubkey, amount_value) else {
+            preview_state.set(PreviewState::Idle);
+            return;
+        };
+
+        let wallet_info = wallet_for_preview.clone();
+        let hardware = hardware_for_preview.clone();
+        let rpc_url = custom_rpc_for_preview.clone();
+        preview_state.set(PreviewState::Loading);
+        drain_confirmed.set(false);
+
+        spawn(async move {
+            let from_address = if let Some(hw) = hardware {
+                hw.get_public_key().await.ok()
+            } else {
+                wallet_info.map(|w| w.address)
+            };
+            let Some(from_address) = from_address else {
+                preview_state.set(PreviewState::Idle);
+                return;
+            };
+
+            let client = TransactionClient::new(rpc_url.as_deref())
+                .with_jito_tip(tip_for(level).await)
+                .with_memo(&memo.peek());
+            let recipient_address = recipient_pubkey.to_string();
+            let result = client.preview_sol_transfer(&from_address, &recipient_address, amount_value).await;
+
+            // Ignore results for inputs the user has since changed
+            if *amount.peek() != amount_str || *resolved_recipient.peek() != Some(recipient_pubkey) || *tip_level.peek() != level {
+                return;
+            }
+
+            match result {
+                Ok(preview) => preview_state.set(PreviewState::Ready(preview)),
+                Err(e) => preview_state.set(PreviewState::Unavailable(e.to_string())),
+            }
+        });
+    });
+
     // Update the recipient balance checking effect to use resolved recipient
     let custom_rpc_for_effect = custom_rpc.clone();
     use_effect(move || {
@@ -222,6 +309,10 @@ pub fn SendModalWithHardware(
             TransactionSuccessModal {
                 signature: transaction_signature(),
                 was_hardware_wallet: was_hardware_transaction(),
+                wallet: wallet.clone(),
+                hardware_wallet: hardware_wallet.clone(),
+                custom_rpc: custom_rpc.clone(),
+                onreplaced: move |replacement| transaction_signature.set(replacement),
                 onclose: move |_| {
                     show_success_modal.set(false);
                     // Call onsuccess when the user closes the modal
@@ -254,6 +345,8 @@ pub fn SendModalWithHardware(
     } else {
         "No Wallet".to_string()
     };
+    let sender_profile = load_wallet_profile(&display_address);
+    let sender_address = display_address.clone();
 
     rsx! {
         div {
@@ -319,6 +412,14 @@ pub fn SendModalWithHardware(
                     }
                 }
 
+                div {
+                    class: if sender_profile.tier == RiskTier::Hot { "info-message" } else { "warning-message" },
+                    "{sender_profile.tier.icon()} {sender_profile.tier.label()} wallet: {sender_profile.tier.description()}"
+                    if !sender_profile.notes.is_empty() {
+                        div { class: "help-text", "{sender_profile.notes}" }
+                    }
+                }
+
                 // Show error if any
                 if let Some(error) = error_message() {
                     div {
@@ -371,6 +472,53 @@ pub fn SendModalWithHardware(
                     }
                 }
 
+                div {
+                    class: "wallet-field",
+                    label { "Memo (optional, public):" }
+                    input {
+                        value: "{memo}",
+                        oninput: move |e| memo.set(e.value()),
+                        placeholder: "Attached to the transaction on-chain"
+                    }
+                    if memo().trim().len() > MAX_MEMO_BYTES {
+                        div { class: "error-message", "Memo is too long ({memo().trim().len()} of at most {MAX_MEMO_BYTES} bytes)" }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Label (optional, private):" }
+                    input {
+                        value: "{label}",
+                        oninput: move |e| label.set(e.value()),
+                        placeholder: "e.g. Rent — March, shown in your history"
+                    }
+                }
+
+                if jito_enabled {
+                    TipPicker {
+                        selected: tip_level(),
+                        onchange: move |level| tip_level.set(level),
+                    }
+                }
+
+                SimulationPreviewPanel { state: preview_state() }
+                DrainWarning {
+                    state: preview_state(),
+                    confirmed: drain_confirmed(),
+                    onchange: move |confirmed| drain_confirmed.set(confirmed),
+                }
+
+                if let Some(transaction) = export_tx() {
+                    TransactionExportPanel {
+                        transaction,
+                        label: "send-sol".to_string(),
+                        wallet: wallet.clone(),
+                        hardware_wallet: hardware_wallet.clone(),
+                        onclose: move |_| export_tx.set(None),
+                    }
+                }
+
                 if hardware_wallet.is_some() {
                     div {
                         class: "info-message",
@@ -379,6 +527,39 @@ pub fn SendModalWithHardware(
                 }
 
                 div { class: "modal-buttons",
+                    button {
+                        class: "modal-button secondary",
+                        disabled: exporting() || sending() || resolved_recipient.read().is_none() || amount().is_empty() || (preview_state.read().needs_confirmation() && !drain_confirmed()),
+                        onclick: {
+                            let from_address = sender_address.clone();
+                            let rpc_url = custom_rpc.clone();
+                            move |_| {
+                                let Some(recipient_pubkey) = *resolved_recipient.read() else { return };
+                                let Some(amount_value) = amount().parse::<f64>().ok().filter(|a| *a > 0.0) else {
+                                    error_message.set(Some("Invalid amount".to_string()));
+                                    return;
+                                };
+                                let from_address = from_address.clone();
+                                let rpc_url = rpc_url.clone();
+                                let level = tip_level();
+                                exporting.set(true);
+                                error_message.set(None);
+                                spawn(async move {
+                                    let client = TransactionClient::new(rpc_url.as_deref())
+                                        .with_jito_tip(tip_for(level).await)
+                                        .with_memo(&memo.peek());
+                                    let result = client.unsigned_sol_transfer(&from_address, &recipient_pubkey.to_string(), amount_value).await
+                                        .map_err(|e| e.to_string());
+                                    match result {
+                                        Ok(transaction) => export_tx.set(Some(transaction)),
+                                        Err(e) => error_message.set(Some(format!("Failed to build transaction: {}", e))),
+                                    }
+                                    exporting.set(false);
+                                });
+                            }
+                        },
+                        if exporting() { "Building..." } else { "Export" }
+                    }
                     button {
                         class: "modal-button primary",
                         onclick: move |_| {
@@ -391,6 +572,11 @@ pub fn SendModalWithHardware(
                                 }
                             };
 
+                            if let Err(e) = policy::check_transfer(&sender_address, &recipient_pubkey.to_string()) {
+                                error_message.set(Some(e));
+                                return;
+                            }
+
                             error_message.set(None);
                             sending.set(true);
 
@@ -409,6 +595,9 @@ pub fn SendModalWithHardware(
                             let recipient_address = recipient_pubkey.to_string(); // ← USE RESOLVED PUBKEY
                             let amount_str = amount();
                             let rpc_url = custom_rpc.clone();
+                            let level = tip_level();
+                            let memo_text = memo();
+                            let label_text = label();
 
                             // Clone the onhardware event handler for use in async block
                             let onhardware_handler = onhardware.clone();
@@ -434,18 +623,38 @@ pub fn SendModalWithHardware(
 
                                 // ← NO NEED TO VALIDATE recipient_address anymore since it's already a valid pubkey!
 
-                                let client = TransactionClient::new(rpc_url.as_deref());
+                                let save_label = |signature: &str| {
+                                    if let Err(e) = history::set_transaction_label(signature, Some(&label_text)) {
+                                        println!("⚠️ Failed to save transaction label: {}", e);
+                                    }
+                                };
+
+                                let fee_payer = match load_fee_sponsor_settings().fee_payer() {
+                                    Ok(fee_payer) => fee_payer,
+                                    Err(e) => {
+                                        error_message.set(Some(e));
+                                        sending.set(false);
+                                        show_hardware_approval.set(false);
+                                        return;
+                                    }
+                                };
+
+                                let client = TransactionClient::new(rpc_url.as_deref())
+                                    .with_jito_tip(tip_for(level).await)
+                                    .with_memo(&memo_text);
 
                                 // Use hardware wallet if available, otherwise use software wallet
                                 if let Some(hw) = hardware_wallet_clone {
                                     let hw_signer = HardwareSigner::from_wallet(hw.clone());
-                                    match client.send_sol_with_signer(&hw_signer, &recipient_address, amount_value).await {
+                                    match client.send_sol_with_fee_payer(&hw_signer, &fee_payer, &recipient_address, amount_value).await {
                                         Ok(signature) => {
                                             println!("Transaction sent with hardware wallet: {}", signature);
 
                                             // Hide hardware approval overlay
                                             show_hardware_approval.set(false);
 
+                                            save_label(&signature);
+
                                             // Set the transaction signature and show success modal
                                             transaction_signature.set(signature);
                                             sending.set(false);
@@ -459,13 +668,15 @@ pub fn SendModalWithHardware(
                                     }
                                 } else if let Some(wallet_info) = wallet_info {
                                     // Load wallet from wallet info
-                                    match Wallet::from_wallet_info(&wallet_info) {
-                                        Ok(wallet) => {
+                                    match SignerType::for_wallet_info(&wallet_info) {
+                                        Ok(signer) => {
                                             // Send transaction with amount in SOL
-                                            match client.send_sol(&wallet, &recipient_address, amount_value).await {
+                                            match client.send_sol_with_fee_payer(&signer, &fee_payer, &recipient_address, amount_value).await {
                                                 Ok(signature) => {
                                                     println!("Transaction sent: {}", signature);
                                                     
+                                                    save_label(&signature);
+
                                                     // Set the transaction signature and show success modal
                                                     transaction_signature.set(signature);
                                                     sending.set(false);
@@ -489,7 +700,7 @@ pub fn SendModalWithHardware(
                                 }
                             });
                         },
-                        disabled: sending() || resolved_recipient.read().is_none() || amount().is_empty(), // ← UPDATED VALIDATION
+                        disabled: sending() || resolved_recipient.read().is_none() || amount().is_empty() || memo().trim().len() > MAX_MEMO_BYTES || (preview_state.read().needs_confirmation() && !drain_confirmed()), // ← UPDATED VALIDATION
                         if sending() && !show_hardware_approval() { "Sending..." } else { "Send" }
                     }
                 }
@@ -0,0 +1,308 @@
+use dioxus::prelude::*;
+use crate::hardware::HardwareWallet;
+use crate::signing::session::{self, SessionAction, SessionKey, TokenAllowance};
+use crate::signing::active_wallet_signer;
+use crate::storage::{load_session_key, remove_session_key, save_session_key};
+use crate::transaction::TransactionClient;
+use crate::wallet::WalletInfo;
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use std::str::FromStr;
+use std::sync::Arc;
+
+fn parse_sol(value: &str, field: &str) -> Result<u64, String> {
+    value.trim().parse::<f64>()
+        .ok()
+        .filter(|v| *v > 0.0)
+        .map(|v| (v * 1_000_000_000.0).round() as u64)
+        .ok_or_else(|| format!("Enter a valid {}", field))
+}
+
+/// Start, inspect and end the session key that signs small automated
+/// actions for this wallet
+#[component]
+pub fn SessionKeyModal(
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    owner_address: String,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let owner_for_load = owner_address.clone();
+    let mut active = use_signal(move || load_session_key(&owner_for_load));
+    let mut hours = use_signal(|| "24".to_string());
+    let mut sol_cap = use_signal(|| "0.05".to_string());
+    let mut per_action = use_signal(|| "0.01".to_string());
+    let mut token_mint = use_signal(|| String::new());
+    let mut token_cap = use_signal(|| String::new());
+    let mut actions = use_signal(|| SessionAction::ALL.to_vec());
+    let mut working = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status_message = use_signal(|| None as Option<String>);
+
+    let start_wallet = wallet.clone();
+    let start_hw = hardware_wallet.clone();
+    let start_rpc = custom_rpc.clone();
+    let start_owner = owner_address.clone();
+    let start = move |_| {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let duration = match hours().trim().parse::<u64>() {
+            Ok(h) if (1..=168).contains(&h) => h * 3_600,
+            _ => {
+                error_message.set(Some("Duration must be between 1 and 168 hours".to_string()));
+                return;
+            }
+        };
+        let (cap, max_per_action) = match (parse_sol(&sol_cap(), "SOL cap"), parse_sol(&per_action(), "per-action limit")) {
+            (Ok(cap), Ok(max)) if max <= cap => (cap, max),
+            (Ok(_), Ok(_)) => {
+                error_message.set(Some("The per-action limit can't exceed the SOL cap".to_string()));
+                return;
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        if actions().is_empty() {
+            error_message.set(Some("Choose at least one action".to_string()));
+            return;
+        }
+        let signer = match active_wallet_signer(start_hw.clone(), start_wallet.clone()) {
+            Ok(signer) => signer,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+
+        let mut new_session = SessionKey::generate(&start_owner, now, duration, cap, max_per_action, actions());
+        let mint_text = token_mint().trim().to_string();
+        let token_cap_text = token_cap().trim().to_string();
+        let client = TransactionClient::new(start_rpc.as_deref());
+        working.set(true);
+        error_message.set(None);
+        spawn(async move {
+            if !mint_text.is_empty() {
+                let allowance = async {
+                    let mint = Pubkey::from_str(&mint_text).map_err(|_| "Invalid token mint".to_string())?;
+                    let owner = Pubkey::from_str(&new_session.owner).map_err(|e| e.to_string())?;
+                    let info = client.get_mint_info(&mint).await.map_err(|e| e.to_string())?;
+                    let amount = token_cap_text.parse::<f64>().ok().filter(|v| *v > 0.0)
+                        .ok_or("Enter a valid token cap")?;
+                    Ok::<_, String>(TokenAllowance {
+                        mint: mint.to_string(),
+                        token_account: get_associated_token_address_with_program_id(&owner, &mint, &info.program_id).to_string(),
+                        token_program: info.program_id.to_string(),
+                        decimals: info.decimals,
+                        cap: (amount * 10_f64.powi(info.decimals as i32)).round() as u64,
+                        spent: 0,
+                    })
+                }.await;
+                match allowance {
+                    Ok(allowance) => new_session.tokens.push(allowance),
+                    Err(e) => {
+                        error_message.set(Some(e));
+                        working.set(false);
+                        return;
+                    }
+                }
+            }
+
+            // Keep the key before funding it so the funds can always be swept back
+            if let Err(e) = save_session_key(&new_session) {
+                error_message.set(Some(e));
+                working.set(false);
+                return;
+            }
+            match session::start_session(&client, signer.as_ref(), &new_session).await {
+                Ok(signature) => {
+                    status_message.set(Some(format!("Session started: {}", signature)));
+                    active.set(Some(new_session));
+                }
+                Err(e) => {
+                    let _ = remove_session_key(&new_session.owner);
+                    error_message.set(Some(format!("Failed to start session: {}", e)));
+                }
+            }
+            working.set(false);
+        });
+    };
+
+    let end_wallet = wallet.clone();
+    let end_hw = hardware_wallet.clone();
+    let end_rpc = custom_rpc.clone();
+    let end = move |_| {
+        let Some(current) = active() else { return };
+        let signer = match active_wallet_signer(end_hw.clone(), end_wallet.clone()) {
+            Ok(signer) => signer,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        let rpc = end_rpc.clone();
+        working.set(true);
+        error_message.set(None);
+        spawn(async move {
+            let client = TransactionClient::new(rpc.as_deref());
+            match session::end_session(&client, signer.as_ref(), &current, rpc.as_deref()).await {
+                Ok(signature) => {
+                    if let Err(e) = remove_session_key(&current.owner) {
+                        error_message.set(Some(e));
+                    }
+                    status_message.set(Some(match signature {
+                        Some(sig) => format!("Session ended: {}", sig),
+                        None => "Session ended".to_string(),
+                    }));
+                    active.set(None);
+                }
+                Err(e) => error_message.set(Some(format!("Failed to end session: {}", e))),
+            }
+            working.set(false);
+        });
+    };
+
+    let now = chrono::Utc::now().timestamp() as u64;
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Session Key" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "A session key signs small automated actions without asking. It can only spend what you fund it with, and larger transfers still need your wallet."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(status) = status_message() {
+                    div { class: "info-message", "{status}" }
+                }
+
+                if let Some(current) = active() {
+                    div {
+                        class: "wallet-field",
+                        label { "Session key:" }
+                        div { class: "address-display", "{current.address}" }
+                    }
+                    if current.is_expired(now) {
+                        div { class: "warning-message", "This session has expired. End it to recover the remaining SOL." }
+                    } else {
+                        div {
+                            class: "info-message",
+                            "Expires in {(current.expires_at - now) / 3_600}h {((current.expires_at - now) % 3_600) / 60}m"
+                        }
+                    }
+                    for (index, line) in current.usage_lines().into_iter().enumerate() {
+                        div { key: "{index}", class: "help-text", "{line}" }
+                    }
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard primary",
+                            disabled: working(),
+                            onclick: end,
+                            if working() { "Ending..." } else { "End Session" }
+                        }
+                    }
+                } else {
+                    div {
+                        class: "wallet-field",
+                        label { "Duration (hours):" }
+                        input {
+                            r#type: "number",
+                            value: "{hours}",
+                            oninput: move |e| hours.set(e.value()),
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "SOL cap:" }
+                        input {
+                            r#type: "number",
+                            value: "{sol_cap}",
+                            oninput: move |e| sol_cap.set(e.value()),
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Max SOL per action:" }
+                        input {
+                            r#type: "number",
+                            value: "{per_action}",
+                            oninput: move |e| per_action.set(e.value()),
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Token allowance (optional):" }
+                        input {
+                            value: "{token_mint}",
+                            oninput: move |e| token_mint.set(e.value()),
+                            placeholder: "Token mint address"
+                        }
+                        input {
+                            r#type: "number",
+                            value: "{token_cap}",
+                            oninput: move |e| token_cap.set(e.value()),
+                            placeholder: "Token cap"
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Allowed actions:" }
+                        for action in SessionAction::ALL {
+                            label {
+                                key: "{action.label()}",
+                                class: "help-text",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: actions().contains(&action),
+                                    oninput: move |_| {
+                                        let mut updated = actions();
+                                        if updated.contains(&action) {
+                                            updated.retain(|a| *a != action);
+                                        } else {
+                                            updated.push(action);
+                                        }
+                                        actions.set(updated);
+                                    }
+                                }
+                                " {action.label()}"
+                            }
+                        }
+                    }
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| onclose.call(()),
+                            "Cancel"
+                        }
+                        button {
+                            class: "button-standard primary",
+                            disabled: working(),
+                            onclick: start,
+                            if working() { "Starting..." } else { "Start Session" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,370 @@
+use dioxus::prelude::*;
+use crate::bridge::{self, BridgeConnection, BridgeMessage, DEFAULT_BRIDGE_PORT};
+use crate::signing::mpc::{self, MpcKeyShare, PendingSignRequest};
+use crate::signing::remote::{self, RemoteSignRequest};
+use crate::storage::{load_mpc_key_share, save_mpc_key_share, save_remote_wallet};
+use tokio::sync::oneshot;
+
+/// Name this device announces to the paired device
+fn device_name() -> String {
+    if cfg!(any(target_os = "android", target_os = "ios")) {
+        "Phone".to_string()
+    } else {
+        "Desktop".to_string()
+    }
+}
+
+/// A request from the paired device waiting for the user's decision
+#[derive(Clone, PartialEq)]
+enum PendingApproval {
+    /// Co-sign for a 2-of-2 shared wallet
+    Shared(PendingSignRequest, MpcKeyShare),
+    /// Sign with a wallet whose key is on this device
+    Remote(RemoteSignRequest),
+}
+
+impl PendingApproval {
+    fn title(&self) -> String {
+        match self {
+            PendingApproval::Shared(request, _) => format!("Co-signature requested for {}", request.group_address),
+            PendingApproval::Remote(request) => format!("Signature requested from {}", request.signer_address),
+        }
+    }
+
+    fn describe(&self) -> Vec<String> {
+        match self {
+            PendingApproval::Shared(request, _) => request.describe(),
+            PendingApproval::Remote(request) => request.describe(),
+        }
+    }
+}
+
+/// Pair with another device to create or co-sign 2-of-2 shared wallets and
+/// to approve transactions for wallets kept on the other device
+#[component]
+pub fn SharedWalletModal(onclose: EventHandler<()>) -> Element {
+    let mut peer_name = use_signal(|| None as Option<String>);
+    let mut join_mode = use_signal(|| false);
+    let mut pairing_code = use_signal(|| String::new());
+    let mut peer_address = use_signal(|| String::new());
+    let mut wallet_name = use_signal(|| "Shared Wallet".to_string());
+    let mut busy = use_signal(|| false);
+    let mut cosigning = use_signal(|| false);
+    let mut pending_request = use_signal(|| None as Option<PendingApproval>);
+    let mut decision = use_signal(|| None as Option<oneshot::Sender<bool>>);
+    let mut status = use_signal(|| None as Option<String>);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    // Pick up a connection made earlier in this session
+    use_effect(move || {
+        spawn(async move {
+            if let Some(conn) = bridge::active_bridge() {
+                peer_name.set(Some(conn.lock().await.peer_name().to_string()));
+            }
+        });
+    });
+
+    let mut respond = move |approved: bool| {
+        if let Some(tx) = decision.write().take() {
+            let _ = tx.send(approved);
+        }
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Paired Device" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Pair with another device you own. A shared wallet is split between both devices and every transaction needs approval on both. Imported wallets keep their key on the other device, which approves each transaction."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(message) = status() {
+                    div { class: "info-message", "{message}" }
+                }
+
+                if let Some(peer) = peer_name() {
+                    div {
+                        class: "wallet-field",
+                        label { "Paired device:" }
+                        div { class: "address-display", "{peer}" }
+                    }
+
+                    if let Some(request) = pending_request() {
+                        div {
+                            class: "wallet-field",
+                            label { "{request.title()}" }
+                            for line in request.describe() {
+                                div { class: "help-text", "{line}" }
+                            }
+                        }
+                        div { class: "modal-buttons",
+                            button {
+                                class: "button-standard secondary",
+                                onclick: move |_| respond(false),
+                                "Reject"
+                            }
+                            button {
+                                class: "button-standard primary",
+                                onclick: move |_| respond(true),
+                                "Approve"
+                            }
+                        }
+                    } else if cosigning() {
+                        div { class: "info-message", "Waiting for requests from {peer}. Keep this window open while approving." }
+                    } else {
+                        div {
+                            class: "wallet-field",
+                            label { "New shared wallet name:" }
+                            input {
+                                value: "{wallet_name}",
+                                oninput: move |e| wallet_name.set(e.value()),
+                            }
+                        }
+                        div { class: "modal-buttons",
+                            button {
+                                class: "button-standard secondary",
+                                disabled: busy(),
+                                onclick: move |_| {
+                                    let Some(conn) = bridge::active_bridge() else { return; };
+                                    cosigning.set(true);
+                                    error_message.set(None);
+                                    spawn(async move {
+                                        // Holds the connection until the modal closes or the peer disconnects
+                                        let mut conn = conn.lock().await;
+                                        loop {
+                                            let message = match conn.recv().await {
+                                                Ok(message) => message,
+                                                Err(e) => {
+                                                    error_message.set(Some(e));
+                                                    break;
+                                                }
+                                            };
+                                            match &message {
+                                                BridgeMessage::MpcKeygenCommit { commitment } => {
+                                                    let name = format!("Shared with {}", conn.peer_name());
+                                                    match mpc::respond_keygen(&mut conn, commitment, &name).await
+                                                        .and_then(|share| save_mpc_key_share(&share).map(|_| share))
+                                                    {
+                                                        Ok(share) => status.set(Some(format!("Shared wallet {} created", share.group_address))),
+                                                        Err(e) => error_message.set(Some(format!("Failed to create shared wallet: {}", e))),
+                                                    }
+                                                    continue;
+                                                }
+                                                BridgeMessage::ListWallets => {
+                                                    let wallets = remote::local_signing_wallets(&device_name());
+                                                    if let Err(e) = conn.send(&BridgeMessage::Wallets { wallets }).await {
+                                                        error_message.set(Some(e));
+                                                        break;
+                                                    }
+                                                    continue;
+                                                }
+                                                _ => {}
+                                            }
+
+                                            let approval = if let Some(request) = PendingSignRequest::from_message(&message) {
+                                                let Some(share) = load_mpc_key_share(&request.group_address) else {
+                                                    let _ = mpc::reject_sign_request(&mut conn, &request, "Unknown shared wallet").await;
+                                                    continue;
+                                                };
+                                                PendingApproval::Shared(request, share)
+                                            } else if let Some(request) = RemoteSignRequest::from_message(&message) {
+                                                PendingApproval::Remote(request)
+                                            } else {
+                                                println!("⚠️ Ignoring bridge message: {:?}", message);
+                                                continue;
+                                            };
+
+                                            let (tx, rx) = oneshot::channel();
+                                            decision.set(Some(tx));
+                                            pending_request.set(Some(approval.clone()));
+                                            let approved = rx.await.unwrap_or(false);
+                                            pending_request.set(None);
+
+                                            let result = match (&approval, approved) {
+                                                (PendingApproval::Shared(request, share), true) => mpc::approve_sign_request(&mut conn, share, request).await,
+                                                (PendingApproval::Shared(request, _), false) => mpc::reject_sign_request(&mut conn, request, "Rejected by user").await,
+                                                (PendingApproval::Remote(request), true) => remote::approve_remote_request(&mut conn, request).await,
+                                                (PendingApproval::Remote(request), false) => remote::reject_remote_request(&mut conn, request, "Rejected by user").await,
+                                            };
+                                            match result {
+                                                Ok(()) if approved => status.set(Some("Transaction signed".to_string())),
+                                                Ok(()) => status.set(Some("Request rejected".to_string())),
+                                                Err(e) => error_message.set(Some(format!("Signing failed: {}", e))),
+                                            }
+                                        }
+                                        cosigning.set(false);
+                                    });
+                                },
+                                "Act as Approver"
+                            }
+                            button {
+                                class: "button-standard primary",
+                                disabled: busy() || wallet_name().trim().is_empty(),
+                                onclick: move |_| {
+                                    let Some(conn) = bridge::active_bridge() else { return; };
+                                    let name = wallet_name().trim().to_string();
+                                    busy.set(true);
+                                    error_message.set(None);
+                                    status.set(Some("Waiting for the other device...".to_string()));
+                                    spawn(async move {
+                                        let mut conn = conn.lock().await;
+                                        match mpc::create_shared_wallet(&mut conn, &name).await
+                                            .and_then(|share| save_mpc_key_share(&share).map(|_| share))
+                                        {
+                                            Ok(share) => status.set(Some(format!("Shared wallet {} created", share.group_address))),
+                                            Err(e) => {
+                                                status.set(None);
+                                                error_message.set(Some(format!("Failed to create shared wallet: {}", e)));
+                                            }
+                                        }
+                                        busy.set(false);
+                                    });
+                                },
+                                "Create Shared Wallet"
+                            }
+                        }
+                        div { class: "help-text", "Put the other device in approver mode before creating a shared wallet or importing its wallets." }
+                        div { class: "modal-buttons",
+                            button {
+                                class: "button-standard secondary",
+                                disabled: busy(),
+                                onclick: move |_| {
+                                    let Some(conn) = bridge::active_bridge() else { return; };
+                                    busy.set(true);
+                                    error_message.set(None);
+                                    spawn(async move {
+                                        let mut conn = conn.lock().await;
+                                        match remote::fetch_remote_wallets(&mut conn).await {
+                                            Ok(wallets) => {
+                                                let imported = wallets.iter()
+                                                    .filter(|w| save_remote_wallet(w).is_ok())
+                                                    .count();
+                                                status.set(Some(format!("Imported {} wallet(s) from {}", imported, conn.peer_name())));
+                                            }
+                                            Err(e) => error_message.set(Some(format!("Failed to import wallets: {}", e))),
+                                        }
+                                        busy.set(false);
+                                    });
+                                },
+                                "Import Wallets from {peer}"
+                            }
+                        }
+                    }
+
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard secondary",
+                            disabled: busy(),
+                            onclick: move |_| {
+                                bridge::clear_active_bridge();
+                                respond(false);
+                                peer_name.set(None);
+                                cosigning.set(false);
+                                status.set(None);
+                            },
+                            "Disconnect"
+                        }
+                    }
+                } else {
+                    div { class: "modal-buttons",
+                        button {
+                            class: if !join_mode() { "button-standard primary" } else { "button-standard secondary" },
+                            onclick: move |_| join_mode.set(false),
+                            "Host"
+                        }
+                        button {
+                            class: if join_mode() { "button-standard primary" } else { "button-standard secondary" },
+                            onclick: move |_| join_mode.set(true),
+                            "Join"
+                        }
+                    }
+
+                    if join_mode() {
+                        div {
+                            class: "wallet-field",
+                            label { "Other device address:" }
+                            input {
+                                value: "{peer_address}",
+                                oninput: move |e| peer_address.set(e.value()),
+                                placeholder: "192.168.1.20:{DEFAULT_BRIDGE_PORT}"
+                            }
+                        }
+                        div {
+                            class: "wallet-field",
+                            label { "Pairing code:" }
+                            input {
+                                value: "{pairing_code}",
+                                oninput: move |e| pairing_code.set(e.value()),
+                                placeholder: "XXXX-XXXX-XXXX"
+                            }
+                        }
+                    } else if busy() {
+                        div {
+                            class: "wallet-field",
+                            label { "Enter this code on the other device:" }
+                            div { class: "address-display", "{pairing_code}" }
+                        }
+                        div { class: "help-text", "Listening on port {DEFAULT_BRIDGE_PORT}. Both devices must be on the same network." }
+                    }
+
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| onclose.call(()),
+                            "Close"
+                        }
+                        button {
+                            class: "button-standard primary",
+                            disabled: busy() || (join_mode() && (peer_address().trim().is_empty() || pairing_code().trim().is_empty())),
+                            onclick: move |_| {
+                                busy.set(true);
+                                error_message.set(None);
+                                let joining = join_mode();
+                                if !joining {
+                                    pairing_code.set(bridge::generate_pairing_code());
+                                }
+                                let code = pairing_code();
+                                let address = peer_address().trim().to_string();
+                                spawn(async move {
+                                    let result = if joining {
+                                        BridgeConnection::connect(&address, &code, &device_name()).await
+                                    } else {
+                                        BridgeConnection::listen(DEFAULT_BRIDGE_PORT, &code, &device_name()).await
+                                    };
+                                    match result {
+                                        Ok(conn) => {
+                                            peer_name.set(Some(conn.peer_name().to_string()));
+                                            bridge::set_active_bridge(conn);
+                                        }
+                                        Err(e) => error_message.set(Some(e)),
+                                    }
+                                    busy.set(false);
+                                });
+                            },
+                            if join_mode() { "Connect" } else { "Start Pairing" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
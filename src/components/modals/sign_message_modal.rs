@@ -0,0 +1,196 @@
+use dioxus::prelude::*;
+use crate::wallet::WalletInfo;
+use crate::hardware::HardwareWallet;
+use crate::signing::{SignerType, TransactionSigner};
+use crate::signing::hardware::HardwareSigner;
+use crate::signing::offchain::{sign_offchain_message, sign_raw_message, verify_offchain_message, SignedMessage};
+use std::sync::Arc;
+
+/// Sign text (or raw bytes) with the active wallet to prove ownership, and
+/// check signatures made by others
+#[component]
+pub fn SignMessageModal(
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut message = use_signal(|| String::new());
+    let mut raw_bytes = use_signal(|| false);
+    let mut signed = use_signal(|| None as Option<SignedMessage>);
+    let mut busy = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut verify_address = use_signal(|| String::new());
+    let mut verify_signature = use_signal(|| String::new());
+    let mut verify_result = use_signal(|| None as Option<Result<bool, String>>);
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Sign Message" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: if !raw_bytes() { "button-standard primary" } else { "button-standard secondary" },
+                        onclick: move |_| {
+                            raw_bytes.set(false);
+                            signed.set(None);
+                        },
+                        "Text"
+                    }
+                    button {
+                        class: if raw_bytes() { "button-standard primary" } else { "button-standard secondary" },
+                        onclick: move |_| {
+                            raw_bytes.set(true);
+                            signed.set(None);
+                        },
+                        "Raw Bytes (hex)"
+                    }
+                }
+                div {
+                    class: "help-text",
+                    if raw_bytes() {
+                        "The bytes are signed as-is. Only use this when a dApp asks for a plain signature, and never for data you can't read."
+                    } else {
+                        "Signed as a Solana off-chain message, which dApps and exchanges can verify and which can never be replayed as a transaction."
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Message:" }
+                    textarea {
+                        value: "{message}",
+                        rows: "5",
+                        oninput: move |e| {
+                            message.set(e.value());
+                            signed.set(None);
+                            verify_result.set(None);
+                        },
+                        placeholder: if raw_bytes() { "48656c6c6f" } else { "I am the owner of this wallet" }
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard primary",
+                        disabled: busy() || message().is_empty(),
+                        onclick: move |_| {
+                            let signer: Box<dyn TransactionSigner> = if let Some(hw) = hardware_wallet.clone() {
+                                Box::new(HardwareSigner::from_wallet(hw))
+                            } else if let Some(w) = wallet.clone() {
+                                match SignerType::for_wallet_info(&w) {
+                                    Ok(signer) => Box::new(signer),
+                                    Err(e) => {
+                                        error_message.set(Some(format!("Failed to load wallet: {}", e)));
+                                        return;
+                                    }
+                                }
+                            } else {
+                                error_message.set(Some("No wallet available".to_string()));
+                                return;
+                            };
+                            let text = message();
+                            let raw = raw_bytes();
+                            busy.set(true);
+                            error_message.set(None);
+                            spawn(async move {
+                                let result = if raw {
+                                    match hex::decode(text.trim().trim_start_matches("0x")) {
+                                        Ok(bytes) => sign_raw_message(signer.as_ref(), &bytes).await.map_err(|e| e.to_string()),
+                                        Err(e) => Err(format!("Invalid hex: {}", e)),
+                                    }
+                                } else {
+                                    sign_offchain_message(signer.as_ref(), &text).await.map_err(|e| e.to_string())
+                                };
+                                match result {
+                                    Ok(result) => signed.set(Some(result)),
+                                    Err(e) => error_message.set(Some(format!("Signing failed: {}", e))),
+                                }
+                                busy.set(false);
+                            });
+                        },
+                        if busy() { "Signing..." } else { "Sign" }
+                    }
+                }
+
+                if let Some(result) = signed() {
+                    div {
+                        class: "wallet-field",
+                        label { "Signer:" }
+                        div { class: "address-display", "{result.signer}" }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Signature (base58):" }
+                        textarea {
+                            value: "{result.signature}",
+                            rows: "3",
+                            readonly: true,
+                        }
+                    }
+                }
+
+                if !raw_bytes() {
+                    div {
+                        class: "wallet-field",
+                        label { "Verify a signature of this message" }
+                        input {
+                            value: "{verify_address}",
+                            oninput: move |e| {
+                                verify_address.set(e.value());
+                                verify_result.set(None);
+                            },
+                            placeholder: "Signer address"
+                        }
+                        input {
+                            value: "{verify_signature}",
+                            oninput: move |e| {
+                                verify_signature.set(e.value());
+                                verify_result.set(None);
+                            },
+                            placeholder: "Signature (base58)"
+                        }
+                    }
+                    match verify_result() {
+                        Some(Ok(true)) => rsx! { div { class: "info-message", "✅ Valid signature" } },
+                        Some(Ok(false)) => rsx! { div { class: "error-message", "❌ Signature does not match" } },
+                        Some(Err(e)) => rsx! { div { class: "error-message", "{e}" } },
+                        None => rsx! {},
+                    }
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard secondary",
+                            disabled: message().is_empty() || verify_address().trim().is_empty() || verify_signature().trim().is_empty(),
+                            onclick: move |_| {
+                                verify_result.set(Some(verify_offchain_message(
+                                    verify_address().trim(),
+                                    &message(),
+                                    &verify_signature(),
+                                )));
+                            },
+                            "Verify"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
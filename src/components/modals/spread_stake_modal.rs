@@ -0,0 +1,256 @@
+use dioxus::prelude::*;
+use crate::components::common::Token;
+use crate::components::modals::stake_modal::analytics_summary;
+use crate::hardware::HardwareWallet;
+use crate::staking::{create_stake_batch, split_evenly, SPREAD_ACCOUNTS_PER_TX};
+use crate::validators::{get_recommended_validators, spread_candidates, ValidatorInfo};
+use crate::wallet::WalletInfo;
+use std::sync::Arc;
+
+/// Validators stake can be spread over
+const SPREAD_COUNTS: &[usize] = &[2, 3, 4, 5, 6, 8, 10];
+/// Rent each new stake account holds on top of its stake
+const STAKE_ACCOUNT_RENT_SOL: f64 = 0.00228288;
+
+/// Split an amount of SOL evenly across the best-scoring validators, creating
+/// the stake accounts a few per transaction
+#[component]
+pub fn SpreadStakeModal(
+    tokens: Vec<Token>,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut validators = use_signal(Vec::<ValidatorInfo>::new);
+    let mut loading = use_signal(|| true);
+    let mut amount = use_signal(String::new);
+    let mut count = use_signal(|| 3usize);
+    // Vote accounts left out of the spread
+    let mut excluded = use_signal(Vec::<String>::new);
+    let mut working = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status = use_signal(|| None as Option<String>);
+    let mut created = use_signal(Vec::<String>::new);
+
+    use_effect(move || {
+        spawn(async move {
+            validators.set(get_recommended_validators().await);
+            loading.set(false);
+        });
+    });
+
+    let sol_balance = tokens
+        .iter()
+        .find(|t| t.mint == "So11111111111111111111111111111111111111112")
+        .map(|t| t.balance)
+        .unwrap_or(0.0);
+    let candidates: Vec<ValidatorInfo> = validators()
+        .into_iter()
+        .filter(|v| !excluded().contains(&v.vote_account))
+        .collect();
+    let picks = spread_candidates(&candidates, count());
+    let lamports = amount()
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|sol| *sol > 0.0)
+        .map(|sol| (sol * 1_000_000_000.0) as u64);
+    let shares = lamports.map(|total| split_evenly(total, picks.len())).unwrap_or_default();
+    let batches = picks.len().div_ceil(SPREAD_ACCOUNTS_PER_TX);
+    let plan = lamports.map(|_| {
+        format!(
+            "{} stake accounts in {} transaction(s), plus about {:.5} SOL of rent held in the accounts",
+            picks.len(),
+            batches,
+            picks.len() as f64 * STAKE_ACCOUNT_RENT_SOL
+        )
+    });
+    let available_label = format!("Available: {:.6} SOL", sol_balance);
+
+    let spread = {
+        let picks = picks.clone();
+        let shares = shares.clone();
+        move |_| {
+            let Some(total) = lamports else {
+                error_message.set(Some("Enter an amount".to_string()));
+                return;
+            };
+            if picks.is_empty() {
+                error_message.set(Some("No validators to spread over".to_string()));
+                return;
+            }
+            let needed = total as f64 / 1_000_000_000.0 + picks.len() as f64 * STAKE_ACCOUNT_RENT_SOL;
+            if needed > sol_balance {
+                error_message.set(Some(format!("Need about {:.6} SOL including rent", needed)));
+                return;
+            }
+            let allocations: Vec<(String, u64)> =
+                picks.iter().map(|v| v.vote_account.clone()).zip(shares.iter().copied()).collect();
+            let wallet = wallet.clone();
+            let hardware_wallet = hardware_wallet.clone();
+            let custom_rpc = custom_rpc.clone();
+            working.set(true);
+            error_message.set(None);
+            created.set(Vec::new());
+            spawn(async move {
+                let batches: Vec<&[(String, u64)]> = allocations.chunks(SPREAD_ACCOUNTS_PER_TX).collect();
+                for (index, batch) in batches.iter().enumerate() {
+                    status.set(Some(if hardware_wallet.is_some() {
+                        format!("Confirm transaction {} of {} on your hardware wallet", index + 1, batches.len())
+                    } else {
+                        format!("Sending transaction {} of {}...", index + 1, batches.len())
+                    }));
+                    match create_stake_batch(wallet.as_ref(), hardware_wallet.clone(), batch, custom_rpc.as_deref()).await {
+                        Ok(accounts) => {
+                            let mut done = created();
+                            done.extend(accounts.iter().map(|a| {
+                                format!(
+                                    "{:.4} SOL to {}: {}",
+                                    a.staked_amount as f64 / 1_000_000_000.0,
+                                    a.validator_vote_account,
+                                    a.transaction_signature
+                                )
+                            }));
+                            created.set(done);
+                        }
+                        Err(e) => {
+                            status.set(None);
+                            error_message.set(Some(format!(
+                                "Transaction {} of {} failed, {} stake account(s) were created before it: {}",
+                                index + 1,
+                                batches.len(),
+                                created().len(),
+                                e
+                            )));
+                            working.set(false);
+                            return;
+                        }
+                    }
+                }
+                status.set(Some(format!("Spread stake over {} validators", created().len())));
+                amount.set(String::new());
+                working.set(false);
+            });
+        }
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Spread Stake" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Stake with several validators at once to spread the risk of any one of them going down or raising its commission. Validators are picked by estimated yield and vote performance, avoiding the superminority."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(message) = status() {
+                    div { class: "info-message", "{message}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Amount (SOL):" }
+                    input {
+                        r#type: "text",
+                        value: "{amount}",
+                        placeholder: "0.0",
+                        oninput: move |e| amount.set(e.value()),
+                    }
+                    div { class: "help-text", "{available_label}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Validators:" }
+                    select {
+                        onchange: move |e| count.set(e.value().parse().unwrap_or(3)),
+                        for n in SPREAD_COUNTS.iter() {
+                            option { key: "{n}", value: "{n}", selected: *n == count(), "{n} validators" }
+                        }
+                    }
+                }
+
+                if loading() {
+                    div { class: "help-text", "Loading validators..." }
+                }
+
+                for (index, validator) in picks.iter().enumerate() {
+                    {
+                        let share = shares
+                            .get(index)
+                            .map(|l| format!("{:.4} SOL", *l as f64 / 1_000_000_000.0))
+                            .unwrap_or_default();
+                        let summary = analytics_summary(validator).unwrap_or_else(|| format!("{}% commission", validator.commission));
+                        let vote_account = validator.vote_account.clone();
+                        rsx! {
+                            div {
+                                key: "{validator.vote_account}",
+                                class: "token-search-row",
+                                div {
+                                    span { class: "token-search-label", "{validator.name}" }
+                                    div { class: "help-text", "{summary}" }
+                                }
+                                span { class: "token-search-balance", "{share}" }
+                                button {
+                                    class: "button-standard secondary",
+                                    disabled: working(),
+                                    onclick: move |_| excluded.write().push(vote_account.clone()),
+                                    "Skip"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !excluded().is_empty() {
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| excluded.set(Vec::new()),
+                        "Restore skipped validators"
+                    }
+                }
+
+                if let Some(plan) = plan {
+                    div { class: "help-text", "{plan}" }
+                }
+
+                for line in created() {
+                    div { class: "help-text", "{line}" }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: working() || loading() || lamports.is_none(),
+                        onclick: spread,
+                        if working() { "Staking..." } else { "Spread Stake" }
+                    }
+                }
+            }
+        }
+    }
+}
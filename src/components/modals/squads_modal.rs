@@ -3,6 +3,8 @@ use crate::wallet::WalletInfo;
 use crate::hardware::HardwareWallet;
 use crate::squads::{SquadsClient, MultisigInfo, PendingTransaction};
 use crate::signing::{SignerType, TransactionSigner};
+use crate::squads::report::ReportTarget;
+use crate::components::modals::TreasuryReportModal;
 use std::sync::Arc;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
@@ -75,7 +77,7 @@ fn ApprovalSuccessModal(
     approval_count: u16,
     onclose: EventHandler<()>,
 ) -> Element {
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     let orb_url = format!("https://orb.helius.dev/tx/{}?cluster=mainnet-beta&tab=summary", signature);
     
     rsx! {
@@ -201,6 +203,7 @@ pub fn SquadsModal(
     let mut success_signature = use_signal(|| String::new());
     let mut success_threshold_met = use_signal(|| false);
     let mut success_approval_count = use_signal(|| 0u16);
+    let mut report_target = use_signal(|| None as Option<ReportTarget>);
 
     // Get wallet address
     let display_address = if let Some(hw) = &hardware_wallet {
@@ -355,6 +358,16 @@ pub fn SquadsModal(
         };
     }
 
+    if let Some(target) = report_target() {
+        return rsx! {
+            TreasuryReportModal {
+                target: target,
+                custom_rpc: custom_rpc.clone(),
+                onclose: move |_| report_target.set(None),
+            }
+        };
+    }
+
     rsx! {
             div {
                 class: "modal-backdrop",
@@ -631,6 +644,15 @@ pub fn SquadsModal(
                                                     class: "validator-description-text",
                                                     "Transaction Index: {multisig.transaction_index}"
                                                 }
+                                                button {
+                                                    class: "button-standard secondary",
+                                                    style: "margin-top: 10px;",
+                                                    onclick: {
+                                                        let target = ReportTarget::from(&multisig);
+                                                        move |_| report_target.set(Some(target.clone()))
+                                                    },
+                                                    "📊 Treasury Report"
+                                                }
                                                 
                                                 // Pending Transactions inline
                                                 div {
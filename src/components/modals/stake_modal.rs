@@ -13,6 +13,9 @@ use crate::unstaking::{
 use std::sync::Arc;
 use std::collections::HashMap;
 use crate::signing::hardware::HardwareSigner;
+use crate::components::{StuckTransactionMonitor, TransactionStatusView};
+use crate::components::simulation_preview::{SimulationPreviewPanel, PreviewState, DrainWarning};
+use crate::domain_resolver::resolve_recipient;
 use crate::staking::create_stake_account;
 use crate::staking::find_mergeable_stake_accounts;
 use std::sync::LazyLock;
@@ -48,6 +51,52 @@ static VALIDATOR_METADATA: LazyLock<HashMap<String, ValidatorEntry>> = LazyLock:
     parse_validators_from_json(VALIDATORS_JSON)
 });
 
+/// Name of a validator in the bundled validator list, by vote account
+pub fn known_validator_name(vote_account: &str) -> Option<String> {
+    VALIDATOR_METADATA
+        .get(vote_account)
+        .map(|v| v.keybase_name.clone())
+        .filter(|name| !name.is_empty())
+}
+
+/// "Est. APY 6.85% + MEV 0.41% (8% MEV commission) • Vote performance
+/// 99.1% • 0.42% of stake"
+pub fn analytics_summary(validator: &ValidatorInfo) -> Option<String> {
+    let analytics = validator.analytics.as_ref()?;
+    let mut parts = Vec::new();
+    match (analytics.estimated_apy, analytics.mev_apy) {
+        (Some(apy), Some(mev)) => parts.push(format!(
+            "Est. APY {:.2}% + MEV {:.2}% ({}% MEV commission)",
+            apy,
+            mev,
+            analytics.mev_commission.unwrap_or(0.0)
+        )),
+        (Some(apy), None) => parts.push(format!("Est. APY {:.2}%, no MEV rewards", apy)),
+        (None, _) => {}
+    }
+    parts.push(format!("Vote performance {:.1}%", analytics.vote_performance));
+    parts.push(format!("{:.2}% of stake", analytics.stake_share));
+    Some(parts.join(" • "))
+}
+
+/// Reasons to think twice before delegating to a validator
+fn analytics_warnings(validator: &ValidatorInfo) -> Vec<&'static str> {
+    let Some(analytics) = &validator.analytics else {
+        return Vec::new();
+    };
+    let mut warnings = Vec::new();
+    if analytics.delinquent {
+        warnings.push("Delinquent, not voting");
+    }
+    if analytics.superminority {
+        warnings.push("In the superminority, staking here concentrates stake");
+    }
+    if analytics.commission_raised() {
+        warnings.push("Raised its commission recently");
+    }
+    warnings
+}
+
 /// Parse validators from JSON string with robust handling
 fn parse_validators_from_json(json_str: &str) -> HashMap<String, ValidatorEntry> {
     let mut map = HashMap::new();
@@ -151,10 +200,15 @@ fn UnstakeSuccessModal(
     operation: String, // "Instant Unstake", "Normal Unstake", or "Partial Unstake"
     amount: f64,
     was_hardware_wallet: bool,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    onreplaced: EventHandler<String>,
     onclose: EventHandler<()>,
 ) -> Element {
+    let tracked_signature = signature.clone();
     // Explorer links - Solscan and Orb
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     let orb_url = format!("https://orb.helius.dev/tx/{}?cluster=mainnet-beta&tab=summary", signature);
     
     rsx! {
@@ -236,6 +290,14 @@ fn UnstakeSuccessModal(
                             class: "copy-hint",
                             "Click to copy"
                         }
+                        StuckTransactionMonitor {
+                            key: "{tracked_signature}",
+                            signature: tracked_signature.clone(),
+                            wallet: wallet.clone(),
+                            hardware_wallet: hardware_wallet.clone(),
+                            custom_rpc: custom_rpc.clone(),
+                            onreplaced: move |replacement| onreplaced.call(replacement),
+                        }
                     }
 
                     div {
@@ -281,10 +343,12 @@ fn StakeSuccessModal(
     stake_amount: f64,
     validator_name: String,
     was_hardware_wallet: bool,
+    custom_rpc: Option<String>,
     onclose: EventHandler<()>,
 ) -> Element {
+    let tracked_signature = signature.clone();
     // Explorer links - Solscan and Orb
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     let orb_url = format!("https://orb.helius.dev/tx/{}?cluster=mainnet-beta&tab=summary", signature);
     
     rsx! {
@@ -374,6 +438,13 @@ fn StakeSuccessModal(
                             class: "copy-hint",
                             "Click to copy"
                         }
+                        // A new stake account is co-signed by a one-off keypair, so
+                        // this can be tracked but not re-signed
+                        TransactionStatusView {
+                            key: "{tracked_signature}",
+                            signature: tracked_signature.clone(),
+                            custom_rpc: custom_rpc.clone(),
+                        }
                     }
 
                     div {
@@ -449,6 +520,8 @@ pub fn StakeModal(
     let normal_unstaking = use_signal(|| false);
     let mut partial_unstaking = use_signal(|| false);
     let mut withdrawing = use_signal(|| false);
+    // Where withdrawn SOL goes; empty means back to this wallet
+    let mut withdraw_destination = use_signal(|| "".to_string());
     
     // Partial unstake modal state
     let mut show_partial_unstake_modal = use_signal(|| false);
@@ -461,6 +534,54 @@ pub fn StakeModal(
     let mut unstake_success_operation = use_signal(|| "".to_string());
     let mut unstake_success_amount = use_signal(|| 0.0);
 
+    // Simulate the stake before any signature is requested
+    let mut preview_state = use_signal(|| PreviewState::Idle);
+    let mut drain_confirmed = use_signal(|| false);
+    let wallet_for_preview = wallet.clone();
+    let hardware_for_preview = hardware_wallet.clone();
+    let custom_rpc_for_preview = custom_rpc.clone();
+    use_effect(move || {
+        let amount_str = amount.read().clone();
+        let amount_value = amount_str.parse::<f64>().ok().filter(|a| *a >= 0.01);
+        let vote_account = selected_validator.read().as_ref().map(|v| v.vote_account.clone());
+
+        let (Some(amount_value), Some(vote_account)) = (amount_value, vote_account) else {
+            preview_state.set(PreviewState::Idle);
+            return;
+        };
+
+        let wallet_info = wallet_for_preview.clone();
+        let hardware = hardware_for_preview.clone();
+        let rpc_url = custom_rpc_for_preview.clone();
+        preview_state.set(PreviewState::Loading);
+        drain_confirmed.set(false);
+
+        spawn(async move {
+            let wallet_address = if let Some(hw) = hardware {
+                hw.get_public_key().await.ok()
+            } else {
+                wallet_info.map(|w| w.address)
+            };
+            let Some(wallet_address) = wallet_address else {
+                preview_state.set(PreviewState::Idle);
+                return;
+            };
+
+            let result = staking::preview_stake(&wallet_address, &vote_account, amount_value, rpc_url.as_deref()).await;
+
+            // Ignore results for inputs the user has since changed
+            let current_vote_account = selected_validator.peek().as_ref().map(|v| v.vote_account.clone());
+            if *amount.peek() != amount_str || current_vote_account.as_deref() != Some(vote_account.as_str()) {
+                return;
+            }
+
+            match result {
+                Ok(preview) => preview_state.set(PreviewState::Ready(preview)),
+                Err(e) => preview_state.set(PreviewState::Unavailable(e)),
+            }
+        });
+    });
+
     // Load validators on component mount
     use_effect(move || {
         spawn(async move {
@@ -755,6 +876,10 @@ pub fn StakeModal(
                 operation: unstake_success_operation(),
                 amount: unstake_success_amount(),
                 was_hardware_wallet: was_hardware_transaction(),
+                wallet: wallet.clone(),
+                hardware_wallet: hardware_wallet.clone(),
+                custom_rpc: custom_rpc.clone(),
+                onreplaced: move |replacement| unstake_success_signature.set(replacement),
                 onclose: move |_| {
                     show_unstake_success_modal.set(false);
                     onsuccess.call(unstake_success_signature());
@@ -771,6 +896,7 @@ pub fn StakeModal(
                 stake_amount: success_amount(),
                 validator_name: success_validator(),
                 was_hardware_wallet: was_hardware_transaction(),  // ADD THIS LINE
+                custom_rpc: custom_rpc.clone(),
                 onclose: move |_| {
                     show_success_modal.set(false);
                     // Call onsuccess when the user closes the modal
@@ -883,6 +1009,9 @@ pub fn StakeModal(
                                                 class: "validator-details",
                                                 "Commission: {validator.commission}% • Skip Rate: {validator.skip_rate:.1}%"
                                             }
+                                            if let Some(summary) = analytics_summary(&validator) {
+                                                div { class: "validator-details", "{summary}" }
+                                            }
                                         }
                                     } else {
                                         div {
@@ -902,38 +1031,59 @@ pub fn StakeModal(
                                     div {
                                         class: "validator-dropdown",
                                         onclick: move |e| e.stop_propagation(),
-                                        for validator in validators() {
-                                            div {
-                                                key: "{validator.identity}",
-                                                class: "validator-option",
-                                                onclick: move |_| {
-                                                    selected_validator.set(Some(validator.clone()));
-                                                    show_validator_dropdown.set(false);
-                                                    error_message.set(None);
-                                                },
-                                                div {
-                                                    class: "validator-option-header",
+                                        for (index, validator) in validators().into_iter().enumerate() {
+                                            {
+                                                let rank = format!("#{}", index + 1);
+                                                let summary = analytics_summary(&validator);
+                                                let warnings = analytics_warnings(&validator).join(" • ");
+                                                let history = validator
+                                                    .analytics
+                                                    .as_ref()
+                                                    .filter(|a| a.commission_history.len() > 1)
+                                                    .map(|a| format!("Commission history: {}", a.commission_trend()));
+                                                rsx! {
                                                     div {
-                                                        class: "validator-option-name",
-                                                        if validator.is_default {
-                                                            "{validator.name} (⭐ Recommended)"
-                                                        } else {
-                                                            "{validator.name}"
+                                                        key: "{validator.identity}",
+                                                        class: "validator-option",
+                                                        onclick: move |_| {
+                                                            selected_validator.set(Some(validator.clone()));
+                                                            show_validator_dropdown.set(false);
+                                                            error_message.set(None);
+                                                        },
+                                                        div {
+                                                            class: "validator-option-header",
+                                                            div {
+                                                                class: "validator-option-name",
+                                                                if validator.is_default {
+                                                                    "{rank} {validator.name} (⭐ Recommended)"
+                                                                } else {
+                                                                    "{rank} {validator.name}"
+                                                                }
+                                                            }
+                                                            div {
+                                                                class: "validator-commission",
+                                                                "Commission: {validator.commission}%"
+                                                            }
+                                                        }
+                                                        div {
+                                                            class: "validator-description",
+                                                            "{validator.description}"
+                                                        }
+                                                        if validator.active_stake > 0.0 {
+                                                            div {
+                                                                class: "validator-stats",
+                                                                "Active Stake: {validator.active_stake:.0} SOL • Skip Rate: {validator.skip_rate:.1}%"
+                                                            }
+                                                        }
+                                                        if let Some(summary) = summary {
+                                                            div { class: "validator-stats", "{summary}" }
+                                                        }
+                                                        if let Some(history) = history {
+                                                            div { class: "validator-stats", "{history}" }
+                                                        }
+                                                        if !warnings.is_empty() {
+                                                            div { class: "validator-warning", "⚠️ {warnings}" }
                                                         }
-                                                    }
-                                                    div {
-                                                        class: "validator-commission",
-                                                        "Commission: {validator.commission}%"
-                                                    }
-                                                }
-                                                div {
-                                                    class: "validator-description",
-                                                    "{validator.description}"
-                                                }
-                                                if validator.active_stake > 0.0 {
-                                                    div {
-                                                        class: "validator-stats",
-                                                        "Active Stake: {validator.active_stake:.0} SOL • Skip Rate: {validator.skip_rate:.1}%"
                                                     }
                                                 }
                                             }
@@ -1035,6 +1185,18 @@ pub fn StakeModal(
                                     }
                                 }
 
+                                if stake_accounts().iter().any(can_withdraw) {
+                                    div {
+                                        class: "wallet-field",
+                                        label { "Withdraw to:" }
+                                        input {
+                                            value: "{withdraw_destination}",
+                                            oninput: move |e| withdraw_destination.set(e.value()),
+                                            placeholder: "This wallet, or an address or domain (e.g. savings.sol)"
+                                        }
+                                    }
+                                }
+
                                 // Merge info (only show if merges available)
                                 // COMMENTED OUT: Merge feature disabled for now
                                 // if !merge_groups().is_empty() {
@@ -1145,12 +1307,14 @@ pub fn StakeModal(
                                                         StakeAccountState::Delegated => "status-badge active",
                                                         StakeAccountState::Initialized => "status-badge activating", 
                                                         StakeAccountState::Uninitialized => "status-badge inactive",
+                                                        StakeAccountState::Deactivating => "status-badge deactivating",
                                                         StakeAccountState::RewardsPool => "status-badge rewards",
                                                     },
                                                     match account.state {
                                                         StakeAccountState::Delegated => "ACTIVE",
                                                         StakeAccountState::Initialized => "ACTIVATING",
                                                         StakeAccountState::Uninitialized => "INACTIVE", 
+                                                        StakeAccountState::Deactivating => "DEACTIVATING",
                                                         StakeAccountState::RewardsPool => "REWARDS",
                                                     }
                                                 }
@@ -1200,12 +1364,27 @@ pub fn StakeModal(
                                                                 let hardware_wallet_clone = hardware_wallet_for_withdraw.clone();
                                                                 let custom_rpc_clone = custom_rpc_for_withdraw.clone();
                                                                 let account_async = account_clone.clone();
+                                                                let destination_text = withdraw_destination();
                                                                 
                                                                 spawn(async move {
+                                                                    let destination = if destination_text.trim().is_empty() {
+                                                                        None
+                                                                    } else {
+                                                                        match resolve_recipient(&destination_text).await {
+                                                                            Ok(resolved) => Some(resolved.pubkey),
+                                                                            Err(e) => {
+                                                                                error_message_clone.set(Some(e));
+                                                                                show_hardware_approval_clone.set(false);
+                                                                                withdrawing_clone.set(false);
+                                                                                return;
+                                                                            }
+                                                                        }
+                                                                    };
                                                                     println!("WITHDRAW: Executing transaction...");
                                                                     
                                                                     match withdraw_stake_account(
                                                                         &account_async,
+                                                                        destination.as_ref(),
                                                                         wallet_clone.as_ref(),
                                                                         hardware_wallet_clone,
                                                                         custom_rpc_clone.as_deref(),
@@ -1422,13 +1601,22 @@ pub fn StakeModal(
                     }
                 }
 
+                if mode() == ModalMode::Stake {
+                    SimulationPreviewPanel { state: preview_state() }
+                    DrainWarning {
+                        state: preview_state(),
+                        confirmed: drain_confirmed(),
+                        onchange: move |confirmed| drain_confirmed.set(confirmed),
+                    }
+                }
+
                 div { 
                     class: "modal-buttons",
                     
                     if mode() == ModalMode::Stake {
                         button {
                             class: "button-standard primary",
-                            disabled: staking() || amount().is_empty() || amount().parse::<f64>().unwrap_or(0.0) < 0.01 || selected_validator().is_none(),
+                            disabled: staking() || amount().is_empty() || amount().parse::<f64>().unwrap_or(0.0) < 0.01 || selected_validator().is_none() || (preview_state.read().needs_confirmation() && !drain_confirmed()),
                             onclick: move |_| {
                                 error_message.set(None);
                                 
@@ -0,0 +1,702 @@
+use dioxus::prelude::*;
+use crate::components::modals::stake_modal::known_validator_name;
+use crate::hardware::HardwareWallet;
+use crate::mev_rewards::{fetch_staker_mev_rewards, mev_by_account, MEV_REWARD_EPOCHS};
+use crate::staking::{
+    fetch_stake_positions, forget_stake_account, import_stake_account, merge_compatible, merge_stake_pair,
+    split_stake_account, validate_split, StakeOrigin, StakePhase, StakePosition, REWARD_EPOCHS,
+};
+use crate::unstaking::{
+    normal_unstake_stake_account, partial_unstake_stake_account, plan_unstake, quote_stake_account_sale,
+    sell_stake_account_instantly, unstake_amount, withdraw_stake_account, InstantSaleQuote, UnstakeStep,
+};
+use crate::wallet::WalletInfo;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn format_sol(lamports: u64) -> String {
+    format!("{:.6} SOL", lamports as f64 / 1_000_000_000.0)
+}
+
+fn short(address: &str) -> String {
+    if address.len() <= 8 {
+        return address.to_string();
+    }
+    format!("{}...{}", &address[..4], &address[address.len() - 4..])
+}
+
+fn phase_badge(phase: StakePhase) -> &'static str {
+    match phase {
+        StakePhase::Active => "status-badge active",
+        StakePhase::Activating => "status-badge activating",
+        StakePhase::Deactivating => "status-badge deactivating",
+        StakePhase::Inactive | StakePhase::Undelegated => "status-badge inactive",
+    }
+}
+
+#[derive(Clone)]
+enum StakeAction {
+    Deactivate,
+    Withdraw,
+    /// Split off this many lamports, deactivating them when `deactivate`
+    Split { lamports: u64, deactivate: bool },
+    /// Merge this account into the position
+    Merge(StakePosition),
+    /// Sell the stake for SOL now through Sanctum's router
+    Sell,
+}
+
+impl StakeAction {
+    fn done(&self) -> &'static str {
+        match self {
+            StakeAction::Deactivate => "Deactivated",
+            StakeAction::Withdraw => "Withdrew",
+            StakeAction::Split { deactivate: false, .. } => "Split",
+            StakeAction::Split { deactivate: true, .. } => "Split and deactivated part of",
+            StakeAction::Merge(_) => "Merged into",
+            StakeAction::Sell => "Sold",
+        }
+    }
+}
+
+/// Every stake account of the wallet with its phase, validator and recent
+/// rewards, and the deactivate, sell and withdraw actions that apply to it
+#[component]
+pub fn StakePositionsModal(
+    address: String,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut positions = use_signal(Vec::<StakePosition>::new);
+    let mut current_epoch = use_signal(|| None as Option<u64>);
+    // Jito MEV claims per stake account over the last `MEV_REWARD_EPOCHS`
+    let mut mev_rewards = use_signal(|| None as Option<HashMap<String, u64>>);
+    let mut loading = use_signal(|| true);
+    let mut working = use_signal(|| None as Option<String>);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status = use_signal(|| None as Option<String>);
+    // Account whose split or merge options are open
+    let mut split_open = use_signal(|| None as Option<String>);
+    let mut split_amount = use_signal(String::new);
+    let mut merge_open = use_signal(|| None as Option<String>);
+    // Sanctum router quote for the account being sold
+    let mut sale_quote = use_signal(|| None as Option<(String, InstantSaleQuote)>);
+    let mut quoting = use_signal(|| false);
+    // SOL to unstake across accounts
+    let mut unstake_sol = use_signal(String::new);
+    let mut unstaking = use_signal(|| false);
+    // Stake account to add by address
+    let mut import_address = use_signal(String::new);
+    let mut importing = use_signal(|| false);
+
+    let load = {
+        let address = address.clone();
+        let custom_rpc = custom_rpc.clone();
+        move || {
+            let address = address.clone();
+            let custom_rpc = custom_rpc.clone();
+            loading.set(true);
+            spawn(async move {
+                match fetch_stake_positions(&address, custom_rpc.as_deref()).await {
+                    Ok((epoch, found)) => {
+                        current_epoch.set(Some(epoch));
+                        positions.set(found);
+                        loading.set(false);
+                        // MEV comes from Jito's API and only adds detail, so it loads after
+                        match fetch_staker_mev_rewards(&address).await {
+                            Ok(rewards) => {
+                                mev_rewards.set(Some(mev_by_account(&rewards, epoch.saturating_sub(MEV_REWARD_EPOCHS))))
+                            }
+                            Err(e) => println!("⚠️ Failed to load MEV rewards: {}", e),
+                        }
+                    }
+                    Err(e) => error_message.set(Some(format!("Failed to load stake accounts: {}", e))),
+                }
+                loading.set(false);
+            });
+        }
+    };
+
+    use_effect({
+        let mut load = load.clone();
+        move || load()
+    });
+
+    let import = {
+        let address = address.clone();
+        let custom_rpc = custom_rpc.clone();
+        let load = load.clone();
+        move |_| {
+            let address = address.clone();
+            let custom_rpc = custom_rpc.clone();
+            let mut reload = load.clone();
+            let account = import_address().trim().to_string();
+            if account.is_empty() {
+                error_message.set(Some("Enter a stake account address".to_string()));
+                return;
+            }
+            importing.set(true);
+            error_message.set(None);
+            spawn(async move {
+                match import_stake_account(&address, &account, custom_rpc.as_deref()).await {
+                    Ok(()) => {
+                        status.set(Some(format!("Imported stake account {}", short(&account))));
+                        import_address.set(String::new());
+                        reload();
+                    }
+                    Err(e) => error_message.set(Some(e.to_string())),
+                }
+                importing.set(false);
+            });
+        }
+    };
+
+    // Active accounts this wallet can deactivate
+    let deactivatable: Vec<_> = positions()
+        .into_iter()
+        .filter(|p| p.can_deactivate(&address))
+        .map(|p| p.account)
+        .collect();
+    let unstake_value = unstake_sol().trim().parse::<f64>().ok().filter(|sol| *sol > 0.0);
+    let unstake_plan = unstake_value.map(|sol| plan_unstake(&deactivatable, (sol * 1_000_000_000.0).round() as u64));
+    let plan_lines: Vec<String> = match &unstake_plan {
+        Some(Ok(steps)) => steps
+            .iter()
+            .map(|step| match step {
+                UnstakeStep::Deactivate(account) => {
+                    format!("Deactivate {} ({})", short(&account.pubkey.to_string()), format_sol(step.lamports()))
+                }
+                UnstakeStep::Split(account, lamports) => format!(
+                    "Split {} off {} and deactivate it",
+                    format_sol(*lamports),
+                    short(&account.pubkey.to_string())
+                ),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let plan_error = match &unstake_plan {
+        Some(Err(e)) => Some(e.to_string()),
+        _ => None,
+    };
+    let can_unstake = matches!(unstake_plan, Some(Ok(_)));
+
+    let unstake = {
+        let wallet = wallet.clone();
+        let hardware_wallet = hardware_wallet.clone();
+        let custom_rpc = custom_rpc.clone();
+        let load = load.clone();
+        let deactivatable = deactivatable.clone();
+        move |_| {
+            let Some(sol) = unstake_value else { return };
+            let accounts = deactivatable.clone();
+            let wallet = wallet.clone();
+            let hardware_wallet = hardware_wallet.clone();
+            let custom_rpc = custom_rpc.clone();
+            let mut reload = load.clone();
+            unstaking.set(true);
+            error_message.set(None);
+            status.set(if hardware_wallet.is_some() {
+                Some("Confirm each transaction on your hardware wallet".to_string())
+            } else {
+                None
+            });
+            spawn(async move {
+                match unstake_amount(&accounts, sol, wallet.as_ref(), hardware_wallet, custom_rpc.as_deref()).await {
+                    Ok(signatures) => {
+                        status.set(Some(format!(
+                            "Unstaking {} SOL in {} transaction(s): {}",
+                            sol,
+                            signatures.len(),
+                            signatures.join(", ")
+                        )));
+                        unstake_sol.set(String::new());
+                        reload();
+                    }
+                    Err(e) => {
+                        status.set(None);
+                        error_message.set(Some(e.to_string()));
+                    }
+                }
+                unstaking.set(false);
+            });
+        }
+    };
+
+    // Quote selling `position` and show it for confirmation
+    let quote_sale = {
+        let custom_rpc = custom_rpc.clone();
+        move |position: StakePosition| {
+            let custom_rpc = custom_rpc.clone();
+            let pubkey = position.account.pubkey.to_string();
+            let voter = position.voter.clone().unwrap_or_default();
+            split_open.set(None);
+            merge_open.set(None);
+            sale_quote.set(None);
+            error_message.set(None);
+            quoting.set(true);
+            spawn(async move {
+                match quote_stake_account_sale(&position.account, &voter, custom_rpc.as_deref()).await {
+                    Ok(quote) => sale_quote.set(Some((pubkey, quote))),
+                    Err(e) => error_message.set(Some(format!("Can't sell this stake now: {}", e))),
+                }
+                quoting.set(false);
+            });
+        }
+    };
+
+    // Send `action` for `position` and reload the accounts once it lands
+    let run = {
+        let load = load.clone();
+        move |action: StakeAction, position: StakePosition| {
+            let wallet = wallet.clone();
+            let hardware_wallet = hardware_wallet.clone();
+            let custom_rpc = custom_rpc.clone();
+            let mut reload = load.clone();
+            working.set(Some(position.account.pubkey.to_string()));
+            error_message.set(None);
+            status.set(None);
+            if hardware_wallet.is_some() {
+                status.set(Some("Confirm the transaction on your hardware wallet".to_string()));
+            }
+            spawn(async move {
+                let account = &position.account;
+                let rpc_url = custom_rpc.as_deref();
+                let result = match &action {
+                    StakeAction::Deactivate => {
+                        normal_unstake_stake_account(account, wallet.as_ref(), hardware_wallet, rpc_url).await
+                    }
+                    StakeAction::Withdraw => {
+                        withdraw_stake_account(account, None, wallet.as_ref(), hardware_wallet, rpc_url).await
+                    }
+                    StakeAction::Split { lamports, deactivate: true } => {
+                        let sol = *lamports as f64 / 1_000_000_000.0;
+                        partial_unstake_stake_account(account, sol, wallet.as_ref(), hardware_wallet, rpc_url).await
+                    }
+                    StakeAction::Split { lamports, deactivate: false } => {
+                        split_stake_account(account, *lamports, wallet.as_ref(), hardware_wallet, rpc_url)
+                            .await
+                            .map(|(_, signature)| signature)
+                    }
+                    StakeAction::Merge(source) => {
+                        merge_stake_pair(&position, source, wallet.as_ref(), hardware_wallet, rpc_url).await
+                    }
+                    StakeAction::Sell => {
+                        let voter = position.voter.clone().unwrap_or_default();
+                        sell_stake_account_instantly(account, &voter, wallet.as_ref(), hardware_wallet, rpc_url).await
+                    }
+                };
+                match result {
+                    Ok(signature) => {
+                        status.set(Some(format!("{} {}: {}", action.done(), short(&account.pubkey.to_string()), signature)));
+                        split_open.set(None);
+                        merge_open.set(None);
+                        sale_quote.set(None);
+                        reload();
+                    }
+                    Err(e) => {
+                        status.set(None);
+                        error_message.set(Some(e.to_string()));
+                    }
+                }
+                working.set(None);
+            });
+        }
+    };
+
+    let total_delegated: u64 = positions()
+        .iter()
+        .filter(|p| matches!(p.phase, StakePhase::Active | StakePhase::Activating))
+        .map(|p| p.delegated)
+        .sum();
+    let total_rewards: u64 = positions().iter().map(|p| p.recent_rewards()).sum();
+    let summary = format!(
+        "{} stake account(s), {} delegated, {} earned over the last {} epochs",
+        positions().len(),
+        format_sol(total_delegated),
+        format_sol(total_rewards),
+        REWARD_EPOCHS
+    );
+    let mev_summary = mev_rewards().map(|mev| {
+        format!(
+            "Jito MEV rewards: {} over the last {} epochs, on top of inflation rewards",
+            format_sol(mev.values().sum()),
+            MEV_REWARD_EPOCHS
+        )
+    });
+    let epoch_note = current_epoch().map(|epoch| format!("Current epoch: {}", epoch)).unwrap_or_default();
+    let mut reload_all = load.clone();
+    let reload_imported = load.clone();
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Stake Positions" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(message) = status() {
+                    div { class: "info-message", "{message}" }
+                }
+
+                if loading() {
+                    div { class: "help-text", "Loading stake accounts..." }
+                } else if positions().is_empty() {
+                    div { class: "help-text", "This wallet has no stake accounts." }
+                } else {
+                    div { class: "info-message", "{summary}" }
+                    if let Some(mev) = mev_summary {
+                        div { class: "help-text", "{mev}" }
+                    }
+                    div { class: "help-text", "{epoch_note}" }
+                }
+
+                for position in positions() {
+                    {
+                        let pubkey = position.account.pubkey.to_string();
+                        let validator = match position.voter.as_deref() {
+                            Some(voter) => known_validator_name(voter).unwrap_or_else(|| format!("Validator {}", short(voter))),
+                            None => "Not delegated".to_string(),
+                        };
+                        let amounts = format!(
+                            "Balance {}, delegated {}",
+                            format_sol(position.account.balance),
+                            format_sol(position.delegated)
+                        );
+                        let rewards = match position.rewards.first() {
+                            Some(last) => format!(
+                                "Inflation rewards: {} in epoch {}, {} over the last {} epochs",
+                                format_sol(last.lamports),
+                                last.epoch,
+                                format_sol(position.recent_rewards()),
+                                REWARD_EPOCHS
+                            ),
+                            None => "No rewards in recent epochs".to_string(),
+                        };
+                        let mev = mev_rewards().map(|mev| match mev.get(&pubkey) {
+                            Some(lamports) => format!("MEV: {} over the last {} epochs", format_sol(*lamports), MEV_REWARD_EPOCHS),
+                            None => "MEV: none, the validator may not run Jito".to_string(),
+                        });
+                        let timing = match position.phase {
+                            StakePhase::Activating => "Active from the next epoch".to_string(),
+                            StakePhase::Deactivating => "Withdrawable from the next epoch".to_string(),
+                            _ => String::new(),
+                        };
+                        let authority_note = (position.staker != address || position.withdrawer != address).then(|| {
+                            format!("Stake authority {}, withdraw authority {}", short(&position.staker), short(&position.withdrawer))
+                        });
+                        let origin_note = match position.origin {
+                            StakeOrigin::Withdrawer => None,
+                            StakeOrigin::Staker => Some("Found by its stake authority: this wallet can delegate and deactivate it, but not withdraw"),
+                            StakeOrigin::Imported => Some("Imported by address"),
+                        };
+                        let imported = position.origin == StakeOrigin::Imported;
+                        let action = if position.can_deactivate(&address) {
+                            Some((StakeAction::Deactivate, "Deactivate"))
+                        } else if position.can_withdraw(&address) {
+                            Some((StakeAction::Withdraw, "Withdraw"))
+                        } else {
+                            None
+                        };
+                        let busy = working() == Some(pubkey.clone());
+                        let can_split = position.staker == address
+                            && matches!(position.phase, StakePhase::Active | StakePhase::Activating | StakePhase::Inactive);
+                        let merge_sources: Vec<StakePosition> = positions()
+                            .into_iter()
+                            .filter(|source| merge_compatible(&position, source))
+                            .collect();
+                        let showing_split = split_open() == Some(pubkey.clone());
+                        let showing_merge = merge_open() == Some(pubkey.clone());
+                        let split_lamports = split_amount()
+                            .trim()
+                            .parse::<f64>()
+                            .ok()
+                            .map(|sol| (sol * 1_000_000_000.0).round() as u64);
+                        let split_error = split_lamports
+                            .map(|lamports| validate_split(&position.account, lamports).err().map(|e| e.to_string()))
+                            .unwrap_or_else(|| Some("Enter the SOL to split off".to_string()));
+                        let active = position.phase == StakePhase::Active;
+                        let run_action = run.clone();
+                        let run_split = run.clone();
+                        let run_split_deactivate = run.clone();
+                        let run_merge = run.clone();
+                        let run_sale = run.clone();
+                        // Depositing into a pool moves both authorities, so the wallet needs both
+                        let can_sell = active && position.staker == address && position.withdrawer == address;
+                        let sale = sale_quote().filter(|(key, _)| *key == pubkey).map(|(_, quote)| quote);
+                        let sale_label = sale.as_ref().map(|quote| {
+                            format!(
+                                "Sell now for about {} through {} and Sanctum's router, {:.2}% below the {} after the deactivation cooldown",
+                                format_sol(quote.out_lamports),
+                                quote.via,
+                                quote.discount_percent(),
+                                format_sol(quote.fair_lamports)
+                            )
+                        });
+                        rsx! {
+                            div {
+                                key: "{pubkey}",
+                                class: "wallet-field",
+                                div {
+                                    class: "info-message",
+                                    span { class: phase_badge(position.phase), "{position.phase}" }
+                                    " {validator}"
+                                }
+                                div { class: "help-text", "{short(&pubkey)} · {amounts}" }
+                                div { class: "help-text", "{rewards}" }
+                                if let Some(mev) = mev {
+                                    div { class: "help-text", "{mev}" }
+                                }
+                                if !timing.is_empty() {
+                                    div { class: "help-text", "{timing}" }
+                                }
+                                if let Some(note) = authority_note {
+                                    div { class: "help-text", "{note}" }
+                                }
+                                if let Some(note) = origin_note {
+                                    div { class: "help-text", "{note}" }
+                                }
+                                div { class: "modal-buttons",
+                                    if let Some((action, label)) = action {
+                                        button {
+                                            class: "button-standard primary",
+                                            disabled: working().is_some(),
+                                            onclick: {
+                                                let position = position.clone();
+                                                let mut run_action = run_action.clone();
+                                                move |_| run_action(action.clone(), position.clone())
+                                            },
+                                            if busy { "Sending..." } else { "{label}" }
+                                        }
+                                    }
+                                    if can_split {
+                                        button {
+                                            class: "button-standard secondary",
+                                            onclick: {
+                                                let pubkey = pubkey.clone();
+                                                move |_| {
+                                                    merge_open.set(None);
+                                                    split_amount.set(String::new());
+                                                    split_open.set(if showing_split { None } else { Some(pubkey.clone()) });
+                                                }
+                                            },
+                                            "Split"
+                                        }
+                                    }
+                                    if can_sell {
+                                        button {
+                                            class: "button-standard secondary",
+                                            disabled: working().is_some() || quoting(),
+                                            onclick: {
+                                                let position = position.clone();
+                                                let mut quote_sale = quote_sale.clone();
+                                                move |_| quote_sale(position.clone())
+                                            },
+                                            if quoting() { "Quoting..." } else { "Sell Now" }
+                                        }
+                                    }
+                                    if imported {
+                                        button {
+                                            class: "button-standard secondary",
+                                            disabled: working().is_some(),
+                                            onclick: {
+                                                let address = address.clone();
+                                                let pubkey = pubkey.clone();
+                                                let mut reload = reload_imported.clone();
+                                                move |_| match forget_stake_account(&address, &pubkey) {
+                                                    Ok(()) => reload(),
+                                                    Err(e) => error_message.set(Some(e.to_string())),
+                                                }
+                                            },
+                                            "Forget"
+                                        }
+                                    }
+                                    if !merge_sources.is_empty() {
+                                        button {
+                                            class: "button-standard secondary",
+                                            onclick: {
+                                                let pubkey = pubkey.clone();
+                                                move |_| {
+                                                    split_open.set(None);
+                                                    merge_open.set(if showing_merge { None } else { Some(pubkey.clone()) });
+                                                }
+                                            },
+                                            "Merge"
+                                        }
+                                    }
+                                }
+                                if let Some(label) = sale_label {
+                                    div { class: "info-message", "{label}" }
+                                    div { class: "modal-buttons",
+                                        button {
+                                            class: "button-standard primary",
+                                            disabled: working().is_some(),
+                                            onclick: {
+                                                let position = position.clone();
+                                                let mut run_sale = run_sale.clone();
+                                                move |_| run_sale(StakeAction::Sell, position.clone())
+                                            },
+                                            if busy { "Selling..." } else { "Confirm Sale" }
+                                        }
+                                        button {
+                                            class: "button-standard secondary",
+                                            onclick: move |_| sale_quote.set(None),
+                                            "Cancel"
+                                        }
+                                    }
+                                }
+                                if showing_split {
+                                    div { class: "help-text", "Move part of this stake into a new stake account with the same validator." }
+                                    input {
+                                        r#type: "text",
+                                        value: "{split_amount}",
+                                        placeholder: "SOL to split off",
+                                        oninput: move |e| split_amount.set(e.value()),
+                                    }
+                                    if let Some(error) = split_error.clone().filter(|_| !split_amount().trim().is_empty()) {
+                                        div { class: "error-message", "{error}" }
+                                    }
+                                    div { class: "modal-buttons",
+                                        button {
+                                            class: "button-standard primary",
+                                            disabled: working().is_some() || split_error.is_some(),
+                                            onclick: {
+                                                let position = position.clone();
+                                                let mut run_split = run_split.clone();
+                                                move |_| {
+                                                    if let Some(lamports) = split_lamports {
+                                                        run_split(StakeAction::Split { lamports, deactivate: false }, position.clone());
+                                                    }
+                                                }
+                                            },
+                                            "Split Off"
+                                        }
+                                        if active {
+                                            button {
+                                                class: "button-standard secondary",
+                                                disabled: working().is_some() || split_error.is_some(),
+                                                onclick: {
+                                                    let position = position.clone();
+                                                    let mut run_split_deactivate = run_split_deactivate.clone();
+                                                    move |_| {
+                                                        if let Some(lamports) = split_lamports {
+                                                            run_split_deactivate(StakeAction::Split { lamports, deactivate: true }, position.clone());
+                                                        }
+                                                    }
+                                                },
+                                                "Split Off & Deactivate"
+                                            }
+                                        }
+                                    }
+                                }
+                                if showing_merge {
+                                    div { class: "help-text", "Merge another account into this one. The merged account is closed and its SOL added here." }
+                                    for source in merge_sources {
+                                        {
+                                            let source_key = source.account.pubkey.to_string();
+                                            let source_label = format!(
+                                                "Merge in {} ({}, {})",
+                                                short(&source_key),
+                                                source.phase,
+                                                format_sol(source.account.balance)
+                                            );
+                                            let position = position.clone();
+                                            let mut run_merge = run_merge.clone();
+                                            rsx! {
+                                                button {
+                                                    key: "{source_key}",
+                                                    class: "button-standard secondary",
+                                                    disabled: working().is_some(),
+                                                    onclick: move |_| run_merge(StakeAction::Merge(source.clone()), position.clone()),
+                                                    "{source_label}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !deactivatable.is_empty() {
+                    div {
+                        class: "wallet-field",
+                        label { "Unstake an amount (SOL):" }
+                        input {
+                            r#type: "text",
+                            value: "{unstake_sol}",
+                            placeholder: "0.0",
+                            oninput: move |e| unstake_sol.set(e.value()),
+                        }
+                        div { class: "help-text", "Whole accounts are deactivated where they add up to the amount, and the rest is split off into a new account first." }
+                        for line in plan_lines {
+                            div { class: "help-text", "{line}" }
+                        }
+                        if let Some(error) = plan_error {
+                            div { class: "error-message", "{error}" }
+                        }
+                        button {
+                            class: "button-standard primary",
+                            disabled: unstaking() || working().is_some() || !can_unstake,
+                            onclick: unstake,
+                            if unstaking() { "Unstaking..." } else { "Unstake" }
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Import a stake account:" }
+                    input {
+                        r#type: "text",
+                        value: "{import_address}",
+                        placeholder: "Stake account address",
+                        oninput: move |e| import_address.set(e.value()),
+                    }
+                    div { class: "help-text", "Accounts this wallet can withdraw or manage are found automatically when the RPC allows it. Add others created with the CLI or another wallet here." }
+                    button {
+                        class: "button-standard secondary",
+                        disabled: importing(),
+                        onclick: import,
+                        if importing() { "Importing..." } else { "Import" }
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        disabled: loading(),
+                        onclick: move |_| {
+                            error_message.set(None);
+                            reload_all();
+                        },
+                        "Refresh"
+                    }
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use dioxus::prelude::*;
+use crate::currency_utils::{format_price_in_selected_currency, get_current_currency_code, get_current_exchange_rate};
+use crate::staking_rewards::{fetch_rewards_history, RewardsHistory, REWARD_HISTORY_EPOCHS};
+use crate::storage::save_export_file;
+
+fn format_day(timestamp: Option<i64>) -> String {
+    timestamp
+        .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "date unknown".to_string())
+}
+
+/// Inflation rewards paid to the wallet's stake accounts per epoch, valued
+/// in the selected currency, with CSV export
+#[component]
+pub fn StakingRewardsModal(
+    address: String,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut epochs = use_signal(|| REWARD_HISTORY_EPOCHS[0]);
+    let mut history = use_signal(|| None as Option<RewardsHistory>);
+    let mut loading = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status = use_signal(|| None as Option<String>);
+
+    let load = {
+        let address = address.clone();
+        move |_| {
+            let address = address.clone();
+            let custom_rpc = custom_rpc.clone();
+            let count = epochs();
+            loading.set(true);
+            error_message.set(None);
+            status.set(None);
+            spawn(async move {
+                match fetch_rewards_history(&address, count, custom_rpc.as_deref()).await {
+                    Ok(found) => history.set(Some(found)),
+                    Err(e) => error_message.set(Some(format!("Failed to load rewards: {}", e))),
+                }
+                loading.set(false);
+            });
+        }
+    };
+
+    let export = move |_| {
+        let Some(current) = history() else { return };
+        let file_name = format!(
+            "staking_rewards_{}_{}.csv",
+            &current.wallet[..current.wallet.len().min(8)],
+            chrono::Utc::now().format("%Y%m%d")
+        );
+        let csv = current.to_csv(&get_current_currency_code(), get_current_exchange_rate());
+        match save_export_file(&file_name, &csv) {
+            Ok(path) => status.set(Some(format!("Saved to {}", path))),
+            Err(e) => error_message.set(Some(e)),
+        }
+    };
+
+    let summary = history().map(|current| {
+        format!(
+            "{:.6} SOL earned over {} reward(s), worth {} when paid",
+            current.total_lamports() as f64 / 1_000_000_000.0,
+            current.entries.len(),
+            format_price_in_selected_currency(current.total_usd())
+        )
+    });
+    let notes: Vec<String> = history()
+        .map(|current| {
+            let mut notes = Vec::new();
+            if current.missing_prices() {
+                notes.push("Some rewards have no SOL price for their day and are left out of the total.".to_string());
+            }
+            if !current.unavailable_epochs.is_empty() {
+                notes.push(format!(
+                    "The RPC has no reward data for {} epoch(s); a full history may need an archive RPC.",
+                    current.unavailable_epochs.len()
+                ));
+            }
+            notes
+        })
+        .unwrap_or_default();
+    let rows = history().map(|current| current.by_epoch()).unwrap_or_default();
+    let can_export = history().is_some_and(|current| !current.entries.is_empty());
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Staking Rewards" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Inflation rewards paid to this wallet's current stake accounts, valued at each day's SOL close. Closed stake accounts aren't included."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(message) = status() {
+                    div { class: "info-message", "{message}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Period:" }
+                    select {
+                        onchange: move |e| epochs.set(e.value().parse().unwrap_or(REWARD_HISTORY_EPOCHS[0])),
+                        for count in REWARD_HISTORY_EPOCHS.iter() {
+                            {
+                                let label = format!("Last {} epochs (~{} days)", count, count * 2);
+                                rsx! {
+                                    option { key: "{count}", value: "{count}", selected: *count == epochs(), "{label}" }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if let Some(summary) = summary {
+                    div { class: "info-message", "{summary}" }
+                }
+                for note in notes {
+                    div { class: "help-text", "{note}" }
+                }
+
+                for row in rows {
+                    {
+                        let label = format!("Epoch {} · {}", row.epoch, format_day(row.paid_at));
+                        let amount = format!("{:.6} SOL", row.lamports as f64 / 1_000_000_000.0);
+                        let value = row.usd.map(format_price_in_selected_currency).unwrap_or_else(|| "no price".to_string());
+                        rsx! {
+                            div {
+                                key: "{row.epoch}",
+                                class: "token-search-row",
+                                span { class: "token-search-label", "{label}" }
+                                span { class: "token-search-balance", "{amount} · {value}" }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        disabled: !can_export,
+                        onclick: export,
+                        "Export CSV"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: loading(),
+                        onclick: load,
+                        if loading() { "Loading..." } else { "Load Rewards" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,418 @@
+use dioxus::prelude::*;
+use crate::wallet::WalletInfo;
+use crate::hardware::HardwareWallet;
+use crate::streaming::{StreamingClient, StreamInfo, CreateStreamParams};
+use crate::signing::active_wallet_signer;
+use crate::transaction::TransactionClient;
+use crate::domain_resolver::resolve_recipient;
+use std::sync::Arc;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Resolve the wallet address for either wallet type
+async fn wallet_address(
+    hardware_wallet: &Option<Arc<HardwareWallet>>,
+    wallet: &Option<WalletInfo>,
+) -> Option<String> {
+    if let Some(hw) = hardware_wallet {
+        hw.get_public_key().await.ok()
+    } else {
+        wallet.as_ref().map(|w| w.address.clone())
+    }
+}
+
+fn format_units(amount: u64, decimals: u8) -> String {
+    format!("{:.4}", amount as f64 / 10_f64.powi(decimals as i32))
+}
+
+#[component]
+pub fn StreamingModal(
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut selected_tab = use_signal(|| "incoming");
+    let mut incoming = use_signal(|| Vec::<StreamInfo>::new());
+    let mut outgoing = use_signal(|| Vec::<StreamInfo>::new());
+    let mut decimals = use_signal(|| std::collections::HashMap::<String, u8>::new());
+    let mut loading = use_signal(|| false);
+    let mut refresh = use_signal(|| 0u32);
+    let mut processing = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut success_message = use_signal(|| None as Option<String>);
+
+    // Create form
+    let mut recipient_input = use_signal(|| String::new());
+    let mut mint_input = use_signal(|| "SOL".to_string());
+    let mut amount_input = use_signal(|| String::new());
+    let mut duration_days_input = use_signal(|| "30".to_string());
+    let mut per_second = use_signal(|| true);
+    let mut name_input = use_signal(|| String::new());
+
+    // Load streams whenever the modal opens or after a transaction
+    let wallet_for_effect = wallet.clone();
+    let hardware_for_effect = hardware_wallet.clone();
+    let rpc_for_effect = custom_rpc.clone();
+    use_effect(move || {
+        let _ = refresh();
+        let wallet = wallet_for_effect.clone();
+        let hardware = hardware_for_effect.clone();
+        let rpc_url = rpc_for_effect.clone();
+
+        loading.set(true);
+        spawn(async move {
+            let Some(address) = wallet_address(&hardware, &wallet).await else {
+                loading.set(false);
+                return;
+            };
+            let Ok(owner) = Pubkey::from_str(&address) else {
+                loading.set(false);
+                return;
+            };
+
+            let client = StreamingClient::new(rpc_url.as_deref());
+            let incoming_streams = client.get_incoming_streams(&owner).await.unwrap_or_else(|e| {
+                error_message.set(Some(format!("Failed to load streams: {}", e)));
+                Vec::new()
+            });
+            let outgoing_streams = client.get_outgoing_streams(&owner).await.unwrap_or_default();
+
+            let mints: Vec<String> = incoming_streams.iter()
+                .chain(outgoing_streams.iter())
+                .map(|s| s.mint.to_string())
+                .collect();
+            let mint_decimals = TransactionClient::new(rpc_url.as_deref())
+                .get_token_decimals_batch(&mints)
+                .await;
+
+            decimals.set(mint_decimals);
+            incoming.set(incoming_streams);
+            outgoing.set(outgoing_streams);
+            loading.set(false);
+        });
+    });
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    let decimals_map = decimals();
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                h2 { class: "modal-title", "Payment Streams" }
+
+                div {
+                    class: "modal-tabs",
+                    for tab in ["incoming", "outgoing", "create"] {
+                        button {
+                            key: "{tab}",
+                            class: if selected_tab() == tab { "button-standard primary" } else { "button-standard secondary" },
+                            onclick: move |_| {
+                                selected_tab.set(tab);
+                                error_message.set(None);
+                                success_message.set(None);
+                            },
+                            match tab {
+                                "incoming" => "Incoming",
+                                "outgoing" => "Outgoing",
+                                _ => "Create",
+                            }
+                        }
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(success) = success_message() {
+                    div { class: "success-message", "{success}" }
+                }
+
+                if loading() && selected_tab() != "create" {
+                    div { class: "info-message", "Loading streams..." }
+                }
+
+                if selected_tab() == "incoming" {
+                    if incoming().is_empty() && !loading() {
+                        div { class: "info-message", "No streams are paying this wallet" }
+                    }
+                    for stream in incoming() {
+                        {
+                            let d = decimals_map.get(&stream.mint.to_string()).copied().unwrap_or(9);
+                            let available = stream.withdrawable_amount(now);
+                            let stream_for_click = stream.clone();
+                            let wallet = wallet.clone();
+                            let hardware_wallet = hardware_wallet.clone();
+                            let custom_rpc = custom_rpc.clone();
+                            rsx! {
+                                div {
+                                    key: "{stream.id}",
+                                    class: "wallet-field",
+                                    label { if stream.name.is_empty() { "Stream {stream.id}" } else { "{stream.name}" } }
+                                    div { "From: {stream.sender}" }
+                                    div { "Streamed: {format_units(stream.unlocked_amount(now), d)} / {format_units(stream.net_amount_deposited, d)}" }
+                                    div { "Available: {format_units(available, d)}" }
+                                    button {
+                                        class: "button-standard primary",
+                                        disabled: processing() || available == 0,
+                                        onclick: move |_| {
+                                            let stream = stream_for_click.clone();
+                                            let signer = active_wallet_signer(hardware_wallet.clone(), wallet.clone());
+                                            let rpc_url = custom_rpc.clone();
+                                            processing.set(true);
+                                            error_message.set(None);
+                                            spawn(async move {
+                                                let result = match signer {
+                                                    Ok(signer) => StreamingClient::new(rpc_url.as_deref())
+                                                        .withdraw_with_signer(&*signer, &stream, None)
+                                                        .await
+                                                        .map_err(|e| e.to_string()),
+                                                    Err(e) => Err(e),
+                                                };
+                                                match result {
+                                                    Ok(signature) => {
+                                                        success_message.set(Some(format!("Withdrawn: {}", signature)));
+                                                        refresh.set(refresh() + 1);
+                                                    }
+                                                    Err(e) => error_message.set(Some(format!("Withdraw failed: {}", e))),
+                                                }
+                                                processing.set(false);
+                                            });
+                                        },
+                                        "Withdraw"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if selected_tab() == "outgoing" {
+                    if outgoing().is_empty() && !loading() {
+                        div { class: "info-message", "You have not created any streams" }
+                    }
+                    for stream in outgoing() {
+                        {
+                            let d = decimals_map.get(&stream.mint.to_string()).copied().unwrap_or(9);
+                            let finished = stream.is_finished(now);
+                            let stream_for_cancel = stream.clone();
+                            let wallet = wallet.clone();
+                            let hardware_wallet = hardware_wallet.clone();
+                            let custom_rpc = custom_rpc.clone();
+                            rsx! {
+                                div {
+                                    key: "{stream.id}",
+                                    class: "wallet-field",
+                                    label { if stream.name.is_empty() { "Stream {stream.id}" } else { "{stream.name}" } }
+                                    div { "To: {stream.recipient}" }
+                                    div { "Streamed: {format_units(stream.unlocked_amount(now), d)} / {format_units(stream.net_amount_deposited, d)}" }
+                                    if !finished && stream.cancelable_by_sender {
+                                        button {
+                                            class: "button-standard secondary",
+                                            disabled: processing(),
+                                            onclick: move |_| {
+                                                let stream = stream_for_cancel.clone();
+                                                let signer = active_wallet_signer(hardware_wallet.clone(), wallet.clone());
+                                                let rpc_url = custom_rpc.clone();
+                                                processing.set(true);
+                                                error_message.set(None);
+                                                spawn(async move {
+                                                    let result = match signer {
+                                                        Ok(signer) => StreamingClient::new(rpc_url.as_deref())
+                                                            .cancel_with_signer(&*signer, &stream)
+                                                            .await
+                                                            .map_err(|e| e.to_string()),
+                                                        Err(e) => Err(e),
+                                                    };
+                                                    match result {
+                                                        Ok(signature) => {
+                                                            success_message.set(Some(format!("Stream canceled: {}", signature)));
+                                                            refresh.set(refresh() + 1);
+                                                        }
+                                                        Err(e) => error_message.set(Some(format!("Cancel failed: {}", e))),
+                                                    }
+                                                    processing.set(false);
+                                                });
+                                            },
+                                            "Cancel Stream"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if selected_tab() == "create" {
+                    div {
+                        class: "wallet-field",
+                        label { "Recipient:" }
+                        input {
+                            value: "{recipient_input}",
+                            oninput: move |e| recipient_input.set(e.value()),
+                            placeholder: "Address or domain (e.g. recipient.sol)"
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Token (SOL or mint address):" }
+                        input {
+                            value: "{mint_input}",
+                            oninput: move |e| mint_input.set(e.value()),
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Total amount:" }
+                        input {
+                            r#type: "number",
+                            value: "{amount_input}",
+                            oninput: move |e| amount_input.set(e.value()),
+                            placeholder: "0.0",
+                            min: "0"
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Duration (days):" }
+                        input {
+                            r#type: "number",
+                            value: "{duration_days_input}",
+                            oninput: move |e| duration_days_input.set(e.value()),
+                            min: "1"
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Release:" }
+                        select {
+                            onchange: move |e| per_second.set(e.value() == "second"),
+                            option { value: "second", selected: per_second(), "Every second" }
+                            option { value: "day", selected: !per_second(), "Every day" }
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Name (optional):" }
+                        input {
+                            value: "{name_input}",
+                            oninput: move |e| name_input.set(e.value()),
+                            placeholder: "Salary"
+                        }
+                    }
+                }
+
+                div {
+                    class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                    if selected_tab() == "create" {
+                        button {
+                            class: "button-standard primary",
+                            disabled: processing(),
+                            onclick: move |_| {
+                                let recipient_text = recipient_input();
+                                let mint = if mint_input().trim().eq_ignore_ascii_case("SOL") {
+                                    spl_token::native_mint::id()
+                                } else {
+                                    match Pubkey::from_str(mint_input().trim()) {
+                                        Ok(pk) => pk,
+                                        Err(_) => {
+                                            error_message.set(Some("Invalid token mint".to_string()));
+                                            return;
+                                        }
+                                    }
+                                };
+                                let amount = match amount_input().parse::<f64>() {
+                                    Ok(a) if a > 0.0 => a,
+                                    _ => {
+                                        error_message.set(Some("Invalid amount".to_string()));
+                                        return;
+                                    }
+                                };
+                                let days = match duration_days_input().parse::<u64>() {
+                                    Ok(d) if d > 0 => d,
+                                    _ => {
+                                        error_message.set(Some("Invalid duration".to_string()));
+                                        return;
+                                    }
+                                };
+
+                                let period = if per_second() { 1 } else { SECONDS_PER_DAY };
+                                let name = name_input();
+                                let signer = active_wallet_signer(hardware_wallet.clone(), wallet.clone());
+                                let rpc_url = custom_rpc.clone();
+
+                                processing.set(true);
+                                error_message.set(None);
+                                spawn(async move {
+                                    let signer = match signer {
+                                        Ok(signer) => signer,
+                                        Err(e) => {
+                                            error_message.set(Some(e));
+                                            processing.set(false);
+                                            return;
+                                        }
+                                    };
+                                    let recipient = match resolve_recipient(&recipient_text).await {
+                                        Ok(resolved) => resolved.pubkey,
+                                        Err(e) => {
+                                            error_message.set(Some(e));
+                                            processing.set(false);
+                                            return;
+                                        }
+                                    };
+
+                                    let token_decimals = TransactionClient::new(rpc_url.as_deref())
+                                        .get_token_decimals_batch(&[mint.to_string()])
+                                        .await
+                                        .get(&mint.to_string())
+                                        .copied()
+                                        .unwrap_or(9);
+                                    let deposit_amount = (amount * 10_f64.powi(token_decimals as i32)) as u64;
+                                    let periods = (days * SECONDS_PER_DAY / period).max(1);
+
+                                    let params = CreateStreamParams {
+                                        recipient,
+                                        mint,
+                                        deposit_amount,
+                                        period,
+                                        amount_per_period: deposit_amount.div_ceil(periods).max(1),
+                                        start_time: 0,
+                                        cliff_amount: 0,
+                                        name,
+                                        cancelable_by_sender: true,
+                                        cancelable_by_recipient: false,
+                                        can_topup: true,
+                                    };
+
+                                    match StreamingClient::new(rpc_url.as_deref()).create_stream_with_signer(&*signer, &params).await {
+                                        Ok(result) => {
+                                            success_message.set(Some(format!("Stream {} created: {}", result.stream_id, result.signature)));
+                                            selected_tab.set("outgoing");
+                                            refresh.set(refresh() + 1);
+                                        }
+                                        Err(e) => error_message.set(Some(format!("Failed to create stream: {}", e))),
+                                    }
+                                    processing.set(false);
+                                });
+                            },
+                            if processing() { "Creating..." } else { "Create Stream" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
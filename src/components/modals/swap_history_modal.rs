@@ -0,0 +1,150 @@
+use dioxus::prelude::*;
+use crate::storage::load_swap_history;
+use crate::swap::history::{provider_stats, update_received};
+
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|d| d.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default()
+}
+
+fn format_sol(lamports: u64) -> String {
+    format!("{:.6} SOL", lamports as f64 / 1_000_000_000.0)
+}
+
+/// Swaps sent from this wallet with the quote, fill and fees of each, and
+/// how every provider's fills compare to its quotes
+#[component]
+pub fn SwapHistoryModal(
+    address: String,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut history = use_signal({
+        let address = address.clone();
+        move || load_swap_history(&address)
+    });
+    let mut checking = use_signal(|| true);
+
+    // Read the fills of swaps that have landed since they were sent
+    use_effect(move || {
+        let address = address.clone();
+        let custom_rpc = custom_rpc.clone();
+        spawn(async move {
+            history.set(update_received(&address, custom_rpc.as_deref()).await);
+            checking.set(false);
+        });
+    });
+
+    let stats: Vec<String> = provider_stats(&history())
+        .into_iter()
+        .map(|stats| {
+            let execution = match stats.average_shortfall_bps {
+                Some(bps) if bps >= 0.0 => format!("fills {:.1} bps under quote", bps),
+                Some(bps) => format!("fills {:.1} bps over quote", -bps),
+                None => "no fills read yet".to_string(),
+            };
+            format!(
+                "{}: {} swap(s), {} (of {}), average cost {}",
+                stats.provider,
+                stats.swaps,
+                execution,
+                stats.filled,
+                format_sol(stats.average_fees)
+            )
+        })
+        .collect();
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Swap History" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                if checking() {
+                    div { class: "help-text", "Checking recent swaps for their fills..." }
+                }
+
+                if history().is_empty() {
+                    div { class: "help-text", "No swaps recorded yet. Swaps sent from this wallet show up here." }
+                } else {
+                    div {
+                        class: "wallet-field",
+                        label { "By provider:" }
+                        for (index, line) in stats.into_iter().enumerate() {
+                            div { key: "{index}", class: "info-message", "{line}" }
+                        }
+                    }
+                }
+
+                for record in history() {
+                    {
+                        let title = format!(
+                            "{} {} → {} via {}",
+                            record.input_amount, record.input_symbol, record.output_symbol, record.provider
+                        );
+                        let quote = format!(
+                            "Quoted {:.6} {} ({:.6} per {}), slippage {}",
+                            record.quoted_output,
+                            record.output_symbol,
+                            record.quoted_price(),
+                            record.input_symbol,
+                            record.slippage.label()
+                        );
+                        let fill = match (record.received, record.shortfall_bps()) {
+                            (Some(received), Some(bps)) => format!("Received {:.6} {} ({:+.1} bps vs quote)", received, record.output_symbol, -bps),
+                            (Some(received), None) => format!("Received {:.6} {}", received, record.output_symbol),
+                            (None, _) => "Fill not read yet".to_string(),
+                        };
+                        let mut fees = format!(
+                            "Network fee {}, app tip {}",
+                            format_sol(record.network_fee),
+                            format_sol(record.app_tip)
+                        );
+                        if record.jito_tip > 0 {
+                            fees.push_str(&format!(", Jito tip {}", format_sol(record.jito_tip)));
+                        }
+                        if record.mev_protected {
+                            fees.push_str(", MEV protected");
+                        }
+                        let when = format_time(record.at);
+                        let explorer = crate::config::network::explorer_tx_url(&record.signature);
+                        rsx! {
+                            div {
+                                key: "{record.signature}",
+                                class: "wallet-field",
+                                div { class: "info-message", "{title}" }
+                                div { class: "help-text", "{when}" }
+                                div { class: "help-text", "{quote}" }
+                                div { class: "help-text", "{fill}" }
+                                div { class: "help-text", "{fees}" }
+                                a { class: "help-text", href: "{explorer}", target: "_blank", "View transaction" }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Close"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,9 +3,27 @@ use crate::wallet::WalletInfo;
 use crate::hardware::HardwareWallet;
 use crate::components::common::Token;
 use crate::transaction::TransactionClient;
+use crate::components::simulation_preview::{SimulationPreviewPanel, PreviewState, DrainWarning};
+use crate::components::transaction_export::TransactionExportPanel;
+use crate::components::tip_picker::TipPicker;
+use crate::components::token_search::TokenSearch;
+use crate::token_utils::{active_transfer_fee, fee_adjusted_quote, with_unique_symbol, FeeAdjustedQuote};
+use crate::storage::{
+    get_current_jito_settings, load_mev_protection, load_slippage_setting, load_swap_provider_settings, save_mev_protection,
+    save_slippage_setting,
+};
+use crate::swap::history::record_swap;
+use crate::swap::protected::send_protected;
+use crate::swap::{
+    describe_leg, dflow_route_legs, is_quote_expired, quote_seconds_left, jupiter_route_legs, parse_slippage_percent, route_summary, titan_route_legs,
+    Slippage, SwapRecord, SLIPPAGE_PRESETS_BPS,
+};
+use crate::tips::{self, tip_for};
+use crate::components::stuck_transaction::StuckTransactionMonitor;
+use crate::components::wsol_cleanup::WrappedSolCleanup;
 use crate::signing::hardware::HardwareSigner;
 use crate::signing::software::SoftwareSigner;
-use crate::signing::TransactionSigner;
+use crate::signing::{sign_available_slots, TransactionSigner};
 use crate::wallet::Wallet;
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
@@ -15,21 +33,16 @@ use solana_sdk::{
     hash::Hash as SolanaHash,
     instruction::Instruction as SolanaInstruction,
     instruction::AccountMeta as SolanaAccountMeta,
-    message::{v0, VersionedMessage},
-    system_instruction,
     address_lookup_table::AddressLookupTableAccount,
 };
 use crate::titan::{TitanClient, build_transaction_from_route};
 use crate::titan::SwapRoute as TitanSwapRoute;
 use crate::timeout;
+use crate::token_approvals::{compile_swap_transaction, ApprovalCheck};
 use std::str::FromStr;
 
 const ICON_SWITCH: &str = "https://cdn.jsdelivr.net/gh/hogyzen12/unruggable-app@main/assets/icons/SWITCH.svg";
 
-// Jules tip address for monetization (0.0001 SOL per swap)
-const JULES_TIP_ADDRESS: &str = "juLesoSmdTcRtzjCzYzRoHrnF8GhVu6KCV7uxq7nJGp";
-const JULES_TIP_LAMPORTS: u64 = 100_000; // 0.0001 SOL
-
 /// Convert SwapInstruction to Solana Instruction
 fn swap_instruction_to_solana(swap_ix: &SwapInstruction) -> Result<SolanaInstruction, String> {
     let program_id = SolanaPubkey::from_str(&swap_ix.program_id)
@@ -69,71 +82,48 @@ async fn fetch_lookup_tables(
     lookup_table_addresses: &[String],
     rpc_url: &str,
 ) -> Result<Vec<AddressLookupTableAccount>, String> {
-    let client = reqwest::Client::new();
+    let accounts = crate::rpc::get_multiple_accounts(lookup_table_addresses, Some(rpc_url))
+        .await
+        .map_err(|e| format!("Failed to fetch lookup tables: {}", e))?;
     let mut lookup_tables = Vec::new();
     
-    for address_str in lookup_table_addresses {
+    for (address_str, account) in lookup_table_addresses.iter().zip(accounts) {
         let pubkey = SolanaPubkey::from_str(address_str)
             .map_err(|e| format!("Invalid lookup table address: {}", e))?;
+        let Some(account) = account else { continue };
+        let data = account.data;
         
-        let request = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getAccountInfo",
-            "params": [
-                address_str,
-                {
-                    "encoding": "base64"
-                }
-            ]
-        });
-        
-        let response = client
-            .post(rpc_url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch lookup table: {}", e))?;
+        // Parse lookup table addresses (skip meta, each address is 32 bytes)
+        const META_SIZE: usize = 56;
+        if data.len() < META_SIZE {
+            continue;
+        }
         
-        let json: serde_json::Value = response.json().await
-            .map_err(|e| format!("Failed to parse lookup table response: {}", e))?;
+        let addresses_data = &data[META_SIZE..];
+        let num_addresses = addresses_data.len() / 32;
         
-        if let Some(data_array) = json["result"]["value"]["data"].as_array() {
-            if let Some(data_str) = data_array.get(0).and_then(|v| v.as_str()) {
-                let data = base64::decode(data_str)
-                    .map_err(|e| format!("Failed to decode lookup table data: {}", e))?;
-                
-                // Parse lookup table addresses (skip meta, each address is 32 bytes)
-                const META_SIZE: usize = 56;
-                if data.len() < META_SIZE {
-                    continue;
-                }
-                
-                let addresses_data = &data[META_SIZE..];
-                let num_addresses = addresses_data.len() / 32;
-                
-                let mut addresses = Vec::with_capacity(num_addresses);
-                for i in 0..num_addresses {
-                    let start = i * 32;
-                    let end = start + 32;
-                    let address_bytes: [u8; 32] = addresses_data[start..end]
-                        .try_into()
-                        .map_err(|_| "Invalid address bytes".to_string())?;
-                    addresses.push(SolanaPubkey::new_from_array(address_bytes));
-                }
-                
-                lookup_tables.push(AddressLookupTableAccount {
-                    key: pubkey,
-                    addresses,
-                });
-            }
+        let mut addresses = Vec::with_capacity(num_addresses);
+        for i in 0..num_addresses {
+            let start = i * 32;
+            let end = start + 32;
+            let address_bytes: [u8; 32] = addresses_data[start..end]
+                .try_into()
+                .map_err(|_| "Invalid address bytes".to_string())?;
+            addresses.push(SolanaPubkey::new_from_array(address_bytes));
         }
+        
+        lookup_tables.push(AddressLookupTableAccount {
+            key: pubkey,
+            addresses,
+        });
     }
     
     Ok(lookup_tables)
 }
 
-/// Build transaction from swap instructions and add jules tip
+/// Build transaction from swap instructions and add jules tip, revoking any
+/// token approval the route grants when `revoke_approvals` is set and
+/// tipping Jito `jito_tip` lamports when given
 async fn build_transaction_from_instructions(
     compute_budget_ixs: Vec<SwapInstruction>,
     setup_ixs: Vec<SwapInstruction>,
@@ -143,7 +133,9 @@ async fn build_transaction_from_instructions(
     lookup_table_addresses: Vec<String>,
     payer: SolanaPubkey,
     rpc_url: &str,
-) -> Result<Vec<u8>, String> {
+    revoke_approvals: bool,
+    jito_tip: Option<u64>,
+) -> Result<(Vec<u8>, ApprovalCheck), String> {
     println!("🔧 Building transaction from swap instructions");
     
     // Get current blockhash and slot
@@ -185,13 +177,9 @@ async fn build_transaction_from_instructions(
         all_instructions.push(swap_instruction_to_solana(&ix)?);
     }
     
-    // Add jules tip instruction
-    let jules_tip_address = SolanaPubkey::from_str(JULES_TIP_ADDRESS)
-        .map_err(|e| format!("Invalid jules tip address: {}", e))?;
-    let tip_ix = system_instruction::transfer(&payer, &jules_tip_address, JULES_TIP_LAMPORTS);
-    all_instructions.push(tip_ix);
-    
-    println!("   Added jules tip (0.0001 SOL) to swap transaction");
+    // Add the app tip, and the Jito tip when given
+    all_instructions.extend(tips::tip_instructions(&payer, jito_tip)?);
+    println!("   Added tips to swap transaction (Jito tip {:?} lamports)", jito_tip);
     println!("   Total instructions: {}", all_instructions.len());
     
     // Fetch lookup tables if any
@@ -202,18 +190,13 @@ async fn build_transaction_from_instructions(
     };
     
     // Build V0 message with lookup tables
-    let message = v0::Message::try_compile(
+    let (transaction, approval_check) = compile_swap_transaction(
         &payer,
         &all_instructions,
         &lookup_tables,
         recent_blockhash,
-    ).map_err(|e| format!("Failed to compile message: {}", e))?;
-    
-    // Create versioned transaction
-    let transaction = VersionedTransaction {
-        signatures: vec![solana_sdk::signature::Signature::default()],
-        message: VersionedMessage::V0(message),
-    };
+        revoke_approvals,
+    )?;
     
     // Serialize to bytes
     let serialized = bincode::serialize(&transaction)
@@ -221,7 +204,7 @@ async fn build_transaction_from_instructions(
     
     println!("   Transaction built: {} bytes", serialized.len());
     
-    Ok(serialized)
+    Ok((serialized, approval_check))
 }
 
 /// Sign a Jupiter Ultra transaction using the provided signer
@@ -256,45 +239,17 @@ async fn sign_jupiter_transaction(
         }
     }
     
-    println!("📋 Transaction has {} signatures expected", transaction.signatures.len());
+    println!("📋 Transaction has {} signatures expected", transaction.message.header().num_required_signatures);
     
-    // Serialize the transaction message for signing
-    let message_bytes = transaction.message.serialize();
-    println!("📝 Message to sign: {} bytes", message_bytes.len());
-    println!("🔍 Message bytes (first 32): {:02x?}", &message_bytes[..message_bytes.len().min(32)]);
-    
-    // Sign the message
-    println!("⏳ Waiting for hardware wallet signature...");
-    let signature_bytes = match signer.sign_message(&message_bytes).await {
-        Ok(sig) => {
-            println!("✅ Hardware wallet returned signature: {} bytes", sig.len());
-            sig
-        }
-        Err(e) => {
-            println!("❌ Hardware wallet signing failed: {}", e);
-            return Err(format!("Failed to sign message: {}", e));
-        }
-    };
-    
-    // Ensure we have exactly 64 bytes for the signature
-    if signature_bytes.len() != 64 {
-        println!("❌ Invalid signature length from hardware wallet");
-        return Err(format!("Invalid signature length: expected 64, got {}", signature_bytes.len()));
+    // Fill our own slot, wherever it is; other slots (e.g. a gasless fee
+    // payer) are signed by the service that built the transaction
+    println!("⏳ Waiting for signature...");
+    let missing = sign_available_slots(&mut transaction, &[signer]).await
+        .map_err(|e| format!("Failed to sign message: {}", e))?;
+    if !missing.is_empty() {
+        println!("ℹ️ Signatures still expected from: {:?}", missing);
     }
     
-    println!("🔍 Signature bytes (first 32): {:02x?}", &signature_bytes[..32]);
-    
-    // Convert to Solana signature
-    let mut sig_array = [0u8; 64];
-    sig_array.copy_from_slice(&signature_bytes);
-    let solana_signature = solana_sdk::signature::Signature::from(sig_array);
-    
-    // Replace the first signature (assumes single signer)
-    if transaction.signatures.is_empty() {
-        return Err("Transaction has no signature slots".to_string());
-    }
-    transaction.signatures[0] = solana_signature;
-    
     println!("✍️ Applied signature to transaction");
     
     // Serialize the signed transaction
@@ -311,6 +266,27 @@ async fn sign_jupiter_transaction(
     Ok(signed_transaction_b64)
 }
 
+/// Simulate an unsigned swap transaction so the balance changes can be shown before signing
+async fn preview_swap_transaction(
+    unsigned_tx_bytes: &[u8],
+    user_pubkey: &SolanaPubkey,
+    rpc_url: &str,
+) -> PreviewState {
+    let transaction: VersionedTransaction = match bincode::deserialize(unsigned_tx_bytes) {
+        Ok(tx) => tx,
+        Err(e) => return PreviewState::Unavailable(format!("Failed to decode transaction: {}", e)),
+    };
+
+    let client = TransactionClient::new(Some(rpc_url));
+    match client.simulate_balance_diff(&transaction, user_pubkey).await {
+        Ok(preview) => PreviewState::Ready(preview),
+        Err(e) => {
+            println!("⚠️ Swap simulation unavailable: {}", e);
+            PreviewState::Unavailable(e.to_string())
+        }
+    }
+}
+
 // Jupiter Ultra API Types
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct UltraOrderResponse {
@@ -404,6 +380,8 @@ pub struct JupiterSwapInstructionsRequest {
     pub use_shared_accounts: bool,
     #[serde(rename = "dynamicComputeUnitLimit")]
     pub dynamic_compute_unit_limit: bool,
+    #[serde(rename = "dynamicSlippage")]
+    pub dynamic_slippage: bool,
     #[serde(rename = "prioritizationFeeLamports")]
     pub prioritization_fee_lamports: serde_json::Value, // Can be "auto" or integer
     #[serde(rename = "quoteResponse")]
@@ -531,6 +509,24 @@ pub struct QuoteResponse {
     pub route_plan: Vec<serde_json::Value>,
 }
 
+/// Inline style of a slippage choice
+fn slippage_option_style(selected: bool) -> String {
+    format!(
+        "
+            background: {};
+            border: 1px solid {};
+            border-radius: 6px;
+            color: #f8fafc;
+            font-size: 12px;
+            font-weight: 600;
+            padding: 6px 10px;
+            cursor: pointer;
+        ",
+        if selected { "#2a2a2a" } else { "transparent" },
+        if selected { "#10b981" } else { "#3a3a3a" }
+    )
+}
+
 // Get token mint address from actual token data
 fn get_token_mint<'a>(symbol: &str, tokens: &'a [Token]) -> &'a str {
     tokens.iter()
@@ -647,10 +643,19 @@ pub fn SwapTransactionSuccessModal(
     buying_token: String,
     buying_amount: String,
     was_hardware_wallet: bool,
+    /// Wallet that swapped, to look for SOL the swap left wrapped
+    address: Option<String>,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    onreplaced: EventHandler<String>,
     onclose: EventHandler<()>,
 ) -> Element {
+    let tracked_signature = signature.clone();
+    // Only swaps from or to SOL wrap it
+    let wsol_owner = address.filter(|_| selling_token == "SOL" || buying_token == "SOL");
     // Explorer links - Solscan and Orb
-    let solscan_url = format!("https://solscan.io/tx/{}", signature);
+    let solscan_url = crate::config::network::explorer_tx_url(&signature);
     let orb_url = format!("https://orb.helius.dev/tx/{}?cluster=mainnet-beta&tab=summary", signature);
     
     rsx! {
@@ -727,6 +732,24 @@ pub fn SwapTransactionSuccessModal(
                             class: "copy-hint",
                             "Click to copy"
                         }
+                        StuckTransactionMonitor {
+                            key: "{tracked_signature}",
+                            signature: tracked_signature.clone(),
+                            wallet: wallet.clone(),
+                            hardware_wallet: hardware_wallet.clone(),
+                            custom_rpc: custom_rpc.clone(),
+                            onreplaced: move |replacement| onreplaced.call(replacement),
+                        }
+                        if let Some(owner) = wsol_owner {
+                            WrappedSolCleanup {
+                                key: "{owner}",
+                                address: owner.clone(),
+                                wallet: wallet.clone(),
+                                hardware_wallet: hardware_wallet.clone(),
+                                custom_rpc: custom_rpc.clone(),
+                                watch: true,
+                            }
+                        }
                     }
                     
                     div {
@@ -789,6 +812,28 @@ pub fn SwapModal(
     let mut transaction_signature = use_signal(|| "".to_string());
     let mut was_hardware_transaction = use_signal(|| false);
     let mut show_hardware_approval = use_signal(|| false);
+    let mut swap_preview = use_signal(|| PreviewState::Idle);
+    let mut drain_confirmed = use_signal(|| false);
+    // Build the swap for export instead of signing and sending it
+    let mut export_only = use_signal(|| false);
+    let mut export_tx = use_signal(|| None as Option<VersionedTransaction>);
+    // Jito tip for this swap, defaulting to the one in Jito settings
+    let jito_enabled = get_current_jito_settings().jito_tx;
+    let mut tip_level = use_signal(|| get_current_jito_settings().tip_level);
+    // Send swaps as Jito bundles only, never through the public RPC path
+    let mut mev_protected = use_signal(load_mev_protection);
+    // The swap being sent, stored in the swap history once it has a signature
+    let mut pending_swap = use_signal(|| None as Option<SwapRecord>);
+    // Token approvals found in the route being signed, and whether to revoke them
+    let mut route_approvals = use_signal(|| None as Option<ApprovalCheck>);
+    let mut revoke_approvals = use_signal(|| true);
+    // Selected quote corrected for Token-2022 transfer fees, with the minimum received
+    let mut fee_quote = use_signal(|| None as Option<FeeAdjustedQuote>);
+
+    // Slippage for every provider's quotes, with the custom value being typed
+    let mut slippage = use_signal(load_slippage_setting);
+    let mut custom_slippage = use_signal(String::new);
+    let mut slippage_error = use_signal(|| None as Option<String>);
 
     // Jupiter Legacy API state (instruction-based)
     let mut jupiter_quote = use_signal(|| None as Option<JupiterQuoteResponse>);
@@ -811,16 +856,57 @@ pub fn SwapModal(
     let mut fetching_titan = use_signal(|| false);
     let mut selected_provider = use_signal(|| None as Option<String>); // "Jupiter", "Dflow", or "Titan"
     let mut manual_provider_override = use_signal(|| None as Option<String>); // Manual provider selection
+    // Providers disabled or pinned in swap provider settings
+    let swap_providers = use_signal(load_swap_provider_settings);
+    // When the quote in use was fetched, and a clock ticking every second for its countdown
+    let mut quoted_at = use_signal(|| None as Option<i64>);
+    let mut quote_clock = use_signal(|| chrono::Utc::now().timestamp());
     
     // Store hardware wallet address (fetched async)
     let mut hw_address = use_signal(|| None as Option<String>);
 
+    // Tokens picked from search that the wallet doesn't hold, and which side
+    // ("sell" or "buy") the token search is open for
+    let mut extra_tokens = use_signal(Vec::<Token>::new);
+    let mut picking_token_for = use_signal(|| None as Option<&'static str>);
+    let wallet_tokens = tokens.clone();
+    let tokens: Vec<Token> = tokens.into_iter().chain(extra_tokens()).collect();
+
+    // Put a token picked from search on one side of the swap
+    let mut pick_token = {
+        let wallet_tokens = wallet_tokens.clone();
+        move |side: &'static str, token: Token| {
+            let known: Vec<Token> = wallet_tokens.iter().cloned().chain(extra_tokens()).collect();
+            let symbol = match known.iter().find(|t| t.mint == token.mint) {
+                Some(existing) => existing.symbol.clone(),
+                None => {
+                    let token = with_unique_symbol(token, &known);
+                    let symbol = token.symbol.clone();
+                    extra_tokens.write().push(token);
+                    symbol
+                }
+            };
+            if side == "sell" {
+                selling_token.set(symbol);
+                selling_amount.set("".to_string());
+            } else {
+                buying_token.set(symbol);
+            }
+            buying_amount.set("0.00".to_string());
+            jupiter_quote.set(None);
+            dflow_quote.set(None);
+            titan_quote.set(None);
+            picking_token_for.set(None);
+        }
+    };
+
     // Clone tokens for closures - need separate clones for each closure
     let tokens_clone = tokens.clone();
     let tokens_clone2 = tokens.clone();
     let tokens_clone3 = tokens.clone();
     let tokens_clone4 = tokens.clone(); // For handle_amount_change
     let tokens_clone5 = tokens.clone(); // For quote comparison use_effect
+    let custom_rpc_for_fee = custom_rpc.clone();
     let tokens_clone6 = tokens.clone(); // For UI rendering
 
     // Show transaction success modal if swap completed
@@ -833,6 +919,11 @@ pub fn SwapModal(
                 buying_token: buying_token(),
                 buying_amount: buying_amount(),
                 was_hardware_wallet: was_hardware_transaction(),
+                address: hw_address().or_else(|| wallet.as_ref().map(|w| w.address.clone())),
+                wallet: wallet.clone(),
+                hardware_wallet: hardware_wallet.clone(),
+                custom_rpc: custom_rpc.clone(),
+                onreplaced: move |replacement| transaction_signature.set(replacement),
                 onclose: move |_| {
                     show_success_modal.set(false);
                     // Call onsuccess when the user closes the modal
@@ -910,6 +1001,7 @@ pub fn SwapModal(
     // Titan Exchange: Fetch quotes with WebSocket streaming
     let fetch_titan_quotes = move |input_mint: String, output_mint: String, amount_lamports: u64, user_pubkey: Option<String>| {
         let client = titan_client();
+        let slippage_bps = slippage().titan_bps();
         spawn(async move {
             // Prevent multiple simultaneous requests
             if fetching_titan() {
@@ -949,7 +1041,7 @@ pub fn SwapModal(
                 &output_mint,
                 amount_lamports,
                 &user_pk,
-                Some(50), // 0.5% slippage
+                slippage_bps,
             ).await {
                 Ok((provider_name, route)) => {
                     println!("✅ Titan quote received from provider: {}", provider_name);
@@ -977,6 +1069,7 @@ pub fn SwapModal(
 
     // Jupiter Legacy API: Fetch quote for instruction-based swaps
     let fetch_jupiter_quote = move |input_mint: String, output_mint: String, amount_lamports: u64| {
+        let slippage_bps = slippage().jupiter_bps();
         spawn(async move {
             // Prevent multiple simultaneous requests
             if fetching_jupiter() {
@@ -1000,8 +1093,8 @@ pub fn SwapModal(
             
             // Build query parameters for Jupiter v1 /quote endpoint with required parameters
             let url = format!(
-                "https://api.jup.ag/swap/v1/quote?inputMint={}&outputMint={}&amount={}&slippageBps=50&swapMode=ExactIn&restrictIntermediateTokens=true&maxAccounts=64&instructionVersion=V1",
-                input_mint, output_mint, amount_lamports
+                "https://api.jup.ag/swap/v1/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}&swapMode=ExactIn&restrictIntermediateTokens=true&maxAccounts=64&instructionVersion=V1",
+                input_mint, output_mint, amount_lamports, slippage_bps
             );
             
             println!("🚀 Fetching Jupiter quote: {}", url);
@@ -1064,6 +1157,7 @@ pub fn SwapModal(
 
     // Dflow API: Fetch quote with API key authentication
     let fetch_dflow_quote = move |input_mint: String, output_mint: String, amount_lamports: u64| {
+        let slippage_param = slippage().dflow_param();
         spawn(async move {
             // Prevent multiple simultaneous requests
             if fetching_dflow() {
@@ -1076,8 +1170,8 @@ pub fn SwapModal(
             
             // Build query parameters
             let url = format!(
-                "https://quote-api.dflow.net/quote?inputMint={}&outputMint={}&amount={}&slippageBps=50",
-                input_mint, output_mint, amount_lamports
+                "https://quote-api.dflow.net/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}",
+                input_mint, output_mint, amount_lamports, slippage_param
             );
             
             println!("🌊 Fetching Dflow quote: {}", url);
@@ -1144,10 +1238,43 @@ pub fn SwapModal(
             let rpc_url = custom_rpc.as_deref();
             let transaction_client = TransactionClient::new(rpc_url);
             
-            // Submit directly to Solana RPC
-            match transaction_client.send_transaction(&signed_tx_b58).await {
+            // Token accounts the swap creates only exist once it lands, so
+            // check for them before sending
+            let transaction = bincode::deserialize::<VersionedTransaction>(&signed_tx_bytes).ok();
+            let fees = match &transaction {
+                Some(transaction) => match transaction_client.fee_breakdown_on_chain(&transaction.message).await {
+                    Ok(fees) => Some(fees),
+                    Err(e) => {
+                        println!("⚠️ Failed to work out swap fees: {}", e);
+                        None
+                    }
+                },
+                None => None,
+            };
+            
+            // Submit as a Jito bundle when MEV protection is on, otherwise directly to Solana RPC
+            let sent = if *mev_protected.peek() {
+                send_protected(&signed_tx_bytes).await.map_err(|e| e.to_string())
+            } else {
+                transaction_client.send_transaction(&signed_tx_b58).await.map_err(|e| e.to_string())
+            };
+            match sent {
                 Ok(signature) => {
                     println!("✅ Titan swap executed successfully! Signature: {}", signature);
+                    let record = pending_swap.write().take();
+                    if let (Some(mut record), Some(transaction)) = (record, transaction) {
+                        record.signature = signature.clone();
+                        record.at = chrono::Utc::now().timestamp();
+                        if let Some(fees) = fees {
+                            record.network_fee = fees.base_fee + fees.priority_fee;
+                            record.jito_tip = fees.jito_tip;
+                            record.app_tip = fees.jules_tip;
+                        }
+                        let owner = transaction.message.static_account_keys()[0].to_string();
+                        if let Err(e) = record_swap(&owner, record) {
+                            println!("⚠️ Failed to store swap in history: {}", e);
+                        }
+                    }
                     transaction_signature.set(signature);
                     swapping.set(false);
                     show_success_modal.set(true);
@@ -1302,11 +1429,16 @@ pub fn SwapModal(
     let mut handle_amount_change = move |value: String| {
         selling_amount.set(value.clone());
         error_message.set(None);
+        swap_preview.set(PreviewState::Idle);
+        drain_confirmed.set(false);
+        route_approvals.set(None);
+        fee_quote.set(None);
         jupiter_quote.set(None); // Clear previous Jupiter quote
         dflow_quote.set(None); // Clear previous Dflow quote
         titan_quote.set(None); // Clear previous Titan quote
         selected_provider.set(None); // Clear provider selection
         manual_provider_override.set(None); // Clear manual override
+        quoted_at.set(None);
         
         if !value.is_empty() {
             if let Ok(amount) = value.parse::<f64>() {
@@ -1356,17 +1488,24 @@ pub fn SwapModal(
                     spawn(async move {
                         tokio::time::sleep(std::time::Duration::from_millis(300)).await;
                         
-                        // Spawn all three quote requests in parallel
-                        println!("🔄 Fetching quotes from Jupiter, Dflow, and Titan...");
+                        // Spawn the enabled providers' quote requests in parallel
+                        let providers = swap_providers.peek().clone();
+                        println!("🔄 Fetching quotes, disabled providers: {:?}", providers.disabled);
                         
                         // Jupiter request (legacy /quote API)
-                        fetch_jupiter_quote(input_mint_jup, output_mint_jup, amount_lamports);
+                        if providers.is_enabled("Jupiter") {
+                            fetch_jupiter_quote(input_mint_jup, output_mint_jup, amount_lamports);
+                        }
                         
                         // Dflow request (runs in parallel)
-                        fetch_dflow_quote(input_mint_dflow, output_mint_dflow, amount_lamports);
+                        if providers.is_enabled("Dflow") {
+                            fetch_dflow_quote(input_mint_dflow, output_mint_dflow, amount_lamports);
+                        }
                         
                         // Titan request (runs in parallel)
-                        fetch_titan_quotes(input_mint_titan, output_mint_titan, amount_lamports, user_pubkey_titan);
+                        if providers.is_enabled("Titan") {
+                            fetch_titan_quotes(input_mint_titan, output_mint_titan, amount_lamports, user_pubkey_titan);
+                        }
                     });
                 }
             }
@@ -1378,6 +1517,56 @@ pub fn SwapModal(
         }
     };
 
+    // Store a new slippage setting and requote with it
+    let set_slippage = {
+        let mut requote = handle_amount_change.clone();
+        move |value: Slippage| {
+            if let Err(e) = save_slippage_setting(&value) {
+                println!("⚠️ Failed to save slippage setting: {}", e);
+            }
+            slippage.set(value);
+            slippage_error.set(None);
+            requote(selling_amount());
+        }
+    };
+
+    // Count down the quote and fetch a new one when it expires, unless a swap is being sent
+    use_effect({
+        let handle_amount_change = handle_amount_change.clone();
+        move || {
+            let mut requote = handle_amount_change.clone();
+            spawn(async move {
+                loop {
+                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                    let now = chrono::Utc::now().timestamp();
+                    quote_clock.set(now);
+                    let expired = quoted_at.peek().map(|at| is_quote_expired(at, now)).unwrap_or(false);
+                    if expired && !*swapping.peek() && !selling_amount.peek().is_empty() {
+                        println!("⏱️ Quote expired, fetching a new one");
+                        requote(selling_amount.peek().clone());
+                    }
+                }
+            });
+        }
+    });
+
+    let quote_expired = quoted_at().map(|at| is_quote_expired(at, quote_clock())).unwrap_or(false);
+    let quote_countdown = match quoted_at() {
+        Some(at) if !quote_expired => format!("Quote refreshes in {}s", quote_seconds_left(at, quote_clock())),
+        Some(_) => "Quote expired, refreshing...".to_string(),
+        None => String::new(),
+    };
+
+    let app_tip_note = match crate::config::app_tip::app_tip_lamports() {
+        0 => "App tip: none".to_string(),
+        lamports => format!("App tip: {} per swap", crate::config::app_tip::app_tip_label(lamports)),
+    };
+
+    let dynamic_slippage_note = format!(
+        "Each provider picks the tolerance for the route (Jupiter up to {})",
+        Slippage::Fixed(crate::swap::DYNAMIC_MAX_BPS).label()
+    );
+
     // Quote comparison logic: Compare Jupiter, Dflow, and Titan quotes and select the best
     use_effect(move || {
         let jup_quote = jupiter_quote();
@@ -1406,15 +1595,15 @@ pub fn SwapModal(
             return;
         }
         
-        // Find the best quote (highest output amount)
-        let best_quote = quotes.iter().max_by_key(|(_, output)| output);
+        // Use the pinned provider's quote if it has one, otherwise the best (highest output amount)
+        let best_quote = swap_providers.peek().pick(&quotes);
         
         if let Some((provider, best_output)) = best_quote {
             println!("📊 Quote Comparison:");
             for (prov, output) in &quotes {
                 println!("   {}: {} lamports", prov, output);
             }
-            println!("🏆 {} wins with best rate", provider);
+            println!("🏆 {} wins", provider);
             
             // Check if user has manually overridden provider selection
             let active_provider = if let Some(manual) = manual_provider_override() {
@@ -1429,6 +1618,7 @@ pub fn SwapModal(
             };
             
             selected_provider.set(Some(active_provider.clone()));
+            quoted_at.set(Some(chrono::Utc::now().timestamp()));
             
             // Update buying amount with selected provider's quote
             let selected_output = quotes.iter()
@@ -1442,7 +1632,29 @@ pub fn SwapModal(
             } else {
                 format!("{:.2}", converted_amount)
             };
-            buying_amount.set(formatted);
+            buying_amount.set(formatted.clone());
+
+            // Token-2022 mints may withhold a transfer fee from the amount sold
+            // and from the swap output
+            let input_mint = get_token_mint(&selling_token(), &tokens_clone5).to_string();
+            let output_mint = get_token_mint(&buying_token(), &tokens_clone5).to_string();
+            let input_units = to_lamports(
+                selling_amount.peek().parse::<f64>().unwrap_or(0.0),
+                &selling_token(),
+                &tokens_clone5,
+            );
+            let slippage_bps = slippage.peek().jupiter_bps();
+            let rpc_url = custom_rpc_for_fee.clone();
+            spawn(async move {
+                let client = TransactionClient::new(rpc_url.as_deref());
+                let input_fee = active_transfer_fee(&client, &input_mint).await.ok().flatten();
+                let output_fee = active_transfer_fee(&client, &output_mint).await.ok().flatten();
+                let quote = fee_adjusted_quote(input_units, input_fee.as_ref(), selected_output, output_fee.as_ref(), slippage_bps);
+                // Ignore fees for a quote that has since been replaced
+                if *buying_amount.peek() == formatted {
+                    fee_quote.set(Some(quote));
+                }
+            });
         }
     });
 
@@ -1457,6 +1669,11 @@ pub fn SwapModal(
                 return;
             }
 
+            if quoted_at().map(|at| is_quote_expired(at, chrono::Utc::now().timestamp())).unwrap_or(false) {
+                error_message.set(Some("The quote expired, wait for the new one".to_string()));
+                return;
+            }
+
             // Clone custom_rpc at the start so it can be used in multiple spawn blocks
             let custom_rpc_for_titan = custom_rpc_clone.clone();
 
@@ -1474,6 +1691,36 @@ pub fn SwapModal(
 
                 // Check which provider won the quote comparison
                 let provider = selected_provider();
+
+                // Keep the quote the swap is sent on for the swap history
+                let quoted_units = match provider.as_deref() {
+                    Some("Jupiter") => jupiter_quote().and_then(|q| q.out_amount.parse::<u64>().ok()),
+                    Some("Dflow") => dflow_quote().and_then(|q| q.out_amount.parse::<u64>().ok()),
+                    Some("Titan") => titan_quote().map(|(_, route)| route.out_amount),
+                    _ => None,
+                };
+                pending_swap.set(Some(SwapRecord {
+                    signature: String::new(),
+                    at: 0,
+                    provider: provider.clone().unwrap_or_default(),
+                    input_mint: get_token_mint(&selling_token(), &tokens_clone3).to_string(),
+                    output_mint: get_token_mint(&buying_token(), &tokens_clone3).to_string(),
+                    input_symbol: selling_token(),
+                    output_symbol: buying_token(),
+                    output_decimals: get_token_decimals(&buying_token(), &tokens_clone3),
+                    input_amount: amount,
+                    quoted_output: from_lamports(
+                        fee_quote().filter(|q| Some(q.quoted) == quoted_units).map(|q| q.received).or(quoted_units).unwrap_or(0),
+                        &buying_token(),
+                        &tokens_clone3,
+                    ),
+                    slippage: slippage(),
+                    mev_protected: mev_protected(),
+                    network_fee: 0,
+                    jito_tip: 0,
+                    app_tip: 0,
+                    received: None,
+                }));
                 
                 if provider == Some("Titan".to_string()) {
                     // Titan won - build transaction from instructions
@@ -1539,6 +1786,8 @@ pub fn SwapModal(
                                 }
                             };
                             
+                            let jito_tip = if jito_enabled || mev_protected() { Some(tip_for(tip_level()).await) } else { None };
+
                             // Build transaction from Titan route with lookup tables
                             let rpc_url = custom_rpc_titan.as_deref().unwrap_or("https://johna-k3cr1v-fast-mainnet.helius-rpc.com");
                             let unsigned_tx_bytes = match build_transaction_from_route(
@@ -1546,9 +1795,12 @@ pub fn SwapModal(
                                 user_pubkey,
                                 recent_blockhash,
                                 rpc_url,
+                                revoke_approvals(),
+                                jito_tip,
                             ).await {
-                                Ok(bytes) => {
+                                Ok((bytes, approval_check)) => {
                                     println!("✅ Transaction built: {} bytes", bytes.len());
+                                    route_approvals.set(Some(approval_check));
                                     bytes
                                 }
                                 Err(e) => {
@@ -1561,6 +1813,30 @@ pub fn SwapModal(
                             
                             // Convert to base64 for signing
                             let unsigned_tx_b64 = base64::encode(&unsigned_tx_bytes);
+
+                            // Simulate before requesting a signature
+                            let preview = preview_swap_transaction(&unsigned_tx_bytes, &user_pubkey, rpc_url).await;
+                            let expected_failure = matches!(&preview, PreviewState::Ready(p) if !p.success);
+                            let needs_confirmation = preview.needs_confirmation() && !drain_confirmed();
+                            swap_preview.set(preview);
+                            if expected_failure {
+                                swapping.set(false);
+                                error_message.set(Some("Swap simulation failed - transaction was not signed".to_string()));
+                                return;
+                            }
+                            if needs_confirmation {
+                                swapping.set(false);
+                                error_message.set(Some("Review the drain warning below and confirm to sign this swap".to_string()));
+                                return;
+                            }
+                            if export_only() {
+                                match bincode::deserialize::<VersionedTransaction>(&unsigned_tx_bytes) {
+                                    Ok(transaction) => export_tx.set(Some(transaction)),
+                                    Err(e) => error_message.set(Some(format!("Failed to decode transaction: {}", e))),
+                                }
+                                swapping.set(false);
+                                return;
+                            }
                             
                             // Continue with signing flow
                             // Determine if this is a hardware wallet transaction
@@ -1648,6 +1924,7 @@ pub fn SwapModal(
                                 wrap_and_unwrap_sol: true,
                                 use_shared_accounts: true,
                                 dynamic_compute_unit_limit: true,
+                                dynamic_slippage: slippage.peek().is_dynamic(),
                                 prioritization_fee_lamports: serde_json::json!("auto"),
                                 quote_response: quote_clone,
                             };
@@ -1688,6 +1965,7 @@ pub fn SwapModal(
                                                     vec![]
                                                 };
                                                 
+                                                let jito_tip = if jito_enabled || mev_protected() { Some(tip_for(tip_level()).await) } else { None };
                                                 match build_transaction_from_instructions(
                                                     swap_ix_response.compute_budget_instructions,
                                                     swap_ix_response.setup_instructions,
@@ -1697,12 +1975,39 @@ pub fn SwapModal(
                                                     swap_ix_response.address_lookup_table_addresses,
                                                     user_pk,
                                                     rpc_url,
+                                                    revoke_approvals(),
+                                                    jito_tip,
                                                 ).await {
-                                                    Ok(unsigned_tx_bytes) => {
+                                                    Ok((unsigned_tx_bytes, approval_check)) => {
+                                                        route_approvals.set(Some(approval_check));
                                                         println!("✅ Jupiter transaction built with jules tip");
                                                         
                                                         // Convert to base64 for signing
                                                         let unsigned_tx_b64 = base64::encode(&unsigned_tx_bytes);
+
+                                                        // Simulate before requesting a signature
+                                                        let preview = preview_swap_transaction(&unsigned_tx_bytes, &user_pk, rpc_url).await;
+                                                        let expected_failure = matches!(&preview, PreviewState::Ready(p) if !p.success);
+                                                        let needs_confirmation = preview.needs_confirmation() && !drain_confirmed();
+                                                        swap_preview.set(preview);
+                                                        if expected_failure {
+                                                            swapping.set(false);
+                                                            error_message.set(Some("Swap simulation failed - transaction was not signed".to_string()));
+                                                            return;
+                                                        }
+                                                        if needs_confirmation {
+                                                            swapping.set(false);
+                                                            error_message.set(Some("Review the drain warning below and confirm to sign this swap".to_string()));
+                                                            return;
+                                                        }
+                                                        if export_only() {
+                                                            match bincode::deserialize::<VersionedTransaction>(&unsigned_tx_bytes) {
+                                                                Ok(transaction) => export_tx.set(Some(transaction)),
+                                                                Err(e) => error_message.set(Some(format!("Failed to decode transaction: {}", e))),
+                                                            }
+                                                            swapping.set(false);
+                                                            return;
+                                                        }
                                                         
                                                         // Determine if hardware wallet
                                                         let is_hardware = hw_clone.is_some();
@@ -1839,6 +2144,7 @@ pub fn SwapModal(
                                                     }
                                                 };
                                                 
+                                                let jito_tip = if jito_enabled || mev_protected() { Some(tip_for(tip_level()).await) } else { None };
                                                 match build_transaction_from_instructions(
                                                     swap_ix_response.compute_budget_instructions,
                                                     swap_ix_response.setup_instructions,
@@ -1848,12 +2154,39 @@ pub fn SwapModal(
                                                     swap_ix_response.address_lookup_table_addresses,
                                                     user_pk,
                                                     rpc_url,
+                                                    revoke_approvals(),
+                                                    jito_tip,
                                                 ).await {
-                                                    Ok(unsigned_tx_bytes) => {
+                                                    Ok((unsigned_tx_bytes, approval_check)) => {
+                                                        route_approvals.set(Some(approval_check));
                                                         println!("✅ Dflow transaction built with jules tip");
                                                         
                                                         // Convert to base64 for signing
                                                         let unsigned_tx_b64 = base64::encode(&unsigned_tx_bytes);
+
+                                                        // Simulate before requesting a signature
+                                                        let preview = preview_swap_transaction(&unsigned_tx_bytes, &user_pk, rpc_url).await;
+                                                        let expected_failure = matches!(&preview, PreviewState::Ready(p) if !p.success);
+                                                        let needs_confirmation = preview.needs_confirmation() && !drain_confirmed();
+                                                        swap_preview.set(preview);
+                                                        if expected_failure {
+                                                            swapping.set(false);
+                                                            error_message.set(Some("Swap simulation failed - transaction was not signed".to_string()));
+                                                            return;
+                                                        }
+                                                        if needs_confirmation {
+                                                            swapping.set(false);
+                                                            error_message.set(Some("Review the drain warning below and confirm to sign this swap".to_string()));
+                                                            return;
+                                                        }
+                                                        if export_only() {
+                                                            match bincode::deserialize::<VersionedTransaction>(&unsigned_tx_bytes) {
+                                                                Ok(transaction) => export_tx.set(Some(transaction)),
+                                                                Err(e) => error_message.set(Some(format!("Failed to decode transaction: {}", e))),
+                                                            }
+                                                            swapping.set(false);
+                                                            return;
+                                                        }
                                                         
                                                         // Determine if hardware wallet
                                                         let is_hardware = hw_clone.is_some();
@@ -1985,6 +2318,46 @@ pub fn SwapModal(
         }
     });
 
+    // What actually arrives once Token-2022 transfer fees are withheld, and at worst
+    let (transfer_fee_note, minimum_received_note) = match fee_quote() {
+        Some(quote) => {
+            let sell = selling_token();
+            let buy = buying_token();
+            let fee_note = quote.has_fees().then(|| {
+                let mut fees = Vec::new();
+                if quote.input_fee > 0 {
+                    fees.push(format!("{} {}", from_lamports(quote.input_fee, &sell, &tokens), sell));
+                }
+                if quote.output_fee > 0 {
+                    fees.push(format!("{} {}", from_lamports(quote.output_fee, &buy, &tokens), buy));
+                }
+                format!("≈{:.6} after {} transfer fee", from_lamports(quote.received, &buy, &tokens), fees.join(" + "))
+            });
+            let minimum = format!("Min. received: {:.6} {}", from_lamports(quote.minimum_received, &buy, &tokens), buy);
+            (fee_note, Some(minimum))
+        }
+        None => (None, None),
+    };
+
+    // Where each provider routes the swap; legs are spelled out for the selected one
+    let token_lookup = |mint: &str| tokens.iter().find(|t| t.mint == mint).map(|t| (t.symbol.clone(), t.decimals));
+    let quote_routes: Vec<(String, String, Vec<String>)> = [
+        ("Jupiter", jupiter_quote().map(|quote| jupiter_route_legs(&quote.route_plan))),
+        ("Dflow", dflow_quote().map(|quote| dflow_route_legs(&quote.route_plan))),
+        ("Titan", titan_quote().map(|(_, route)| titan_route_legs(&route))),
+    ]
+    .into_iter()
+    .filter_map(|(provider, legs)| {
+        let legs = legs.filter(|legs| !legs.is_empty())?;
+        let details = if selected_provider().as_deref() == Some(provider) {
+            legs.iter().map(|leg| describe_leg(leg, &token_lookup)).collect()
+        } else {
+            Vec::new()
+        };
+        Some((provider.to_string(), route_summary(&legs), details))
+    })
+    .collect();
+
     rsx! {
         div {
             class: "modal-backdrop",
@@ -2066,6 +2439,92 @@ pub fn SwapModal(
                         "{error}"
                     }
                 }
+
+                SimulationPreviewPanel { state: swap_preview() }
+                DrainWarning {
+                    state: swap_preview(),
+                    confirmed: drain_confirmed(),
+                    onchange: move |confirmed| drain_confirmed.set(confirmed),
+                }
+
+                if let Some(check) = route_approvals().filter(|c| !c.approvals.is_empty()) {
+                    div {
+                        class: "warning-message simulation-preview",
+                        div { "🔑 This route approves a token delegate:" }
+                        for (index, line) in check.summary_lines().into_iter().enumerate() {
+                            div { key: "{index}", "{line}" }
+                        }
+                    }
+                }
+
+                label {
+                    class: "help-text",
+                    style: "display: block; margin: 0 16px;",
+                    input {
+                        r#type: "checkbox",
+                        checked: revoke_approvals(),
+                        oninput: move |_| revoke_approvals.set(!revoke_approvals()),
+                    }
+                    " Revoke token approvals after the swap when the route needs one"
+                }
+
+                label {
+                    class: "help-text",
+                    style: "display: block; margin: 0 16px;",
+                    input {
+                        r#type: "checkbox",
+                        checked: mev_protected(),
+                        disabled: swapping(),
+                        oninput: move |_| {
+                            let enabled = !mev_protected();
+                            if let Err(e) = save_mev_protection(enabled) {
+                                println!("⚠️ Failed to save MEV protection setting: {}", e);
+                            }
+                            mev_protected.set(enabled);
+                        },
+                    }
+                    " MEV protection: send the swap only as a Jito bundle, never through the public mempool"
+                }
+
+                div {
+                    class: "help-text",
+                    style: "margin: 0 16px;",
+                    "{app_tip_note}"
+                }
+
+                if jito_enabled || mev_protected() {
+                    div {
+                        style: "margin: 0 16px;",
+                        TipPicker {
+                            selected: tip_level(),
+                            onchange: move |level| tip_level.set(level),
+                        }
+                    }
+                }
+
+                label {
+                    class: "help-text",
+                    style: "display: block; margin: 0 16px;",
+                    input {
+                        r#type: "checkbox",
+                        checked: export_only(),
+                        oninput: move |_| export_only.set(!export_only()),
+                    }
+                    " Export the transaction instead of sending it"
+                }
+
+                if let Some(transaction) = export_tx() {
+                    div {
+                        style: "margin: 0 16px;",
+                        TransactionExportPanel {
+                            transaction,
+                            label: "swap".to_string(),
+                            wallet: wallet.clone(),
+                            hardware_wallet: hardware_wallet.clone(),
+                            onclose: move |_| export_tx.set(None),
+                        }
+                    }
+                }
                 
                 // Selling section - COMPACT
                 div {
@@ -2150,6 +2609,22 @@ pub fn SwapModal(
                                     }
                                 }
                             }
+                            button {
+                                class: "swap-token-search-button",
+                                style: "
+                                    background: #2a2a2a;
+                                    border: 1px solid #5a5a5a;
+                                    border-radius: 8px;
+                                    color: #ffffff;
+                                    cursor: pointer;
+                                    min-height: 38px;
+                                    padding: 0 8px;
+                                ",
+                                title: "Search all tokens or paste a mint",
+                                disabled: swapping(),
+                                onclick: move |_| picking_token_for.set(Some("sell")),
+                                "🔍"
+                            }
                         }
                         
                         // Amount input - COMPACT
@@ -2318,6 +2793,22 @@ pub fn SwapModal(
                                     }
                                 }
                             }
+                            button {
+                                class: "swap-token-search-button",
+                                style: "
+                                    background: #2a2a2a;
+                                    border: 1px solid #5a5a5a;
+                                    border-radius: 8px;
+                                    color: #ffffff;
+                                    cursor: pointer;
+                                    min-height: 38px;
+                                    padding: 0 8px;
+                                ",
+                                title: "Search all tokens or paste a mint",
+                                disabled: swapping(),
+                                onclick: move |_| picking_token_for.set(Some("buy")),
+                                "🔍"
+                            }
                         }
                         
                         // Amount display (read-only) - COMPACT
@@ -2359,10 +2850,103 @@ pub fn SwapModal(
                                 ",
                                 "${buying_usd_value():.2}"
                             }
+                            if let Some(note) = transfer_fee_note {
+                                div {
+                                    class: "swap-amount-usd",
+                                    style: "color: #f59e0b; font-size: 11px; text-align: right; margin-top: 2px;",
+                                    "{note}"
+                                }
+                            }
+                            if let Some(note) = minimum_received_note {
+                                div {
+                                    class: "swap-amount-usd",
+                                    style: "color: #94a3b8; font-size: 11px; text-align: right; margin-top: 2px;",
+                                    "{note}"
+                                }
+                            }
                         }
                     }
                 }
                 
+                // Slippage tolerance - COMPACT
+                div {
+                    class: "slippage-selector",
+                    style: "
+                        background: #1a1a1a;
+                        border-radius: 10px;
+                        border: 1.5px solid #4a4a4a;
+                        padding: 10px;
+                        margin: 0 16px 12px;
+                    ",
+
+                    div {
+                        style: "color: #94a3b8; font-size: 11px; margin-bottom: 8px; font-weight: 600;",
+                        "SLIPPAGE: {slippage().label()}"
+                    }
+
+                    div {
+                        style: "display: flex; flex-wrap: wrap; gap: 6px; align-items: center;",
+                        for bps in SLIPPAGE_PRESETS_BPS {
+                            button {
+                                key: "{bps}",
+                                style: slippage_option_style(slippage() == Slippage::Fixed(bps)),
+                                disabled: swapping(),
+                                onclick: {
+                                    let mut set_slippage = set_slippage.clone();
+                                    move |_| set_slippage(Slippage::Fixed(bps))
+                                },
+                                "{Slippage::Fixed(bps).label()}"
+                            }
+                        }
+                        button {
+                            style: slippage_option_style(slippage().is_dynamic()),
+                            disabled: swapping(),
+                            onclick: {
+                                let mut set_slippage = set_slippage.clone();
+                                move |_| set_slippage(Slippage::Dynamic)
+                            },
+                            "Dynamic"
+                        }
+                        input {
+                            style: "
+                                background: #2a2a2a;
+                                border: 1px solid #5a5a5a;
+                                border-radius: 6px;
+                                color: #ffffff;
+                                font-size: 12px;
+                                padding: 6px 8px;
+                                width: 70px;
+                            ",
+                            r#type: "text",
+                            inputmode: "decimal",
+                            placeholder: "Custom %",
+                            value: custom_slippage(),
+                            disabled: swapping(),
+                            oninput: move |e| custom_slippage.set(e.value()),
+                            onchange: {
+                                let mut set_slippage = set_slippage.clone();
+                                move |e: Event<FormData>| match parse_slippage_percent(&e.value()) {
+                                    Ok(bps) => set_slippage(Slippage::Fixed(bps)),
+                                    Err(message) => slippage_error.set(Some(message)),
+                                }
+                            },
+                        }
+                    }
+
+                    if let Some(message) = slippage_error() {
+                        div {
+                            style: "color: #f87171; font-size: 11px; margin-top: 6px;",
+                            "{message}"
+                        }
+                    }
+                    if slippage().is_dynamic() {
+                        div {
+                            style: "color: #94a3b8; font-size: 11px; margin-top: 6px;",
+                            "{dynamic_slippage_note}"
+                        }
+                    }
+                }
+
                 // Provider Selector - COMPACT
                 div {
                     class: "provider-selector",
@@ -2378,261 +2962,314 @@ pub fn SwapModal(
                         style: "color: #94a3b8; font-size: 11px; margin-bottom: 8px; font-weight: 600;",
                         "SELECT PROVIDER"
                     }
+                    if !quote_countdown.is_empty() {
+                        div {
+                            style: format!(
+                                "color: {}; font-size: 11px; margin-bottom: 8px;",
+                                if quote_expired { "#f87171" } else { "#94a3b8" }
+                            ),
+                            "{quote_countdown}"
+                        }
+                    }
                     
                     // Provider options
                     div {
                         style: "display: flex; flex-direction: column; gap: 6px;",
                         
                         // Jupiter
-                        div {
-                            class: "provider-option",
-                            style: format!("
-                                display: flex;
-                                justify-content: space-between;
-                                align-items: center;
-                                padding: 8px 10px;
-                                border-radius: 6px;
-                                cursor: pointer;
-                                transition: all 0.2s ease;
-                                background: {};
-                                border: 1px solid {};
-                                box-shadow: {};
-                            ", 
-                                if fetching_jupiter() {
-                                    "linear-gradient(90deg, rgba(251,191,36,0.15) 0%, rgba(251,191,36,0.3) 50%, rgba(251,191,36,0.15) 100%)"
-                                } else if selected_provider() == Some("Jupiter".to_string()) {
-                                    "#2a2a2a"
-                                } else {
-                                    "transparent"
-                                },
-                                if fetching_jupiter() {
-                                    "#fbbf24"
-                                } else if selected_provider() == Some("Jupiter".to_string()) {
-                                    "#10b981"
-                                } else {
-                                    "#3a3a3a"
+                        if swap_providers().is_enabled("Jupiter") {
+                            div {
+                                class: "provider-option",
+                                style: format!("
+                                    display: flex;
+                                    justify-content: space-between;
+                                    align-items: center;
+                                    padding: 8px 10px;
+                                    border-radius: 6px;
+                                    cursor: pointer;
+                                    transition: all 0.2s ease;
+                                    background: {};
+                                    border: 1px solid {};
+                                    box-shadow: {};
+                                ", 
+                                    if fetching_jupiter() {
+                                        "linear-gradient(90deg, rgba(251,191,36,0.15) 0%, rgba(251,191,36,0.3) 50%, rgba(251,191,36,0.15) 100%)"
+                                    } else if selected_provider() == Some("Jupiter".to_string()) {
+                                        "#2a2a2a"
+                                    } else {
+                                        "transparent"
+                                    },
+                                    if fetching_jupiter() {
+                                        "#fbbf24"
+                                    } else if selected_provider() == Some("Jupiter".to_string()) {
+                                        "#10b981"
+                                    } else {
+                                        "#3a3a3a"
+                                    },
+                                    if fetching_jupiter() {
+                                        "0 0 30px rgba(251,191,36,0.4), inset 0 0 20px rgba(251,191,36,0.1)"
+                                    } else {
+                                        "none"
+                                    }
+                                ),
+                                onclick: move |_| {
+                                    manual_provider_override.set(Some("Jupiter".to_string()));
+                                    selected_provider.set(Some("Jupiter".to_string()));
                                 },
-                                if fetching_jupiter() {
-                                    "0 0 30px rgba(251,191,36,0.4), inset 0 0 20px rgba(251,191,36,0.1)"
-                                } else {
-                                    "none"
-                                }
-                            ),
-                            onclick: move |_| {
-                                manual_provider_override.set(Some("Jupiter".to_string()));
-                                selected_provider.set(Some("Jupiter".to_string()));
-                            },
                             
-                            div {
-                                style: "display: flex; align-items: center; gap: 8px;",
                                 div {
-                                    style: format!("
-                                        width: 16px;
-                                        height: 16px;
-                                        border-radius: 50%;
-                                        border: 2px solid {};
-                                        background: {};
-                                    ",
-                                        if selected_provider() == Some("Jupiter".to_string()) { "#10b981" } else { "#4a4a4a" },
-                                        if selected_provider() == Some("Jupiter".to_string()) { "#10b981" } else { "transparent" }
-                                    )
-                                }
-                                span {
-                                    style: "color: #f8fafc; font-size: 13px; font-weight: 600;",
-                                    "Jupiter"
+                                    style: "display: flex; align-items: center; gap: 8px;",
+                                    div {
+                                        style: format!("
+                                            width: 16px;
+                                            height: 16px;
+                                            border-radius: 50%;
+                                            border: 2px solid {};
+                                            background: {};
+                                        ",
+                                            if selected_provider() == Some("Jupiter".to_string()) { "#10b981" } else { "#4a4a4a" },
+                                            if selected_provider() == Some("Jupiter".to_string()) { "#10b981" } else { "transparent" }
+                                        )
+                                    }
+                                    span {
+                                        style: "color: #f8fafc; font-size: 13px; font-weight: 600;",
+                                        "Jupiter"
+                                    }
                                 }
-                            }
                             
-                            if let Some(quote) = jupiter_quote() {
-                                span {
-                                    style: "color: #cbd5e1; font-size: 11px;",
-                                    {
-                                        let output = quote.out_amount.parse::<u64>().unwrap_or(0);
-                                        let converted = from_lamports(output, &buying_token(), &tokens);
-                                        if converted < 0.01 && converted > 0.0 {
-                                            format!("{:.6}", converted)
-                                        } else {
-                                            format!("{:.4}", converted)
+                                if let Some(quote) = jupiter_quote() {
+                                    span {
+                                        style: "color: #cbd5e1; font-size: 11px;",
+                                        {
+                                            let output = quote.out_amount.parse::<u64>().unwrap_or(0);
+                                            let converted = from_lamports(output, &buying_token(), &tokens);
+                                            if converted < 0.01 && converted > 0.0 {
+                                                format!("{:.6}", converted)
+                                            } else {
+                                                format!("{:.4}", converted)
+                                            }
                                         }
                                     }
-                                }
-                            } else if fetching_jupiter() {
-                                span {
-                                    style: "color: #fbbf24; font-size: 11px; display: inline-flex; gap: 2px;",
-                                    span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0s; opacity: 0.4;", "•" }
-                                    span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.2s; opacity: 0.4;", "•" }
-                                    span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.4s; opacity: 0.4;", "•" }
+                                } else if fetching_jupiter() {
+                                    span {
+                                        style: "color: #fbbf24; font-size: 11px; display: inline-flex; gap: 2px;",
+                                        span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0s; opacity: 0.4;", "•" }
+                                        span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.2s; opacity: 0.4;", "•" }
+                                        span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.4s; opacity: 0.4;", "•" }
+                                    }
                                 }
                             }
                         }
                         
                         // Dflow
-                        div {
-                            class: "provider-option",
-                            style: format!("
-                                display: flex;
-                                justify-content: space-between;
-                                align-items: center;
-                                padding: 8px 10px;
-                                border-radius: 6px;
-                                cursor: pointer;
-                                transition: all 0.2s ease;
-                                background: {};
-                                border: 1px solid {};
-                                box-shadow: {};
-                            ",
-                                if fetching_dflow() {
-                                    "linear-gradient(90deg, rgba(251,191,36,0.15) 0%, rgba(251,191,36,0.3) 50%, rgba(251,191,36,0.15) 100%)"
-                                } else if selected_provider() == Some("Dflow".to_string()) {
-                                    "#2a2a2a"
-                                } else {
-                                    "transparent"
-                                },
-                                if fetching_dflow() {
-                                    "#fbbf24"
-                                } else if selected_provider() == Some("Dflow".to_string()) {
-                                    "#10b981"
-                                } else {
-                                    "#3a3a3a"
+                        if swap_providers().is_enabled("Dflow") {
+                            div {
+                                class: "provider-option",
+                                style: format!("
+                                    display: flex;
+                                    justify-content: space-between;
+                                    align-items: center;
+                                    padding: 8px 10px;
+                                    border-radius: 6px;
+                                    cursor: pointer;
+                                    transition: all 0.2s ease;
+                                    background: {};
+                                    border: 1px solid {};
+                                    box-shadow: {};
+                                ",
+                                    if fetching_dflow() {
+                                        "linear-gradient(90deg, rgba(251,191,36,0.15) 0%, rgba(251,191,36,0.3) 50%, rgba(251,191,36,0.15) 100%)"
+                                    } else if selected_provider() == Some("Dflow".to_string()) {
+                                        "#2a2a2a"
+                                    } else {
+                                        "transparent"
+                                    },
+                                    if fetching_dflow() {
+                                        "#fbbf24"
+                                    } else if selected_provider() == Some("Dflow".to_string()) {
+                                        "#10b981"
+                                    } else {
+                                        "#3a3a3a"
+                                    },
+                                    if fetching_dflow() {
+                                        "0 0 30px rgba(251,191,36,0.4), inset 0 0 20px rgba(251,191,36,0.1)"
+                                    } else {
+                                        "none"
+                                    }
+                                ),
+                                onclick: move |_| {
+                                    manual_provider_override.set(Some("Dflow".to_string()));
+                                    selected_provider.set(Some("Dflow".to_string()));
                                 },
-                                if fetching_dflow() {
-                                    "0 0 30px rgba(251,191,36,0.4), inset 0 0 20px rgba(251,191,36,0.1)"
-                                } else {
-                                    "none"
-                                }
-                            ),
-                            onclick: move |_| {
-                                manual_provider_override.set(Some("Dflow".to_string()));
-                                selected_provider.set(Some("Dflow".to_string()));
-                            },
                             
-                            div {
-                                style: "display: flex; align-items: center; gap: 8px;",
                                 div {
-                                    style: format!("
-                                        width: 16px;
-                                        height: 16px;
-                                        border-radius: 50%;
-                                        border: 2px solid {};
-                                        background: {};
-                                    ",
-                                        if selected_provider() == Some("Dflow".to_string()) { "#10b981" } else { "#4a4a4a" },
-                                        if selected_provider() == Some("Dflow".to_string()) { "#10b981" } else { "transparent" }
-                                    )
-                                }
-                                span {
-                                    style: "color: #f8fafc; font-size: 13px; font-weight: 600;",
-                                    "Dflow"
+                                    style: "display: flex; align-items: center; gap: 8px;",
+                                    div {
+                                        style: format!("
+                                            width: 16px;
+                                            height: 16px;
+                                            border-radius: 50%;
+                                            border: 2px solid {};
+                                            background: {};
+                                        ",
+                                            if selected_provider() == Some("Dflow".to_string()) { "#10b981" } else { "#4a4a4a" },
+                                            if selected_provider() == Some("Dflow".to_string()) { "#10b981" } else { "transparent" }
+                                        )
+                                    }
+                                    span {
+                                        style: "color: #f8fafc; font-size: 13px; font-weight: 600;",
+                                        "Dflow"
+                                    }
                                 }
-                            }
                             
-                            if let Some(quote) = dflow_quote() {
-                                span {
-                                    style: "color: #cbd5e1; font-size: 11px;",
-                                    {
-                                        let output = quote.out_amount.parse::<u64>().unwrap_or(0);
-                                        let converted = from_lamports(output, &buying_token(), &tokens);
-                                        if converted < 0.01 && converted > 0.0 {
-                                            format!("{:.6}", converted)
-                                        } else {
-                                            format!("{:.4}", converted)
+                                if let Some(quote) = dflow_quote() {
+                                    span {
+                                        style: "color: #cbd5e1; font-size: 11px;",
+                                        {
+                                            let output = quote.out_amount.parse::<u64>().unwrap_or(0);
+                                            let converted = from_lamports(output, &buying_token(), &tokens);
+                                            if converted < 0.01 && converted > 0.0 {
+                                                format!("{:.6}", converted)
+                                            } else {
+                                                format!("{:.4}", converted)
+                                            }
                                         }
                                     }
-                                }
-                            } else if fetching_dflow() {
-                                span {
-                                    style: "color: #fbbf24; font-size: 11px; display: inline-flex; gap: 2px;",
-                                    span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0s; opacity: 0.4;", "•" }
-                                    span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.2s; opacity: 0.4;", "•" }
-                                    span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.4s; opacity: 0.4;", "•" }
+                                } else if fetching_dflow() {
+                                    span {
+                                        style: "color: #fbbf24; font-size: 11px; display: inline-flex; gap: 2px;",
+                                        span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0s; opacity: 0.4;", "•" }
+                                        span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.2s; opacity: 0.4;", "•" }
+                                        span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.4s; opacity: 0.4;", "•" }
+                                    }
                                 }
                             }
                         }
                         
                         // Titan
-                        div {
-                            class: "provider-option",
-                            style: format!("
-                                display: flex;
-                                justify-content: space-between;
-                                align-items: center;
-                                padding: 8px 10px;
-                                border-radius: 6px;
-                                cursor: pointer;
-                                transition: all 0.2s ease;
-                                background: {};
-                                border: 1px solid {};
-                                box-shadow: {};
-                            ",
-                                if fetching_titan() {
-                                    "linear-gradient(90deg, rgba(251,191,36,0.15) 0%, rgba(251,191,36,0.3) 50%, rgba(251,191,36,0.15) 100%)"
-                                } else if selected_provider() == Some("Titan".to_string()) {
-                                    "#2a2a2a"
-                                } else {
-                                    "transparent"
-                                },
-                                if fetching_titan() {
-                                    "#fbbf24"
-                                } else if selected_provider() == Some("Titan".to_string()) {
-                                    "#10b981"
-                                } else {
-                                    "#3a3a3a"
+                        if swap_providers().is_enabled("Titan") {
+                            div {
+                                class: "provider-option",
+                                style: format!("
+                                    display: flex;
+                                    justify-content: space-between;
+                                    align-items: center;
+                                    padding: 8px 10px;
+                                    border-radius: 6px;
+                                    cursor: pointer;
+                                    transition: all 0.2s ease;
+                                    background: {};
+                                    border: 1px solid {};
+                                    box-shadow: {};
+                                ",
+                                    if fetching_titan() {
+                                        "linear-gradient(90deg, rgba(251,191,36,0.15) 0%, rgba(251,191,36,0.3) 50%, rgba(251,191,36,0.15) 100%)"
+                                    } else if selected_provider() == Some("Titan".to_string()) {
+                                        "#2a2a2a"
+                                    } else {
+                                        "transparent"
+                                    },
+                                    if fetching_titan() {
+                                        "#fbbf24"
+                                    } else if selected_provider() == Some("Titan".to_string()) {
+                                        "#10b981"
+                                    } else {
+                                        "#3a3a3a"
+                                    },
+                                    if fetching_titan() {
+                                        "0 0 30px rgba(251,191,36,0.4), inset 0 0 20px rgba(251,191,36,0.1)"
+                                    } else {
+                                        "none"
+                                    }
+                                ),
+                                onclick: move |_| {
+                                    manual_provider_override.set(Some("Titan".to_string()));
+                                    selected_provider.set(Some("Titan".to_string()));
                                 },
-                                if fetching_titan() {
-                                    "0 0 30px rgba(251,191,36,0.4), inset 0 0 20px rgba(251,191,36,0.1)"
-                                } else {
-                                    "none"
-                                }
-                            ),
-                            onclick: move |_| {
-                                manual_provider_override.set(Some("Titan".to_string()));
-                                selected_provider.set(Some("Titan".to_string()));
-                            },
                             
-                            div {
-                                style: "display: flex; align-items: center; gap: 8px;",
                                 div {
-                                    style: format!("
-                                        width: 16px;
-                                        height: 16px;
-                                        border-radius: 50%;
-                                        border: 2px solid {};
-                                        background: {};
-                                    ",
-                                        if selected_provider() == Some("Titan".to_string()) { "#10b981" } else { "#4a4a4a" },
-                                        if selected_provider() == Some("Titan".to_string()) { "#10b981" } else { "transparent" }
-                                    )
-                                }
-                                span {
-                                    style: "color: #f8fafc; font-size: 13px; font-weight: 600;",
-                                    "Titan"
+                                    style: "display: flex; align-items: center; gap: 8px;",
+                                    div {
+                                        style: format!("
+                                            width: 16px;
+                                            height: 16px;
+                                            border-radius: 50%;
+                                            border: 2px solid {};
+                                            background: {};
+                                        ",
+                                            if selected_provider() == Some("Titan".to_string()) { "#10b981" } else { "#4a4a4a" },
+                                            if selected_provider() == Some("Titan".to_string()) { "#10b981" } else { "transparent" }
+                                        )
+                                    }
+                                    span {
+                                        style: "color: #f8fafc; font-size: 13px; font-weight: 600;",
+                                        "Titan"
+                                    }
                                 }
-                            }
                             
-                            if let Some((_, route)) = titan_quote() {
-                                span {
-                                    style: "color: #cbd5e1; font-size: 11px;",
-                                    {
-                                        let converted = from_lamports(route.out_amount, &buying_token(), &tokens);
-                                        if converted < 0.01 && converted > 0.0 {
-                                            format!("{:.6}", converted)
-                                        } else {
-                                            format!("{:.4}", converted)
+                                if let Some((_, route)) = titan_quote() {
+                                    span {
+                                        style: "color: #cbd5e1; font-size: 11px;",
+                                        {
+                                            let converted = from_lamports(route.out_amount, &buying_token(), &tokens);
+                                            if converted < 0.01 && converted > 0.0 {
+                                                format!("{:.6}", converted)
+                                            } else {
+                                                format!("{:.4}", converted)
+                                            }
                                         }
                                     }
-                                }
-                            } else if fetching_titan() {
-                                span {
-                                    style: "color: #fbbf24; font-size: 11px; display: inline-flex; gap: 2px;",
-                                    span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0s; opacity: 0.4;", "•" }
-                                    span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.2s; opacity: 0.4;", "•" }
-                                    span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.4s; opacity: 0.4;", "•" }
+                                } else if fetching_titan() {
+                                    span {
+                                        style: "color: #fbbf24; font-size: 11px; display: inline-flex; gap: 2px;",
+                                        span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0s; opacity: 0.4;", "•" }
+                                        span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.2s; opacity: 0.4;", "•" }
+                                        span { style: "animation: pulse 1.4s ease-in-out infinite; animation-delay: 0.4s; opacity: 0.4;", "•" }
+                                    }
                                 }
                             }
                         }
                     }
                 }
                 
+                // Route breakdown - COMPACT
+                if !quote_routes.is_empty() {
+                    div {
+                        class: "route-breakdown",
+                        style: "
+                            background: #1a1a1a;
+                            border-radius: 10px;
+                            border: 1.5px solid #4a4a4a;
+                            padding: 10px;
+                            margin: 0 16px 12px;
+                        ",
+
+                        div {
+                            style: "color: #94a3b8; font-size: 11px; margin-bottom: 8px; font-weight: 600;",
+                            "ROUTES"
+                        }
+
+                        for (provider, summary, details) in quote_routes {
+                            div {
+                                key: "{provider}",
+                                style: "margin-bottom: 6px;",
+                                div {
+                                    style: "color: #f8fafc; font-size: 12px;",
+                                    span { style: "font-weight: 600;", "{provider}: " }
+                                    span { style: "color: #cbd5e1;", "{summary}" }
+                                }
+                                for (index, line) in details.into_iter().enumerate() {
+                                    div {
+                                        key: "{index}",
+                                        style: "color: #94a3b8; font-size: 11px; padding-left: 10px;",
+                                        "{line}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
                 // Action button - COMPACT
                 div {
                     class: "modal-buttons",
@@ -2658,19 +3295,30 @@ pub fn SwapModal(
                             min-height: 48px;
                             box-shadow: 0 4px 12px rgba(255, 255, 255, 0.2);
                         ",
-                        disabled: swapping() || selling_amount().is_empty() || fetching_jupiter(),
+                        disabled: swapping() || selling_amount().is_empty() || fetching_jupiter() || quote_expired,
                         onclick: handle_swap,
                         
                         if fetching_jupiter() || fetching_dflow() || fetching_titan() {
                             "Getting Quotes..."
                         } else if swapping() {
                             "Swapping..."
+                        } else if quote_expired {
+                            "Quote Expired"
                         } else {
                             "Swap"
                         }
                     }
                 }
             }
+
+            if let Some(side) = picking_token_for() {
+                TokenSearch {
+                    tokens: wallet_tokens.clone(),
+                    custom_rpc: custom_rpc.clone(),
+                    onselect: move |token| pick_token(side, token),
+                    onclose: move |_| picking_token_for.set(None),
+                }
+            }
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,121 @@
+use dioxus::prelude::*;
+use crate::config::app_tip::{app_tip_lamports, parse_app_tip_sol, set_app_tip_lamports};
+use crate::storage::{load_swap_provider_settings, save_swap_provider_settings};
+use crate::swap::{SwapProviderSettings, SWAP_PROVIDERS};
+
+/// Enable or disable the swap aggregators and pin one, and set the app tip.
+/// Disabled providers aren't asked for quotes; a pinned one is used whenever
+/// it quotes.
+#[component]
+pub fn SwapProvidersModal(onclose: EventHandler<()>) -> Element {
+    let mut settings = use_signal(load_swap_provider_settings);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut app_tip = use_signal(|| (app_tip_lamports() as f64 / 1_000_000_000.0).to_string());
+
+    let save = move |_| {
+        let current: SwapProviderSettings = settings();
+        let saved = current
+            .validate()
+            .and_then(|()| parse_app_tip_sol(&app_tip()))
+            .and_then(set_app_tip_lamports)
+            .and_then(|()| save_swap_provider_settings(&current));
+        if let Err(e) = saved {
+            error_message.set(Some(e));
+            return;
+        }
+        onclose.call(());
+    };
+
+    let preferred = settings().preferred.unwrap_or_default();
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Swap Providers" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Swaps are quoted by every enabled provider and go through the best quote, unless a provider is pinned and has a quote."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                for provider in SWAP_PROVIDERS {
+                    div {
+                        key: "{provider}",
+                        class: "wallet-field",
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: settings().is_enabled(provider),
+                                onchange: move |e| settings.write().set_enabled(provider, e.checked()),
+                            }
+                            " {provider}"
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Pinned provider:" }
+                    select {
+                        onchange: move |e| {
+                            let value = e.value();
+                            settings.write().preferred = (!value.is_empty()).then_some(value);
+                        },
+                        option { value: "", selected: preferred.is_empty(), "None (best quote)" }
+                        for provider in SWAP_PROVIDERS {
+                            if settings().is_enabled(provider) {
+                                option { key: "{provider}", value: "{provider}", selected: preferred == provider, "{provider}" }
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "App tip per swap (SOL):" }
+                    input {
+                        r#type: "text",
+                        inputmode: "decimal",
+                        value: "{app_tip}",
+                        oninput: move |e| app_tip.set(e.value()),
+                    }
+                    div {
+                        class: "help-text",
+                        "Paid to the app on every swap and on sends with JitoTx, listed in each transaction's fee breakdown. Set 0 to tip nothing."
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        onclick: save,
+                        "Save"
+                    }
+                }
+            }
+        }
+    }
+}
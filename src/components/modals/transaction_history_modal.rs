@@ -1,5 +1,8 @@
 use dioxus::prelude::*;
-use crate::rpc::{get_transaction_history, get_transaction_details, TransactionInfo};
+use crate::history::{self, HistoryIndex, HISTORY_PAGE_SIZE};
+use crate::storage::{
+    load_address_labels, load_history_index, load_transaction_labels, load_transaction_notes, save_export_file,
+};
 use std::collections::HashMap;
 
 #[component]
@@ -8,30 +11,43 @@ pub fn TransactionHistoryModal(
     custom_rpc: Option<String>,
     onclose: EventHandler<()>,
 ) -> Element {
-    let mut transactions = use_signal(|| Vec::<TransactionInfo>::new());
+    let address_for_index = address.clone();
+    let mut index = use_signal(move || load_history_index(&address_for_index));
+    let mut shown = use_signal(|| HISTORY_PAGE_SIZE);
     let mut loading = use_signal(|| true);
+    let mut loading_older = use_signal(|| false);
     let mut error = use_signal(|| None as Option<String>);
     let mut selected_tx = use_signal(|| None as Option<String>);
     let mut tx_details = use_signal(|| None as Option<HashMap<String, serde_json::Value>>);
     let mut loading_details = use_signal(|| false);
     let mut detail_error = use_signal(|| None as Option<String>);
+    let mut category_input = use_signal(String::new);
+    let mut labels = use_signal(load_transaction_labels);
+    let mut label_input = use_signal(String::new);
+    let mut notes = use_signal(load_transaction_notes);
+    let mut note_input = use_signal(String::new);
+    let mut address_labels = use_signal(load_address_labels);
+    let address_for_label = address.clone();
+    let mut own_label_input = use_signal(move || load_address_labels().get(&address_for_label).cloned().unwrap_or_default());
+    let mut other_address_input = use_signal(String::new);
+    let mut other_label_input = use_signal(String::new);
+    let mut export_status = use_signal(|| None as Option<String>);
 
     // Clone props for use in effects
     let address_for_effect = address.clone();
     let custom_rpc_for_effect = custom_rpc.clone();
 
-    // Fetch transaction history when the modal opens
+    // Show the stored history right away and fetch only what's new
     use_effect(move || {
         let addr = address_for_effect.clone();
         let rpc_url = custom_rpc_for_effect.clone();
         loading.set(true);
         error.set(None);
-        transactions.set(Vec::new());
 
         spawn(async move {
-            match get_transaction_history(&addr, 20, rpc_url.as_deref()).await {
-                Ok(txs) => {
-                    transactions.set(txs);
+            match history::sync_newer(&addr, rpc_url.as_deref()).await {
+                Ok(synced) => {
+                    index.set(synced);
                 }
                 Err(e) => {
                     error.set(Some(format!("Failed to load transactions: {}", e)));
@@ -41,19 +57,77 @@ pub fn TransactionHistoryModal(
         });
     });
 
+    // Show the next page, fetching older signatures once the stored ones run out
+    let address_for_older = address.clone();
+    let custom_rpc_for_older = custom_rpc.clone();
+    let mut show_more = move || {
+        let current: HistoryIndex = index();
+        if loading_older() || !current.has_more(shown()) {
+            return;
+        }
+        shown.set(shown() + HISTORY_PAGE_SIZE);
+        if shown() <= current.entries.len() || current.reached_start {
+            return;
+        }
+        let addr = address_for_older.clone();
+        let rpc_url = custom_rpc_for_older.clone();
+        loading_older.set(true);
+        spawn(async move {
+            match history::load_older(&addr, rpc_url.as_deref()).await {
+                Ok(older) => index.set(older),
+                Err(e) => error.set(Some(format!("Failed to load older transactions: {}", e))),
+            }
+            loading_older.set(false);
+        });
+    };
+
+    let transactions = index().page(shown());
+    let has_more = index().has_more(shown());
+    let involved = tx_details()
+        .map(|details| {
+            history::labelled_addresses(&details, &address_labels())
+                .into_iter()
+                .filter(|(involved, _)| *involved != address)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let mut save_address_label = move |target: String, label: String| {
+        match history::set_address_label(target.trim(), Some(&label)) {
+            Ok(()) => address_labels.set(load_address_labels()),
+            Err(e) => detail_error.set(Some(e)),
+        }
+    };
+
+    let address_for_export = address.clone();
+    let export_csv = move |_| {
+        let current = index();
+        let file_name = format!(
+            "history_{}_{}.csv",
+            &address_for_export[..8.min(address_for_export.len())],
+            chrono::Utc::now().format("%Y%m%d")
+        );
+        match save_export_file(&file_name, &history::history_csv(&current)) {
+            Ok(path) => export_status.set(Some(format!("Saved to {}", path))),
+            Err(e) => error.set(Some(e)),
+        }
+    };
+
     // Clone needed for second effect
+    let address_for_detail = address.clone();
     let custom_rpc_for_detail = custom_rpc.clone();
 
     // Fetch transaction details when a transaction is selected
     use_effect(move || {
         if let Some(signature) = selected_tx() {
             let sig = signature.clone();
+            let addr = address_for_detail.clone();
             let rpc_url = custom_rpc_for_detail.clone();
             loading_details.set(true);
             detail_error.set(None);
 
             spawn(async move {
-                match get_transaction_details(&sig, rpc_url.as_deref()).await {
+                match history::transaction_details(&addr, &sig, rpc_url.as_deref()).await {
                     Ok(details) => {
                         tx_details.set(Some(details));
                     }
@@ -89,6 +163,23 @@ pub fn TransactionHistoryModal(
                     class: "transaction-address",
                     "Address: ",
                     span { class: "address-text", "{address}" }
+                    input {
+                        value: "{own_label_input}",
+                        oninput: move |e| own_label_input.set(e.value()),
+                        placeholder: "Name this address"
+                    }
+                    button {
+                        class: "button-standard secondary",
+                        onclick: {
+                            let address = address.clone();
+                            move |_| save_address_label(address.clone(), own_label_input())
+                        },
+                        "Save Name"
+                    }
+                }
+
+                if let Some(status) = export_status() {
+                    div { class: "info-message", "{status}" }
                 }
                 
                 // Main content container
@@ -99,18 +190,33 @@ pub fn TransactionHistoryModal(
                     div {
                         class: "transaction-list-container",
                         
-                        if loading() {
+                        if let Some(err) = error() {
+                            div {
+                                class: "error-message",
+                                if transactions.is_empty() { "{err}" } else { "{err} Showing saved history." }
+                            }
+                        }
+                        if loading() && transactions.is_empty() {
                             div { class: "loading-indicator", "Loading transactions..." }
-                        } else if let Some(err) = error() {
-                            div { class: "error-message", "{err}" }
-                        } else if transactions().is_empty() {
-                            div { class: "no-transactions", "No transactions found for this address." }
+                        } else if transactions.is_empty() {
+                            if error().is_none() {
+                                div { class: "no-transactions", "No transactions found for this address." }
+                            }
                         } else {
-                            // Transaction list
+                            // Transaction list, paged in as it's scrolled
                             div {
                                 class: "transaction-list",
-                                // Use transactions() to get a clone of the list
-                                for tx in transactions() {
+                                onscroll: {
+                                    let mut show_more = show_more.clone();
+                                    move |e: Event<ScrollData>| {
+                                        let data = e.data();
+                                        let remaining = data.scroll_height() as f64 - data.scroll_top() - data.client_height() as f64;
+                                        if remaining < 200.0 {
+                                            show_more();
+                                        }
+                                    }
+                                },
+                                for tx in transactions.clone() {
                                     div {
                                         key: "{tx.signature}",
                                         class: if Some(&tx.signature) == selected_tx.as_ref().as_deref() {
@@ -119,6 +225,9 @@ pub fn TransactionHistoryModal(
                                             "transaction-item"
                                         },
                                         onclick: move |_| {
+                                            category_input.set(index().category(&tx.signature).unwrap_or_default().to_string());
+                                            label_input.set(labels().get(&tx.signature).cloned().unwrap_or_default());
+                                            note_input.set(notes().get(&tx.signature).cloned().unwrap_or_default());
                                             selected_tx.set(Some(tx.signature.clone()));
                                             tx_details.set(None);
                                         },
@@ -133,9 +242,13 @@ pub fn TransactionHistoryModal(
                                             class: "transaction-item-content",
                                             div {
                                                 class: "transaction-item-header",
-                                                div {
-                                                    class: "transaction-signature",
-                                                    "{tx.signature.chars().take(8).collect::<String>()}...{tx.signature.chars().rev().take(4).collect::<String>().chars().rev().collect::<String>()}"
+                                                if let Some(label) = labels().get(&tx.signature) {
+                                                    div { class: "transaction-signature", "{label}" }
+                                                } else {
+                                                    div {
+                                                        class: "transaction-signature",
+                                                        "{tx.signature.chars().take(8).collect::<String>()}...{tx.signature.chars().rev().take(4).collect::<String>().chars().rev().collect::<String>()}"
+                                                    }
                                                 }
                                                 div {
                                                     class: "transaction-time",
@@ -158,9 +271,22 @@ pub fn TransactionHistoryModal(
                                             if let Some(ref memo) = tx.memo {
                                                 div { class: "transaction-memo", "Memo: {memo}" }
                                             }
+                                            
+                                            if let Some(note) = notes().get(&tx.signature) {
+                                                div { class: "transaction-memo", "📝 {note}" }
+                                            }
                                         }
                                     }
                                 }
+                                if loading_older() {
+                                    div { class: "loading-indicator", "Loading older transactions..." }
+                                } else if has_more {
+                                    button {
+                                        class: "button-standard secondary",
+                                        onclick: move |_| show_more(),
+                                        "Load More"
+                                    }
+                                }
                             }
                         }
                     }
@@ -175,12 +301,125 @@ pub fn TransactionHistoryModal(
                                 h3 { "Transaction Details" }
                                 a {
                                     class: "external-link",
-                                    href: "https://explorer.solana.com/tx/{signature}",
+                                    href: crate::config::network::explorer_tx_url(signature),
                                     target: "_blank",
                                     rel: "noopener noreferrer",
                                     "View in Explorer"
                                 }
                             }
+
+                            div {
+                                class: "details-section",
+                                h4 { "Label" }
+                                input {
+                                    value: "{label_input}",
+                                    oninput: move |e| label_input.set(e.value()),
+                                    placeholder: "e.g. Rent — March"
+                                }
+                                button {
+                                    class: "button-standard secondary",
+                                    onclick: {
+                                        let signature = signature.clone();
+                                        move |_| {
+                                            let label = label_input();
+                                            match history::set_transaction_label(&signature, Some(&label)) {
+                                                Ok(()) => labels.set(load_transaction_labels()),
+                                                Err(e) => detail_error.set(Some(e)),
+                                            }
+                                        }
+                                    },
+                                    "Save Label"
+                                }
+                            }
+
+                            div {
+                                class: "details-section",
+                                h4 { "Note" }
+                                textarea {
+                                    value: "{note_input}",
+                                    oninput: move |e| note_input.set(e.value()),
+                                    placeholder: "Why this was sent, who it was for, receipts...",
+                                    rows: "3"
+                                }
+                                button {
+                                    class: "button-standard secondary",
+                                    onclick: {
+                                        let signature = signature.clone();
+                                        move |_| {
+                                            let note = note_input();
+                                            match history::set_transaction_note(&signature, Some(&note)) {
+                                                Ok(()) => notes.set(load_transaction_notes()),
+                                                Err(e) => detail_error.set(Some(e)),
+                                            }
+                                        }
+                                    },
+                                    "Save Note"
+                                }
+                            }
+
+                            div {
+                                class: "details-section",
+                                h4 { "Addresses" }
+                                for (involved_address, involved_label) in involved.iter() {
+                                    div { class: "detail-item",
+                                        key: "{involved_address}",
+                                        div { class: "detail-label", "{involved_label}:" }
+                                        div { class: "detail-value signature-value", "{involved_address}" }
+                                    }
+                                }
+                                input {
+                                    value: "{other_address_input}",
+                                    oninput: move |e| other_address_input.set(e.value()),
+                                    placeholder: "Address from this transaction"
+                                }
+                                input {
+                                    value: "{other_label_input}",
+                                    oninput: move |e| other_label_input.set(e.value()),
+                                    placeholder: "Name, e.g. Landlord"
+                                }
+                                button {
+                                    class: "button-standard secondary",
+                                    disabled: other_address_input().trim().is_empty(),
+                                    onclick: move |_| {
+                                        save_address_label(other_address_input(), other_label_input());
+                                        other_address_input.set(String::new());
+                                        other_label_input.set(String::new());
+                                    },
+                                    "Save Address Name"
+                                }
+                            }
+
+                            div {
+                                class: "details-section",
+                                h4 { "Category" }
+                                input {
+                                    value: "{category_input}",
+                                    list: "history-categories",
+                                    oninput: move |e| category_input.set(e.value()),
+                                    placeholder: "e.g. Food, Bills, Savings"
+                                }
+                                datalist {
+                                    id: "history-categories",
+                                    for category in index().known_categories() {
+                                        option { key: "{category}", value: "{category}" }
+                                    }
+                                }
+                                button {
+                                    class: "button-standard secondary",
+                                    onclick: {
+                                        let address = address.clone();
+                                        let signature = signature.clone();
+                                        move |_| {
+                                            let category = category_input();
+                                            match history::set_category(&address, &signature, Some(&category)) {
+                                                Ok(updated) => index.set(updated),
+                                                Err(e) => detail_error.set(Some(e)),
+                                            }
+                                        }
+                                    },
+                                    "Save Category"
+                                }
+                            }
                             
                             if loading_details() {
                                 div { class: "loading-indicator", "Loading details..." }
@@ -303,6 +542,12 @@ pub fn TransactionHistoryModal(
                 
                 // Footer with action buttons
                 div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        disabled: index().entries.is_empty(),
+                        onclick: export_csv,
+                        "Export CSV"
+                    }
                     button {
                         class: "button-standard primary",
                         onclick: move |_| onclose.call(()),
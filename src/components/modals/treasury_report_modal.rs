@@ -0,0 +1,175 @@
+use dioxus::prelude::*;
+use crate::squads::report::{self, ReportPeriod, ReportTarget, TreasuryReport};
+use crate::storage::save_export_file;
+
+/// Holdings, flows, proposal activity and signer participation for a
+/// multisig vault, with CSV and PDF export
+#[component]
+pub fn TreasuryReportModal(
+    target: ReportTarget,
+    custom_rpc: Option<String>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut period = use_signal(|| ReportPeriod::Month);
+    let mut report = use_signal(|| None as Option<TreasuryReport>);
+    let mut working = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut status = use_signal(|| None as Option<String>);
+
+    let generate = move |_| {
+        let target = target.clone();
+        let rpc = custom_rpc.clone();
+        let chosen = period();
+        working.set(true);
+        error_message.set(None);
+        status.set(None);
+        report.set(None);
+        spawn(async move {
+            match report::generate_report(&target, chosen, rpc.as_deref()).await {
+                Ok(generated) => report.set(Some(generated)),
+                Err(e) => error_message.set(Some(e)),
+            }
+            working.set(false);
+        });
+    };
+
+    let mut export = move |extension: &str| {
+        let Some(current) = report() else { return };
+        let contents = if extension == "pdf" { current.to_pdf() } else { current.to_csv() };
+        let file_name = format!(
+            "treasury_{}_{}.{}",
+            &current.target.multisig.to_string()[..8],
+            chrono::Utc::now().format("%Y%m%d"),
+            extension
+        );
+        match save_export_file(&file_name, &contents) {
+            Ok(path) => status.set(Some(format!("Saved to {}", path))),
+            Err(e) => error_message.set(Some(e)),
+        }
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Treasury Report" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "help-text",
+                    "Covers the default vault up to the end of yesterday (UTC). Holdings are valued at that day's closing prices."
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+                if let Some(message) = status() {
+                    div { class: "info-message", "{message}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Period:" }
+                    div { class: "modal-buttons",
+                        for option in ReportPeriod::ALL {
+                            button {
+                                key: "{option.days()}",
+                                class: if period() == option { "button-standard primary" } else { "button-standard secondary" },
+                                disabled: working(),
+                                onclick: move |_| period.set(option),
+                                "{option.label()}"
+                            }
+                        }
+                    }
+                }
+
+                if let Some(current) = report() {
+                    div {
+                        class: "wallet-field",
+                        label { "{current.target.name}" }
+                        div { class: "address-display", "{current.target.vault}" }
+                        div { class: "help-text", "{current.period_label()}" }
+                    }
+
+                    div {
+                        class: "wallet-field",
+                        label { "Holdings (${current.total_usd():.2})" }
+                        for (index, holding) in current.holdings.iter().enumerate() {
+                            div { key: "{index}", class: "info-message", "{TreasuryReport::holding_line(holding)}" }
+                        }
+                    }
+
+                    div {
+                        class: "wallet-field",
+                        label { "Inflows & Outflows ({current.transactions} transactions)" }
+                        if current.flows.is_empty() {
+                            div { class: "help-text", "No movements in this period" }
+                        }
+                        for (index, flow) in current.flows.iter().enumerate() {
+                            div { key: "{index}", class: "info-message", "{TreasuryReport::flow_line(flow)}" }
+                        }
+                        if !current.flows_complete {
+                            div { class: "help-text", "Busy vault: only the most recent transactions are included." }
+                        }
+                    }
+
+                    div {
+                        class: "wallet-field",
+                        label { "Proposal Activity" }
+                        if current.proposals.is_empty() {
+                            div { class: "help-text", "No proposals changed state in this period" }
+                        }
+                        for (state, count) in current.proposals.iter() {
+                            div { key: "{state.label()}", class: "info-message", "{state.label()}: {count}" }
+                        }
+                    }
+
+                    div {
+                        class: "wallet-field",
+                        label { "Signer Participation" }
+                        for signer in current.participation.iter() {
+                            div { key: "{signer.member}", class: "info-message", "{TreasuryReport::participation_line(signer)}" }
+                        }
+                    }
+                }
+
+                if working() {
+                    div { class: "help-text", "Replaying vault history and proposals... this can take a while." }
+                }
+
+                div { class: "modal-buttons",
+                    if report().is_some() {
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| export("csv"),
+                            "Export CSV"
+                        }
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| export("pdf"),
+                            "Export PDF"
+                        }
+                    }
+                    button {
+                        class: "button-standard primary",
+                        disabled: working(),
+                        onclick: generate,
+                        if working() { "Generating..." } else { "Generate" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,11 @@
 use dioxus::prelude::*;
-use crate::wallet::{Wallet, WalletInfo};
-use crate::storage::import_wallet_from_key;
+use crate::wallet::{generate_mnemonic, Wallet, WalletInfo};
+use crate::wallet_import::{detect_format, scan_accounts, DerivationScheme, DerivedAccount, ImportFormat};
+use crate::storage::{
+    import_wallet_from_key, import_wallet_from_keystore, import_wallet_from_mnemonic, import_wallet_from_shares,
+    load_rpc_from_storage,
+};
+use crate::components::KeySharesPanel;
 
 #[component]
 pub fn WalletModal(mode: String, onclose: EventHandler<()>, onsave: EventHandler<WalletInfo>) -> Element {
@@ -10,6 +15,36 @@ pub fn WalletModal(mode: String, onclose: EventHandler<()>, onsave: EventHandler
     let mut generated_wallet = use_signal(|| None as Option<Wallet>);
     let mut error_message = use_signal(|| None as Option<String>);
     let mut show_format_help = use_signal(|| false);
+    // BIP39 passphrase, the optional "25th word" of a recovery phrase
+    let mut passphrase = use_signal(|| "".to_string());
+    let mut generated_phrase = use_signal(|| None as Option<String>);
+    // Whether the generated wallet was derived with a passphrase, as the
+    // field can still be edited after generating
+    let mut generated_with_passphrase = use_signal(|| false);
+    // Accounts an imported phrase derives to, and the one to import
+    let mut scanned_accounts = use_signal(|| Vec::<DerivedAccount>::new());
+    let mut selected_path = use_signal(|| (DerivationScheme::Bip44Change, 0u32));
+    let mut scanning = use_signal(|| false);
+    let mut keystore_password = use_signal(|| "".to_string());
+    let detected_format = detect_format(&import_key());
+    let importing_phrase = detected_format == Some(ImportFormat::RecoveryPhrase);
+    
+    let passphrase_field = move || rsx! {
+        div {
+            class: "wallet-field",
+            label { "Passphrase (optional):" }
+            input {
+                r#type: "password",
+                value: "{passphrase}",
+                oninput: move |e| passphrase.set(e.value()),
+                placeholder: "Leave empty for none"
+            }
+            div {
+                class: "help-text",
+                "Each passphrase opens a different wallet from the same phrase. You need both to recover it, and a forgotten passphrase can't be reset."
+            }
+        }
+    };
     
     rsx! {
         div {
@@ -49,6 +84,16 @@ pub fn WalletModal(mode: String, onclose: EventHandler<()>, onsave: EventHandler
                                 label { "Public Address:" }
                                 div { class: "address-display", "{wallet.get_public_key()}" }
                             }
+                            if let Some(phrase) = generated_phrase() {
+                                div { class: "wallet-field",
+                                    label {
+                                        if !generated_with_passphrase() { "Recovery Phrase:" } else { "Recovery Phrase (with passphrase):" }
+                                    }
+                                    if show_generated_key() {
+                                        div { class: "private-key-display", "{phrase}" }
+                                    }
+                                }
+                            }
                             div { class: "wallet-field",
                                 label { "Private Key:" }
                                 div { class: "private-key-warning",
@@ -73,8 +118,9 @@ pub fn WalletModal(mode: String, onclose: EventHandler<()>, onsave: EventHandler
                                     }
                                     div { 
                                         class: "copy-hint",
-                                        "Make sure to copy this key before saving!"
+                                        "Make sure to write down the recovery phrase or copy this key before saving!"
                                     }
+                                    KeySharesPanel { private_key: wallet.get_private_key() }
                                 } else {
                                     button {
                                         class: "show-key-button",
@@ -94,6 +140,7 @@ pub fn WalletModal(mode: String, onclose: EventHandler<()>, onsave: EventHandler
                                 placeholder: "My Wallet"
                             }
                         }
+                        {passphrase_field()}
                         div {
                             class: "info-message",
                             "Click 'Generate Wallet' to create a new wallet"
@@ -122,8 +169,12 @@ pub fn WalletModal(mode: String, onclose: EventHandler<()>, onsave: EventHandler
                         }
                         textarea {
                             value: "{import_key}",
-                            oninput: move |e| import_key.set(e.value()),
-                            placeholder: "Enter your private key in bs58 or json",
+                            oninput: move |e| {
+                                import_key.set(e.value());
+                                scanned_accounts.set(Vec::new());
+                                selected_path.set((DerivationScheme::Bip44Change, 0));
+                            },
+                            placeholder: "Enter your private key in bs58 or json, a recovery phrase, or recovery shares one per line",
                             rows: "4"
                         }
                         
@@ -140,6 +191,84 @@ pub fn WalletModal(mode: String, onclose: EventHandler<()>, onsave: EventHandler
                                     strong { "2. JSON Array (Phantom/Sollet):" }
                                     code { "[252,183,12,...,159,189]" }
                                 }
+                                div { class: "format-example",
+                                    strong { "3. Recovery phrase (12-24 words):" }
+                                    code { "abandon ability able ..." }
+                                }
+                                div { class: "format-example",
+                                    strong { "4. SLIP-39 shares (33 words each, one per line):" }
+                                    code { "academic acid acrobat ..." }
+                                }
+                            }
+                        }
+                    }
+                    if let Some(format) = detected_format {
+                        div { class: "help-text", "Detected: {format.label()}" }
+                    }
+                    if detected_format == Some(ImportFormat::EncryptedKeystore) {
+                        div {
+                            class: "wallet-field",
+                            label { "Keystore password:" }
+                            input {
+                                r#type: "password",
+                                value: "{keystore_password}",
+                                oninput: move |e| keystore_password.set(e.value()),
+                            }
+                        }
+                    }
+                    if importing_phrase {
+                        {passphrase_field()}
+                        div {
+                            class: "wallet-field",
+                            label { "Account:" }
+                            div {
+                                class: "help-text",
+                                "Wallets derive accounts from a phrase on different paths. Find Accounts checks the common ones for funds."
+                            }
+                            button {
+                                class: "show-key-button",
+                                disabled: scanning(),
+                                onclick: move |_| {
+                                    scanning.set(true);
+                                    error_message.set(None);
+                                    let phrase = import_key();
+                                    let passphrase_text = passphrase();
+                                    spawn(async move {
+                                        let rpc_url = load_rpc_from_storage();
+                                        match scan_accounts(&phrase, &passphrase_text, rpc_url.as_deref()).await {
+                                            Ok(accounts) => {
+                                                // Start from the first funded account, if any
+                                                if let Some(funded) = accounts.iter().find(|a| a.lamports.unwrap_or(0) > 0) {
+                                                    selected_path.set((funded.scheme, funded.account));
+                                                }
+                                                scanned_accounts.set(accounts);
+                                            }
+                                            Err(e) => error_message.set(Some(e)),
+                                        }
+                                        scanning.set(false);
+                                    });
+                                },
+                                if scanning() { "Checking..." } else { "Find Accounts" }
+                            }
+                            for derived in scanned_accounts() {
+                                {
+                                    let selected = selected_path() == (derived.scheme, derived.account);
+                                    let sol = derived.lamports.unwrap_or(0) as f64 / 1_000_000_000.0;
+                                    let path = derived.scheme.path(derived.account);
+                                    let address = format!("{}...{}", &derived.address[..4], &derived.address[derived.address.len() - 4..]);
+                                    rsx! {
+                                        div {
+                                            key: "{derived.address}",
+                                            class: if selected { "transaction-item selected" } else { "transaction-item" },
+                                            onclick: move |_| selected_path.set((derived.scheme, derived.account)),
+                                            div {
+                                                class: "transaction-item-content",
+                                                div { "{address} · {sol:.4} SOL" }
+                                                div { class: "help-text", "{path} ({derived.scheme.label()})" }
+                                            }
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -158,14 +287,20 @@ pub fn WalletModal(mode: String, onclose: EventHandler<()>, onsave: EventHandler
                             button {
                                 class: "modal-button primary",
                                 onclick: move |_| {
-                                    let new_wallet = Wallet::new(
-                                        if wallet_name().is_empty() { 
-                                            "New Wallet".to_string() 
-                                        } else { 
-                                            wallet_name() 
+                                    let phrase = generate_mnemonic();
+                                    let name = if wallet_name().is_empty() { 
+                                        "New Wallet".to_string() 
+                                    } else { 
+                                        wallet_name() 
+                                    };
+                                    match Wallet::from_mnemonic(&phrase, &passphrase(), name) {
+                                        Ok(new_wallet) => {
+                                            generated_wallet.set(Some(new_wallet));
+                                            generated_phrase.set(Some(phrase));
+                                            generated_with_passphrase.set(!passphrase().is_empty());
                                         }
-                                    );
-                                    generated_wallet.set(Some(new_wallet));
+                                        Err(e) => error_message.set(Some(e)),
+                                    }
                                 },
                                 "Generate Wallet"
                             }
@@ -180,6 +315,7 @@ pub fn WalletModal(mode: String, onclose: EventHandler<()>, onsave: EventHandler
                                         } else {
                                             wallet_name()
                                         };
+                                        wallet_info.passphrase_protected = generated_with_passphrase();
                                         onsave.call(wallet_info);
                                     }
                                 },
@@ -196,7 +332,18 @@ pub fn WalletModal(mode: String, onclose: EventHandler<()>, onsave: EventHandler
                             class: "modal-button primary",
                             onclick: move |_| {
                                 if !import_key().is_empty() {
-                                    match import_wallet_from_key(&import_key(), wallet_name()) {
+                                    let imported = match detect_format(&import_key()) {
+                                        Some(ImportFormat::Shares) => import_wallet_from_shares(&import_key(), wallet_name()),
+                                        Some(ImportFormat::EncryptedKeystore) => {
+                                            import_wallet_from_keystore(&import_key(), &keystore_password(), wallet_name())
+                                        }
+                                        Some(ImportFormat::RecoveryPhrase) => {
+                                            let (scheme, account) = selected_path();
+                                            import_wallet_from_mnemonic(&import_key(), &passphrase(), scheme, account, wallet_name())
+                                        }
+                                        _ => import_wallet_from_key(&import_key(), wallet_name()),
+                                    };
+                                    match imported {
                                         Ok(wallet_info) => {
                                             error_message.set(None);
                                             onsave.call(wallet_info);
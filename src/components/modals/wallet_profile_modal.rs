@@ -0,0 +1,93 @@
+use dioxus::prelude::*;
+use crate::policy::RiskTier;
+use crate::storage::{load_wallet_profile, save_wallet_profile};
+use crate::wallet::WalletInfo;
+
+/// Edit the notes and risk tier of a stored wallet
+#[component]
+pub fn WalletProfileModal(wallet: WalletInfo, onclose: EventHandler<()>) -> Element {
+    let initial = load_wallet_profile(&wallet.address);
+    let mut tier = use_signal(|| initial.tier);
+    let mut notes = use_signal(|| initial.notes.clone());
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| onclose.call(()),
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Notes & Risk Tier" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Wallet:" }
+                    div { class: "address-display", "{wallet.name} ({wallet.address})" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Risk tier:" }
+                    div { class: "modal-buttons",
+                        for option in RiskTier::ALL {
+                            button {
+                                key: "{option.label()}",
+                                class: if tier() == option { "button-standard primary" } else { "button-standard secondary" },
+                                onclick: move |_| tier.set(option),
+                                "{option.icon()} {option.label()}"
+                            }
+                        }
+                    }
+                    div { class: "help-text", "{tier().description()}" }
+                }
+
+                div {
+                    class: "wallet-field",
+                    label { "Notes:" }
+                    textarea {
+                        value: "{notes}",
+                        rows: "4",
+                        oninput: move |e| notes.set(e.value()),
+                        placeholder: "What is this wallet for?"
+                    }
+                }
+
+                div { class: "modal-buttons",
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| onclose.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        class: "button-standard primary",
+                        onclick: move |_| {
+                            let mut profile = load_wallet_profile(&wallet.address);
+                            profile.tier = tier();
+                            profile.notes = notes().trim().to_string();
+                            match save_wallet_profile(&profile) {
+                                Ok(()) => onclose.call(()),
+                                Err(e) => error_message.set(Some(e)),
+                            }
+                        },
+                        "Save"
+                    }
+                }
+            }
+        }
+    }
+}
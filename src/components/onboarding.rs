@@ -1,6 +1,9 @@
 // src/components/onboarding.rs
 use dioxus::prelude::*;
 use crate::storage;
+use crate::backup;
+use crate::paper_backup;
+use crate::components::modals::device_transfer_modal::CAMERA_SCANNER_JS;
 use crate::components::pin_input::PinInput;
 
 const ONBOARDING_KEY: &str = "https://cdn.jsdelivr.net/gh/hogyzen12/unruggable-app@main/assets/onboarding_key.webp";
@@ -48,6 +51,72 @@ pub fn OnboardingFlow(on_complete: EventHandler<()>) -> Element {
     let mut confirming_pin = use_signal(|| String::new());
     let mut show_success = use_signal(|| false);
     
+    // Restoring wallets and settings from a backup made on another machine
+    let mut restoring = use_signal(|| false);
+    let mut backup_path = use_signal(|| String::new());
+    let mut backup_password = use_signal(|| String::new());
+    let mut restore_error = use_signal(|| None::<String>);
+    let mut restore_summary = use_signal(|| None::<String>);
+    // Restoring one wallet from the QR code of a paper backup instead
+    let mut paper_restore = use_signal(|| false);
+    let mut paper_code = use_signal(|| String::new());
+    let mut stop_camera = use_signal(|| None as Option<document::Eval>);
+    
+    let mut stop_scanning = move || {
+        if let Some(eval) = stop_camera.write().take() {
+            let _ = eval.send(true);
+        }
+    };
+    
+    let restore = move |_| {
+        let text = match storage::read_export_file(backup_path().trim()) {
+            Ok(text) => text,
+            Err(e) => {
+                restore_error.set(Some(e));
+                return;
+            }
+        };
+        match backup::open(&text, &backup_password()).and_then(|contents| backup::restore(&contents)) {
+            Ok(summary) => {
+                let mut message = format!(
+                    "Restored {} wallet(s) and {} setting(s).",
+                    summary.wallets_added, summary.settings_restored
+                );
+                if summary.wallets_skipped > 0 {
+                    message.push_str(&format!(" {} wallet(s) were already here.", summary.wallets_skipped));
+                }
+                restore_summary.set(Some(message));
+                restore_error.set(None);
+                backup_password.set(String::new());
+                restoring.set(false);
+                // Go on to the PIN, which seals the restored keys
+                current_step.set(total_steps - 1);
+            }
+            Err(e) => restore_error.set(Some(e)),
+        }
+    };
+    
+    let restore_paper = move |_| {
+        match paper_backup::open_qr(&paper_code(), &backup_password(), "Paper Backup".to_string()) {
+            Ok(wallet) => {
+                let wallet_info = wallet.to_wallet_info();
+                let message = if storage::load_wallets_from_storage().iter().any(|w| w.address == wallet_info.address) {
+                    format!("Wallet {} was already here.", wallet_info.address)
+                } else {
+                    storage::save_wallet_to_storage(&wallet_info);
+                    format!("Restored wallet {}.", wallet_info.address)
+                };
+                restore_summary.set(Some(message));
+                restore_error.set(None);
+                backup_password.set(String::new());
+                paper_code.set(String::new());
+                restoring.set(false);
+                current_step.set(total_steps - 1);
+            }
+            Err(e) => restore_error.set(Some(e)),
+        }
+    };
+    
     let handle_pin_complete = move |pin: String| {
         match pin_setup_mode() {
             PinSetupMode::EnterPin => {
@@ -120,192 +189,335 @@ pub fn OnboardingFlow(on_complete: EventHandler<()>) -> Element {
             div {
                 class: "onboarding-container",
                 
-                match current_step() {
-                    0 => rsx! {
+                if restoring() && paper_restore() {
+                    div {
+                        class: "onboarding-step",
+                        h1 { 
+                            class: "onboarding-title",
+                            "Restore from Paper Backup"
+                        }
+                        
+                        p { 
+                            class: "onboarding-description",
+                            "Scan the QR code on the sheet and enter the password it was printed with. To use the recovery words instead, choose Import Wallet once set up."
+                        }
+                        
+                        if let Some(error) = restore_error() {
+                            div { class: "error-message", "{error}" }
+                        }
+                        
                         div {
-                            class: "onboarding-step",
-                            h1 { 
-                                class: "onboarding-title",
-                                "Welcome to"
+                            class: "onboarding-buttons",
+                            button {
+                                class: "onboarding-button secondary",
+                                disabled: stop_camera.read().is_some(),
+                                onclick: move |_| {
+                                    restore_error.set(None);
+                                    let mut eval = document::eval(CAMERA_SCANNER_JS);
+                                    stop_camera.set(Some(eval));
+                                    spawn(async move {
+                                        while let Ok(code) = eval.recv::<String>().await {
+                                            if let Some(reason) = code.strip_prefix("!error:") {
+                                                restore_error.set(Some(reason.to_string()));
+                                                break;
+                                            }
+                                            if paper_backup::is_paper_qr(&code) {
+                                                paper_code.set(code);
+                                                let _ = eval.send(true);
+                                                break;
+                                            }
+                                        }
+                                        stop_camera.set(None);
+                                    });
+                                },
+                                if stop_camera.read().is_some() { "Scanning..." } else { "Scan QR Code" }
                             }
-                            h1 { 
-                                class: "onboarding-title brand",
-                                "Unruggable."
+                        }
+                        div {
+                            class: "wallet-field",
+                            label { "Or paste the scanned code:" }
+                            input {
+                                value: "{paper_code}",
+                                oninput: move |e| paper_code.set(e.value()),
+                                placeholder: "UNRUG-PAPER:..."
                             }
-                            
-                            img {
-                                class: "onboarding-key-image",
-                                src: ONBOARDING_KEY,
-                                alt: "Unruggable Key"
+                        }
+                        div {
+                            class: "wallet-field",
+                            label { "Password:" }
+                            input {
+                                r#type: "password",
+                                value: "{backup_password}",
+                                oninput: move |e| backup_password.set(e.value()),
                             }
-                            
-                            p { 
-                                class: "onboarding-subtitle",
-                                span { class: "highlight", "Your Unruggable account" }
-                                br {}
-                                span { class: "highlight", "is secured." }
+                        }
+                        
+                        div {
+                            class: "onboarding-buttons",
+                            button {
+                                class: "onboarding-button secondary",
+                                onclick: move |_| {
+                                    stop_scanning();
+                                    restoring.set(false);
+                                    paper_restore.set(false);
+                                    restore_error.set(None);
+                                },
+                                "Back"
                             }
-                            
-                            div {
-                                class: "onboarding-footer",
-                                p { class: "powered-by", "Powered by" }
-                                p { class: "seeker-logo", "Seeker" }
-                                p { class: "seeker-subtitle", "SOLANA ≡ MOBILE" }
+                            button {
+                                class: "onboarding-button primary",
+                                disabled: paper_code().trim().is_empty() || backup_password().is_empty(),
+                                onclick: restore_paper,
+                                "Restore"
+                            }
+                        }
+                    }
+                } else if restoring() {
+                    div {
+                        class: "onboarding-step",
+                        h1 { 
+                            class: "onboarding-title",
+                            "Restore from Backup"
+                        }
+                        
+                        p { 
+                            class: "onboarding-description",
+                            "Enter where you copied the backup file and the password it was made with."
+                        }
+                        
+                        if let Some(error) = restore_error() {
+                            div { class: "error-message", "{error}" }
+                        }
+                        
+                        div {
+                            class: "wallet-field",
+                            label { "Backup file:" }
+                            input {
+                                value: "{backup_path}",
+                                oninput: move |e| backup_path.set(e.value()),
+                                placeholder: "/path/to/unruggable_backup.json"
                             }
                         }
-                    },
-                    1 => rsx! {
                         div {
-                            class: "onboarding-step",
-                            h1 { 
-                                class: "onboarding-title",
-                                "Secure & Private"
+                            class: "wallet-field",
+                            label { "Password:" }
+                            input {
+                                r#type: "password",
+                                value: "{backup_password}",
+                                oninput: move |e| backup_password.set(e.value()),
                             }
+                        }
+                        
+                        div {
+                            class: "onboarding-buttons",
+                            button {
+                                class: "onboarding-button secondary",
+                                onclick: move |_| {
+                                    restoring.set(false);
+                                    restore_error.set(None);
+                                },
+                                "Back"
+                            }
+                            button {
+                                class: "onboarding-button primary",
+                                disabled: backup_path().trim().is_empty() || backup_password().is_empty(),
+                                onclick: restore,
+                                "Restore"
+                            }
+                        }
+                    }
+                } else {
+                    match current_step() {
+                        0 => rsx! {
+                            div {
+                                class: "onboarding-step",
+                                h1 { 
+                                    class: "onboarding-title",
+                                    "Welcome to"
+                                }
+                                h1 { 
+                                    class: "onboarding-title brand",
+                                    "Unruggable."
+                                }
                             
-                            div { 
-                                class: "onboarding-icon-large", 
-                                "🛡️" 
+                                img {
+                                    class: "onboarding-key-image",
+                                    src: ONBOARDING_KEY,
+                                    alt: "Unruggable Key"
+                                }
+                            
+                                p { 
+                                    class: "onboarding-subtitle",
+                                    span { class: "highlight", "Your Unruggable account" }
+                                    br {}
+                                    span { class: "highlight", "is secured." }
+                                }
+                            
+                                div {
+                                    class: "onboarding-footer",
+                                    p { class: "powered-by", "Powered by" }
+                                    p { class: "seeker-logo", "Seeker" }
+                                    p { class: "seeker-subtitle", "SOLANA ≡ MOBILE" }
+                                }
                             }
+                        },
+                        1 => rsx! {
+                            div {
+                                class: "onboarding-step",
+                                h1 { 
+                                    class: "onboarding-title",
+                                    "Secure & Private"
+                                }
+                            
+                                div { 
+                                    class: "onboarding-icon-large", 
+                                    "🛡️" 
+                                }
                             
-                            p { 
-                                class: "onboarding-description",
-                                "Your keys are stored locally and encrypted."
-                                br {}
-                                "They never leave your device."
+                                p { 
+                                    class: "onboarding-description",
+                                    "Your keys are stored locally and encrypted."
+                                    br {}
+                                    "They never leave your device."
+                                }
                             }
-                        }
-                    },
-                    2 => rsx! {
-                        div {
-                            class: "onboarding-step",
+                        },
+                        2 => rsx! {
+                            div {
+                                class: "onboarding-step",
                             
-                            match pin_setup_mode() {
-                                PinSetupMode::AskUser => rsx! {
-                                    h1 { 
-                                        class: "onboarding-title",
-                                        "Set Up PIN"
-                                    }
-                                    
-                                    div { 
-                                        class: "onboarding-icon-large", 
-                                        "🔐" 
-                                    }
+                                match pin_setup_mode() {
+                                    PinSetupMode::AskUser => rsx! {
+                                        h1 { 
+                                            class: "onboarding-title",
+                                            "Set Up PIN"
+                                        }
                                     
-                                    p { 
-                                        class: "onboarding-description",
-                                        "Protect your wallet with a 6-digit PIN."
-                                        br {}
-                                        "You'll need it to unlock the app."
-                                    }
+                                        div { 
+                                            class: "onboarding-icon-large", 
+                                            "🔐" 
+                                        }
                                     
-                                    div {
-                                        class: "onboarding-buttons pin-setup-buttons",
-                                        button {
-                                            class: "onboarding-button secondary",
-                                            onclick: skip_pin,
-                                            "Skip for Now"
+                                        if let Some(summary) = restore_summary() {
+                                            div { class: "info-message", "{summary}" }
                                         }
-                                        button {
-                                            class: "onboarding-button primary",
-                                            onclick: setup_pin,
-                                            "Set Up PIN"
+                                    
+                                        p { 
+                                            class: "onboarding-description",
+                                            "Protect your wallet with a 6-digit PIN."
+                                            br {}
+                                            "You'll need it to unlock the app."
                                         }
-                                    }
-                                },
-                                PinSetupMode::EnterPin => rsx! {
-                                    if show_success() {
+                                    
                                         div {
-                                            class: "pin-success-screen",
-                                            div {
-                                                class: "success-icon-large",
-                                                "✓"
+                                            class: "onboarding-buttons pin-setup-buttons",
+                                            button {
+                                                class: "onboarding-button secondary",
+                                                onclick: skip_pin,
+                                                "Skip for Now"
                                             }
-                                            h2 {
-                                                class: "success-title",
-                                                "PIN Set Successfully!"
-                                            }
-                                            p {
-                                                class: "success-subtitle",
-                                                "Your wallet is now protected"
+                                            button {
+                                                class: "onboarding-button primary",
+                                                onclick: setup_pin,
+                                                "Set Up PIN"
                                             }
                                         }
-                                    } else {
-                                        div {
-                                            key: "{entered_pin.read().len()}_entry",
-                                            PinInput {
-                                                title: "Create Your PIN".to_string(),
-                                                subtitle: Some("Choose a secure 6-digit code".to_string()),
-                                                error_message: pin_error().clone(),
-                                                on_complete: handle_pin_complete,
-                                                on_cancel: Some(handle_pin_cancel.clone()),
-                                                show_strength: Some(true),
-                                                step_indicator: Some("Step 1 of 2".to_string()),
-                                                clear_on_complete: Some(true),
+                                    },
+                                    PinSetupMode::EnterPin => rsx! {
+                                        if show_success() {
+                                            div {
+                                                class: "pin-success-screen",
+                                                div {
+                                                    class: "success-icon-large",
+                                                    "✓"
+                                                }
+                                                h2 {
+                                                    class: "success-title",
+                                                    "PIN Set Successfully!"
+                                                }
+                                                p {
+                                                    class: "success-subtitle",
+                                                    "Your wallet is now protected"
+                                                }
+                                            }
+                                        } else {
+                                            div {
+                                                key: "{entered_pin.read().len()}_entry",
+                                                PinInput {
+                                                    title: "Create Your PIN".to_string(),
+                                                    subtitle: Some("Choose a secure 6-digit code".to_string()),
+                                                    error_message: pin_error().clone(),
+                                                    on_complete: handle_pin_complete,
+                                                    on_cancel: Some(handle_pin_cancel.clone()),
+                                                    show_strength: Some(true),
+                                                    step_indicator: Some("Step 1 of 2".to_string()),
+                                                    clear_on_complete: Some(true),
+                                                }
                                             }
                                         }
-                                    }
-                                },
-                                PinSetupMode::Transitioning => rsx! {
-                                    div {
-                                        class: "pin-transition-screen",
-                                        div {
-                                            class: "transition-icon",
-                                            "✓"
-                                        }
-                                        h2 {
-                                            class: "transition-title",
-                                            "Great!"
-                                        }
-                                        p {
-                                            class: "transition-subtitle",
-                                            "Now confirm your PIN"
-                                        }
-                                        div {
-                                            class: "transition-loader"
-                                        }
-                                    }
-                                },
-                                PinSetupMode::ConfirmPin => rsx! {
-                                    if show_success() {
+                                    },
+                                    PinSetupMode::Transitioning => rsx! {
                                         div {
-                                            class: "pin-success-screen",
+                                            class: "pin-transition-screen",
                                             div {
-                                                class: "success-icon-large animated-checkmark",
+                                                class: "transition-icon",
                                                 "✓"
                                             }
                                             h2 {
-                                                class: "success-title",
-                                                "PIN Set Successfully!"
+                                                class: "transition-title",
+                                                "Great!"
                                             }
                                             p {
-                                                class: "success-subtitle",
-                                                "Your wallet is now protected"
+                                                class: "transition-subtitle",
+                                                "Now confirm your PIN"
+                                            }
+                                            div {
+                                                class: "transition-loader"
                                             }
                                         }
-                                    } else {
-                                        div {
-                                            key: "{confirming_pin.read().len()}_confirm",
-                                            PinInput {
-                                                title: "Confirm Your PIN".to_string(),
-                                                subtitle: Some("Enter the same PIN again".to_string()),
-                                                error_message: pin_error().clone(),
-                                                on_complete: handle_pin_complete,
-                                                on_cancel: Some(handle_pin_cancel.clone()),
-                                                show_strength: Some(false),
-                                                step_indicator: Some("Step 2 of 2".to_string()),
-                                                clear_on_complete: Some(true),
+                                    },
+                                    PinSetupMode::ConfirmPin => rsx! {
+                                        if show_success() {
+                                            div {
+                                                class: "pin-success-screen",
+                                                div {
+                                                    class: "success-icon-large animated-checkmark",
+                                                    "✓"
+                                                }
+                                                h2 {
+                                                    class: "success-title",
+                                                    "PIN Set Successfully!"
+                                                }
+                                                p {
+                                                    class: "success-subtitle",
+                                                    "Your wallet is now protected"
+                                                }
+                                            }
+                                        } else {
+                                            div {
+                                                key: "{confirming_pin.read().len()}_confirm",
+                                                PinInput {
+                                                    title: "Confirm Your PIN".to_string(),
+                                                    subtitle: Some("Enter the same PIN again".to_string()),
+                                                    error_message: pin_error().clone(),
+                                                    on_complete: handle_pin_complete,
+                                                    on_cancel: Some(handle_pin_cancel.clone()),
+                                                    show_strength: Some(false),
+                                                    step_indicator: Some("Step 2 of 2".to_string()),
+                                                    clear_on_complete: Some(true),
+                                                }
                                             }
                                         }
-                                    }
-                                },
+                                    },
+                                }
                             }
-                        }
-                    },
-                    _ => rsx! { div {} }
+                        },
+                        _ => rsx! { div {} }
+                    }
                 }
 
                 // Only show progress and buttons if not in PIN setup mode
-                if current_step() != 2 || pin_setup_mode() == PinSetupMode::AskUser {
+                if !restoring() && (current_step() != 2 || pin_setup_mode() == PinSetupMode::AskUser) {
                     div {
                         class: "onboarding-progress",
                         for i in 0..total_steps {
@@ -316,10 +528,26 @@ pub fn OnboardingFlow(on_complete: EventHandler<()>) -> Element {
                     }
                 }
 
-                if current_step() != 2 || pin_setup_mode() == PinSetupMode::AskUser {
+                if !restoring() && (current_step() != 2 || pin_setup_mode() == PinSetupMode::AskUser) {
                     div {
                         class: "onboarding-buttons",
                         
+                        if current_step() == 0 {
+                            button {
+                                class: "onboarding-button secondary",
+                                onclick: move |_| restoring.set(true),
+                                "Restore from Backup"
+                            }
+                            button {
+                                class: "onboarding-button secondary",
+                                onclick: move |_| {
+                                    paper_restore.set(true);
+                                    restoring.set(true);
+                                },
+                                "Restore from Paper Backup"
+                            }
+                        }
+                        
                         if current_step() < total_steps - 1 {
                             button {
                                 class: "onboarding-button secondary",
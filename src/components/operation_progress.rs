@@ -0,0 +1,111 @@
+// src/components/operation_progress.rs
+use dioxus::prelude::*;
+use crate::operations::{Operation, OperationState, StepStatus};
+
+#[derive(Props, Clone, PartialEq)]
+pub struct OperationProgressProps {
+    pub operation: Operation,
+    pub on_pause: EventHandler<()>,
+    pub on_resume: EventHandler<()>,
+    pub on_dismiss: EventHandler<()>,
+}
+
+/// Progress panel for a multi-step operation with pause/resume controls
+#[component]
+pub fn OperationProgress(props: OperationProgressProps) -> Element {
+    let operation = &props.operation;
+    let percent = (operation.progress() * 100.0).round() as u32;
+    let total = operation.steps.len();
+    let completed = operation.completed_steps();
+
+    let state_label = match operation.state {
+        OperationState::Running => "Running",
+        OperationState::Paused => "Paused",
+        OperationState::Failed => "Stopped after an error",
+        OperationState::Completed => "Completed",
+    };
+
+    rsx! {
+        div {
+            class: "operation-progress",
+
+            div {
+                class: "operation-progress-header",
+                h3 { "{operation.title}" }
+                span { class: "operation-progress-state", "{state_label}" }
+            }
+
+            div {
+                class: "operation-progress-bar",
+                div {
+                    class: "operation-progress-fill",
+                    style: "width: {percent}%; height: 6px; background: #4ade80; border-radius: 3px;",
+                }
+            }
+
+            p {
+                class: "operation-progress-count",
+                "{completed} of {total} steps complete"
+            }
+
+            div {
+                class: "operation-progress-steps",
+                for (index, step) in operation.steps.iter().enumerate() {
+                    div {
+                        key: "{index}",
+                        class: "operation-progress-step",
+                        span {
+                            match step.status {
+                                StepStatus::Pending => "⏳",
+                                StepStatus::Running => "🔄",
+                                StepStatus::Completed => "✅",
+                                StepStatus::Failed => "❌",
+                            }
+                        }
+                        span { " {step.label}" }
+                        if step.attempts > 1 && step.status != StepStatus::Completed {
+                            span { class: "operation-progress-attempts", " (attempt {step.attempts})" }
+                        }
+                        if let Some(error) = &step.error {
+                            if step.status != StepStatus::Completed {
+                                div { class: "error-message", "{error}" }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "modal-buttons",
+                match operation.state {
+                    OperationState::Running => rsx! {
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| props.on_pause.call(()),
+                            "Pause"
+                        }
+                    },
+                    OperationState::Paused | OperationState::Failed => rsx! {
+                        button {
+                            class: "button-standard secondary",
+                            onclick: move |_| props.on_dismiss.call(()),
+                            "Close"
+                        }
+                        button {
+                            class: "button-standard primary",
+                            onclick: move |_| props.on_resume.call(()),
+                            "Resume"
+                        }
+                    },
+                    OperationState::Completed => rsx! {
+                        button {
+                            class: "button-standard primary",
+                            onclick: move |_| props.on_dismiss.call(()),
+                            "Done"
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
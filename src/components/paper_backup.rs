@@ -0,0 +1,130 @@
+// src/components/paper_backup.rs
+use dioxus::prelude::*;
+use crate::backup::MIN_PASSWORD_LENGTH;
+use crate::paper_backup::{create, PaperBackup};
+use crate::wallet::Wallet;
+use qrcode::{QrCode, render::svg};
+
+fn qr_code_svg(data: &str) -> Option<String> {
+    QrCode::new(data).ok().map(|qr| {
+        qr.render()
+            .min_dimensions(220, 220)
+            .dark_color(svg::Color("#000000"))
+            .light_color(svg::Color("#ffffff"))
+            .build()
+    })
+}
+
+/// Lay out a printable paper backup of a wallet: its recovery words and a
+/// password-sealed QR code of the keypair
+#[component]
+pub fn PaperBackupPanel(private_key: String, name: String) -> Element {
+    let mut password = use_signal(String::new);
+    let mut confirm_password = use_signal(String::new);
+    let mut paper = use_signal(|| None as Option<PaperBackup>);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    let generate = move |_| {
+        if password() != confirm_password() {
+            error_message.set(Some("Passwords don't match".to_string()));
+            return;
+        }
+        let result = bs58::decode(&private_key)
+            .into_vec()
+            .map_err(|e| format!("Decode error: {}", e))
+            .and_then(|bytes| Wallet::from_private_key(&bytes, name.clone()))
+            .and_then(|wallet| create(&wallet, &password()));
+        match result {
+            Ok(layout) => {
+                error_message.set(None);
+                password.set(String::new());
+                confirm_password.set(String::new());
+                paper.set(Some(layout));
+            }
+            Err(e) => error_message.set(Some(e)),
+        }
+    };
+
+    let password_hint = format!("At least {} characters", MIN_PASSWORD_LENGTH);
+
+    rsx! {
+        div {
+            class: "wallet-field",
+            label { "Paper Backup:" }
+            if let Some(error) = error_message() {
+                div { class: "error-message", "{error}" }
+            }
+            match paper() {
+                None => rsx! {
+                    div {
+                        class: "help-text",
+                        "Prints the recovery words and a QR code of the key sealed under a password. Restore the QR code from onboarding with Restore from Paper Backup."
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "QR password:" }
+                        input {
+                            r#type: "password",
+                            value: "{password}",
+                            placeholder: "{password_hint}",
+                            oninput: move |e| password.set(e.value()),
+                        }
+                    }
+                    div {
+                        class: "wallet-field",
+                        label { "Confirm password:" }
+                        input {
+                            r#type: "password",
+                            value: "{confirm_password}",
+                            oninput: move |e| confirm_password.set(e.value()),
+                        }
+                    }
+                    button {
+                        class: "show-key-button",
+                        disabled: password().is_empty(),
+                        onclick: generate,
+                        "Create Paper Backup"
+                    }
+                },
+                Some(layout) => {
+                    let qr = qr_code_svg(&layout.qr_payload);
+                    rsx! {
+                        div {
+                            class: "paper-backup",
+                            h3 { "Unruggable Paper Backup" }
+                            div { class: "paper-backup-meta", "{layout.name} · created {layout.created_on}" }
+                            div { class: "paper-backup-meta", "{layout.address}" }
+                            div {
+                                class: "paper-backup-words",
+                                for (i, word) in layout.words.iter().enumerate() {
+                                    div { key: "{i}", class: "paper-backup-word", "{i + 1}. {word}" }
+                                }
+                            }
+                            if let Some(svg) = qr {
+                                div { class: "paper-backup-qr", dangerous_inner_html: "{svg}" }
+                            }
+                            div {
+                                class: "paper-backup-meta",
+                                "Words: Import Wallet, entered as one line. QR code: Restore from Paper Backup, with the password you chose. Anyone holding this sheet can take the wallet with the words alone."
+                            }
+                        }
+                        div { class: "modal-buttons",
+                            button {
+                                class: "button-standard secondary",
+                                onclick: move |_| paper.set(None),
+                                "Discard"
+                            }
+                            button {
+                                class: "button-standard primary",
+                                onclick: move |_| {
+                                    let _ = document::eval("window.print();");
+                                },
+                                "Print"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
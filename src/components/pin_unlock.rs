@@ -1,12 +1,53 @@
 // src/components/pin_unlock.rs
 use dioxus::prelude::*;
 use crate::storage;
+use crate::biometric;
+use crate::pin::format_lockout;
 use crate::components::pin_input::PinInput;
 
 #[component]
 pub fn PinUnlock(on_unlock: EventHandler<()>) -> Element {
     let mut error_message = use_signal(|| None::<String>);
-    let mut is_locked = use_signal(|| storage::is_pin_locked());
+    let mut lockout = use_signal(storage::pin_lockout_remaining);
+    let mut biometric_name = use_signal(|| None::<String>);
+    
+    let mut unlock_with_biometrics = move || {
+        spawn(async move {
+            match biometric::unlock().await {
+                Ok(()) => {
+                    log::info!("Biometrics verified - unlocking app");
+                    error_message.set(None);
+                    on_unlock.call(());
+                }
+                Err(e) => {
+                    log::warn!("Biometric unlock failed: {}", e);
+                    error_message.set(Some(e));
+                }
+            }
+        });
+    };
+    
+    // Count a lockout down so the PIN pad comes back when it ends
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            if lockout() > 0 {
+                lockout.set(storage::pin_lockout_remaining());
+            }
+        }
+    });
+    
+    // Offer biometrics straight away when turned on; the PIN stays available
+    use_effect(move || {
+        if biometric::is_enabled() && !storage::is_pin_locked() {
+            spawn(async move {
+                if let Some(name) = biometric::authenticator_name().await {
+                    biometric_name.set(Some(name));
+                    unlock_with_biometrics();
+                }
+            });
+        }
+    });
     
     let handle_pin_complete = move |pin: String| {
         // Verify PIN
@@ -22,10 +63,8 @@ pub fn PinUnlock(on_unlock: EventHandler<()>) -> Element {
                 log::warn!("PIN verification failed: {}", e);
                 error_message.set(Some(e.clone()));
                 
-                // Check if locked
-                if storage::is_pin_locked() {
-                    is_locked.set(true);
-                }
+                // Check if locked out
+                lockout.set(storage::pin_lockout_remaining());
             }
         }
     };
@@ -34,7 +73,7 @@ pub fn PinUnlock(on_unlock: EventHandler<()>) -> Element {
         div {
             class: "pin-unlock-overlay",
             
-            if is_locked() {
+            if lockout() > 0 {
                 div {
                     class: "pin-locked-container",
                     
@@ -45,14 +84,14 @@ pub fn PinUnlock(on_unlock: EventHandler<()>) -> Element {
                     
                     h2 {
                         class: "pin-locked-title",
-                        "Wallet Locked"
+                        "PIN Locked"
                     }
                     
                     p {
                         class: "pin-locked-message",
-                        "Too many failed attempts."
+                        "Too many wrong PINs."
                         br {}
-                        "Please reinstall the app to reset."
+                        "Try again in {format_lockout(lockout())}."
                     }
                 }
             } else {
@@ -66,6 +105,14 @@ pub fn PinUnlock(on_unlock: EventHandler<()>) -> Element {
                     step_indicator: None,
                     clear_on_complete: Some(true),
                 }
+                
+                if let Some(name) = biometric_name() {
+                    button {
+                        class: "biometric-unlock-button",
+                        onclick: move |_| unlock_with_biometrics(),
+                        "Unlock with {name}"
+                    }
+                }
             }
         }
     }
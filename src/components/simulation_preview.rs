@@ -0,0 +1,115 @@
+// src/components/simulation_preview.rs
+use dioxus::prelude::*;
+use crate::signing::firewall::Severity;
+use crate::transaction::SimulationPreview;
+
+/// Simulation state shown before a transaction is signed
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewState {
+    Idle,
+    Loading,
+    Ready(SimulationPreview),
+    Unavailable(String),
+}
+
+impl PreviewState {
+    /// Whether signing needs an explicit extra confirmation (likely wallet drain)
+    pub fn needs_confirmation(&self) -> bool {
+        matches!(self, PreviewState::Ready(preview) if !preview.drain_risks.is_empty())
+    }
+}
+
+/// Drain warnings plus a "sign anyway" checkbox, shown when the preview
+/// needs an extra confirmation
+#[component]
+pub fn DrainWarning(state: PreviewState, confirmed: bool, onchange: EventHandler<bool>) -> Element {
+    let PreviewState::Ready(preview) = state else { return rsx! {} };
+    if preview.drain_risks.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "error-message simulation-preview",
+            div { "⚠️ This transaction looks like a wallet drain:" }
+            for (index, risk) in preview.drain_risks.iter().enumerate() {
+                div { key: "drain-{index}", "• {risk.describe()}" }
+            }
+        }
+        div {
+            class: "wallet-field",
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: confirmed,
+                    onchange: move |e| onchange.call(e.checked()),
+                }
+                " I understand the risks and want to sign anyway"
+            }
+        }
+    }
+}
+
+/// "You will send X, receive Y, pay Z fee" panel for send and swap modals
+#[component]
+pub fn SimulationPreviewPanel(state: PreviewState) -> Element {
+    match state {
+        PreviewState::Idle => rsx! {},
+        PreviewState::Loading => rsx! {
+            div {
+                class: "info-message simulation-preview",
+                "Simulating transaction..."
+            }
+        },
+        PreviewState::Unavailable(reason) => rsx! {
+            div {
+                class: "info-message simulation-preview",
+                "Preview unavailable: {reason}"
+            }
+        },
+        PreviewState::Ready(preview) => {
+            let firewall = rsx! {
+                for (index, finding) in preview.firewall.iter().enumerate() {
+                    div {
+                        key: "fw-{index}",
+                        class: if finding.severity == Severity::Block { "error-message simulation-preview" } else { "warning-message simulation-preview" },
+                        "{finding.describe()}"
+                    }
+                }
+            };
+            let instructions = rsx! {
+                if !preview.instructions.is_empty() {
+                    div {
+                        class: "help-text simulation-preview",
+                        "Instructions:"
+                        for (index, line) in preview.instructions.iter().enumerate() {
+                            div { key: "ix-{index}", "{line}" }
+                        }
+                    }
+                }
+            };
+            if !preview.success {
+                let error = preview.error.clone().unwrap_or_else(|| "unknown error".to_string());
+                rsx! {
+                    {firewall}
+                    div {
+                        class: "error-message simulation-preview",
+                        "⚠️ This transaction is expected to fail: {error}"
+                    }
+                    {instructions}
+                }
+            } else {
+                rsx! {
+                    {firewall}
+                    div {
+                        class: "info-message simulation-preview",
+                        for (index, line) in preview.summary_lines().into_iter().enumerate() {
+                            div { key: "{index}", "{line}" }
+                        }
+                    }
+                    {instructions}
+                }
+            }
+        }
+    }
+}
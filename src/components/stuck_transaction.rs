@@ -0,0 +1,182 @@
+// src/components/stuck_transaction.rs
+use dioxus::prelude::*;
+use crate::hardware::HardwareWallet;
+use crate::signing::active_wallet_signer;
+use crate::storage::{load_fee_bump_settings, save_fee_bump_settings};
+use crate::submission;
+use crate::transaction::{TransactionClient, TransactionStatus};
+use crate::components::transaction_status::use_transaction_status;
+use crate::wallet::WalletInfo;
+use std::sync::Arc;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq)]
+enum LandingState {
+    Pending,
+    Stuck,
+    Bumping,
+    Resigning,
+}
+
+/// Watches a submitted transaction and offers to re-send it with a higher
+/// priority fee when it hasn't landed in time (or does so automatically),
+/// or to rebuild it on a fresh blockhash with a higher fee once the old one
+/// has expired.
+///
+/// Render it with `key: "{signature}"` so a replacement starts a new watch.
+#[component]
+pub fn StuckTransactionMonitor(
+    signature: String,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    onreplaced: EventHandler<String>,
+) -> Element {
+    let mut state = use_signal(|| LandingState::Pending);
+    let mut settings = use_signal(load_fee_bump_settings);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let status = use_transaction_status(signature.clone(), custom_rpc.clone());
+
+    let mut bump = {
+        let signature = signature.clone();
+        let wallet = wallet.clone();
+        let hardware_wallet = hardware_wallet.clone();
+        let custom_rpc = custom_rpc.clone();
+        move || {
+            let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+                Ok(signer) => signer,
+                Err(e) => {
+                    error_message.set(Some(e));
+                    return;
+                }
+            };
+            let signature = signature.clone();
+            let client = TransactionClient::new(custom_rpc.as_deref());
+            let current = settings.peek().clone();
+            state.set(LandingState::Bumping);
+            error_message.set(None);
+            spawn(async move {
+                match submission::bump_fee(&client, signer.as_ref(), &signature, &current).await {
+                    Ok(replacement) => onreplaced.call(replacement),
+                    Err(e) => {
+                        error_message.set(Some(format!("Could not speed up: {}", e)));
+                        state.set(LandingState::Stuck);
+                    }
+                }
+            });
+        }
+    };
+
+    let resign = {
+        let signature = signature.clone();
+        let wallet = wallet.clone();
+        let hardware_wallet = hardware_wallet.clone();
+        let custom_rpc = custom_rpc.clone();
+        move |_| {
+            let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+                Ok(signer) => signer,
+                Err(e) => {
+                    error_message.set(Some(e));
+                    return;
+                }
+            };
+            let signature = signature.clone();
+            let client = TransactionClient::new(custom_rpc.as_deref());
+            let current = settings.peek().clone();
+            state.set(LandingState::Resigning);
+            error_message.set(None);
+            spawn(async move {
+                match submission::rebuild_and_resend(&client, signer.as_ref(), &signature, &current).await {
+                    Ok(replacement) => onreplaced.call(replacement),
+                    Err(e) => {
+                        error_message.set(Some(format!("Could not rebuild: {}", e)));
+                        state.set(LandingState::Pending);
+                    }
+                }
+            });
+        }
+    };
+
+    let signature_for_watch = signature.clone();
+    let mut bump_for_watch = bump.clone();
+    use_effect(move || {
+        let signature = signature_for_watch.clone();
+        spawn(async move {
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                // Seen by the cluster (or expired); the tracker takes it from here
+                if *status.peek() != TransactionStatus::Submitted {
+                    break;
+                }
+                // Replaced, or never broadcast through the tracker
+                if submission::tracked(&signature).is_none() {
+                    break;
+                }
+                if *state.peek() == LandingState::Pending && submission::is_stuck(&signature, &settings.peek()) {
+                    state.set(LandingState::Stuck);
+                    if settings.peek().auto_bump {
+                        bump_for_watch();
+                    }
+                }
+            }
+        });
+    });
+
+    rsx! {
+        div {
+            class: "wallet-field",
+            match (status(), state()) {
+                (TransactionStatus::Expired, LandingState::Resigning) => rsx! { div { class: "info-message", "🔁 Rebuilding with a fresh blockhash and a higher fee..." } },
+                (TransactionStatus::Expired, _) => rsx! {
+                    div { class: "error-message", "{status().label()}" }
+                    div { class: "help-text", "Nothing was charged. The same instructions can be rebuilt with a fresh blockhash and a higher priority fee." }
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard primary",
+                            onclick: resign,
+                            "Rebuild & Resend"
+                        }
+                    }
+                },
+                (TransactionStatus::Failed(_), _) => rsx! { div { class: "error-message", "{status().label()}" } },
+                (TransactionStatus::Confirmed | TransactionStatus::Finalized, _) => rsx! { div { class: "info-message", "{status().label()}" } },
+                (_, LandingState::Pending) => rsx! { div { class: "help-text", "{status().label()}" } },
+                (_, LandingState::Bumping | LandingState::Resigning) => rsx! { div { class: "info-message", "⛽ Re-sending with a higher priority fee..." } },
+                (_, LandingState::Stuck) => rsx! {
+                    div {
+                        class: "warning-message",
+                        "This transaction hasn't landed after {settings().stuck_after_secs}s. The network may be congested."
+                    }
+                    div { class: "modal-buttons",
+                        button {
+                            class: "button-standard primary",
+                            onclick: move |_| bump(),
+                            "Speed Up"
+                        }
+                    }
+                },
+            }
+            if let Some(error) = error_message() {
+                div { class: "error-message", "{error}" }
+            }
+            label {
+                class: "help-text",
+                input {
+                    r#type: "checkbox",
+                    checked: settings().auto_bump,
+                    oninput: move |_| {
+                        let mut updated = settings();
+                        updated.auto_bump = !updated.auto_bump;
+                        if let Err(e) = save_fee_bump_settings(&updated) {
+                            error_message.set(Some(e));
+                        }
+                        settings.set(updated);
+                    }
+                }
+                " Speed up stuck transactions automatically"
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// src/components/tip_picker.rs
+use dioxus::prelude::*;
+use crate::tips::{fetch_tip_floor, TipFloor, TipLevel};
+
+/// Jito tip selector showing what each level costs right now
+#[component]
+pub fn TipPicker(selected: TipLevel, onchange: EventHandler<TipLevel>) -> Element {
+    let mut floor = use_signal(|| None as Option<TipFloor>);
+
+    use_future(move || async move {
+        match fetch_tip_floor().await {
+            Ok(current) => floor.set(Some(current)),
+            Err(e) => println!("⚠️ {}", e),
+        }
+    });
+
+    let options: Vec<(TipLevel, String)> = TipLevel::ALL
+        .into_iter()
+        .map(|level| {
+            let text = match floor() {
+                Some(current) if level != TipLevel::Fixed => format!(
+                    "{} · {:.6} SOL",
+                    level.label(),
+                    current.lamports_for(level) as f64 / 1_000_000_000.0
+                ),
+                _ => level.label().to_string(),
+            };
+            (level, text)
+        })
+        .collect();
+
+    rsx! {
+        div {
+            class: "wallet-field",
+            label { "Jito tip:" }
+            select {
+                onchange: move |e| onchange.call(TipLevel::from_key(&e.value())),
+                for (level, text) in options {
+                    option {
+                        key: "{level.key()}",
+                        value: "{level.key()}",
+                        selected: level == selected,
+                        "{text}"
+                    }
+                }
+            }
+            if floor().is_none() && selected != TipLevel::Fixed {
+                div { class: "help-text", "Current tip levels unavailable; the fixed tip is used if they can't be fetched" }
+            }
+        }
+    }
+}
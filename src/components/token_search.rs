@@ -0,0 +1,193 @@
+// src/components/token_search.rs
+use dioxus::prelude::*;
+use crate::components::common::Token;
+use crate::config::tokens::get_verified_tokens;
+use crate::prices::search_tokens;
+use crate::token_utils::{token_from_jupiter_info, token_from_mint};
+use crate::transaction::TransactionClient;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// Wait this long after the last keystroke before searching
+const SEARCH_DEBOUNCE_MS: u64 = 300;
+
+/// A search result and whether the token is on a verified list
+#[derive(Clone, PartialEq)]
+struct SearchResult {
+    token: Token,
+    verified: bool,
+}
+
+fn is_verified(mint: &str, jupiter_verified: Option<bool>) -> bool {
+    jupiter_verified.unwrap_or(false) || get_verified_tokens().contains_key(mint)
+}
+
+/// Pick any token: the wallet's own, a Jupiter token list search by name or
+/// symbol, or a pasted mint address (looked up on chain if Jupiter doesn't
+/// list it)
+#[component]
+pub fn TokenSearch(
+    tokens: Vec<Token>,
+    custom_rpc: Option<String>,
+    onselect: EventHandler<Token>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut query = use_signal(String::new);
+    let mut results = use_signal(Vec::<SearchResult>::new);
+    let mut searching = use_signal(|| false);
+    let mut error_message = use_signal(|| None as Option<String>);
+    let mut generation = use_signal(|| 0u64);
+
+    let mut search = move |text: String| {
+        query.set(text.clone());
+        error_message.set(None);
+        let current = generation() + 1;
+        generation.set(current);
+        if text.trim().is_empty() {
+            results.set(Vec::new());
+            searching.set(false);
+            return;
+        }
+        let custom_rpc = custom_rpc.clone();
+        spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(SEARCH_DEBOUNCE_MS)).await;
+            if *generation.peek() != current {
+                return;
+            }
+            searching.set(true);
+            let text = text.trim().to_string();
+            let mut found: Vec<SearchResult> = match search_tokens(&text).await {
+                Ok(infos) => infos
+                    .iter()
+                    .map(|info| SearchResult {
+                        token: token_from_jupiter_info(info),
+                        verified: is_verified(&info.id, info.is_verified),
+                    })
+                    .collect(),
+                Err(e) => {
+                    error_message.set(Some(format!("Token search failed: {}", e)));
+                    Vec::new()
+                }
+            };
+
+            // A pasted mint Jupiter doesn't list
+            if let Ok(mint) = Pubkey::from_str(&text) {
+                if !found.iter().any(|r| r.token.mint == text) {
+                    match TransactionClient::new(custom_rpc.as_deref()).get_mint_info(&mint).await {
+                        Ok(info) => found.insert(0, SearchResult { token: token_from_mint(&text, info.decimals), verified: false }),
+                        Err(e) => error_message.set(Some(format!("{} is not a token mint: {}", text, e))),
+                    }
+                }
+            }
+
+            if *generation.peek() == current {
+                results.set(found);
+                searching.set(false);
+            }
+        });
+    };
+
+    let needle = query().trim().to_lowercase();
+    let held: Vec<Token> = tokens
+        .iter()
+        .filter(|t| {
+            needle.is_empty()
+                || t.symbol.to_lowercase().contains(&needle)
+                || t.name.to_lowercase().contains(&needle)
+                || t.mint.to_lowercase() == needle
+        })
+        .cloned()
+        .collect();
+    let others: Vec<SearchResult> = results()
+        .into_iter()
+        .filter(|r| !tokens.iter().any(|t| t.mint == r.token.mint))
+        .collect();
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |e| {
+                e.stop_propagation();
+                onclose.call(());
+            },
+
+            div {
+                class: "modal-content",
+                onclick: move |e| e.stop_propagation(),
+
+                div {
+                    class: "modal-header",
+                    h2 { class: "modal-title", "Select Token" }
+                    button {
+                        class: "modal-close-button",
+                        onclick: move |_| onclose.call(()),
+                        "×"
+                    }
+                }
+
+                div {
+                    class: "wallet-field",
+                    input {
+                        r#type: "text",
+                        value: "{query}",
+                        placeholder: "Search by name or symbol, or paste a mint address",
+                        oninput: move |e| search(e.value()),
+                    }
+                }
+
+                if let Some(error) = error_message() {
+                    div { class: "error-message", "{error}" }
+                }
+
+                if !held.is_empty() {
+                    div { class: "help-text", "In your wallet" }
+                }
+                for token in held {
+                    {
+                        let label = format!("{} · {}", token.symbol, token.name);
+                        let balance = format!("{:.4}", token.balance);
+                        let picked = token.clone();
+                        rsx! {
+                            div {
+                                key: "{token.mint}",
+                                class: "token-search-row",
+                                onclick: move |_| onselect.call(picked.clone()),
+                                img { class: "token-search-icon", src: "{token.icon_type}" }
+                                span { class: "token-search-label", "{label}" }
+                                span { class: "token-search-balance", "{balance}" }
+                            }
+                        }
+                    }
+                }
+
+                if searching() {
+                    div { class: "help-text", "Searching..." }
+                } else if !others.is_empty() {
+                    div { class: "help-text", "All tokens" }
+                }
+                for result in others {
+                    {
+                        let label = format!("{} · {}", result.token.symbol, result.token.name);
+                        let short_mint = format!("{}…{}", &result.token.mint[..4], &result.token.mint[result.token.mint.len() - 4..]);
+                        let token = result.token.clone();
+                        rsx! {
+                            div {
+                                key: "{result.token.mint}",
+                                class: "token-search-row",
+                                onclick: move |_| onselect.call(token.clone()),
+                                img { class: "token-search-icon", src: "{result.token.icon_type}" }
+                                span { class: "token-search-label", "{label}" }
+                                if result.verified {
+                                    span { class: "token-search-verified", title: "Verified", "✓" }
+                                } else {
+                                    span { class: "token-search-unverified", title: "Not verified, check the mint", "⚠️" }
+                                }
+                                span { class: "token-search-balance", "{short_mint}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+// src/components/transaction_export.rs
+use dioxus::prelude::*;
+use crate::hardware::HardwareWallet;
+use crate::qr_transfer::{encode_frames, TransferKind};
+use crate::signing::partial::{encode_transaction, is_fully_signed, partially_sign};
+use crate::signing::active_wallet_signer;
+use crate::storage::save_export_file;
+use crate::wallet::WalletInfo;
+use qrcode::{QrCode, render::svg};
+use solana_sdk::transaction::VersionedTransaction;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Time each frame of an animated code stays on screen
+const FRAME_INTERVAL: Duration = Duration::from_millis(400);
+
+fn qr_code_svg(data: &str) -> Option<String> {
+    QrCode::new(data).ok().map(|qr| {
+        qr.render()
+            .min_dimensions(260, 260)
+            .dark_color(svg::Color("#000000"))
+            .light_color(svg::Color("#ffffff"))
+            .build()
+    })
+}
+
+/// Export a built transaction in base64 wire format, unsigned or signed by
+/// the active wallet, to the clipboard, a file or (animated) QR codes. The
+/// transaction is never broadcast from here.
+#[component]
+pub fn TransactionExportPanel(
+    transaction: VersionedTransaction,
+    /// Used in the exported file name, e.g. "send" or "swap"
+    label: String,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    onclose: EventHandler<()>,
+) -> Element {
+    let mut current = use_signal(|| transaction.clone());
+    let mut signing = use_signal(|| false);
+    let mut frames = use_signal(Vec::<String>::new);
+    let mut frame_index = use_signal(|| 0usize);
+    let mut status = use_signal(|| None as Option<String>);
+    let mut error_message = use_signal(|| None as Option<String>);
+
+    // Cycle through the frames of a multi-part code
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(FRAME_INTERVAL).await;
+            let count = frames.peek().len();
+            if count > 1 {
+                frame_index.set((*frame_index.peek() + 1) % count);
+            }
+        }
+    });
+
+    let encoded = encode_transaction(&current()).unwrap_or_default();
+    let signed = is_fully_signed(&current());
+    let state = if signed { "signed" } else { "unsigned" };
+
+    let sign = move |_| {
+        let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+            Ok(signer) => signer,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        signing.set(true);
+        error_message.set(None);
+        spawn(async move {
+            let mut transaction = current();
+            match partially_sign(&mut transaction, signer.as_ref()).await {
+                Ok(()) => {
+                    current.set(transaction);
+                    frames.set(Vec::new());
+                    status.set(Some("Signed. Nothing was sent.".to_string()));
+                }
+                Err(e) => error_message.set(Some(format!("Signing failed: {}", e))),
+            }
+            signing.set(false);
+        });
+    };
+
+    let encoded_for_copy = encoded.clone();
+    let copy = move |_| {
+        let text = encoded_for_copy.clone();
+        spawn(async move {
+            let mut clipboard = document::eval("await navigator.clipboard.writeText(await dioxus.recv()); dioxus.send(true);");
+            let _ = clipboard.send(text);
+            match clipboard.recv::<bool>().await {
+                Ok(_) => status.set(Some("Copied to clipboard".to_string())),
+                Err(_) => error_message.set(Some("Clipboard unavailable, copy the text instead".to_string())),
+            }
+        });
+    };
+
+    let encoded_for_file = encoded.clone();
+    let label_for_file = label.clone();
+    let save = move |_| {
+        let file_name = format!("{}-{}-{}.txt", label_for_file, state, chrono::Utc::now().timestamp());
+        match save_export_file(&file_name, &encoded_for_file) {
+            Ok(path) => status.set(Some(format!("Saved to {}", path))),
+            Err(e) => error_message.set(Some(e)),
+        }
+    };
+
+    let encoded_for_qr = encoded.clone();
+
+    rsx! {
+        div {
+            class: "wallet-field",
+            label { "Export {state} transaction (base64):" }
+            textarea {
+                readonly: true,
+                rows: "4",
+                value: "{encoded}",
+            }
+            div { class: "help-text", "{encoded.len()} characters. Inspect it in external tools or broadcast it from another machine." }
+
+            if let Some(message) = status() {
+                div { class: "info-message", "{message}" }
+            }
+            if let Some(error) = error_message() {
+                div { class: "error-message", "{error}" }
+            }
+
+            if !frames().is_empty() {
+                if let Some(svg) = frames().get(frame_index()).and_then(|frame| qr_code_svg(frame)) {
+                    div {
+                        class: "qr-code-container",
+                        dangerous_inner_html: "{svg}"
+                    }
+                }
+                if frames().len() > 1 {
+                    div { class: "help-text", "Frame {frame_index() + 1} of {frames().len()}. Scan it from Device Transfer on the other device." }
+                }
+            }
+
+            div { class: "modal-buttons",
+                if !signed {
+                    button {
+                        class: "button-standard secondary",
+                        disabled: signing(),
+                        onclick: sign,
+                        if signing() { "Signing..." } else { "Sign Without Sending" }
+                    }
+                }
+                button {
+                    class: "button-standard secondary",
+                    onclick: copy,
+                    "Copy"
+                }
+                button {
+                    class: "button-standard secondary",
+                    onclick: save,
+                    "Save File"
+                }
+                button {
+                    class: "button-standard secondary",
+                    onclick: move |_| {
+                        if frames().is_empty() {
+                            frame_index.set(0);
+                            frames.set(encode_frames(TransferKind::Transaction, &encoded_for_qr));
+                        } else {
+                            frames.set(Vec::new());
+                        }
+                    },
+                    if frames().is_empty() { "Show QR" } else { "Hide QR" }
+                }
+                button {
+                    class: "button-standard secondary",
+                    onclick: move |_| onclose.call(()),
+                    "Close"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+// src/components/transaction_status.rs
+use dioxus::prelude::*;
+use crate::transaction::{TransactionClient, TransactionStatus};
+
+/// Live status of a submitted transaction, updated from the confirmation
+/// tracker. Use it with `key: "{signature}"` so a new signature starts a new
+/// tracker.
+pub fn use_transaction_status(signature: String, custom_rpc: Option<String>) -> Signal<TransactionStatus> {
+    let mut status = use_signal(|| TransactionStatus::Submitted);
+    use_future(move || {
+        let signature = signature.clone();
+        let custom_rpc = custom_rpc.clone();
+        async move {
+            let mut receiver = TransactionClient::new(custom_rpc.as_deref()).watch_transaction(&signature);
+            while receiver.changed().await.is_ok() {
+                let latest = receiver.borrow_and_update().clone();
+                status.set(latest);
+            }
+        }
+    });
+    status
+}
+
+/// One-line live confirmation status for a transaction signature
+#[component]
+pub fn TransactionStatusView(signature: String, custom_rpc: Option<String>) -> Element {
+    let status = use_transaction_status(signature, custom_rpc);
+
+    rsx! {
+        match status() {
+            TransactionStatus::Submitted => rsx! { div { class: "help-text", "{status().label()}" } },
+            TransactionStatus::Failed(_) | TransactionStatus::Expired => rsx! { div { class: "error-message", "{status().label()}" } },
+            _ => rsx! { div { class: "info-message", "{status().label()}" } },
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// src/components/update_banner.rs
+use dioxus::prelude::*;
+use crate::storage::{load_dismissed_update_version, save_dismissed_update_version};
+use crate::updates::{self, ReleaseManifest};
+
+#[derive(Debug, Clone, PartialEq)]
+enum InstallState {
+    Idle,
+    Downloading,
+    Installed,
+    Failed(String),
+}
+
+/// Checks the signed release manifest once per launch and offers newer
+/// versions with their changelog. Desktop builds can download, verify and
+/// install the update in place.
+#[component]
+pub fn UpdateBanner() -> Element {
+    let mut available = use_signal(|| None as Option<ReleaseManifest>);
+    let mut show_changelog = use_signal(|| false);
+    let mut install_state = use_signal(|| InstallState::Idle);
+
+    use_future(move || async move {
+        match updates::check_for_update().await {
+            Ok(Some(manifest)) => {
+                let dismissed = load_dismissed_update_version();
+                // Critical releases can't be dismissed
+                if manifest.is_critical() || dismissed.as_deref() != Some(manifest.version.as_str()) {
+                    available.set(Some(manifest));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => println!("⚠️ Update check skipped: {}", e),
+        }
+    });
+
+    let Some(manifest) = available() else {
+        return rsx! {};
+    };
+    let critical = manifest.is_critical();
+    let version = manifest.version.clone();
+    let version_for_dismiss = version.clone();
+    let manifest_for_install = manifest.clone();
+
+    rsx! {
+        div {
+            class: if critical { "error-message" } else { "info-message" },
+            style: "margin: 8px 16px;",
+            if critical {
+                div { "⚠️ Version {version} fixes a security issue in the version you are running ({updates::current_version()}). Please update." }
+            } else {
+                div { "🆕 Version {version} is available (you have {updates::current_version()})." }
+            }
+
+            if show_changelog() {
+                for (index, line) in manifest.changelog.iter().enumerate() {
+                    div { key: "{index}", class: "help-text", "• {line}" }
+                }
+            }
+
+            match install_state() {
+                InstallState::Downloading => rsx! { div { class: "help-text", "Downloading and verifying..." } },
+                InstallState::Installed => rsx! { div { class: "help-text", "✅ Update installed. Restart the app to use it." } },
+                InstallState::Failed(e) => rsx! { div { class: "help-text", "Update failed: {e}" } },
+                InstallState::Idle => rsx! {},
+            }
+
+            div { class: "modal-buttons",
+                if !manifest.changelog.is_empty() {
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| show_changelog.set(!show_changelog()),
+                        if show_changelog() { "Hide Changes" } else { "What's New" }
+                    }
+                }
+                if updates::can_self_update() && install_state() != InstallState::Installed {
+                    button {
+                        class: "button-standard primary",
+                        disabled: install_state() == InstallState::Downloading,
+                        onclick: move |_| {
+                            let manifest = manifest_for_install.clone();
+                            install_state.set(InstallState::Downloading);
+                            spawn(async move {
+                                let result = match updates::download_update(&manifest).await {
+                                    Ok(binary) => updates::apply_update(&binary),
+                                    Err(e) => Err(e),
+                                };
+                                install_state.set(match result {
+                                    Ok(()) => InstallState::Installed,
+                                    Err(e) => InstallState::Failed(e),
+                                });
+                            });
+                        },
+                        "Update Now"
+                    }
+                }
+                if !critical {
+                    button {
+                        class: "button-standard secondary",
+                        onclick: move |_| {
+                            if let Err(e) = save_dismissed_update_version(&version_for_dismiss) {
+                                println!("⚠️ {}", e);
+                            }
+                            available.set(None);
+                        },
+                        "Later"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -9,6 +9,7 @@ use crate::storage::{
     load_jito_settings_from_storage,
     save_jito_settings_to_storage,
     delete_wallet_from_storage,
+    load_outbox,
     JitoSettings
 };
 use crate::currency::{
@@ -25,21 +26,25 @@ use crate::currency_utils::{
     format_token_value_smart,
     format_token_amount, 
     format_price_change,
+    format_percentage_change,
     get_current_currency_code,
     format_portfolio_balance
 };
 use crate::components::modals::currency_modal::CurrencyModal;
-use crate::components::modals::{WalletModal, RpcModal, SendModalWithHardware, SendTokenModal, HardwareWalletModal, ReceiveModal, JitoModal, StakeModal, BulkSendModal, EjectModal, SwapModal, TransactionHistoryModal, LendModal, ExportWalletModal, DeleteWalletModal, SquadsModal, CarrotModal, BonkStakingModal, QuantumVaultModal};
+use crate::components::modals::{WalletModal, RpcModal, SendModalWithHardware, SendTokenModal, HardwareWalletModal, ReceiveModal, JitoModal, StakeModal, BulkSendModal, EjectModal, SwapModal, TransactionHistoryModal, LendModal, ExportWalletModal, DeleteWalletModal, SquadsModal, CarrotModal, BonkStakingModal, QuantumVaultModal, StreamingModal, AddressBookModal, SharedWalletModal, PartialSignModal, WalletProfileModal, SignMessageModal, DeviceTransferModal, SessionKeyModal, HistoricalBalancesModal, CostBasisModal, PriceProvidersModal, SwapProvidersModal, LimitOrdersModal, DcaModal, SwapHistoryModal, StakePositionsModal, LiquidStakingModal, SpreadStakeModal, BackupModal, StakingRewardsModal, NotificationsModal, OutboxModal, ComposerModal, BudgetModal, BroadcastModal, MultiTransferModal, OrganizeWalletsModal, PinSecurityModal, DuressPinModal};
 use crate::components::modals::send_modal::HardwareWalletEvent;
+use crate::components::wsol_cleanup::WrappedSolCleanup;
 use crate::token_utils::process_tokens_for_display;
 use crate::components::common::TokenDisplayData;
 use crate::components::common::{Token, TokenSortConfig, TokenFilter, SortCriteria};
-use crate::rpc::{self, CollectibleInfo, fetch_collectibles, TokenAccountFilter};
+use crate::rpc::{self, CollectibleInfo, fetch_collectibles};
 use crate::prices;
 use crate::hardware::HardwareWallet;
 use crate::hardware::HardwareDeviceType;
 use crate::components::background_themes::BackgroundTheme;
 use crate::components::modals::BackgroundModal;
+use crate::components::update_banner::UpdateBanner;
+use crate::components::budget_overlay::BudgetOverlay;
 use crate::prices::CandlestickData;
 use crate::config::tokens::{get_verified_tokens, VerifiedToken};
 use std::sync::Arc;
@@ -273,6 +278,8 @@ fn CandlestickChart(
     data: Vec<CandlestickData>,
     symbol: String,
     timeframe: String, // Just pass the timeframe as a simple string
+    #[props(default)] show_moving_average: bool,
+    #[props(default)] show_rsi: bool,
 ) -> Element {
     println!("🎯 Rendering candlestick chart for {} with {} candles ({})", symbol, data.len(), timeframe);
     
@@ -290,12 +297,17 @@ fn CandlestickChart(
     let height = 160.0; // Increase from 120.0
     let margin = 15.0;  // Increase margin slightly
     let chart_width = width - (margin * 2.0);
-    let chart_height = height - (margin * 2.0);
+    // Bottom band of the chart holds the volume bars
+    let has_volume = data.iter().any(|c| c.volume.unwrap_or(0.0) > 0.0);
+    let volume_height = if has_volume { 30.0 } else { 0.0 };
+    let chart_height = height - (margin * 2.0) - volume_height;
+    let rsi_height = 50.0;
 
     // Find price range
     let min_price = data.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
     let max_price = data.iter().map(|c| c.high).fold(0.0, f64::max);
     let price_range = max_price - min_price;
+    let max_volume = data.iter().filter_map(|c| c.volume).fold(0.0, f64::max);
 
     // Scale functions
     let price_to_y = |price: f64| -> f64 {
@@ -317,6 +329,39 @@ fn CandlestickChart(
         4.0
     };
 
+    let closes: Vec<f64> = data.iter().map(|c| c.close).collect();
+    let to_points = |values: Vec<Option<f64>>, to_y: &dyn Fn(f64) -> f64| -> String {
+        values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, value)| value.map(|v| format!("{:.1},{:.1}", index_to_x(i), to_y(v))))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let moving_average_points = if show_moving_average {
+        to_points(prices::moving_average(&closes, prices::MOVING_AVERAGE_PERIOD), &price_to_y)
+    } else {
+        String::new()
+    };
+    let rsi_to_y = |value: f64| 5.0 + (100.0 - value) / 100.0 * (rsi_height - 10.0);
+    let rsi_points = if show_rsi {
+        to_points(prices::rsi(&closes, prices::RSI_PERIOD), &rsi_to_y)
+    } else {
+        String::new()
+    };
+    let (overbought_y, oversold_y) = (rsi_to_y(70.0), rsi_to_y(30.0));
+    let volume_bars: Vec<(f64, f64, f64, &'static str)> = data
+        .iter()
+        .enumerate()
+        .filter_map(|(i, candle)| {
+            let volume = candle.volume.filter(|v| *v > 0.0 && max_volume > 0.0)?;
+            let bar_height = (volume / max_volume * volume_height).max(1.0);
+            let color = if candle.close >= candle.open { "#22c55e" } else { "#ef4444" };
+            Some((index_to_x(i) - candle_width / 2.0, height - margin - bar_height, bar_height, color))
+        })
+        .collect();
+    let (_, _, period_label) = prices::chart_timeframe(&timeframe);
+
     rsx! {
         div {
             class: "candlestick-chart-container",
@@ -346,6 +391,19 @@ fn CandlestickChart(
                     height: "{height}",
                     fill: "url(#grid-{symbol})",
                 }
+
+                // Volume bars
+                for (i, (x, y, bar_height, color)) in volume_bars.into_iter().enumerate() {
+                    rect {
+                        key: "vol-{i}",
+                        x: "{x}",
+                        y: "{y}",
+                        width: "{candle_width}",
+                        height: "{bar_height}",
+                        fill: "{color}",
+                        opacity: "0.35",
+                    }
+                }
                 
                 // Draw candlesticks
                 for (i, candle) in data.iter().enumerate() {
@@ -391,6 +449,16 @@ fn CandlestickChart(
                         }
                     }
                 }
+
+                // Moving average
+                if !moving_average_points.is_empty() {
+                    polyline {
+                        points: "{moving_average_points}",
+                        fill: "none",
+                        stroke: "#60a5fa",
+                        stroke_width: "1.5",
+                    }
+                }
                 
                 // Price labels (min/max)
                 text {
@@ -410,6 +478,32 @@ fn CandlestickChart(
                     "${min_price:.2}"
                 }
             }
+
+            // RSI panel
+            if !rsi_points.is_empty() {
+                svg {
+                    width: "{width}",
+                    height: "{rsi_height}",
+                    view_box: "0 0 {width} {rsi_height}",
+                    style: "background: rgba(0, 0, 0, 0.3); border-radius: 8px; margin-top: 4px;",
+                    line { x1: "{margin}", y1: "{overbought_y}", x2: "{width - margin}", y2: "{overbought_y}", stroke: "rgba(239, 68, 68, 0.4)", stroke_dasharray: "3 3" }
+                    line { x1: "{margin}", y1: "{oversold_y}", x2: "{width - margin}", y2: "{oversold_y}", stroke: "rgba(34, 197, 94, 0.4)", stroke_dasharray: "3 3" }
+                    polyline {
+                        points: "{rsi_points}",
+                        fill: "none",
+                        stroke: "#a78bfa",
+                        stroke_width: "1.5",
+                    }
+                    text {
+                        x: "{margin}",
+                        y: "12",
+                        fill: "#888",
+                        font_size: "10",
+                        font_family: "monospace",
+                        "RSI {prices::RSI_PERIOD}"
+                    }
+                }
+            }
             
             // Chart summary below
             div {
@@ -417,16 +511,14 @@ fn CandlestickChart(
                 span {
                     "Range: ${min_price:.2} - ${max_price:.2}"
                 }
+                if show_moving_average {
+                    span { class: "chart-legend-ma", "MA{prices::MOVING_AVERAGE_PERIOD}" }
+                }
                 span {
                     {
                         let latest = data.last().unwrap();
                         let change = latest.close - data.first().unwrap().close;
                         let change_pct = (change / data.first().unwrap().close) * 100.0;
-                        let period_label = match timeframe.as_str() {
-                            "1H" => "3D",
-                            "1D" => "30D", 
-                            _ => "Period",
-                        };
                         if change >= 0.0 {
                             format!("{}: +{:.1}%", period_label, change_pct)
                         } else {
@@ -454,10 +546,20 @@ pub fn WalletView() -> Element {
     let mut show_history_modal = use_signal(|| false);
     let mut show_stake_modal = use_signal(|| false);
     let mut show_swap_modal = use_signal(|| false);
+    let mut show_limit_orders_modal = use_signal(|| false);
+    let mut show_dca_modal = use_signal(|| false);
+    let mut show_swap_history_modal = use_signal(|| false);
+    let mut show_stake_positions_modal = use_signal(|| false);
+    let mut show_liquid_staking_modal = use_signal(|| false);
+    let mut show_spread_stake_modal = use_signal(|| false);
+    let mut show_staking_rewards_modal = use_signal(|| false);
+    // DCA buys that are due but need the wallet to confirm them
+    let mut dca_due = use_signal(|| 0usize);
     let mut show_squads_modal = use_signal(|| false);
     let mut show_carrot_modal = use_signal(|| false);
     let mut show_bonk_staking_modal = use_signal(|| false);
     let mut show_quantum_vault_modal = use_signal(|| false);
+    let mut show_streaming_modal = use_signal(|| false);
     
     // Integrations collapse/expand state
     let mut show_integrations = use_signal(|| false);
@@ -465,6 +567,30 @@ pub fn WalletView() -> Element {
     // Hardware wallet state
     let mut hardware_wallet = use_signal(|| None as Option<Arc<HardwareWallet>>);
     let mut show_hardware_modal = use_signal(|| false);
+    let mut show_address_book_modal = use_signal(|| false);
+    let mut show_shared_wallet_modal = use_signal(|| false);
+    let mut show_partial_sign_modal = use_signal(|| false);
+    let mut show_wallet_profile_modal = use_signal(|| false);
+    let mut show_organize_wallets_modal = use_signal(|| false);
+    // Folders, order, colors and emojis of the wallet list
+    let mut wallet_profiles = use_signal(crate::storage::load_wallet_profiles);
+    let mut biometric_enabled = use_signal(crate::biometric::is_enabled);
+    let mut show_pin_security_modal = use_signal(|| false);
+    let mut show_duress_pin_modal = use_signal(|| false);
+    let mut show_sign_message_modal = use_signal(|| false);
+    let mut show_device_transfer_modal = use_signal(|| false);
+    let mut show_session_key_modal = use_signal(|| false);
+    let mut show_historical_balances_modal = use_signal(|| false);
+    let mut show_cost_basis_modal = use_signal(|| false);
+    // Last computed cost basis of the current wallet, see `cost_basis`
+    let mut cost_basis_report = use_signal(|| None as Option<crate::cost_basis::CostBasisReport>);
+    let mut show_notifications_modal = use_signal(|| false);
+    let mut show_outbox_modal = use_signal(|| false);
+    let mut show_composer_modal = use_signal(|| false);
+    let mut show_budget_modal = use_signal(|| false);
+    let mut budget_revision = use_signal(|| 0u32);
+    let mut show_broadcast_modal = use_signal(|| false);
+    let mut show_multi_transfer_modal = use_signal(|| false);
     let mut hardware_device_present = use_signal(|| false);
     let mut hardware_connected = use_signal(|| false);
     let mut hardware_pubkey = use_signal(|| None as Option<String>);
@@ -479,6 +605,7 @@ pub fn WalletView() -> Element {
 
     //Additional Wallet features
     let mut show_export_modal = use_signal(|| false);
+    let mut show_backup_modal = use_signal(|| false);
     let mut show_delete_confirmation = use_signal(|| false);
 
     // Balance management
@@ -501,8 +628,16 @@ pub fn WalletView() -> Element {
     let mut token_prices = use_signal(|| HashMap::<String, f64>::new());
     let mut prices_loading = use_signal(|| false);
     let mut price_error = use_signal(|| None as Option<String>);
+    // Streaming price feed for held tokens, see `prices::stream_prices`
+    let mut live_prices = use_signal(|| false);
+    let mut price_stream = use_signal(|| None as Option<Task>);
+
+    // Active cluster, refreshed when RPC settings are saved
+    let mut active_network = use_signal(crate::config::network::active);
+    let mut airdropping = use_signal(|| false);
 
     let verified_tokens = use_memo(move || {
+        let _ = active_network();
         get_verified_tokens().clone()
     });
 
@@ -512,6 +647,8 @@ pub fn WalletView() -> Element {
 
     //Currency
     let mut show_currency_modal = use_signal(|| false);
+    let mut show_price_providers_modal = use_signal(|| false);
+    let mut show_swap_providers_modal = use_signal(|| false);
 
     //Tokens
     let mut show_send_token_modal = use_signal(|| false);
@@ -541,20 +678,31 @@ pub fn WalletView() -> Element {
     let mut chart_data = use_signal(|| HashMap::<String, Vec<CandlestickData>>::new());
     let mut chart_loading = use_signal(|| HashSet::<String>::new());
     let mut selected_timeframe = use_signal(|| HashMap::<String, String>::new()); // Per-token timeframe
+    // Chart indicator toggles, shared by every token chart
+    let mut chart_moving_average = use_signal(|| false);
+    let mut chart_rsi = use_signal(|| false);
     let mut chart_timeframe_data = use_signal(|| HashMap::<String, HashMap<String, Vec<CandlestickData>>>::new());
 
     let mut show_lend_modal = use_signal(|| false);
     let mut active_tab = use_signal(|| "tokens".to_string());
     let mut collectibles = use_signal(|| Vec::<CollectibleInfo>::new());
     let mut collectibles_loading = use_signal(|| false);
+    // Floor-price estimate of the collectibles, see `nft_valuation`
+    let mut nft_valuation = use_signal(|| None as Option<crate::nft_valuation::NftValuation>);
+    let mut show_nft_breakdown = use_signal(|| false);
 
     // Add this signal near your other hardware wallet signals in wallet_view.rs
     let mut hardware_device_type = use_signal(|| None as Option<HardwareDeviceType>);
     let mut refresh_trigger = use_signal(|| 0u32);
+    // Whether the balance subscription is connected, in which case balances
+    // don't need re-fetching after each transaction
+    let mut live_balances = use_signal(|| false);
+    let mut balance_subscription = use_signal(|| None as Option<Task>);
     let mut is_refreshing = use_signal(|| false);
     
     // Load wallets from storage on component mount
     use_effect(move || {
+        let _span = crate::startup::span("load wallets");
         let stored_wallets = load_wallets_from_storage();
         if stored_wallets.is_empty() {
             let new_wallet = Wallet::new("Main Wallet".to_string());
@@ -569,8 +717,9 @@ pub fn WalletView() -> Element {
     // Monitor hardware wallet presence - check every 2 seconds
     use_effect(move || {
         spawn(async move {
+            crate::startup::after_first_frame().await;
             loop {
-                let is_present = HardwareWallet::is_device_present();
+                let is_present = HardwareWallet::detect_device_present().await;
                 hardware_device_present.set(is_present);
                 
                 if !is_present && hardware_connected() {
@@ -583,6 +732,72 @@ pub fn WalletView() -> Element {
             }
         });
     });
+
+    // Run due DCA buys the session key may sign; count the ones left for the user
+    use_effect(move || {
+        spawn(async move {
+            crate::startup::after_first_frame().await;
+            loop {
+                let address = if *hardware_connected.peek() {
+                    hardware_pubkey.peek().clone()
+                } else {
+                    wallets.peek().get(*current_wallet_index.peek()).map(|wallet| wallet.address.clone())
+                };
+                if let Some(address) = address {
+                    let rpc = custom_rpc.peek().clone();
+                    dca_due.set(crate::swap::dca::run_due_buys(&address, rpc.as_deref()).await);
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            }
+        });
+    });
+
+    // Re-benchmark RPC endpoints so auto-select keeps using the fastest one
+    use_effect(move || {
+        spawn(async move {
+            crate::startup::after_first_frame().await;
+            loop {
+                let primary = custom_rpc.peek().clone().unwrap_or_else(|| crate::rpc_pool::DEFAULT_RPC_URL.to_string());
+                crate::rpc_pool::benchmark(&primary).await;
+                tokio::time::sleep(crate::rpc_pool::BENCHMARK_INTERVAL).await;
+            }
+        });
+    });
+
+    // Broadcast queued offline transactions once the network is reachable again
+    use_effect(move || {
+        spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+                if load_outbox().iter().any(|item| item.is_pending()) {
+                    crate::transaction::TransactionClient::new(custom_rpc.peek().as_deref()).flush_outbox().await;
+                }
+            }
+        });
+    });
+
+    // Android can drop the USB link while the app is in the background, so
+    // check it (and reattach a granted device) whenever the app is visible again
+    use_effect(move || {
+        spawn(async move {
+            let mut visibility = document::eval(r#"
+                document.addEventListener('visibilitychange', () => {
+                    if (document.visibilityState === 'visible') {
+                        dioxus.send('visible');
+                    }
+                });
+            "#);
+            while visibility.recv::<String>().await.is_ok() {
+                let Some(hw) = hardware_wallet().filter(|_| hardware_connected()) else { continue };
+                if let Err(e) = hw.reattach().await {
+                    println!("⚠️ Hardware wallet lost: {}", e);
+                    hardware_connected.set(false);
+                    hardware_wallet.set(None);
+                    hardware_pubkey.set(None);
+                }
+            }
+        });
+    });
     
     async fn fetch_chart_data(
         symbol: String,
@@ -646,20 +861,19 @@ pub fn WalletView() -> Element {
         
         let cache_key = format!("{}_{}", symbol, timeframe);
         
-        // Add to loading set
-        {
+        // Show stored candles right away; only show loading without them
+        let stored = prices::cached_candles(&symbol, &timeframe);
+        if stored.is_empty() {
             let mut loading_set = chart_loading();
             loading_set.insert(cache_key.clone());
             chart_loading.set(loading_set);
+        } else {
+            let mut chart_map = chart_data();
+            chart_map.insert(cache_key.clone(), stored);
+            chart_data.set(chart_map);
         }
 
-        let (days, resolution) = match timeframe.as_str() {
-            "1H" => (3, "60"),   // 7 days of hourly data
-            "1D" => (30, "1D"),  // 30 days of daily data
-            _ => (30, "1D"),     // Default fallback
-        };
-
-        match prices::get_candlestick_data_with_resolution(&symbol, days, resolution).await {
+        match prices::get_cached_candlestick_data(&symbol, &timeframe).await {
             Ok(data) => {
                 println!("✅ Got {} candlesticks for {} ({})", data.len(), symbol, timeframe);
                 
@@ -725,17 +939,60 @@ pub fn WalletView() -> Element {
 
     use_effect(move || {
         spawn(async move {
+            // Prices aren't needed to paint the wallet, so wait for the first frame
+            crate::startup::after_first_frame().await;
+
             // Initial fetch
             fetch_token_prices(token_prices, prices_loading, price_error, sol_price, daily_change, daily_change_percent, token_changes, multi_timeframe_data).await;
             
-            // Then fetch every 2 minutes (120 seconds)
+            // Then fetch every 2 minutes (120 seconds), unless prices are streaming
             loop {
                 tokio::time::sleep(std::time::Duration::from_secs(120)).await;
+                if *live_prices.peek() {
+                    continue;
+                }
                 fetch_token_prices(token_prices, prices_loading, price_error, sol_price, daily_change, daily_change_percent, token_changes, multi_timeframe_data).await;
             }
         });
     });
 
+    // Stream prices of held tokens that have a Pyth feed
+    let streamed_symbols = use_memo(move || {
+        prices::streamable_symbols(tokens.read().iter().map(|token| token.symbol.as_str()))
+    });
+
+    use_effect(move || {
+        let symbols = streamed_symbols();
+        if let Some(task) = price_stream.write().take() {
+            task.cancel();
+        }
+        live_prices.set(false);
+        if symbols.is_empty() {
+            return;
+        }
+
+        let task = spawn(async move {
+            crate::startup::after_first_frame().await;
+            prices::stream_prices(
+                symbols,
+                move |symbol, price| {
+                    token_prices.write().insert(symbol.clone(), price);
+                    crate::currency::update_crypto_rate(&symbol, price);
+                    if symbol == "SOL" {
+                        sol_price.set(price);
+                    }
+                    for token in tokens.write().iter_mut().filter(|token| token.symbol == symbol) {
+                        token.price = price;
+                        token.value_usd = token.balance * price;
+                    }
+                },
+                move |live| live_prices.set(live),
+            )
+            .await;
+        });
+        price_stream.set(Some(task));
+    });
+
     // 5. Helper function to extract multi-timeframe data
     fn get_multi_timeframe_changes(
         symbol: &str,
@@ -753,12 +1010,23 @@ pub fn WalletView() -> Element {
     }
 
     // Fetch balance and token accounts when wallet changes or hardware wallet connects
+    // Show the stored cost basis of whichever wallet is active
+    use_effect(move || {
+        let address = if hardware_connected() && hardware_pubkey().is_some() {
+            hardware_pubkey()
+        } else {
+            wallets.read().get(current_wallet_index()).map(|wallet| wallet.address.clone())
+        };
+        cost_basis_report.set(address.and_then(|address| crate::storage::load_cost_basis(&address)));
+    });
+
     use_effect(move || {
         let wallets_list = wallets.read();
         let index = current_wallet_index();
         let hw_connected = hardware_connected();
         let hw_pubkey = hardware_pubkey();
         let _ = refresh_trigger();
+        let _ = active_network();
         
         let address = if hw_connected && hw_pubkey.is_some() {
             hw_pubkey.clone().unwrap()
@@ -788,40 +1056,13 @@ pub fn WalletView() -> Element {
             }
             
             
-            // Fetch token accounts from BOTH Token and Token-2022 programs
-            println!("Fetching token accounts from both Token and Token-2022 programs...");
-            
-            // Fetch from standard Token program
-            let filter_token = Some(rpc::TokenAccountFilter::ProgramId(
-                "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string()
-            ));
-            let token_accounts = rpc::get_token_accounts_by_owner(&address, filter_token, rpc_url.as_deref()).await
-                .unwrap_or_else(|e| {
-                    println!("Failed to fetch Token program accounts: {}", e);
-                    vec![]
-                });
-            
-            // Fetch from Token-2022 program
-            let filter_token22 = Some(rpc::TokenAccountFilter::ProgramId(
-                "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb".to_string()
-            ));
-            let token22_accounts = rpc::get_token_accounts_by_owner(&address, filter_token22, rpc_url.as_deref()).await
+            // Fetch token accounts from both the Token and Token-2022 programs
+            let all_token_accounts = rpc::get_all_token_accounts(&address, rpc_url.as_deref()).await
                 .unwrap_or_else(|e| {
-                    println!("Failed to fetch Token-2022 program accounts: {}", e);
+                    println!("Failed to fetch token accounts: {}", e);
                     vec![]
                 });
             
-            // Merge both sets of token accounts
-            let mut all_token_accounts = token_accounts;
-            let token22_count = token22_accounts.len();
-            all_token_accounts.extend(token22_accounts);
-            
-            println!("Found {} token accounts total ({} Token + {} Token-2022)", 
-                all_token_accounts.len(), 
-                all_token_accounts.len() - token22_count,
-                token22_count
-            );
-            
             if !all_token_accounts.is_empty() {
                     println!("Raw token accounts for address {}: {:?}", address, all_token_accounts);
                     
@@ -849,31 +1090,48 @@ pub fn WalletView() -> Element {
 
                     println!("All non-zero token accounts: {} tokens", all_non_zero_accounts.len());
 
-                    // STEP 2: Fetch token metadata from Jupiter Token API
+                    // STEP 2: Token metadata and collectibles from one DAS query,
+                    // with the Jupiter Token API filling in mints the indexer misses
+                    let mut token_metadata: HashMap<String, rpc::DasTokenMetadata> = HashMap::new();
+                    match rpc::fetch_wallet_assets(&address, rpc_url.as_deref()).await {
+                        Ok(assets) => {
+                            token_metadata.extend(
+                                assets.tokens.into_iter().filter(|(_, metadata)| !metadata.symbol.is_empty())
+                            );
+                            collectibles.set(assets.collectibles);
+                        }
+                        Err(e) => println!("Error fetching DAS assets: {}", e),
+                    }
+
                     let mint_addresses: Vec<String> = all_non_zero_accounts.iter()
                         .map(|account| account.mint.clone())
+                        .filter(|mint| !token_metadata.contains_key(mint))
                         .collect();
 
-                    let token_metadata = if !mint_addresses.is_empty() {
+                    if !mint_addresses.is_empty() {
                         match prices::get_token_metadata(mint_addresses).await {
                             Ok(metadata) => {
                                 println!("Successfully fetched metadata for {} tokens", metadata.len());
-                                metadata
+                                token_metadata.extend(metadata.into_iter().map(|(mint, info)| {
+                                    (mint, rpc::DasTokenMetadata {
+                                        name: info.name,
+                                        symbol: info.symbol,
+                                        image: info.icon,
+                                        decimals: Some(info.decimals),
+                                    })
+                                }));
                             },
                             Err(e) => {
                                 println!("Error fetching token metadata: {}", e);
-                                HashMap::new()
                             }
                         }
-                    } else {
-                        HashMap::new()
-                    };
+                    }
 
                     // STEP 3: Build mint->symbol mapping for price fetching (updated)
                     let mut mint_to_symbol_map = HashMap::new();
                     for account in &all_non_zero_accounts {
                         let symbol = if let Some(metadata) = token_metadata.get(&account.mint) {
-                            // Use metadata from Jupiter Token API
+                            // Use metadata from DAS or the Jupiter Token API
                             metadata.symbol.clone()
                         } else if let Some(verified_token) = verified_tokens_map.get(&account.mint) {
                             // Use verified token name
@@ -916,9 +1174,9 @@ pub fn WalletView() -> Element {
                                 .cloned()
                                 .unwrap_or_else(|| format!("UNKNOWN_{}", &account.mint[..6]));
                             
-                            // Get token metadata from Jupiter API or verified tokens
+                            // Get token metadata from DAS, Jupiter API or verified tokens
                             let (token_name, icon_url) = if let Some(metadata) = token_metadata.get(&account.mint) {
-                                (metadata.name.clone(), metadata.icon.clone())
+                                (metadata.name.clone(), metadata.image.clone())
                             } else if let Some(verified_token) = verified_tokens_map.get(&account.mint) {
                                 (verified_token.name.clone(), Some(verified_token.logo_uri.clone()))
                             } else {
@@ -948,7 +1206,7 @@ pub fn WalletView() -> Element {
                             // Determine icon to use - prioritize real icons from metadata
                             let icon_type = if let Some(icon_url) = icon_url {
                                 if !icon_url.is_empty() {
-                                    icon_url  // Use real icon from Jupiter Token API
+                                    icon_url  // Use real icon from token metadata
                                 } else {
                                     get_fallback_icon(&symbol)  // Use fallback for empty URLs
                                 }
@@ -1042,8 +1300,60 @@ pub fn WalletView() -> Element {
         });
     });
 
+    // Push SOL and token balance changes as they land instead of polling
+    use_effect(move || {
+        let address = if hardware_connected() && hardware_pubkey().is_some() {
+            hardware_pubkey()
+        } else {
+            wallets.read().get(current_wallet_index()).map(|wallet| wallet.address.clone())
+        };
+        let rpc_url = custom_rpc();
+        let _ = active_network();
+
+        if let Some(task) = balance_subscription.write().take() {
+            task.cancel();
+        }
+        live_balances.set(false);
+        let Some(address) = address else { return };
+
+        let task = spawn(async move {
+            rpc::subscribe_wallet(
+                address,
+                rpc_url,
+                move |update| match update {
+                    rpc::AccountUpdate::Sol(sol_balance) => {
+                        balance.set(sol_balance);
+                        if let Some(sol) = tokens.write().iter_mut().find(|t| t.symbol == "SOL") {
+                            sol.balance = sol_balance;
+                            sol.value_usd = sol_balance * sol.price;
+                        }
+                    }
+                    rpc::AccountUpdate::Token { mint, amount, .. } => {
+                        let updated = match tokens.write().iter_mut().find(|t| t.mint == mint) {
+                            Some(token) if amount > 0.0 => {
+                                token.balance = amount;
+                                token.value_usd = amount * token.price;
+                                true
+                            }
+                            _ => false,
+                        };
+                        // New or emptied token: reload the list for metadata and prices
+                        if !updated {
+                            refresh_trigger.set(refresh_trigger() + 1);
+                        }
+                    }
+                },
+                move |live| live_balances.set(live),
+            )
+            .await;
+        });
+        balance_subscription.set(Some(task));
+    });
+
     use_effect(move || {
         spawn(async move {
+            crate::startup::after_first_frame().await;
+
             // Initialize currency system
             initialize_currency_system().await;
             
@@ -1052,6 +1362,18 @@ pub fn WalletView() -> Element {
         });
     });
 
+    // Value collectibles at their collection floors whenever they change
+    use_effect(move || {
+        let held = collectibles();
+        if held.is_empty() || !active_network().is_mainnet() {
+            nft_valuation.set(None);
+            return;
+        }
+        spawn(async move {
+            nft_valuation.set(Some(crate::nft_valuation::value_collectibles(&held).await));
+        });
+    });
+
     use_effect(move || {
         if active_tab() == "collectibles" && collectibles().is_empty() && !collectibles_loading() {
             collectibles_loading.set(true);
@@ -1144,6 +1466,45 @@ pub fn WalletView() -> Element {
                 }
             },
             
+            UpdateBanner {}
+
+            if !active_network().is_mainnet() {
+                div {
+                    class: "warning-message network-banner",
+                    "⚠️ {active_network().label()} — balances and transactions here are not on mainnet"
+                    if active_network().supports_airdrop() {
+                        button {
+                            class: "button-standard ghost",
+                            disabled: airdropping(),
+                            onclick: move |_| {
+                                let address = if hardware_connected() && hardware_pubkey().is_some() {
+                                    hardware_pubkey()
+                                } else {
+                                    wallets.read().get(current_wallet_index()).map(|wallet| wallet.address.clone())
+                                };
+                                let Some(address) = address else { return };
+                                let rpc_url = custom_rpc();
+                                airdropping.set(true);
+                                spawn(async move {
+                                    let sol = crate::config::network::AIRDROP_SOL;
+                                    match rpc::request_airdrop(&address, sol, rpc_url.as_deref()).await {
+                                        Ok(signature) => {
+                                            println!("🚰 Airdropped {} SOL: {}", sol, signature);
+                                            // Give the faucet transfer a moment to land
+                                            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+                                            refresh_trigger.set(refresh_trigger() + 1);
+                                        }
+                                        Err(e) => println!("❌ {}", e),
+                                    }
+                                    airdropping.set(false);
+                                });
+                            },
+                            if airdropping() { "Requesting..." } else { "Airdrop 1 SOL" }
+                        }
+                    }
+                }
+            }
+
             // Header
             div {
                 class: "wallet-header-enhanced",
@@ -1313,38 +1674,62 @@ pub fn WalletView() -> Element {
                         
                         div { class: "dropdown-divider" }
                         
-                        for (index, wallet) in wallets.read().iter().enumerate() {
-                            button {
-                                class: if index == current_wallet_index() { 
-                                    "dropdown-item wallet-list-item active" 
-                                } else { 
-                                    "dropdown-item wallet-list-item" 
-                                },
-                                onclick: move |_| {
-                                    current_wallet_index.set(index);
-                                    show_dropdown.set(false);
-                                    hardware_connected.set(false);
-                                    hardware_pubkey.set(None);
-                                },
-                                div {
-                                    class: "dropdown-icon",
-                                    img {
-                                        src: "{ICON_WALLET}",
-                                        alt: "Wallet",
-                                        style: "width: 24px; height: 24px;"
-                                    }
-                                }
-                                div {
-                                    class: "wallet-info",
-                                    div { class: "wallet-name", "{wallet.name}" }
-                                    div { 
-                                        class: "wallet-address",
-                                        {
-                                            let addr = &wallet.address;
-                                            if addr.len() >= 8 {
-                                                format!("{}...{}", &addr[..4], &addr[addr.len()-4..])
-                                            } else {
-                                                addr.clone()
+                        for folder in crate::wallet_groups::organize(&wallets.read(), &wallet_profiles.read()) {
+                            if !folder.name.is_empty() {
+                                div { class: "dropdown-folder-label", "📁 {folder.name}" }
+                            }
+                            for index in folder.indices {
+                                {
+                                    let wallet = wallets.read()[index].clone();
+                                    let profile = wallet_profiles.read().iter().find(|p| p.address == wallet.address).cloned().unwrap_or_default();
+                                    let accent = profile.color.map(|c| format!("border-left: 3px solid {};", c)).unwrap_or_default();
+                                    rsx! {
+                                        button {
+                                            key: "{wallet.address}",
+                                            class: if index == current_wallet_index() { 
+                                                "dropdown-item wallet-list-item active" 
+                                            } else { 
+                                                "dropdown-item wallet-list-item" 
+                                            },
+                                            style: "{accent}",
+                                            onclick: move |_| {
+                                                current_wallet_index.set(index);
+                                                show_dropdown.set(false);
+                                                hardware_connected.set(false);
+                                                hardware_pubkey.set(None);
+                                            },
+                                            div {
+                                                class: "dropdown-icon",
+                                                if profile.emoji.is_empty() {
+                                                    img {
+                                                        src: "{ICON_WALLET}",
+                                                        alt: "Wallet",
+                                                        style: "width: 24px; height: 24px;"
+                                                    }
+                                                } else {
+                                                    span { class: "wallet-emoji", "{profile.emoji}" }
+                                                }
+                                            }
+                                            div {
+                                                class: "wallet-info",
+                                                div {
+                                                    class: "wallet-name",
+                                                    "{wallet.name}"
+                                                    if wallet.passphrase_protected {
+                                                        span { title: "Protected by a recovery passphrase", " 🔏" }
+                                                    }
+                                                }
+                                                div { 
+                                                    class: "wallet-address",
+                                                    {
+                                                        let addr = &wallet.address;
+                                                        if addr.len() >= 8 {
+                                                            format!("{}...{}", &addr[..4], &addr[addr.len()-4..])
+                                                        } else {
+                                                            addr.clone()
+                                                        }
+                                                    }
+                                                }
                                             }
                                         }
                                     }
@@ -1363,93 +1748,523 @@ pub fn WalletView() -> Element {
                             },
                             div {
                                 class: "dropdown-icon action-icon",
-                                "💱"
-                            }
-                            div {
-                                class: "currency-display",
-                                "Currency: "
-                                span {
-                                    class: "current-symbol",
-                                    "{get_current_currency_code()}"
-                                }
+                                "💱"
+                            }
+                            div {
+                                class: "currency-display",
+                                "Currency: "
+                                span {
+                                    class: "current-symbol",
+                                    "{get_current_currency_code()}"
+                                }
+                            }
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_price_providers_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "📈"
+                            }
+                            "Price Providers"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_swap_providers_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "🔀"
+                            }
+                            "Swap Providers"
+                        }
+                        
+                        div { class: "dropdown-divider" }
+                        
+                        // Existing action buttons (unchanged)
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                modal_mode.set("create".to_string());
+                                show_wallet_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                img {
+                                    src: "{ICON_CREATE}",
+                                    alt: "Import",
+                                    style: "width: 24px; height: 24px;"
+                                }
+                            }
+                            "Create Wallet"
+                        }
+                        
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                modal_mode.set("import".to_string());
+                                show_wallet_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                img {
+                                    src: "{ICON_IMPORT}",
+                                    alt: "Import",
+                                    style: "width: 24px; height: 24px;"
+                                }
+                            }
+                            "Import Wallet"
+                        }
+
+                        if current_wallet.is_some() && !hardware_connected() {
+                            button {
+                                class: "dropdown-item",
+                                onclick: move |_| {
+                                    show_export_modal.set(true);
+                                    show_dropdown.set(false);
+                                },
+                                div {
+                                    class: "dropdown-icon action-icon",
+                                    img {
+                                        src: "{ICON_EXPORT}",
+                                        alt: "Export",
+                                        style: "width: 24px; height: 24px;"
+                                    }
+                                }
+                                "Export Wallet"
+                            }
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_backup_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "💾"
+                            }
+                            "Backup"
+                        }
+
+                        if current_wallet.is_some() && !hardware_connected() {
+                            button {
+                                class: "dropdown-item",
+                                onclick: move |_| {
+                                    show_wallet_profile_modal.set(true);
+                                    show_dropdown.set(false);
+                                },
+                                div {
+                                    class: "dropdown-icon action-icon",
+                                    "🏷️"
+                                }
+                                "Notes & Risk Tier"
+                            }
+                        }
+
+                        if wallets.read().len() > 1 {
+                            button {
+                                class: "dropdown-item",
+                                onclick: move |_| {
+                                    show_organize_wallets_modal.set(true);
+                                    show_dropdown.set(false);
+                                },
+                                div {
+                                    class: "dropdown-icon action-icon",
+                                    "🗂️"
+                                }
+                                "Organize Wallets"
+                            }
+                        }
+
+                        if let Some(wallet_info) = current_wallet.clone() {
+                            if !hardware_connected() && !wallet_info.encrypted_key.is_empty() && crate::signing::passkey::platform_authenticator().is_some() {
+                                button {
+                                    class: "dropdown-item",
+                                    onclick: move |_| {
+                                        show_dropdown.set(false);
+                                        let wallet_info = wallet_info.clone();
+                                        spawn(async move {
+                                            match crate::signing::passkey::protect_wallet(&wallet_info).await {
+                                                Ok(()) => {
+                                                    println!("🔐 Wallet {} protected with passkey", wallet_info.address);
+                                                    wallets.set(load_wallets_from_storage());
+                                                }
+                                                Err(e) => println!("❌ Failed to protect wallet with passkey: {}", e),
+                                            }
+                                        });
+                                    },
+                                    div {
+                                        class: "dropdown-icon action-icon",
+                                        "🔐"
+                                    }
+                                    "Protect with Passkey"
+                                }
+                            }
+                        }
+
+                        if crate::storage::has_pin() && !crate::pin::is_duress_session() && crate::signing::passkey::platform_authenticator().is_some() {
+                            button {
+                                class: "dropdown-item",
+                                onclick: move |_| {
+                                    show_dropdown.set(false);
+                                    if biometric_enabled() {
+                                        match crate::biometric::disable() {
+                                            Ok(()) => biometric_enabled.set(false),
+                                            Err(e) => println!("❌ Failed to turn off biometric unlock: {}", e),
+                                        }
+                                    } else {
+                                        spawn(async move {
+                                            match crate::biometric::enable().await {
+                                                Ok(()) => {
+                                                    println!("🔐 Biometric unlock turned on");
+                                                    biometric_enabled.set(true);
+                                                }
+                                                Err(e) => println!("❌ Failed to turn on biometric unlock: {}", e),
+                                            }
+                                        });
+                                    }
+                                },
+                                div {
+                                    class: "dropdown-icon action-icon",
+                                    "👆"
+                                }
+                                if biometric_enabled() { "Turn Off Biometric Unlock" } else { "Unlock with Biometrics" }
+                            }
+                        }
+
+                        if crate::storage::has_pin() && !crate::pin::is_duress_session() {
+                            button {
+                                class: "dropdown-item",
+                                onclick: move |_| {
+                                    show_pin_security_modal.set(true);
+                                    show_dropdown.set(false);
+                                },
+                                div {
+                                    class: "dropdown-icon action-icon",
+                                    "🔢"
+                                }
+                                "PIN Security"
+                            }
+
+                            button {
+                                class: "dropdown-item",
+                                onclick: move |_| {
+                                    show_duress_pin_modal.set(true);
+                                    show_dropdown.set(false);
+                                },
+                                div {
+                                    class: "dropdown-icon action-icon",
+                                    "🎭"
+                                }
+                                "Duress PIN"
+                            }
+                        }
+
+                        // NEW: Delete Wallet button (only show if there's a current wallet and not hardware)
+                        if current_wallet.is_some() && !hardware_connected() {
+                            button {
+                                class: "dropdown-item delete-item",
+                                onclick: move |_| {
+                                    show_delete_confirmation.set(true);
+                                    show_dropdown.set(false);
+                                },
+                                div {
+                                    class: "dropdown-icon action-icon danger",
+                                    img {
+                                        src: "{ICON_DELETE}",
+                                        alt: "Delete",
+                                        style: "width: 24px; height: 24px;"
+                                    }
+                                }
+                                "Delete Wallet"
+                            }
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_partial_sign_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "✍️"
+                            }
+                            "Co-sign Transaction"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_sign_message_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "🖋️"
+                            }
+                            "Sign Message"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_device_transfer_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "📲"
+                            }
+                            "Device Transfer (QR)"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_session_key_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "🔑"
+                            }
+                            "Session Key"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_historical_balances_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "🕰️"
+                            }
+                            "Balances on Date"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_cost_basis_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "📒"
+                            }
+                            "Cost Basis & P&L"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_limit_orders_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "🎯"
+                            }
+                            "Limit Orders"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_dca_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "📅"
+                            }
+                            "DCA"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_swap_history_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "🧾"
+                            }
+                            "Swap History"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_stake_positions_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "🥩"
+                            }
+                            "Stake Positions"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_staking_rewards_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "📈"
+                            }
+                            "Staking Rewards"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_liquid_staking_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "💧"
+                            }
+                            "Liquid Staking"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_spread_stake_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "🧩"
+                            }
+                            "Spread Stake"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_notifications_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "📣"
+                            }
+                            "Critical Alerts"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_outbox_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "📤"
+                            }
+                            "Outbox"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_composer_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "🧱"
+                            }
+                            "Transaction Composer"
+                        }
+
+                        button {
+                            class: "dropdown-item",
+                            onclick: move |_| {
+                                show_budget_modal.set(true);
+                                show_dropdown.set(false);
+                            },
+                            div {
+                                class: "dropdown-icon action-icon",
+                                "🎯"
                             }
+                            "Goals & Budgets"
                         }
-                        
-                        div { class: "dropdown-divider" }
-                        
-                        // Existing action buttons (unchanged)
+
                         button {
                             class: "dropdown-item",
                             onclick: move |_| {
-                                modal_mode.set("create".to_string());
-                                show_wallet_modal.set(true);
+                                show_broadcast_modal.set(true);
                                 show_dropdown.set(false);
                             },
                             div {
                                 class: "dropdown-icon action-icon",
-                                img {
-                                    src: "{ICON_CREATE}",
-                                    alt: "Import",
-                                    style: "width: 24px; height: 24px;"
-                                }
+                                "📡"
                             }
-                            "Create Wallet"
+                            "Broadcast Transaction"
                         }
-                        
+
                         button {
                             class: "dropdown-item",
                             onclick: move |_| {
-                                modal_mode.set("import".to_string());
-                                show_wallet_modal.set(true);
+                                show_multi_transfer_modal.set(true);
                                 show_dropdown.set(false);
                             },
                             div {
                                 class: "dropdown-icon action-icon",
-                                img {
-                                    src: "{ICON_IMPORT}",
-                                    alt: "Import",
-                                    style: "width: 24px; height: 24px;"
-                                }
+                                "👥"
                             }
-                            "Import Wallet"
+                            "Multi-Recipient Send"
                         }
 
-                        if current_wallet.is_some() && !hardware_connected() {
+                        if !hardware_connected() {
                             button {
                                 class: "dropdown-item",
                                 onclick: move |_| {
-                                    show_export_modal.set(true);
+                                    show_shared_wallet_modal.set(true);
                                     show_dropdown.set(false);
                                 },
                                 div {
                                     class: "dropdown-icon action-icon",
-                                    img {
-                                        src: "{ICON_EXPORT}",
-                                        alt: "Export",
-                                        style: "width: 24px; height: 24px;"
-                                    }
+                                    "🤝"
                                 }
-                                "Export Wallet"
+                                "Paired Device"
                             }
                         }
 
-                        // NEW: Delete Wallet button (only show if there's a current wallet and not hardware)
-                        if current_wallet.is_some() && !hardware_connected() {
+                        if hardware_connected() && matches!(hardware_device_type(), Some(HardwareDeviceType::ESP32)) {
                             button {
-                                class: "dropdown-item delete-item",
+                                class: "dropdown-item",
                                 onclick: move |_| {
-                                    show_delete_confirmation.set(true);
+                                    show_address_book_modal.set(true);
                                     show_dropdown.set(false);
                                 },
                                 div {
-                                    class: "dropdown-icon action-icon danger",
-                                    img {
-                                        src: "{ICON_DELETE}",
-                                        alt: "Delete",
-                                        style: "width: 24px; height: 24px;"
-                                    }
+                                    class: "dropdown-icon action-icon",
+                                    "📇"
                                 }
-                                "Delete Wallet"
+                                "Device Address Book"
                             }
                         }
                         
@@ -1537,6 +2352,44 @@ pub fn WalletView() -> Element {
                 }
             }
 
+            if show_backup_modal() {
+                BackupModal {
+                    onclose: move |_| show_backup_modal.set(false)
+                }
+            }
+
+            if show_wallet_profile_modal() {
+                if let Some(wallet) = wallets.read().get(current_wallet_index()).cloned() {
+                    WalletProfileModal {
+                        wallet: wallet,
+                        onclose: move |_| show_wallet_profile_modal.set(false),
+                    }
+                }
+            }
+
+            if show_organize_wallets_modal() {
+                OrganizeWalletsModal {
+                    wallets: wallets(),
+                    onclose: move |_| {
+                        wallet_profiles.set(crate::storage::load_wallet_profiles());
+                        show_organize_wallets_modal.set(false);
+                    },
+                }
+            }
+
+            if show_pin_security_modal() {
+                PinSecurityModal {
+                    onclose: move |_| show_pin_security_modal.set(false),
+                }
+            }
+
+            if show_duress_pin_modal() {
+                DuressPinModal {
+                    wallets: wallets(),
+                    onclose: move |_| show_duress_pin_modal.set(false),
+                }
+            }
+
             // Delete Wallet Confirmation Modal  
             if show_delete_confirmation() {
                 DeleteWalletModal {
@@ -1586,6 +2439,14 @@ pub fn WalletView() -> Element {
                             save_rpc_to_storage(&new_rpc);
                         }
                         show_rpc_modal.set(false);
+
+                        let network = crate::config::network::active();
+                        if network != active_network() {
+                            // Nothing from the previous cluster carries over
+                            tokens.set(vec![]);
+                            collectibles.set(vec![]);
+                            active_network.set(network);
+                        }
                         
                         if let Some(wallet) = wallets.read().get(current_wallet_index()) {
                             let address = wallet.address.clone();
@@ -1664,7 +2525,7 @@ pub fn WalletView() -> Element {
                     onsuccess: move |_| {
                         show_send_modal.set(false);
                         // Don't reset hardware_wallet here either
-                        if let Some(wallet) = wallets.read().get(current_wallet_index()) {
+                        if let Some(wallet) = wallets.read().get(current_wallet_index()).filter(|_| !live_balances()) {
                             let address = wallet.address.clone();
                             let rpc_url = custom_rpc();
                             
@@ -1732,7 +2593,7 @@ pub fn WalletView() -> Element {
                         println!("Token transaction successful: {}", signature);
                         
                         // Refresh balances after successful transaction
-                        if let Some(wallet) = wallets.read().get(current_wallet_index()) {
+                        if let Some(wallet) = wallets.read().get(current_wallet_index()).filter(|_| !live_balances()) {
                             let address = wallet.address.clone();
                             let rpc_url = custom_rpc();
                             
@@ -1780,7 +2641,7 @@ pub fn WalletView() -> Element {
                         println!("Bulk send transaction successful: {}", signature);
                         
                         // Refresh balances after successful transaction
-                        if let Some(wallet) = wallets.read().get(current_wallet_index()) {
+                        if let Some(wallet) = wallets.read().get(current_wallet_index()).filter(|_| !live_balances()) {
                             let address = wallet.address.clone();
                             let rpc_url = custom_rpc();
                             
@@ -1819,7 +2680,7 @@ pub fn WalletView() -> Element {
                         println!("EJECT transaction successful: {}", signature);
 
                         // Refresh balances after successful transaction
-                        if let Some(wallet) = wallets.read().get(current_wallet_index()) {
+                        if let Some(wallet) = wallets.read().get(current_wallet_index()).filter(|_| !live_balances()) {
                             let address = wallet.address.clone();
                             let rpc_url = custom_rpc();
 
@@ -1858,7 +2719,7 @@ pub fn WalletView() -> Element {
                     onsuccess: move |_| {
                         show_stake_modal.set(false);
                         // Refresh balance after staking
-                        if let Some(wallet) = wallets.read().get(current_wallet_index()) {
+                        if let Some(wallet) = wallets.read().get(current_wallet_index()).filter(|_| !live_balances()) {
                             let address = wallet.address.clone();
                             let rpc_url = custom_rpc();
                             
@@ -1893,6 +2754,73 @@ pub fn WalletView() -> Element {
                 }
             }
 
+            if show_limit_orders_modal() {
+                LimitOrdersModal {
+                    tokens: tokens(),
+                    address: full_address.clone(),
+                    wallet: current_wallet.clone(),
+                    hardware_wallet: hardware_wallet(),
+                    onclose: move |_| show_limit_orders_modal.set(false),
+                }
+            }
+
+            if show_dca_modal() {
+                DcaModal {
+                    tokens: tokens(),
+                    address: full_address.clone(),
+                    wallet: current_wallet.clone(),
+                    hardware_wallet: hardware_wallet(),
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_dca_modal.set(false),
+                }
+            }
+
+            if show_swap_history_modal() {
+                SwapHistoryModal {
+                    address: full_address.clone(),
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_swap_history_modal.set(false),
+                }
+            }
+
+            if show_stake_positions_modal() {
+                StakePositionsModal {
+                    address: full_address.clone(),
+                    wallet: current_wallet.clone(),
+                    hardware_wallet: hardware_wallet(),
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_stake_positions_modal.set(false),
+                }
+            }
+
+            if show_staking_rewards_modal() {
+                StakingRewardsModal {
+                    address: full_address.clone(),
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_staking_rewards_modal.set(false),
+                }
+            }
+
+            if show_liquid_staking_modal() {
+                LiquidStakingModal {
+                    tokens: tokens(),
+                    wallet: current_wallet.clone(),
+                    hardware_wallet: hardware_wallet(),
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_liquid_staking_modal.set(false),
+                }
+            }
+
+            if show_spread_stake_modal() {
+                SpreadStakeModal {
+                    tokens: tokens(),
+                    wallet: current_wallet.clone(),
+                    hardware_wallet: hardware_wallet(),
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_spread_stake_modal.set(false),
+                }
+            }
+
             if show_lend_modal() {
                 LendModal {
                     tokens: tokens(),
@@ -1906,7 +2834,7 @@ pub fn WalletView() -> Element {
                             println!("✅ Lend completed with signature: {}", signature);
                             show_lend_modal.set(false);
                             // Refresh balances after successful lend
-                            if let Some(wallet) = wallet_for_refresh.clone() {
+                            if let Some(wallet) = wallet_for_refresh.clone().filter(|_| !live_balances()) {
                                 let address = wallet.address.clone();
                                 let rpc_url = custom_rpc();
                                 
@@ -1966,6 +2894,145 @@ pub fn WalletView() -> Element {
                     onclose: move |_| show_quantum_vault_modal.set(false),
                 }
             }
+
+            if show_address_book_modal() {
+                if let Some(hw) = hardware_wallet() {
+                    AddressBookModal {
+                        hardware_wallet: hw,
+                        onclose: move |_| show_address_book_modal.set(false),
+                    }
+                }
+            }
+
+            if show_partial_sign_modal() {
+                PartialSignModal {
+                    wallet: wallets().get(current_wallet_index()).cloned(),
+                    hardware_wallet: hardware_wallet(),
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_partial_sign_modal.set(false),
+                }
+            }
+
+            if show_session_key_modal() {
+                if let Some(owner_address) = hardware_pubkey().filter(|_| hardware_connected()).or_else(|| wallets().get(current_wallet_index()).map(|w| w.address.clone())) {
+                    SessionKeyModal {
+                        wallet: wallets().get(current_wallet_index()).cloned(),
+                        hardware_wallet: hardware_wallet(),
+                        owner_address,
+                        custom_rpc: custom_rpc(),
+                        onclose: move |_| show_session_key_modal.set(false),
+                    }
+                }
+            }
+
+            if show_historical_balances_modal() {
+                HistoricalBalancesModal {
+                    wallets: wallets(),
+                    hardware_address: hardware_pubkey().filter(|_| hardware_connected()),
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_historical_balances_modal.set(false),
+                }
+            }
+
+            if show_cost_basis_modal() {
+                CostBasisModal {
+                    address: full_address.clone(),
+                    custom_rpc: custom_rpc(),
+                    usd_prices: tokens().iter().map(|token| (token.mint.clone(), token.price)).collect(),
+                    oncomputed: move |report| cost_basis_report.set(Some(report)),
+                    onclose: move |_| show_cost_basis_modal.set(false),
+                }
+            }
+
+            if show_notifications_modal() {
+                NotificationsModal {
+                    onclose: move |_| show_notifications_modal.set(false),
+                }
+            }
+
+            if show_outbox_modal() {
+                OutboxModal {
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_outbox_modal.set(false),
+                }
+            }
+
+            if show_composer_modal() {
+                if let Some(owner_address) = hardware_pubkey().filter(|_| hardware_connected()).or_else(|| wallets().get(current_wallet_index()).map(|w| w.address.clone())) {
+                    ComposerModal {
+                        wallet: wallets().get(current_wallet_index()).cloned(),
+                        hardware_wallet: hardware_wallet(),
+                        owner_address,
+                        custom_rpc: custom_rpc(),
+                        onclose: move |_| show_composer_modal.set(false),
+                    }
+                }
+            }
+
+            if show_budget_modal() {
+                if let Some(address) = hardware_pubkey().filter(|_| hardware_connected()).or_else(|| wallets().get(current_wallet_index()).map(|w| w.address.clone())) {
+                    BudgetModal {
+                        address,
+                        onclose: move |_| {
+                            show_budget_modal.set(false);
+                            budget_revision.set(budget_revision() + 1);
+                        },
+                    }
+                }
+            }
+
+            if show_broadcast_modal() {
+                BroadcastModal {
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_broadcast_modal.set(false),
+                }
+            }
+
+            if show_multi_transfer_modal() {
+                if let Some(owner_address) = hardware_pubkey().filter(|_| hardware_connected()).or_else(|| wallets().get(current_wallet_index()).map(|w| w.address.clone())) {
+                    MultiTransferModal {
+                        tokens: tokens(),
+                        wallet: wallets().get(current_wallet_index()).cloned(),
+                        hardware_wallet: hardware_wallet(),
+                        owner_address,
+                        custom_rpc: custom_rpc(),
+                        onclose: move |_| show_multi_transfer_modal.set(false),
+                    }
+                }
+            }
+
+            if show_device_transfer_modal() {
+                DeviceTransferModal {
+                    wallet: wallets().get(current_wallet_index()).cloned(),
+                    onclose: move |_| show_device_transfer_modal.set(false),
+                }
+            }
+
+            if show_sign_message_modal() {
+                SignMessageModal {
+                    wallet: wallets().get(current_wallet_index()).cloned(),
+                    hardware_wallet: hardware_wallet(),
+                    onclose: move |_| show_sign_message_modal.set(false),
+                }
+            }
+
+            if show_shared_wallet_modal() {
+                SharedWalletModal {
+                    onclose: move |_| {
+                        show_shared_wallet_modal.set(false);
+                        wallets.set(load_wallets_from_storage());
+                    },
+                }
+            }
+
+            if show_streaming_modal() {
+                StreamingModal {
+                    wallet: wallets().get(current_wallet_index()).cloned(),
+                    hardware_wallet: hardware_wallet(),
+                    custom_rpc: custom_rpc(),
+                    onclose: move |_| show_streaming_modal.set(false),
+                }
+            }
             
             if show_background_modal() {
                 BackgroundModal {
@@ -1983,6 +3050,22 @@ pub fn WalletView() -> Element {
                     onclose: move |_| show_currency_modal.set(false)
                 }
             }
+
+            if show_price_providers_modal() {
+                PriceProvidersModal {
+                    onclose: move |_| {
+                        show_price_providers_modal.set(false);
+                        // Refetch prices in the new provider order
+                        refresh_trigger.set(refresh_trigger() + 1);
+                    }
+                }
+            }
+
+            if show_swap_providers_modal() {
+                SwapProvidersModal {
+                    onclose: move |_| show_swap_providers_modal.set(false),
+                }
+            }
                                     
             // Main content container for balance, address, and actions
             div {
@@ -2006,11 +3089,84 @@ pub fn WalletView() -> Element {
                             } else {
                                 // Calculate total portfolio value (sum of all token values) and round to nearest dollar
                                 {
-                                    let total_value = tokens.read().iter().fold(0.0, |acc, token| acc + token.value_usd);
+                                    let nft_value = nft_valuation.read().as_ref().map_or(0.0, |v| v.total_sol()) * sol_price();
+                                    let total_value = tokens.read().iter().fold(0.0, |acc, token| acc + token.value_usd) + nft_value;
                                     format_portfolio_balance(total_value)
                                 }
                             }
                         }
+
+                        if let Some(valuation) = nft_valuation().filter(|v| v.total_sol() > 0.0) {
+                            {
+                                let nft_label = format!(
+                                    "Includes {} in NFTs at floor ({} items)",
+                                    format_price_in_selected_currency(valuation.total_sol() * sol_price()),
+                                    valuation.valued_items()
+                                );
+                                rsx! {
+                                    div {
+                                        class: "nft-value",
+                                        onclick: move |_| show_nft_breakdown.set(!show_nft_breakdown()),
+                                        "{nft_label}"
+                                    }
+                                    if show_nft_breakdown() {
+                                        div {
+                                            class: "nft-value-breakdown",
+                                            for collection in valuation.collections.iter() {
+                                                {
+                                                    let line = match collection.value_sol() {
+                                                        Some(value) => format!(
+                                                            "{} × {} @ {:.2} SOL = {}",
+                                                            collection.items,
+                                                            collection.collection,
+                                                            collection.floor_sol.unwrap_or_default(),
+                                                            format_price_in_selected_currency(value * sol_price())
+                                                        ),
+                                                        None if !collection.verified => format!("{} × {} (unverified, not counted)", collection.items, collection.collection),
+                                                        None => format!("{} × {} (no floor price)", collection.items, collection.collection),
+                                                    };
+                                                    rsx! {
+                                                        div { key: "{collection.collection}-{collection.verified}", "{line}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        if dca_due() > 0 {
+                            div {
+                                class: "dca-due",
+                                onclick: move |_| show_dca_modal.set(true),
+                                "📅 {dca_due} DCA buy(s) due, tap to confirm"
+                            }
+                        }
+
+                        if current_wallet.is_some() || hardware_connected() {
+                            WrappedSolCleanup {
+                                key: "{full_address}",
+                                address: full_address.clone(),
+                                wallet: current_wallet.clone(),
+                                hardware_wallet: hardware_wallet(),
+                                custom_rpc: custom_rpc(),
+                                watch: false,
+                            }
+                        }
+
+                        {
+                            let sources = prices::active_price_sources();
+                            let label = format!("Prices via {}", sources.join(", "));
+                            rsx! {
+                                if !sources.is_empty() {
+                                    div {
+                                        class: "price-source",
+                                        "{label}"
+                                    }
+                                }
+                            }
+                        }
                     }
                     
                     // Right side - Device/Wallet indicator
@@ -2075,6 +3231,14 @@ pub fn WalletView() -> Element {
                     }
                 }
                 
+                if let Some(address) = hardware_pubkey().filter(|_| hardware_connected()).or_else(|| wallets().get(current_wallet_index()).map(|w| w.address.clone())) {
+                    BudgetOverlay {
+                        address,
+                        custom_rpc: custom_rpc(),
+                        refresh: refresh_trigger() + budget_revision(),
+                    }
+                }
+
                 // Replace the current action-buttons div with this segmented version
                 div {
                     class: "action-buttons-segmented",
@@ -2314,6 +3478,24 @@ pub fn WalletView() -> Element {
                                 }
                             }
 
+                            button {
+                                class: "action-button-segmented",
+                                onclick: move |_| {
+                                    println!("Streams button clicked!");
+                                    show_streaming_modal.set(true);
+                                },
+
+                                div {
+                                    class: "action-icon-segmented",
+                                    "🌊"
+                                }
+
+                                div {
+                                    class: "action-label-segmented",
+                                    "Streams"
+                                }
+                            }
+
                             button {
                                 class: "action-button-segmented",
                                 onclick: move |_| {
@@ -2421,6 +3603,18 @@ pub fn WalletView() -> Element {
                                     let token_price = token.price;
                                     let token_balance = token.balance;
                                     let token_value_usd = token.value_usd;
+                                    let token_pnl = cost_basis_report
+                                        .read()
+                                        .as_ref()
+                                        .and_then(|report| report.position(&token_mint).cloned())
+                                        .filter(|position| position.cost_usd > 0.0 && token_price > 0.0)
+                                        .map(|position| {
+                                            let unrealized = position.unrealized_usd(token_price);
+                                            let percentage = position.unrealized_percentage(token_price).unwrap_or(0.0);
+                                            let class = if unrealized >= 0.0 { "token-pnl positive" } else { "token-pnl negative" };
+                                            let sign = if unrealized >= 0.0 { "+" } else { "-" };
+                                            (class, format!("{}{} ({})", sign, format_price_in_selected_currency(unrealized.abs()), format_percentage_change(percentage)))
+                                        });
                                     
                                     rsx! {
                                         div {
@@ -2590,6 +3784,13 @@ pub fn WalletView() -> Element {
                                                         class: "token-amount",
                                                         "{format_token_amount(token_balance, &token_symbol)}"
                                                     }
+                                                    if let Some((pnl_class, pnl_label)) = token_pnl.clone() {
+                                                        div {
+                                                            class: "{pnl_class}",
+                                                            title: "Unrealized P&L against your average cost",
+                                                            "{pnl_label}"
+                                                        }
+                                                    }
                                                 }
                                             }
                                             
@@ -2613,40 +3814,36 @@ pub fn WalletView() -> Element {
                                                             div {
                                                                 class: "chart-timeframe-selector",
                                                                 
+                                                                for timeframe in prices::CHART_TIMEFRAMES.iter().copied() {
+                                                                    button {
+                                                                        key: "{timeframe}",
+                                                                        class: if timeframe_for_buttons == timeframe { "timeframe-btn active" } else { "timeframe-btn" },
+                                                                        onclick: {
+                                                                            let symbol_clone = token_symbol.clone();
+                                                                            move |_| {
+                                                                                let mut timeframes = selected_timeframe();
+                                                                                timeframes.insert(symbol_clone.clone(), timeframe.to_string());
+                                                                                selected_timeframe.set(timeframes);
+                                                                                
+                                                                                let symbol_for_fetch = symbol_clone.clone();
+                                                                                spawn(async move {
+                                                                                    fetch_chart_data_with_timeframe(symbol_for_fetch, timeframe.to_string(), chart_data, chart_loading).await;
+                                                                                });
+                                                                            }
+                                                                        },
+                                                                        "{timeframe}"
+                                                                    }
+                                                                }
+
                                                                 button {
-                                                                    class: if timeframe_for_buttons == "1H" { "timeframe-btn active" } else { "timeframe-btn" },
-                                                                    onclick: {
-                                                                        let symbol_clone = token_symbol.clone();
-                                                                        move |_| {
-                                                                            let mut timeframes = selected_timeframe();
-                                                                            timeframes.insert(symbol_clone.clone(), "1H".to_string());
-                                                                            selected_timeframe.set(timeframes);
-                                                                            
-                                                                            let symbol_for_fetch = symbol_clone.clone();
-                                                                            spawn(async move {
-                                                                                fetch_chart_data_with_timeframe(symbol_for_fetch, "1H".to_string(), chart_data, chart_loading).await;
-                                                                            });
-                                                                        }
-                                                                    },
-                                                                    "1H"
+                                                                    class: if chart_moving_average() { "timeframe-btn active" } else { "timeframe-btn" },
+                                                                    onclick: move |_| chart_moving_average.set(!chart_moving_average()),
+                                                                    "MA"
                                                                 }
-                                                                
                                                                 button {
-                                                                    class: if timeframe_for_buttons == "1D" { "timeframe-btn active" } else { "timeframe-btn" },
-                                                                    onclick: {
-                                                                        let symbol_clone = token_symbol.clone();
-                                                                        move |_| {
-                                                                            let mut timeframes = selected_timeframe();
-                                                                            timeframes.insert(symbol_clone.clone(), "1D".to_string());
-                                                                            selected_timeframe.set(timeframes);
-                                                                            
-                                                                            let symbol_for_fetch = symbol_clone.clone();
-                                                                            spawn(async move {
-                                                                                fetch_chart_data_with_timeframe(symbol_for_fetch, "1D".to_string(), chart_data, chart_loading).await;
-                                                                            });
-                                                                        }
-                                                                    },
-                                                                    "1D"
+                                                                    class: if chart_rsi() { "timeframe-btn active" } else { "timeframe-btn" },
+                                                                    onclick: move |_| chart_rsi.set(!chart_rsi()),
+                                                                    "RSI"
                                                                 }
                                                             }
                                                             
@@ -2664,6 +3861,8 @@ pub fn WalletView() -> Element {
                                                                         data: candlesticks,
                                                                         symbol: token_symbol.clone(),
                                                                         timeframe: timeframe_for_chart,
+                                                                        show_moving_average: chart_moving_average(),
+                                                                        show_rsi: chart_rsi(),
                                                                     }
                                                                 }
                                                             }
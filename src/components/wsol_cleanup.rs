@@ -0,0 +1,103 @@
+// src/components/wsol_cleanup.rs
+use dioxus::prelude::*;
+use crate::hardware::HardwareWallet;
+use crate::rpc::TokenAccountInfo;
+use crate::signing::active_wallet_signer;
+use crate::swap::wsol::{find_wrapped_sol, unwrap_all, wrapped_total};
+use crate::transaction::TransactionClient;
+use crate::wallet::WalletInfo;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// After a swap, look for stranded wrapped SOL this often, this many times
+const WATCH_INTERVAL: Duration = Duration::from_secs(10);
+const WATCH_CHECKS: usize = 6;
+
+/// Offers to unwrap SOL left in wrapped SOL accounts. Checks once, or with
+/// `watch` for a minute while a swap that wrapped SOL finalizes.
+///
+/// Render it with `key: "{address}"` so switching wallets checks again.
+#[component]
+pub fn WrappedSolCleanup(
+    address: String,
+    wallet: Option<WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    custom_rpc: Option<String>,
+    watch: bool,
+) -> Element {
+    let mut accounts = use_signal(Vec::<TokenAccountInfo>::new);
+    let mut unwrapping = use_signal(|| false);
+    let mut message = use_signal(|| None as Option<String>);
+
+    use_effect({
+        let address = address.clone();
+        let custom_rpc = custom_rpc.clone();
+        move || {
+            let address = address.clone();
+            let custom_rpc = custom_rpc.clone();
+            spawn(async move {
+                let checks = if watch { WATCH_CHECKS } else { 1 };
+                for check in 0..checks {
+                    if check > 0 {
+                        tokio::time::sleep(WATCH_INTERVAL).await;
+                    }
+                    match find_wrapped_sol(&address, custom_rpc.as_deref()).await {
+                        Ok(found) => {
+                            let stranded = wrapped_total(&found) > 0.0;
+                            accounts.set(found);
+                            if stranded {
+                                break;
+                            }
+                        }
+                        Err(e) => println!("⚠️ Failed to check for wrapped SOL: {}", e),
+                    }
+                }
+            });
+        }
+    });
+
+    let unwrap = move |_| {
+        let signer = match active_wallet_signer(hardware_wallet.clone(), wallet.clone()) {
+            Ok(signer) => signer,
+            Err(e) => {
+                message.set(Some(e));
+                return;
+            }
+        };
+        let client = TransactionClient::new(custom_rpc.as_deref());
+        let current = accounts();
+        unwrapping.set(true);
+        message.set(None);
+        spawn(async move {
+            match unwrap_all(&client, signer.as_ref(), &current).await {
+                Ok(_) => {
+                    message.set(Some(format!("Unwrapped {} SOL", wrapped_total(&current))));
+                    accounts.set(Vec::new());
+                }
+                Err(e) => message.set(Some(format!("Unwrap failed: {}", e))),
+            }
+            unwrapping.set(false);
+        });
+    };
+
+    let total = wrapped_total(&accounts());
+    let prompt = format!("🌯 {} SOL is still wrapped", total);
+
+    rsx! {
+        if total > 0.0 {
+            div {
+                class: "wsol-cleanup",
+                span { "{prompt}" }
+                button {
+                    class: "button-standard secondary",
+                    disabled: unwrapping(),
+                    onclick: unwrap,
+                    if unwrapping() { "Unwrapping..." } else { "Unwrap to SOL" }
+                }
+            }
+        }
+        if let Some(text) = message() {
+            div { class: "help-text", "{text}" }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+// src/composer.rs
+//! Instruction composer
+//!
+//! Power users can stack several high-level actions (SOL transfers, memos,
+//! new stake delegations and Jito tips) into a single transaction. A Jito tip
+//! also carries the app tip, as on every other JitoTx transaction. The stack
+//! is lowered into a `TransactionBuilder`, whose size and account count are
+//! checked against the network limits every time the stack changes.
+
+use crate::tips::tip_instructions;
+use crate::transaction::TransactionBuilder;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    stake::{
+        instruction::create_account_and_delegate_stake,
+        state::{Authorized, Lockup},
+    },
+    system_instruction,
+};
+use std::str::FromStr;
+
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+/// Size of a stake account, for its rent exemption
+pub const STAKE_ACCOUNT_SIZE: usize = 200;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComposerAction {
+    Transfer { to: Pubkey, lamports: u64 },
+    Memo { text: String },
+    /// Create a new stake account and delegate it
+    Stake { vote_account: Pubkey, lamports: u64 },
+    Tip { lamports: u64 },
+}
+
+fn sol(lamports: u64) -> f64 {
+    lamports as f64 / 1_000_000_000.0
+}
+
+impl ComposerAction {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ComposerAction::Transfer { .. } => "💸",
+            ComposerAction::Memo { .. } => "📝",
+            ComposerAction::Stake { .. } => "🥩",
+            ComposerAction::Tip { .. } => "⚡",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            ComposerAction::Transfer { to, lamports } => format!("Send {} SOL to {}", sol(*lamports), to),
+            ComposerAction::Memo { text } => format!("Memo: \"{}\"", text),
+            ComposerAction::Stake { vote_account, lamports } => {
+                format!("Stake {} SOL with {}", sol(*lamports), vote_account)
+            }
+            ComposerAction::Tip { lamports } => format!("Jito tip of {} SOL", sol(*lamports)),
+        }
+    }
+
+    /// SOL leaving the wallet, not counting fees
+    pub fn outgoing_lamports(&self, stake_rent: u64) -> u64 {
+        match self {
+            ComposerAction::Transfer { lamports, .. } => *lamports,
+            ComposerAction::Tip { lamports } => lamports + crate::config::app_tip::app_tip_lamports(),
+            ComposerAction::Stake { lamports, .. } => lamports + stake_rent,
+            ComposerAction::Memo { .. } => 0,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            ComposerAction::Memo { text } if text.trim().is_empty() => Err("A memo can't be empty".to_string()),
+            ComposerAction::Transfer { lamports: 0, .. }
+            | ComposerAction::Stake { lamports: 0, .. }
+            | ComposerAction::Tip { lamports: 0 } => Err("Amounts must be greater than zero".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Memo instruction signed by `signer`
+pub fn memo_instruction(signer: &Pubkey, text: &str) -> Instruction {
+    Instruction {
+        program_id: Pubkey::from_str(MEMO_PROGRAM_ID).expect("memo program id is valid"),
+        accounts: vec![AccountMeta::new_readonly(*signer, true)],
+        data: text.as_bytes().to_vec(),
+    }
+}
+
+/// Lower the action stack into a builder for `payer`. Every stake action gets
+/// a fresh stake account keypair as co-signer.
+pub fn compose(payer: &Pubkey, actions: &[ComposerAction], stake_rent: u64) -> Result<TransactionBuilder, String> {
+    let mut builder = TransactionBuilder::new(*payer);
+    for action in actions {
+        action.validate()?;
+        match action {
+            ComposerAction::Transfer { to, lamports } => {
+                builder.add_instruction(system_instruction::transfer(payer, to, *lamports));
+            }
+            ComposerAction::Memo { text } => builder.add_instruction(memo_instruction(payer, text)),
+            ComposerAction::Stake { vote_account, lamports } => {
+                let stake_account = Keypair::new();
+                let authorized = Authorized { staker: *payer, withdrawer: *payer };
+                for instruction in create_account_and_delegate_stake(
+                    payer,
+                    &stake_account.pubkey(),
+                    vote_account,
+                    &authorized,
+                    &Lockup::default(),
+                    lamports + stake_rent,
+                ) {
+                    builder.add_instruction(instruction);
+                }
+                builder.add_co_signer(stake_account);
+            }
+            ComposerAction::Tip { lamports } => {
+                for instruction in tip_instructions(payer, Some(*lamports))? {
+                    builder.add_instruction(instruction);
+                }
+            }
+        }
+    }
+    Ok(builder)
+}
+
+/// Swap the action at `index` with its neighbour above (`up`) or below
+pub fn move_action(actions: &mut [ComposerAction], index: usize, up: bool) {
+    let other = if up { index.checked_sub(1) } else { Some(index + 1) };
+    if let Some(other) = other.filter(|o| *o < actions.len() && index < actions.len()) {
+        actions.swap(index, other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose_stack() {
+        let payer = Pubkey::new_unique();
+        let actions = vec![
+            ComposerAction::Transfer { to: Pubkey::new_unique(), lamports: 1_000_000 },
+            ComposerAction::Memo { text: "rent for October".to_string() },
+            ComposerAction::Stake { vote_account: Pubkey::new_unique(), lamports: 2_000_000_000 },
+            ComposerAction::Tip { lamports: 100_000 },
+        ];
+        let builder = compose(&payer, &actions, 2_282_880).unwrap();
+        // Stake is create account + initialize + delegate, the tip is the
+        // app tip (unless turned off) and the Jito tip
+        let tips = tip_instructions(&payer, Some(100_000)).unwrap();
+        assert_eq!(builder.instructions().len(), 5 + tips.len());
+        assert_eq!(builder.instructions()[1].program_id.to_string(), MEMO_PROGRAM_ID);
+        assert_eq!(builder.instructions().last(), tips.last());
+        assert!(builder.limits().check().is_ok());
+
+        let app_tip = crate::config::app_tip::app_tip_lamports();
+        let total: u64 = actions.iter().map(|a| a.outgoing_lamports(2_282_880)).sum();
+        assert_eq!(total, 1_000_000 + 2_000_000_000 + 2_282_880 + 100_000 + app_tip);
+    }
+
+    #[test]
+    fn test_invalid_actions_rejected() {
+        let payer = Pubkey::new_unique();
+        assert!(compose(&payer, &[ComposerAction::Memo { text: "  ".to_string() }], 0).is_err());
+        assert!(compose(&payer, &[ComposerAction::Tip { lamports: 0 }], 0).is_err());
+    }
+
+    #[test]
+    fn test_move_action() {
+        let mut actions = vec![
+            ComposerAction::Tip { lamports: 1 },
+            ComposerAction::Tip { lamports: 2 },
+        ];
+        move_action(&mut actions, 1, true);
+        assert_eq!(actions[0], ComposerAction::Tip { lamports: 2 });
+        move_action(&mut actions, 1, false);
+        move_action(&mut actions, 0, true);
+        assert_eq!(actions[0], ComposerAction::Tip { lamports: 2 });
+    }
+}
@@ -0,0 +1,80 @@
+// src/config/app_tip.rs
+//! App tip
+//!
+//! Swaps, and other transactions sent with JitoTx, tip the app's (jules)
+//! account. The amount is a setting rather than a constant and may be zero,
+//! in which case no tip transfer is added at all.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, RwLock};
+
+pub const DEFAULT_APP_TIP_LAMPORTS: u64 = 100_000; // 0.0001 SOL
+/// Highest tip accepted, 0.01 SOL
+pub const MAX_APP_TIP_LAMPORTS: u64 = 10_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppTipSettings {
+    pub lamports: u64,
+}
+
+impl Default for AppTipSettings {
+    fn default() -> Self {
+        Self { lamports: DEFAULT_APP_TIP_LAMPORTS }
+    }
+}
+
+static ACTIVE: LazyLock<RwLock<AppTipSettings>> = LazyLock::new(|| RwLock::new(crate::storage::load_app_tip_settings()));
+
+/// Lamports tipped to the app per transaction, 0 for none
+pub fn app_tip_lamports() -> u64 {
+    ACTIVE.read().map(|settings| settings.lamports).unwrap_or(DEFAULT_APP_TIP_LAMPORTS)
+}
+
+/// Change the app tip and remember it
+pub fn set_app_tip_lamports(lamports: u64) -> Result<(), String> {
+    if lamports > MAX_APP_TIP_LAMPORTS {
+        return Err(format!("The app tip can't be above {} SOL", MAX_APP_TIP_LAMPORTS as f64 / 1_000_000_000.0));
+    }
+    let settings = AppTipSettings { lamports };
+    crate::storage::save_app_tip_settings(&settings)?;
+    if let Ok(mut active) = ACTIVE.write() {
+        *active = settings;
+    }
+    Ok(())
+}
+
+/// Lamports from a SOL amount typed by the user ("0.0001" → 100000)
+pub fn parse_app_tip_sol(input: &str) -> Result<u64, String> {
+    let sol: f64 = input
+        .trim()
+        .parse()
+        .map_err(|_| "Enter the app tip in SOL, 0 for none".to_string())?;
+    if sol < 0.0 {
+        return Err("The app tip can't be negative".to_string());
+    }
+    Ok((sol * 1_000_000_000.0).round() as u64)
+}
+
+/// "0.0001 SOL" or "None"
+pub fn app_tip_label(lamports: u64) -> String {
+    if lamports == 0 {
+        "None".to_string()
+    } else {
+        format!("{} SOL", lamports as f64 / 1_000_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_app_tip() {
+        assert_eq!(parse_app_tip_sol("0.0001"), Ok(100_000));
+        assert_eq!(parse_app_tip_sol(" 0 "), Ok(0));
+        assert!(parse_app_tip_sol("-1").is_err());
+        assert!(parse_app_tip_sol("abc").is_err());
+        assert_eq!(app_tip_label(0), "None");
+        assert_eq!(app_tip_label(DEFAULT_APP_TIP_LAMPORTS), "0.0001 SOL");
+    }
+}
@@ -1 +1,4 @@
-pub mod tokens;
\ No newline at end of file
+pub mod tokens;
+pub mod network;
+pub mod providers;
+pub mod app_tip;
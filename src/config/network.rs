@@ -0,0 +1,141 @@
+// src/config/network.rs
+//! Active Solana cluster
+//!
+//! The wallet runs against mainnet unless another network is picked in RPC
+//! settings. The choice is app-wide: the RPC pool sends every request to the
+//! network's endpoint, the verified token list and explorer links follow it,
+//! and the wallet shows a banner whenever it isn't on mainnet so real funds
+//! aren't sent on the wrong cluster by accident.
+//!
+//! Debug builds also offer a local `solana-test-validator`, with faucet
+//! airdrops and confirmation that doesn't wait for finalization, so
+//! contributors can run the send, stake and swap flows without real funds.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, RwLock};
+
+pub const DEVNET_RPC_URL: &str = "https://api.devnet.solana.com";
+pub const TESTNET_RPC_URL: &str = "https://api.testnet.solana.com";
+/// Default `solana-test-validator` RPC
+pub const LOCALNET_RPC_URL: &str = "http://127.0.0.1:8899";
+/// SOL requested per faucet airdrop
+pub const AIRDROP_SOL: f64 = 1.0;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Devnet,
+    Testnet,
+    /// Local `solana-test-validator`
+    Localnet,
+    /// Any other cluster, e.g. a private one
+    Custom { rpc_url: String },
+}
+
+impl Network {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "Mainnet",
+            Network::Devnet => "Devnet",
+            Network::Testnet => "Testnet",
+            Network::Localnet => "Localnet",
+            Network::Custom { .. } => "Custom",
+        }
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        *self == Network::Mainnet
+    }
+
+    /// Whether the cluster has a faucet behind `requestAirdrop`
+    pub fn supports_airdrop(&self) -> bool {
+        matches!(self, Network::Devnet | Network::Testnet | Network::Localnet)
+    }
+
+    /// Commitment for blockhashes and preflight. A fresh test validator can
+    /// take a while to finalize its first slots.
+    pub fn commitment(&self) -> &'static str {
+        match self {
+            Network::Localnet => "confirmed",
+            _ => "finalized",
+        }
+    }
+
+    /// Treat confirmed transactions as done instead of waiting for finality
+    pub fn relaxed_confirmation(&self) -> bool {
+        *self == Network::Localnet
+    }
+
+    /// RPC endpoint that replaces the selected one, `None` on mainnet where
+    /// the RPC settings apply as usual
+    pub fn rpc_override(&self) -> Option<&str> {
+        match self {
+            Network::Mainnet => None,
+            Network::Devnet => Some(DEVNET_RPC_URL),
+            Network::Testnet => Some(TESTNET_RPC_URL),
+            Network::Localnet => Some(LOCALNET_RPC_URL),
+            Network::Custom { rpc_url } => Some(rpc_url),
+        }
+    }
+
+    /// Query string selecting the cluster on Solscan
+    fn cluster_query(&self) -> String {
+        match self {
+            Network::Mainnet => String::new(),
+            Network::Devnet => "?cluster=devnet".to_string(),
+            Network::Testnet => "?cluster=testnet".to_string(),
+            Network::Localnet => format!("?cluster=custom&customUrl={}", LOCALNET_RPC_URL),
+            Network::Custom { rpc_url } => format!("?cluster=custom&customUrl={}", rpc_url),
+        }
+    }
+
+    pub fn explorer_tx_url(&self, signature: &str) -> String {
+        format!("https://solscan.io/tx/{}{}", signature, self.cluster_query())
+    }
+
+    pub fn explorer_address_url(&self, address: &str) -> String {
+        format!("https://solscan.io/account/{}{}", address, self.cluster_query())
+    }
+}
+
+static ACTIVE: LazyLock<RwLock<Network>> = LazyLock::new(|| RwLock::new(crate::storage::load_network()));
+
+/// The network the app is running against
+pub fn active() -> Network {
+    ACTIVE.read().map(|n| n.clone()).unwrap_or_default()
+}
+
+/// Switch networks and remember the choice
+pub fn set_active(network: Network) -> Result<(), String> {
+    crate::storage::save_network(&network)?;
+    if let Ok(mut active) = ACTIVE.write() {
+        println!("🌐 Switched to {}", network.label());
+        *active = network;
+    }
+    Ok(())
+}
+
+/// Solscan link for a transaction on the active network
+pub fn explorer_tx_url(signature: &str) -> String {
+    active().explorer_tx_url(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explorer_links_follow_cluster() {
+        assert_eq!(Network::Mainnet.explorer_tx_url("sig"), "https://solscan.io/tx/sig");
+        assert_eq!(Network::Devnet.explorer_tx_url("sig"), "https://solscan.io/tx/sig?cluster=devnet");
+        assert_eq!(
+            Network::Custom { rpc_url: "http://127.0.0.1:8899".to_string() }.explorer_address_url("addr"),
+            "https://solscan.io/account/addr?cluster=custom&customUrl=http://127.0.0.1:8899"
+        );
+        assert_eq!(Network::Mainnet.rpc_override(), None);
+        assert_eq!(Network::Testnet.rpc_override(), Some(TESTNET_RPC_URL));
+        assert_eq!(Network::Localnet.explorer_tx_url("sig"), "https://solscan.io/tx/sig?cluster=custom&customUrl=http://127.0.0.1:8899");
+        assert!(Network::Localnet.supports_airdrop() && !Network::Mainnet.supports_airdrop());
+    }
+}
@@ -0,0 +1,105 @@
+// src/config/providers.rs
+//! Known RPC providers
+//!
+//! Presets for the RPC settings and what each provider supports beyond the
+//! standard JSON-RPC API, so features that need DAS, WebSockets or a
+//! priority-fee API can check before calling an endpoint that can't serve
+//! them.
+
+/// What a provider serves beyond standard JSON-RPC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderCapabilities {
+    /// Digital Asset Standard (`getAssetsByOwner` and friends)
+    pub das: bool,
+    pub websockets: bool,
+    /// `getPriorityFeeEstimate`
+    pub priority_fee_api: bool,
+}
+
+/// Assumed for endpoints that don't match a known provider
+pub const UNKNOWN_CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    das: true,
+    websockets: true,
+    priority_fee_api: false,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcProvider {
+    pub id: &'static str,
+    pub name: &'static str,
+    /// Hosts served by the provider
+    hosts: &'static [&'static str],
+    /// URL filled in by the preset, with placeholders to replace
+    pub url_template: &'static str,
+    /// How to get the values for the placeholders
+    pub setup_hint: &'static str,
+    pub capabilities: ProviderCapabilities,
+}
+
+pub const PROVIDERS: &[RpcProvider] = &[
+    RpcProvider {
+        id: "helius",
+        name: "Helius",
+        hosts: &["helius-rpc.com"],
+        url_template: "https://mainnet.helius-rpc.com/?api-key=<api-key>",
+        setup_hint: "Create an API key at dashboard.helius.dev and replace <api-key> with it",
+        capabilities: ProviderCapabilities { das: true, websockets: true, priority_fee_api: true },
+    },
+    RpcProvider {
+        id: "triton",
+        name: "Triton",
+        hosts: &["rpcpool.com"],
+        url_template: "https://<name>.mainnet.rpcpool.com/<token>",
+        setup_hint: "Copy the endpoint from your Triton dashboard; it includes the name and token",
+        capabilities: ProviderCapabilities { das: true, websockets: true, priority_fee_api: false },
+    },
+    RpcProvider {
+        id: "quicknode",
+        name: "QuickNode",
+        hosts: &["quiknode.pro"],
+        url_template: "https://<endpoint>.solana-mainnet.quiknode.pro/<token>/",
+        setup_hint: "Copy the HTTP provider URL of your QuickNode endpoint. DAS needs the Metaplex add-on.",
+        capabilities: ProviderCapabilities { das: false, websockets: true, priority_fee_api: false },
+    },
+    RpcProvider {
+        id: "public",
+        name: "Public mainnet",
+        hosts: &["api.mainnet-beta.solana.com"],
+        url_template: "https://api.mainnet-beta.solana.com",
+        setup_hint: "No key needed, but heavily rate limited",
+        capabilities: ProviderCapabilities { das: false, websockets: true, priority_fee_api: false },
+    },
+];
+
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    rest.split(['/', '?', ':']).next().unwrap_or(rest)
+}
+
+/// Provider serving `url`, if it's a known one
+pub fn provider_for_url(url: &str) -> Option<&'static RpcProvider> {
+    let host = host(url);
+    PROVIDERS.iter().find(|provider| {
+        provider.hosts.iter().any(|known| host == *known || host.ends_with(&format!(".{}", known)))
+    })
+}
+
+/// What the endpoint at `url` supports
+pub fn capabilities(url: &str) -> ProviderCapabilities {
+    provider_for_url(url).map(|provider| provider.capabilities).unwrap_or(UNKNOWN_CAPABILITIES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_detection() {
+        assert_eq!(provider_for_url("https://johna-k3cr1v-fast-mainnet.helius-rpc.com").map(|p| p.id), Some("helius"));
+        assert_eq!(provider_for_url("https://acme.mainnet.rpcpool.com/abc").map(|p| p.id), Some("triton"));
+        assert_eq!(provider_for_url("https://api.mainnet-beta.solana.com").map(|p| p.id), Some("public"));
+        assert_eq!(provider_for_url("https://evil-helius-rpc.com"), None);
+        assert!(!capabilities("https://api.mainnet-beta.solana.com").das);
+        assert_eq!(capabilities("http://127.0.0.1:8899"), UNKNOWN_CAPABILITIES);
+    }
+}
@@ -69,14 +69,38 @@ fn parse_tokens_from_json(json_str: &str) -> HashMap<String, VerifiedToken> {
     }
 }
 
-/// Get reference to the verified tokens HashMap (mobile-safe)
+// Mints on the test clusters; mainnet mints don't exist there
+static DEVNET_TOKENS: LazyLock<HashMap<String, VerifiedToken>> = LazyLock::new(|| {
+    let tokens = [
+        ("So11111111111111111111111111111111111111112", "Wrapped SOL", "SOL"),
+        ("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU", "USD Coin (Devnet)", "USDC"),
+    ];
+    tokens
+        .into_iter()
+        .map(|(address, name, symbol)| {
+            (address.to_string(), VerifiedToken {
+                address: address.to_string(),
+                name: name.to_string(),
+                symbol: symbol.to_string(),
+                logo_uri: String::new(),
+                tags: vec!["devnet".to_string()],
+            })
+        })
+        .collect()
+});
+
+/// Get reference to the verified tokens HashMap for the active network (mobile-safe)
 pub fn get_verified_tokens() -> &'static HashMap<String, VerifiedToken> {
-    &VERIFIED_TOKENS
+    if crate::config::network::active().is_mainnet() {
+        &VERIFIED_TOKENS
+    } else {
+        &DEVNET_TOKENS
+    }
 }
 
 /// Get a cloned copy of the verified tokens HashMap
 pub fn get_verified_tokens_cloned() -> HashMap<String, VerifiedToken> {
-    VERIFIED_TOKENS.clone()
+    get_verified_tokens().clone()
 }
 
 // ============================================================================
@@ -0,0 +1,298 @@
+// src/cost_basis.rs
+//! Cost basis and profit & loss
+//!
+//! Positions are rebuilt from the wallet's locally indexed history (see
+//! `history`): the SOL and token balance changes of every transaction are
+//! priced at the USD close of their day and folded into average-cost
+//! positions. Receiving or buying adds to the cost at that day's price;
+//! sending, selling or swapping away realizes the difference between that
+//! price and the average cost. What is still held is compared against the
+//! current price for the unrealized P&L.
+//!
+//! Only what the index holds is known. Incoming token transfers that don't
+//! mention the wallet itself are missed, and amounts that leave the wallet
+//! beyond what the known history brought in were acquired before it, so they
+//! reduce the position without a basis and the position is marked partial.
+
+use crate::balance_history::{historical_price, parse_balance_change, BalanceChange};
+use crate::history;
+use crate::prices::get_token_metadata;
+use crate::storage::save_cost_basis;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Native SOL is tracked under the wrapped SOL mint, so wrapping and
+/// unwrapping nets out within a transaction
+pub const SOL_ASSET: &str = "So11111111111111111111111111111111111111112";
+/// Transactions read when building positions, oldest first
+const MAX_COST_BASIS_TRANSACTIONS: usize = 300;
+/// Quantities below this are rounding left-overs
+const DUST: f64 = 1e-9;
+
+/// One asset's balance change in a transaction, in UI units
+#[derive(Debug, Clone, PartialEq)]
+pub struct Movement {
+    pub asset: String,
+    pub amount: f64,
+    pub usd_price: Option<f64>,
+}
+
+/// Average-cost position in one asset
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    /// Mint address, `SOL_ASSET` for SOL
+    pub asset: String,
+    pub symbol: String,
+    pub quantity: f64,
+    /// USD cost of the quantity held
+    pub cost_usd: f64,
+    pub realized_usd: f64,
+    /// Changes without a price for their day; inflows among them were added at the average cost
+    pub unpriced: usize,
+    /// Amount that left the wallet beyond what the known history brought in
+    pub uncovered: f64,
+}
+
+impl Position {
+    pub fn average_cost(&self) -> Option<f64> {
+        (self.quantity > DUST).then(|| self.cost_usd / self.quantity)
+    }
+
+    pub fn unrealized_usd(&self, usd_price: f64) -> f64 {
+        self.quantity * usd_price - self.cost_usd
+    }
+
+    pub fn unrealized_percentage(&self, usd_price: f64) -> Option<f64> {
+        (self.cost_usd > 0.0).then(|| self.unrealized_usd(usd_price) / self.cost_usd * 100.0)
+    }
+
+    /// Whether part of the position predates the known history
+    pub fn is_partial(&self) -> bool {
+        self.uncovered > DUST || self.unpriced > 0
+    }
+
+    fn apply(&mut self, amount: f64, usd_price: Option<f64>) {
+        let average = self.average_cost().unwrap_or(0.0);
+        if usd_price.is_none() {
+            self.unpriced += 1;
+        }
+
+        if amount > 0.0 {
+            self.quantity += amount;
+            self.cost_usd += amount * usd_price.unwrap_or(average);
+            return;
+        }
+
+        let sent = -amount;
+        let covered = sent.min(self.quantity);
+        if let Some(price) = usd_price {
+            self.realized_usd += covered * (price - average);
+        }
+        self.cost_usd -= covered * average;
+        self.quantity -= covered;
+        self.uncovered += sent - covered;
+        if self.quantity <= DUST {
+            self.quantity = 0.0;
+            self.cost_usd = 0.0;
+        }
+    }
+}
+
+/// SOL and token movements of one transaction, one per asset
+pub fn movements(change: &BalanceChange) -> Vec<(String, f64)> {
+    let mut by_asset: Vec<(String, f64)> = Vec::new();
+    let mut add = |asset: &str, amount: f64| match by_asset.iter_mut().find(|(a, _)| a == asset) {
+        Some((_, total)) => *total += amount,
+        None => by_asset.push((asset.to_string(), amount)),
+    };
+    if change.sol_delta != 0 {
+        add(SOL_ASSET, change.sol_delta as f64 / 1_000_000_000.0);
+    }
+    for (mint, delta, decimals) in &change.token_deltas {
+        add(mint, *delta as f64 / 10_f64.powi(*decimals as i32));
+    }
+    by_asset.retain(|(_, amount)| amount.abs() > DUST);
+    by_asset
+}
+
+/// Fold priced movements, oldest first, into positions sorted by symbol
+pub fn build_positions(movements: &[Movement], symbols: &HashMap<String, String>) -> Vec<Position> {
+    let mut positions: HashMap<String, Position> = HashMap::new();
+    for movement in movements {
+        let position = positions.entry(movement.asset.clone()).or_insert_with(|| Position {
+            asset: movement.asset.clone(),
+            symbol: symbols.get(&movement.asset).cloned().unwrap_or_else(|| movement.asset.chars().take(6).collect()),
+            ..Default::default()
+        });
+        position.apply(movement.amount, movement.usd_price);
+    }
+    let mut positions: Vec<Position> = positions.into_values().collect();
+    positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    positions
+}
+
+/// Positions of a wallet as of the last computation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostBasisReport {
+    pub wallet: String,
+    pub positions: Vec<Position>,
+    pub transactions: usize,
+    /// The whole history of the wallet was read
+    pub complete: bool,
+    pub computed_at: i64,
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl CostBasisReport {
+    pub fn position(&self, asset: &str) -> Option<&Position> {
+        self.positions.iter().find(|p| p.asset == asset)
+    }
+
+    pub fn total_realized_usd(&self) -> f64 {
+        self.positions.iter().map(|p| p.realized_usd).sum()
+    }
+
+    /// One row per position, with the unrealized P&L at `usd_prices` (by asset)
+    pub fn to_csv(&self, usd_prices: &HashMap<String, f64>) -> String {
+        let mut rows = vec![
+            "symbol,asset,quantity,average_cost_usd,cost_usd,current_price_usd,unrealized_usd,realized_usd,partial".to_string(),
+        ];
+        for position in &self.positions {
+            let price = usd_prices.get(&position.asset).copied();
+            let optional = |value: Option<f64>| value.map(|v| format!("{:.6}", v)).unwrap_or_default();
+            rows.push(format!(
+                "{},{},{},{},{:.2},{},{},{:.2},{}",
+                csv_field(&position.symbol),
+                position.asset,
+                position.quantity,
+                optional(position.average_cost()),
+                position.cost_usd,
+                optional(price),
+                price.map(|p| format!("{:.2}", position.unrealized_usd(p))).unwrap_or_default(),
+                position.realized_usd,
+                position.is_partial()
+            ));
+        }
+        rows.join("\n")
+    }
+}
+
+/// Sync the wallet's history index, rebuild its positions and save them
+pub async fn compute_cost_basis(wallet: &str, rpc_url: Option<&str>) -> Result<CostBasisReport, String> {
+    let mut index = history::sync_newer(wallet, rpc_url).await?;
+    while !index.reached_start && index.entries.len() < MAX_COST_BASIS_TRANSACTIONS {
+        let before = index.entries.len();
+        index = history::load_older(wallet, rpc_url).await?;
+        if index.entries.len() == before {
+            break;
+        }
+    }
+    let complete = index.reached_start && index.entries.len() <= MAX_COST_BASIS_TRANSACTIONS;
+
+    // Oldest first
+    let items: Vec<_> = index.entries.iter().take(MAX_COST_BASIS_TRANSACTIONS).rev().cloned().collect();
+    println!("📒 Computing cost basis of {} from {} transaction(s)", wallet, items.len());
+    let mut changes = Vec::new();
+    for item in &items {
+        let Some(block_time) = item.block_time else { continue };
+        let details = history::transaction_details(wallet, &item.signature, rpc_url).await?;
+        let (Some(message), Some(meta)) = (details.get("message"), details.get("meta")) else { continue };
+        if let Some(change) = parse_balance_change(wallet, &item.signature, Some(block_time), message, meta) {
+            changes.push(change);
+        }
+    }
+
+    let mints: Vec<String> = changes
+        .iter()
+        .flat_map(|c| c.token_deltas.iter().map(|(mint, _, _)| mint.clone()))
+        .filter(|mint| mint != SOL_ASSET)
+        .collect::<std::collections::HashSet<_>>()
+        .into_iter()
+        .collect();
+    let mut symbols: HashMap<String, String> = get_token_metadata(mints)
+        .await
+        .unwrap_or_else(|e| {
+            println!("⚠️ Token metadata unavailable: {}", e);
+            HashMap::new()
+        })
+        .into_iter()
+        .map(|(mint, metadata)| (mint, metadata.symbol))
+        .collect();
+    symbols.insert(SOL_ASSET.to_string(), "SOL".to_string());
+
+    let mut priced = Vec::new();
+    for change in &changes {
+        let timestamp = change.block_time.unwrap_or_default();
+        for (asset, amount) in movements(change) {
+            let usd_price = match symbols.get(&asset) {
+                Some(symbol) => historical_price(symbol, timestamp).await,
+                None => None,
+            };
+            priced.push(Movement { asset, amount, usd_price });
+        }
+    }
+
+    let report = CostBasisReport {
+        wallet: wallet.to_string(),
+        positions: build_positions(&priced, &symbols),
+        transactions: changes.len(),
+        complete,
+        computed_at: chrono::Utc::now().timestamp(),
+    };
+    save_cost_basis(&report)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movement(amount: f64, usd_price: Option<f64>) -> Movement {
+        Movement { asset: SOL_ASSET.to_string(), amount, usd_price }
+    }
+
+    #[test]
+    fn test_average_cost_and_realized() {
+        let symbols = HashMap::from([(SOL_ASSET.to_string(), "SOL".to_string())]);
+        let positions = build_positions(
+            &[movement(2.0, Some(100.0)), movement(2.0, Some(200.0)), movement(-1.0, Some(300.0))],
+            &symbols,
+        );
+        let sol = &positions[0];
+        assert_eq!(sol.symbol, "SOL");
+        assert_eq!(sol.quantity, 3.0);
+        assert_eq!(sol.average_cost(), Some(150.0));
+        assert_eq!(sol.realized_usd, 150.0);
+        assert_eq!(sol.unrealized_usd(200.0), 150.0);
+        assert!(!sol.is_partial());
+    }
+
+    #[test]
+    fn test_outflow_beyond_history_is_uncovered() {
+        let positions = build_positions(&[movement(1.0, Some(10.0)), movement(-3.0, Some(20.0))], &HashMap::new());
+        let position = &positions[0];
+        assert_eq!(position.quantity, 0.0);
+        assert_eq!(position.realized_usd, 10.0);
+        assert_eq!(position.uncovered, 2.0);
+        assert!(position.is_partial());
+    }
+
+    #[test]
+    fn test_wrapping_nets_out() {
+        let change = BalanceChange {
+            sol_delta: -1_000_005_000,
+            token_deltas: vec![(SOL_ASSET.to_string(), 1_000_000_000, 9)],
+            ..Default::default()
+        };
+        let moved = movements(&change);
+        assert_eq!(moved.len(), 1);
+        assert!((moved[0].1 + 0.000005).abs() < DUST);
+    }
+}
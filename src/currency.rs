@@ -10,6 +10,11 @@ use reqwest::Client;
 pub static SELECTED_CURRENCY: GlobalSignal<String> = Signal::global(|| "USD".to_string());
 pub static EXCHANGE_RATES: GlobalSignal<HashMap<String, f64>> = Signal::global(HashMap::new);
 
+/// Quote currencies that are crypto assets rather than fiat. Their rate is
+/// the inverse of the asset's USD price, so they go through the same
+/// USD-based conversion as fiat currencies.
+pub const CRYPTO_CURRENCIES: &[&str] = &["SOL", "BTC"];
+
 /// Supported currencies with their display information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CurrencyInfo {
@@ -100,6 +105,18 @@ pub fn get_supported_currencies() -> Vec<CurrencyInfo> {
             symbol: "MX$".to_string(),
             pyth_id: Some("e13b1c1ffb32f34e1be9545583f01ef385fde7f42ee66049d30570dc866b77ca".to_string()),
         },
+        CurrencyInfo {
+            code: "SOL".to_string(),
+            name: "Solana".to_string(),
+            symbol: "◎".to_string(),
+            pyth_id: Some("ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d".to_string()),
+        },
+        CurrencyInfo {
+            code: "BTC".to_string(),
+            name: "Bitcoin".to_string(),
+            symbol: "₿".to_string(),
+            pyth_id: Some("e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43".to_string()),
+        },
     ]
 }
 
@@ -169,6 +186,9 @@ pub async fn fetch_exchange_rates() -> Result<HashMap<String, f64>, Box<dyn Erro
                 "GBP" => 1.0 / rate,  // GBP/USD rate -> need USD/GBP rate  
                 "AUD" => 1.0 / rate,  // AUD/USD rate -> need USD/AUD rate
                 
+                // Crypto feeds are priced in USD, so the rate is SOL/BTC per USD
+                "SOL" | "BTC" => 1.0 / rate,
+                
                 // These pairs are USD/XXX (USD per foreign currency), so rate gives foreign currency per USD directly
                 "JPY" => rate,  // USD/JPY rate is direct
                 "CAD" => rate,  // USD/CAD rate is direct
@@ -205,16 +225,36 @@ pub fn convert_to_usd(amount: f64, from_currency: &str) -> f64 {
     amount / rate
 }
 
+/// Whether the currency is a crypto asset (SOL, BTC) rather than fiat
+pub fn is_crypto_currency(currency_code: &str) -> bool {
+    CRYPTO_CURRENCIES.contains(&currency_code)
+}
+
+/// Decimal places used when displaying amounts in the currency
+pub fn currency_precision(currency_code: &str) -> usize {
+    match currency_code {
+        "JPY" => 0, // Yen doesn't use decimal places
+        "SOL" => 3,
+        "BTC" => 6, // Typical balances are fractions of a bitcoin
+        _ => 2,
+    }
+}
+
+/// Update the rate of a crypto quote currency from a fresh USD price, so
+/// SOL/BTC denominated values follow live prices between FX refreshes
+pub fn update_crypto_rate(symbol: &str, usd_price: f64) {
+    if is_crypto_currency(symbol) && usd_price > 0.0 {
+        EXCHANGE_RATES.write().insert(symbol.to_string(), 1.0 / usd_price);
+    }
+}
+
 /// Format currency amount with appropriate symbol and precision
 pub fn format_currency_amount(amount: f64, currency_code: &str) -> String {
     let currencies = get_supported_currencies();
     let currency = currencies.iter().find(|c| c.code == currency_code);
     
     let symbol = currency.map_or("$", |c| &c.symbol);
-    let precision = match currency_code {
-        "JPY" => 0, // Yen doesn't use decimal places
-        _ => 2,
-    };
+    let precision = currency_precision(currency_code);
     
     format!("{}{:.precision$}", symbol, amount, precision = precision)
 }
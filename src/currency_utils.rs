@@ -6,7 +6,9 @@ use crate::currency::{
     EXCHANGE_RATES, 
     convert_from_usd, 
     get_current_currency_symbol,
-    format_currency_amount
+    format_currency_amount,
+    currency_precision,
+    is_crypto_currency
 };
 
 /// Convert and format a USD price to the selected currency
@@ -43,8 +45,10 @@ pub fn format_price_change(usd_change: f64) -> String {
     let converted_change = convert_from_usd(usd_change, &selected_currency);
     let symbol = get_current_currency_symbol();
     
+    let precision = currency_precision(&selected_currency);
+    
     let sign = if converted_change >= 0.0 { "+" } else { "" };
-    format!("{}{}{:.2}", sign, symbol, converted_change)
+    format!("{}{}{:.precision$}", sign, symbol, converted_change, precision = precision)
 }
 
 /// Get current currency code for display
@@ -74,9 +78,16 @@ pub fn format_percentage_change(percentage: f64) -> String {
 pub fn format_portfolio_balance(usd_amount: f64) -> String {
     let selected_currency = SELECTED_CURRENCY.read().clone();
     let converted_amount = convert_from_usd(usd_amount, &selected_currency);
-    let rounded_amount = converted_amount.round();
     let symbol = get_current_currency_symbol();
     
+    // Crypto balances are mostly fractional, keep their decimals
+    if is_crypto_currency(&selected_currency) {
+        let precision = currency_precision(&selected_currency);
+        return format!("{}{:.precision$}", symbol, converted_amount, precision = precision);
+    }
+    
+    let rounded_amount = converted_amount.round();
+    
     // Always format without decimals for portfolio balance
     format!("{}{:.0}", symbol, rounded_amount)
 }
@@ -101,6 +112,8 @@ pub fn format_large_currency_amount(usd_amount: f64) -> String {
         // For amounts under 1000, check if it's a whole number
         if value.fract() == 0.0 {
             format!("{}{:.0}", symbol, value)  // No decimals for whole numbers
+        } else if is_crypto_currency(&selected_currency) {
+            format!("{}{:.precision$}", symbol, value, precision = currency_precision(&selected_currency))
         } else {
             format!("{}{:.2}", symbol, value)  // Keep decimals for fractional amounts
         }
@@ -202,9 +215,10 @@ pub fn format_token_value_smart(token_amount: f64, token_usd_price: f64) -> Stri
         return "$0".to_string();
     }
     
-    // Get currency symbol (could be $, €, £, etc.)
+    // Get currency symbol (could be $, €, £, ◎, etc.)
     let symbol = get_current_currency_symbol();
-    let converted_value = convert_from_usd(usd_value, &SELECTED_CURRENCY.read());
+    let selected_currency = SELECTED_CURRENCY.read().clone();
+    let converted_value = convert_from_usd(usd_value, &selected_currency);
     
     // For very large amounts, use B/M/K abbreviations
     if converted_value >= 1_000_000_000.0 {
@@ -253,6 +267,12 @@ pub fn format_token_value_smart(token_amount: f64, token_usd_price: f64) -> Stri
         return format!("{}{:.2}", symbol, converted_value); // e.g., "$4.49"
     }
     
+    // Fractions of a SOL or BTC still matter
+    if is_crypto_currency(&selected_currency) {
+        let precision = currency_precision(&selected_currency);
+        return format!("{}{:.precision$}", symbol, converted_value, precision = precision); // e.g., "₿0.001234"
+    }
+    
     if converted_value >= 0.01 {
         return format!("{}{:.2}", symbol, converted_value); // e.g., "$0.12"
     }
@@ -0,0 +1,414 @@
+// src/decoder.rs
+//! Human-readable transaction decoding
+//!
+//! Turns the instructions of a transaction into one line each, e.g.
+//! "Transfer 0.5 SOL to 9xQe...", for the send and swap confirmation
+//! screens, the broadcast tool and bridge signing requests. Programs the
+//! decoder doesn't understand are named if the app knows them and otherwise
+//! shown by address; nothing here decides whether a transaction is safe,
+//! that is the signing firewall's job.
+
+use crate::signing::firewall::program_label;
+use solana_sdk::{
+    compute_budget,
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    stake::{self, instruction::StakeInstruction, state::StakeAuthorize},
+    system_instruction::SystemInstruction,
+    system_program,
+    transaction::VersionedTransaction,
+};
+use spl_token::instruction::TokenInstruction;
+
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const MEMO_PROGRAM_IDS: &[&str] = &[
+    "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr",
+    "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo",
+];
+
+/// One decoded instruction
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedInstruction {
+    /// Display name of the program, or its address
+    pub program: String,
+    pub summary: String,
+}
+
+fn short(key: &Pubkey) -> String {
+    let s = key.to_string();
+    format!("{}...{}", &s[..4], &s[s.len() - 4..])
+}
+
+fn sol(lamports: u64) -> String {
+    ui_amount(lamports, 9)
+}
+
+/// Base units as a decimal amount without trailing zeros
+fn ui_amount(amount: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let scale = 10u128.pow(decimals as u32);
+    let whole = amount as u128 / scale;
+    let fraction = amount as u128 % scale;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{:0width$}", fraction, width = decimals as usize);
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+/// Decode every instruction of a message. Accounts only reachable through
+/// address lookup tables show as "a lookup table account".
+pub fn decode_message(message: &VersionedMessage) -> Vec<DecodedInstruction> {
+    let keys = message.static_account_keys();
+    message
+        .instructions()
+        .iter()
+        .map(|ix| {
+            let Some(program_id) = keys.get(ix.program_id_index as usize) else {
+                return DecodedInstruction {
+                    program: "Unknown program".to_string(),
+                    summary: "Calls a program loaded from a lookup table".to_string(),
+                };
+            };
+            let account = |position: usize| -> Option<Pubkey> {
+                ix.accounts.get(position).and_then(|&i| keys.get(i as usize)).copied()
+            };
+            decode_instruction(program_id, &ix.data, &account, ix.accounts.len())
+        })
+        .collect()
+}
+
+fn decode_instruction(
+    program_id: &Pubkey,
+    data: &[u8],
+    account: &dyn Fn(usize) -> Option<Pubkey>,
+    account_count: usize,
+) -> DecodedInstruction {
+    let label = program_label(program_id).map(String::from).unwrap_or_else(|| program_id.to_string());
+    let decoded = if *program_id == system_program::id() {
+        decode_system(data, account)
+    } else if *program_id == spl_token::id() || program_id.to_string() == TOKEN_2022_PROGRAM_ID {
+        decode_token(data, account)
+    } else if *program_id == spl_associated_token_account::id() {
+        decode_associated_token_account(data, account)
+    } else if *program_id == stake::program::id() {
+        decode_stake(data, account)
+    } else if *program_id == compute_budget::id() {
+        decode_compute_budget(data)
+    } else if MEMO_PROGRAM_IDS.contains(&program_id.to_string().as_str()) {
+        Some(match std::str::from_utf8(data) {
+            Ok(text) => format!("Memo: \"{}\"", text),
+            Err(_) => format!("Memo ({} bytes)", data.len()),
+        })
+    } else {
+        None
+    };
+    let summary = decoded.unwrap_or_else(|| {
+        format!("Call {} ({} accounts, {} bytes of data)", label, account_count, data.len())
+    });
+    DecodedInstruction { program: label, summary }
+}
+
+/// Display form of an account at `position`
+fn name(account: &dyn Fn(usize) -> Option<Pubkey>, position: usize) -> String {
+    account(position).map(|k| k.to_string()).unwrap_or_else(|| "a lookup table account".to_string())
+}
+
+fn short_name(account: &dyn Fn(usize) -> Option<Pubkey>, position: usize) -> String {
+    account(position).map(|k| short(&k)).unwrap_or_else(|| "a lookup table account".to_string())
+}
+
+fn decode_system(data: &[u8], account: &dyn Fn(usize) -> Option<Pubkey>) -> Option<String> {
+    let instruction = bincode::deserialize::<SystemInstruction>(data).ok()?;
+    Some(match instruction {
+        SystemInstruction::Transfer { lamports } => {
+            format!("Transfer {} SOL to {}", sol(lamports), name(account, 1))
+        }
+        SystemInstruction::TransferWithSeed { lamports, .. } => {
+            format!("Transfer {} SOL to {}", sol(lamports), name(account, 2))
+        }
+        SystemInstruction::CreateAccount { lamports, space, owner } => format!(
+            "Create account {} ({} bytes, {} SOL) owned by {}",
+            short_name(account, 1),
+            space,
+            sol(lamports),
+            program_label(&owner).map(String::from).unwrap_or_else(|| owner.to_string())
+        ),
+        SystemInstruction::CreateAccountWithSeed { lamports, space, owner, .. } => format!(
+            "Create account {} ({} bytes, {} SOL) owned by {}",
+            short_name(account, 1),
+            space,
+            sol(lamports),
+            program_label(&owner).map(String::from).unwrap_or_else(|| owner.to_string())
+        ),
+        SystemInstruction::Assign { owner } => {
+            format!("Assign {} to program {}", short_name(account, 0), owner)
+        }
+        SystemInstruction::Allocate { space } => {
+            format!("Allocate {} bytes for {}", space, short_name(account, 0))
+        }
+        SystemInstruction::AdvanceNonceAccount => {
+            format!("Advance durable nonce {}", short_name(account, 0))
+        }
+        SystemInstruction::WithdrawNonceAccount(lamports) => format!(
+            "Withdraw {} SOL from nonce account {} to {}",
+            sol(lamports),
+            short_name(account, 0),
+            name(account, 1)
+        ),
+        SystemInstruction::InitializeNonceAccount(authority) => {
+            format!("Initialize nonce account {} with authority {}", short_name(account, 0), authority)
+        }
+        SystemInstruction::AuthorizeNonceAccount(authority) => {
+            format!("Hand nonce account {} authority to {}", short_name(account, 0), authority)
+        }
+        other => format!("System: {:?}", other),
+    })
+}
+
+fn decode_token(data: &[u8], account: &dyn Fn(usize) -> Option<Pubkey>) -> Option<String> {
+    // Token-2022 extension instructions don't parse with the base layout
+    let instruction = TokenInstruction::unpack(data).ok()?;
+    Some(match instruction {
+        TokenInstruction::Transfer { amount } => format!(
+            "Transfer {} base units of tokens from {} to token account {}",
+            amount,
+            short_name(account, 0),
+            name(account, 1)
+        ),
+        TokenInstruction::TransferChecked { amount, decimals } => format!(
+            "Transfer {} of mint {} to token account {}",
+            ui_amount(amount, decimals),
+            short_name(account, 1),
+            name(account, 2)
+        ),
+        TokenInstruction::Approve { amount } if amount == u64::MAX => format!(
+            "Approve {} to spend everything in {}",
+            name(account, 1),
+            short_name(account, 0)
+        ),
+        TokenInstruction::Approve { amount } => format!(
+            "Approve {} to spend {} base units from {}",
+            name(account, 1),
+            amount,
+            short_name(account, 0)
+        ),
+        TokenInstruction::ApproveChecked { amount, decimals } => format!(
+            "Approve {} to spend {} from {}",
+            name(account, 2),
+            ui_amount(amount, decimals),
+            short_name(account, 0)
+        ),
+        TokenInstruction::Revoke => format!("Revoke the delegate of {}", short_name(account, 0)),
+        TokenInstruction::SetAuthority { authority_type, new_authority } => {
+            match Option::<Pubkey>::from(new_authority) {
+                Some(new) => format!("Set {:?} authority of {} to {}", authority_type, short_name(account, 0), new),
+                None => format!("Remove {:?} authority of {}", authority_type, short_name(account, 0)),
+            }
+        }
+        TokenInstruction::MintTo { amount } => format!(
+            "Mint {} base units of {} to {}",
+            amount,
+            short_name(account, 0),
+            name(account, 1)
+        ),
+        TokenInstruction::MintToChecked { amount, decimals } => format!(
+            "Mint {} of {} to {}",
+            ui_amount(amount, decimals),
+            short_name(account, 0),
+            name(account, 1)
+        ),
+        TokenInstruction::Burn { amount } => format!(
+            "Burn {} base units from {}",
+            amount,
+            short_name(account, 0)
+        ),
+        TokenInstruction::BurnChecked { amount, decimals } => format!(
+            "Burn {} from {}",
+            ui_amount(amount, decimals),
+            short_name(account, 0)
+        ),
+        TokenInstruction::CloseAccount => format!(
+            "Close token account {} and send its rent to {}",
+            short_name(account, 0),
+            name(account, 1)
+        ),
+        TokenInstruction::FreezeAccount => format!("Freeze token account {}", short_name(account, 0)),
+        TokenInstruction::ThawAccount => format!("Thaw token account {}", short_name(account, 0)),
+        TokenInstruction::SyncNative => format!("Sync wrapped SOL balance of {}", short_name(account, 0)),
+        TokenInstruction::InitializeAccount
+        | TokenInstruction::InitializeAccount2 { .. }
+        | TokenInstruction::InitializeAccount3 { .. } => {
+            format!("Initialize token account {}", short_name(account, 0))
+        }
+        other => format!("Token: {:?}", other),
+    })
+}
+
+fn decode_associated_token_account(data: &[u8], account: &dyn Fn(usize) -> Option<Pubkey>) -> Option<String> {
+    let action = match data.first() {
+        None | Some(0) => "Create",
+        Some(1) => "Create (if missing)",
+        Some(2) => "Recover nested",
+        Some(_) => return None,
+    };
+    Some(format!(
+        "{} token account {} for {} (mint {})",
+        action,
+        short_name(account, 1),
+        name(account, 2),
+        short_name(account, 3)
+    ))
+}
+
+fn decode_stake(data: &[u8], account: &dyn Fn(usize) -> Option<Pubkey>) -> Option<String> {
+    let instruction = bincode::deserialize::<StakeInstruction>(data).ok()?;
+    let role = |kind: StakeAuthorize| match kind {
+        StakeAuthorize::Staker => "stake",
+        StakeAuthorize::Withdrawer => "withdraw",
+    };
+    Some(match instruction {
+        StakeInstruction::Initialize(authorized, _) => format!(
+            "Initialize stake account {} (staker {}, withdrawer {})",
+            short_name(account, 0),
+            short(&authorized.staker),
+            short(&authorized.withdrawer)
+        ),
+        StakeInstruction::InitializeChecked => format!("Initialize stake account {}", short_name(account, 0)),
+        StakeInstruction::DelegateStake => format!(
+            "Delegate stake account {} to validator {}",
+            short_name(account, 0),
+            name(account, 1)
+        ),
+        StakeInstruction::Deactivate => format!("Deactivate stake account {}", short_name(account, 0)),
+        StakeInstruction::Withdraw(lamports) => format!(
+            "Withdraw {} SOL from stake account {} to {}",
+            sol(lamports),
+            short_name(account, 0),
+            name(account, 1)
+        ),
+        StakeInstruction::Split(lamports) => format!(
+            "Split {} SOL from stake account {} into {}",
+            sol(lamports),
+            short_name(account, 0),
+            short_name(account, 1)
+        ),
+        StakeInstruction::Merge => format!(
+            "Merge stake account {} into {}",
+            short_name(account, 1),
+            short_name(account, 0)
+        ),
+        StakeInstruction::Authorize(new_authority, kind) => format!(
+            "Hand {} authority of stake account {} to {}",
+            role(kind),
+            short_name(account, 0),
+            new_authority
+        ),
+        StakeInstruction::AuthorizeChecked(kind) => format!(
+            "Hand {} authority of stake account {} to {}",
+            role(kind),
+            short_name(account, 0),
+            name(account, 3)
+        ),
+        other => format!("Stake: {:?}", other),
+    })
+}
+
+fn decode_compute_budget(data: &[u8]) -> Option<String> {
+    match data.split_first()? {
+        (2, rest) => {
+            let units = u32::from_le_bytes(rest.get(..4)?.try_into().ok()?);
+            Some(format!("Set compute unit limit to {}", units))
+        }
+        (3, rest) => {
+            let price = u64::from_le_bytes(rest.get(..8)?.try_into().ok()?);
+            Some(format!("Set priority fee to {} micro-lamports per unit", price))
+        }
+        _ => Some("Compute budget setting".to_string()),
+    }
+}
+
+/// One line per instruction, numbered
+pub fn describe_message(message: &VersionedMessage) -> Vec<String> {
+    decode_message(message)
+        .into_iter()
+        .enumerate()
+        .map(|(i, decoded)| format!("#{} {}", i + 1, decoded.summary))
+        .collect()
+}
+
+pub fn describe_transaction(transaction: &VersionedTransaction) -> Vec<String> {
+    describe_message(&transaction.message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        system_instruction,
+    };
+
+    fn message_for(instructions: &[Instruction], payer: &Pubkey) -> VersionedMessage {
+        VersionedMessage::Legacy(Message::new_with_blockhash(instructions, Some(payer), &Hash::new_unique()))
+    }
+
+    #[test]
+    fn test_ui_amount() {
+        assert_eq!(ui_amount(1_500_000_000, 9), "1.5");
+        assert_eq!(ui_amount(2_000_000, 6), "2");
+        assert_eq!(ui_amount(1, 6), "0.000001");
+        assert_eq!(ui_amount(42, 0), "42");
+    }
+
+    #[test]
+    fn test_decode_common_instructions() {
+        let payer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            ComputeBudgetInstruction::set_compute_unit_price(1_000),
+            system_instruction::transfer(&payer, &recipient, 500_000_000),
+            spl_token::instruction::transfer_checked(
+                &spl_token::id(), &source, &mint, &destination, &payer, &[], 2_500_000, 6,
+            ).unwrap(),
+            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &payer, &recipient, &mint, &spl_token::id(),
+            ),
+        ];
+        let lines = describe_message(&message_for(&instructions, &payer));
+        assert_eq!(lines[0], "#1 Set compute unit limit to 200000");
+        assert_eq!(lines[1], "#2 Set priority fee to 1000 micro-lamports per unit");
+        assert_eq!(lines[2], format!("#3 Transfer 0.5 SOL to {}", recipient));
+        assert!(lines[3].starts_with("#4 Transfer 2.5 of mint"));
+        assert!(lines[3].ends_with(&destination.to_string()));
+        assert!(lines[4].starts_with("#5 Create (if missing) token account"));
+    }
+
+    #[test]
+    fn test_decode_stake_and_unknown_programs() {
+        let payer = Pubkey::new_unique();
+        let stake_account = Pubkey::new_unique();
+        let vote = Pubkey::new_unique();
+        let unknown = Pubkey::new_unique();
+        let instructions = vec![
+            stake::instruction::delegate_stake(&stake_account, &payer, &vote),
+            stake::instruction::deactivate_stake(&stake_account, &payer),
+            Instruction::new_with_bytes(unknown, &[1, 2, 3], vec![AccountMeta::new(payer, true)]),
+        ];
+        let decoded = decode_message(&message_for(&instructions, &payer));
+        assert_eq!(decoded[0].program, "Stake Program");
+        assert!(decoded[0].summary.ends_with(&vote.to_string()));
+        assert!(decoded[1].summary.starts_with("Deactivate stake account"));
+        assert_eq!(decoded[2].program, unknown.to_string());
+        assert!(decoded[2].summary.contains("1 accounts, 3 bytes of data"));
+    }
+}
@@ -4,7 +4,8 @@ use solana_client::nonblocking::rpc_client::RpcClient;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 
 use crate::ans_resolver::resolve_ans_domain;
@@ -258,6 +259,17 @@ impl DomainResolver {
         }
     }
 
+    /// Drop one domain from the caches so the next lookup goes to the network
+    pub fn forget(&self, domain: &str) {
+        let domain = domain.trim().to_lowercase();
+        if let Ok(mut cache) = self.sns_cache.lock() {
+            cache.remove(&format!("sns:{}", self.trim_sol_tld(&domain)));
+        }
+        if let Ok(mut cache) = self.ans_cache.lock() {
+            cache.remove(&format!("ans:{}", domain));
+        }
+    }
+
     /// Clear all caches
     pub fn clear_cache(&self) {
         if let Ok(mut cache) = self.sns_cache.lock() {
@@ -287,4 +299,110 @@ impl Clone for DomainResolver {
             ans_cache: self.ans_cache.clone(),
         }
     }
-}
\ No newline at end of file
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Recipient resolution
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// How long a resolved domain is trusted before it is looked up again.
+/// Domains can change hands, so this stays short.
+const RECIPIENT_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+static RECIPIENT_RESOLVER: LazyLock<DomainResolver> = LazyLock::new(|| {
+    DomainResolver::new("https://johna-k3cr1v-fast-mainnet.helius-rpc.com".to_string())
+});
+
+static RECIPIENT_CACHE: LazyLock<Mutex<HashMap<String, (Pubkey, Instant)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A recipient typed by the user, after resolution
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedRecipient {
+    pub pubkey: Pubkey,
+    /// The domain it was resolved from, lowercased
+    pub domain: Option<String>,
+}
+
+impl ResolvedRecipient {
+    /// e.g. "SNS Domain: bonfida.sol" or "Direct address"
+    pub fn description(&self) -> String {
+        match &self.domain {
+            Some(domain) if RECIPIENT_RESOLVER.is_ans_domain(domain) => format!("ANS Domain: {}", domain),
+            Some(domain) => format!("SNS Domain: {}", domain),
+            None => "Direct address".to_string(),
+        }
+    }
+}
+
+/// What the user typed into a recipient field
+#[derive(Debug, Clone, PartialEq)]
+enum RecipientInput {
+    Address(Pubkey),
+    Domain(String),
+}
+
+fn parse_recipient_input(input: &str) -> Result<RecipientInput, String> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err("Address cannot be empty".to_string());
+    }
+    if RECIPIENT_RESOLVER.is_solana_pubkey(trimmed) {
+        return Pubkey::from_str(trimmed)
+            .map(RecipientInput::Address)
+            .map_err(|e| format!("Invalid public key: {}", e));
+    }
+    if RECIPIENT_RESOLVER.is_domain(trimmed) {
+        return Ok(RecipientInput::Domain(trimmed.to_lowercase()));
+    }
+    Err("Enter a valid Solana address or domain (.sol, .abc, .bonk, etc.)".to_string())
+}
+
+/// Resolve anything a user can type as a recipient: a base58 address, an SNS
+/// `.sol` name or an AllDomains (ANS) name. Every address field goes through
+/// this so they all accept the same inputs.
+pub async fn resolve_recipient(input: &str) -> Result<ResolvedRecipient, String> {
+    let domain = match parse_recipient_input(input)? {
+        RecipientInput::Address(pubkey) => return Ok(ResolvedRecipient { pubkey, domain: None }),
+        RecipientInput::Domain(domain) => domain,
+    };
+
+    if let Some((pubkey, resolved_at)) = RECIPIENT_CACHE.lock().unwrap().get(&domain).copied() {
+        if resolved_at.elapsed() < RECIPIENT_CACHE_TTL {
+            return Ok(ResolvedRecipient { pubkey, domain: Some(domain) });
+        }
+    }
+
+    RECIPIENT_RESOLVER.forget(&domain);
+    let pubkey = match RECIPIENT_RESOLVER.resolve_domain_async(&domain).await {
+        Ok(pubkey) => pubkey,
+        Err(DomainError::NotFound) => return Err(format!("Domain '{}' not found", domain)),
+        Err(e) => return Err(format!("Failed to resolve domain '{}': {:?}", domain, e)),
+    };
+    RECIPIENT_CACHE.lock().unwrap().insert(domain.clone(), (pubkey, Instant::now()));
+    println!("🔎 Resolved {} to {}", domain, pubkey);
+    Ok(ResolvedRecipient { pubkey, domain: Some(domain) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recipient_input() {
+        let pubkey = Pubkey::new_unique();
+        assert_eq!(parse_recipient_input(&format!(" {} ", pubkey)), Ok(RecipientInput::Address(pubkey)));
+        assert_eq!(parse_recipient_input("Bonfida.SOL"), Ok(RecipientInput::Domain("bonfida.sol".to_string())));
+        assert_eq!(parse_recipient_input("miester.abc"), Ok(RecipientInput::Domain("miester.abc".to_string())));
+        assert!(parse_recipient_input("   ").is_err());
+        assert!(parse_recipient_input("not a domain.xyz").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_addresses_resolve_without_network() {
+        let pubkey = Pubkey::new_unique();
+        let resolved = resolve_recipient(&pubkey.to_string()).await.unwrap();
+        assert_eq!(resolved, ResolvedRecipient { pubkey, domain: None });
+        assert_eq!(resolved.description(), "Direct address");
+    }
+}
@@ -8,6 +8,24 @@ use jni::JNIEnv;
 use dioxus::mobile::wry::prelude::dispatch;
 #[cfg(target_os = "android")]
 use crate::hardware::protocol::{Command, Response, format_esp32_command, parse_esp32_response};
+#[cfg(target_os = "android")]
+use crate::hardware::usb_grants::{find_grant, UsbDeviceGrant};
+#[cfg(target_os = "android")]
+use crate::storage::{load_usb_device_grants, save_usb_device_grant};
+
+/// Broadcast action for the USB permission prompt result
+#[cfg(target_os = "android")]
+const USB_PERMISSION_ACTION: &str = "com.unruggable.USB_PERMISSION";
+/// `PendingIntent.FLAG_MUTABLE`; the system fills in the grant result
+#[cfg(target_os = "android")]
+const PENDING_INTENT_FLAG_MUTABLE: i32 = 0x0200_0000;
+/// How long to wait for the user to answer the permission prompt
+#[cfg(target_os = "android")]
+const PERMISSION_TIMEOUT_SECS: u64 = 30;
+/// Foreground service that keeps the USB link alive while the device waits
+/// for approval; added to the Android project by `scripts/android.bundle.sh`
+#[cfg(target_os = "android")]
+const SIGNING_SERVICE_CLASS: &str = "com/unruggable/usb/HardwareSigningService";
 
 #[derive(Debug, Clone)]
 pub struct StorageError(String);
@@ -41,6 +59,9 @@ pub struct AndroidUsbDevice {
     pub device_name: String,
     pub manufacturer: Option<String>,
     pub product_name: Option<String>,
+    /// Only readable once the app holds permission for the device
+    pub serial_number: Option<String>,
+    pub has_permission: bool,
 }
 
 #[cfg(target_os = "android")]
@@ -83,6 +104,11 @@ impl AndroidUsbSerial {
             return Err(StorageError("No hardware wallet devices found".to_string()));
         }
 
+        // Devices the user granted before go first
+        let grants = load_usb_device_grants();
+        let mut devices = devices;
+        devices.sort_by_key(|d| find_grant(&grants, d.vendor_id, d.product_id, d.serial_number.as_deref()).is_none());
+
         // Try to connect to the first compatible device
         for device in devices {
             if Self::is_hardware_wallet_device(device.vendor_id, device.product_id) {
@@ -102,8 +128,78 @@ impl AndroidUsbSerial {
         Err(StorageError("Failed to connect to any hardware wallet device".to_string()))
     }
 
+    /// Reconnect to a device the user granted access to before, without
+    /// touching devices they never approved
+    pub async fn find_and_connect_known(&mut self) -> Result<(), StorageError> {
+        let grants = load_usb_device_grants();
+        let devices = Self::scan_for_devices().await?;
+        let known: Vec<_> = devices
+            .into_iter()
+            .filter(|d| find_grant(&grants, d.vendor_id, d.product_id, d.serial_number.as_deref()).is_some())
+            .collect();
+
+        for device in known {
+            match self.connect_to_device(&device).await {
+                Ok(_) => return Ok(()),
+                Err(e) => log::warn!("❌ Failed to reattach {}: {}", device.device_name, e),
+            }
+        }
+        Err(StorageError("No previously granted hardware wallet is attached".to_string()))
+    }
+
+    /// Ask for permission to use `device` and wait for the user's answer
+    async fn ensure_permission(device: &AndroidUsbDevice) -> Result<(), StorageError> {
+        if Self::device_permission(device, true).await? {
+            return Ok(());
+        }
+        log::info!("🔐 Waiting for USB permission for {}", device.device_name);
+        for _ in 0..PERMISSION_TIMEOUT_SECS * 2 {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            if Self::device_permission(device, false).await? {
+                log::info!("✅ USB permission granted for {}", device.device_name);
+                return Ok(());
+            }
+        }
+        Err(StorageError("USB permission was not granted".to_string()))
+    }
+
+    /// Whether the app may open `device`; asks the system when `request` is set and it may not
+    async fn device_permission(device: &AndroidUsbDevice, request: bool) -> Result<bool, StorageError> {
+        let device_clone = device.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        dispatch(move |env, activity, _webview| {
+            let result = Self::java_device_permission(env, activity, &device_clone, request);
+            if result.is_err() {
+                let _ = env.exception_clear();
+            }
+            tx.send(result).unwrap();
+        });
+
+        match rx.recv() {
+            Ok(result) => result,
+            Err(e) => Err(StorageError(format!("Channel receive error: {}", e))),
+        }
+    }
+
+    /// Remember the connected device so it is reattached after backgrounding
+    pub fn remember_grant(&self, public_key: &str) {
+        let Some(device) = &self.device_info else { return };
+        let grant = UsbDeviceGrant {
+            vendor_id: device.vendor_id,
+            product_id: device.product_id,
+            serial_number: device.serial_number.clone(),
+            public_key: Some(public_key.to_string()),
+            granted_at: chrono::Utc::now().timestamp() as u64,
+        };
+        if let Err(e) = save_usb_device_grant(&grant) {
+            log::warn!("⚠️ Failed to remember USB device: {}", e);
+        }
+    }
+
     /// Connect to a specific USB device
     pub async fn connect_to_device(&mut self, device: &AndroidUsbDevice) -> Result<(), StorageError> {
+        Self::ensure_permission(device).await?;
         let device_clone = device.clone();
         let (tx, rx) = std::sync::mpsc::channel();
 
@@ -114,9 +210,13 @@ impl AndroidUsbSerial {
 
         match rx.recv() {
             Ok(result) => match result {
-                Ok(port_global) => {
+                Ok((port_global, serial_number)) => {
                     self.port = Some(port_global);
-                    self.device_info = Some(device.clone());
+                    self.device_info = Some(AndroidUsbDevice {
+                        serial_number: serial_number.or_else(|| device.serial_number.clone()),
+                        has_permission: true,
+                        ..device.clone()
+                    });
                     log::info!("✅ Connected to USB serial device: {}", device.device_name);
                     Ok(())
                 }
@@ -192,12 +292,16 @@ impl AndroidUsbSerial {
             };
 
             if Self::is_hardware_wallet_device(vendor_id, product_id) {
+                let has_permission = env.call_method(&usb_manager, "hasPermission", "(Landroid/hardware/usb/UsbDevice;)Z", &[(&usb_device).into()])?.z()?;
+                let serial_number = if has_permission { Self::java_serial_number(env, &usb_device) } else { None };
                 let hw_device = AndroidUsbDevice {
                     vendor_id,
                     product_id,
                     device_name: device_name_str,
                     manufacturer: None,
                     product_name: None,
+                    serial_number,
+                    has_permission,
                 };
                 hardware_devices.push(hw_device);
                 log::info!("🔍 Found potential hardware wallet: {:04X}:{:04X}", vendor_id, product_id);
@@ -206,11 +310,84 @@ impl AndroidUsbSerial {
         Ok(hardware_devices)
     }
 
+    /// `UsbDevice.getSerialNumber()`, which throws without permission
+    fn java_serial_number(env: &mut JNIEnv<'_>, usb_device: &JObject<'_>) -> Option<String> {
+        let serial = match env.call_method(usb_device, "getSerialNumber", "()Ljava/lang/String;", &[]).and_then(|v| v.l()) {
+            Ok(serial) => serial,
+            Err(_) => {
+                let _ = env.exception_clear();
+                return None;
+            }
+        };
+        if serial.is_null() {
+            return None;
+        }
+        env.get_string(&JString::from(serial)).ok().map(|s| s.into())
+    }
+
+    fn java_device_permission(
+        env: &mut JNIEnv<'_>,
+        activity: &JObject<'_>,
+        device: &AndroidUsbDevice,
+        request: bool,
+    ) -> Result<bool, StorageError> {
+        let usb_service = env.get_static_field("android/content/Context", "USB_SERVICE", "Ljava/lang/String;")?.l()?;
+        let usb_manager = env.call_method(activity, "getSystemService", "(Ljava/lang/String;)Ljava/lang/Object;", &[(&usb_service).into()])?.l()?;
+
+        let device_list = env.call_method(&usb_manager, "getDeviceList", "()Ljava/util/HashMap;", &[])?.l()?;
+        let values = env.call_method(&device_list, "values", "()Ljava/util/Collection;", &[])?.l()?;
+        let iterator = env.call_method(&values, "iterator", "()Ljava/util/Iterator;", &[])?.l()?;
+
+        while env.call_method(&iterator, "hasNext", "()Z", &[])?.z()? {
+            let usb_device = env.call_method(&iterator, "next", "()Ljava/lang/Object;", &[])?.l()?;
+            let vendor_id = env.call_method(&usb_device, "getVendorId", "()I", &[])?.i()?;
+            let product_id = env.call_method(&usb_device, "getProductId", "()I", &[])?.i()?;
+            if vendor_id != device.vendor_id || product_id != device.product_id {
+                continue;
+            }
+
+            let granted = env.call_method(&usb_manager, "hasPermission", "(Landroid/hardware/usb/UsbDevice;)Z", &[(&usb_device).into()])?.z()?;
+            if granted || !request {
+                return Ok(granted);
+            }
+
+            // Ask for this one device only; the result is polled with hasPermission
+            let action = env.new_string(USB_PERMISSION_ACTION)?;
+            let intent = env.new_object("android/content/Intent", "(Ljava/lang/String;)V", &[(&action).into()])?;
+            let package = env.call_method(activity, "getPackageName", "()Ljava/lang/String;", &[])?.l()?;
+            env.call_method(&intent, "setPackage", "(Ljava/lang/String;)Landroid/content/Intent;", &[(&package).into()])?;
+            let pending_intent = env.call_static_method(
+                "android/app/PendingIntent",
+                "getBroadcast",
+                "(Landroid/content/Context;ILandroid/content/Intent;I)Landroid/app/PendingIntent;",
+                &[activity.into(), 0.into(), (&intent).into(), PENDING_INTENT_FLAG_MUTABLE.into()],
+            )?.l()?;
+            env.call_method(&usb_manager, "requestPermission", "(Landroid/hardware/usb/UsbDevice;Landroid/app/PendingIntent;)V", &[(&usb_device).into(), (&pending_intent).into()])?;
+            log::info!("🔐 Requested USB permission for {:04X}:{:04X}", vendor_id, product_id);
+            return Ok(false);
+        }
+
+        Err(StorageError("Hardware wallet is no longer attached".to_string()))
+    }
+
+    fn java_set_signing_service(env: &mut JNIEnv<'_>, activity: &JObject<'_>, running: bool) -> Result<(), StorageError> {
+        let service_class = env.find_class(SIGNING_SERVICE_CLASS)?;
+        let intent = env.new_object("android/content/Intent", "(Landroid/content/Context;Ljava/lang/Class;)V", &[activity.into(), (&service_class).into()])?;
+        if !running {
+            env.call_method(activity, "stopService", "(Landroid/content/Intent;)Z", &[(&intent).into()])?;
+            return Ok(());
+        }
+        let sdk_int = env.get_static_field("android/os/Build$VERSION", "SDK_INT", "I")?.i()?;
+        let method = if sdk_int >= 26 { "startForegroundService" } else { "startService" };
+        env.call_method(activity, method, "(Landroid/content/Intent;)Landroid/content/ComponentName;", &[(&intent).into()])?;
+        Ok(())
+    }
+
     fn java_connect_usb_serial_device(
         env: &mut JNIEnv<'_>,
         activity: &JObject<'_>,
         device: &AndroidUsbDevice,
-    ) -> Result<GlobalRef, StorageError> {
+    ) -> Result<(GlobalRef, Option<String>), StorageError> {
         log::info!("🔄 Connecting to USB serial device: {:04X}:{:04X}", device.vendor_id, device.product_id);
 
         let usb_service = env.get_static_field("android/content/Context", "USB_SERVICE", "Ljava/lang/String;")?.l()?;
@@ -250,8 +427,9 @@ impl AndroidUsbSerial {
         env.call_method(&port, "setParameters", "(IIII)V", &[115200.into(), 8.into(), 1.into(), 0.into()])?;
 
         let port_global = env.new_global_ref(&port)?;
+        let serial_number = Self::java_serial_number(env, &usb_device);
         log::info!("✅ USB serial connection established");
-        Ok(port_global)
+        Ok((port_global, serial_number))
     }
 
     fn java_usb_serial_transfer(
@@ -317,4 +495,34 @@ impl AndroidUsbSerial {
             _ => false,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Keeps the signing foreground service running while alive, so Android
+/// doesn't cut the USB link if the app is backgrounded mid-approval
+#[cfg(target_os = "android")]
+pub struct SigningServiceGuard;
+
+#[cfg(target_os = "android")]
+impl SigningServiceGuard {
+    pub fn start() -> Self {
+        set_signing_service(true);
+        SigningServiceGuard
+    }
+}
+
+#[cfg(target_os = "android")]
+impl Drop for SigningServiceGuard {
+    fn drop(&mut self) {
+        set_signing_service(false);
+    }
+}
+
+#[cfg(target_os = "android")]
+fn set_signing_service(running: bool) {
+    dispatch(move |env, activity, _webview| {
+        if let Err(e) = AndroidUsbSerial::java_set_signing_service(env, activity, running) {
+            let _ = env.exception_clear();
+            log::warn!("⚠️ Signing service unavailable: {}", e);
+        }
+    });
+}
@@ -5,11 +5,12 @@ pub mod serial;
 pub mod android_usb;
 
 pub mod protocol;
+pub mod usb_grants;
 // Only include ledger module on desktop platforms (not mobile)
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub mod ledger;
 
-use protocol::{Command, Response};
+use protocol::{Command, Response, TrustedAddress};
 use std::error::Error;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -86,6 +87,19 @@ impl HardwareWallet {
         Self::is_esp32_present() || Self::is_ledger_present()
     }
 
+    /// Like `is_device_present`, but also scans USB on Android where that
+    /// has to go through the (async) Java bridge
+    pub async fn detect_device_present() -> bool {
+        #[cfg(target_os = "android")]
+        {
+            android_usb::AndroidUsbSerial::check_device_presence().await
+        }
+        #[cfg(not(target_os = "android"))]
+        {
+            Self::is_device_present()
+        }
+    }
+
     /// Check if ESP32 devices are present
     pub fn is_esp32_present() -> bool {
         #[cfg(not(target_os = "android"))]
@@ -221,6 +235,7 @@ impl HardwareWallet {
                     if let Err(e) = bs58::decode(&pubkey).into_vec() {
                         return Err(format!("Invalid public key format: {}", e).into());
                     }
+                    connection.remember_grant(&pubkey);
                     *self.public_key.lock().await = Some(pubkey);
                     *self.device_type.lock().await = Some(HardwareDeviceType::ESP32);
                 }
@@ -238,6 +253,42 @@ impl HardwareWallet {
         Ok(())
     }
 
+    /// Make sure the USB link to a connected ESP32 still works, reopening it
+    /// when Android dropped it while the app was in the background. Only
+    /// previously granted devices reporting the same key are reattached.
+    pub async fn reattach(&self) -> Result<(), Box<dyn Error>> {
+        #[cfg(target_os = "android")]
+        {
+            if self.get_device_type().await != Some(HardwareDeviceType::ESP32) {
+                return Ok(());
+            }
+            let expected = self.get_public_key().await?;
+            let mut esp32_guard = self.esp32_connection.lock().await;
+            if let Some(connection) = esp32_guard.as_ref() {
+                if matches!(connection.send_command(Command::GetPubkey).await, Ok(Response::Pubkey(ref key)) if *key == expected) {
+                    return Ok(());
+                }
+            }
+            if let Some(mut stale) = esp32_guard.take() {
+                stale.disconnect().await;
+            }
+
+            let mut connection = android_usb::AndroidUsbSerial::new();
+            connection.find_and_connect_known().await
+                .map_err(|e| format!("Failed to reattach hardware wallet: {}", e))?;
+            match connection.send_command(Command::GetPubkey).await
+                .map_err(|e| format!("Failed to get public key: {}", e))? {
+                Response::Pubkey(key) if key == expected => {}
+                Response::Pubkey(_) => return Err("A different hardware wallet is attached".into()),
+                Response::Error(e) => return Err(format!("Hardware wallet error: {}", e).into()),
+                _ => return Err("Unexpected response from hardware wallet".into()),
+            }
+            *esp32_guard = Some(connection);
+            log::info!("🔌 Reattached hardware wallet after returning to the foreground");
+        }
+        Ok(())
+    }
+
     /// Connect specifically to a Ledger device (desktop only)
     pub async fn connect_ledger(&self) -> Result<(), Box<dyn Error>> {
         #[cfg(not(any(target_os = "android", target_os = "ios")))]
@@ -317,6 +368,8 @@ impl HardwareWallet {
         
         match device_type {
             Some(HardwareDeviceType::ESP32) => {
+                #[cfg(target_os = "android")]
+                let _service = android_usb::SigningServiceGuard::start();
                 let response = self.send_command(Command::SignMessage(message.to_vec())).await?;
                 match response {
                     Response::Signature(sig) => Ok(sig),
@@ -344,6 +397,60 @@ impl HardwareWallet {
         }
     }
     
+    /// Ensure the connected device supports the on-device address book (ESP32 only)
+    async fn require_esp32(&self) -> Result<(), Box<dyn Error>> {
+        match self.get_device_type().await {
+            Some(HardwareDeviceType::ESP32) => Ok(()),
+            Some(HardwareDeviceType::Ledger) => Err("Address book is only supported on the Unruggable device".into()),
+            None => Err("No hardware wallet connected".into()),
+        }
+    }
+
+    /// Send an address book command that is answered with `OK`
+    async fn send_address_book_command(&self, command: Command) -> Result<(), Box<dyn Error>> {
+        self.require_esp32().await?;
+        match self.send_command(command).await? {
+            Response::Ok => Ok(()),
+            Response::Error(e) => Err(format!("Hardware wallet error: {}", e).into()),
+            _ => Err("Unexpected response from hardware wallet".into()),
+        }
+    }
+
+    /// Store a trusted recipient on the device (confirmed on the device screen)
+    pub async fn add_trusted_address(&self, address: &str, label: &str) -> Result<(), Box<dyn Error>> {
+        if bs58::decode(address).into_vec().map(|b| b.len()).unwrap_or(0) != 32 {
+            return Err(format!("Invalid address: {}", address).into());
+        }
+        self.send_address_book_command(Command::AddTrustedAddress(TrustedAddress {
+            address: address.to_string(),
+            label: label.to_string(),
+        })).await?;
+        log::info!("📇 Added trusted address {} to device", address);
+        Ok(())
+    }
+
+    /// Remove a trusted recipient from the device
+    pub async fn remove_trusted_address(&self, address: &str) -> Result<(), Box<dyn Error>> {
+        self.send_address_book_command(Command::RemoveTrustedAddress(address.to_string())).await?;
+        log::info!("📇 Removed trusted address {} from device", address);
+        Ok(())
+    }
+
+    /// Remove every trusted recipient from the device
+    pub async fn clear_trusted_addresses(&self) -> Result<(), Box<dyn Error>> {
+        self.send_address_book_command(Command::ClearTrustedAddresses).await
+    }
+
+    /// Read the trusted recipients stored on the device
+    pub async fn list_trusted_addresses(&self) -> Result<Vec<TrustedAddress>, Box<dyn Error>> {
+        self.require_esp32().await?;
+        match self.send_command(Command::ListTrustedAddresses).await? {
+            Response::TrustedAddresses(entries) => Ok(entries),
+            Response::Error(e) => Err(format!("Hardware wallet error: {}", e).into()),
+            _ => Err("Unexpected response from hardware wallet".into()),
+        }
+    }
+
     /// Disconnect from the device (enhanced - supports both devices)
     pub async fn disconnect(&self) -> Result<(), Box<dyn Error>> {
         // Disconnect ESP32
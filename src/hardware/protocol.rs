@@ -2,11 +2,28 @@
 use std::error::Error;
 use base64::Engine; // Add this import
 
+/// Maximum label length the device can show next to a trusted address
+pub const MAX_TRUSTED_LABEL_LEN: usize = 20;
+
+/// Recipient stored in the device address book.
+///
+/// While signing, the device marks recipients found in its address book as
+/// "trusted" on screen and warns for any recipient it does not know.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrustedAddress {
+    pub address: String,
+    pub label: String,
+}
+
 /// Command types that can be sent to the hardware wallet
 #[derive(Debug, Clone)]
 pub enum Command {
     GetPubkey,
     SignMessage(Vec<u8>),
+    AddTrustedAddress(TrustedAddress),
+    RemoveTrustedAddress(String),
+    ListTrustedAddresses,
+    ClearTrustedAddresses,
 }
 
 /// Response types from the hardware wallet
@@ -14,6 +31,8 @@ pub enum Command {
 pub enum Response {
     Pubkey(String),
     Signature(Vec<u8>),
+    TrustedAddresses(Vec<TrustedAddress>),
+    Ok,
     Error(String),
 }
 
@@ -29,6 +48,15 @@ pub fn format_esp32_command(cmd: &Command) -> Vec<u8> {
             formatted.push(b'\n');
             formatted
         }
+        Command::AddTrustedAddress(entry) => {
+            // Labels are base64 encoded so they can't clash with the ':' separator
+            let label: String = entry.label.chars().take(MAX_TRUSTED_LABEL_LEN).collect();
+            let encoded = base64::engine::general_purpose::STANDARD.encode(label.as_bytes());
+            format!("ADDR_ADD:{}:{}\n", entry.address, encoded).into_bytes()
+        }
+        Command::RemoveTrustedAddress(address) => format!("ADDR_DEL:{}\n", address).into_bytes(),
+        Command::ListTrustedAddresses => b"ADDR_LIST\n".to_vec(),
+        Command::ClearTrustedAddresses => b"ADDR_CLEAR\n".to_vec(),
     }
 }
 
@@ -45,10 +73,52 @@ pub fn parse_esp32_response(data: &[u8]) -> Result<Response, Box<dyn Error>> {
         // Use the standard base64 engine
         let sig_bytes = base64::engine::general_purpose::STANDARD.decode(sig_b64)?;
         Ok(Response::Signature(sig_bytes))
+    } else if let Some(list) = response_str.strip_prefix("ADDRS:") {
+        // Format: ADDRS:<address>=<label_b64>,<address>=<label_b64>
+        let mut entries = Vec::new();
+        for item in list.split(',').filter(|item| !item.is_empty()) {
+            let (address, label_b64) = item.split_once('=').unwrap_or((item, ""));
+            let label = base64::engine::general_purpose::STANDARD.decode(label_b64)?;
+            entries.push(TrustedAddress {
+                address: address.to_string(),
+                label: String::from_utf8_lossy(&label).to_string(),
+            });
+        }
+        Ok(Response::TrustedAddresses(entries))
+    } else if response_str == "OK" {
+        Ok(Response::Ok)
     } else if response_str.starts_with("ERROR:") {
         let error = response_str.strip_prefix("ERROR:").unwrap();
         Ok(Response::Error(error.to_string()))
     } else {
         Err(format!("Unknown response format: {}", response_str).into())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_trusted_address_command() {
+        let cmd = Command::AddTrustedAddress(TrustedAddress {
+            address: "11111111111111111111111111111111".to_string(),
+            label: "Cold: savings".to_string(),
+        });
+        let formatted = String::from_utf8(format_esp32_command(&cmd)).unwrap();
+        assert_eq!(formatted, "ADDR_ADD:11111111111111111111111111111111:Q29sZDogc2F2aW5ncw==\n");
+    }
+
+    #[test]
+    fn test_parse_trusted_address_list() {
+        match parse_esp32_response(b"ADDRS:abc=Qm9i,def=\n").unwrap() {
+            Response::TrustedAddresses(entries) => {
+                assert_eq!(entries.len(), 2);
+                assert_eq!(entries[0].label, "Bob");
+                assert_eq!(entries[1].address, "def");
+            }
+            other => panic!("unexpected response: {:?}", other),
+        }
+        assert!(matches!(parse_esp32_response(b"ADDRS:\n").unwrap(), Response::TrustedAddresses(e) if e.is_empty()));
+    }
+}
@@ -0,0 +1,90 @@
+// src/hardware/usb_grants.rs
+//! USB devices the user has granted access to
+//!
+//! Android forgets a USB permission as soon as the device is detached or the
+//! app is restarted. The identity of every device the user approved is kept
+//! here so the app can re-request access for that device only when it
+//! returns to the foreground. Unknown devices still need an explicit connect.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsbDeviceGrant {
+    pub vendor_id: i32,
+    pub product_id: i32,
+    /// Only readable once permission is granted; older bridges report none
+    #[serde(default)]
+    pub serial_number: Option<String>,
+    /// Wallet the device reported when it was granted
+    #[serde(default)]
+    pub public_key: Option<String>,
+    pub granted_at: u64,
+}
+
+impl UsbDeviceGrant {
+    /// Same USB identity. Devices without a serial match on VID/PID alone.
+    pub fn matches(&self, vendor_id: i32, product_id: i32, serial_number: Option<&str>) -> bool {
+        if self.vendor_id != vendor_id || self.product_id != product_id {
+            return false;
+        }
+        match (self.serial_number.as_deref(), serial_number) {
+            (Some(granted), Some(seen)) => granted == seen,
+            _ => true,
+        }
+    }
+
+    /// e.g. "303A:1001 (serial 7C:DF:A1)"
+    pub fn label(&self) -> String {
+        match &self.serial_number {
+            Some(serial) => format!("{:04X}:{:04X} (serial {})", self.vendor_id, self.product_id, serial),
+            None => format!("{:04X}:{:04X}", self.vendor_id, self.product_id),
+        }
+    }
+}
+
+/// The grant for a device, if the user approved it before
+pub fn find_grant<'a>(
+    grants: &'a [UsbDeviceGrant],
+    vendor_id: i32,
+    product_id: i32,
+    serial_number: Option<&str>,
+) -> Option<&'a UsbDeviceGrant> {
+    // Prefer an exact serial match over a VID/PID-only grant
+    grants
+        .iter()
+        .filter(|g| g.matches(vendor_id, product_id, serial_number))
+        .max_by_key(|g| g.serial_number.is_some() && serial_number.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(serial: Option<&str>) -> UsbDeviceGrant {
+        UsbDeviceGrant {
+            vendor_id: 0x303A,
+            product_id: 0x1001,
+            serial_number: serial.map(str::to_string),
+            public_key: None,
+            granted_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_grant_matching() {
+        let with_serial = grant(Some("AA"));
+        assert!(with_serial.matches(0x303A, 0x1001, Some("AA")));
+        assert!(!with_serial.matches(0x303A, 0x1001, Some("BB")));
+        assert!(with_serial.matches(0x303A, 0x1001, None));
+        assert!(!with_serial.matches(0x10C4, 0xEA60, Some("AA")));
+        assert!(grant(None).matches(0x303A, 0x1001, Some("BB")));
+    }
+
+    #[test]
+    fn test_exact_serial_preferred() {
+        let grants = vec![grant(None), grant(Some("AA"))];
+        let found = find_grant(&grants, 0x303A, 0x1001, Some("AA")).unwrap();
+        assert_eq!(found.serial_number.as_deref(), Some("AA"));
+        assert!(find_grant(&grants, 0x0403, 0x6001, None).is_none());
+    }
+}
@@ -0,0 +1,374 @@
+// src/history.rs
+//! Local transaction history index
+//!
+//! Every address's signatures are kept on the device, newest first. Opening
+//! the history only asks the RPC for signatures newer than the newest stored
+//! one; older pages are fetched once, as the list is scrolled, and parsed
+//! transactions are cached so both the list and the details work offline.
+
+use crate::rpc::{self, TransactionHistoryItem, TransactionInfo};
+use crate::storage::{
+    load_address_labels, load_cached_transactions, load_history_index, load_transaction_labels,
+    load_transaction_notes, save_address_labels, save_cached_transactions, save_history_index,
+    save_transaction_labels, save_transaction_notes,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Entries shown per page of the list
+pub const HISTORY_PAGE_SIZE: usize = 25;
+/// Signatures requested per `getSignaturesForAddress` call
+const SYNC_PAGE_SIZE: usize = 100;
+/// Pages fetched when catching up; after that the index restarts from the newest
+const MAX_SYNC_PAGES: usize = 10;
+/// Parsed transactions kept per address
+const MAX_CACHED_TRANSACTIONS: usize = 200;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryIndex {
+    pub address: String,
+    /// Newest first
+    pub entries: Vec<TransactionHistoryItem>,
+    /// The address's first transaction is stored, so there is nothing older
+    pub reached_start: bool,
+    /// Unix time of the last successful sync
+    pub synced_at: Option<i64>,
+    /// Spending category tagged on a transaction, by signature
+    #[serde(default)]
+    pub categories: HashMap<String, String>,
+}
+
+impl HistoryIndex {
+    /// The first `count` entries in display form
+    pub fn page(&self, count: usize) -> Vec<TransactionInfo> {
+        let now = chrono::Utc::now().timestamp();
+        self.entries
+            .iter()
+            .take(count)
+            .cloned()
+            .map(|item| rpc::to_transaction_info(item, now))
+            .collect()
+    }
+
+    pub fn category(&self, signature: &str) -> Option<&str> {
+        self.categories.get(signature).map(String::as_str)
+    }
+
+    /// Every category used so far, sorted
+    pub fn known_categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = self.categories.values().cloned().collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+
+    pub fn has_more(&self, shown: usize) -> bool {
+        shown < self.entries.len() || !self.reached_start
+    }
+
+    /// Put `newer` (newest first) in front of the stored entries. When it
+    /// doesn't reach back to them there may be a gap, so the stored entries
+    /// are dropped and older pages are fetched again as needed.
+    fn merge_newer(&mut self, newer: Vec<TransactionHistoryItem>, connected: bool) -> usize {
+        let known: std::collections::HashSet<String> = self.entries.iter().map(|e| e.signature.clone()).collect();
+        let fresh: Vec<TransactionHistoryItem> = newer.into_iter().filter(|item| !known.contains(&item.signature)).collect();
+        let added = fresh.len();
+        if connected {
+            self.entries.splice(0..0, fresh);
+        } else {
+            self.entries = fresh;
+            self.reached_start = false;
+        }
+        added
+    }
+
+    /// Add a page fetched before the oldest stored entry
+    fn append_older(&mut self, older: Vec<TransactionHistoryItem>, requested: usize) {
+        self.reached_start = older.len() < requested;
+        let known: std::collections::HashSet<String> = self.entries.iter().map(|e| e.signature.clone()).collect();
+        self.entries.extend(older.into_iter().filter(|item| !known.contains(&item.signature)));
+    }
+}
+
+/// Fetch signatures newer than the stored ones and save the index
+pub async fn sync_newer(address: &str, rpc_url: Option<&str>) -> Result<HistoryIndex, String> {
+    let mut index = load_history_index(address);
+    index.address = address.to_string();
+    let newest_known = index.entries.first().map(|e| e.signature.clone());
+
+    let mut newer = Vec::new();
+    let mut found_known = false;
+    let mut reached_end = false;
+    let mut before: Option<String> = None;
+    for _ in 0..MAX_SYNC_PAGES {
+        let page = rpc::get_signatures_for_address(address, before.as_deref(), SYNC_PAGE_SIZE, rpc_url).await?;
+        // A short page means there is nothing older
+        reached_end = page.len() < SYNC_PAGE_SIZE;
+        before = page.last().map(|item| item.signature.clone());
+        for item in page {
+            if Some(&item.signature) == newest_known.as_ref() {
+                found_known = true;
+                break;
+            }
+            newer.push(item);
+        }
+        if found_known || reached_end {
+            break;
+        }
+    }
+    if newest_known.is_none() {
+        index.reached_start = reached_end;
+    }
+
+    let added = index.merge_newer(newer, found_known || reached_end || newest_known.is_none());
+    index.synced_at = Some(chrono::Utc::now().timestamp());
+    save_history_index(&index)?;
+    println!("📜 History of {}: {} new, {} stored", address, added, index.entries.len());
+    Ok(index)
+}
+
+/// Fetch the page before the oldest stored entry and save the index
+pub async fn load_older(address: &str, rpc_url: Option<&str>) -> Result<HistoryIndex, String> {
+    let mut index = load_history_index(address);
+    if index.reached_start {
+        return Ok(index);
+    }
+    let before = index.entries.last().map(|e| e.signature.clone());
+    let page = rpc::get_signatures_for_address(address, before.as_deref(), SYNC_PAGE_SIZE, rpc_url).await?;
+    index.append_older(page, SYNC_PAGE_SIZE);
+    save_history_index(&index)?;
+    Ok(index)
+}
+
+/// Tag a transaction with a spending category, or clear its tag
+pub fn set_category(address: &str, signature: &str, category: Option<&str>) -> Result<HistoryIndex, String> {
+    let mut index = load_history_index(address);
+    index.address = address.to_string();
+    match category.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(category) => {
+            index.categories.insert(signature.to_string(), category.to_string());
+        }
+        None => {
+            index.categories.remove(signature);
+        }
+    }
+    save_history_index(&index)?;
+    Ok(index)
+}
+
+/// Label the user gave a transaction, e.g. "Rent — March"
+pub fn transaction_label(signature: &str) -> Option<String> {
+    load_transaction_labels().remove(signature)
+}
+
+/// Label a transaction by signature, or clear its label. Labels are kept
+/// apart from the per-address index so one can be set right after sending,
+/// before the transaction shows up in any history.
+pub fn set_transaction_label(signature: &str, label: Option<&str>) -> Result<(), String> {
+    let mut labels = load_transaction_labels();
+    set_or_clear(&mut labels, signature, label);
+    save_transaction_labels(&labels)
+}
+
+/// Longer free-form note on a transaction, or clear it
+pub fn set_transaction_note(signature: &str, note: Option<&str>) -> Result<(), String> {
+    let mut notes = load_transaction_notes();
+    set_or_clear(&mut notes, signature, note);
+    save_transaction_notes(&notes)
+}
+
+/// Name an address, e.g. "Landlord", or clear its name
+pub fn set_address_label(address: &str, label: Option<&str>) -> Result<(), String> {
+    let mut labels = load_address_labels();
+    set_or_clear(&mut labels, address, label);
+    save_address_labels(&labels)
+}
+
+fn set_or_clear(map: &mut HashMap<String, String>, key: &str, value: Option<&str>) {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(value) => {
+            map.insert(key.to_string(), value.to_string());
+        }
+        None => {
+            map.remove(key);
+        }
+    }
+}
+
+/// Named addresses a transaction's instructions mention, by walking every
+/// string in its parsed details
+pub fn labelled_addresses(details: &HashMap<String, Value>, address_labels: &HashMap<String, String>) -> Vec<(String, String)> {
+    fn walk(value: &Value, address_labels: &HashMap<String, String>, found: &mut Vec<(String, String)>) {
+        match value {
+            Value::String(text) => {
+                if let Some(label) = address_labels.get(text) {
+                    if !found.iter().any(|(address, _)| address == text) {
+                        found.push((text.clone(), label.clone()));
+                    }
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|item| walk(item, address_labels, found)),
+            Value::Object(fields) => fields.values().for_each(|field| walk(field, address_labels, found)),
+            _ => {}
+        }
+    }
+    let mut found = Vec::new();
+    if let Some(instructions) = details.get("instructions") {
+        walk(instructions, address_labels, &mut found);
+    }
+    found
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Every stored transaction of an address with its label, note and
+/// category, and the named addresses it involves where its details are cached
+pub fn history_csv(index: &HistoryIndex) -> String {
+    let labels = load_transaction_labels();
+    let notes = load_transaction_notes();
+    let address_labels = load_address_labels();
+    let cached: HashMap<String, HashMap<String, Value>> = load_cached_transactions(&index.address)
+        .into_iter()
+        .map(|c| (c.signature, c.details))
+        .collect();
+
+    let mut rows = vec![format!(
+        "# {}{}",
+        index.address,
+        address_labels.get(&index.address).map(|l| format!(" ({})", l)).unwrap_or_default()
+    )];
+    rows.push("date_utc,signature,status,category,label,note,memo,counterparties".to_string());
+    for entry in &index.entries {
+        let counterparties = cached
+            .get(&entry.signature)
+            .map(|details| {
+                labelled_addresses(details, &address_labels)
+                    .into_iter()
+                    .filter(|(address, _)| *address != index.address)
+                    .map(|(address, label)| format!("{} ({})", label, address))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            })
+            .unwrap_or_default();
+        let text = |map: &HashMap<String, String>| csv_field(map.get(&entry.signature).map(String::as_str).unwrap_or(""));
+        rows.push(format!(
+            "{},{},{},{},{},{},{},{}",
+            entry.block_time.map(crate::rpc::format_timestamp).unwrap_or_default(),
+            entry.signature,
+            if entry.err.is_some() { "Failed" } else { "Success" },
+            csv_field(index.category(&entry.signature).unwrap_or("")),
+            text(&labels),
+            text(&notes),
+            csv_field(entry.memo.as_deref().unwrap_or("")),
+            csv_field(&counterparties)
+        ));
+    }
+    rows.join("\n")
+}
+
+/// A parsed transaction kept for offline viewing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedTransaction {
+    pub signature: String,
+    pub details: HashMap<String, Value>,
+}
+
+/// Parsed details of a transaction, from the cache when possible
+pub async fn transaction_details(
+    address: &str,
+    signature: &str,
+    rpc_url: Option<&str>,
+) -> Result<HashMap<String, Value>, String> {
+    let mut cached = load_cached_transactions(address);
+    if let Some(hit) = cached.iter().find(|c| c.signature == signature) {
+        return Ok(hit.details.clone());
+    }
+
+    let details = rpc::get_transaction_details(signature, rpc_url).await?;
+    cached.insert(0, CachedTransaction { signature: signature.to_string(), details: details.clone() });
+    cached.truncate(MAX_CACHED_TRANSACTIONS);
+    if let Err(e) = save_cached_transactions(address, &cached) {
+        println!("⚠️ Transaction not cached: {}", e);
+    }
+    Ok(details)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(signature: &str) -> TransactionHistoryItem {
+        TransactionHistoryItem {
+            signature: signature.to_string(),
+            slot: 0,
+            block_time: None,
+            confirmation_status: None,
+            err: None,
+            memo: None,
+        }
+    }
+
+    fn signatures(index: &HistoryIndex) -> Vec<&str> {
+        index.entries.iter().map(|e| e.signature.as_str()).collect()
+    }
+
+    #[test]
+    fn test_merge_newer_prepends() {
+        let mut index = HistoryIndex { entries: vec![item("c"), item("d")], reached_start: true, ..Default::default() };
+        assert_eq!(index.merge_newer(vec![item("a"), item("b")], true), 2);
+        assert_eq!(signatures(&index), vec!["a", "b", "c", "d"]);
+        assert!(index.reached_start);
+    }
+
+    #[test]
+    fn test_merge_with_gap_restarts() {
+        let mut index = HistoryIndex { entries: vec![item("y"), item("z")], reached_start: true, ..Default::default() };
+        index.merge_newer(vec![item("a"), item("b")], false);
+        assert_eq!(signatures(&index), vec!["a", "b"]);
+        assert!(!index.reached_start);
+        assert!(index.has_more(2));
+    }
+
+    #[test]
+    fn test_labelled_addresses() {
+        let mut details = HashMap::new();
+        details.insert(
+            "instructions".to_string(),
+            serde_json::json!([
+                { "parsed": { "info": { "source": "me", "destination": "landlord", "lamports": 5 } } },
+                { "accounts": ["landlord", "someone"] }
+            ]),
+        );
+        let mut labels = HashMap::new();
+        labels.insert("landlord".to_string(), "Landlord".to_string());
+        labels.insert("stranger".to_string(), "Stranger".to_string());
+        assert_eq!(
+            labelled_addresses(&details, &labels),
+            vec![("landlord".to_string(), "Landlord".to_string())]
+        );
+
+        let mut map = HashMap::new();
+        set_or_clear(&mut map, "sig", Some("  rent  "));
+        assert_eq!(map.get("sig").map(String::as_str), Some("rent"));
+        set_or_clear(&mut map, "sig", Some(" "));
+        assert!(map.is_empty());
+        assert_eq!(csv_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+    }
+
+    #[test]
+    fn test_append_older_detects_start() {
+        let mut index = HistoryIndex { entries: vec![item("a")], ..Default::default() };
+        index.append_older(vec![item("a"), item("b")], 100);
+        assert_eq!(signatures(&index), vec!["a", "b"]);
+        assert!(index.reached_start);
+        assert!(!index.has_more(2));
+        assert_eq!(index.page(1).len(), 1);
+    }
+}
@@ -0,0 +1,178 @@
+// src/keystore.rs
+//! Key files for other tools
+//!
+//! Two formats: the Solana CLI keypair file (`id.json`, the 64 keypair bytes
+//! as a JSON array, unencrypted), and an encrypted keystore laid out like
+//! Ethereum's Web3 Secret Storage files, with Argon2id and AES-256-GCM. Every
+//! parameter needed to open the keystore is spelled out in it, so tools other
+//! than this app can read it with stock Argon2 and AES-GCM libraries.
+
+use crate::backup::MIN_PASSWORD_LENGTH;
+use crate::pin::{decrypt_with_key, derive_key_argon2id, encrypt_with_key, generate_salt, KdfParams};
+use crate::storage::save_export_file;
+use crate::wallet::Wallet;
+use serde::{Deserialize, Serialize};
+
+const KEYSTORE_VERSION: u32 = 1;
+const KEYSTORE_CIPHER: &str = "aes-256-gcm";
+const KEYSTORE_KDF: &str = "argon2id";
+/// AES-GCM nonce length; `encrypt_with_key` prepends it to the ciphertext
+const NONCE_LENGTH: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub address: String,
+    pub crypto: KeystoreCrypto,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    /// Hex-encoded ciphertext of the 64 keypair bytes, with the GCM tag
+    pub ciphertext: String,
+    pub cipherparams: CipherParams,
+    pub kdf: String,
+    pub kdfparams: Argon2Params,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub nonce: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Argon2Params {
+    /// Memory in KiB
+    pub m: u32,
+    pub t: u32,
+    pub p: u32,
+    pub salt: String,
+    pub dklen: u32,
+}
+
+/// The wallet as a Solana CLI keypair file
+pub fn cli_keypair_json(wallet: &Wallet) -> Result<String, String> {
+    let keypair = bs58::decode(wallet.get_private_key())
+        .into_vec()
+        .map_err(|e| format!("Decode error: {}", e))?;
+    serde_json::to_string(&keypair).map_err(|e| format!("Failed to serialize keypair: {}", e))
+}
+
+/// The wallet as an encrypted keystore under `password`
+pub fn encrypt_keystore(wallet: &Wallet, password: &str) -> Result<String, String> {
+    encrypt_keystore_with_params(wallet, password, KdfParams::DEFAULT)
+}
+
+fn encrypt_keystore_with_params(wallet: &Wallet, password: &str, params: KdfParams) -> Result<String, String> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(format!("Use a password of at least {} characters", MIN_PASSWORD_LENGTH));
+    }
+    let keypair = bs58::decode(wallet.get_private_key())
+        .into_vec()
+        .map_err(|e| format!("Decode error: {}", e))?;
+    let salt = generate_salt();
+    let key = derive_key_argon2id(password, &salt, params)?;
+    let sealed = encrypt_with_key(&keypair, &key)?;
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+    let keystore = Keystore {
+        version: KEYSTORE_VERSION,
+        address: wallet.get_public_key(),
+        crypto: KeystoreCrypto {
+            cipher: KEYSTORE_CIPHER.to_string(),
+            ciphertext: hex::encode(ciphertext),
+            cipherparams: CipherParams { nonce: hex::encode(nonce) },
+            kdf: KEYSTORE_KDF.to_string(),
+            kdfparams: Argon2Params {
+                m: params.memory_kib,
+                t: params.iterations,
+                p: params.parallelism,
+                salt: hex::encode(salt),
+                dklen: 32,
+            },
+        },
+    };
+    serde_json::to_string_pretty(&keystore).map_err(|e| format!("Failed to serialize keystore: {}", e))
+}
+
+/// Whether text is an encrypted keystore rather than a plain key file
+pub fn is_encrypted_keystore(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text.trim()).is_ok_and(|value| value.get("crypto").is_some())
+}
+
+/// Open an encrypted keystore, checking the key matches its address
+pub fn decrypt_keystore(text: &str, password: &str, name: String) -> Result<Wallet, String> {
+    let keystore: Keystore =
+        serde_json::from_str(text.trim()).map_err(|e| format!("Invalid keystore: {}", e))?;
+    if keystore.version > KEYSTORE_VERSION {
+        return Err(format!("Unsupported keystore version {}", keystore.version));
+    }
+    let crypto = &keystore.crypto;
+    if crypto.cipher != KEYSTORE_CIPHER || crypto.kdf != KEYSTORE_KDF {
+        return Err(format!("Unsupported keystore encryption: {} with {}", crypto.cipher, crypto.kdf));
+    }
+    let decode = |field: &str| hex::decode(field).map_err(|e| format!("Invalid keystore: {}", e));
+    let params = KdfParams {
+        memory_kib: crypto.kdfparams.m,
+        iterations: crypto.kdfparams.t,
+        parallelism: crypto.kdfparams.p,
+    };
+    params.check_limits()?;
+    let key = derive_key_argon2id(password, &decode(&crypto.kdfparams.salt)?, params)?;
+    let mut sealed = decode(&crypto.cipherparams.nonce)?;
+    if sealed.len() != NONCE_LENGTH {
+        return Err("Invalid keystore: bad nonce length".to_string());
+    }
+    sealed.extend(decode(&crypto.ciphertext)?);
+    let keypair = decrypt_with_key(&sealed, &key).map_err(|_| "Wrong password for this keystore".to_string())?;
+    let wallet = Wallet::from_private_key(&keypair, name)?;
+    if wallet.get_public_key() != keystore.address {
+        return Err("Keystore key doesn't match its address".to_string());
+    }
+    Ok(wallet)
+}
+
+/// Write the CLI keypair file to the exports folder, returning its path
+pub fn export_cli_keypair(wallet: &Wallet) -> Result<String, String> {
+    save_export_file(&format!("{}.json", wallet.get_public_key()), &cli_keypair_json(wallet)?)
+}
+
+/// Write an encrypted keystore to the exports folder, returning its path
+pub fn export_keystore(wallet: &Wallet, password: &str) -> Result<String, String> {
+    save_export_file(
+        &format!("keystore_{}.json", wallet.get_public_key()),
+        &encrypt_keystore(wallet, password)?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pin::TEST_PARAMS;
+
+    #[test]
+    fn test_cli_keypair_json() {
+        let wallet = Wallet::new("a".to_string());
+        let bytes: Vec<u8> = serde_json::from_str(&cli_keypair_json(&wallet).unwrap()).unwrap();
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(Wallet::from_private_key(&bytes, String::new()).unwrap().get_public_key(), wallet.get_public_key());
+    }
+
+    #[test]
+    fn test_keystore_round_trip() {
+        let wallet = Wallet::new("a".to_string());
+        assert!(encrypt_keystore_with_params(&wallet, "short", TEST_PARAMS).is_err());
+
+        let text = encrypt_keystore_with_params(&wallet, "correct horse", TEST_PARAMS).unwrap();
+        assert!(is_encrypted_keystore(&text));
+        assert!(!is_encrypted_keystore(&cli_keypair_json(&wallet).unwrap()));
+        let opened = decrypt_keystore(&text, "correct horse", "b".to_string()).unwrap();
+        assert_eq!(opened.get_public_key(), wallet.get_public_key());
+        assert!(decrypt_keystore(&text, "battery staple", "b".to_string()).is_err());
+
+        let mut other_address: Keystore = serde_json::from_str(&text).unwrap();
+        other_address.address = Wallet::new("c".to_string()).get_public_key();
+        let tampered = serde_json::to_string(&other_address).unwrap();
+        assert!(decrypt_keystore(&tampered, "correct horse", "b".to_string()).is_err());
+    }
+}
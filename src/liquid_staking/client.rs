@@ -0,0 +1,363 @@
+// src/liquid_staking/client.rs
+//! Exchange rates and SOL ↔ LST conversions
+
+use reqwest::Client as HttpClient;
+use serde_json::{json, Value};
+use solana_sdk::stake::{instruction::authorize, state::StakeAuthorize};
+use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
+use spl_associated_token_account::{get_associated_token_address_with_program_id, instruction::create_associated_token_account_idempotent};
+use std::error::Error as StdError;
+use std::str::FromStr;
+
+use super::marinade::{self, MarinadeAccounts, MARINADE_PROGRAM_ID, MARINADE_STATE, MSOL_MINT};
+use super::stake_pool::{self, STAKE_POOL_PROGRAM_IDS};
+use super::types::{ExchangeRate, LiquidStakingToken, LstProtocol, RateSource, StakePoolInfo, LIQUID_STAKING_TOKENS};
+use crate::prices::get_jupiter_prices_for_mints;
+use crate::rpc::{self, TokenAccountFilter};
+use crate::signing::{sign_available_slots, TransactionSigner};
+use crate::storage::get_current_jito_settings;
+use crate::transaction::{TransactionBuilder, TransactionClient};
+
+type Result<T> = std::result::Result<T, Box<dyn StdError>>;
+
+const JUPITER_SWAP_URL: &str = "https://lite-api.jup.ag/swap/v1";
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+/// Jupiter's names for Sanctum's stake pool router and the Infinity pool
+const SANCTUM_DEXES: &str = "Sanctum,Sanctum Infinity";
+
+pub struct LiquidStakingClient {
+    rpc_url: Option<String>,
+    transaction_client: TransactionClient,
+}
+
+impl LiquidStakingClient {
+    pub fn new(rpc_url: Option<&str>) -> Self {
+        Self {
+            rpc_url: rpc_url.map(str::to_string),
+            transaction_client: TransactionClient::new(rpc_url),
+        }
+    }
+
+    /// SOL one `token` is worth: the pool's backing for stake pools, market
+    /// prices otherwise
+    pub async fn exchange_rate(&self, token: &LiquidStakingToken) -> Result<ExchangeRate> {
+        if let LstProtocol::StakePool(pool) = token.protocol {
+            return Ok(self.stake_pool(pool).await?.rate());
+        }
+
+        let prices = get_jupiter_prices_for_mints(vec![token.mint.to_string(), SOL_MINT.to_string()]).await?;
+        match (prices.get(token.mint), prices.get(SOL_MINT)) {
+            (Some(token_usd), Some(sol_usd)) if *sol_usd > 0.0 => {
+                Ok(ExchangeRate { sol_per_token: token_usd / sol_usd, source: RateSource::Market })
+            }
+            _ => Err(format!("No price for {}", token.symbol).into()),
+        }
+    }
+
+    /// Convert `lamports` of SOL into `token`
+    pub async fn stake(&self, token: &LiquidStakingToken, signer: &dyn TransactionSigner, lamports: u64) -> Result<String> {
+        let user = Pubkey::from_str(&signer.get_public_key().await?)?;
+        match token.protocol {
+            LstProtocol::StakePool(pool) => {
+                let pool = self.stake_pool(pool).await?;
+                self.deposit_to_pool(&pool, signer, lamports).await
+            }
+            LstProtocol::Marinade => {
+                let accounts = self.marinade_accounts().await?;
+                let user_msol = get_associated_token_address_with_program_id(&user, &accounts.msol_mint, &spl_token::id());
+
+                let mut builder = TransactionBuilder::new(user);
+                builder.add_instruction(create_associated_token_account_idempotent(&user, &user, &accounts.msol_mint, &spl_token::id()));
+                builder.add_instruction(marinade::deposit(&accounts, &user, &user_msol, lamports));
+                self.send(builder, signer).await
+            }
+            LstProtocol::Jupiter => self.swap(SOL_MINT, token.mint, lamports, signer).await,
+        }
+    }
+
+    /// Convert `amount` base units of `token` back into SOL
+    pub async fn unstake(&self, token: &LiquidStakingToken, signer: &dyn TransactionSigner, amount: u64) -> Result<String> {
+        let user = Pubkey::from_str(&signer.get_public_key().await?)?;
+        match token.protocol {
+            LstProtocol::StakePool(pool) => {
+                let pool = self.stake_pool(pool).await?;
+                self.withdraw_from_pool(&pool, signer, amount).await
+            }
+            LstProtocol::Marinade => {
+                let accounts = self.marinade_accounts().await?;
+                let user_msol = get_associated_token_address_with_program_id(&user, &accounts.msol_mint, &spl_token::id());
+
+                let mut builder = TransactionBuilder::new(user);
+                builder.add_instruction(marinade::liquid_unstake(&accounts, &user, &user_msol, amount));
+                self.send(builder, signer).await
+            }
+            LstProtocol::Jupiter => self.swap(token.mint, SOL_MINT, amount, signer).await,
+        }
+    }
+
+    /// Load any stake pool run by one of the stake pool program deployments
+    pub async fn stake_pool(&self, address: &str) -> Result<StakePoolInfo> {
+        let account = rpc::get_multiple_accounts(&[address.to_string()], self.rpc_url.as_deref())
+            .await?
+            .into_iter()
+            .next()
+            .flatten()
+            .ok_or_else(|| format!("Stake pool {} not found", address))?;
+        if !STAKE_POOL_PROGRAM_IDS.contains(&account.owner.as_str()) {
+            return Err(format!("{} is not owned by a stake pool program", address).into());
+        }
+        let state = stake_pool::parse_stake_pool(&account.data)?;
+        let mint = self.transaction_client.get_mint_info(&state.pool_mint).await?;
+        Ok(StakePoolInfo {
+            address: Pubkey::from_str(address)?,
+            program_id: Pubkey::from_str(&account.owner)?,
+            state,
+            decimals: mint.decimals,
+        })
+    }
+
+    /// Deposit `lamports` into `pool` for its pool tokens
+    pub async fn deposit_to_pool(&self, pool: &StakePoolInfo, signer: &dyn TransactionSigner, lamports: u64) -> Result<String> {
+        if pool.state.sol_deposit_authority.is_some() {
+            return Err("This pool only accepts SOL deposits approved by its deposit authority".into());
+        }
+        self.check_pool_updated(pool).await?;
+        let user = Pubkey::from_str(&signer.get_public_key().await?)?;
+        let state = &pool.state;
+        let user_tokens = get_associated_token_address_with_program_id(&user, &state.pool_mint, &state.token_program_id);
+
+        let mut builder = TransactionBuilder::new(user);
+        builder.add_instruction(create_associated_token_account_idempotent(&user, &user, &state.pool_mint, &state.token_program_id));
+        builder.add_instruction(stake_pool::deposit_sol(&pool.program_id, &pool.address, state, &user, &user_tokens, lamports));
+        self.send(builder, signer).await
+    }
+
+    /// Redeem `pool_tokens` base units of `pool` for SOL from its reserve
+    pub async fn withdraw_from_pool(&self, pool: &StakePoolInfo, signer: &dyn TransactionSigner, pool_tokens: u64) -> Result<String> {
+        if pool.state.sol_withdraw_authority.is_some() {
+            return Err("This pool only allows SOL withdrawals approved by its withdraw authority".into());
+        }
+        self.check_pool_updated(pool).await?;
+        let user = Pubkey::from_str(&signer.get_public_key().await?)?;
+        let state = &pool.state;
+        let user_tokens = get_associated_token_address_with_program_id(&user, &state.pool_mint, &state.token_program_id);
+
+        let mut builder = TransactionBuilder::new(user);
+        builder.add_instruction(stake_pool::withdraw_sol(&pool.program_id, &pool.address, state, &user, &user_tokens, &user, pool_tokens));
+        self.send(builder, signer).await
+    }
+
+    /// A listed stake pool that takes stake delegated to `vote_account`
+    pub async fn pool_for_validator(&self, vote_account: &Pubkey) -> Result<Option<(LiquidStakingToken, StakePoolInfo)>> {
+        for token in LIQUID_STAKING_TOKENS {
+            let LstProtocol::StakePool(address) = token.protocol else { continue };
+            let pool = self.stake_pool(address).await?;
+            if self.pool_validator(&pool, vote_account).await?.is_some() {
+                return Ok(Some((*token, pool)));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn pool_validator(&self, pool: &StakePoolInfo, vote_account: &Pubkey) -> Result<Option<stake_pool::PoolValidator>> {
+        let list = rpc::get_multiple_accounts(&[pool.state.validator_list.to_string()], self.rpc_url.as_deref())
+            .await?
+            .into_iter()
+            .next()
+            .flatten()
+            .ok_or("Stake pool validator list not found")?;
+        Ok(stake_pool::parse_validator_list(&list.data)?
+            .into_iter()
+            .find(|validator| validator.vote_account == *vote_account && validator.active))
+    }
+
+    /// Hand a fully active stake account delegated to `vote_account` over to
+    /// `pool` for its pool tokens
+    pub async fn deposit_stake_account(
+        &self,
+        pool: &StakePoolInfo,
+        signer: &dyn TransactionSigner,
+        stake_account: &Pubkey,
+        vote_account: &Pubkey,
+    ) -> Result<String> {
+        if pool.state.stake_deposit_authority != stake_pool::deposit_authority(&pool.program_id, &pool.address) {
+            return Err("This pool only accepts stake deposits approved by its deposit authority".into());
+        }
+        let validator = self
+            .pool_validator(pool, vote_account)
+            .await?
+            .ok_or("The pool doesn't hold stake with this validator")?;
+        self.check_pool_updated(pool).await?;
+
+        let user = Pubkey::from_str(&signer.get_public_key().await?)?;
+        let builder = Self::stake_deposit_builder(pool, &user, stake_account, vote_account, validator.seed_suffix);
+        self.send(builder, signer).await
+    }
+
+    /// The stake deposit `deposit_stake_account` sends: hand the stake
+    /// account's staker and withdrawer to the pool's deposit authority, then
+    /// deposit it into the pool's stake account for `vote_account`
+    pub fn stake_deposit_builder(
+        pool: &StakePoolInfo,
+        user: &Pubkey,
+        stake_account: &Pubkey,
+        vote_account: &Pubkey,
+        seed_suffix: u32,
+    ) -> TransactionBuilder {
+        let state = &pool.state;
+        let user_tokens = get_associated_token_address_with_program_id(user, &state.pool_mint, &state.token_program_id);
+        let validator_stake = stake_pool::validator_stake_address(&pool.program_id, vote_account, &pool.address, seed_suffix);
+
+        let mut builder = TransactionBuilder::new(*user);
+        builder.add_instruction(create_associated_token_account_idempotent(user, user, &state.pool_mint, &state.token_program_id));
+        for role in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+            builder.add_instruction(authorize(stake_account, user, &state.stake_deposit_authority, role, None));
+        }
+        builder.add_instruction(stake_pool::deposit_stake(
+            &pool.program_id,
+            &pool.address,
+            state,
+            stake_account,
+            &validator_stake,
+            &user_tokens,
+        ));
+        builder
+    }
+
+    /// Pool tokens `owner` holds in its associated token account, in base units
+    pub async fn pool_token_balance(&self, pool: &StakePoolInfo, owner: &Pubkey) -> Result<u64> {
+        let account = get_associated_token_address_with_program_id(owner, &pool.state.pool_mint, &pool.state.token_program_id);
+        let data = rpc::get_multiple_accounts(&[account.to_string()], self.rpc_url.as_deref())
+            .await?
+            .into_iter()
+            .next()
+            .flatten()
+            .map(|account| account.data)
+            .unwrap_or_default();
+        // Token accounts hold the mint, the owner, then the amount
+        Ok(data.get(64..72).map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap_or([0; 8]))).unwrap_or(0))
+    }
+
+    /// Deposits and withdrawals fail until the pool's balances are updated
+    /// for the current epoch
+    async fn check_pool_updated(&self, pool: &StakePoolInfo) -> Result<()> {
+        let epoch = self.transaction_client.get_current_epoch().await?;
+        if pool.state.last_update_epoch < epoch {
+            return Err("The stake pool hasn't been updated for this epoch yet, try again shortly".into());
+        }
+        Ok(())
+    }
+
+    async fn marinade_accounts(&self) -> Result<MarinadeAccounts> {
+        let state = rpc::get_multiple_accounts(&[MARINADE_STATE.to_string()], self.rpc_url.as_deref())
+            .await?
+            .into_iter()
+            .next()
+            .flatten()
+            .ok_or("Marinade state account not found")?;
+        let accounts = MarinadeAccounts {
+            program_id: Pubkey::from_str(MARINADE_PROGRAM_ID)?,
+            state: Pubkey::from_str(MARINADE_STATE)?,
+            msol_mint: Pubkey::from_str(MSOL_MINT)?,
+            liq_pool_msol_leg: Pubkey::default(),
+            treasury_msol_account: marinade::parse_treasury_msol_account(&state.data)?,
+        };
+
+        let msol_leg = rpc::get_token_accounts_by_owner(
+            &accounts.liq_pool_msol_leg_authority().to_string(),
+            Some(TokenAccountFilter::Mint(accounts.msol_mint.to_string())),
+            self.rpc_url.as_deref(),
+        )
+        .await?
+        .into_iter()
+        .next()
+        .ok_or("Marinade liquidity pool mSOL account not found")?;
+        Ok(MarinadeAccounts { liq_pool_msol_leg: Pubkey::from_str(&msol_leg.pubkey)?, ..accounts })
+    }
+
+    async fn send(&self, mut builder: TransactionBuilder, signer: &dyn TransactionSigner) -> Result<String> {
+        if get_current_jito_settings().jito_tx {
+            let payer = Pubkey::from_str(&signer.get_public_key().await?)?;
+            for instruction in crate::tips::tip_instructions(&payer, Some(crate::tips::DEFAULT_JITO_TIP_LAMPORTS))? {
+                builder.add_instruction(instruction);
+            }
+        }
+        self.transaction_client.send_built(builder, signer).await
+    }
+
+    /// Swap through Jupiter, for tokens without a direct deposit
+    async fn swap(&self, input_mint: &str, output_mint: &str, amount: u64, signer: &dyn TransactionSigner) -> Result<String> {
+        let quote = self.quote(input_mint, output_mint, amount, None).await?;
+        self.execute_swap(quote, signer).await
+    }
+
+    /// Lamports Sanctum's router pays right now for `amount` of the token
+    /// `input_mint`, with the quote to pass to [`Self::sell_quoted`]
+    pub async fn quote_sale(&self, input_mint: &str, amount: u64) -> Result<(u64, Value)> {
+        let quote = self.quote(input_mint, SOL_MINT, amount, Some(SANCTUM_DEXES)).await?;
+        let out = quote["outAmount"].as_str().and_then(|out| out.parse().ok()).ok_or("Invalid router quote")?;
+        Ok((out, quote))
+    }
+
+    /// Sell for SOL at a quote from [`Self::quote_sale`]
+    pub async fn sell_quoted(&self, quote: Value, signer: &dyn TransactionSigner) -> Result<String> {
+        self.execute_swap(quote, signer).await
+    }
+
+    /// A Jupiter quote, limited to `dexes` when given
+    async fn quote(&self, input_mint: &str, output_mint: &str, amount: u64, dexes: Option<&str>) -> Result<Value> {
+        let slippage = crate::storage::load_slippage_setting();
+        let mut query = vec![
+            ("inputMint", input_mint.to_string()),
+            ("outputMint", output_mint.to_string()),
+            ("amount", amount.to_string()),
+            ("slippageBps", slippage.jupiter_bps().to_string()),
+        ];
+        if let Some(dexes) = dexes {
+            query.push(("dexes", dexes.to_string()));
+        }
+
+        let quote: Value = HttpClient::new()
+            .get(format!("{}/quote", JUPITER_SWAP_URL))
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| format!("Jupiter quote request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Jupiter quote: {}", e))?;
+        if quote["outAmount"].as_str().is_none() {
+            return Err("No Jupiter route for this conversion".into());
+        }
+        Ok(quote)
+    }
+
+    async fn execute_swap(&self, quote: Value, signer: &dyn TransactionSigner) -> Result<String> {
+        let user = signer.get_public_key().await?;
+        let slippage = crate::storage::load_slippage_setting();
+
+        let swap: Value = HttpClient::new()
+            .post(format!("{}/swap", JUPITER_SWAP_URL))
+            .json(&json!({
+                "quoteResponse": quote,
+                "userPublicKey": user,
+                "wrapAndUnwrapSol": true,
+                "dynamicComputeUnitLimit": true,
+                "dynamicSlippage": slippage.is_dynamic(),
+                "prioritizationFeeLamports": "auto",
+            }))
+            .send()
+            .await
+            .map_err(|e| format!("Jupiter swap request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Jupiter swap response: {}", e))?;
+        let unsigned = swap["swapTransaction"].as_str().ok_or("No transaction in Jupiter swap response")?;
+
+        let mut transaction: VersionedTransaction = bincode::deserialize(&base64::decode(unsigned)?)?;
+        sign_available_slots(&mut transaction, &[signer]).await?;
+        self.transaction_client
+            .send_transaction(&bs58::encode(bincode::serialize(&transaction)?).into_string())
+            .await
+    }
+}
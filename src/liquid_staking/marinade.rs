@@ -0,0 +1,140 @@
+// src/liquid_staking/marinade.rs
+//! Marinade `deposit` and `liquid_unstake` instructions
+
+use sha2::{Digest, Sha256};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+pub const MARINADE_PROGRAM_ID: &str = "MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD";
+pub const MARINADE_STATE: &str = "8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC";
+pub const MSOL_MINT: &str = "mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So";
+
+/// The state account starts with the Anchor discriminator, then the mSOL
+/// mint, admin authority, operational SOL account and treasury mSOL account
+const TREASURY_MSOL_OFFSET: usize = 8 + 32 * 3;
+
+/// Marinade accounts the instructions need besides the program's PDAs
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarinadeAccounts {
+    pub program_id: Pubkey,
+    pub state: Pubkey,
+    pub msol_mint: Pubkey,
+    /// mSOL side of the liquidity pool, owned by `liq_pool_msol_leg_authority`
+    pub liq_pool_msol_leg: Pubkey,
+    pub treasury_msol_account: Pubkey,
+}
+
+impl MarinadeAccounts {
+    fn pda(&self, seed: &[u8]) -> Pubkey {
+        Pubkey::find_program_address(&[self.state.as_ref(), seed], &self.program_id).0
+    }
+
+    pub fn reserve(&self) -> Pubkey {
+        self.pda(b"reserve")
+    }
+
+    pub fn msol_mint_authority(&self) -> Pubkey {
+        self.pda(b"st_mint")
+    }
+
+    pub fn liq_pool_sol_leg(&self) -> Pubkey {
+        self.pda(b"liq_sol")
+    }
+
+    pub fn liq_pool_msol_leg_authority(&self) -> Pubkey {
+        self.pda(b"liq_st_sol_authority")
+    }
+}
+
+/// Treasury mSOL account from the state account's data
+pub fn parse_treasury_msol_account(state_data: &[u8]) -> Result<Pubkey, String> {
+    let bytes = state_data
+        .get(TREASURY_MSOL_OFFSET..TREASURY_MSOL_OFFSET + 32)
+        .ok_or("Marinade state account is too short")?;
+    let mut key = [0u8; 32];
+    key.copy_from_slice(bytes);
+    Ok(Pubkey::new_from_array(key))
+}
+
+fn anchor_data(name: &str, amount: u64) -> Vec<u8> {
+    let hash = Sha256::digest(format!("global:{}", name).as_bytes());
+    let mut data = hash[..8].to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data
+}
+
+/// Stake `lamports` from `user` and mint mSOL to `msol_to`
+pub fn deposit(accounts: &MarinadeAccounts, user: &Pubkey, msol_to: &Pubkey, lamports: u64) -> Instruction {
+    Instruction {
+        program_id: accounts.program_id,
+        accounts: vec![
+            AccountMeta::new(accounts.state, false),
+            AccountMeta::new(accounts.msol_mint, false),
+            AccountMeta::new(accounts.liq_pool_sol_leg(), false),
+            AccountMeta::new(accounts.liq_pool_msol_leg, false),
+            AccountMeta::new_readonly(accounts.liq_pool_msol_leg_authority(), false),
+            AccountMeta::new(accounts.reserve(), false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new(*msol_to, false),
+            AccountMeta::new_readonly(accounts.msol_mint_authority(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: anchor_data("deposit", lamports),
+    }
+}
+
+/// Sell `msol_amount` from `msol_from` to the liquidity pool for SOL, paid to `user`
+pub fn liquid_unstake(accounts: &MarinadeAccounts, user: &Pubkey, msol_from: &Pubkey, msol_amount: u64) -> Instruction {
+    Instruction {
+        program_id: accounts.program_id,
+        accounts: vec![
+            AccountMeta::new(accounts.state, false),
+            AccountMeta::new(accounts.msol_mint, false),
+            AccountMeta::new(accounts.liq_pool_sol_leg(), false),
+            AccountMeta::new(accounts.liq_pool_msol_leg, false),
+            AccountMeta::new(accounts.treasury_msol_account, false),
+            AccountMeta::new(*msol_from, false),
+            AccountMeta::new_readonly(*user, true),
+            AccountMeta::new(*user, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: anchor_data("liquid_unstake", msol_amount),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marinade_instructions() {
+        let treasury = Pubkey::new_unique();
+        let mut state_data = vec![0u8; 200];
+        state_data[TREASURY_MSOL_OFFSET..TREASURY_MSOL_OFFSET + 32].copy_from_slice(treasury.as_ref());
+        assert_eq!(parse_treasury_msol_account(&state_data), Ok(treasury));
+        assert!(parse_treasury_msol_account(&state_data[..50]).is_err());
+
+        let accounts = MarinadeAccounts {
+            program_id: Pubkey::new_unique(),
+            state: Pubkey::new_unique(),
+            msol_mint: Pubkey::new_unique(),
+            liq_pool_msol_leg: Pubkey::new_unique(),
+            treasury_msol_account: treasury,
+        };
+        let (user, msol_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let deposit_ix = deposit(&accounts, &user, &msol_account, 1_000);
+        assert_eq!(deposit_ix.data.len(), 16);
+        assert_eq!(&deposit_ix.data[8..], &1_000u64.to_le_bytes());
+        assert!(deposit_ix.accounts[6].is_signer);
+
+        let unstake_ix = liquid_unstake(&accounts, &user, &msol_account, 500);
+        assert_ne!(unstake_ix.data[..8], deposit_ix.data[..8]);
+        assert_eq!(unstake_ix.accounts[4].pubkey, treasury);
+    }
+}
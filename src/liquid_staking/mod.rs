@@ -0,0 +1,21 @@
+// src/liquid_staking/mod.rs
+//! Liquid staking: convert SOL to an LST and back
+//!
+//! JitoSOL is an SPL stake pool, so SOL goes in with the pool's `DepositSol`
+//! and comes out with `WithdrawSol`. mSOL uses Marinade's own program:
+//! `deposit` mints it and `liquid_unstake` sells it back through Marinade's
+//! liquidity pool. INF is Sanctum's multi-LST Infinity pool, which prices
+//! every deposit through per-LST calculator programs, so conversions go
+//! through Jupiter, which routes them into the pool.
+//!
+//! Any other SPL stake pool can be used by its address: its state gives the
+//! exchange rate and fees, and SOL goes in and out the same way as JitoSOL.
+
+mod client;
+mod marinade;
+mod stake_pool;
+mod types;
+
+pub use client::LiquidStakingClient;
+pub use stake_pool::{approved_stake_deposit, Fee, StakePoolState};
+pub use types::*;
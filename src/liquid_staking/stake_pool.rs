@@ -0,0 +1,549 @@
+// src/liquid_staking/stake_pool.rs
+//! SPL stake pool state and SOL deposit/withdraw instructions
+
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+pub const STAKE_POOL_PROGRAM_ID: &str = "SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy";
+
+/// Programs running the SPL stake pool code: the SPL deployment and
+/// Sanctum's single- and multi-validator deployments
+pub const STAKE_POOL_PROGRAM_IDS: &[&str] = &[
+    STAKE_POOL_PROGRAM_ID,
+    "SP12tWFxD9oJsVWNavTTBZvMbA6gkAmxtVgxdqvyvhY",
+    "SPMBzsVUuoHA4Jm6KunbsotaahvVikZs1JyTW6iJvbn",
+];
+
+/// `StakePoolInstruction` variants, in the program's order
+const DEPOSIT_STAKE_TAG: u8 = 9;
+const DEPOSIT_SOL_TAG: u8 = 14;
+const WITHDRAW_SOL_TAG: u8 = 16;
+
+/// Fixed-position fields of a `StakePool` account (the layout only varies
+/// after the fees that follow them)
+const STAKE_DEPOSIT_AUTHORITY_OFFSET: usize = 65;
+const VALIDATOR_LIST_OFFSET: usize = 98;
+const RESERVE_STAKE_OFFSET: usize = 130;
+const POOL_MINT_OFFSET: usize = 162;
+const MANAGER_FEE_ACCOUNT_OFFSET: usize = 194;
+const TOKEN_PROGRAM_OFFSET: usize = 226;
+const TOTAL_LAMPORTS_OFFSET: usize = 258;
+const POOL_TOKEN_SUPPLY_OFFSET: usize = 266;
+const LAST_UPDATE_EPOCH_OFFSET: usize = 274;
+/// `epoch_fee`, right after the lockup
+const EPOCH_FEE_OFFSET: usize = 330;
+
+/// A fee as a fraction of the amount
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Fee {
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+impl Fee {
+    /// Fee charged on `amount`, rounded up like the program does
+    pub fn apply(&self, amount: u64) -> u64 {
+        if self.numerator == 0 || self.denominator == 0 {
+            return 0;
+        }
+        let fee = (amount as u128 * self.numerator as u128).div_ceil(self.denominator as u128);
+        fee.min(amount as u128) as u64
+    }
+
+    pub fn percent(&self) -> f64 {
+        if self.denominator == 0 {
+            0.0
+        } else {
+            self.numerator as f64 * 100.0 / self.denominator as f64
+        }
+    }
+}
+
+/// What depositing and withdrawing need from a stake pool account
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakePoolState {
+    /// Must authorize stake deposits; the program's PDA unless restricted
+    pub stake_deposit_authority: Pubkey,
+    pub validator_list: Pubkey,
+    pub reserve_stake: Pubkey,
+    pub pool_mint: Pubkey,
+    pub manager_fee_account: Pubkey,
+    pub token_program_id: Pubkey,
+    pub total_lamports: u64,
+    pub pool_token_supply: u64,
+    pub last_update_epoch: u64,
+    /// Share of each epoch's rewards the manager takes
+    pub epoch_fee: Fee,
+    pub stake_deposit_fee: Fee,
+    /// Percent of the stake deposit fee paid to the referrer
+    pub stake_referral_fee: u8,
+    pub sol_deposit_fee: Fee,
+    /// Percent of the SOL deposit fee paid to the referrer
+    pub sol_referral_fee: u8,
+    pub sol_withdrawal_fee: Fee,
+    /// When set, only this key may deposit SOL
+    pub sol_deposit_authority: Option<Pubkey>,
+    /// When set, only this key may withdraw SOL
+    pub sol_withdraw_authority: Option<Pubkey>,
+}
+
+impl StakePoolState {
+    /// SOL backing one pool token
+    pub fn sol_per_token(&self) -> f64 {
+        if self.pool_token_supply == 0 {
+            1.0
+        } else {
+            self.total_lamports as f64 / self.pool_token_supply as f64
+        }
+    }
+
+    /// Pool tokens a SOL deposit mints to the depositor, who is also the
+    /// referrer and gets the referral share of the fee back
+    pub fn deposit_quote(&self, lamports: u64) -> u64 {
+        let minted = if self.pool_token_supply == 0 || self.total_lamports == 0 {
+            lamports
+        } else {
+            (lamports as u128 * self.pool_token_supply as u128 / self.total_lamports as u128) as u64
+        };
+        let fee = self.sol_deposit_fee.apply(minted);
+        let referral = fee * self.sol_referral_fee.min(100) as u64 / 100;
+        minted - fee + referral
+    }
+
+    /// Pool tokens, about, for depositing a stake account holding `lamports`
+    pub fn stake_deposit_quote(&self, lamports: u64) -> u64 {
+        let minted = if self.pool_token_supply == 0 || self.total_lamports == 0 {
+            lamports
+        } else {
+            (lamports as u128 * self.pool_token_supply as u128 / self.total_lamports as u128) as u64
+        };
+        let fee = self.stake_deposit_fee.apply(minted);
+        let referral = fee * self.stake_referral_fee.min(100) as u64 / 100;
+        minted - fee + referral
+    }
+
+    /// Lamports a withdrawal of `pool_tokens` pays out after the fee
+    pub fn withdraw_quote(&self, pool_tokens: u64) -> u64 {
+        let burned = pool_tokens - self.sol_withdrawal_fee.apply(pool_tokens);
+        if self.pool_token_supply == 0 {
+            return burned;
+        }
+        (burned as u128 * self.total_lamports as u128 / self.pool_token_supply as u128) as u64
+    }
+}
+
+/// Reads the variable-length part of the account in order
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], String> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + len)
+            .ok_or("Stake pool account is too short")?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64_at(self.take(8)?, 0))
+    }
+
+    fn fee(&mut self) -> Result<Fee, String> {
+        let denominator = self.u64()?;
+        let numerator = self.u64()?;
+        Ok(Fee { numerator, denominator })
+    }
+
+    fn optional_pubkey(&mut self) -> Result<Option<Pubkey>, String> {
+        match self.u8()? {
+            0 => Ok(None),
+            _ => Ok(Some(pubkey_at(self.take(32)?, 0))),
+        }
+    }
+
+    /// `FutureEpoch<Fee>`: none, or a fee taking effect in one or two epochs
+    fn skip_future_fee(&mut self) -> Result<(), String> {
+        if self.u8()? != 0 {
+            self.fee()?;
+        }
+        Ok(())
+    }
+}
+
+fn pubkey_at(data: &[u8], offset: usize) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&data[offset..offset + 32]);
+    Pubkey::new_from_array(bytes)
+}
+
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+pub fn parse_stake_pool(data: &[u8]) -> Result<StakePoolState, String> {
+    // account_type 1 is an initialized stake pool
+    if data.len() < EPOCH_FEE_OFFSET || data[0] != 1 {
+        return Err("Account is not a stake pool".to_string());
+    }
+
+    let mut reader = Reader { data, offset: EPOCH_FEE_OFFSET };
+    let epoch_fee = reader.fee()?;
+    reader.skip_future_fee()?; // next_epoch_fee
+    reader.optional_pubkey()?; // preferred_deposit_validator_vote_address
+    reader.optional_pubkey()?; // preferred_withdraw_validator_vote_address
+    let stake_deposit_fee = reader.fee()?;
+    reader.fee()?; // stake_withdrawal_fee
+    reader.skip_future_fee()?; // next_stake_withdrawal_fee
+    let stake_referral_fee = reader.u8()?;
+    let sol_deposit_authority = reader.optional_pubkey()?;
+    let sol_deposit_fee = reader.fee()?;
+    let sol_referral_fee = reader.u8()?;
+    let sol_withdraw_authority = reader.optional_pubkey()?;
+    let sol_withdrawal_fee = reader.fee()?;
+
+    Ok(StakePoolState {
+        stake_deposit_authority: pubkey_at(data, STAKE_DEPOSIT_AUTHORITY_OFFSET),
+        validator_list: pubkey_at(data, VALIDATOR_LIST_OFFSET),
+        reserve_stake: pubkey_at(data, RESERVE_STAKE_OFFSET),
+        pool_mint: pubkey_at(data, POOL_MINT_OFFSET),
+        manager_fee_account: pubkey_at(data, MANAGER_FEE_ACCOUNT_OFFSET),
+        token_program_id: pubkey_at(data, TOKEN_PROGRAM_OFFSET),
+        total_lamports: u64_at(data, TOTAL_LAMPORTS_OFFSET),
+        pool_token_supply: u64_at(data, POOL_TOKEN_SUPPLY_OFFSET),
+        last_update_epoch: u64_at(data, LAST_UPDATE_EPOCH_OFFSET),
+        epoch_fee,
+        stake_deposit_fee,
+        stake_referral_fee,
+        sol_deposit_fee,
+        sol_referral_fee,
+        sol_withdrawal_fee,
+        sol_deposit_authority,
+        sol_withdraw_authority,
+    })
+}
+
+pub fn withdraw_authority(program_id: &Pubkey, stake_pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[stake_pool.as_ref(), b"withdraw"], program_id).0
+}
+
+/// Default authority stake deposits are authorized to
+pub fn deposit_authority(program_id: &Pubkey, stake_pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[stake_pool.as_ref(), b"deposit"], program_id).0
+}
+
+/// The stake account and deposit authority of a `DepositStake` into a pool
+/// of a known stake pool program, when the authority is the pool's own
+/// derived one. The stake account's staker and withdrawer must be handed to
+/// that authority in the same transaction, which the signing firewall allows
+/// only for deposits found here.
+pub fn approved_stake_deposit(
+    program_id: &Pubkey,
+    data: &[u8],
+    account: &dyn Fn(usize) -> Option<Pubkey>,
+) -> Option<(Pubkey, Pubkey)> {
+    let program = program_id.to_string();
+    if !STAKE_POOL_PROGRAM_IDS.contains(&program.as_str()) || data != [DEPOSIT_STAKE_TAG] {
+        return None;
+    }
+    let (stake_pool, authority, stake) = (account(0)?, account(2)?, account(4)?);
+    (authority == deposit_authority(program_id, &stake_pool)).then_some((stake, authority))
+}
+
+/// The pool's stake account delegated to `vote_account`
+pub fn validator_stake_address(program_id: &Pubkey, vote_account: &Pubkey, stake_pool: &Pubkey, seed_suffix: u32) -> Pubkey {
+    let suffix = seed_suffix.to_le_bytes();
+    let mut seeds: Vec<&[u8]> = vec![vote_account.as_ref(), stake_pool.as_ref()];
+    if seed_suffix != 0 {
+        seeds.push(&suffix);
+    }
+    Pubkey::find_program_address(&seeds, program_id).0
+}
+
+/// A validator in the pool's validator list
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolValidator {
+    pub vote_account: Pubkey,
+    pub seed_suffix: u32,
+    /// Only active validators take stake deposits
+    pub active: bool,
+}
+
+/// Header (account type and max validators), then a length-prefixed list
+/// of `ValidatorStakeInfo` entries
+const VALIDATOR_LIST_HEADER: usize = 1 + 4;
+const VALIDATOR_STAKE_INFO_LEN: usize = 73;
+
+pub fn parse_validator_list(data: &[u8]) -> Result<Vec<PoolValidator>, String> {
+    // account_type 2 is a validator list
+    if data.len() < VALIDATOR_LIST_HEADER + 4 || data[0] != 2 {
+        return Err("Account is not a stake pool validator list".to_string());
+    }
+    let mut len_bytes = [0u8; 4];
+    len_bytes.copy_from_slice(&data[VALIDATOR_LIST_HEADER..VALIDATOR_LIST_HEADER + 4]);
+    let count = u32::from_le_bytes(len_bytes) as usize;
+    let entries = &data[VALIDATOR_LIST_HEADER + 4..];
+    if entries.len() < count * VALIDATOR_STAKE_INFO_LEN {
+        return Err("Validator list is too short".to_string());
+    }
+
+    // Each entry: four u64 amounts/seeds, an unused u32, the validator seed
+    // suffix, the status and the vote account
+    Ok(entries
+        .chunks_exact(VALIDATOR_STAKE_INFO_LEN)
+        .take(count)
+        .map(|entry| {
+            let mut suffix = [0u8; 4];
+            suffix.copy_from_slice(&entry[36..40]);
+            PoolValidator {
+                vote_account: pubkey_at(entry, 41),
+                seed_suffix: u32::from_le_bytes(suffix),
+                active: entry[40] == 0,
+            }
+        })
+        .collect())
+}
+
+fn instruction_data(tag: u8, amount: u64) -> Vec<u8> {
+    let mut data = Vec::with_capacity(9);
+    data.push(tag);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data
+}
+
+/// Deposit `lamports` from `from` and mint pool tokens to `pool_tokens_to`
+pub fn deposit_sol(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    state: &StakePoolState,
+    from: &Pubkey,
+    pool_tokens_to: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*stake_pool, false),
+            AccountMeta::new_readonly(withdraw_authority(program_id, stake_pool), false),
+            AccountMeta::new(state.reserve_stake, false),
+            AccountMeta::new(*from, true),
+            AccountMeta::new(*pool_tokens_to, false),
+            AccountMeta::new(state.manager_fee_account, false),
+            // No referrer: the referral fee goes to the depositor's own account
+            AccountMeta::new(*pool_tokens_to, false),
+            AccountMeta::new(state.pool_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(state.token_program_id, false),
+        ],
+        data: instruction_data(DEPOSIT_SOL_TAG, lamports),
+    }
+}
+
+/// Merge `stake` into the pool's stake account for its validator and mint
+/// pool tokens to `pool_tokens_to`. The stake account's staker and
+/// withdrawer must already be the pool's deposit authority.
+pub fn deposit_stake(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    state: &StakePoolState,
+    stake: &Pubkey,
+    validator_stake: &Pubkey,
+    pool_tokens_to: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*stake_pool, false),
+            AccountMeta::new(state.validator_list, false),
+            AccountMeta::new_readonly(state.stake_deposit_authority, false),
+            AccountMeta::new_readonly(withdraw_authority(program_id, stake_pool), false),
+            AccountMeta::new(*stake, false),
+            AccountMeta::new(*validator_stake, false),
+            AccountMeta::new(state.reserve_stake, false),
+            AccountMeta::new(*pool_tokens_to, false),
+            AccountMeta::new(state.manager_fee_account, false),
+            // No referrer: the referral fee goes to the depositor's own account
+            AccountMeta::new(*pool_tokens_to, false),
+            AccountMeta::new(state.pool_mint, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+            AccountMeta::new_readonly(state.token_program_id, false),
+            AccountMeta::new_readonly(solana_sdk::stake::program::id(), false),
+        ],
+        data: vec![DEPOSIT_STAKE_TAG],
+    }
+}
+
+/// Burn `pool_tokens` from `pool_tokens_from` and withdraw their SOL from
+/// the pool's reserve to `lamports_to`
+pub fn withdraw_sol(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    state: &StakePoolState,
+    authority: &Pubkey,
+    pool_tokens_from: &Pubkey,
+    lamports_to: &Pubkey,
+    pool_tokens: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*stake_pool, false),
+            AccountMeta::new_readonly(withdraw_authority(program_id, stake_pool), false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*pool_tokens_from, false),
+            AccountMeta::new(state.reserve_stake, false),
+            AccountMeta::new(*lamports_to, false),
+            AccountMeta::new(state.manager_fee_account, false),
+            AccountMeta::new(state.pool_mint, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+            AccountMeta::new_readonly(solana_sdk::stake::program::id(), false),
+            AccountMeta::new_readonly(state.token_program_id, false),
+        ],
+        data: instruction_data(WITHDRAW_SOL_TAG, pool_tokens),
+    }
+}
+
+/// Pool state with the given stake deposit authority, for tests elsewhere
+#[cfg(test)]
+pub fn test_state(stake_deposit_authority: Pubkey) -> StakePoolState {
+    let mut data = vec![0u8; 600];
+    data[0] = 1;
+    data[STAKE_DEPOSIT_AUTHORITY_OFFSET..STAKE_DEPOSIT_AUTHORITY_OFFSET + 32].copy_from_slice(stake_deposit_authority.as_ref());
+    for offset in [VALIDATOR_LIST_OFFSET, RESERVE_STAKE_OFFSET, POOL_MINT_OFFSET, MANAGER_FEE_ACCOUNT_OFFSET] {
+        data[offset..offset + 32].copy_from_slice(Pubkey::new_unique().as_ref());
+    }
+    data[TOKEN_PROGRAM_OFFSET..TOKEN_PROGRAM_OFFSET + 32].copy_from_slice(spl_token::id().as_ref());
+    parse_stake_pool(&data).expect("test pool parses")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stake_pool_and_instructions() {
+        let (reserve, mint, fee_account) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = vec![0u8; 600];
+        data[0] = 1;
+        data[RESERVE_STAKE_OFFSET..RESERVE_STAKE_OFFSET + 32].copy_from_slice(reserve.as_ref());
+        data[POOL_MINT_OFFSET..POOL_MINT_OFFSET + 32].copy_from_slice(mint.as_ref());
+        data[MANAGER_FEE_ACCOUNT_OFFSET..MANAGER_FEE_ACCOUNT_OFFSET + 32].copy_from_slice(fee_account.as_ref());
+        data[TOKEN_PROGRAM_OFFSET..TOKEN_PROGRAM_OFFSET + 32].copy_from_slice(spl_token::id().as_ref());
+        data[TOTAL_LAMPORTS_OFFSET..TOTAL_LAMPORTS_OFFSET + 8].copy_from_slice(&1_250u64.to_le_bytes());
+        data[POOL_TOKEN_SUPPLY_OFFSET..POOL_TOKEN_SUPPLY_OFFSET + 8].copy_from_slice(&1_000u64.to_le_bytes());
+
+        let state = parse_stake_pool(&data).unwrap();
+        assert_eq!(state.reserve_stake, reserve);
+        assert_eq!(state.token_program_id, spl_token::id());
+        assert_eq!(state.sol_per_token(), 1.25);
+        assert!(parse_stake_pool(&data[..100]).is_err());
+
+        let (program, pool, user, user_tokens) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let deposit = deposit_sol(&program, &pool, &state, &user, &user_tokens, 5_000);
+        assert_eq!(deposit.data, [vec![DEPOSIT_SOL_TAG], 5_000u64.to_le_bytes().to_vec()].concat());
+        assert!(deposit.accounts[3].is_signer);
+        assert_eq!(deposit.accounts[1].pubkey, withdraw_authority(&program, &pool));
+
+        let withdraw = withdraw_sol(&program, &pool, &state, &user, &user_tokens, &user, 4_000);
+        assert_eq!(withdraw.data[0], WITHDRAW_SOL_TAG);
+        assert_eq!(withdraw.accounts.len(), 12);
+        assert!(withdraw.accounts[2].is_signer);
+    }
+
+    fn fee_bytes(numerator: u64, denominator: u64) -> Vec<u8> {
+        [denominator.to_le_bytes(), numerator.to_le_bytes()].concat()
+    }
+
+    #[test]
+    fn test_stake_pool_fees() {
+        let deposit_authority = Pubkey::new_unique();
+        let mut data = vec![0u8; EPOCH_FEE_OFFSET];
+        data[0] = 1;
+        data[TOTAL_LAMPORTS_OFFSET..TOTAL_LAMPORTS_OFFSET + 8].copy_from_slice(&2_000_000u64.to_le_bytes());
+        data[POOL_TOKEN_SUPPLY_OFFSET..POOL_TOKEN_SUPPLY_OFFSET + 8].copy_from_slice(&1_000_000u64.to_le_bytes());
+        data.extend(fee_bytes(5, 100)); // epoch_fee
+        data.push(1); // next_epoch_fee takes effect next epoch
+        data.extend(fee_bytes(6, 100));
+        data.push(0); // no preferred deposit validator
+        data.push(1); // preferred withdraw validator
+        data.extend(Pubkey::new_unique().to_bytes());
+        data.extend(fee_bytes(0, 0)); // stake_deposit_fee
+        data.extend(fee_bytes(0, 0)); // stake_withdrawal_fee
+        data.push(0); // next_stake_withdrawal_fee
+        data.push(0); // stake_referral_fee
+        data.push(1);
+        data.extend(deposit_authority.to_bytes());
+        data.extend(fee_bytes(10, 1_000)); // sol_deposit_fee
+        data.push(50); // sol_referral_fee
+        data.push(0); // no sol_withdraw_authority
+        data.extend(fee_bytes(3, 1_000)); // sol_withdrawal_fee
+
+        let state = parse_stake_pool(&data).unwrap();
+        assert_eq!(state.epoch_fee.percent(), 5.0);
+        assert_eq!(state.sol_deposit_authority, Some(deposit_authority));
+        assert_eq!(state.sol_withdraw_authority, None);
+        assert_eq!(state.sol_referral_fee, 50);
+        assert!(parse_stake_pool(&data[..data.len() - 1]).is_err());
+
+        // 1000 lamports mint 500 tokens, 5 of them the fee, 2 of that referred back
+        assert_eq!(state.deposit_quote(1_000), 497);
+        // 3 of 1000 tokens go to the fee, 997 burn for 1994 lamports
+        assert_eq!(state.withdraw_quote(1_000), 1_994);
+        // The fee rounds up
+        assert_eq!(state.withdraw_quote(100), 198);
+        assert_eq!(Fee { numerator: 0, denominator: 0 }.apply(1_000), 0);
+    }
+
+    #[test]
+    fn test_validator_list_and_deposit_stake() {
+        let (vote_a, vote_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let entry = |vote: &Pubkey, suffix: u32, status: u8| {
+            let mut bytes = vec![0u8; 36];
+            bytes.extend(suffix.to_le_bytes());
+            bytes.push(status);
+            bytes.extend(vote.to_bytes());
+            bytes
+        };
+        let mut data = vec![2u8, 10, 0, 0, 0];
+        data.extend(2u32.to_le_bytes());
+        data.extend(entry(&vote_a, 0, 0));
+        data.extend(entry(&vote_b, 7, 1));
+        data.extend(vec![0u8; VALIDATOR_STAKE_INFO_LEN]); // spare capacity
+
+        let validators = parse_validator_list(&data).unwrap();
+        assert_eq!(validators.len(), 2);
+        assert_eq!(validators[0], PoolValidator { vote_account: vote_a, seed_suffix: 0, active: true });
+        assert_eq!(validators[1].seed_suffix, 7);
+        assert!(!validators[1].active);
+        assert!(parse_validator_list(&data[..50]).is_err());
+
+        let (program, pool) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_ne!(
+            validator_stake_address(&program, &vote_b, &pool, 0),
+            validator_stake_address(&program, &vote_b, &pool, 7)
+        );
+
+        let mut pool_data = vec![0u8; 600];
+        pool_data[0] = 1;
+        let state = parse_stake_pool(&pool_data).unwrap();
+        let (stake, validator_stake, user_tokens) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let deposit = deposit_stake(&program, &pool, &state, &stake, &validator_stake, &user_tokens);
+        assert_eq!(deposit.data, vec![DEPOSIT_STAKE_TAG]);
+        assert_eq!(deposit.accounts.len(), 15);
+        assert!(deposit.accounts.iter().all(|meta| !meta.is_signer));
+    }
+}
@@ -0,0 +1,148 @@
+// src/liquid_staking/types.rs
+//! Liquid staking tokens and exchange rates
+
+use solana_sdk::pubkey::Pubkey;
+
+use super::stake_pool::StakePoolState;
+
+/// How a token is minted and redeemed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LstProtocol {
+    Marinade,
+    /// SPL stake pool at this address
+    StakePool(&'static str),
+    /// Converted through a Jupiter swap
+    Jupiter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiquidStakingToken {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    pub mint: &'static str,
+    pub decimals: u8,
+    pub protocol: LstProtocol,
+}
+
+pub const LIQUID_STAKING_TOKENS: &[LiquidStakingToken] = &[
+    LiquidStakingToken {
+        symbol: "JitoSOL",
+        name: "Jito Staked SOL",
+        mint: "J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn",
+        decimals: 9,
+        protocol: LstProtocol::StakePool("Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb"),
+    },
+    LiquidStakingToken {
+        symbol: "mSOL",
+        name: "Marinade Staked SOL",
+        mint: super::marinade::MSOL_MINT,
+        decimals: 9,
+        protocol: LstProtocol::Marinade,
+    },
+    LiquidStakingToken {
+        symbol: "INF",
+        name: "Sanctum Infinity",
+        mint: "5oVNBeEEQvYi1cX3ir8Dx5n1P7pdxydbGF2X4TxVusJm",
+        decimals: 9,
+        protocol: LstProtocol::Jupiter,
+    },
+];
+
+pub fn lst_by_symbol(symbol: &str) -> Option<&'static LiquidStakingToken> {
+    LIQUID_STAKING_TOKENS.iter().find(|token| token.symbol == symbol)
+}
+
+/// Where an exchange rate comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateSource {
+    /// The pool's own SOL backing per token
+    Pool,
+    /// Market prices of the token and SOL
+    Market,
+}
+
+/// SOL one token is worth, before fees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExchangeRate {
+    pub sol_per_token: f64,
+    pub source: RateSource,
+}
+
+impl ExchangeRate {
+    pub fn tokens_for_sol(&self, sol: f64) -> f64 {
+        if self.sol_per_token > 0.0 {
+            sol / self.sol_per_token
+        } else {
+            0.0
+        }
+    }
+
+    pub fn sol_for_tokens(&self, tokens: f64) -> f64 {
+        tokens * self.sol_per_token
+    }
+
+    /// "1 JitoSOL = 1.2345 SOL (pool rate)"
+    pub fn label(&self, symbol: &str) -> String {
+        let source = match self.source {
+            RateSource::Pool => "pool rate",
+            RateSource::Market => "market rate",
+        };
+        format!("1 {} = {:.4} SOL ({})", symbol, self.sol_per_token, source)
+    }
+}
+
+/// Any SPL stake pool, loaded by its address
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakePoolInfo {
+    pub address: Pubkey,
+    /// Stake pool program deployment that owns the pool
+    pub program_id: Pubkey,
+    pub state: StakePoolState,
+    pub decimals: u8,
+}
+
+impl StakePoolInfo {
+    pub fn rate(&self) -> ExchangeRate {
+        // `sol_per_token` compares base units, pool tokens may not have 9 decimals
+        let scale = 10_f64.powi(self.decimals as i32 - 9);
+        ExchangeRate { sol_per_token: self.state.sol_per_token() * scale, source: RateSource::Pool }
+    }
+
+    /// "Deposit fee 0.10%, withdrawal fee 0.30%, 4.00% of rewards"
+    pub fn fee_summary(&self) -> String {
+        format!(
+            "Deposit fee {:.2}%, withdrawal fee {:.2}%, {:.2}% of rewards",
+            self.state.sol_deposit_fee.percent(),
+            self.state.sol_withdrawal_fee.percent(),
+            self.state.epoch_fee.percent()
+        )
+    }
+}
+
+/// A pool of the SPL deployment taking stake deposits through its own
+/// derived authority
+#[cfg(test)]
+impl StakePoolInfo {
+    pub fn test_pool() -> Self {
+        use std::str::FromStr;
+        let program_id = Pubkey::from_str(super::stake_pool::STAKE_POOL_PROGRAM_ID).unwrap();
+        let address = Pubkey::new_unique();
+        let authority = super::stake_pool::deposit_authority(&program_id, &address);
+        Self { address, program_id, state: super::stake_pool::test_state(authority), decimals: 9 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exchange_rate() {
+        let rate = ExchangeRate { sol_per_token: 1.25, source: RateSource::Pool };
+        assert_eq!(rate.tokens_for_sol(5.0), 4.0);
+        assert_eq!(rate.sol_for_tokens(4.0), 5.0);
+        assert_eq!(rate.label("JitoSOL"), "1 JitoSOL = 1.2500 SOL (pool rate)");
+        assert_eq!(ExchangeRate { sol_per_token: 0.0, source: RateSource::Market }.tokens_for_sol(1.0), 0.0);
+        assert_eq!(lst_by_symbol("mSOL").map(|t| t.protocol), Some(LstProtocol::Marinade));
+    }
+}
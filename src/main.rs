@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 mod wallet;
 mod rpc;
+mod rpc_pool;
 mod prices;
 mod transaction;
 mod signing;
@@ -11,6 +12,9 @@ mod storage;
 mod components;
 mod validators;
 mod staking;
+mod liquid_staking;
+mod staking_rewards;
+mod mev_rewards;
 mod unstaking;
 mod currency;
 mod currency_utils;
@@ -23,9 +27,36 @@ mod squads;
 mod carrot;
 mod bonk_staking;
 mod quantum_vault;
+mod streaming;
 mod titan;
+mod swap;
 mod pin;
+mod biometric;
+mod auto_lock;
+mod backup;
+mod shamir;
+mod paper_backup;
+mod wallet_import;
+mod keystore;
+mod wallet_groups;
 mod timeout;
+mod operations;
+mod bridge;
+mod policy;
+mod submission;
+mod qr_transfer;
+mod token_approvals;
+mod updates;
+mod balance_history;
+mod cost_basis;
+mod nft_valuation;
+mod notifications;
+mod composer;
+mod history;
+mod budget;
+mod startup;
+mod decoder;
+mod tips;
 
 use components::*;
 
@@ -50,6 +81,8 @@ const PIN_CSS_URL: &str = "https://cdn.jsdelivr.net/gh/hogyzen12/unruggable-app@
 // ── DESKTOP (macOS/Windows/Linux) ─────────────────────────────────────────────
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android"), not(target_os = "ios")))]
 fn main() {
+    startup::begin();
+
     // Hard-disable Dioxus edit server & devtools in the shipped app
     std::env::set_var("DIOXUS_DISABLE_EDIT", "1");
     std::env::set_var("DX_DISABLE_EDIT", "1");
@@ -69,6 +102,7 @@ fn main() {
 // Web & Mobile keep the generic launcher:
 #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
 fn main() {
+    startup::begin();
     dioxus::launch(App);
 }
 
@@ -77,24 +111,78 @@ fn main() {
 fn App() -> Element {
     // Check if onboarding has been completed
     //let mut show_onboarding = use_signal(|| true);
-    let mut show_onboarding = use_signal(|| !storage::has_completed_onboarding());
+    let mut show_onboarding = use_signal(|| {
+        let _span = startup::span("onboarding check");
+        !storage::has_completed_onboarding()
+    });
     
     // Check if PIN is set and locked
-    let mut is_locked = use_signal(|| storage::has_pin());
+    let mut is_locked = use_signal(|| {
+        let _span = startup::span("PIN check");
+        storage::has_pin()
+    });
     
     // Initialize unified domain resolver (supports SNS .sol + ANS .abc, .bonk, etc.)
-    let domain_resolver = Arc::new(domain_resolver::DomainResolver::new(
-        "https://johna-k3cr1v-fast-mainnet.helius-rpc.com".to_string()
-    ));
-
-    // Provide domain resolver to the entire app
-    use_context_provider(|| domain_resolver);
+    // Built once, on the first render only
+    use_context_provider(|| {
+        let _span = startup::span("domain resolver");
+        Arc::new(domain_resolver::DomainResolver::new(
+            "https://johna-k3cr1v-fast-mainnet.helius-rpc.com".to_string()
+        ))
+    });
     
     // Keep SNS resolver for backward compatibility (optional - can remove if not needed elsewhere)
-    let sns_resolver = Arc::new(sns::SnsResolver::new(
-        "https://johna-k3cr1v-fast-mainnet.helius-rpc.com".to_string()
-    ));
-    use_context_provider(|| sns_resolver);
+    use_context_provider(|| {
+        let _span = startup::span("SNS resolver");
+        Arc::new(sns::SnsResolver::new(
+            "https://johna-k3cr1v-fast-mainnet.helius-rpc.com".to_string()
+        ))
+    });
+
+    // Lock again after inactivity or when the app goes to the background,
+    // dropping the vault key so wallet keys can't be opened until the PIN
+    let mut lock_app = move || {
+        if !*is_locked.peek() && !*show_onboarding.peek() && storage::has_pin() {
+            log::info!("🔒 Auto-locking");
+            pin::lock_vault();
+            is_locked.set(true);
+        }
+    };
+    use_effect(move || {
+        auto_lock::record_activity(chrono::Utc::now().timestamp() as u64);
+        spawn(async move {
+            let mut events = document::eval(auto_lock::ACTIVITY_JS);
+            while let Ok(event) = events.recv::<String>().await {
+                let settings = auto_lock::current();
+                let now = chrono::Utc::now().timestamp() as u64;
+                let lock = match event.as_str() {
+                    "hidden" => settings.lock_on_background,
+                    "visible" => settings.idle_expired(auto_lock::last_activity(), now),
+                    _ => {
+                        auto_lock::record_activity(now);
+                        false
+                    }
+                };
+                if lock {
+                    lock_app();
+                }
+            }
+        });
+    });
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            let now = chrono::Utc::now().timestamp() as u64;
+            if auto_lock::current().idle_expired(auto_lock::last_activity(), now) {
+                lock_app();
+            }
+        }
+    });
+
+    // Report the startup timeline once something is on screen
+    use_effect(move || {
+        spawn(startup::after_first_frame());
+    });
 
     rsx! {
         // For iOS/macOS builds, uncomment these lines and comment out the asset! lines below
@@ -110,6 +198,7 @@ fn App() -> Element {
         if is_locked() {
             PinUnlock {
                 on_unlock: move |_| {
+                    auto_lock::record_activity(chrono::Utc::now().timestamp() as u64);
                     is_locked.set(false);
                 }
             }
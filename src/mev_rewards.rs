@@ -0,0 +1,142 @@
+// src/mev_rewards.rs
+//! MEV rewards from Jito
+//!
+//! Validators running the Jito client collect tips from bundles. After each
+//! epoch the tips, less the validator's MEV commission, are claimed into the
+//! stake accounts delegated to it, on top of the inflation rewards. Jito's
+//! API reports both the per-validator figures and the claims per stake
+//! account.
+
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const JITO_API_URL: &str = "https://kobe.mainnet.jito.network/api/v1";
+/// Epochs in a year, at about two days each
+const EPOCHS_PER_YEAR: f64 = 182.0;
+/// Epochs of claims summed per stake account
+pub const MEV_REWARD_EPOCHS: u64 = 10;
+
+/// A validator's MEV figures for the last completed epoch
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MevValidator {
+    pub vote_account: String,
+    #[serde(default)]
+    pub running_jito: bool,
+    /// Share of the tips the validator keeps, in basis points
+    #[serde(default)]
+    pub mev_commission_bps: Option<u16>,
+    /// Tips collected, in lamports
+    #[serde(default)]
+    pub mev_rewards: Option<u64>,
+    /// Active stake, in lamports
+    #[serde(default)]
+    pub active_stake: u64,
+}
+
+impl MevValidator {
+    /// Yearly yield the tips add for stakers, percent
+    pub fn estimated_mev_apy(&self) -> Option<f64> {
+        if !self.running_jito || self.active_stake == 0 {
+            return None;
+        }
+        let commission = self.mev_commission_bps? as f64 / 10_000.0;
+        let to_stakers = self.mev_rewards? as f64 * (1.0 - commission.min(1.0));
+        Some(to_stakers / self.active_stake as f64 * EPOCHS_PER_YEAR * 100.0)
+    }
+
+    pub fn mev_commission_percent(&self) -> Option<f64> {
+        self.mev_commission_bps.map(|bps| bps as f64 / 100.0)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidatorsResponse {
+    validators: Vec<MevValidator>,
+}
+
+/// One MEV claim paid into a stake account
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StakerMevReward {
+    pub stake_account: String,
+    #[serde(default)]
+    pub validator_vote_account: String,
+    pub epoch: u64,
+    /// Lamports claimed
+    pub amount: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct StakerRewardsResponse {
+    rewards: Vec<StakerMevReward>,
+}
+
+/// MEV figures of every validator Jito knows, by vote account
+pub async fn fetch_mev_validators() -> Result<HashMap<String, MevValidator>, String> {
+    let response: ValidatorsResponse = Client::new()
+        .get(format!("{}/validators", JITO_API_URL))
+        .send()
+        .await
+        .map_err(|e| format!("Jito validators request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Jito validators: {}", e))?;
+    Ok(response
+        .validators
+        .into_iter()
+        .map(|validator| (validator.vote_account.clone(), validator))
+        .collect())
+}
+
+/// MEV claims paid into stake accounts whose stake authority is `wallet`
+pub async fn fetch_staker_mev_rewards(wallet: &str) -> Result<Vec<StakerMevReward>, String> {
+    let response: StakerRewardsResponse = Client::new()
+        .get(format!("{}/staker_rewards", JITO_API_URL))
+        .query(&[("stake_authority", wallet), ("limit", "1000")])
+        .send()
+        .await
+        .map_err(|e| format!("Jito staker rewards request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Jito staker rewards: {}", e))?;
+    Ok(response.rewards)
+}
+
+/// Lamports claimed per stake account in epochs from `since_epoch` on
+pub fn mev_by_account(rewards: &[StakerMevReward], since_epoch: u64) -> HashMap<String, u64> {
+    let mut totals = HashMap::new();
+    for reward in rewards.iter().filter(|r| r.epoch >= since_epoch) {
+        *totals.entry(reward.stake_account.clone()).or_insert(0) += reward.amount;
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mev_rewards() {
+        let validator = MevValidator {
+            vote_account: "vote".to_string(),
+            running_jito: true,
+            mev_commission_bps: Some(1_000),
+            mev_rewards: Some(1_000_000_000),
+            active_stake: 182_000_000_000,
+        };
+        // 0.9 SOL an epoch to 182 SOL of stake, 182 epochs a year
+        assert!((validator.estimated_mev_apy().unwrap() - 90.0).abs() < 1e-9);
+        assert_eq!(validator.mev_commission_percent(), Some(10.0));
+        assert_eq!(MevValidator { running_jito: false, ..validator }.estimated_mev_apy(), None);
+
+        let reward = |account: &str, epoch: u64, amount: u64| StakerMevReward {
+            stake_account: account.to_string(),
+            validator_vote_account: "vote".to_string(),
+            epoch,
+            amount,
+        };
+        let totals = mev_by_account(&[reward("a", 500, 10), reward("a", 501, 5), reward("b", 499, 7)], 500);
+        assert_eq!(totals.get("a"), Some(&15));
+        assert_eq!(totals.get("b"), None);
+    }
+}
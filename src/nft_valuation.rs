@@ -0,0 +1,180 @@
+// src/nft_valuation.rs
+//! Estimated value of a wallet's NFTs
+//!
+//! Each verified collection the wallet holds is valued at its Magic Eden
+//! floor price times the number of items held. Magic Eden identifies
+//! collections by its own symbol, so one held item per collection is looked
+//! up to find it. Unverified items are listed without a value: anyone can
+//! mint an item that claims to belong to a collection.
+
+use crate::rpc::CollectibleInfo;
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const MAGIC_EDEN_API_URL: &str = "https://api-mainnet.magiceden.dev/v2";
+/// Floors are refetched after this long
+const FLOOR_CACHE_TIMEOUT: Duration = Duration::from_secs(600);
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Held items of one collection and what they're worth at the floor
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionValue {
+    pub collection: String,
+    pub items: usize,
+    pub verified: bool,
+    pub floor_sol: Option<f64>,
+}
+
+impl CollectionValue {
+    pub fn value_sol(&self) -> Option<f64> {
+        self.floor_sol.map(|floor| floor * self.items as f64)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NftValuation {
+    /// Most valuable first
+    pub collections: Vec<CollectionValue>,
+}
+
+impl NftValuation {
+    pub fn total_sol(&self) -> f64 {
+        self.collections.iter().filter_map(|c| c.value_sol()).sum()
+    }
+
+    /// Number of held items that have a floor price
+    pub fn valued_items(&self) -> usize {
+        self.collections.iter().filter(|c| c.floor_sol.is_some()).map(|c| c.items).sum()
+    }
+}
+
+/// Held items grouped by collection: (collection, verified, items)
+pub fn group_by_collection(collectibles: &[CollectibleInfo]) -> Vec<(String, bool, Vec<&CollectibleInfo>)> {
+    let mut groups: Vec<(String, bool, Vec<&CollectibleInfo>)> = Vec::new();
+    for item in collectibles {
+        match groups.iter_mut().find(|(name, verified, _)| *name == item.collection && *verified == item.verified) {
+            Some((_, _, items)) => items.push(item),
+            None => groups.push((item.collection.clone(), item.verified, vec![item])),
+        }
+    }
+    groups
+}
+
+/// Floor price in SOL from a Magic Eden collection stats response
+fn parse_floor_sol(stats: &Value) -> Option<f64> {
+    stats["floorPrice"].as_f64().filter(|floor| *floor > 0.0).map(|lamports| lamports / LAMPORTS_PER_SOL)
+}
+
+static FLOOR_CACHE: OnceLock<Mutex<HashMap<String, (Option<f64>, Instant)>>> = OnceLock::new();
+
+fn floor_cache() -> &'static Mutex<HashMap<String, (Option<f64>, Instant)>> {
+    FLOOR_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+async fn get_json(client: &Client, url: &str) -> Result<Value, String> {
+    let response = client
+        .get(url)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("Magic Eden request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Magic Eden error {}", response.status()));
+    }
+    response.json().await.map_err(|e| format!("Failed to parse Magic Eden response: {}", e))
+}
+
+/// Floor of the collection `mint` belongs to, in SOL
+async fn collection_floor(client: &Client, mint: &str) -> Result<Option<f64>, String> {
+    let token = get_json(client, &format!("{}/tokens/{}", MAGIC_EDEN_API_URL, mint)).await?;
+    let Some(symbol) = token["collection"].as_str().filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+    let stats = get_json(client, &format!("{}/collections/{}/stats", MAGIC_EDEN_API_URL, symbol)).await?;
+    Ok(parse_floor_sol(&stats))
+}
+
+/// Value the held collectibles at their collections' floor prices
+pub async fn value_collectibles(collectibles: &[CollectibleInfo]) -> NftValuation {
+    let client = Client::new();
+    let mut collections = Vec::new();
+
+    for (collection, verified, items) in group_by_collection(collectibles) {
+        let mut floor_sol = None;
+        if verified {
+            let cached = floor_cache()
+                .lock()
+                .ok()
+                .and_then(|cache| cache.get(&collection).filter(|(_, at)| at.elapsed() < FLOOR_CACHE_TIMEOUT).map(|(floor, _)| *floor));
+            floor_sol = match cached {
+                Some(floor) => floor,
+                None => match collection_floor(&client, &items[0].mint).await {
+                    Ok(floor) => {
+                        if let Ok(mut cache) = floor_cache().lock() {
+                            cache.insert(collection.clone(), (floor, Instant::now()));
+                        }
+                        floor
+                    }
+                    Err(e) => {
+                        println!("⚠️ No floor price for {}: {}", collection, e);
+                        None
+                    }
+                },
+            };
+        }
+        collections.push(CollectionValue { collection, items: items.len(), verified, floor_sol });
+    }
+
+    collections.sort_by(|a, b| {
+        b.value_sol()
+            .unwrap_or(0.0)
+            .partial_cmp(&a.value_sol().unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    println!("🖼️ Valued {} collection(s)", collections.len());
+    NftValuation { collections }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(mint: &str, collection: &str, verified: bool) -> CollectibleInfo {
+        CollectibleInfo {
+            mint: mint.to_string(),
+            name: mint.to_string(),
+            collection: collection.to_string(),
+            image: String::new(),
+            description: None,
+            verified,
+            compressed: false,
+        }
+    }
+
+    #[test]
+    fn test_grouping_and_totals() {
+        let held = vec![item("a", "Mad Lads", true), item("b", "Mad Lads", true), item("c", "Mad Lads", false)];
+        let groups = group_by_collection(&held);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].2.len(), 2);
+
+        let valuation = NftValuation {
+            collections: vec![
+                CollectionValue { collection: "Mad Lads".to_string(), items: 2, verified: true, floor_sol: Some(40.0) },
+                CollectionValue { collection: "Mad Lads".to_string(), items: 1, verified: false, floor_sol: None },
+            ],
+        };
+        assert_eq!(valuation.total_sol(), 80.0);
+        assert_eq!(valuation.valued_items(), 2);
+    }
+
+    #[test]
+    fn test_parse_floor() {
+        assert_eq!(parse_floor_sol(&serde_json::json!({ "floorPrice": 2_500_000_000u64 })), Some(2.5));
+        assert_eq!(parse_floor_sol(&serde_json::json!({ "floorPrice": 0 })), None);
+        assert_eq!(parse_floor_sol(&serde_json::json!({})), None);
+    }
+}
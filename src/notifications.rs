@@ -0,0 +1,326 @@
+// src/notifications.rs
+//! Outbound alerts for critical events
+//!
+//! Besides the in-app UI, a Telegram bot and/or an HTTP email API can be told
+//! about a short list of critical events: large outgoing transfers, new
+//! bridge connections and failed PIN attempts. Nothing else is ever sent.
+//!
+//! The bot token and email API key are sealed with a local key before they
+//! are stored. Messages are built from a fixed set of fields, shorten every
+//! address, and are refused outright if anything in them looks like key
+//! material.
+
+use crate::pin::{decrypt_with_key, encrypt_with_key};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{message::VersionedMessage, pubkey::Pubkey, system_instruction::SystemInstruction, system_program};
+use std::time::Duration;
+
+pub const DEFAULT_EMAIL_API_URL: &str = "https://api.resend.com/emails";
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+const DEFAULT_LARGE_TRANSFER_SOL: f64 = 10.0;
+
+fn default_large_transfer_sol() -> f64 {
+    DEFAULT_LARGE_TRANSFER_SOL
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelegramChannel {
+    pub chat_id: String,
+    /// Sealed bot token (base64)
+    pub bot_token: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmailChannel {
+    /// Resend-compatible endpoint taking `{from, to, subject, text}`
+    pub api_url: String,
+    pub from: String,
+    pub to: String,
+    /// Sealed API key (base64)
+    pub api_key: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    #[serde(default)]
+    pub telegram: Option<TelegramChannel>,
+    #[serde(default)]
+    pub email: Option<EmailChannel>,
+    #[serde(default = "default_true")]
+    pub large_transfers: bool,
+    #[serde(default = "default_large_transfer_sol")]
+    pub large_transfer_sol: f64,
+    #[serde(default = "default_true")]
+    pub new_connections: bool,
+    #[serde(default = "default_true")]
+    pub failed_logins: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            telegram: None,
+            email: None,
+            large_transfers: true,
+            large_transfer_sol: DEFAULT_LARGE_TRANSFER_SOL,
+            new_connections: true,
+            failed_logins: true,
+        }
+    }
+}
+
+impl NotificationSettings {
+    pub fn has_channel(&self) -> bool {
+        self.telegram.is_some() || self.email.is_some()
+    }
+
+    fn wants(&self, event: &CriticalEvent) -> bool {
+        match event {
+            CriticalEvent::LargeTransfer { lamports, .. } => {
+                self.large_transfers && *lamports as f64 / 1_000_000_000.0 >= self.large_transfer_sol
+            }
+            CriticalEvent::NewConnection { .. } => self.new_connections,
+            CriticalEvent::FailedLogin { .. } => self.failed_logins,
+            CriticalEvent::Test => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CriticalEvent {
+    /// A transaction moving this many lamports out of `wallet` was signed
+    LargeTransfer { wallet: String, lamports: u64 },
+    /// Another app or device connected over the bridge
+    NewConnection { peer: String },
+    /// A wrong PIN was entered, locking PIN entry for a while or wiping the
+    /// wallets
+    FailedLogin { failed_attempts: u32, locked: bool, wiped: bool },
+    /// Sent from the settings screen
+    Test,
+}
+
+/// "9xQe...4k3D"
+fn short_address(address: &str) -> String {
+    if address.len() <= 12 {
+        return address.to_string();
+    }
+    format!("{}...{}", &address[..4], &address[address.len() - 4..])
+}
+
+impl CriticalEvent {
+    pub fn subject(&self) -> &'static str {
+        match self {
+            CriticalEvent::LargeTransfer { .. } => "Large outgoing transfer",
+            CriticalEvent::NewConnection { .. } => "New connection",
+            CriticalEvent::FailedLogin { .. } => "Failed unlock attempt",
+            CriticalEvent::Test => "Test notification",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        let body = match self {
+            CriticalEvent::LargeTransfer { wallet, lamports } => format!(
+                "A transaction sending {:.4} SOL from wallet {} was just signed. If this wasn't you, move your funds now.",
+                *lamports as f64 / 1_000_000_000.0,
+                short_address(wallet)
+            ),
+            CriticalEvent::NewConnection { peer } => format!(
+                "\"{}\" connected to your wallet and can now request signatures.",
+                peer.chars().take(40).collect::<String>()
+            ),
+            CriticalEvent::FailedLogin { failed_attempts, wiped: true, .. } => format!(
+                "The wallets on the device were wiped after {} wrong PIN attempts.",
+                failed_attempts
+            ),
+            CriticalEvent::FailedLogin { failed_attempts, locked: true, .. } => format!(
+                "The app was locked for a while after {} wrong PIN attempts.",
+                failed_attempts
+            ),
+            CriticalEvent::FailedLogin { failed_attempts, locked: false, .. } => format!(
+                "A wrong PIN was entered ({} failed attempt(s) in a row).",
+                failed_attempts
+            ),
+            CriticalEvent::Test => "Notifications from your Unruggable wallet are working.".to_string(),
+        };
+        format!("🛡️ Unruggable: {}\n{}", self.subject(), body)
+    }
+}
+
+/// Whether `text` contains anything shaped like a secret key or seed: a
+/// base58 word that decodes to 64 bytes, or a long hex string
+pub fn contains_key_material(text: &str) -> bool {
+    text.split(|c: char| c.is_whitespace() || c == '"' || c == ',' || c == ':')
+        .any(|word| {
+            let looks_like_keypair = word.len() >= 80
+                && bs58::decode(word).into_vec().map(|b| b.len() == 64).unwrap_or(false);
+            let looks_like_hex_key = word.len() >= 64 && word.chars().all(|c| c.is_ascii_hexdigit());
+            looks_like_keypair || looks_like_hex_key
+        })
+}
+
+/// Seal a channel secret for storage
+pub fn seal_secret(secret: &str) -> Result<String, String> {
+    let key = crate::storage::load_or_create_notification_key()?;
+    Ok(base64::encode(encrypt_with_key(secret.trim().as_bytes(), &key)?))
+}
+
+fn open_secret(sealed: &str) -> Result<String, String> {
+    let key = crate::storage::load_or_create_notification_key()?;
+    let ciphertext = base64::decode(sealed).map_err(|e| format!("Invalid sealed secret: {}", e))?;
+    let plaintext = decrypt_with_key(&ciphertext, &key)?;
+    String::from_utf8(plaintext).map_err(|e| format!("Invalid sealed secret: {}", e))
+}
+
+/// Lamports sent out of `owner` by plain system transfers in a message
+pub fn outgoing_lamports(message: &VersionedMessage, owner: &Pubkey) -> u64 {
+    let keys = message.static_account_keys();
+    message
+        .instructions()
+        .iter()
+        .filter(|ix| keys.get(ix.program_id_index as usize) == Some(&system_program::id()))
+        .filter(|ix| ix.accounts.first().and_then(|i| keys.get(*i as usize)) == Some(owner))
+        .filter_map(|ix| match bincode::deserialize::<SystemInstruction>(&ix.data).ok()? {
+            SystemInstruction::Transfer { lamports } => Some(lamports),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Alert if a message about to be signed by `owner` sends out more SOL
+/// than the configured threshold. Called from the signing firewall.
+pub fn check_outgoing_transfer(message_bytes: &[u8], owner: &Pubkey) {
+    let Ok(message) = bincode::deserialize::<VersionedMessage>(message_bytes) else { return };
+    let lamports = outgoing_lamports(&message, owner);
+    if lamports > 0 {
+        notify(CriticalEvent::LargeTransfer { wallet: owner.to_string(), lamports });
+    }
+}
+
+async fn send_telegram(channel: &TelegramChannel, text: &str) -> Result<(), String> {
+    let token = open_secret(&channel.bot_token)?;
+    let response = reqwest::Client::new()
+        .post(format!("{}/bot{}/sendMessage", TELEGRAM_API_URL, token))
+        .timeout(Duration::from_secs(15))
+        .json(&serde_json::json!({ "chat_id": channel.chat_id, "text": text }))
+        .send()
+        .await
+        // The URL holds the bot token, so only the error kind is reported
+        .map_err(|e| format!("Telegram request failed{}", if e.is_timeout() { " (timeout)" } else { "" }))?;
+    if !response.status().is_success() {
+        return Err(format!("Telegram returned {}", response.status()));
+    }
+    Ok(())
+}
+
+async fn send_email(channel: &EmailChannel, subject: &str, text: &str) -> Result<(), String> {
+    let api_key = open_secret(&channel.api_key)?;
+    let response = reqwest::Client::new()
+        .post(&channel.api_url)
+        .timeout(Duration::from_secs(15))
+        .bearer_auth(api_key)
+        .json(&serde_json::json!({
+            "from": channel.from,
+            "to": [channel.to],
+            "subject": format!("Unruggable: {}", subject),
+            "text": text,
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Email request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Email API returned {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Send `event` to every configured channel that wants it
+pub async fn send_event(settings: &NotificationSettings, event: &CriticalEvent) -> Result<(), String> {
+    if !settings.wants(event) {
+        return Ok(());
+    }
+    let text = event.message();
+    if contains_key_material(&text) {
+        return Err("Refusing to send a notification that looks like it contains key material".to_string());
+    }
+
+    let mut errors = Vec::new();
+    if let Some(telegram) = &settings.telegram {
+        if let Err(e) = send_telegram(telegram, &text).await {
+            errors.push(e);
+        }
+    }
+    if let Some(email) = &settings.email {
+        if let Err(e) = send_email(email, event.subject(), &text).await {
+            errors.push(e);
+        }
+    }
+    if errors.is_empty() {
+        println!("📣 Sent notification: {}", event.subject());
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Fire-and-forget `send_event` with the stored settings
+pub fn notify(event: CriticalEvent) {
+    let settings = crate::storage::load_notification_settings();
+    if !settings.has_channel() || !settings.wants(&event) {
+        return;
+    }
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else { return };
+    runtime.spawn(async move {
+        if let Err(e) = send_event(&settings, &event).await {
+            println!("⚠️ Notification not sent: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{hash::Hash, message::Message, signature::Keypair, signer::Signer, system_instruction};
+
+    #[test]
+    fn test_outgoing_lamports_only_counts_owner() {
+        let owner = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let instructions = vec![
+            system_instruction::transfer(&owner, &Pubkey::new_unique(), 3_000_000_000),
+            system_instruction::transfer(&owner, &Pubkey::new_unique(), 2_000_000_000),
+            system_instruction::transfer(&other, &owner, 7_000_000_000),
+        ];
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(&instructions, Some(&owner), &Hash::new_unique()));
+        assert_eq!(outgoing_lamports(&message, &owner), 5_000_000_000);
+    }
+
+    #[test]
+    fn test_threshold_and_toggles() {
+        let mut settings = NotificationSettings::default();
+        let small = CriticalEvent::LargeTransfer { wallet: "w".to_string(), lamports: 1_000_000_000 };
+        let large = CriticalEvent::LargeTransfer { wallet: "w".to_string(), lamports: 10_000_000_000 };
+        assert!(!settings.wants(&small));
+        assert!(settings.wants(&large));
+
+        settings.failed_logins = false;
+        assert!(!settings.wants(&CriticalEvent::FailedLogin { failed_attempts: 1, locked: false, wiped: false }));
+    }
+
+    #[test]
+    fn test_key_material_detection() {
+        let keypair = Keypair::new();
+        assert!(contains_key_material(&format!("oops {}", keypair.to_base58_string())));
+        assert!(contains_key_material(&hex::encode([7u8; 32])));
+        assert!(!contains_key_material(&keypair.pubkey().to_string()));
+
+        let event = CriticalEvent::LargeTransfer { wallet: keypair.pubkey().to_string(), lamports: 12_500_000_000 };
+        let message = event.message();
+        assert!(!contains_key_material(&message));
+        assert!(message.contains("12.5000 SOL"));
+        assert!(!message.contains(&keypair.pubkey().to_string()));
+    }
+}
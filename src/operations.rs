@@ -0,0 +1,616 @@
+// src/operations.rs
+//! Long-running multi-step operations
+//!
+//! Sweeps, consolidations, bulk sends and batch claims are made of many
+//! sequential transactions. An `Operation` records each step and its outcome
+//! in storage after every change, so an interrupted run can be resumed from
+//! the first unfinished step instead of leaving funds half-moved.
+//!
+//! Executors record a step's transaction before broadcasting it. A step left
+//! `Running` by a crash, or one whose send returned an error after that, is
+//! looked up on chain before it is sent again, so funds are never moved twice.
+
+use crate::signing::TransactionSigner;
+use crate::storage;
+use crate::transaction::{RecipientTransfer, TransactionClient};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Default number of attempts per step before the operation stops
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Base delay between retries of the same step (doubled on every attempt)
+const RETRY_BASE_DELAY_MS: u64 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// A single step of an operation, usually one transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationStep {
+    pub label: String,
+    pub status: StepStatus,
+    pub attempts: u32,
+    /// Signature (or other result) of the completed step
+    pub result: Option<String>,
+    pub error: Option<String>,
+    /// Step-specific input, e.g. the mint and amount of a sweep leg
+    #[serde(default)]
+    pub payload: serde_json::Value,
+    /// Signature of the transaction sent for the current attempt, recorded
+    /// before it is broadcast
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// Blockhash of that transaction, which bounds how long it can land
+    #[serde(default)]
+    pub blockhash: Option<String>,
+}
+
+impl OperationStep {
+    pub fn new(label: &str, payload: serde_json::Value) -> Self {
+        Self {
+            label: label.to_string(),
+            status: StepStatus::Pending,
+            attempts: 0,
+            result: None,
+            error: None,
+            payload,
+            signature: None,
+            blockhash: None,
+        }
+    }
+}
+
+/// What became of a step whose transaction may have been broadcast
+#[derive(Debug, Clone, PartialEq)]
+pub enum SentStep {
+    /// The transaction landed; the step is done with this result
+    Landed(String),
+    /// Nothing landed and nothing can any more, so it is safe to send again
+    NotLanded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OperationState {
+    Running,
+    Paused,
+    Failed,
+    Completed,
+}
+
+/// A persisted multi-step operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Operation {
+    pub id: String,
+    /// Kind of operation, e.g. "sweep" or "bulk_send"
+    pub kind: String,
+    pub title: String,
+    /// Wallet address the operation acts on
+    pub wallet_address: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub state: OperationState,
+    pub steps: Vec<OperationStep>,
+}
+
+impl Operation {
+    pub fn new(kind: &str, title: &str, wallet_address: &str, steps: Vec<OperationStep>) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: format!("{}-{}", kind, chrono::Utc::now().timestamp_millis()),
+            kind: kind.to_string(),
+            title: title.to_string(),
+            wallet_address: wallet_address.to_string(),
+            created_at: now,
+            updated_at: now,
+            state: OperationState::Paused,
+            steps,
+        }
+    }
+
+    pub fn completed_steps(&self) -> usize {
+        self.steps.iter().filter(|s| s.status == StepStatus::Completed).count()
+    }
+
+    /// Fraction of steps completed, from 0.0 to 1.0
+    pub fn progress(&self) -> f64 {
+        if self.steps.is_empty() {
+            return 1.0;
+        }
+        self.completed_steps() as f64 / self.steps.len() as f64
+    }
+
+    /// Index of the first step that still needs to run
+    pub fn next_step_index(&self) -> Option<usize> {
+        self.steps.iter().position(|s| s.status != StepStatus::Completed)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.next_step_index().is_none()
+    }
+
+    /// Whether the operation was interrupted and can be picked up again
+    pub fn is_resumable(&self) -> bool {
+        !self.is_complete() && self.state != OperationState::Completed
+    }
+
+    /// Give steps that failed another round of attempts on resume. Steps
+    /// left `Running` are kept as they are, for the runner to look up.
+    fn recover_failed(&mut self) {
+        for step in self.steps.iter_mut() {
+            if step.status == StepStatus::Failed {
+                step.status = StepStatus::Pending;
+                step.attempts = 0;
+            }
+        }
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = chrono::Utc::now().timestamp();
+    }
+}
+
+/// Shared pause flag; set from the UI, checked by the runner between steps
+#[derive(Debug, Clone, Default)]
+pub struct PauseHandle(Arc<AtomicBool>);
+
+impl PauseHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pause(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Handed to the executor of a step to record its transaction before
+/// broadcasting it. The record is persisted straight away.
+#[derive(Clone)]
+pub struct StepRecorder {
+    operation: Arc<Mutex<Operation>>,
+    index: usize,
+    persist: bool,
+}
+
+impl StepRecorder {
+    pub fn record(&self, signature: &str, blockhash: &str) {
+        let mut operation = self.operation.lock().unwrap();
+        let step = &mut operation.steps[self.index];
+        step.signature = Some(signature.to_string());
+        step.blockhash = Some(blockhash.to_string());
+        operation.touch();
+        if self.persist {
+            storage::save_operation_to_storage(&operation);
+        }
+    }
+
+    fn recorded(&self) -> (Option<String>, Option<String>) {
+        let operation = self.operation.lock().unwrap();
+        let step = &operation.steps[self.index];
+        (step.signature.clone(), step.blockhash.clone())
+    }
+}
+
+/// Runs operation steps in order with retry, pause and persistence
+pub struct OperationRunner {
+    pub max_attempts: u32,
+    pub pause: PauseHandle,
+    /// Persist progress after every step change
+    pub persist: bool,
+}
+
+impl OperationRunner {
+    pub fn new(pause: PauseHandle) -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            pause,
+            persist: true,
+        }
+    }
+
+    /// Run (or resume) the operation until it completes, fails or is paused.
+    ///
+    /// `execute` runs one step and returns its result (usually a signature),
+    /// recording its transaction with the `StepRecorder` before sending it.
+    /// `check` looks up a recorded transaction before its step is sent again;
+    /// an error means it can't tell yet, and the operation pauses.
+    /// `on_progress` is called with a snapshot after every state change.
+    pub async fn run<F, Fut, C, CFut, P>(
+        &self,
+        operation: &mut Operation,
+        mut execute: F,
+        mut check: C,
+        mut on_progress: P,
+    ) -> Result<(), String>
+    where
+        F: FnMut(usize, OperationStep, StepRecorder) -> Fut,
+        Fut: Future<Output = Result<String, String>>,
+        C: FnMut(usize, OperationStep) -> CFut,
+        CFut: Future<Output = Result<SentStep, String>>,
+        P: FnMut(&Operation),
+    {
+        operation.recover_failed();
+        operation.state = OperationState::Running;
+        self.save(operation, &mut on_progress);
+
+        println!("🔁 Running operation {} ({} steps)", operation.id, operation.steps.len());
+
+        // A step interrupted mid-flight may have landed
+        if let Some(index) = operation.steps.iter().position(|s| s.status == StepStatus::Running) {
+            if !self.settle_sent_step(operation, index, &mut check, &mut on_progress).await {
+                return Ok(());
+            }
+        }
+
+        while let Some(index) = operation.next_step_index() {
+            if self.pause.is_paused() {
+                println!("⏸️ Operation {} paused at step {}", operation.id, index + 1);
+                operation.state = OperationState::Paused;
+                self.save(operation, &mut on_progress);
+                return Ok(());
+            }
+
+            let step = &mut operation.steps[index];
+            step.status = StepStatus::Running;
+            step.attempts += 1;
+            step.signature = None;
+            step.blockhash = None;
+            self.save(operation, &mut on_progress);
+
+            let recorder = StepRecorder {
+                operation: Arc::new(Mutex::new(operation.clone())),
+                index,
+                persist: self.persist,
+            };
+            let outcome = execute(index, operation.steps[index].clone(), recorder.clone()).await;
+            let (signature, blockhash) = recorder.recorded();
+            operation.steps[index].signature = signature;
+            operation.steps[index].blockhash = blockhash;
+
+            match outcome {
+                Ok(result) => {
+                    println!("✅ Step {} done: {}", index + 1, result);
+                    let step = &mut operation.steps[index];
+                    step.status = StepStatus::Completed;
+                    step.result = Some(result);
+                    step.error = None;
+                    self.save(operation, &mut on_progress);
+                }
+                Err(e) => {
+                    println!("❌ Step {} failed (attempt {}): {}", index + 1, operation.steps[index].attempts, e);
+                    let attempts = operation.steps[index].attempts;
+                    operation.steps[index].error = Some(e.clone());
+
+                    // The error may have come after the transaction was sent
+                    if operation.steps[index].signature.is_some() {
+                        if !self.settle_sent_step(operation, index, &mut check, &mut on_progress).await {
+                            return Ok(());
+                        }
+                        if operation.steps[index].status == StepStatus::Completed {
+                            continue;
+                        }
+                    }
+
+                    let step = &mut operation.steps[index];
+                    if attempts >= self.max_attempts {
+                        step.status = StepStatus::Failed;
+                        operation.state = OperationState::Failed;
+                        self.save(operation, &mut on_progress);
+                        return Err(format!("Step {} failed after {} attempts: {}", index + 1, attempts, e));
+                    }
+
+                    step.status = StepStatus::Pending;
+                    self.save(operation, &mut on_progress);
+                    retry_delay(attempts).await;
+                }
+            }
+        }
+
+        operation.state = OperationState::Completed;
+        self.save(operation, &mut on_progress);
+        println!("🎉 Operation {} completed", operation.id);
+        Ok(())
+    }
+
+    /// Look up the transaction recorded for a step that may have been sent.
+    /// The step ends up `Completed` if it landed or `Pending` if it can be
+    /// sent again. Returns false, with the operation paused, if that can't
+    /// be told yet.
+    async fn settle_sent_step<C, CFut, P>(
+        &self,
+        operation: &mut Operation,
+        index: usize,
+        check: &mut C,
+        on_progress: &mut P,
+    ) -> bool
+    where
+        C: FnMut(usize, OperationStep) -> CFut,
+        CFut: Future<Output = Result<SentStep, String>>,
+        P: FnMut(&Operation),
+    {
+        let outcome = match operation.steps[index].signature {
+            Some(_) => check(index, operation.steps[index].clone()).await,
+            // Nothing was signed, so nothing can have been sent
+            None => Ok(SentStep::NotLanded),
+        };
+        let step = &mut operation.steps[index];
+        match outcome {
+            Ok(SentStep::Landed(result)) => {
+                println!("✅ Step {} had already landed: {}", index + 1, result);
+                step.status = StepStatus::Completed;
+                step.result = Some(result);
+                step.error = None;
+            }
+            Ok(SentStep::NotLanded) => {
+                step.status = StepStatus::Pending;
+                step.signature = None;
+                step.blockhash = None;
+            }
+            Err(e) => {
+                println!("⏸️ Can't tell yet whether step {} landed: {}", index + 1, e);
+                step.status = StepStatus::Running;
+                step.error = Some(e);
+                operation.state = OperationState::Paused;
+                self.save(operation, on_progress);
+                return false;
+            }
+        }
+        self.save(operation, on_progress);
+        true
+    }
+
+    fn save<P: FnMut(&Operation)>(&self, operation: &mut Operation, on_progress: &mut P) {
+        operation.touch();
+        if self.persist {
+            storage::save_operation_to_storage(operation);
+        }
+        on_progress(operation);
+    }
+}
+
+async fn retry_delay(attempt: u32) {
+    let delay = Duration::from_millis(RETRY_BASE_DELAY_MS * 2u64.pow(attempt.saturating_sub(1)));
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(delay).await;
+    #[cfg(target_arch = "wasm32")]
+    let _ = delay;
+}
+
+/// A step sending `transfers` in one transaction, as planned by
+/// `TransactionClient::plan_transfer_batches`
+pub fn transfer_step(label: &str, transfers: &[RecipientTransfer], memo: Option<&str>) -> OperationStep {
+    let transfers: Vec<serde_json::Value> = transfers.iter().map(RecipientTransfer::to_json).collect();
+    OperationStep::new(label, json!({ "transfers": transfers, "memo": memo }))
+}
+
+fn step_transfers(step: &OperationStep) -> Result<Vec<RecipientTransfer>, String> {
+    step.payload["transfers"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|transfer| RecipientTransfer::from_json(transfer).ok_or_else(|| format!("Step \"{}\" has an invalid transfer", step.label)))
+        .collect()
+}
+
+/// Run (or resume) a bulk send or sweep made of `transfer_step`s, checking
+/// on chain whether an interrupted step's transaction landed before
+/// sending it again
+pub async fn run_transfer_operation<P: FnMut(&Operation)>(
+    runner: &OperationRunner,
+    operation: &mut Operation,
+    client: &TransactionClient,
+    signer: &dyn TransactionSigner,
+    on_progress: P,
+) -> Result<(), String> {
+    let payer = signer.get_public_key().await.map_err(|e| e.to_string())?;
+    let payer = Pubkey::from_str(&payer).map_err(|e| e.to_string())?;
+    if payer.to_string() != operation.wallet_address {
+        return Err("The operation belongs to a different wallet".to_string());
+    }
+
+    runner
+        .run(
+            operation,
+            move |_, step, recorder| async move {
+                let transfers = step_transfers(&step)?;
+                let memo = step.payload["memo"].as_str();
+                let builder = client.transfer_batch_builder(&payer, &transfers, memo).await.map_err(|e| e.to_string())?;
+                client
+                    .send_built_recording(builder, signer, |signature, blockhash| recorder.record(signature, blockhash))
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            move |_, step| async move { client.check_sent_step(&step).await },
+            on_progress,
+        )
+        .await
+}
+
+/// Operations that were interrupted and can be resumed
+pub fn load_resumable_operations() -> Vec<Operation> {
+    storage::load_operations_from_storage()
+        .into_iter()
+        .filter(|o| o.is_resumable())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_operation() -> Operation {
+        Operation::new(
+            "sweep",
+            "Sweep tokens",
+            "11111111111111111111111111111111",
+            vec![
+                OperationStep::new("one", serde_json::Value::Null),
+                OperationStep::new("two", serde_json::Value::Null),
+                OperationStep::new("three", serde_json::Value::Null),
+            ],
+        )
+    }
+
+    fn test_runner() -> OperationRunner {
+        let mut runner = OperationRunner::new(PauseHandle::new());
+        runner.persist = false;
+        runner
+    }
+
+    #[test]
+    fn test_progress_and_next_step() {
+        let mut op = test_operation();
+        assert_eq!(op.next_step_index(), Some(0));
+        op.steps[0].status = StepStatus::Completed;
+        assert_eq!(op.next_step_index(), Some(1));
+        assert!((op.progress() - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert!(op.is_resumable());
+    }
+
+    /// Interrupted while step two's transaction was in flight
+    fn interrupted_operation() -> Operation {
+        let mut op = test_operation();
+        op.steps[0].status = StepStatus::Completed;
+        op.steps[1].status = StepStatus::Running;
+        op.steps[1].signature = Some("sig1".to_string());
+        op.steps[1].blockhash = Some("hash".to_string());
+        op
+    }
+
+    async fn never_checked(_: usize, _: OperationStep) -> Result<SentStep, String> {
+        panic!("no step was sent")
+    }
+
+    #[tokio::test]
+    async fn test_runner_completes_all_steps() {
+        let mut op = test_operation();
+        let result = test_runner()
+            .run(&mut op, |i, _, _| async move { Ok(format!("sig{}", i)) }, never_checked, |_| {})
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(op.state, OperationState::Completed);
+        assert_eq!(op.steps[2].result.as_deref(), Some("sig2"));
+    }
+
+    #[tokio::test]
+    async fn test_runner_stops_when_paused() {
+        let mut op = test_operation();
+        let runner = test_runner();
+        runner.pause.pause();
+        runner.run(&mut op, |_, _, _| async { Ok(String::new()) }, never_checked, |_| {}).await.unwrap();
+        assert_eq!(op.state, OperationState::Paused);
+        assert_eq!(op.completed_steps(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_step_that_landed_is_not_resent() {
+        let mut op = interrupted_operation();
+        let mut executed = Vec::new();
+        test_runner()
+            .run(
+                &mut op,
+                |i, _, _| {
+                    executed.push(i);
+                    async move { Ok(format!("sig{}", i)) }
+                },
+                |_, step| async move { Ok(SentStep::Landed(step.signature.unwrap())) },
+                |_| {},
+            )
+            .await
+            .unwrap();
+        assert_eq!(executed, vec![2]);
+        assert_eq!(op.steps[1].result.as_deref(), Some("sig1"));
+        assert_eq!(op.state, OperationState::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_step_that_did_not_land_is_resent() {
+        let mut op = interrupted_operation();
+        let mut executed = Vec::new();
+        test_runner()
+            .run(
+                &mut op,
+                |i, _, _| {
+                    executed.push(i);
+                    async move { Ok(format!("new{}", i)) }
+                },
+                |_, _| async { Ok(SentStep::NotLanded) },
+                |_| {},
+            )
+            .await
+            .unwrap();
+        assert_eq!(executed, vec![1, 2]);
+        assert_eq!(op.steps[1].result.as_deref(), Some("new1"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_interrupted_step_pauses() {
+        let mut op = interrupted_operation();
+        test_runner()
+            .run(
+                &mut op,
+                |_, _, _| async { Err("a step that may have landed was resent".to_string()) },
+                |_, _| async { Err("still pending".to_string()) },
+                |_| {},
+            )
+            .await
+            .unwrap();
+        assert_eq!(op.state, OperationState::Paused);
+        assert_eq!(op.steps[1].status, StepStatus::Running);
+        assert_eq!(op.steps[1].error.as_deref(), Some("still pending"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_send_is_checked_before_retry() {
+        let mut op = test_operation();
+        let mut attempts = 0;
+        test_runner()
+            .run(
+                &mut op,
+                |i, _, recorder| {
+                    if i == 0 {
+                        attempts += 1;
+                    }
+                    async move {
+                        recorder.record(&format!("sig{}", i), "hash");
+                        if i == 0 { Err("timed out".to_string()) } else { Ok(format!("sig{}", i)) }
+                    }
+                },
+                |_, step| async move { Ok(SentStep::Landed(step.signature.unwrap())) },
+                |_| {},
+            )
+            .await
+            .unwrap();
+        assert_eq!(attempts, 1);
+        assert_eq!(op.steps[0].status, StepStatus::Completed);
+        assert_eq!(op.steps[0].result.as_deref(), Some("sig0"));
+    }
+
+    #[test]
+    fn test_transfer_step_payload() {
+        let transfers = vec![RecipientTransfer { recipient: Pubkey::new_unique(), mint: None, amount: 0.5 }];
+        let step = transfer_step("0.5 SOL", &transfers, Some("rent"));
+        assert_eq!(step_transfers(&step).unwrap(), transfers);
+        assert_eq!(step.payload["memo"], "rent");
+    }
+}
@@ -0,0 +1,87 @@
+// src/paper_backup.rs
+//! Printable paper backups of a single wallet
+//!
+//! A sheet carries the wallet's key twice: as 33 recovery words (a 1-of-1
+//! SLIP-39 share, see `shamir`) that can be typed into Import Wallet, and as
+//! a QR code of the keypair sealed under a password (see `pin`), scanned from
+//! onboarding. Someone who finds the sheet gets the key from the words, so it
+//! has to be stored like cash; the password only protects the QR half.
+
+use crate::backup::MIN_PASSWORD_LENGTH;
+use crate::pin::{open_with_password, seal_with_password, KdfParams};
+use crate::shamir::recovery_words;
+use crate::wallet::Wallet;
+
+/// Versioned prefix of the sealed keypair in a paper backup's QR code
+const PAPER_QR_PREFIX: &str = "UNRUG-PAPER:1:";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaperBackup {
+    pub name: String,
+    pub address: String,
+    pub words: Vec<String>,
+    /// Text of the QR code
+    pub qr_payload: String,
+    pub created_on: String,
+}
+
+/// Lay out a paper backup for a wallet, sealing the QR code under `password`
+pub fn create(wallet: &Wallet, password: &str) -> Result<PaperBackup, String> {
+    create_with_params(wallet, password, KdfParams::DEFAULT)
+}
+
+fn create_with_params(wallet: &Wallet, password: &str, params: KdfParams) -> Result<PaperBackup, String> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(format!("Use a password of at least {} characters", MIN_PASSWORD_LENGTH));
+    }
+    let private_key = wallet.get_private_key();
+    let keypair = bs58::decode(&private_key)
+        .into_vec()
+        .map_err(|e| format!("Decode error: {}", e))?;
+    let sealed = seal_with_password(&keypair, password, params)?;
+    Ok(PaperBackup {
+        name: wallet.name.clone(),
+        address: wallet.get_public_key(),
+        words: recovery_words(&private_key)?.split_whitespace().map(str::to_string).collect(),
+        qr_payload: format!("{}{}", PAPER_QR_PREFIX, base64::encode(sealed)),
+        created_on: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+    })
+}
+
+/// Whether scanned text is the QR code of a paper backup
+pub fn is_paper_qr(text: &str) -> bool {
+    text.trim().starts_with(PAPER_QR_PREFIX)
+}
+
+/// Rebuild the wallet from a paper backup's QR code and its password
+pub fn open_qr(text: &str, password: &str, name: String) -> Result<Wallet, String> {
+    let encoded = text
+        .trim()
+        .strip_prefix(PAPER_QR_PREFIX)
+        .ok_or_else(|| "This is not an Unruggable paper backup code".to_string())?;
+    let sealed = base64::decode(encoded).map_err(|e| format!("Corrupted paper backup code: {}", e))?;
+    let keypair = open_with_password(&sealed, password)?;
+    Wallet::from_private_key(&keypair, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pin::TEST_PARAMS;
+
+    #[test]
+    fn test_paper_backup_round_trip() {
+        let wallet = Wallet::new("Cold".to_string());
+        assert!(create_with_params(&wallet, "short", TEST_PARAMS).is_err());
+
+        let paper = create_with_params(&wallet, "correct horse", TEST_PARAMS).unwrap();
+        assert_eq!(paper.words.len(), 33);
+        assert!(is_paper_qr(&paper.qr_payload));
+        assert!(!paper.qr_payload.contains(&wallet.get_private_key()));
+
+        let restored = open_qr(&format!(" {}\n", paper.qr_payload), "correct horse", "Cold".to_string()).unwrap();
+        assert_eq!(restored.get_public_key(), wallet.get_public_key());
+        assert!(open_qr(&paper.qr_payload, "battery staple", "Cold".to_string()).is_err());
+        assert!(open_qr("UNRUG:address:1/1:0:abc", "correct horse", "Cold".to_string()).is_err());
+    }
+}
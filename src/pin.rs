@@ -1,17 +1,34 @@
 // src/pin.rs
 use aes_gcm::{
-    aead::{Aead, KeyInit, OsRng},
+    aead::{Aead, KeyInit, OsRng, Payload},
     Aes256Gcm, Nonce,
 };
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Algorithm, Argon2, Params, Version,
+};
 use pbkdf2::{pbkdf2_hmac};
 use sha2::Sha256;
 use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 const PBKDF2_ITERATIONS: u32 = 100_000; // iOS standard
 const KEY_LENGTH: usize = 32; // 256 bits for AES-256
 const SALT_LENGTH: usize = 16;
 const NONCE_LENGTH: usize = 12;
 
+/// Marks a stored wallet key as sealed rather than raw base58
+pub const SEALED_KEY_PREFIX: &str = "sealed:";
+const KEY_BLOB_MAGIC: &[u8; 4] = b"UKEY";
+const KEY_BLOB_VERSION: u8 = 1;
+/// Magic, version, three KDF parameters, salt and nonce
+const KEY_BLOB_HEADER_LENGTH: usize = 4 + 1 + 12 + SALT_LENGTH + NONCE_LENGTH;
+
+/// Three KDF parameters, salt and key
+const VAULT_KEY_BYTES_LENGTH: usize = 12 + SALT_LENGTH + KEY_LENGTH;
+
 /// Derive encryption key from PIN using PBKDF2
 pub fn derive_key_from_pin(pin: &str, salt: &[u8]) -> [u8; KEY_LENGTH] {
     let mut key = [0u8; KEY_LENGTH];
@@ -29,7 +46,19 @@ pub fn generate_salt() -> [u8; SALT_LENGTH] {
 /// Encrypt data using PIN-derived key
 pub fn encrypt_with_pin(data: &[u8], pin: &str, salt: &[u8]) -> Result<Vec<u8>, String> {
     let key = derive_key_from_pin(pin, salt);
-    let cipher = Aes256Gcm::new_from_slice(&key)
+    encrypt_with_key(data, &key)
+}
+
+/// Decrypt data using PIN-derived key
+pub fn decrypt_with_pin(encrypted_data: &[u8], pin: &str, salt: &[u8]) -> Result<Vec<u8>, String> {
+    let key = derive_key_from_pin(pin, salt);
+    decrypt_with_key(encrypted_data, &key)
+        .map_err(|_| "Decryption failed - incorrect PIN".to_string())
+}
+
+/// Encrypt data with a raw 256-bit key (nonce is prepended to the ciphertext)
+pub fn encrypt_with_key(data: &[u8], key: &[u8; KEY_LENGTH]) -> Result<Vec<u8>, String> {
+    let cipher = Aes256Gcm::new_from_slice(key)
         .map_err(|e| format!("Failed to create cipher: {}", e))?;
     
     // Generate random nonce
@@ -48,8 +77,8 @@ pub fn encrypt_with_pin(data: &[u8], pin: &str, salt: &[u8]) -> Result<Vec<u8>,
     Ok(result)
 }
 
-/// Decrypt data using PIN-derived key
-pub fn decrypt_with_pin(encrypted_data: &[u8], pin: &str, salt: &[u8]) -> Result<Vec<u8>, String> {
+/// Decrypt data produced by `encrypt_with_key`
+pub fn decrypt_with_key(encrypted_data: &[u8], key: &[u8; KEY_LENGTH]) -> Result<Vec<u8>, String> {
     if encrypted_data.len() < NONCE_LENGTH {
         return Err("Invalid encrypted data".to_string());
     }
@@ -58,17 +87,347 @@ pub fn decrypt_with_pin(encrypted_data: &[u8], pin: &str, salt: &[u8]) -> Result
     let (nonce_bytes, ciphertext) = encrypted_data.split_at(NONCE_LENGTH);
     let nonce = Nonce::from_slice(nonce_bytes);
     
-    let key = derive_key_from_pin(pin, salt);
-    let cipher = Aes256Gcm::new_from_slice(&key)
+    let cipher = Aes256Gcm::new_from_slice(key)
         .map_err(|e| format!("Failed to create cipher: {}", e))?;
     
     // Decrypt data
     cipher.decrypt(nonce, ciphertext)
-        .map_err(|_| "Decryption failed - incorrect PIN".to_string())
+        .map_err(|_| "Decryption failed - wrong key".to_string())
+}
+
+/// Argon2id cost parameters, recorded in every sealed key so they can be
+/// raised later without breaking keys sealed before
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    /// OWASP's Argon2id baseline: 19 MiB, two passes, one lane
+    pub const DEFAULT: KdfParams = KdfParams { memory_kib: 19 * 1024, iterations: 2, parallelism: 1 };
+
+    /// Largest parameters accepted from a file, so a crafted backup or
+    /// keystore can't make the app allocate gigabytes or spin for minutes
+    pub const MAX: KdfParams = KdfParams { memory_kib: 1024 * 1024, iterations: 16, parallelism: 8 };
+
+    /// Refuse parameters read from untrusted data that exceed `MAX`
+    pub fn check_limits(&self) -> Result<(), String> {
+        let max = Self::MAX;
+        if self.memory_kib > max.memory_kib || self.iterations > max.iterations || self.parallelism > max.parallelism {
+            return Err(format!(
+                "Key derivation settings too costly ({} MiB, {} passes, {} lanes)",
+                self.memory_kib / 1024,
+                self.iterations,
+                self.parallelism
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Cheap parameters so tests stay fast in debug builds
+#[cfg(test)]
+pub(crate) const TEST_PARAMS: KdfParams = KdfParams { memory_kib: 64, iterations: 1, parallelism: 1 };
+
+/// Derive encryption key from PIN using Argon2id
+pub fn derive_key_argon2id(pin: &str, salt: &[u8], params: KdfParams) -> Result<[u8; KEY_LENGTH], String> {
+    let params = Params::new(params.memory_kib, params.iterations, params.parallelism, Some(KEY_LENGTH))
+        .map_err(|e| format!("Invalid Argon2 parameters: {}", e))?;
+    let mut key = [0u8; KEY_LENGTH];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(pin.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+/// Key derived from the PIN that seals wallet keys at rest
+#[derive(Clone)]
+pub struct VaultKey {
+    key: [u8; KEY_LENGTH],
+    salt: [u8; SALT_LENGTH],
+    params: KdfParams,
+}
+
+impl VaultKey {
+    pub fn derive(pin: &str, salt: &[u8], params: KdfParams) -> Result<Self, String> {
+        let salt: [u8; SALT_LENGTH] = salt
+            .try_into()
+            .map_err(|_| format!("Salt must be {} bytes", SALT_LENGTH))?;
+        Ok(Self { key: derive_key_argon2id(pin, &salt, params)?, salt, params })
+    }
+
+    /// Encrypt under a versioned header that names the KDF parameters and
+    /// salt. The header is authenticated along with the ciphertext.
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+        let mut nonce_bytes = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce_bytes);
+
+        let mut blob = Vec::with_capacity(KEY_BLOB_HEADER_LENGTH + plaintext.len() + 16);
+        blob.extend_from_slice(KEY_BLOB_MAGIC);
+        blob.push(KEY_BLOB_VERSION);
+        blob.extend_from_slice(&self.params.memory_kib.to_le_bytes());
+        blob.extend_from_slice(&self.params.iterations.to_le_bytes());
+        blob.extend_from_slice(&self.params.parallelism.to_le_bytes());
+        blob.extend_from_slice(&self.salt);
+        blob.extend_from_slice(&nonce_bytes);
+
+        let cipher = Aes256Gcm::new_from_slice(&self.key)
+            .map_err(|e| format!("Failed to create cipher: {}", e))?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), Payload { msg: plaintext, aad: &blob })
+            .map_err(|e| format!("Encryption failed: {}", e))?;
+        blob.extend_from_slice(&ciphertext);
+        Ok(blob)
+    }
+
+    /// The key with its parameters and salt, for wrapping under another key
+    /// (see `biometric`)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(VAULT_KEY_BYTES_LENGTH);
+        bytes.extend_from_slice(&self.params.memory_kib.to_le_bytes());
+        bytes.extend_from_slice(&self.params.iterations.to_le_bytes());
+        bytes.extend_from_slice(&self.params.parallelism.to_le_bytes());
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&self.key);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != VAULT_KEY_BYTES_LENGTH {
+            return Err("Invalid vault key length".to_string());
+        }
+        let word = |at: usize| u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]);
+        let params = KdfParams { memory_kib: word(0), iterations: word(4), parallelism: word(8) };
+        let mut salt = [0u8; SALT_LENGTH];
+        salt.copy_from_slice(&bytes[12..12 + SALT_LENGTH]);
+        let mut key = [0u8; KEY_LENGTH];
+        key.copy_from_slice(&bytes[12 + SALT_LENGTH..]);
+        Ok(Self { key, salt, params })
+    }
+
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+
+    /// Decrypt a blob produced by `seal`
+    pub fn open(&self, blob: &[u8]) -> Result<Vec<u8>, String> {
+        let (params, salt) = read_blob_header(blob)?;
+        if params != self.params || salt != self.salt {
+            return Err("Key was sealed under a different PIN".to_string());
+        }
+        let (header, ciphertext) = blob.split_at(KEY_BLOB_HEADER_LENGTH);
+        let nonce = Nonce::from_slice(&header[17 + SALT_LENGTH..]);
+
+        let cipher = Aes256Gcm::new_from_slice(&self.key)
+            .map_err(|e| format!("Failed to create cipher: {}", e))?;
+        cipher
+            .decrypt(nonce, Payload { msg: ciphertext, aad: header })
+            .map_err(|_| "Decryption failed - wrong PIN or corrupted key".to_string())
+    }
+}
+
+/// Wipe the key when the session is locked and the last copy goes away
+impl Drop for VaultKey {
+    fn drop(&mut self) {
+        self.key.fill(0);
+    }
+}
+
+/// KDF parameters and salt from the header of a sealed blob
+fn read_blob_header(blob: &[u8]) -> Result<(KdfParams, [u8; SALT_LENGTH]), String> {
+    if blob.len() < KEY_BLOB_HEADER_LENGTH || &blob[..4] != KEY_BLOB_MAGIC {
+        return Err("Not a sealed key".to_string());
+    }
+    if blob[4] != KEY_BLOB_VERSION {
+        return Err(format!("Unsupported sealed key version {}", blob[4]));
+    }
+    let word = |at: usize| u32::from_le_bytes([blob[at], blob[at + 1], blob[at + 2], blob[at + 3]]);
+    let params = KdfParams { memory_kib: word(5), iterations: word(9), parallelism: word(13) };
+    params.check_limits()?;
+    let mut salt = [0u8; SALT_LENGTH];
+    salt.copy_from_slice(&blob[17..17 + SALT_LENGTH]);
+    Ok((params, salt))
+}
+
+/// Seal data under a password with a fresh salt, for data that leaves the
+/// device such as backups
+pub fn seal_with_password(plaintext: &[u8], password: &str, params: KdfParams) -> Result<Vec<u8>, String> {
+    VaultKey::derive(password, &generate_salt(), params)?.seal(plaintext)
+}
+
+/// Open data sealed by `seal_with_password`, deriving the key with the
+/// parameters and salt its header names
+pub fn open_with_password(blob: &[u8], password: &str) -> Result<Vec<u8>, String> {
+    let (params, salt) = read_blob_header(blob)?;
+    VaultKey::derive(password, &salt, params)?
+        .open(blob)
+        .map_err(|_| "Wrong password or corrupted data".to_string())
+}
+
+/// Vault key for this session, set once the PIN is entered
+static VAULT_KEY: Mutex<Option<VaultKey>> = Mutex::new(None);
+
+pub fn unlock_vault(key: VaultKey) {
+    *VAULT_KEY.lock().unwrap() = Some(key);
+}
+
+pub fn lock_vault() {
+    *VAULT_KEY.lock().unwrap() = None;
+    set_duress_session(false);
+}
+
+pub fn vault_key() -> Option<VaultKey> {
+    VAULT_KEY.lock().unwrap().clone()
+}
+
+/// Set when the session was unlocked with the duress PIN, which opens only
+/// the decoy wallets
+static DURESS_SESSION: AtomicBool = AtomicBool::new(false);
+
+pub fn set_duress_session(duress: bool) {
+    DURESS_SESSION.store(duress, Ordering::SeqCst);
+}
+
+pub fn is_duress_session() -> bool {
+    DURESS_SESSION.load(Ordering::SeqCst)
+}
+
+pub fn is_sealed_key(stored: &str) -> bool {
+    stored.starts_with(SEALED_KEY_PREFIX)
+}
+
+/// Seal a base58 private key for storage with the session's vault key
+pub fn seal_wallet_key(private_key: &str) -> Result<String, String> {
+    let key = vault_key().ok_or_else(|| "Wallet keys are locked - enter your PIN".to_string())?;
+    seal_wallet_key_with(&key, private_key)
+}
+
+/// Seal a base58 private key for storage with a given vault key
+pub fn seal_wallet_key_with(key: &VaultKey, private_key: &str) -> Result<String, String> {
+    Ok(format!("{}{}", SEALED_KEY_PREFIX, base64::encode(key.seal(private_key.as_bytes())?)))
+}
+
+/// The base58 private key behind a stored key, sealed or not
+pub fn open_wallet_key(stored: &str) -> Result<String, String> {
+    let Some(sealed) = stored.strip_prefix(SEALED_KEY_PREFIX) else {
+        return Ok(stored.to_string());
+    };
+    let key = vault_key().ok_or_else(|| "Wallet keys are locked - enter your PIN".to_string())?;
+    let blob = base64::decode(sealed).map_err(|e| format!("Invalid sealed key: {}", e))?;
+    String::from_utf8(key.open(&blob)?).map_err(|e| format!("Invalid sealed key: {}", e))
+}
+
+/// Hash PIN for storage verification (not for encryption), as an Argon2id
+/// PHC string
+pub fn hash_pin(pin: &str) -> Result<String, String> {
+    let salt = SaltString::encode_b64(&generate_salt())
+        .map_err(|e| format!("Failed to encode salt: {}", e))?;
+    Argon2::default()
+        .hash_password(pin.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("Failed to hash PIN: {}", e))
+}
+
+/// Check a PIN against a stored hash, Argon2id or the older bare SHA-256
+pub fn verify_pin_hash(pin: &str, stored: &str) -> bool {
+    if is_legacy_pin_hash(stored) {
+        return legacy_pin_hash(pin) == stored;
+    }
+    PasswordHash::new(stored)
+        .map(|hash| Argon2::default().verify_password(pin.as_bytes(), &hash).is_ok())
+        .unwrap_or(false)
+}
+
+/// Whether a stored hash predates Argon2id and should be replaced
+pub fn is_legacy_pin_hash(stored: &str) -> bool {
+    !stored.starts_with("$argon2")
+}
+
+/// Wrong PINs in a row allowed before each further one earns a lockout
+pub const FREE_PIN_ATTEMPTS: u32 = 4;
+/// Wrong PINs in a row that wipe the wallets, for users who turn that on
+pub const MAX_PIN_ATTEMPTS: u32 = 10;
+const FIRST_LOCKOUT_SECS: u64 = 30;
+const MAX_LOCKOUT_SECS: u64 = 60 * 60;
+
+/// Wrong PINs entered in a row and the lockout they earned, stored with the
+/// PIN hash
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PinAttempts {
+    pub failed_attempts: u32,
+    /// Unix time before which no PIN is checked
+    #[serde(default)]
+    pub locked_until: u64,
+    /// Unix time of the last wrong PIN, to spot a clock set back
+    #[serde(default)]
+    pub last_failure: u64,
+}
+
+impl PinAttempts {
+    /// Count a wrong PIN at `now`, returning the lockout it earned in seconds
+    pub fn record_failure(&mut self, now: u64) -> u64 {
+        self.failed_attempts += 1;
+        let lockout = lockout_secs(self.failed_attempts);
+        self.locked_until = now + lockout;
+        self.last_failure = now;
+        lockout
+    }
+
+    /// Restart the lockout from `now` when the clock reads earlier than the
+    /// last wrong PIN, so setting it back never shortens a lockout. Returns
+    /// whether anything changed and needs saving.
+    pub fn restart_if_clock_went_back(&mut self, now: u64) -> bool {
+        if now >= self.last_failure {
+            return false;
+        }
+        self.locked_until = now + lockout_secs(self.failed_attempts);
+        self.last_failure = now;
+        true
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Seconds left before another PIN can be tried. A clock that went back
+    /// past the last wrong PIN counts as the whole lockout still to run.
+    pub fn remaining_lockout(&self, now: u64) -> u64 {
+        if now < self.last_failure {
+            return lockout_secs(self.failed_attempts);
+        }
+        self.locked_until.saturating_sub(now)
+    }
+
+    /// Whether the wallets should be wiped, when the user has opted in
+    pub fn should_wipe(&self, wipe_enabled: bool) -> bool {
+        wipe_enabled && self.failed_attempts >= MAX_PIN_ATTEMPTS
+    }
 }
 
-/// Hash PIN for storage verification (not for encryption)
-pub fn hash_pin(pin: &str) -> String {
+/// Lockout after `failed_attempts` wrong PINs in a row: none for the first
+/// few, then 30 seconds doubling with each one up to an hour
+pub fn lockout_secs(failed_attempts: u32) -> u64 {
+    if failed_attempts <= FREE_PIN_ATTEMPTS {
+        return 0;
+    }
+    let doublings = (failed_attempts - FREE_PIN_ATTEMPTS - 1).min(16);
+    (FIRST_LOCKOUT_SECS << doublings).min(MAX_LOCKOUT_SECS)
+}
+
+/// "45s", "8 min", "1 h"
+pub fn format_lockout(secs: u64) -> String {
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 60 * 60 {
+        format!("{} min", secs.div_ceil(60))
+    } else {
+        format!("{} h", secs.div_ceil(60 * 60))
+    }
+}
+
+fn legacy_pin_hash(pin: &str) -> String {
     use sha2::Digest;
     let mut hasher = Sha256::new();
     hasher.update(pin.as_bytes());
@@ -103,4 +462,95 @@ mod tests {
         
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_sealed_key_round_trip() {
+        let salt = generate_salt();
+        let key = VaultKey::derive("123456", &salt, TEST_PARAMS).unwrap();
+        let blob = key.seal(b"wallet key").unwrap();
+
+        assert_eq!(&blob[..4], KEY_BLOB_MAGIC);
+        assert_eq!(blob[4], KEY_BLOB_VERSION);
+        assert_eq!(key.open(&blob).unwrap(), b"wallet key".to_vec());
+
+        let wrong = VaultKey::derive("654321", &salt, TEST_PARAMS).unwrap();
+        assert!(wrong.open(&blob).is_err());
+
+        // The header is authenticated along with the ciphertext
+        let mut tampered = blob.clone();
+        tampered[KEY_BLOB_HEADER_LENGTH - 1] ^= 1;
+        assert!(key.open(&tampered).is_err());
+
+        let mut future = blob;
+        future[4] = KEY_BLOB_VERSION + 1;
+        assert!(key.open(&future).unwrap_err().contains("version"));
+    }
+
+    #[test]
+    fn test_vault_key_bytes() {
+        let key = VaultKey::derive("123456", &generate_salt(), TEST_PARAMS).unwrap();
+        let restored = VaultKey::from_bytes(&key.to_bytes()).unwrap();
+        assert_eq!(restored.open(&key.seal(b"wallet key").unwrap()).unwrap(), b"wallet key".to_vec());
+        assert!(VaultKey::from_bytes(&key.to_bytes()[1..]).is_err());
+    }
+
+    #[test]
+    fn test_password_sealing() {
+        let blob = seal_with_password(b"backup", "correct horse", TEST_PARAMS).unwrap();
+        assert_eq!(open_with_password(&blob, "correct horse").unwrap(), b"backup".to_vec());
+        assert!(open_with_password(&blob, "battery staple").is_err());
+
+        // A header asking for 4 GiB is refused before deriving anything
+        let mut oversized = blob.clone();
+        oversized[5..9].copy_from_slice(&(4 * 1024 * 1024u32).to_le_bytes());
+        let error = open_with_password(&oversized, "correct horse").unwrap_err();
+        assert!(error.contains("too costly"), "{}", error);
+        let mut spinning = blob;
+        spinning[9..13].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(open_with_password(&spinning, "correct horse").unwrap_err().contains("too costly"));
+    }
+
+    #[test]
+    fn test_pin_attempts() {
+        let mut attempts = PinAttempts::default();
+        for _ in 0..FREE_PIN_ATTEMPTS {
+            assert_eq!(attempts.record_failure(1_000), 0);
+        }
+        assert_eq!(attempts.remaining_lockout(1_000), 0);
+
+        assert_eq!(attempts.record_failure(1_000), 30);
+        assert_eq!(attempts.remaining_lockout(1_010), 20);
+        assert_eq!(attempts.record_failure(2_000), 60);
+        assert_eq!(attempts.remaining_lockout(2_060), 0);
+
+        // Setting the clock back doesn't end a lockout early
+        assert_eq!(attempts.remaining_lockout(1_500), 60);
+        assert!(attempts.restart_if_clock_went_back(1_500));
+        assert_eq!(attempts.remaining_lockout(1_530), 30);
+        assert!(!attempts.restart_if_clock_went_back(1_530));
+
+        while attempts.failed_attempts < MAX_PIN_ATTEMPTS {
+            attempts.record_failure(3_000);
+        }
+        assert!(attempts.should_wipe(true));
+        assert!(!attempts.should_wipe(false));
+        assert_eq!(lockout_secs(MAX_PIN_ATTEMPTS + 20), MAX_LOCKOUT_SECS);
+
+        attempts.reset();
+        assert_eq!(attempts, PinAttempts::default());
+        assert_eq!(format_lockout(90), "2 min");
+    }
+
+    #[test]
+    fn test_pin_hash() {
+        let hash = hash_pin("123456").unwrap();
+        assert!(!is_legacy_pin_hash(&hash));
+        assert!(verify_pin_hash("123456", &hash));
+        assert!(!verify_pin_hash("654321", &hash));
+
+        let legacy = legacy_pin_hash("123456");
+        assert!(is_legacy_pin_hash(&legacy));
+        assert!(verify_pin_hash("123456", &legacy));
+        assert!(!verify_pin_hash("654321", &legacy));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,124 @@
+// src/policy.rs
+//! Per-wallet notes, risk tiers and the rules enforced before signing
+//!
+//! Each stored wallet can be labelled cold, warm or hot. Cold wallets are
+//! treated as vaults: they may only send to other wallets in this app.
+
+use serde::{Deserialize, Serialize};
+
+/// How much risk a wallet is allowed to take
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskTier {
+    /// Long-term storage; outgoing transfers only to own addresses
+    Cold,
+    /// Occasional use
+    Warm,
+    /// Day-to-day spending
+    #[default]
+    Hot,
+}
+
+impl RiskTier {
+    pub const ALL: [RiskTier; 3] = [RiskTier::Cold, RiskTier::Warm, RiskTier::Hot];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RiskTier::Cold => "Cold",
+            RiskTier::Warm => "Warm",
+            RiskTier::Hot => "Hot",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            RiskTier::Cold => "🧊",
+            RiskTier::Warm => "🌤️",
+            RiskTier::Hot => "🔥",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            RiskTier::Cold => "Vault. Can only send to your own wallets.",
+            RiskTier::Warm => "Occasional use. Transfers are allowed.",
+            RiskTier::Hot => "Everyday spending. No restrictions.",
+        }
+    }
+}
+
+/// User-supplied metadata for a stored wallet
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct WalletProfile {
+    pub address: String,
+    #[serde(default)]
+    pub tier: RiskTier,
+    #[serde(default)]
+    pub notes: String,
+    /// Folder the wallet is listed under; empty for none
+    #[serde(default)]
+    pub folder: String,
+    /// Shown in place of the wallet icon when set
+    #[serde(default)]
+    pub emoji: String,
+    /// Accent color, one of `wallet_groups::WALLET_COLORS`
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Place in the wallet list; unplaced wallets follow in the order added
+    #[serde(default)]
+    pub position: Option<u32>,
+}
+
+impl WalletProfile {
+    pub fn new(address: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Check an outgoing transfer against the sender's tier
+pub fn evaluate_transfer(tier: RiskTier, recipient: &str, own_addresses: &[String]) -> Result<(), String> {
+    match tier {
+        RiskTier::Cold if !own_addresses.iter().any(|a| a == recipient) => Err(format!(
+            "This is a cold wallet. It can only send to your own wallets, and {} is not one of them.",
+            recipient
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Check a transfer from a stored wallet using its saved profile
+pub fn check_transfer(from: &str, recipient: &str) -> Result<(), String> {
+    let tier = crate::storage::load_wallet_profile(from).tier;
+    let own_addresses: Vec<String> = crate::storage::load_wallets_from_storage()
+        .into_iter()
+        .map(|w| w.address)
+        .collect();
+    evaluate_transfer(tier, recipient, &own_addresses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cold_wallet_only_sends_to_own_addresses() {
+        let own = vec!["Own1".to_string(), "Own2".to_string()];
+        assert!(evaluate_transfer(RiskTier::Cold, "Own2", &own).is_ok());
+        assert!(evaluate_transfer(RiskTier::Cold, "Stranger", &own).is_err());
+        assert!(evaluate_transfer(RiskTier::Warm, "Stranger", &own).is_ok());
+        assert!(evaluate_transfer(RiskTier::Hot, "Stranger", &own).is_ok());
+    }
+
+    #[test]
+    fn test_profile_defaults_to_hot() {
+        let profile: WalletProfile = serde_json::from_str(r#"{"address":"abc"}"#).unwrap();
+        assert_eq!(profile.tier, RiskTier::Hot);
+        assert!(profile.notes.is_empty());
+
+        let cold: WalletProfile = serde_json::from_str(r#"{"address":"abc","tier":"cold"}"#).unwrap();
+        assert_eq!(cold.tier, RiskTier::Cold);
+    }
+}
@@ -1,9 +1,10 @@
+use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error;
 use chrono::Utc;
-use std::sync::Mutex;
+use std::sync::{LazyLock, Mutex, RwLock};
 use std::time::{Duration, Instant};
 use std::sync::OnceLock;
 
@@ -11,6 +12,30 @@ use std::sync::OnceLock;
 const PYTH_HISTORY_URL: &str = "https://benchmarks.pyth.network/v1/shims/tradingview/history";
 const JUPITER_PRICE_API_URL: &str = "https://lite-api.jup.ag/price/v3";
 const JUPITER_TOKEN_API_URL: &str = "https://lite-api.jup.ag/tokens/v2/search";
+const COINGECKO_TOKEN_PRICE_URL: &str = "https://api.coingecko.com/api/v3/simple/token_price/solana";
+/// Contract addresses per CoinGecko request
+const COINGECKO_BATCH_SIZE: usize = 30;
+const PYTH_HERMES_STREAM_URL: &str = "https://hermes.pyth.network/v2/updates/price/stream";
+const PYTH_HERMES_LATEST_URL: &str = "https://hermes.pyth.network/v2/updates/price/latest";
+const BIRDEYE_MULTI_PRICE_URL: &str = "https://public-api.birdeye.so/defi/multi_price";
+/// Addresses per Birdeye request
+const BIRDEYE_BATCH_SIZE: usize = 100;
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+/// Emit a streamed price for a symbol at most this often
+const PRICE_STREAM_MIN_INTERVAL: Duration = Duration::from_secs(1);
+const PRICE_STREAM_RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+/// Pyth price feed IDs (USD quotes) by symbol
+pub const PYTH_FEED_IDS: &[(&str, &str)] = &[
+    ("SOL", "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"),
+    ("USDC", "eaa020c61cc479712813461ce153894a96a6c00b21ed0cfc2798d1f9a9e9c94a"),
+    ("USDT", "2b89b9dc8fdf9f34709a5b106b472f0f39bb6ca9ce04b0fd7f2e971688e2e53b"),
+    ("JUP", "0a0408d619e9380abad35060f9192039ed5042fa6f82301d0e48bb52be830996"),
+    ("JTO", "b43660a5f790c69354b0729a5ef9d50d68f1df92107540210b9cccba1f947cc2"),
+    ("BONK", "72b021217ca3fe68922a19aaf990109cb9d84e9ad004b4d2025ad6f529314419"),
+    ("BTC", "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43"),
+    ("ETH", "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace"),
+];
 const PRICE_CACHE_TIMEOUT: u64 = 120; // 2 minutes
 
 // Token mint addresses for Jupiter API
@@ -115,6 +140,457 @@ struct TradingViewHistoryResponse {
     v: Option<Vec<f64>>, // Volume (optional)
 }
 
+// ══════════════════════════════════════════════════════════════════════════════
+// Price providers
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// A source of current USD prices by mint
+#[async_trait]
+pub trait PriceProvider: Send + Sync {
+    /// Stable identifier used in the saved settings
+    fn id(&self) -> &'static str;
+
+    /// Name shown next to prices from this provider
+    fn name(&self) -> &'static str;
+
+    /// Prices of the mints the provider knows; unknown mints are left out
+    async fn prices_for_mints(&self, mints: &[String]) -> Result<HashMap<String, f64>, String>;
+
+    /// Time a price request for SOL, which every provider knows
+    async fn health_check(&self) -> Result<Duration, String> {
+        let started = Instant::now();
+        let prices = self.prices_for_mints(&[SOL_MINT.to_string()]).await?;
+        if !prices.contains_key(SOL_MINT) {
+            return Err("No SOL price returned".to_string());
+        }
+        Ok(started.elapsed())
+    }
+}
+
+pub struct JupiterPriceProvider;
+
+#[async_trait]
+impl PriceProvider for JupiterPriceProvider {
+    fn id(&self) -> &'static str {
+        "jupiter"
+    }
+
+    fn name(&self) -> &'static str {
+        "Jupiter"
+    }
+
+    async fn prices_for_mints(&self, mints: &[String]) -> Result<HashMap<String, f64>, String> {
+        get_jupiter_prices_for_mints(mints.to_vec()).await.map_err(|e| e.to_string())
+    }
+}
+
+pub struct CoinGeckoPriceProvider;
+
+#[async_trait]
+impl PriceProvider for CoinGeckoPriceProvider {
+    fn id(&self) -> &'static str {
+        "coingecko"
+    }
+
+    fn name(&self) -> &'static str {
+        "CoinGecko"
+    }
+
+    async fn prices_for_mints(&self, mints: &[String]) -> Result<HashMap<String, f64>, String> {
+        let client = Client::new();
+        let mut prices = HashMap::new();
+        for chunk in mints.chunks(COINGECKO_BATCH_SIZE) {
+            let response = client
+                .get(COINGECKO_TOKEN_PRICE_URL)
+                .query(&[("contract_addresses", chunk.join(",").as_str()), ("vs_currencies", "usd")])
+                .header("Accept", "application/json")
+                .send()
+                .await
+                .map_err(|e| format!("CoinGecko request failed: {}", e))?;
+            if !response.status().is_success() {
+                return Err(format!("CoinGecko error {}", response.status()));
+            }
+            let body: serde_json::Value = response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse CoinGecko response: {}", e))?;
+            prices.extend(parse_coingecko_prices(&body, chunk));
+        }
+        println!("CoinGecko returned {} prices", prices.len());
+        Ok(prices)
+    }
+}
+
+/// Prices from a CoinGecko `token_price` response, keyed by the mint as asked
+/// for (CoinGecko may change the case of addresses)
+fn parse_coingecko_prices(body: &serde_json::Value, mints: &[String]) -> HashMap<String, f64> {
+    let Some(entries) = body.as_object() else { return HashMap::new() };
+    mints
+        .iter()
+        .filter_map(|mint| {
+            let entry = entries.get(mint).or_else(|| {
+                entries.iter().find(|(address, _)| address.eq_ignore_ascii_case(mint)).map(|(_, entry)| entry)
+            })?;
+            Some((mint.clone(), entry["usd"].as_f64()?))
+        })
+        .collect()
+}
+
+/// Pyth Hermes latest prices, for the listed tokens that have a Pyth feed
+pub struct PythPriceProvider;
+
+#[async_trait]
+impl PriceProvider for PythPriceProvider {
+    fn id(&self) -> &'static str {
+        "pyth"
+    }
+
+    fn name(&self) -> &'static str {
+        "Pyth"
+    }
+
+    async fn prices_for_mints(&self, mints: &[String]) -> Result<HashMap<String, f64>, String> {
+        // Feeds are by symbol, so only mints with a known symbol can be priced
+        let symbols: Vec<(&str, &str)> = TOKEN_MINTS
+            .iter()
+            .filter(|(_, mint)| mints.iter().any(|m| m == mint))
+            .copied()
+            .collect();
+        let ids: Vec<(&str, &str)> = PYTH_FEED_IDS
+            .iter()
+            .filter(|(symbol, _)| symbols.iter().any(|(s, _)| s == symbol))
+            .map(|(_, id)| ("ids[]", *id))
+            .collect();
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let response = Client::new()
+            .get(PYTH_HERMES_LATEST_URL)
+            .query(&ids)
+            .query(&[("parsed", "true")])
+            .send()
+            .await
+            .map_err(|e| format!("Pyth request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Pyth error {}", response.status()));
+        }
+        let body = response.text().await.map_err(|e| format!("Failed to read Pyth response: {}", e))?;
+        Ok(parse_hermes_prices(&body)
+            .into_iter()
+            .filter_map(|(symbol, price)| {
+                let (_, mint) = symbols.iter().find(|(s, _)| *s == symbol)?;
+                Some((mint.to_string(), price))
+            })
+            .collect())
+    }
+}
+
+/// Birdeye multi-price API, needs an API key from the settings
+pub struct BirdeyePriceProvider {
+    api_key: Option<String>,
+}
+
+#[async_trait]
+impl PriceProvider for BirdeyePriceProvider {
+    fn id(&self) -> &'static str {
+        "birdeye"
+    }
+
+    fn name(&self) -> &'static str {
+        "Birdeye"
+    }
+
+    async fn prices_for_mints(&self, mints: &[String]) -> Result<HashMap<String, f64>, String> {
+        let api_key = self.api_key.as_deref().ok_or("Birdeye needs an API key")?;
+        let client = Client::new();
+        let mut prices = HashMap::new();
+        for chunk in mints.chunks(BIRDEYE_BATCH_SIZE) {
+            let response = client
+                .get(BIRDEYE_MULTI_PRICE_URL)
+                .query(&[("list_address", chunk.join(","))])
+                .header("X-API-KEY", api_key)
+                .header("x-chain", "solana")
+                .header("Accept", "application/json")
+                .send()
+                .await
+                .map_err(|e| format!("Birdeye request failed: {}", e))?;
+            if !response.status().is_success() {
+                return Err(format!("Birdeye error {}", response.status()));
+            }
+            let body: serde_json::Value = response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse Birdeye response: {}", e))?;
+            prices.extend(parse_birdeye_prices(&body));
+        }
+        println!("Birdeye returned {} prices", prices.len());
+        Ok(prices)
+    }
+}
+
+/// Prices from a Birdeye `multi_price` response
+fn parse_birdeye_prices(body: &serde_json::Value) -> HashMap<String, f64> {
+    let Some(entries) = body["data"].as_object() else { return HashMap::new() };
+    entries
+        .iter()
+        .filter_map(|(mint, entry)| Some((mint.clone(), entry.get("value")?.as_f64()?)))
+        .collect()
+}
+
+/// A provider's place in the lookup order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceProviderEntry {
+    pub id: String,
+    pub enabled: bool,
+}
+
+/// Which providers are asked for prices, and in what order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceProviderSettings {
+    pub providers: Vec<PriceProviderEntry>,
+    #[serde(default)]
+    pub birdeye_api_key: Option<String>,
+}
+
+impl Default for PriceProviderSettings {
+    fn default() -> Self {
+        Self {
+            providers: PRICE_PROVIDER_IDS
+                .iter()
+                .map(|id| PriceProviderEntry { id: id.to_string(), enabled: *id != "birdeye" })
+                .collect(),
+            birdeye_api_key: None,
+        }
+    }
+}
+
+impl PriceProviderSettings {
+    /// Drop unknown providers and append ones added since the settings were saved
+    pub fn normalized(mut self) -> Self {
+        self.providers.retain(|entry| PRICE_PROVIDER_IDS.contains(&entry.id.as_str()));
+        for id in PRICE_PROVIDER_IDS {
+            if !self.providers.iter().any(|entry| entry.id == *id) {
+                self.providers.push(PriceProviderEntry { id: id.to_string(), enabled: false });
+            }
+        }
+        self
+    }
+
+    /// Move the provider at `index` one place up (`-1`) or down (`1`)
+    pub fn move_provider(&mut self, index: usize, offset: isize) {
+        let target = index as isize + offset;
+        if target >= 0 && (target as usize) < self.providers.len() && index < self.providers.len() {
+            self.providers.swap(index, target as usize);
+        }
+    }
+}
+
+/// Every provider the app knows, default order
+pub const PRICE_PROVIDER_IDS: &[&str] = &["jupiter", "pyth", "coingecko", "birdeye"];
+
+/// Build the provider with the given id
+pub fn price_provider(id: &str, settings: &PriceProviderSettings) -> Option<Box<dyn PriceProvider>> {
+    match id {
+        "jupiter" => Some(Box::new(JupiterPriceProvider)),
+        "pyth" => Some(Box::new(PythPriceProvider)),
+        "coingecko" => Some(Box::new(CoinGeckoPriceProvider)),
+        "birdeye" => Some(Box::new(BirdeyePriceProvider {
+            api_key: settings.birdeye_api_key.clone().filter(|key| !key.trim().is_empty()),
+        })),
+        _ => None,
+    }
+}
+
+static PROVIDER_SETTINGS: LazyLock<RwLock<PriceProviderSettings>> =
+    LazyLock::new(|| RwLock::new(crate::storage::load_price_provider_settings().normalized()));
+
+pub fn price_provider_settings() -> PriceProviderSettings {
+    PROVIDER_SETTINGS.read().map(|settings| settings.clone()).unwrap_or_default()
+}
+
+/// Save the provider order and use it from the next price request
+pub fn save_price_provider_settings(settings: PriceProviderSettings) -> Result<(), String> {
+    let settings = settings.normalized();
+    crate::storage::save_price_provider_settings(&settings)?;
+    if let Ok(mut current) = PROVIDER_SETTINGS.write() {
+        *current = settings;
+    }
+    Ok(())
+}
+
+/// Enabled providers in the order they're asked
+fn price_providers() -> Vec<Box<dyn PriceProvider>> {
+    let settings = price_provider_settings();
+    settings
+        .providers
+        .iter()
+        .filter(|entry| entry.enabled)
+        .filter_map(|entry| price_provider(&entry.id, &settings))
+        .collect()
+}
+
+/// Provider each mint's latest price came from
+static PRICE_SOURCES: OnceLock<Mutex<HashMap<String, &'static str>>> = OnceLock::new();
+
+fn price_sources() -> &'static Mutex<HashMap<String, &'static str>> {
+    PRICE_SOURCES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Provider the latest price of `mint` came from
+pub fn price_source(mint: &str) -> Option<&'static str> {
+    price_sources().lock().ok()?.get(mint).copied()
+}
+
+/// Every provider the current prices came from, primary first
+pub fn active_price_sources() -> Vec<&'static str> {
+    let used: Vec<&'static str> = price_sources()
+        .lock()
+        .map(|sources| sources.values().copied().collect())
+        .unwrap_or_default();
+    price_providers()
+        .iter()
+        .map(|provider| provider.name())
+        .filter(|name| used.contains(name))
+        .collect()
+}
+
+/// USD prices by mint, asking each provider in turn for the mints the
+/// previous ones failed on or didn't know
+pub async fn get_prices_with_fallback(mints: &[String]) -> Result<HashMap<String, f64>, Box<dyn Error>> {
+    let mut prices = HashMap::new();
+    let mut last_error = None;
+    for provider in price_providers() {
+        let missing: Vec<String> = mints.iter().filter(|mint| !prices.contains_key(*mint)).cloned().collect();
+        if missing.is_empty() {
+            break;
+        }
+        match provider.prices_for_mints(&missing).await {
+            Ok(found) => {
+                if let Ok(mut sources) = price_sources().lock() {
+                    for mint in found.keys() {
+                        sources.insert(mint.clone(), provider.name());
+                    }
+                }
+                prices.extend(found);
+            }
+            Err(e) => {
+                println!("⚠️ {} prices failed, trying the next provider: {}", provider.name(), e);
+                last_error = Some(e);
+            }
+        }
+    }
+    match last_error {
+        Some(e) if prices.is_empty() && !mints.is_empty() => Err(e.into()),
+        _ => Ok(prices),
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Streaming prices (Pyth Hermes server-sent events)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Symbols that have a Pyth feed to stream
+pub fn streamable_symbols<'a>(symbols: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut streamable: Vec<String> = symbols
+        .into_iter()
+        .filter(|symbol| PYTH_FEED_IDS.iter().any(|(known, _)| known == symbol))
+        .map(str::to_string)
+        .collect();
+    streamable.sort();
+    streamable.dedup();
+    streamable
+}
+
+/// Take the complete events out of an SSE buffer, returning their `data`
+fn drain_sse_events(buffer: &mut String) -> Vec<String> {
+    let mut events = Vec::new();
+    while let Some(end) = buffer.find("\n\n") {
+        let event: String = buffer.drain(..end + 2).collect();
+        let data: Vec<&str> = event
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(str::trim_start)
+            .collect();
+        if !data.is_empty() {
+            events.push(data.join("\n"));
+        }
+    }
+    events
+}
+
+/// (symbol, USD price) pairs in a Hermes price update
+fn parse_hermes_prices(data: &str) -> Vec<(String, f64)> {
+    let Ok(update) = serde_json::from_str::<serde_json::Value>(data) else { return Vec::new() };
+    let Some(feeds) = update["parsed"].as_array() else { return Vec::new() };
+    feeds
+        .iter()
+        .filter_map(|feed| {
+            let id = feed["id"].as_str()?.trim_start_matches("0x");
+            let (symbol, _) = PYTH_FEED_IDS.iter().find(|(_, feed_id)| *feed_id == id)?;
+            let price: i64 = feed["price"]["price"].as_str()?.parse().ok()?;
+            let expo = feed["price"]["expo"].as_i64()? as i32;
+            let price = if expo < 0 { price as f64 / 10f64.powi(-expo) } else { price as f64 * 10f64.powi(expo) };
+            Some((symbol.to_string(), price))
+        })
+        .collect()
+}
+
+/// Stream USD prices of `symbols` from Pyth to `on_price` until the task
+/// running it is cancelled, at most once a second per symbol. Reconnects when
+/// the stream drops; `on_live` reports whether it's connected.
+pub async fn stream_prices(
+    symbols: Vec<String>,
+    mut on_price: impl FnMut(String, f64),
+    mut on_live: impl FnMut(bool),
+) {
+    let ids: Vec<(&str, &str)> = PYTH_FEED_IDS
+        .iter()
+        .filter(|(symbol, _)| symbols.iter().any(|s| s == symbol))
+        .map(|(_, id)| ("ids[]", *id))
+        .collect();
+    if ids.is_empty() {
+        return;
+    }
+    let client = Client::new();
+    let mut last_emitted: HashMap<String, Instant> = HashMap::new();
+
+    loop {
+        let request = client
+            .get(PYTH_HERMES_STREAM_URL)
+            .query(&ids)
+            .query(&[("parsed", "true")])
+            .header("Accept", "text/event-stream")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        match request {
+            Ok(mut response) => {
+                println!("📈 Streaming prices for {:?}", symbols);
+                on_live(true);
+                let mut buffer = String::new();
+                while let Ok(Some(chunk)) = response.chunk().await {
+                    buffer.push_str(&String::from_utf8_lossy(&chunk));
+                    for event in drain_sse_events(&mut buffer) {
+                        for (symbol, price) in parse_hermes_prices(&event) {
+                            let due = last_emitted
+                                .get(&symbol)
+                                .is_none_or(|at| at.elapsed() >= PRICE_STREAM_MIN_INTERVAL);
+                            if due {
+                                last_emitted.insert(symbol.clone(), Instant::now());
+                                on_price(symbol, price);
+                            }
+                        }
+                    }
+                }
+                on_live(false);
+                println!("⚠️ Price stream dropped, reconnecting");
+            }
+            Err(e) => println!("⚠️ Price stream failed: {}", e),
+        }
+        tokio::time::sleep(PRICE_STREAM_RECONNECT_DELAY).await;
+    }
+}
+
 // Cache for price data
 static PRICE_CACHE: OnceLock<Mutex<(HashMap<String, f64>, HashMap<String, MultiTimeframePriceData>, Instant)>> = OnceLock::new();
 
@@ -167,47 +643,20 @@ pub async fn get_jupiter_prices_for_mints(mint_addresses: Vec<String>) -> Result
     Ok(prices)
 }
 
-/// Fetch prices from Jupiter API for all hardcoded tokens
+/// Fetch prices for all hardcoded tokens, Jupiter first with fallbacks
 pub async fn get_jupiter_prices() -> Result<HashMap<String, f64>, Box<dyn Error>> {
-    println!("Fetching prices from Jupiter API...");
-    
-    let client = Client::new();
+    println!("Fetching prices for hardcoded tokens...");
     
-    // Build comma-separated mint addresses for all tokens
-    let mint_addresses: Vec<&str> = TOKEN_MINTS.iter().map(|(_, mint)| *mint).collect();
-    let ids_param = mint_addresses.join(",");
-    
-    println!("Jupiter API request: {} with IDs: {}", JUPITER_PRICE_API_URL, ids_param);
-    
-    let response = client
-        .get(JUPITER_PRICE_API_URL)
-        .query(&[("ids", &ids_param)])
-        .header("Accept", "application/json")
-        .send()
-        .await
-        .map_err(|e| format!("Jupiter API request failed: {}", e))?;
-
-    let status = response.status();
-    println!("Jupiter API response status: {}", status);
-
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Jupiter API error {}: {}", status, error_text).into());
-    }
-
-    let response_text = response.text().await?;
-    println!("Jupiter API raw response: {}", response_text);
-
-    let jupiter_response: HashMap<String, JupiterTokenPrice> = serde_json::from_str(&response_text)
-        .map_err(|e| format!("Failed to parse Jupiter response: {} - Response: {}", e, response_text))?;
+    let mint_addresses: Vec<String> = TOKEN_MINTS.iter().map(|(_, mint)| mint.to_string()).collect();
+    let mint_prices = get_prices_with_fallback(&mint_addresses).await?;
 
     let mut prices = HashMap::new();
     
     // Map mint addresses back to token symbols
     for (token_symbol, mint_address) in TOKEN_MINTS {
-        if let Some(token_data) = jupiter_response.get(*mint_address) {
-            prices.insert(token_symbol.to_string(), token_data.usd_price);
-            println!("Jupiter: {} = ${:.4}", token_symbol, token_data.usd_price);
+        if let Some(price) = mint_prices.get(*mint_address) {
+            prices.insert(token_symbol.to_string(), *price);
+            println!("{}: {} = ${:.4}", price_source(mint_address).unwrap_or("?"), token_symbol, price);
         } else {
             println!("Warning: No price data for {} ({})", token_symbol, mint_address);
         }
@@ -223,7 +672,7 @@ pub async fn get_jupiter_prices() -> Result<HashMap<String, f64>, Box<dyn Error>
         println!("Using fixed price for USDT: $1.00");
     }
     
-    println!("Jupiter API returned {} prices", prices.len());
+    println!("Fetched {} prices", prices.len());
     Ok(prices)
 }
 
@@ -245,7 +694,7 @@ pub async fn get_prices_for_tokens(token_mint_to_symbol: HashMap<String, String>
     println!("Requesting prices for mints: {:?}", all_mints);
     
     // Fetch prices by mint addresses
-    let mint_prices = get_jupiter_prices_for_mints(all_mints).await?;
+    let mint_prices = get_prices_with_fallback(&all_mints).await?;
     
     // Convert from mint->price to symbol->price
     let mut symbol_prices = HashMap::new();
@@ -328,6 +777,25 @@ pub async fn get_token_metadata(mint_addresses: Vec<String>) -> Result<HashMap<S
     Ok(all_tokens)
 }
 
+/// Search Jupiter's token list by symbol, name or mint address
+pub async fn search_tokens(query: &str) -> Result<Vec<JupiterTokenInfo>, Box<dyn Error>> {
+    let response = Client::new()
+        .get(JUPITER_TOKEN_API_URL)
+        .query(&[("query", query.trim())])
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("Jupiter Token API request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Jupiter Token API error {}", response.status()).into());
+    }
+    let tokens: Vec<JupiterTokenInfo> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Jupiter Token API response: {}", e))?;
+    Ok(tokens)
+}
+
 fn create_dummy_multi_data(prices: &HashMap<String, f64>) -> HashMap<String, MultiTimeframePriceData> {
     let mut multi_data = HashMap::new();
     
@@ -420,7 +888,7 @@ pub async fn get_candlestick_data(symbol: &str, days: i64) -> Result<Vec<Candles
             high: highs[i],
             low: lows[i],
             close: closes[i],
-            volume: volumes.as_ref().map(|v| v[i]),
+            volume: volumes.as_ref().and_then(|v| v.get(i).copied()),
         });
     }
     
@@ -433,15 +901,21 @@ pub async fn get_candlestick_data_with_resolution(
     days: i64, 
     resolution: &str
 ) -> Result<Vec<CandlestickData>, Box<dyn Error>> {
-    let client = Client::new();
     let end_time = Utc::now();
     let start_time = end_time - chrono::Duration::days(days);
+    fetch_candles(symbol, resolution, start_time.timestamp(), end_time.timestamp()).await
+}
+
+/// Candles of `symbol` between `from` and `to` (unix seconds). A range
+/// without trades gives no candles rather than an error.
+async fn fetch_candles(symbol: &str, resolution: &str, from: i64, to: i64) -> Result<Vec<CandlestickData>, Box<dyn Error>> {
+    let client = Client::new();
     
     let params = [
         ("symbol", format!("Crypto.{}/USD", symbol)),
         ("resolution", resolution.to_string()),
-        ("from", start_time.timestamp().to_string()),
-        ("to", end_time.timestamp().to_string()),
+        ("from", from.to_string()),
+        ("to", to.to_string()),
     ];
     
     let response = client
@@ -457,6 +931,9 @@ pub async fn get_candlestick_data_with_resolution(
     
     let hist_data: TradingViewHistoryResponse = response.json().await?;
     
+    if hist_data.s == "no_data" {
+        return Ok(Vec::new());
+    }
     if hist_data.s != "ok" {
         return Err(format!("API returned error status: {}", hist_data.s).into());
     }
@@ -476,13 +953,161 @@ pub async fn get_candlestick_data_with_resolution(
             high: highs[i],
             low: lows[i],
             close: closes[i],
-            volume: volumes.as_ref().map(|v| v[i]),
+            volume: volumes.as_ref().and_then(|v| v.get(i).copied()),
         });
     }
     
     Ok(candlesticks)
 }
 
+// ══════════════════════════════════════════════════════════════════════════════
+// Candle cache
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Candles stored for a symbol and chart timeframe, for showing a chart
+/// before the network answers
+pub fn cached_candles(symbol: &str, timeframe: &str) -> Vec<CandlestickData> {
+    crate::storage::load_candles(symbol, timeframe)
+}
+
+/// Put `fresh` candles after the cached ones, replacing cached candles from
+/// the first fresh one on (the last cached candle may have still been
+/// forming), and drop candles that fell out of the chart window
+fn merge_candles(cached: Vec<CandlestickData>, fresh: Vec<CandlestickData>, window_start: i64) -> Vec<CandlestickData> {
+    let first_fresh = fresh.first().map(|c| c.timestamp).unwrap_or(i64::MAX);
+    let mut merged: Vec<CandlestickData> = cached
+        .into_iter()
+        .filter(|c| c.timestamp < first_fresh && c.timestamp >= window_start)
+        .collect();
+    merged.extend(fresh.into_iter().filter(|c| c.timestamp >= window_start));
+    merged
+}
+
+/// Candles for a chart timeframe (see `chart_timeframe`), only asking Pyth
+/// for those since the last stored candle. Falls back to the stored candles
+/// when the update fails.
+pub async fn get_cached_candlestick_data(symbol: &str, timeframe: &str) -> Result<Vec<CandlestickData>, Box<dyn Error>> {
+    let (days, resolution, _) = chart_timeframe(timeframe);
+    let now = Utc::now().timestamp();
+    let window_start = now - days * 86_400;
+    let cached = cached_candles(symbol, timeframe);
+    let from = cached
+        .last()
+        .map(|c| c.timestamp)
+        .filter(|last| *last >= window_start)
+        .unwrap_or(window_start);
+
+    let fresh = match fetch_candles(symbol, resolution, from, now).await {
+        Ok(fresh) => fresh,
+        Err(e) if !cached.is_empty() => {
+            println!("⚠️ Using stored candles for {} ({}): {}", symbol, timeframe, e);
+            return Ok(cached);
+        }
+        Err(e) => return Err(e),
+    };
+    println!("🕯️ {} new candle(s) for {} ({}), {} stored", fresh.len(), symbol, timeframe, cached.len());
+
+    let candles = merge_candles(if from == window_start { Vec::new() } else { cached }, fresh, window_start);
+    if let Err(e) = crate::storage::save_candles(symbol, timeframe, &candles) {
+        println!("⚠️ Failed to store candles: {}", e);
+    }
+    Ok(candles)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Chart timeframes and indicators
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Timeframes offered on token charts
+pub const CHART_TIMEFRAMES: &[&str] = &["1H", "1D", "1W", "1M"];
+/// Period of the moving average drawn on charts
+pub const MOVING_AVERAGE_PERIOD: usize = 20;
+pub const RSI_PERIOD: usize = 14;
+
+/// Days of history, Pyth resolution and label of the period shown for a
+/// chart timeframe (candle size)
+pub fn chart_timeframe(timeframe: &str) -> (i64, &'static str, &'static str) {
+    match timeframe {
+        "1H" => (3, "60", "3D"),
+        "1W" => (365, "1W", "1Y"),
+        "1M" => (1825, "1M", "5Y"),
+        _ => (30, "1D", "30D"),
+    }
+}
+
+/// Simple moving average of `values`, `None` until `period` values are in
+pub fn moving_average(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    if period == 0 {
+        return vec![None; values.len()];
+    }
+    let mut sum = 0.0;
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            sum += value;
+            if i >= period {
+                sum -= values[i - period];
+            }
+            (i + 1 >= period).then(|| sum / period as f64)
+        })
+        .collect()
+}
+
+/// Relative strength index (Wilder's smoothing) of `closes`, `None` for the
+/// first `period` candles
+pub fn rsi(closes: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; closes.len()];
+    if period == 0 || closes.len() <= period {
+        return result;
+    }
+    let change = |i: usize| closes[i] - closes[i - 1];
+    let mut gain: f64 = (1..=period).map(|i| change(i).max(0.0)).sum::<f64>() / period as f64;
+    let mut loss: f64 = (1..=period).map(|i| (-change(i)).max(0.0)).sum::<f64>() / period as f64;
+    let value = |gain: f64, loss: f64| if loss == 0.0 { 100.0 } else { 100.0 - 100.0 / (1.0 + gain / loss) };
+    result[period] = Some(value(gain, loss));
+    for i in period + 1..closes.len() {
+        gain = (gain * (period - 1) as f64 + change(i).max(0.0)) / period as f64;
+        loss = (loss * (period - 1) as f64 + (-change(i)).max(0.0)) / period as f64;
+        result[i] = Some(value(gain, loss));
+    }
+    result
+}
+
+/// Daily close in USD for `symbol` on the UTC day containing `timestamp`, if
+/// Pyth has a candle for that day
+pub async fn get_historical_close(symbol: &str, timestamp: i64) -> Result<Option<f64>, Box<dyn Error>> {
+    let client = Client::new();
+    let day_start = timestamp - timestamp.rem_euclid(86_400);
+
+    let params = [
+        ("symbol", format!("Crypto.{}/USD", symbol)),
+        ("resolution", "1D".to_string()),
+        ("from", day_start.to_string()),
+        ("to", (day_start + 86_399).to_string()),
+    ];
+
+    let response = client
+        .get(PYTH_HISTORY_URL)
+        .query(&params)
+        .header("accept", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(format!("API error for {}: {}", symbol, response.status()).into());
+    }
+
+    let hist_data: TradingViewHistoryResponse = response.json().await?;
+
+    // "no_data" just means Pyth has no feed or candle for that day
+    if hist_data.s != "ok" {
+        return Ok(None);
+    }
+
+    Ok(hist_data.c.and_then(|closes| closes.last().copied()))
+}
+
 // Legacy compatibility functions
 pub async fn get_prices() -> Result<HashMap<String, f64>, Box<dyn Error>> {
     get_jupiter_prices().await
@@ -538,4 +1163,83 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_stream_parsing() {
+        let mut buffer = String::from(
+            "data:{\"parsed\":[{\"id\":\"ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d\",\
+             \"price\":{\"price\":\"15025000000\",\"conf\":\"1\",\"expo\":-8,\"publish_time\":1}}]}\n\ndata:{\"parsed\"",
+        );
+        let events = drain_sse_events(&mut buffer);
+        assert_eq!(events.len(), 1);
+        assert_eq!(buffer, "data:{\"parsed\"");
+        assert_eq!(parse_hermes_prices(&events[0]), vec![("SOL".to_string(), 150.25)]);
+        assert_eq!(streamable_symbols(["SOL", "WIF", "SOL", "BONK"]), vec!["BONK", "SOL"]);
+    }
+
+    #[test]
+    fn test_indicators() {
+        assert_eq!(moving_average(&[1.0, 2.0, 3.0, 4.0], 2), vec![None, Some(1.5), Some(2.5), Some(3.5)]);
+
+        let rising: Vec<f64> = (1..=20).map(f64::from).collect();
+        let values = rsi(&rising, RSI_PERIOD);
+        assert!(values[..RSI_PERIOD].iter().all(Option::is_none));
+        assert_eq!(values[RSI_PERIOD], Some(100.0));
+
+        let alternating: Vec<f64> = (0..30).map(|i| if i % 2 == 0 { 10.0 } else { 11.0 }).collect();
+        let last = rsi(&alternating, RSI_PERIOD).last().copied().flatten().unwrap();
+        assert!((40.0..60.0).contains(&last));
+    }
+
+    #[test]
+    fn test_parse_coingecko_prices() {
+        let body = serde_json::json!({
+            "epjfwdd5aufqssqem2qn1xzybapc8g4wegggkzwytdt1v": { "usd": 0.9998 },
+            "So11111111111111111111111111111111111111112": { "usd": 150.25 },
+            "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263": {}
+        });
+        let mints = vec![
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+            "So11111111111111111111111111111111111111112".to_string(),
+            "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263".to_string(),
+        ];
+        let prices = parse_coingecko_prices(&body, &mints);
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices["EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"], 0.9998);
+        assert_eq!(prices["So11111111111111111111111111111111111111112"], 150.25);
+    }
+
+    #[test]
+    fn test_provider_settings() {
+        let saved = PriceProviderSettings {
+            providers: vec![
+                PriceProviderEntry { id: "coingecko".to_string(), enabled: true },
+                PriceProviderEntry { id: "retired".to_string(), enabled: true },
+            ],
+            birdeye_api_key: None,
+        };
+        let mut settings = saved.normalized();
+        let ids: Vec<&str> = settings.providers.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["coingecko", "jupiter", "pyth", "birdeye"]);
+        assert!(!settings.providers[1].enabled);
+
+        settings.move_provider(1, -1);
+        settings.move_provider(0, -1);
+        assert_eq!(settings.providers[0].id, "jupiter");
+
+        let body = serde_json::json!({ "data": { "So11111111111111111111111111111111111111112": { "value": 150.5 } } });
+        assert_eq!(parse_birdeye_prices(&body)[SOL_MINT], 150.5);
+    }
+
+    #[test]
+    fn test_merge_candles() {
+        let candle = |timestamp: i64, close: f64| CandlestickData { timestamp, open: close, high: close, low: close, close, volume: None };
+        let cached = vec![candle(100, 1.0), candle(200, 2.0), candle(300, 3.0)];
+        let fresh = vec![candle(300, 3.5), candle(400, 4.0)];
+        let merged = merge_candles(cached, fresh, 150);
+        let closes: Vec<f64> = merged.iter().map(|c| c.close).collect();
+        assert_eq!(closes, vec![2.0, 3.5, 4.0]);
+
+        assert_eq!(merge_candles(vec![candle(200, 2.0)], Vec::new(), 0).len(), 1);
+    }
+}
@@ -0,0 +1,176 @@
+// src/qr_transfer.rs
+//! Moving addresses and unsigned transactions between my own devices by QR
+//!
+//! A payload too large for one QR code is split into numbered frames that are
+//! shown in a loop (an "animated" QR). The receiver collects frames in any
+//! order, skipping repeats, until every part has been seen and the checksum
+//! matches. No clipboard or network is involved.
+//!
+//! Frame format: `UNRUG:{kind}:{index}/{total}:{checksum}:{chunk}`
+
+use sha2::{Digest, Sha256};
+
+const FRAME_PREFIX: &str = "UNRUG";
+/// Characters of payload per frame; keeps each code easy to scan on screen
+pub const FRAME_CHUNK_CHARS: usize = 300;
+
+/// What a transfer carries, so the receiver knows how to treat it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferKind {
+    Address,
+    Transaction,
+}
+
+impl TransferKind {
+    fn tag(&self) -> &'static str {
+        match self {
+            TransferKind::Address => "addr",
+            TransferKind::Transaction => "tx",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "addr" => Some(TransferKind::Address),
+            "tx" => Some(TransferKind::Transaction),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TransferKind::Address => "Address",
+            TransferKind::Transaction => "Unsigned transaction",
+        }
+    }
+}
+
+fn checksum(payload: &str) -> String {
+    hex::encode(&Sha256::digest(payload.as_bytes())[..4])
+}
+
+/// Split `payload` (ASCII, e.g. base58 or base64) into QR frames
+pub fn encode_frames(kind: TransferKind, payload: &str) -> Vec<String> {
+    let sum = checksum(payload);
+    let chunks: Vec<&str> = payload
+        .as_bytes()
+        .chunks(FRAME_CHUNK_CHARS)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+    let total = chunks.len().max(1);
+    (0..total)
+        .map(|i| {
+            format!(
+                "{}:{}:{}/{}:{}:{}",
+                FRAME_PREFIX,
+                kind.tag(),
+                i + 1,
+                total,
+                sum,
+                chunks.get(i).copied().unwrap_or_default()
+            )
+        })
+        .collect()
+}
+
+/// Collects scanned frames until the payload is complete
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameAssembler {
+    kind: Option<TransferKind>,
+    checksum: String,
+    parts: Vec<Option<String>>,
+}
+
+impl FrameAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one scanned frame. Frames from a different transfer than the one
+    /// in progress are rejected; scanning the same frame again is harmless.
+    pub fn add(&mut self, frame: &str) -> Result<(), String> {
+        let mut fields = frame.trim().splitn(5, ':');
+        let (Some(FRAME_PREFIX), Some(tag), Some(position), Some(sum), Some(chunk)) =
+            (fields.next(), fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err("Not a transfer code from this app".to_string());
+        };
+        let kind = TransferKind::from_tag(tag).ok_or("Unknown transfer type")?;
+        let (index, total) = position
+            .split_once('/')
+            .and_then(|(i, t)| Some((i.parse::<usize>().ok()?, t.parse::<usize>().ok()?)))
+            .filter(|(i, t)| *i >= 1 && i <= t)
+            .ok_or("Malformed frame number")?;
+
+        if self.parts.is_empty() {
+            self.kind = Some(kind);
+            self.checksum = sum.to_string();
+            self.parts = vec![None; total];
+        } else if self.kind != Some(kind) || self.checksum != sum || self.parts.len() != total {
+            return Err("This code belongs to a different transfer. Start over to switch.".to_string());
+        }
+        self.parts[index - 1] = Some(chunk.to_string());
+        Ok(())
+    }
+
+    /// Frames received so far and the total number of frames
+    pub fn progress(&self) -> (usize, usize) {
+        (self.parts.iter().filter(|p| p.is_some()).count(), self.parts.len())
+    }
+
+    /// The payload once every frame is in and the checksum matches
+    pub fn finish(&self) -> Option<Result<(TransferKind, String), String>> {
+        let (received, total) = self.progress();
+        if total == 0 || received < total {
+            return None;
+        }
+        let payload: String = self.parts.iter().flatten().map(String::as_str).collect();
+        if checksum(&payload) != self.checksum {
+            return Some(Err("Checksum mismatch. Start over and scan again.".to_string()));
+        }
+        Some(Ok((self.kind?, payload)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_frame_round_trip() {
+        let address = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+        let frames = encode_frames(TransferKind::Address, address);
+        assert_eq!(frames.len(), 1);
+
+        let mut assembler = FrameAssembler::new();
+        assembler.add(&frames[0]).unwrap();
+        assert_eq!(assembler.finish(), Some(Ok((TransferKind::Address, address.to_string()))));
+    }
+
+    #[test]
+    fn test_multi_frame_out_of_order_with_repeats() {
+        let payload = "A".repeat(FRAME_CHUNK_CHARS * 2) + "tail";
+        let frames = encode_frames(TransferKind::Transaction, &payload);
+        assert_eq!(frames.len(), 3);
+
+        let mut assembler = FrameAssembler::new();
+        assembler.add(&frames[2]).unwrap();
+        assembler.add(&frames[0]).unwrap();
+        assembler.add(&frames[0]).unwrap();
+        assert_eq!(assembler.progress(), (2, 3));
+        assert!(assembler.finish().is_none());
+
+        assembler.add(&frames[1]).unwrap();
+        assert_eq!(assembler.finish(), Some(Ok((TransferKind::Transaction, payload))));
+    }
+
+    #[test]
+    fn test_rejects_foreign_frames() {
+        let mut assembler = FrameAssembler::new();
+        assert!(assembler.add("hello").is_err());
+        assert!(assembler.add("UNRUG:tx:3/2:00000000:abc").is_err());
+
+        assembler.add(&encode_frames(TransferKind::Address, "first")[0]).unwrap();
+        assert!(assembler.add(&encode_frames(TransferKind::Address, "second")[0]).is_err());
+    }
+}
@@ -25,10 +25,10 @@ pub struct QuantumVaultClient {
 impl QuantumVaultClient {
     /// Create a new quantum vault client
     pub fn new(rpc_url: Option<&str>) -> Result<Self, String> {
-        let url = rpc_url.unwrap_or("https://api.mainnet-beta.solana.com");
+        let url = crate::rpc_pool::resolve(rpc_url.unwrap_or("https://api.mainnet-beta.solana.com"));
         Ok(Self {
             rpc_client: RpcClient::new_with_commitment(
-                url.to_string(),
+                url,
                 CommitmentConfig::confirmed(),
             ),
             program_id: Pubkey::from_str(QUANTUM_VAULT_PROGRAM_ID)
@@ -47,19 +47,9 @@ pub async fn get_balance(address: &str, rpc_url: Option<&str>) -> Result<f64, St
         ],
     };
 
-    let response = client
-        .post(url)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
+    let json: Value = crate::rpc_pool::post_json(&client, url, &request)
         .await
-        .map_err(|e| format!("Failed to send request: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("RPC error: {}", response.status()));
-    }
-
-    let json: Value = response.json().await.map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| format!("RPC request failed: {}", e))?;
 
     if let Some(error) = json.get("error") {
         return Err(format!("RPC error: {:?}", error));
@@ -90,13 +80,7 @@ pub async fn get_minimum_balance_for_rent_exemption(
         "params": [account_size]
     });
 
-    let response = client
-        .post(url)
-        .json(&request)
-        .send()
-        .await?;
-
-    let json: Value = response.json().await?;
+    let json = crate::rpc_pool::post_json(&client, url, &request).await?;
     Ok(json["result"].as_u64().ok_or("Invalid rent exemption response")?)
 }
 
@@ -166,7 +150,7 @@ pub enum TokenAccountFilter {
 }
 
 /// Struct to return token account details in a user-friendly format.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct TokenAccountInfo {
     pub pubkey: String,
     pub mint: String,
@@ -174,6 +158,8 @@ pub struct TokenAccountInfo {
     pub amount: f64,
     pub decimals: u8,
     pub state: String,
+    /// Token or Token-2022 program owning the account
+    pub program_id: String,
 }
 
 /// Fetches token accounts owned by the specified address, filtered by mint or program ID.
@@ -205,22 +191,9 @@ pub async fn get_token_accounts_by_owner(
         ],
     };
 
-    let response = client
-        .post(url)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("RPC error: {}", response.status()));
-    }
-
-    let json: serde_json::Value = response
-        .json()
+    let json: serde_json::Value = crate::rpc_pool::post_json(&client, url, &request)
         .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| format!("RPC request failed: {}", e))?;
 
     // Check for errors in the response
     if let Some(error) = json.get("error") {
@@ -243,12 +216,51 @@ pub async fn get_token_accounts_by_owner(
             amount: account.account.data.parsed.info.token_amount.ui_amount,
             decimals: account.account.data.parsed.info.token_amount.decimals,
             state: account.account.data.parsed.info.state,
+            program_id: account.account.owner,
         })
         .collect();
 
     Ok(token_accounts)
 }
 
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// Merge token account lists, keeping the first entry of each account
+fn dedupe_token_accounts(lists: impl IntoIterator<Item = Vec<TokenAccountInfo>>) -> Vec<TokenAccountInfo> {
+    let mut seen = std::collections::HashSet::new();
+    lists
+        .into_iter()
+        .flatten()
+        .filter(|account| seen.insert(account.pubkey.clone()))
+        .collect()
+}
+
+/// Every Token and Token-2022 account owned by `address`, fetched in parallel
+pub async fn get_all_token_accounts(address: &str, rpc_url: Option<&str>) -> Result<Vec<TokenAccountInfo>, String> {
+    let (token, token_2022) = futures_util::future::join(
+        get_token_accounts_by_owner(address, Some(TokenAccountFilter::ProgramId(TOKEN_PROGRAM_ID.to_string())), rpc_url),
+        get_token_accounts_by_owner(address, Some(TokenAccountFilter::ProgramId(TOKEN_2022_PROGRAM_ID.to_string())), rpc_url),
+    )
+    .await;
+
+    // One program failing shouldn't hide the other's tokens
+    let (token, token_2022) = match (token, token_2022) {
+        (Err(e), Err(_)) => return Err(e),
+        (token, token_2022) => {
+            if let Err(e) = &token {
+                println!("Failed to fetch Token program accounts: {}", e);
+            }
+            if let Err(e) = &token_2022 {
+                println!("Failed to fetch Token-2022 program accounts: {}", e);
+            }
+            (token.unwrap_or_default(), token_2022.unwrap_or_default())
+        }
+    };
+    println!("Found {} token accounts ({} Token + {} Token-2022)", token.len() + token_2022.len(), token.len(), token_2022.len());
+    Ok(dedupe_token_accounts([token, token_2022]))
+}
+
 // =================== STAKE ACCOUNT SUPPORT ===================
 
 /// Stake account specific structures for parsing getProgramAccounts response
@@ -346,10 +358,32 @@ pub struct EpochInfo {
     pub transaction_count: Option<u64>,
 }
 
+/// Stake accounts store their stake authority at byte 12 and their withdraw
+/// authority at byte 44
+const STAKER_OFFSET: u64 = 12;
+const WITHDRAWER_OFFSET: u64 = 44;
+
 /// Fetches all stake accounts owned by the specified wallet address
 pub async fn get_stake_accounts_by_owner(
     wallet_address: &str,
     rpc_url: Option<&str>,
+) -> Result<Vec<StakeAccountRpcData>, String> {
+    get_stake_accounts_by_authority(wallet_address, WITHDRAWER_OFFSET, rpc_url).await
+}
+
+/// Fetches stake accounts whose stake authority is the wallet, including
+/// ones withdrawable by someone else
+pub async fn get_stake_accounts_by_staker(
+    wallet_address: &str,
+    rpc_url: Option<&str>,
+) -> Result<Vec<StakeAccountRpcData>, String> {
+    get_stake_accounts_by_authority(wallet_address, STAKER_OFFSET, rpc_url).await
+}
+
+async fn get_stake_accounts_by_authority(
+    wallet_address: &str,
+    offset: u64,
+    rpc_url: Option<&str>,
 ) -> Result<Vec<StakeAccountRpcData>, String> {
     let client = Client::new();
     let url = rpc_url.unwrap_or(DEFAULT_RPC_URL);
@@ -367,7 +401,7 @@ pub async fn get_stake_accounts_by_owner(
                 "filters": [
                     {
                         "memcmp": {
-                            "offset": 44,
+                            "offset": offset,
                             "bytes": wallet_address
                         }
                     }
@@ -376,22 +410,9 @@ pub async fn get_stake_accounts_by_owner(
         ],
     };
 
-    let response = client
-        .post(url)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("RPC error: {}", response.status()));
-    }
-
-    let json: serde_json::Value = response
-        .json()
+    let json: serde_json::Value = crate::rpc_pool::post_json(&client, url, &request)
         .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| format!("RPC request failed: {}", e))?;
 
     // Check for errors in the response
     if let Some(error) = json.get("error") {
@@ -418,35 +439,88 @@ pub async fn get_epoch_info(rpc_url: Option<&str>) -> Result<EpochInfo, String>
         params: vec![],
     };
 
-    let response = client
-        .post(url)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
+    let json: serde_json::Value = crate::rpc_pool::post_json(&client, url, &request)
         .await
-        .map_err(|e| format!("Failed to send request: {}", e))?;
+        .map_err(|e| format!("RPC request failed: {}", e))?;
 
-    if !response.status().is_success() {
-        return Err(format!("RPC error: {}", response.status()));
+    // Check for errors in the response
+    if let Some(error) = json.get("error") {
+        return Err(format!("RPC error: {:?}", error));
     }
 
-    let json: serde_json::Value = response
-        .json()
+    // Deserialize the result
+    let rpc_response: RpcResponse<EpochInfo> = serde_json::from_value(json)
+        .map_err(|e| format!("Failed to deserialize response: {}", e))?;
+
+    Ok(rpc_response.result)
+}
+
+/// Inflation reward paid to an account at the end of an epoch
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InflationReward {
+    pub epoch: u64,
+    pub amount: u64,
+    pub post_balance: u64,
+    pub effective_slot: u64,
+    /// Commission of a vote account when the reward was paid
+    #[serde(default)]
+    pub commission: Option<u8>,
+}
+
+/// Rewards paid to `addresses` for `epoch`, `None` where an account earned nothing
+pub async fn get_inflation_rewards(
+    addresses: &[String],
+    epoch: u64,
+    rpc_url: Option<&str>,
+) -> Result<Vec<Option<InflationReward>>, String> {
+    let client = Client::new();
+    let url = rpc_url.unwrap_or(DEFAULT_RPC_URL);
+
+    let request = RpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: 1,
+        method: "getInflationReward".to_string(),
+        params: vec![json!(addresses), json!({ "epoch": epoch })],
+    };
+
+    let json: serde_json::Value = crate::rpc_pool::post_json(&client, url, &request)
         .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| format!("RPC request failed: {}", e))?;
 
-    // Check for errors in the response
     if let Some(error) = json.get("error") {
         return Err(format!("RPC error: {:?}", error));
     }
 
-    // Deserialize the result
-    let rpc_response: RpcResponse<EpochInfo> = serde_json::from_value(json)
+    let rpc_response: RpcResponse<Vec<Option<InflationReward>>> = serde_json::from_value(json)
         .map_err(|e| format!("Failed to deserialize response: {}", e))?;
 
     Ok(rpc_response.result)
 }
 
+/// Unix time a slot's block was produced, `None` if the node no longer has it
+pub async fn get_block_time(slot: u64, rpc_url: Option<&str>) -> Result<Option<i64>, String> {
+    let client = Client::new();
+    let url = rpc_url.unwrap_or(DEFAULT_RPC_URL);
+
+    let request = RpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: 1,
+        method: "getBlockTime".to_string(),
+        params: vec![json!(slot)],
+    };
+
+    let json: serde_json::Value = crate::rpc_pool::post_json(&client, url, &request)
+        .await
+        .map_err(|e| format!("RPC request failed: {}", e))?;
+
+    if let Some(error) = json.get("error") {
+        return Err(format!("RPC error: {:?}", error));
+    }
+
+    Ok(json["result"].as_i64())
+}
+
 // =================== EXISTING TRANSACTION HISTORY CODE ===================
 
 /// Transaction history related structs
@@ -481,6 +555,63 @@ pub struct TransactionInfo {
     pub error: Option<String>,
 }
 
+/// Display form of a signature entry; `current_time` is used for "time ago"
+pub fn to_transaction_info(tx: TransactionHistoryItem, current_time: i64) -> TransactionInfo {
+    let timestamp = if let Some(block_time) = tx.block_time {
+        format_timestamp(block_time)
+    } else {
+        "Unknown time".to_string()
+    };
+    
+    // Calculate time ago
+    let time_ago = if let Some(block_time) = tx.block_time {
+        let diff = current_time - block_time;
+        if diff < 60 {
+            format!("{} seconds ago", diff)
+        } else if diff < 3600 {
+            format!("{} minutes ago", diff / 60)
+        } else if diff < 86400 {
+            format!("{} hours ago", diff / 3600)
+        } else {
+            format!("{} days ago", diff / 86400)
+        }
+    } else {
+        "Unknown time".to_string()
+    };
+    
+    // Determine status
+    let status = if let Some(_err) = &tx.err {
+        "Failed".to_string()
+    } else {
+        "Success".to_string()
+    };
+    
+    let raw_status = tx.confirmation_status
+        .unwrap_or_else(|| "unknown".to_string());
+    
+    // Extract error message if any
+    let error = if let Some(err) = tx.err {
+        let err_str = format!("{:?}", err);
+        if err_str.len() > 100 {
+            Some(format!("{}...", &err_str[..100]))
+        } else {
+            Some(err_str)
+        }
+    } else {
+        None
+    };
+    
+    TransactionInfo {
+        signature: tx.signature,
+        timestamp,
+        time_ago,
+        status,
+        raw_status,
+        memo: tx.memo,
+        error,
+    }
+}
+
 /// Fetches transactions history for a given address
 pub async fn get_transaction_history(
     address: &str,
@@ -506,22 +637,9 @@ pub async fn get_transaction_history(
         ],
     };
     
-    let response = client
-        .post(url)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
+    let json: serde_json::Value = crate::rpc_pool::post_json(&client, url, &request)
         .await
-        .map_err(|e| format!("Failed to send request: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("RPC error: {}", response.status()));
-    }
-    
-    let json: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| format!("RPC request failed: {}", e))?;
     
     // Check for errors in the response
     if let Some(error) = json.get("error") {
@@ -540,62 +658,7 @@ pub async fn get_transaction_history(
         // Convert to TransactionInfo
         let transactions_info = transactions
             .into_iter()
-            .map(|tx| {
-                let timestamp = if let Some(block_time) = tx.block_time {
-                    let formatted = format_timestamp(block_time);
-                    formatted
-                } else {
-                    "Unknown time".to_string()
-                };
-                
-                // Calculate time ago
-                let time_ago = if let Some(block_time) = tx.block_time {
-                    let diff = current_time - block_time;
-                    if diff < 60 {
-                        format!("{} seconds ago", diff)
-                    } else if diff < 3600 {
-                        format!("{} minutes ago", diff / 60)
-                    } else if diff < 86400 {
-                        format!("{} hours ago", diff / 3600)
-                    } else {
-                        format!("{} days ago", diff / 86400)
-                    }
-                } else {
-                    "Unknown time".to_string()
-                };
-                
-                // Determine status
-                let status = if let Some(_err) = &tx.err {
-                    "Failed".to_string()
-                } else {
-                    "Success".to_string()
-                };
-                
-                let raw_status = tx.confirmation_status
-                    .unwrap_or_else(|| "unknown".to_string());
-                
-                // Extract error message if any
-                let error = if let Some(err) = tx.err {
-                    let err_str = format!("{:?}", err);
-                    if err_str.len() > 100 {
-                        Some(format!("{}...", &err_str[..100]))
-                    } else {
-                        Some(err_str)
-                    }
-                } else {
-                    None
-                };
-                
-                TransactionInfo {
-                    signature: tx.signature,
-                    timestamp,
-                    time_ago,
-                    status,
-                    raw_status,
-                    memo: tx.memo,
-                    error,
-                }
-            })
+            .map(|tx| to_transaction_info(tx, current_time))
             .collect();
         
         Ok(transactions_info)
@@ -604,6 +667,44 @@ pub async fn get_transaction_history(
     }
 }
 
+/// Fetches one page of raw signatures for an address, newest first, starting
+/// before `before` when given (up to 1000 per page)
+pub async fn get_signatures_for_address(
+    address: &str,
+    before: Option<&str>,
+    limit: usize,
+    rpc_url: Option<&str>,
+) -> Result<Vec<TransactionHistoryItem>, String> {
+    let client = Client::new();
+    let url = rpc_url.unwrap_or(DEFAULT_RPC_URL);
+
+    let mut config = serde_json::json!({
+        "limit": limit.min(1000).max(1),
+        "commitment": "finalized"
+    });
+    if let Some(before) = before {
+        config["before"] = serde_json::Value::String(before.to_string());
+    }
+
+    let request = RpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: 1,
+        method: "getSignaturesForAddress".to_string(),
+        params: vec![serde_json::Value::String(address.to_string()), config],
+    };
+
+    let json: serde_json::Value = crate::rpc_pool::post_json(&client, url, &request)
+        .await
+        .map_err(|e| format!("RPC request failed: {}", e))?;
+
+    if let Some(error) = json.get("error") {
+        return Err(format!("RPC error: {:?}", error));
+    }
+
+    let result = json.get("result").ok_or("Failed to get signatures from response")?;
+    serde_json::from_value(result.clone()).map_err(|e| format!("Failed to parse signatures: {}", e))
+}
+
 /// Gets detailed information about a specific transaction
 pub async fn get_transaction_details(
     signature: &str,
@@ -626,22 +727,9 @@ pub async fn get_transaction_details(
         ],
     };
     
-    let response = client
-        .post(url)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("RPC error: {}", response.status()));
-    }
-    
-    let json: serde_json::Value = response
-        .json()
+    let json: serde_json::Value = crate::rpc_pool::post_json(&client, url, &request)
         .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| format!("RPC request failed: {}", e))?;
     
     // Check for errors in the response
     if let Some(error) = json.get("error") {
@@ -716,7 +804,41 @@ pub async fn get_transaction_details(
     }
 }
 
-// NFT with DAS from helius Struts
+/// Request `sol` from the faucet of a test cluster, returning the airdrop signature
+pub async fn request_airdrop(address: &str, sol: f64, rpc_url: Option<&str>) -> Result<String, String> {
+    let network = crate::config::network::active();
+    if !network.supports_airdrop() {
+        return Err(format!("{} has no faucet", network.label()));
+    }
+    let client = Client::new();
+    let url = rpc_url.unwrap_or(DEFAULT_RPC_URL);
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "requestAirdrop",
+        "params": [address, (sol * 1_000_000_000.0) as u64]
+    });
+    let json = crate::rpc_pool::post_json(&client, url, &request)
+        .await
+        .map_err(|e| format!("Failed to request airdrop: {}", e))?;
+    if let Some(error) = json.get("error") {
+        return Err(format!("Airdrop failed: {}", error["message"].as_str().unwrap_or("unknown error")));
+    }
+    json["result"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("Unexpected airdrop response: {}", json))
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Digital Asset Standard (DAS) client
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Assets requested per `getAssetsByOwner` page (the API maximum)
+const DAS_PAGE_LIMIT: usize = 1000;
+/// Stop paginating after this many pages
+const DAS_MAX_PAGES: u32 = 10;
+const NFT_PLACEHOLDER_IMAGE: &str = "https://via.placeholder.com/200x200/6b7280/ffffff?text=NFT";
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CollectibleInfo {
@@ -726,192 +848,270 @@ pub struct CollectibleInfo {
     pub image: String,
     pub description: Option<String>,
     pub verified: bool,
+    /// Compressed NFT (state kept in a merkle tree, no mint account)
+    pub compressed: bool,
 }
 
-#[derive(Debug, Deserialize)]
-struct DasResponse {
-    jsonrpc: String,
-    result: DasResult,
-    id: String,
+/// Token metadata from the DAS indexer
+#[derive(Debug, Clone, PartialEq)]
+pub struct DasTokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub image: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+/// One asset returned by `getAssetsByOwner`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DasAsset {
+    pub id: String,
+    pub interface: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    /// Collection name, or its address when the indexer has no metadata for it
+    pub collection: Option<String>,
+    pub collection_verified: bool,
+    pub compressed: bool,
+    pub decimals: Option<u8>,
+    pub owner: Option<String>,
+    pub burnt: bool,
+}
+
+impl DasAsset {
+    pub fn is_fungible(&self) -> bool {
+        matches!(self.interface.as_str(), "FungibleToken" | "FungibleAsset")
+    }
+
+    fn to_collectible(&self) -> CollectibleInfo {
+        CollectibleInfo {
+            mint: self.id.clone(),
+            name: self.name.clone().unwrap_or_else(|| "Unknown NFT".to_string()),
+            collection: self.collection.clone().unwrap_or_else(|| "Unknown Collection".to_string()),
+            image: self.image.clone().unwrap_or_else(|| NFT_PLACEHOLDER_IMAGE.to_string()),
+            description: self.description.clone(),
+            verified: self.collection_verified,
+            compressed: self.compressed,
+        }
+    }
+
+    fn to_token_metadata(&self) -> DasTokenMetadata {
+        DasTokenMetadata {
+            name: self.name.clone().or_else(|| self.symbol.clone()).unwrap_or_default(),
+            symbol: self.symbol.clone().unwrap_or_default(),
+            image: self.image.clone(),
+            decimals: self.decimals,
+        }
+    }
+}
+
+/// Collectibles and token metadata for a wallet from one indexer call
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WalletAssets {
+    pub collectibles: Vec<CollectibleInfo>,
+    /// Fungible token metadata by mint
+    pub tokens: HashMap<String, DasTokenMetadata>,
 }
 
 #[derive(Debug, Deserialize)]
-struct DasResult {
-    total: u32,
-    limit: u32,
-    page: u32,
-    items: Vec<DasAsset>,
+struct DasPage {
+    items: Vec<DasRawAsset>,
 }
 
 #[derive(Debug, Deserialize)]
-struct DasAsset {
+struct DasRawAsset {
     id: String,
+    interface: Option<String>,
     content: Option<DasContent>,
     grouping: Option<Vec<DasGrouping>>,
     ownership: Option<DasOwnership>,
+    compression: Option<DasCompression>,
+    token_info: Option<DasTokenInfo>,
     burnt: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 struct DasContent {
-    #[serde(rename = "json_uri")]
-    json_uri: Option<String>,
     files: Option<Vec<DasFile>>,
     metadata: Option<DasMetadata>,
+    links: Option<DasLinks>,
 }
 
 #[derive(Debug, Deserialize)]
 struct DasFile {
     uri: Option<String>,
-    #[serde(rename = "cdn_uri")]
     cdn_uri: Option<String>,
-    mime: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct DasMetadata {
     name: Option<String>,
+    symbol: Option<String>,
     description: Option<String>,
     image: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct DasLinks {
+    image: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct DasGrouping {
     group_key: String,
     group_value: String,
+    verified: Option<bool>,
+    collection_metadata: Option<DasCollectionMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DasCollectionMetadata {
+    name: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct DasOwnership {
-    frozen: Option<bool>,
-    delegated: Option<bool>,
     owner: String,
 }
 
-/// Fetches collectibles (NFTs) for a wallet using Helius DAS API
-pub async fn fetch_collectibles(wallet_address: &str, rpc_url: Option<&str>) -> Result<Vec<CollectibleInfo>, String> {
+#[derive(Debug, Deserialize)]
+struct DasCompression {
+    compressed: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct DasTokenInfo {
+    symbol: Option<String>,
+    decimals: Option<u8>,
+}
+
+/// Flatten a raw DAS asset
+fn parse_das_asset(raw: DasRawAsset) -> DasAsset {
+    let content = raw.content.as_ref();
+    let metadata = content.and_then(|c| c.metadata.as_ref());
+    // Prefer the CDN copy of the first file, then the file, the link and
+    // the metadata image
+    let image = content
+        .and_then(|c| c.files.as_ref())
+        .and_then(|files| files.first())
+        .and_then(|f| f.cdn_uri.clone().or_else(|| f.uri.clone()))
+        .or_else(|| content.and_then(|c| c.links.as_ref()).and_then(|l| l.image.clone()))
+        .or_else(|| metadata.and_then(|m| m.image.clone()))
+        .filter(|image| !image.is_empty());
+    let collection = raw.grouping.as_ref().and_then(|groups| groups.iter().find(|g| g.group_key == "collection"));
+
+    DasAsset {
+        interface: raw.interface.clone().unwrap_or_default(),
+        name: metadata.and_then(|m| m.name.clone()).filter(|n| !n.is_empty()),
+        symbol: raw.token_info.as_ref().and_then(|t| t.symbol.clone())
+            .or_else(|| metadata.and_then(|m| m.symbol.clone()))
+            .filter(|s| !s.is_empty()),
+        description: metadata.and_then(|m| m.description.clone()),
+        image,
+        collection: collection.map(|g| {
+            g.collection_metadata.as_ref()
+                .and_then(|m| m.name.clone())
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| g.group_value.clone())
+        }),
+        collection_verified: collection.and_then(|g| g.verified).unwrap_or(true),
+        compressed: raw.compression.as_ref().is_some_and(|c| c.compressed),
+        decimals: raw.token_info.as_ref().and_then(|t| t.decimals),
+        owner: raw.ownership.as_ref().map(|o| o.owner.clone()),
+        burnt: raw.burnt.unwrap_or(false),
+        id: raw.id,
+    }
+}
+
+/// Every asset owned by `owner`, including compressed ones, following
+/// pagination. Fungible tokens are included when `include_fungible` is set.
+/// DAS is only queried on the selected RPC, as fallbacks may not index assets.
+pub async fn get_assets_by_owner(owner: &str, rpc_url: Option<&str>, include_fungible: bool) -> Result<Vec<DasAsset>, String> {
     let client = Client::new();
-    let url = rpc_url.unwrap_or(DEFAULT_RPC_URL);
-    
-    println!("🎨 Fetching collectibles for wallet: {}", wallet_address);
-    
-    let request_body = json!({
-        "jsonrpc": "2.0",
-        "id": "1",
-        "method": "getAssetsByOwner",
-        "params": {
-            "ownerAddress": wallet_address,
-            "page": 1,
-            "limit": 50,
-            "sortBy": {
-                "sortBy": "created",
-                "sortDirection": "desc"
-            },
-            "options": {
-                "showUnverifiedCollections": true,
-                "showCollectionMetadata": true,
-                "showGrandTotal": false,
-                "showFungible": false,
-                "showNativeBalance": false,
-                "showInscription": false,
-                "showZeroBalance": false
+    let url = crate::rpc_pool::resolve(rpc_url.unwrap_or(DEFAULT_RPC_URL));
+    if !crate::config::providers::capabilities(&url).das {
+        return Err(format!("{} doesn't serve the DAS API", url));
+    }
+    let headers = crate::rpc_pool::endpoint_headers().remove(&url).unwrap_or_default();
+    let mut assets = Vec::new();
+
+    for page in 1..=DAS_MAX_PAGES {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": "1",
+            "method": "getAssetsByOwner",
+            "params": {
+                "ownerAddress": owner,
+                "page": page,
+                "limit": DAS_PAGE_LIMIT,
+                "sortBy": { "sortBy": "created", "sortDirection": "desc" },
+                "options": {
+                    "showUnverifiedCollections": true,
+                    "showCollectionMetadata": true,
+                    "showFungible": include_fungible,
+                    "showZeroBalance": false
+                }
             }
+        });
+        let mut builder = client.post(&url);
+        for (name, value) in &headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send DAS request: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("DAS API error: {}", response.status()));
+        }
+        let json: Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse DAS response: {}", e))?;
+        if let Some(error) = json.get("error") {
+            return Err(format!("DAS API error: {:?}", error));
+        }
+        let page_assets: DasPage = serde_json::from_value(json["result"].clone())
+            .map_err(|e| format!("Failed to deserialize DAS response: {}", e))?;
+
+        let count = page_assets.items.len();
+        assets.extend(page_assets.items.into_iter().map(parse_das_asset));
+        if count < DAS_PAGE_LIMIT {
+            break;
         }
-    });
-    
-    let response = client
-        .post(url)
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send DAS request: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("DAS API error: {}", response.status()));
     }
-    
-    let json: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse DAS response: {}", e))?;
-    
-    // Check for errors in the response
-    if let Some(error) = json.get("error") {
-        return Err(format!("DAS API error: {:?}", error));
+
+    // Skip burnt assets and any the indexer reports under another owner
+    assets.retain(|asset| !asset.burnt && asset.owner.as_deref().is_none_or(|o| o == owner));
+    println!("🎨 Found {} assets from DAS API", assets.len());
+    Ok(assets)
+}
+
+/// Collectibles and token metadata for a wallet in one DAS query
+pub async fn fetch_wallet_assets(owner: &str, rpc_url: Option<&str>) -> Result<WalletAssets, String> {
+    let assets = get_assets_by_owner(owner, rpc_url, true).await?;
+    let mut wallet_assets = WalletAssets::default();
+    for asset in assets {
+        if asset.is_fungible() {
+            wallet_assets.tokens.insert(asset.id.clone(), asset.to_token_metadata());
+        } else {
+            wallet_assets.collectibles.push(asset.to_collectible());
+        }
     }
-    
-    // Parse the DAS response
-    let das_response: DasResponse = serde_json::from_value(json)
-        .map_err(|e| format!("Failed to deserialize DAS response: {}", e))?;
-    
-    println!("🎨 Found {} assets from DAS API", das_response.result.items.len());
-    
-    // Convert DAS assets to CollectibleInfo with explicit type annotation
-    let collectibles: Vec<CollectibleInfo> = das_response.result.items
-        .into_iter()
-        .filter_map(|asset| {
-            // Skip burnt assets
-            if asset.burnt.unwrap_or(false) {
-                return None;
-            }
-            
-            // Skip if not owned by the wallet
-            if let Some(ownership) = &asset.ownership {
-                if ownership.owner != wallet_address {
-                    return None;
-                }
-            }
-            
-            let content = asset.content.as_ref()?;
-            
-            // Try to get name from metadata first, then fallback to parsing from URI
-            let name = if let Some(metadata) = &content.metadata {
-                metadata.name.clone().unwrap_or_else(|| "Unknown NFT".to_string())
-            } else {
-                "Unknown NFT".to_string()
-            };
-            
-            // Get description
-            let description = content.metadata.as_ref()
-                .and_then(|m| m.description.clone());
-            
-            // Get image - prefer CDN URI, then regular URI, then metadata image
-            let image = if let Some(files) = &content.files {
-                files.first().and_then(|f| 
-                    f.cdn_uri.clone()
-                        .or_else(|| f.uri.clone())
-                ).unwrap_or_else(|| "https://via.placeholder.com/200x200/6b7280/ffffff?text=NFT".to_string())
-            } else if let Some(metadata) = &content.metadata {
-                metadata.image.clone().unwrap_or_else(|| "https://via.placeholder.com/200x200/6b7280/ffffff?text=NFT".to_string())
-            } else {
-                "https://via.placeholder.com/200x200/6b7280/ffffff?text=NFT".to_string()
-            };
-            
-            // Get collection name from grouping
-            let collection = if let Some(grouping) = &asset.grouping {
-                grouping.iter()
-                    .find(|g| g.group_key == "collection")
-                    .map(|g| g.group_value.clone())
-                    .unwrap_or_else(|| "Unknown Collection".to_string())
-            } else {
-                "Unknown Collection".to_string()
-            };
-            
-            // For now, assume all are verified - you could add more logic here
-            let verified = true;
-            
-            Some(CollectibleInfo {
-                mint: asset.id,
-                name,
-                collection,
-                image,
-                description,
-                verified,
-            })
-        })
+    Ok(wallet_assets)
+}
+
+/// Fetches collectibles (NFTs, including compressed ones) for a wallet using the DAS API
+pub async fn fetch_collectibles(wallet_address: &str, rpc_url: Option<&str>) -> Result<Vec<CollectibleInfo>, String> {
+    println!("🎨 Fetching collectibles for wallet: {}", wallet_address);
+    let collectibles: Vec<CollectibleInfo> = get_assets_by_owner(wallet_address, rpc_url, false)
+        .await?
+        .iter()
+        .filter(|asset| !asset.is_fungible())
+        .map(DasAsset::to_collectible)
         .collect();
-    
     println!("✅ Converted to {} collectible items", collectibles.len());
     Ok(collectibles)
 }
@@ -936,4 +1136,323 @@ pub async fn fetch_nft_metadata(json_uri: &str) -> Result<HashMap<String, serde_
         .map_err(|e| format!("Failed to parse metadata JSON: {}", e))?;
     
     Ok(metadata)
-}
\ No newline at end of file
+}
+// ══════════════════════════════════════════════════════════════════════════════
+// Batched account fetching
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Most accounts `getMultipleAccounts` returns per request
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Raw account as returned with `"encoding": "base64"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawAccount {
+    pub lamports: u64,
+    pub owner: String,
+    pub data: Vec<u8>,
+}
+
+/// Parse one entry of a base64 `getMultipleAccounts` / `getAccountInfo` value
+pub fn parse_account_data(value: &Value) -> Option<RawAccount> {
+    if value.is_null() {
+        return None;
+    }
+    Some(RawAccount {
+        lamports: value["lamports"].as_u64().unwrap_or(0),
+        owner: value["owner"].as_str().unwrap_or_default().to_string(),
+        data: value["data"][0].as_str().and_then(|d| base64::decode(d).ok()).unwrap_or_default(),
+    })
+}
+
+/// Fetch many accounts at once, 100 per request. Results are in the order
+/// asked for, `None` for accounts that don't exist.
+pub async fn get_multiple_accounts(addresses: &[String], rpc_url: Option<&str>) -> Result<Vec<Option<RawAccount>>, String> {
+    let client = Client::new();
+    let url = rpc_url.unwrap_or(DEFAULT_RPC_URL);
+    let mut accounts = Vec::with_capacity(addresses.len());
+
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getMultipleAccounts",
+            "params": [chunk, { "encoding": "base64", "commitment": "confirmed" }]
+        });
+        let json = crate::rpc_pool::post_json(&client, url, &request)
+            .await
+            .map_err(|e| format!("RPC request failed: {}", e))?;
+        if let Some(error) = json.get("error") {
+            return Err(format!("RPC error: {:?}", error));
+        }
+        let values = json["result"]["value"]
+            .as_array()
+            .ok_or_else(|| format!("Failed to parse accounts from response: {:?}", json))?;
+        if values.len() != chunk.len() {
+            return Err(format!("Asked for {} accounts, got {}", chunk.len(), values.len()));
+        }
+        accounts.extend(values.iter().map(parse_account_data));
+    }
+
+    Ok(accounts)
+}
+
+/// Fetch stake accounts by address, in the order asked for. `None` for
+/// addresses that don't hold a stake account.
+pub async fn get_stake_accounts(addresses: &[String], rpc_url: Option<&str>) -> Result<Vec<Option<StakeAccountRpcData>>, String> {
+    let client = Client::new();
+    let url = rpc_url.unwrap_or(DEFAULT_RPC_URL);
+    let mut accounts = Vec::with_capacity(addresses.len());
+
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getMultipleAccounts",
+            "params": [chunk, { "encoding": "jsonParsed", "commitment": "confirmed" }]
+        });
+        let json = crate::rpc_pool::post_json(&client, url, &request)
+            .await
+            .map_err(|e| format!("RPC request failed: {}", e))?;
+        if let Some(error) = json.get("error") {
+            return Err(format!("RPC error: {:?}", error));
+        }
+        let values = json["result"]["value"]
+            .as_array()
+            .ok_or_else(|| format!("Failed to parse accounts from response: {:?}", json))?;
+        if values.len() != chunk.len() {
+            return Err(format!("Asked for {} accounts, got {}", chunk.len(), values.len()));
+        }
+        accounts.extend(chunk.iter().zip(values).map(|(pubkey, value)| {
+            serde_json::from_value::<StakeAccountData>(value.clone())
+                .ok()
+                .filter(|account| account.owner == "Stake11111111111111111111111111111111111111")
+                .map(|account| StakeAccountRpcData { account, pubkey: pubkey.clone() })
+        }));
+    }
+
+    Ok(accounts)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Live balance subscriptions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Wait this long before reconnecting a dropped subscription socket
+const RESUBSCRIBE_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+/// Providers drop idle sockets, so ping at least this often
+const SUBSCRIPTION_PING_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// A balance change pushed by a wallet subscription
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountUpdate {
+    /// New SOL balance
+    Sol(f64),
+    /// New balance of one of the wallet's token accounts
+    Token { account: String, mint: String, amount: f64 },
+}
+
+/// `accountSubscribe` for the wallet plus a `programSubscribe` per token
+/// program for accounts it owns (the owner sits at byte 32 of token accounts)
+pub fn wallet_subscription_requests(address: &str) -> Vec<Value> {
+    let mut requests = vec![json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "accountSubscribe",
+        "params": [address, { "encoding": "base64", "commitment": "confirmed" }]
+    })];
+    for (id, program) in [(2, TOKEN_PROGRAM_ID), (3, TOKEN_2022_PROGRAM_ID)] {
+        requests.push(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "programSubscribe",
+            "params": [
+                program,
+                {
+                    "encoding": "jsonParsed",
+                    "commitment": "confirmed",
+                    "filters": [{ "memcmp": { "offset": 32, "bytes": address } }]
+                }
+            ]
+        }));
+    }
+    requests
+}
+
+/// Read a balance change out of a subscription notification
+pub fn parse_account_update(text: &str) -> Option<AccountUpdate> {
+    let json: Value = serde_json::from_str(text).ok()?;
+    let value = &json["params"]["result"]["value"];
+
+    match json["method"].as_str()? {
+        "accountNotification" => Some(AccountUpdate::Sol(value["lamports"].as_u64()? as f64 / 1_000_000_000.0)),
+        "programNotification" => {
+            let info = &value["account"]["data"]["parsed"]["info"];
+            Some(AccountUpdate::Token {
+                account: value["pubkey"].as_str()?.to_string(),
+                mint: info["mint"].as_str()?.to_string(),
+                amount: info["tokenAmount"]["uiAmount"].as_f64().unwrap_or(0.0),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Push balance changes of `address` to `on_update` until the task running
+/// it is cancelled, reconnecting when the socket drops. `on_live` reports
+/// whether the subscription is currently connected.
+pub async fn subscribe_wallet(
+    address: String,
+    rpc_url: Option<String>,
+    mut on_update: impl FnMut(AccountUpdate),
+    mut on_live: impl FnMut(bool),
+) {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    let rpc_url = rpc_url.unwrap_or_else(|| DEFAULT_RPC_URL.to_string());
+    if !crate::config::providers::capabilities(&crate::rpc_pool::resolve(&rpc_url)).websockets {
+        println!("⚠️ {} has no WebSocket API, balances refresh by polling", rpc_url);
+        on_live(false);
+        return;
+    }
+    loop {
+        match crate::rpc_pool::connect_websocket(&rpc_url).await {
+            Ok(mut socket) => {
+                let mut subscribed = true;
+                for request in wallet_subscription_requests(&address) {
+                    if socket.send(WsMessage::Text(request.to_string())).await.is_err() {
+                        subscribed = false;
+                        break;
+                    }
+                }
+                if subscribed {
+                    println!("📡 Live balances on for {}", address);
+                    on_live(true);
+                    let mut ping = tokio::time::interval(SUBSCRIPTION_PING_INTERVAL);
+                    loop {
+                        tokio::select! {
+                            message = socket.next() => match message {
+                                Some(Ok(WsMessage::Text(text))) => {
+                                    if let Some(update) = parse_account_update(&text) {
+                                        on_update(update);
+                                    }
+                                }
+                                Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => break,
+                                Some(Ok(_)) => {}
+                            },
+                            _ = ping.tick() => {
+                                if socket.send(WsMessage::Ping(Vec::new())).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    on_live(false);
+                }
+                println!("⚠️ Balance subscription for {} dropped, reconnecting", address);
+            }
+            Err(e) => println!("⚠️ Balance subscription failed: {}", e),
+        }
+        tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_account_update() {
+        let sol = r#"{"jsonrpc":"2.0","method":"accountNotification","params":{"result":{"context":{"slot":5},"value":{"lamports":1500000000,"owner":"11111111111111111111111111111111"}},"subscription":23}}"#;
+        assert_eq!(parse_account_update(sol), Some(AccountUpdate::Sol(1.5)));
+
+        let token = r#"{"jsonrpc":"2.0","method":"programNotification","params":{"result":{"context":{"slot":5},"value":{"pubkey":"Acc1","account":{"data":{"parsed":{"info":{"mint":"Mint1","tokenAmount":{"uiAmount":12.5}}}}}}},"subscription":24}}"#;
+        assert_eq!(
+            parse_account_update(token),
+            Some(AccountUpdate::Token { account: "Acc1".to_string(), mint: "Mint1".to_string(), amount: 12.5 })
+        );
+
+        assert_eq!(parse_account_update(r#"{"jsonrpc":"2.0","result":23,"id":1}"#), None);
+    }
+
+    #[test]
+    fn test_dedupe_token_accounts() {
+        let account = |pubkey: &str, program_id: &str| TokenAccountInfo {
+            pubkey: pubkey.to_string(),
+            mint: "Mint1".to_string(),
+            owner: "Owner1".to_string(),
+            amount: 1.0,
+            decimals: 6,
+            state: "initialized".to_string(),
+            program_id: program_id.to_string(),
+        };
+        let merged = dedupe_token_accounts([
+            vec![account("A", TOKEN_PROGRAM_ID), account("B", TOKEN_PROGRAM_ID)],
+            vec![account("B", TOKEN_2022_PROGRAM_ID), account("C", TOKEN_2022_PROGRAM_ID)],
+        ]);
+        let pubkeys: Vec<&str> = merged.iter().map(|a| a.pubkey.as_str()).collect();
+        assert_eq!(pubkeys, vec!["A", "B", "C"]);
+        assert_eq!(merged[1].program_id, TOKEN_PROGRAM_ID);
+    }
+
+    #[test]
+    fn test_parse_das_asset() {
+        let raw: DasRawAsset = serde_json::from_value(json!({
+            "interface": "V1_NFT",
+            "id": "Asset1",
+            "content": {
+                "files": [{ "uri": "https://arweave.net/x.png", "cdn_uri": "https://cdn.helius/x.png" }],
+                "metadata": { "name": "Mad Lad #1", "symbol": "MAD" },
+                "links": { "image": "https://arweave.net/x.png" }
+            },
+            "grouping": [{
+                "group_key": "collection",
+                "group_value": "Collection1",
+                "verified": false,
+                "collection_metadata": { "name": "Mad Lads" }
+            }],
+            "compression": { "compressed": true, "tree": "Tree1" },
+            "ownership": { "owner": "Owner1", "frozen": false },
+            "burnt": false
+        }))
+        .unwrap();
+        let asset = parse_das_asset(raw);
+        assert!(!asset.is_fungible());
+        let collectible = asset.to_collectible();
+        assert_eq!(collectible.image, "https://cdn.helius/x.png");
+        assert_eq!(collectible.collection, "Mad Lads");
+        assert!(collectible.compressed);
+        assert!(!collectible.verified);
+
+        let raw: DasRawAsset = serde_json::from_value(json!({
+            "interface": "FungibleToken",
+            "id": "Mint1",
+            "content": { "metadata": { "name": "Bonk" }, "links": { "image": "https://bonk/logo.png" } },
+            "token_info": { "symbol": "BONK", "balance": 5, "decimals": 5 }
+        }))
+        .unwrap();
+        let asset = parse_das_asset(raw);
+        assert!(asset.is_fungible());
+        assert_eq!(asset.to_token_metadata(), DasTokenMetadata {
+            name: "Bonk".to_string(),
+            symbol: "BONK".to_string(),
+            image: Some("https://bonk/logo.png".to_string()),
+            decimals: Some(5),
+        });
+    }
+
+    #[test]
+    fn test_parse_account_data() {
+        let value = json!({
+            "lamports": 2_039_280,
+            "owner": TOKEN_PROGRAM_ID,
+            "data": [base64::encode([1u8, 2, 3]), "base64"],
+            "executable": false
+        });
+        let account = parse_account_data(&value).unwrap();
+        assert_eq!(account.lamports, 2_039_280);
+        assert_eq!(account.owner, TOKEN_PROGRAM_ID);
+        assert_eq!(account.data, vec![1, 2, 3]);
+        assert_eq!(parse_account_data(&Value::Null), None);
+    }
+}
@@ -0,0 +1,586 @@
+// src/rpc_pool.rs
+//! RPC endpoint pool
+//!
+//! Requests go to the selected RPC first. When it times out, can't be
+//! reached, rate limits (429) or fails with a 5xx, the request is retried
+//! against the fallback endpoints from RPC settings. An endpoint that fails
+//! is put on a cooldown that grows with consecutive failures, and is tried
+//! last until it expires, so one flaky node doesn't slow down every request.
+//!
+//! The endpoints are also benchmarked periodically on latency and slot
+//! freshness. With auto-select on, the fastest endpoint that is keeping up
+//! with the chain is tried first instead of the selected one.
+//!
+//! Off mainnet the pool only talks to the active network's endpoint.
+//!
+//! Requests to each endpoint pass through a token bucket so portfolio loads
+//! don't burst past free-tier limits, and 429s are retried on the same
+//! endpoint with exponential backoff and jitter before failing over.
+//!
+//! Endpoints that authenticate with headers rather than URL tokens get the
+//! headers configured for them in RPC settings on every HTTP request and
+//! WebSocket handshake.
+//!
+//! Every request is counted per endpoint (requests, errors, latency and
+//! bytes transferred) for the diagnostics in RPC settings.
+
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Public mainnet RPC, used as a last resort behind the built-in RPC
+pub const PUBLIC_MAINNET_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+/// Built-in RPC used when none is configured
+pub const DEFAULT_RPC_URL: &str = "https://johna-k3cr1v-fast-mainnet.helius-rpc.com";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+const BASE_COOLDOWN: Duration = Duration::from_secs(15);
+const MAX_COOLDOWN: Duration = Duration::from_secs(300);
+/// How often the background benchmark re-runs
+pub const BENCHMARK_INTERVAL: Duration = Duration::from_secs(600);
+/// Endpoints further behind the freshest one than this aren't auto-selected
+const MAX_SLOTS_BEHIND: u64 = 50;
+/// Sustained requests per second sent to one endpoint
+const RATE_LIMIT_PER_SEC: f64 = 10.0;
+/// Requests one endpoint can be sent at once before throttling kicks in
+const RATE_LIMIT_BURST: f64 = 20.0;
+/// Times a rate-limited request is retried on the same endpoint
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+const BASE_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// Latency samples kept per endpoint for percentiles
+const LATENCY_SAMPLES: usize = 200;
+
+/// Health of one endpoint
+#[derive(Debug, Clone, Default)]
+pub struct EndpointHealth {
+    pub consecutive_failures: u32,
+    pub cooldown_until: Option<Instant>,
+    pub last_error: Option<String>,
+    pub last_success: Option<Instant>,
+}
+
+impl EndpointHealth {
+    pub fn cooling_down(&self) -> bool {
+        self.cooldown_until.is_some_and(|until| until > Instant::now())
+    }
+}
+
+/// Latency and freshness of one endpoint
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EndpointBenchmark {
+    pub url: String,
+    pub latency_ms: Option<u64>,
+    pub slot: Option<u64>,
+    /// Slots behind the freshest endpoint benchmarked
+    pub slots_behind: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl EndpointBenchmark {
+    /// Answered and is keeping up with the chain
+    pub fn usable(&self) -> bool {
+        self.latency_ms.is_some() && self.slots_behind.is_some_and(|behind| behind <= MAX_SLOTS_BEHIND)
+    }
+}
+
+/// Request counters for one endpoint since the app started
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndpointMetrics {
+    pub requests: u64,
+    pub errors: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    /// Latest latencies in milliseconds, oldest first
+    latencies_ms: VecDeque<u64>,
+}
+
+impl EndpointMetrics {
+    fn record(&mut self, latency: Duration, sent: u64, received: u64, failed: bool) {
+        self.requests += 1;
+        self.errors += failed as u64;
+        self.bytes_sent += sent;
+        self.bytes_received += received;
+        if self.latencies_ms.len() == LATENCY_SAMPLES {
+            self.latencies_ms.pop_front();
+        }
+        self.latencies_ms.push_back(latency.as_millis() as u64);
+    }
+
+    /// 95th percentile latency of the recent requests
+    pub fn p95_ms(&self) -> Option<u64> {
+        let mut samples: Vec<u64> = self.latencies_ms.iter().copied().collect();
+        samples.sort_unstable();
+        let rank = (samples.len() * 95).div_ceil(100);
+        samples.get(rank.checked_sub(1)?).copied()
+    }
+
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.requests as f64
+        }
+    }
+}
+
+/// Token bucket smoothing requests to one endpoint
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    tokens: f64,
+    capacity: f64,
+    refill_per_sec: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: f64, refill_per_sec: f64, now: Instant) -> Self {
+        Self { tokens: capacity, capacity, refill_per_sec, updated: now }
+    }
+
+    /// Take a token, returning how long to wait before it's available.
+    /// Tokens can go negative so concurrent callers queue up behind each other.
+    pub fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.updated = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.refill_per_sec)
+        }
+    }
+}
+
+static HEALTH: LazyLock<Mutex<HashMap<String, EndpointHealth>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+static BENCHMARK: LazyLock<Mutex<Vec<EndpointBenchmark>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+static HEADERS: LazyLock<RwLock<HashMap<String, Vec<(String, String)>>>> =
+    LazyLock::new(|| RwLock::new(crate::storage::load_rpc_headers()));
+static METRICS: LazyLock<Mutex<HashMap<String, EndpointMetrics>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+static BUCKETS: LazyLock<Mutex<HashMap<String, TokenBucket>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Cooldown after `failures` consecutive failures: 15s doubling up to 5 minutes
+pub fn cooldown_for(failures: u32) -> Duration {
+    let doublings = failures.saturating_sub(1).min(10);
+    (BASE_COOLDOWN * 2u32.pow(doublings)).min(MAX_COOLDOWN)
+}
+
+/// Backoff before retry `attempt` (from 0) of a rate-limited request.
+/// `jitter` in [0, 1) spreads retries between half and all of the delay.
+pub fn retry_delay(attempt: u32, jitter: f64) -> Duration {
+    let delay = BASE_RETRY_DELAY * 2u32.pow(attempt.min(6));
+    delay.mul_f64(0.5 + jitter.clamp(0.0, 1.0) / 2.0)
+}
+
+fn is_rate_limited(error: &reqwest::Error) -> bool {
+    error.status().is_some_and(|status| status.as_u16() == 429)
+}
+
+/// Wait for a slot in the endpoint's token bucket
+async fn throttle(url: &str) {
+    let wait = BUCKETS
+        .lock()
+        .map(|mut buckets| {
+            let now = Instant::now();
+            buckets
+                .entry(url.to_string())
+                .or_insert_with(|| TokenBucket::new(RATE_LIMIT_BURST, RATE_LIMIT_PER_SEC, now))
+                .reserve(now)
+        })
+        .unwrap_or(Duration::ZERO);
+    if !wait.is_zero() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Whether a request error means another endpoint should be tried
+pub fn should_fail_over(error: &reqwest::Error) -> bool {
+    if let Some(status) = error.status() {
+        return status.as_u16() == 429 || status.is_server_error();
+    }
+    error.is_timeout() || error.is_connect() || error.is_request() || error.is_decode()
+}
+
+/// Put endpoints that aren't cooling down first, keeping the given order otherwise
+pub fn order_endpoints(endpoints: Vec<String>, cooling_down: impl Fn(&str) -> bool) -> Vec<String> {
+    let (mut ready, cooling): (Vec<String>, Vec<String>) =
+        endpoints.into_iter().partition(|url| !cooling_down(url));
+    ready.extend(cooling);
+    ready
+}
+
+/// Endpoint to use in place of `primary` on the active network
+pub fn resolve(primary: &str) -> String {
+    crate::config::network::active()
+        .rpc_override()
+        .unwrap_or(primary)
+        .to_string()
+}
+
+/// The selected endpoint followed by the fallbacks
+fn configured_endpoints(primary: &str) -> Vec<String> {
+    // Fallbacks are mainnet endpoints
+    if let Some(url) = crate::config::network::active().rpc_override() {
+        return vec![url.to_string()];
+    }
+    let mut endpoints = vec![primary.to_string()];
+    let mut fallbacks = crate::storage::load_rpc_fallbacks();
+    if primary == DEFAULT_RPC_URL {
+        fallbacks.push(PUBLIC_MAINNET_RPC_URL.to_string());
+    }
+    for url in fallbacks {
+        let url = url.trim().to_string();
+        if !url.is_empty() && !endpoints.contains(&url) {
+            endpoints.push(url);
+        }
+    }
+    endpoints
+}
+
+/// Endpoints to try for a request, the selected one first (or the fastest
+/// one with auto-select on)
+pub fn endpoints(primary: &str) -> Vec<String> {
+    let mut endpoints = configured_endpoints(primary);
+    if crate::storage::load_rpc_auto_select() && crate::config::network::active().is_mainnet() {
+        if let Some(fastest) = fastest_endpoint().filter(|url| endpoints.contains(url)) {
+            endpoints.retain(|url| *url != fastest);
+            endpoints.insert(0, fastest);
+        }
+    }
+
+    let health = HEALTH.lock().map(|h| h.clone()).unwrap_or_default();
+    order_endpoints(endpoints, |url| health.get(url).is_some_and(EndpointHealth::cooling_down))
+}
+
+/// Request counters of every endpoint used so far
+pub fn metrics_snapshot() -> Vec<(String, EndpointMetrics)> {
+    let mut snapshot: Vec<(String, EndpointMetrics)> = METRICS
+        .lock()
+        .map(|m| m.iter().map(|(url, metrics)| (url.clone(), metrics.clone())).collect())
+        .unwrap_or_default();
+    snapshot.sort_by(|a, b| a.0.cmp(&b.0));
+    snapshot
+}
+
+pub fn reset_metrics() {
+    if let Ok(mut metrics) = METRICS.lock() {
+        metrics.clear();
+    }
+}
+
+/// Current health of every endpoint used so far
+pub fn health_snapshot() -> Vec<(String, EndpointHealth)> {
+    let mut snapshot: Vec<(String, EndpointHealth)> = HEALTH
+        .lock()
+        .map(|h| h.iter().map(|(url, health)| (url.clone(), health.clone())).collect())
+        .unwrap_or_default();
+    snapshot.sort_by(|a, b| a.0.cmp(&b.0));
+    snapshot
+}
+
+fn record_success(url: &str) {
+    if let Ok(mut health) = HEALTH.lock() {
+        let entry = health.entry(url.to_string()).or_default();
+        entry.consecutive_failures = 0;
+        entry.cooldown_until = None;
+        entry.last_success = Some(Instant::now());
+    }
+}
+
+fn record_failure(url: &str, error: &reqwest::Error) {
+    if let Ok(mut health) = HEALTH.lock() {
+        let entry = health.entry(url.to_string()).or_default();
+        entry.consecutive_failures += 1;
+        entry.cooldown_until = Some(Instant::now() + cooldown_for(entry.consecutive_failures));
+        entry.last_error = Some(error.to_string());
+    }
+}
+
+/// Headers configured for every endpoint
+pub fn endpoint_headers() -> HashMap<String, Vec<(String, String)>> {
+    HEADERS.read().map(|h| h.clone()).unwrap_or_default()
+}
+
+/// Headers sent to `url`
+fn headers_for(url: &str) -> Vec<(String, String)> {
+    HEADERS.read().ok().and_then(|h| h.get(url).cloned()).unwrap_or_default()
+}
+
+pub fn save_endpoint_headers(headers: HashMap<String, Vec<(String, String)>>) -> Result<(), String> {
+    crate::storage::save_rpc_headers(&headers)?;
+    if let Ok(mut current) = HEADERS.write() {
+        *current = headers;
+    }
+    Ok(())
+}
+
+/// Parse `<url> <Header-Name>: <value>` lines into headers per endpoint
+pub fn parse_header_lines(text: &str) -> Result<HashMap<String, Vec<(String, String)>>, String> {
+    let mut headers: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let parsed = line
+            .split_once(char::is_whitespace)
+            .and_then(|(url, header)| header.split_once(':').map(|(name, value)| (url, name.trim(), value.trim())));
+        let Some((url, name, value)) = parsed else {
+            return Err(format!("Expected \"<url> <Header>: <value>\", got \"{}\"", line));
+        };
+        if HeaderName::from_bytes(name.as_bytes()).is_err() || HeaderValue::from_str(value).is_err() {
+            return Err(format!("Invalid header in \"{}\"", line));
+        }
+        headers.entry(url.to_string()).or_default().push((name.to_string(), value.to_string()));
+    }
+    Ok(headers)
+}
+
+/// Inverse of [`parse_header_lines`], sorted by endpoint
+pub fn format_header_lines(headers: &HashMap<String, Vec<(String, String)>>) -> String {
+    let mut urls: Vec<&String> = headers.keys().collect();
+    urls.sort();
+    urls.into_iter()
+        .flat_map(|url| headers[url].iter().map(move |(name, value)| format!("{} {}: {}", url, name, value)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub type WebSocket = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Open a WebSocket to the endpoint serving `rpc_url` on the active network,
+/// with its configured headers
+pub async fn connect_websocket(rpc_url: &str) -> Result<WebSocket, String> {
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+    let url = resolve(rpc_url);
+    let mut request = crate::transaction::websocket_url(&url)
+        .into_client_request()
+        .map_err(|e| e.to_string())?;
+    for (name, value) in headers_for(&url) {
+        if let (Ok(name), Ok(value)) = (
+            tokio_tungstenite::tungstenite::http::HeaderName::from_bytes(name.as_bytes()),
+            tokio_tungstenite::tungstenite::http::HeaderValue::from_str(&value),
+        ) {
+            request.headers_mut().insert(name, value);
+        }
+    }
+    let (socket, _) = tokio_tungstenite::connect_async(request).await.map_err(|e| e.to_string())?;
+    Ok(socket)
+}
+
+async fn post_to<T: Serialize + ?Sized>(client: &Client, url: &str, body: &T) -> Result<Value, reqwest::Error> {
+    let started = Instant::now();
+    let sent = serde_json::to_vec(body).map(|bytes| bytes.len() as u64).unwrap_or(0);
+    let mut received = 0;
+    let result = send_to(client, url, body, &mut received).await;
+    if let Ok(mut metrics) = METRICS.lock() {
+        metrics
+            .entry(url.to_string())
+            .or_default()
+            .record(started.elapsed(), sent, received, result.is_err());
+    }
+    result
+}
+
+async fn send_to<T: Serialize + ?Sized>(client: &Client, url: &str, body: &T, received: &mut u64) -> Result<Value, reqwest::Error> {
+    let mut request = client.post(url);
+    for (name, value) in headers_for(url) {
+        request = request.header(name, value);
+    }
+    let response = request
+        .json(body)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?;
+    let content_length = response.content_length();
+    let json: Value = response.json().await?;
+    // Chunked responses don't say how big they are, count the payload instead
+    *received = content_length.unwrap_or_else(|| serde_json::to_vec(&json).map(|b| b.len() as u64).unwrap_or(0));
+    Ok(json)
+}
+
+/// Check a single endpoint answers, without failing over
+pub async fn check_endpoint(url: &str) -> Result<u64, String> {
+    let request = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "getSlot", "params": [] });
+    let json = post_to(&Client::new(), url, &request)
+        .await
+        .map_err(|e| e.to_string())?;
+    json["result"]
+        .as_u64()
+        .ok_or_else(|| format!("Unexpected response: {}", json))
+}
+
+/// Work out how far behind each endpoint is and sort usable endpoints by
+/// latency, followed by the rest
+pub fn rank_benchmarks(mut results: Vec<EndpointBenchmark>) -> Vec<EndpointBenchmark> {
+    let freshest = results.iter().filter_map(|r| r.slot).max();
+    for result in &mut results {
+        result.slots_behind = result.slot.zip(freshest).map(|(slot, freshest)| freshest.saturating_sub(slot));
+    }
+    results.sort_by_key(|r| (!r.usable(), r.latency_ms.unwrap_or(u64::MAX)));
+    results
+}
+
+/// Measure every configured endpoint and remember the results
+pub async fn benchmark(primary: &str) -> Vec<EndpointBenchmark> {
+    let runs = configured_endpoints(primary).into_iter().map(|url| async move {
+        let started = Instant::now();
+        match check_endpoint(&url).await {
+            Ok(slot) => EndpointBenchmark {
+                latency_ms: Some(started.elapsed().as_millis() as u64),
+                slot: Some(slot),
+                url,
+                ..Default::default()
+            },
+            Err(e) => EndpointBenchmark { url, error: Some(e), ..Default::default() },
+        }
+    });
+    let results = rank_benchmarks(futures_util::future::join_all(runs).await);
+
+    if let Some(best) = results.first().filter(|r| r.usable()) {
+        println!("🏁 Fastest RPC: {} ({:?} ms)", best.url, best.latency_ms);
+    }
+    if let Ok(mut last) = BENCHMARK.lock() {
+        *last = results.clone();
+    }
+    results
+}
+
+/// Results of the last benchmark, best first
+pub fn last_benchmark() -> Vec<EndpointBenchmark> {
+    BENCHMARK.lock().map(|b| b.clone()).unwrap_or_default()
+}
+
+/// Fastest usable endpoint from the last benchmark
+pub fn fastest_endpoint() -> Option<String> {
+    last_benchmark().into_iter().find(EndpointBenchmark::usable).map(|r| r.url)
+}
+
+/// POST a JSON-RPC request to `primary`, failing over to the other
+/// endpoints in the pool. Returns the error of the last endpoint tried when
+/// all of them fail.
+pub async fn post_json<T: Serialize + ?Sized>(client: &Client, primary: &str, body: &T) -> Result<Value, reqwest::Error> {
+    let urls = endpoints(primary);
+    let mut index = 0;
+    let mut retries = 0;
+    loop {
+        let url = &urls[index];
+        throttle(url).await;
+        match post_to(client, url, body).await {
+            Ok(json) => {
+                record_success(url);
+                return Ok(json);
+            }
+            Err(e) if is_rate_limited(&e) && retries < MAX_RATE_LIMIT_RETRIES => {
+                let delay = retry_delay(retries, rand::random());
+                retries += 1;
+                println!("⏳ RPC {} rate limited, retrying in {} ms", url, delay.as_millis());
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) if should_fail_over(&e) => {
+                record_failure(url, &e);
+                retries = 0;
+                index += 1;
+                if index == urls.len() {
+                    return Err(e);
+                }
+                println!("⚠️ RPC {} failed ({}), trying {}", url, e, urls[index]);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown_grows_and_caps() {
+        assert_eq!(cooldown_for(1), BASE_COOLDOWN);
+        assert_eq!(cooldown_for(2), BASE_COOLDOWN * 2);
+        assert_eq!(cooldown_for(3), BASE_COOLDOWN * 4);
+        assert_eq!(cooldown_for(50), MAX_COOLDOWN);
+    }
+
+    #[test]
+    fn test_token_bucket_smooths_bursts() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, 4.0, start);
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        // Third request waits one refill, the fourth queues behind it
+        assert_eq!(bucket.reserve(start), Duration::from_millis(250));
+        assert_eq!(bucket.reserve(start), Duration::from_millis(500));
+        // Refilled after a quiet second
+        assert_eq!(bucket.reserve(start + Duration::from_secs(1)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_with_jitter() {
+        assert_eq!(retry_delay(0, 0.0), BASE_RETRY_DELAY / 2);
+        assert_eq!(retry_delay(0, 1.0), BASE_RETRY_DELAY);
+        assert_eq!(retry_delay(2, 1.0), BASE_RETRY_DELAY * 4);
+        assert!(retry_delay(3, 0.5) > retry_delay(2, 0.5));
+    }
+
+    #[test]
+    fn test_header_lines_round_trip() {
+        let text = "https://b.rpc x-api-key: secret\nhttps://a.rpc Authorization: Bearer abc def\n";
+        let headers = parse_header_lines(text).unwrap();
+        assert_eq!(headers["https://a.rpc"], vec![("Authorization".to_string(), "Bearer abc def".to_string())]);
+        assert_eq!(
+            format_header_lines(&headers),
+            "https://a.rpc Authorization: Bearer abc def\nhttps://b.rpc x-api-key: secret"
+        );
+        assert!(parse_header_lines("https://a.rpc no-colon").is_err());
+        assert!(parse_header_lines("https://a.rpc bad header: x").is_err());
+    }
+
+    #[test]
+    fn test_metrics_p95() {
+        let mut metrics = EndpointMetrics::default();
+        assert_eq!(metrics.p95_ms(), None);
+        for ms in 1..=100 {
+            metrics.record(Duration::from_millis(ms), 10, 100, ms % 10 == 0);
+        }
+        assert_eq!(metrics.p95_ms(), Some(95));
+        assert_eq!(metrics.errors, 10);
+        assert_eq!(metrics.bytes_received, 10_000);
+        assert!((metrics.error_rate() - 0.1).abs() < f64::EPSILON);
+
+        for _ in 0..LATENCY_SAMPLES {
+            metrics.record(Duration::from_millis(7), 0, 0, false);
+        }
+        assert_eq!(metrics.p95_ms(), Some(7));
+    }
+
+    #[test]
+    fn test_order_endpoints_moves_cooling_last() {
+        let endpoints = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let ordered = order_endpoints(endpoints, |url| url == "a");
+        assert_eq!(ordered, vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_rank_benchmarks() {
+        let result = |url: &str, latency_ms: Option<u64>, slot: Option<u64>| EndpointBenchmark {
+            url: url.to_string(),
+            latency_ms,
+            slot,
+            ..Default::default()
+        };
+        let ranked = rank_benchmarks(vec![
+            result("slow", Some(300), Some(1_000)),
+            result("stale", Some(50), Some(900)),
+            result("down", None, None),
+            result("fast", Some(100), Some(995)),
+        ]);
+        let order: Vec<&str> = ranked.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(order, vec!["fast", "slow", "stale", "down"]);
+        assert_eq!(ranked[2].slots_behind, Some(100));
+        assert!(!ranked[2].usable());
+    }
+}
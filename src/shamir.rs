@@ -0,0 +1,94 @@
+// src/shamir.rs
+//! Split a wallet key into SLIP-39 shares
+//!
+//! Any `threshold` of the `count` shares rebuild the key, and fewer reveal
+//! nothing about it, so shares can be kept in different places without any
+//! one of them being a single point of failure. Shares carry the wallet's
+//! 32-byte private key, not its BIP39 recovery phrase, so a wallet imported
+//! from them is the same account whatever phrase or passphrase made it.
+
+use crate::wallet::Wallet;
+
+/// Most shares SLIP-39 allows in a group
+pub const MAX_SHARES: u8 = 16;
+/// Words in a share of a 256-bit secret
+const SHARE_WORDS: usize = 33;
+
+/// Split a base58 wallet key into `count` shares, one mnemonic per entry
+pub fn split_wallet_key(private_key: &str, threshold: u8, count: u8) -> Result<Vec<String>, String> {
+    if threshold < 2 || threshold > count || count > MAX_SHARES {
+        return Err(format!("Choose between 2 and {} shares and a threshold no larger than that", MAX_SHARES));
+    }
+    generate_shares(private_key, threshold, count)
+}
+
+/// A base58 wallet key as one 1-of-1 share: 33 words for a paper backup,
+/// imported like any other set of shares
+pub fn recovery_words(private_key: &str) -> Result<String, String> {
+    Ok(generate_shares(private_key, 1, 1)?.remove(0))
+}
+
+fn generate_shares(private_key: &str, threshold: u8, count: u8) -> Result<Vec<String>, String> {
+    let bytes = bs58::decode(private_key.trim())
+        .into_vec()
+        .map_err(|e| format!("Decode error: {}", e))?;
+    let wallet = Wallet::from_private_key(&bytes, String::new())?;
+    let groups = sssmc39::generate_mnemonics(1, &[(threshold, count)], &wallet.signing_key.to_bytes(), "", 0)
+        .map_err(|e| format!("Failed to split key: {}", e))?;
+    let shares = groups[0]
+        .mnemonic_list()
+        .map_err(|e| format!("Failed to split key: {}", e))?
+        .into_iter()
+        .map(|words| words.join(" "))
+        .collect();
+    Ok(shares)
+}
+
+/// Rebuild a wallet from shares written one per line
+pub fn wallet_from_shares(text: &str, name: String) -> Result<Wallet, String> {
+    let shares: Vec<Vec<String>> = share_lines(text)
+        .map(|line| line.split_whitespace().map(|w| w.to_lowercase()).collect())
+        .collect();
+    let secret = sssmc39::combine_mnemonics(&shares, "").map_err(|e| format!("Couldn't combine shares: {}", e))?;
+    Wallet::from_private_key(&secret, name)
+}
+
+/// Whether an import looks like SLIP-39 shares rather than a key or phrase
+pub fn looks_like_shares(input: &str) -> bool {
+    let mut lines = share_lines(input).peekable();
+    lines.peek().is_some()
+        && lines.all(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            words.len() == SHARE_WORDS && words.iter().all(|w| w.chars().all(|c| c.is_alphabetic()))
+        })
+}
+
+fn share_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().map(str::trim).filter(|line| !line.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_combine() {
+        let wallet = Wallet::new("Main".to_string());
+        let shares = split_wallet_key(&wallet.get_private_key(), 2, 3).unwrap();
+        assert_eq!(shares.len(), 3);
+        assert!(looks_like_shares(&shares.join("\n")));
+        assert!(!looks_like_shares(&wallet.get_private_key()));
+
+        let text = format!("{}\n\n  {}  \n", shares[2], shares[0].to_uppercase());
+        let rebuilt = wallet_from_shares(&text, "Rebuilt".to_string()).unwrap();
+        assert_eq!(rebuilt.get_public_key(), wallet.get_public_key());
+        assert!(wallet_from_shares(&shares[1], "Short".to_string()).is_err());
+
+        assert!(split_wallet_key(&wallet.get_private_key(), 1, 3).is_err());
+        assert!(split_wallet_key(&wallet.get_private_key(), 4, 3).is_err());
+
+        let words = recovery_words(&wallet.get_private_key()).unwrap();
+        assert!(looks_like_shares(&words));
+        assert_eq!(wallet_from_shares(&words, "Paper".to_string()).unwrap().get_public_key(), wallet.get_public_key());
+    }
+}
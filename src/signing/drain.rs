@@ -0,0 +1,231 @@
+// src/signing/drain.rs
+//! Wallet-drain heuristics
+//!
+//! The firewall refuses patterns that are dangerous on their own. These
+//! flag transactions that show up in most wallet drains but also have
+//! legitimate uses: sending away (nearly) the whole SOL balance, handing a
+//! delegate or authority over a token or stake account to someone else,
+//! closing several token accounts at once and calling a program deployed in
+//! the last couple of days. Screens that preview a transaction ask for an
+//! explicit extra confirmation before signing when any of them is found.
+
+use super::firewall::program_label;
+use solana_sdk::{
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    stake::{self, instruction::StakeInstruction},
+};
+use spl_token::instruction::TokenInstruction;
+use std::str::FromStr;
+
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const UPGRADEABLE_LOADER_ID: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+/// Less than this left in the wallet counts as emptying it
+pub const EMPTY_BALANCE_LAMPORTS: u64 = 1_000_000; // 0.001 SOL
+/// Closing this many token accounts in one transaction is flagged
+pub const MANY_CLOSED_ACCOUNTS: usize = 3;
+/// Programs deployed (or upgraded) within this many slots count as fresh, ~2 days
+pub const FRESH_PROGRAM_SLOTS: u64 = 432_000;
+
+/// A drain pattern found in a transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrainRisk {
+    EmptiesBalance { remaining_lamports: u64 },
+    NewDelegate { account: Option<Pubkey>, delegate: Option<Pubkey> },
+    NewAuthority { account: Option<Pubkey>, new_authority: Option<Pubkey> },
+    ClosesManyAccounts { count: usize },
+    FreshProgram { program: Pubkey, age_slots: u64 },
+}
+
+fn display(key: &Option<Pubkey>) -> String {
+    key.map(|k| k.to_string()).unwrap_or_else(|| "an unknown account".to_string())
+}
+
+impl DrainRisk {
+    pub fn describe(&self) -> String {
+        match self {
+            DrainRisk::EmptiesBalance { remaining_lamports } => format!(
+                "Leaves only {:.6} SOL in your wallet",
+                *remaining_lamports as f64 / 1_000_000_000.0
+            ),
+            DrainRisk::NewDelegate { account, delegate } => format!(
+                "Lets {} spend tokens from {}",
+                display(delegate),
+                display(account)
+            ),
+            DrainRisk::NewAuthority { account, new_authority: Some(new_authority) } => format!(
+                "Gives {} authority over {}",
+                new_authority,
+                display(account)
+            ),
+            DrainRisk::NewAuthority { account, new_authority: None } => {
+                format!("Removes your authority over {}", display(account))
+            }
+            DrainRisk::ClosesManyAccounts { count } => format!("Closes {} of your token accounts at once", count),
+            DrainRisk::FreshProgram { program, age_slots } => format!(
+                "Calls program {} deployed about {} hours ago",
+                program,
+                // ~400ms per slot
+                age_slots * 2 / 5 / 3_600
+            ),
+        }
+    }
+}
+
+/// Delegate, authority and close-account patterns signed by `owner`
+pub fn instruction_risks(message: &VersionedMessage, owner: &Pubkey) -> Vec<DrainRisk> {
+    let keys = message.static_account_keys();
+    let token_2022 = Pubkey::from_str(TOKEN_2022_PROGRAM_ID).ok();
+    let mut risks = Vec::new();
+    let mut closed = 0;
+
+    for ix in message.instructions() {
+        let Some(program_id) = keys.get(ix.program_id_index as usize) else { continue };
+        let account = |position: usize| -> Option<Pubkey> {
+            ix.accounts.get(position).and_then(|&i| keys.get(i as usize)).copied()
+        };
+
+        if *program_id == spl_token::id() || Some(*program_id) == token_2022 {
+            let Ok(instruction) = TokenInstruction::unpack(&ix.data) else { continue };
+            match instruction {
+                TokenInstruction::Approve { .. } if account(2) == Some(*owner) => {
+                    risks.push(DrainRisk::NewDelegate { account: account(0), delegate: account(1) });
+                }
+                TokenInstruction::ApproveChecked { .. } if account(3) == Some(*owner) => {
+                    risks.push(DrainRisk::NewDelegate { account: account(0), delegate: account(2) });
+                }
+                TokenInstruction::SetAuthority { new_authority, .. } if account(1) == Some(*owner) => {
+                    let new_authority = Option::<Pubkey>::from(new_authority);
+                    if new_authority != Some(*owner) {
+                        risks.push(DrainRisk::NewAuthority { account: account(0), new_authority });
+                    }
+                }
+                TokenInstruction::CloseAccount if account(2) == Some(*owner) => closed += 1,
+                _ => {}
+            }
+        } else if *program_id == stake::program::id() {
+            let Ok(instruction) = bincode::deserialize::<StakeInstruction>(&ix.data) else { continue };
+            let new_authority = match instruction {
+                StakeInstruction::Authorize(new_authority, _) => Some(new_authority),
+                StakeInstruction::AuthorizeChecked(_) => account(3),
+                _ => continue,
+            };
+            if account(2) == Some(*owner) && new_authority != Some(*owner) {
+                risks.push(DrainRisk::NewAuthority { account: account(0), new_authority });
+            }
+        }
+    }
+
+    if closed >= MANY_CLOSED_ACCOUNTS {
+        risks.push(DrainRisk::ClosesManyAccounts { count: closed });
+    }
+    risks
+}
+
+/// Whether a transaction moving `sol_change` lamports (fee excluded) out of
+/// a wallet holding `balance` leaves it (nearly) empty
+pub fn balance_risk(balance: u64, sol_change: i64, fee: u64) -> Option<DrainRisk> {
+    if sol_change >= 0 {
+        return None;
+    }
+    let remaining = (balance as i128 + sol_change as i128 - fee as i128).max(0) as u64;
+    (remaining < EMPTY_BALANCE_LAMPORTS).then_some(DrainRisk::EmptiesBalance { remaining_lamports: remaining })
+}
+
+/// Programs called by the message that the app doesn't know
+pub fn unknown_programs(message: &VersionedMessage) -> Vec<Pubkey> {
+    let keys = message.static_account_keys();
+    let mut programs: Vec<Pubkey> = message
+        .instructions()
+        .iter()
+        .filter_map(|ix| keys.get(ix.program_id_index as usize).copied())
+        .filter(|program| program_label(program).is_none() && *program != spl_associated_token_account::id())
+        .collect();
+    programs.sort();
+    programs.dedup();
+    programs
+}
+
+/// Address of the program data account of an upgradeable program account
+pub fn program_data_address(program_account: &[u8]) -> Option<Pubkey> {
+    // UpgradeableLoaderState::Program { programdata_address }
+    if program_account.get(..4)? != 2u32.to_le_bytes() {
+        return None;
+    }
+    Pubkey::try_from(program_account.get(4..36)?).ok()
+}
+
+/// Slot a program data account was last deployed at
+pub fn deployed_slot(program_data: &[u8]) -> Option<u64> {
+    // UpgradeableLoaderState::ProgramData { slot, .. }
+    if program_data.get(..4)? != 3u32.to_le_bytes() {
+        return None;
+    }
+    Some(u64::from_le_bytes(program_data.get(4..12)?.try_into().ok()?))
+}
+
+pub fn fresh_program_risk(program: Pubkey, deployed_slot: u64, current_slot: u64) -> Option<DrainRisk> {
+    let age_slots = current_slot.saturating_sub(deployed_slot);
+    (age_slots < FRESH_PROGRAM_SLOTS).then_some(DrainRisk::FreshProgram { program, age_slots })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        hash::Hash,
+        instruction::Instruction,
+        message::Message,
+        system_instruction,
+    };
+
+    fn message_for(instructions: &[Instruction], payer: &Pubkey) -> VersionedMessage {
+        VersionedMessage::Legacy(Message::new_with_blockhash(instructions, Some(payer), &Hash::new_unique()))
+    }
+
+    #[test]
+    fn test_balance_risk() {
+        // 1 SOL wallet sending 0.999 SOL plus fee
+        assert!(balance_risk(1_000_000_000, -999_000_000, 5_000).is_some());
+        assert!(balance_risk(1_000_000_000, -500_000_000, 5_000).is_none());
+        assert!(balance_risk(1_000_000_000, 200_000_000, 5_000).is_none());
+    }
+
+    #[test]
+    fn test_delegates_and_closes() {
+        let owner = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let token_account = Pubkey::new_unique();
+        let mut instructions = vec![
+            spl_token::instruction::approve(&spl_token::id(), &token_account, &delegate, &owner, &[], 10).unwrap(),
+            system_instruction::transfer(&owner, &Pubkey::new_unique(), 1_000),
+        ];
+        let risks = instruction_risks(&message_for(&instructions, &owner), &owner);
+        assert_eq!(risks, vec![DrainRisk::NewDelegate { account: Some(token_account), delegate: Some(delegate) }]);
+
+        for _ in 0..MANY_CLOSED_ACCOUNTS {
+            instructions.push(
+                spl_token::instruction::close_account(&spl_token::id(), &Pubkey::new_unique(), &owner, &owner, &[]).unwrap(),
+            );
+        }
+        let risks = instruction_risks(&message_for(&instructions, &owner), &owner);
+        assert_eq!(risks.last(), Some(&DrainRisk::ClosesManyAccounts { count: MANY_CLOSED_ACCOUNTS }));
+    }
+
+    #[test]
+    fn test_program_age() {
+        let program_data = Pubkey::new_unique();
+        let mut program_account = 2u32.to_le_bytes().to_vec();
+        program_account.extend_from_slice(program_data.as_ref());
+        assert_eq!(program_data_address(&program_account), Some(program_data));
+
+        let mut data = 3u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        assert_eq!(deployed_slot(&data), Some(1_000));
+
+        let program = Pubkey::new_unique();
+        assert!(fresh_program_risk(program, 1_000, 2_000).is_some());
+        assert!(fresh_program_risk(program, 1_000, 1_000 + FRESH_PROGRAM_SLOTS).is_none());
+    }
+}
@@ -0,0 +1,179 @@
+// src/signing/fee_payer.rs
+//! Separate fee payer support
+//!
+//! Lets a different account pay network fees (and ATA rent) than the account
+//! moving funds. The fee payer is either another local signer, or a sponsor
+//! service that co-signs transactions for users who hold zero SOL.
+
+use crate::signing::{sign_transaction_slot, place_signature, SignerType, TransactionSigner};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
+use std::error::Error;
+use std::str::FromStr;
+
+/// Request body sent to a fee sponsor service
+#[derive(Debug, Serialize)]
+struct SponsorRequest {
+    /// Base64 wire transaction, already signed by the funds owner
+    transaction: String,
+}
+
+/// Response from a fee sponsor service
+#[derive(Debug, Deserialize)]
+struct SponsorResponse {
+    /// Base58 signature of the sponsor over the transaction message
+    signature: Option<String>,
+    error: Option<String>,
+}
+
+/// HTTP client for a fee sponsor service
+#[derive(Debug, Clone, PartialEq)]
+pub struct SponsorClient {
+    /// Endpoint that accepts a partially signed transaction and returns the payer signature
+    pub url: String,
+    /// Public key the sponsor signs with
+    pub payer_pubkey: Pubkey,
+}
+
+impl SponsorClient {
+    pub fn new(url: &str, payer_pubkey: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            url: url.to_string(),
+            payer_pubkey: Pubkey::from_str(payer_pubkey)?,
+        })
+    }
+
+    /// Ask the sponsor to sign as fee payer and return the raw signature bytes
+    pub async fn request_signature(&self, transaction: &VersionedTransaction) -> Result<Vec<u8>, Box<dyn Error>> {
+        let serialized = bincode::serialize(transaction)?;
+        let request = SponsorRequest {
+            transaction: base64::encode(&serialized),
+        };
+
+        println!("🧾 Requesting fee sponsorship from {}", self.url);
+
+        let response = Client::new()
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(format!("Sponsor service error: {}", response.status()).into());
+        }
+
+        let body: SponsorResponse = response.json().await?;
+        if let Some(error) = body.error {
+            return Err(format!("Sponsor declined transaction: {}", error).into());
+        }
+
+        let signature = body.signature.ok_or("Sponsor response did not include a signature")?;
+        let bytes = bs58::decode(&signature).into_vec()?;
+        Ok(bytes)
+    }
+}
+
+/// Fee sponsor used for sends, chosen in the Jito settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeSponsorSettings {
+    /// Send through the sponsor instead of paying fees from the wallet
+    pub enabled: bool,
+    pub url: String,
+    pub payer_pubkey: String,
+}
+
+impl FeeSponsorSettings {
+    /// The fee payer for a send: the sponsor when enabled, otherwise the owner
+    pub fn fee_payer(&self) -> Result<FeePayer, String> {
+        if !self.enabled {
+            return Ok(FeePayer::Owner);
+        }
+        if self.url.trim().is_empty() {
+            return Err("Fee sponsor URL is not set".to_string());
+        }
+        SponsorClient::new(self.url.trim(), self.payer_pubkey.trim())
+            .map(FeePayer::Sponsor)
+            .map_err(|e| format!("Invalid fee sponsor address: {}", e))
+    }
+}
+
+/// Who pays the transaction fee
+#[derive(Clone)]
+pub enum FeePayer {
+    /// The funds owner pays its own fees (default behaviour)
+    Owner,
+    /// Another local signer pays fees
+    Signer(SignerType),
+    /// A remote sponsor service pays fees
+    Sponsor(SponsorClient),
+}
+
+impl FeePayer {
+    /// Resolve the fee payer public key, falling back to the owner
+    pub async fn pubkey(&self, owner: &Pubkey) -> Result<Pubkey, Box<dyn Error>> {
+        match self {
+            FeePayer::Owner => Ok(*owner),
+            FeePayer::Signer(signer) => Ok(Pubkey::from_str(&signer.get_public_key().await?)?),
+            FeePayer::Sponsor(sponsor) => Ok(sponsor.payer_pubkey),
+        }
+    }
+
+    /// Whether the fee payer is a different account from the owner
+    pub fn is_separate(&self) -> bool {
+        !matches!(self, FeePayer::Owner)
+    }
+
+    /// Collect the owner signature and the fee payer signature, placing each in its slot.
+    ///
+    /// The owner always signs first so that a sponsor only ever sees a transaction
+    /// the user has already approved.
+    pub async fn sign(
+        &self,
+        transaction: &mut VersionedTransaction,
+        owner: &dyn TransactionSigner,
+    ) -> Result<(), Box<dyn Error>> {
+        sign_transaction_slot(transaction, owner).await?;
+
+        match self {
+            FeePayer::Owner => {}
+            FeePayer::Signer(signer) => {
+                sign_transaction_slot(transaction, signer).await?;
+            }
+            FeePayer::Sponsor(sponsor) => {
+                let signature = sponsor.request_signature(transaction).await?;
+                place_signature(transaction, &sponsor.payer_pubkey, &signature)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sponsor_settings_fee_payer() {
+        let mut settings = FeeSponsorSettings::default();
+        assert!(!settings.fee_payer().unwrap().is_separate());
+
+        settings.enabled = true;
+        assert!(settings.fee_payer().is_err());
+
+        settings.url = "https://sponsor.example.com/sign".to_string();
+        settings.payer_pubkey = "not a key".to_string();
+        assert!(settings.fee_payer().is_err());
+
+        let payer = Pubkey::new_unique();
+        settings.payer_pubkey = payer.to_string();
+        match settings.fee_payer().unwrap() {
+            FeePayer::Sponsor(sponsor) => {
+                assert_eq!(sponsor.payer_pubkey, payer);
+                assert_eq!(sponsor.url, settings.url);
+            }
+            _ => panic!("expected the sponsor to pay fees"),
+        }
+    }
+}
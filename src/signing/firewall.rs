@@ -0,0 +1,451 @@
+// src/signing/firewall.rs
+//! Instruction firewall applied to every transaction before it is signed
+//!
+//! Each signer runs the message through `enforce` so dangerous patterns are
+//! caught no matter which screen built the transaction: handing a token
+//! account or stake account to someone else, closing a token account into a
+//! foreign wallet, unlimited delegate approvals and reassigning the wallet
+//! itself to a program. Those are blocked outright, except for handing a
+//! stake account to a stake pool that the same transaction deposits it into; calls into programs the
+//! app doesn't know and bounded approvals only produce warnings, which the
+//! simulation preview and approval prompts show.
+
+use solana_sdk::{
+    compute_budget,
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    stake::{
+        self,
+        instruction::StakeInstruction,
+        state::StakeAuthorize,
+    },
+    system_instruction::SystemInstruction,
+    system_program,
+};
+use spl_token::instruction::{AuthorityType, TokenInstruction};
+use std::error::Error;
+use std::str::FromStr;
+
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// Programs the app builds transactions for, by address and display name
+const KNOWN_PROGRAMS: &[(&str, &str)] = &[
+    ("11111111111111111111111111111111", "System Program"),
+    ("ComputeBudget111111111111111111111111111111", "Compute Budget"),
+    ("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "Token Program"),
+    (TOKEN_2022_PROGRAM_ID, "Token-2022 Program"),
+    ("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL", "Associated Token Account"),
+    ("Stake11111111111111111111111111111111111111", "Stake Program"),
+    ("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr", "Memo Program"),
+    ("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo", "Memo Program (v1)"),
+    ("AddressLookupTab1e1111111111111111111111111", "Address Lookup Table"),
+    ("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4", "Jupiter Aggregator"),
+    ("23MzuyVH6EKGbUHq7GjBY6ydSCVoZQYDmzeKVdDBKWNQ", "Transaction Timeout"),
+    ("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf", "Squads Multisig"),
+    ("strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUxfmKg5m", "Streamflow"),
+    ("5gyqnhRbYmy2KQaLLVS5F8NJ81EwG2KsJdCcV7w11BUZ", "Quantum Vault"),
+    ("2rU1oCHtQ7WJUvy15tKtFvxdYNNSc3id7AzUcjeFSddo", "Unstake Program"),
+    ("ALTNSZ46uaAUU7XUV6awvdorLGqAsPwa9shm7h4uP2FK", "ANS Domains"),
+    ("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy", "SPL Stake Pool"),
+    ("SP12tWFxD9oJsVWNavTTBZvMbA6gkAmxtVgxdqvyvhY", "Sanctum Stake Pool"),
+    ("SPMBzsVUuoHA4Jm6KunbsotaahvVikZs1JyTW6iJvbn", "Sanctum Stake Pool"),
+];
+
+/// Display name of a program the app knows about
+pub fn program_label(program_id: &Pubkey) -> Option<&'static str> {
+    let id = program_id.to_string();
+    KNOWN_PROGRAMS.iter().find(|(known, _)| *known == id).map(|(_, name)| *name)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Shown to the user, signing continues
+    Warn,
+    /// Signing is refused
+    Block,
+}
+
+/// Something the firewall noticed about one instruction
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    /// Index of the instruction within the message
+    pub instruction: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Finding {
+    fn warn(instruction: usize, message: String) -> Self {
+        Self { instruction, severity: Severity::Warn, message }
+    }
+
+    fn block(instruction: usize, message: String) -> Self {
+        Self { instruction, severity: Severity::Block, message }
+    }
+
+    /// One line for display, e.g. "⛔ Instruction 2: ..."
+    pub fn describe(&self) -> String {
+        let icon = match self.severity {
+            Severity::Warn => "⚠️",
+            Severity::Block => "⛔",
+        };
+        format!("{} Instruction {}: {}", icon, self.instruction + 1, self.message)
+    }
+}
+
+fn short(key: &Pubkey) -> String {
+    let s = key.to_string();
+    format!("{}...{}", &s[..4], &s[s.len() - 4..])
+}
+
+fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == spl_token::id() || program_id.to_string() == TOKEN_2022_PROGRAM_ID
+}
+
+/// Inspect every instruction of `message` from the point of view of `owner`,
+/// the wallet about to sign it.
+///
+/// Accounts that are only reachable through address lookup tables can't be
+/// resolved here, so rules that depend on them are skipped for those.
+pub fn inspect_message(message: &VersionedMessage, owner: &Pubkey) -> Vec<Finding> {
+    let keys = message.static_account_keys();
+    let mut findings = Vec::new();
+
+    // Stake accounts deposited into a pool, with the authority they go to
+    let pool_deposits: Vec<(Pubkey, Pubkey)> = message
+        .instructions()
+        .iter()
+        .filter_map(|ix| {
+            let program_id = keys.get(ix.program_id_index as usize)?;
+            let account = |position: usize| ix.accounts.get(position).and_then(|&i| keys.get(i as usize)).copied();
+            crate::liquid_staking::approved_stake_deposit(program_id, &ix.data, &account)
+        })
+        .collect();
+
+    for (index, ix) in message.instructions().iter().enumerate() {
+        let Some(program_id) = keys.get(ix.program_id_index as usize) else {
+            findings.push(Finding::warn(index, "Calls a program loaded from a lookup table".to_string()));
+            continue;
+        };
+        let account = |position: usize| -> Option<Pubkey> {
+            ix.accounts.get(position).and_then(|&i| keys.get(i as usize)).copied()
+        };
+
+        if is_token_program(program_id) {
+            inspect_token_instruction(index, &ix.data, &account, owner, &mut findings);
+        } else if *program_id == system_program::id() {
+            inspect_system_instruction(index, &ix.data, &account, owner, &mut findings);
+        } else if *program_id == stake::program::id() {
+            inspect_stake_instruction(index, &ix.data, &account, owner, &pool_deposits, &mut findings);
+        } else if *program_id == compute_budget::id() {
+            // Fee settings only
+        } else if program_label(program_id).is_none() {
+            findings.push(Finding::warn(index, format!("Calls an unrecognized program {}", program_id)));
+        }
+    }
+
+    findings
+}
+
+fn inspect_token_instruction(
+    index: usize,
+    data: &[u8],
+    account: &dyn Fn(usize) -> Option<Pubkey>,
+    owner: &Pubkey,
+    findings: &mut Vec<Finding>,
+) {
+    // Token-2022 extension instructions don't parse here and aren't checked
+    let Ok(instruction) = TokenInstruction::unpack(data) else {
+        return;
+    };
+    match instruction {
+        TokenInstruction::SetAuthority { authority_type, new_authority } => {
+            let (Some(target), Some(current)) = (account(0), account(1)) else { return };
+            if current != *owner || Option::<Pubkey>::from(new_authority) == Some(*owner) {
+                return;
+            }
+            let change = match Option::<Pubkey>::from(new_authority) {
+                Some(new) => format!("hands {:?} authority of {} to {}", authority_type, short(&target), new),
+                None => format!("removes your {:?} authority over {}", authority_type, short(&target)),
+            };
+            match authority_type {
+                AuthorityType::AccountOwner | AuthorityType::CloseAccount => {
+                    findings.push(Finding::block(index, format!("Token account takeover: {}", change)));
+                }
+                _ => findings.push(Finding::warn(index, format!("Authority change: {}", change))),
+            }
+        }
+        TokenInstruction::CloseAccount => {
+            let (Some(target), Some(destination), Some(authority)) = (account(0), account(1), account(2)) else { return };
+            if authority == *owner && destination != *owner {
+                findings.push(Finding::block(index, format!(
+                    "Closes your token account {} and sends its balance to {}",
+                    short(&target),
+                    destination
+                )));
+            }
+        }
+        TokenInstruction::Approve { amount } => {
+            if account(2) == Some(*owner) {
+                inspect_approval(index, account(0), account(1), amount, findings);
+            }
+        }
+        TokenInstruction::ApproveChecked { amount, .. } => {
+            if account(3) == Some(*owner) {
+                inspect_approval(index, account(0), account(2), amount, findings);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn inspect_approval(
+    index: usize,
+    source: Option<Pubkey>,
+    delegate: Option<Pubkey>,
+    amount: u64,
+    findings: &mut Vec<Finding>,
+) {
+    let source = source.map(|s| short(&s)).unwrap_or_else(|| "a token account".to_string());
+    let delegate = delegate.map(|d| d.to_string()).unwrap_or_else(|| "an unknown delegate".to_string());
+    if amount == u64::MAX {
+        findings.push(Finding::block(index, format!(
+            "Unlimited approval: lets {} spend everything in {}",
+            delegate, source
+        )));
+    } else {
+        findings.push(Finding::warn(index, format!(
+            "Approves {} to spend up to {} base units from {}",
+            delegate, amount, source
+        )));
+    }
+}
+
+fn inspect_system_instruction(
+    index: usize,
+    data: &[u8],
+    account: &dyn Fn(usize) -> Option<Pubkey>,
+    owner: &Pubkey,
+    findings: &mut Vec<Finding>,
+) {
+    let Ok(instruction) = bincode::deserialize::<SystemInstruction>(data) else {
+        return;
+    };
+    match instruction {
+        SystemInstruction::Assign { owner: program } if account(0) == Some(*owner) => {
+            findings.push(Finding::block(index, format!(
+                "Reassigns your wallet to program {}, which would then control it",
+                program
+            )));
+        }
+        SystemInstruction::AuthorizeNonceAccount(new_authority) if account(1) == Some(*owner) && new_authority != *owner => {
+            findings.push(Finding::warn(index, format!(
+                "Hands nonce account authority to {}",
+                new_authority
+            )));
+        }
+        _ => {}
+    }
+}
+
+fn inspect_stake_instruction(
+    index: usize,
+    data: &[u8],
+    account: &dyn Fn(usize) -> Option<Pubkey>,
+    owner: &Pubkey,
+    pool_deposits: &[(Pubkey, Pubkey)],
+    findings: &mut Vec<Finding>,
+) {
+    let Ok(instruction) = bincode::deserialize::<StakeInstruction>(data) else {
+        return;
+    };
+    let (new_authority, kind) = match instruction {
+        StakeInstruction::Authorize(new_authority, kind) => (Some(new_authority), kind),
+        StakeInstruction::AuthorizeChecked(kind) => (account(3), kind),
+        _ => return,
+    };
+    let (Some(stake_account), Some(current), Some(new_authority)) = (account(0), account(2), new_authority) else {
+        return;
+    };
+    if current != *owner || new_authority == *owner || pool_deposits.contains(&(stake_account, new_authority)) {
+        return;
+    }
+    match kind {
+        StakeAuthorize::Withdrawer => findings.push(Finding::block(index, format!(
+            "Hands withdraw authority of stake account {} to {}",
+            short(&stake_account),
+            new_authority
+        ))),
+        StakeAuthorize::Staker => findings.push(Finding::warn(index, format!(
+            "Hands stake authority of stake account {} to {}",
+            short(&stake_account),
+            new_authority
+        ))),
+    }
+}
+
+/// Firewall findings for serialized message bytes. Off-chain messages and
+/// bytes that aren't a transaction message have nothing to inspect.
+pub fn inspect_message_bytes(message_bytes: &[u8], owner: &Pubkey) -> Vec<Finding> {
+    if super::offchain::decode_offchain_message(message_bytes).is_some() {
+        return Vec::new();
+    }
+    match bincode::deserialize::<VersionedMessage>(message_bytes) {
+        Ok(message) => inspect_message(&message, owner),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Display lines for the findings on a message `owner` is asked to sign
+pub fn finding_lines(message_bytes: &[u8], owner: &str) -> Vec<String> {
+    match Pubkey::from_str(owner) {
+        Ok(owner) => inspect_message_bytes(message_bytes, &owner).iter().map(Finding::describe).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Refuse to sign when any instruction is blocked; warnings are only logged.
+/// Called by every signer before it produces a signature.
+pub fn enforce(message_bytes: &[u8], owner: &str) -> Result<(), Box<dyn Error>> {
+    let owner = Pubkey::from_str(owner)?;
+    let findings = inspect_message_bytes(message_bytes, &owner);
+    for finding in &findings {
+        println!("🧱 Firewall: {}", finding.describe());
+    }
+    let blocked: Vec<String> = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Block)
+        .map(|f| f.describe())
+        .collect();
+    if !blocked.is_empty() {
+        return Err(format!("Transaction blocked by the signing firewall:\n{}", blocked.join("\n")).into());
+    }
+    crate::notifications::check_outgoing_transfer(message_bytes, &owner);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        system_instruction,
+    };
+
+    fn message_for(instructions: &[Instruction], payer: &Pubkey) -> VersionedMessage {
+        VersionedMessage::Legacy(Message::new_with_blockhash(instructions, Some(payer), &Hash::new_unique()))
+    }
+
+    fn severities(findings: &[Finding]) -> Vec<Severity> {
+        findings.iter().map(|f| f.severity).collect()
+    }
+
+    #[test]
+    fn test_plain_transfer_passes() {
+        let owner = Pubkey::new_unique();
+        let message = message_for(&[system_instruction::transfer(&owner, &Pubkey::new_unique(), 1_000)], &owner);
+        assert!(inspect_message(&message, &owner).is_empty());
+        assert!(enforce(&message.serialize(), &owner.to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_token_account_takeover_is_blocked() {
+        let owner = Pubkey::new_unique();
+        let token_account = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+        let ix = spl_token::instruction::set_authority(
+            &spl_token::id(),
+            &token_account,
+            Some(&attacker),
+            AuthorityType::AccountOwner,
+            &owner,
+            &[],
+        ).unwrap();
+        let message = message_for(&[ix], &owner);
+        assert_eq!(severities(&inspect_message(&message, &owner)), vec![Severity::Block]);
+        assert!(enforce(&message.serialize(), &owner.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_close_account_destination() {
+        let owner = Pubkey::new_unique();
+        let token_account = Pubkey::new_unique();
+        let own_close = spl_token::instruction::close_account(&spl_token::id(), &token_account, &owner, &owner, &[]).unwrap();
+        assert!(inspect_message(&message_for(&[own_close], &owner), &owner).is_empty());
+
+        let foreign_close = spl_token::instruction::close_account(
+            &spl_token::id(), &token_account, &Pubkey::new_unique(), &owner, &[],
+        ).unwrap();
+        let findings = inspect_message(&message_for(&[foreign_close], &owner), &owner);
+        assert_eq!(severities(&findings), vec![Severity::Block]);
+    }
+
+    #[test]
+    fn test_approvals() {
+        let owner = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let unlimited = spl_token::instruction::approve(&spl_token::id(), &source, &delegate, &owner, &[], u64::MAX).unwrap();
+        let bounded = spl_token::instruction::approve(&spl_token::id(), &source, &delegate, &owner, &[], 500).unwrap();
+        let findings = inspect_message(&message_for(&[unlimited, bounded], &owner), &owner);
+        assert_eq!(severities(&findings), vec![Severity::Block, Severity::Warn]);
+        assert_eq!(findings[1].instruction, 1);
+    }
+
+    #[test]
+    fn test_assign_and_unknown_program() {
+        let owner = Pubkey::new_unique();
+        let assign = system_instruction::assign(&owner, &Pubkey::new_unique());
+        let unknown = Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], vec![AccountMeta::new(owner, true)]);
+        let findings = inspect_message(&message_for(&[assign, unknown], &owner), &owner);
+        assert_eq!(severities(&findings), vec![Severity::Block, Severity::Warn]);
+    }
+
+    #[test]
+    fn test_stake_withdrawer_change_is_blocked() {
+        let owner = Pubkey::new_unique();
+        let stake_account = Pubkey::new_unique();
+        let ix = stake::instruction::authorize(
+            &stake_account,
+            &owner,
+            &Pubkey::new_unique(),
+            StakeAuthorize::Withdrawer,
+            None,
+        );
+        let findings = inspect_message(&message_for(&[ix], &owner), &owner);
+        assert_eq!(severities(&findings), vec![Severity::Block]);
+    }
+
+    #[test]
+    fn test_stake_pool_deposit_passes() {
+        use crate::liquid_staking::{LiquidStakingClient, StakePoolInfo};
+
+        let owner = Pubkey::new_unique();
+        let stake_account = Pubkey::new_unique();
+        let pool = StakePoolInfo::test_pool();
+        let builder = LiquidStakingClient::stake_deposit_builder(&pool, &owner, &stake_account, &Pubkey::new_unique(), 0);
+        let message = message_for(builder.instructions(), &owner);
+        assert!(inspect_message(&message, &owner).is_empty());
+        assert!(enforce(&message.serialize(), &owner.to_string()).is_ok());
+
+        // Without the deposit, or to an authority the pool didn't derive,
+        // handing over the stake account is still blocked
+        let authorizes = &builder.instructions()[1..3];
+        let findings = inspect_message(&message_for(authorizes, &owner), &owner);
+        assert_eq!(severities(&findings), vec![Severity::Warn, Severity::Block]);
+
+        let mut foreign = pool.clone();
+        foreign.state.stake_deposit_authority = Pubkey::new_unique();
+        let builder = LiquidStakingClient::stake_deposit_builder(&foreign, &owner, &stake_account, &Pubkey::new_unique(), 0);
+        assert!(enforce(&message_for(builder.instructions(), &owner).serialize(), &owner.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_offchain_messages_are_not_inspected() {
+        let owner = Pubkey::new_unique();
+        let envelope = super::super::offchain::encode_offchain_message("hello").unwrap();
+        assert!(enforce(&envelope, &owner.to_string()).is_ok());
+    }
+}
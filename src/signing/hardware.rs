@@ -1,6 +1,6 @@
 
 // src/signing/hardware.rs
-use crate::signing::TransactionSigner;
+use crate::signing::{firewall, TransactionSigner};
 use crate::hardware::HardwareWallet;
 use async_trait::async_trait;
 use std::error::Error;
@@ -32,6 +32,8 @@ impl TransactionSigner for HardwareSigner {
     }
     
     async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        firewall::enforce(message, &self.wallet.get_public_key().await?)?;
+
         // For Solana transactions, the message is already the serialized transaction
         // We need to sign it directly and return the signature
         let signature = self.wallet.sign_message(message).await?;
@@ -1,13 +1,34 @@
 // src/signing/mod.rs
-use crate::wallet::Wallet;
+use crate::hardware::HardwareWallet;
+use crate::wallet::{Wallet, WalletInfo};
 use std::error::Error;
+use std::str::FromStr;
+use std::sync::Arc;
 use async_trait::async_trait;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Signature as SolanaSignature,
+    message::VersionedMessage,
+    transaction::VersionedTransaction,
+};
 
 pub mod software;
 pub mod hardware;
+pub mod fee_payer;
+pub mod passkey;
+pub mod mpc;
+pub mod partial;
+pub mod remote;
+pub mod offchain;
+pub mod firewall;
+pub mod drain;
+pub mod session;
 
 use software::SoftwareSigner;
 use hardware::HardwareSigner;
+use passkey::PasskeySigner;
+use mpc::MpcSigner;
+use remote::RemoteSigner;
 
 /// Trait for different transaction signing methods
 #[async_trait]
@@ -30,6 +51,9 @@ pub trait TransactionSigner: Send + Sync {
 pub enum SignerType {
     Software(SoftwareSigner),
     Hardware(HardwareSigner),
+    Passkey(PasskeySigner),
+    Mpc(MpcSigner),
+    Remote(RemoteSigner),
 }
 
 impl SignerType {
@@ -43,6 +67,42 @@ impl SignerType {
         let signer = HardwareSigner::new().await?;
         Ok(SignerType::Hardware(signer))
     }
+
+    /// Create the signer for a stored software wallet, unlocking it with a
+    /// passkey when the key has been moved out of plain storage, co-signing
+    /// with the paired device for a shared wallet, or forwarding to the paired
+    /// device when the key lives there
+    pub fn for_wallet_info(info: &WalletInfo) -> Result<Self, Box<dyn Error>> {
+        if let Some(share) = crate::storage::load_mpc_key_share(&info.address) {
+            return Ok(SignerType::Mpc(MpcSigner::new(share)));
+        }
+        if let Some(remote) = crate::storage::load_remote_wallet(&info.address) {
+            return Ok(SignerType::Remote(RemoteSigner::new(remote)));
+        }
+        if let Some(wrapped) = crate::storage::load_passkey_wrapped_key(&info.address) {
+            let authenticator = passkey::platform_authenticator()
+                .ok_or("This wallet is protected by a passkey, which is not supported on this device")?;
+            return Ok(SignerType::Passkey(PasskeySigner::new(wrapped, authenticator)));
+        }
+        Ok(SignerType::from_wallet(Wallet::from_wallet_info(info)?))
+    }
+}
+
+/// Build the signer for the active wallet: the connected hardware wallet if
+/// there is one, otherwise the stored wallet
+pub fn active_wallet_signer(
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    wallet: Option<WalletInfo>,
+) -> Result<Box<dyn TransactionSigner>, String> {
+    if let Some(hw) = hardware_wallet {
+        Ok(Box::new(HardwareSigner::from_wallet(hw)))
+    } else if let Some(w) = wallet {
+        SignerType::for_wallet_info(&w)
+            .map(|s| Box::new(s) as Box<dyn TransactionSigner>)
+            .map_err(|e| format!("Failed to load wallet: {}", e))
+    } else {
+        Err("No wallet available".to_string())
+    }
 }
 
 #[async_trait]
@@ -51,6 +111,9 @@ impl TransactionSigner for SignerType {
         match self {
             SignerType::Software(s) => s.get_public_key().await,
             SignerType::Hardware(h) => h.get_public_key().await,
+            SignerType::Passkey(p) => p.get_public_key().await,
+            SignerType::Mpc(m) => m.get_public_key().await,
+            SignerType::Remote(r) => r.get_public_key().await,
         }
     }
     
@@ -58,6 +121,9 @@ impl TransactionSigner for SignerType {
         match self {
             SignerType::Software(s) => s.sign_message(message).await,
             SignerType::Hardware(h) => h.sign_message(message).await,
+            SignerType::Passkey(p) => p.sign_message(message).await,
+            SignerType::Mpc(m) => m.sign_message(message).await,
+            SignerType::Remote(r) => r.sign_message(message).await,
         }
     }
     
@@ -65,6 +131,9 @@ impl TransactionSigner for SignerType {
         match self {
             SignerType::Software(s) => s.get_name(),
             SignerType::Hardware(h) => h.get_name(),
+            SignerType::Passkey(p) => p.get_name(),
+            SignerType::Mpc(m) => m.get_name(),
+            SignerType::Remote(r) => r.get_name(),
         }
     }
     
@@ -72,6 +141,175 @@ impl TransactionSigner for SignerType {
         match self {
             SignerType::Software(s) => s.is_available().await,
             SignerType::Hardware(h) => h.is_available().await,
+            SignerType::Passkey(p) => p.is_available().await,
+            SignerType::Mpc(m) => m.is_available().await,
+            SignerType::Remote(r) => r.is_available().await,
+        }
+    }
+}
+
+/// Find the signature slot index for `signer_pubkey` in a transaction.
+///
+/// Required signers are the first `num_required_signatures` static account keys,
+/// and the signature at index `i` belongs to the key at index `i`.
+pub fn find_signer_index(transaction: &VersionedTransaction, signer_pubkey: &Pubkey) -> Option<usize> {
+    let required = transaction.message.header().num_required_signatures as usize;
+    transaction.message
+        .static_account_keys()
+        .iter()
+        .take(required)
+        .position(|key| key == signer_pubkey)
+}
+
+/// Place raw signature bytes into the slot that belongs to `signer_pubkey`
+pub fn place_signature(
+    transaction: &mut VersionedTransaction,
+    signer_pubkey: &Pubkey,
+    signature_bytes: &[u8],
+) -> Result<(), Box<dyn Error>> {
+    if signature_bytes.len() != 64 {
+        return Err(format!("Invalid signature length: expected 64, got {}", signature_bytes.len()).into());
+    }
+
+    let index = find_signer_index(transaction, signer_pubkey)
+        .ok_or_else(|| format!("{} is not a required signer of this transaction", signer_pubkey))?;
+
+    // Make sure there is a slot for every required signer
+    let required = transaction.message.header().num_required_signatures as usize;
+    if transaction.signatures.len() < required {
+        transaction.signatures.resize(required, SolanaSignature::default());
+    }
+
+    let mut sig_array = [0u8; 64];
+    sig_array.copy_from_slice(signature_bytes);
+    transaction.signatures[index] = SolanaSignature::from(sig_array);
+    Ok(())
+}
+
+/// Sign the transaction message with `signer` and place the signature in its own slot
+pub async fn sign_transaction_slot(
+    transaction: &mut VersionedTransaction,
+    signer: &dyn TransactionSigner,
+) -> Result<(), Box<dyn Error>> {
+    let signer_pubkey = Pubkey::from_str(&signer.get_public_key().await?)?;
+    let message_bytes = transaction.message.serialize();
+    let signature_bytes = signer.sign_message(&message_bytes).await?;
+    place_signature(transaction, &signer_pubkey, &signature_bytes)?;
+    println!("✍️ {} signed slot for {}", signer.get_name(), signer_pubkey);
+    Ok(())
+}
+
+/// Sign every still-empty slot that one of `signers` holds the key for.
+///
+/// Slots that already carry a valid signature are left alone, and signers
+/// that aren't required are skipped. Returns the required signers that are
+/// still missing a signature.
+pub async fn sign_available_slots(
+    transaction: &mut VersionedTransaction,
+    signers: &[&dyn TransactionSigner],
+) -> Result<Vec<Pubkey>, Box<dyn Error>> {
+    let message_bytes = transaction.message.serialize();
+    let required = transaction.message.header().num_required_signatures as usize;
+    if transaction.signatures.len() < required {
+        transaction.signatures.resize(required, SolanaSignature::default());
+    }
+    let is_signed = |tx: &VersionedTransaction, index: usize| {
+        tx.signatures[index].verify(tx.message.static_account_keys()[index].as_ref(), &message_bytes)
+    };
+
+    for signer in signers {
+        let signer_pubkey = Pubkey::from_str(&signer.get_public_key().await?)?;
+        let Some(index) = find_signer_index(transaction, &signer_pubkey) else {
+            println!("⏭️ {} is not a signer of this transaction", signer_pubkey);
+            continue;
+        };
+        if is_signed(transaction, index) {
+            continue;
+        }
+        let signature_bytes = signer.sign_message(&message_bytes).await?;
+        place_signature(transaction, &signer_pubkey, &signature_bytes)?;
+        println!("✍️ {} signed slot {} for {}", signer.get_name(), index, signer_pubkey);
+    }
+
+    Ok((0..required)
+        .filter(|&index| !is_signed(transaction, index))
+        .map(|index| transaction.message.static_account_keys()[index])
+        .collect())
+}
+
+/// Collect all required signatures from `signers`, failing if any is missing
+pub async fn sign_all_slots(
+    transaction: &mut VersionedTransaction,
+    signers: &[&dyn TransactionSigner],
+) -> Result<(), Box<dyn Error>> {
+    let missing = sign_available_slots(transaction, signers).await?;
+    if !missing.is_empty() {
+        let missing: Vec<String> = missing.iter().map(|k| k.to_string()).collect();
+        return Err(format!("No available signer for: {}", missing.join(", ")).into());
+    }
+    Ok(())
+}
+
+/// Short human-readable summary of a serialized transaction message (or the
+/// text of an off-chain message), shown when another device asks this one to
+/// sign it
+pub fn describe_message(message_bytes: &[u8]) -> Vec<String> {
+    if let Some(text) = offchain::decode_offchain_message(message_bytes) {
+        return vec!["Off-chain message:".to_string(), text];
+    }
+    let message: VersionedMessage = match bincode::deserialize(message_bytes) {
+        Ok(message) => message,
+        Err(_) => return vec![format!("Raw message ({} bytes)", message_bytes.len())],
+    };
+    let keys = message.static_account_keys();
+    let mut lines = vec![format!("Fee payer: {}", keys.first().map(|k| k.to_string()).unwrap_or_default())];
+    lines.extend(crate::decoder::describe_message(&message));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use software::KeypairSigner;
+    use solana_sdk::{
+        hash::Hash,
+        message::Message,
+        signature::{Keypair, Signer},
+        system_instruction,
+    };
+
+    /// Create-account transaction: the payer and the new account must both sign
+    fn create_account_transaction(payer: &Pubkey, new_account: &Pubkey) -> VersionedTransaction {
+        let ix = system_instruction::create_account(payer, new_account, 1_000_000, 200, &Pubkey::new_unique());
+        let message = Message::new_with_blockhash(&[ix], Some(payer), &Hash::new_unique());
+        VersionedTransaction {
+            signatures: vec![SolanaSignature::default(); 2],
+            message: VersionedMessage::Legacy(message),
         }
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_sign_all_slots_collects_every_signer() {
+        let wallet = SoftwareSigner::new(Wallet::new("payer".to_string()));
+        let payer = Pubkey::from_str(&wallet.get_public_key().await.unwrap()).unwrap();
+        let new_account = Keypair::new();
+        let mut tx = create_account_transaction(&payer, &new_account.pubkey());
+
+        // Order of signers doesn't matter; each fills its own slot
+        let account_signer = KeypairSigner::new(new_account);
+        sign_all_slots(&mut tx, &[&account_signer, &wallet]).await.unwrap();
+        assert!(tx.verify_with_results().iter().all(|ok| *ok));
+    }
+
+    #[tokio::test]
+    async fn test_missing_signer_is_reported() {
+        let wallet = SoftwareSigner::new(Wallet::new("payer".to_string()));
+        let payer = Pubkey::from_str(&wallet.get_public_key().await.unwrap()).unwrap();
+        let new_account = Pubkey::new_unique();
+        let mut tx = create_account_transaction(&payer, &new_account);
+
+        assert_eq!(sign_available_slots(&mut tx, &[&wallet]).await.unwrap(), vec![new_account]);
+        let error = sign_all_slots(&mut tx, &[&wallet]).await.unwrap_err();
+        assert!(error.to_string().contains(&new_account.to_string()));
+    }
+}
@@ -0,0 +1,447 @@
+// src/signing/mpc.rs
+//! 2-of-2 threshold (MPC) signer
+//!
+//! The wallet key never exists in one place. Each device holds an additive
+//! share `x_i` and the wallet address is the ed25519 point `X = x_1·G + x_2·G`.
+//! Signing is a two-party Schnorr exchange over the device bridge that ends in
+//! a standard ed25519 signature, so the chain sees a normal wallet:
+//!
+//! 1. requester → co-signer: message and a commitment to `R_1`
+//! 2. co-signer → requester: `R_2`, once the user approves
+//! 3. requester → co-signer: `R_1`, checked against the commitment
+//! 4. co-signer → requester: `s_2 = r_2 + c·x_2` with `c = H(R || X || M)`
+//!
+//! The requester adds `s_1 = r_1 + c·x_1` and verifies the result before use.
+//! Key shares are created the same way: commit, reveal, reveal.
+
+use crate::bridge::{BridgeConnection, BridgeMessage};
+use crate::signing::{describe_message, firewall, TransactionSigner};
+use async_trait::async_trait;
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::error::Error;
+use std::time::Duration;
+
+/// How long the requester waits for the user to approve on the other device
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(180);
+/// How long to wait for protocol replies that need no user action
+const REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// This device's share of a 2-of-2 wallet, safe to persist on its own
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MpcKeyShare {
+    /// Base58 wallet address (the joint public key)
+    pub group_address: String,
+    pub name: String,
+    /// Name of the device holding the other share
+    pub peer_name: String,
+    /// Base64 secret scalar share
+    pub secret_share: String,
+    /// Base64 public share of this device
+    pub own_public: String,
+    /// Base64 public share of the other device
+    pub peer_public: String,
+}
+
+impl MpcKeyShare {
+    fn secret(&self) -> Result<Scalar, String> {
+        decode_scalar(&self.secret_share)
+    }
+
+    fn group_point(&self) -> Result<EdwardsPoint, String> {
+        let bytes = bs58::decode(&self.group_address)
+            .into_vec()
+            .map_err(|e| format!("Invalid group address: {}", e))?;
+        point_from_bytes(&bytes)
+    }
+
+    fn peer_point(&self) -> Result<EdwardsPoint, String> {
+        decode_point(&self.peer_public)
+    }
+}
+
+// ── Curve helpers ───────────────────────────────────────────────────────────
+
+fn random_scalar() -> Scalar {
+    let mut wide = [0u8; 64];
+    OsRng.fill_bytes(&mut wide);
+    let scalar = Scalar::from_bytes_mod_order_wide(&wide);
+    wide.fill(0);
+    scalar
+}
+
+fn commit(point: &EdwardsPoint) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"unruggable-mpc-commit");
+    hasher.update(point.compress().as_bytes());
+    base64::encode(hasher.finalize())
+}
+
+/// Ed25519 challenge `H(R || A || M)` reduced mod the group order
+fn challenge(r: &EdwardsPoint, a: &EdwardsPoint, message: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(r.compress().as_bytes());
+    hasher.update(a.compress().as_bytes());
+    hasher.update(message);
+    let mut wide = [0u8; 64];
+    wide.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order_wide(&wide)
+}
+
+fn encode_point(point: &EdwardsPoint) -> String {
+    base64::encode(point.compress().as_bytes())
+}
+
+fn decode_point(encoded: &str) -> Result<EdwardsPoint, String> {
+    let bytes = base64::decode(encoded).map_err(|e| format!("Invalid point encoding: {}", e))?;
+    point_from_bytes(&bytes)
+}
+
+/// Decompress a point, rejecting anything outside the prime-order subgroup
+fn point_from_bytes(bytes: &[u8]) -> Result<EdwardsPoint, String> {
+    let bytes: [u8; 32] = bytes.try_into().map_err(|_| "Invalid point length".to_string())?;
+    let point = CompressedEdwardsY(bytes)
+        .decompress()
+        .ok_or("Invalid curve point")?;
+    if point.is_small_order() || !point.is_torsion_free() {
+        return Err("Rejected weak curve point".to_string());
+    }
+    Ok(point)
+}
+
+fn encode_scalar(scalar: &Scalar) -> String {
+    base64::encode(scalar.as_bytes())
+}
+
+fn decode_scalar(encoded: &str) -> Result<Scalar, String> {
+    let bytes = base64::decode(encoded).map_err(|e| format!("Invalid scalar encoding: {}", e))?;
+    let bytes: [u8; 32] = bytes.try_into().map_err(|_| "Invalid scalar length".to_string())?;
+    Option::from(Scalar::from_canonical_bytes(bytes)).ok_or_else(|| "Non-canonical scalar".to_string())
+}
+
+/// Build the joint share record once both public shares are known
+fn finish_keygen(
+    secret: Scalar,
+    own_public: EdwardsPoint,
+    peer_public: EdwardsPoint,
+    name: &str,
+    peer_name: &str,
+) -> Result<MpcKeyShare, String> {
+    let group = own_public + peer_public;
+    if group.is_identity() {
+        return Err("Invalid joint key".to_string());
+    }
+    Ok(MpcKeyShare {
+        group_address: bs58::encode(group.compress().as_bytes()).into_string(),
+        name: name.to_string(),
+        peer_name: peer_name.to_string(),
+        secret_share: encode_scalar(&secret),
+        own_public: encode_point(&own_public),
+        peer_public: encode_point(&peer_public),
+    })
+}
+
+// ── Bridge helpers ──────────────────────────────────────────────────────────
+
+async fn recv_within(conn: &mut BridgeConnection, timeout: Duration) -> Result<BridgeMessage, String> {
+    match tokio::time::timeout(timeout, conn.recv()).await {
+        Ok(result) => result,
+        Err(_) => Err("Timed out waiting for the other device".to_string()),
+    }
+}
+
+fn unexpected(message: BridgeMessage) -> String {
+    match message {
+        BridgeMessage::Reject { reason, .. } => format!("Co-signer rejected: {}", reason),
+        other => format!("Unexpected message from co-signer: {:?}", other),
+    }
+}
+
+// ── Key generation ──────────────────────────────────────────────────────────
+
+/// Start creating a shared wallet with the paired device
+pub async fn create_shared_wallet(conn: &mut BridgeConnection, name: &str) -> Result<MpcKeyShare, String> {
+    let secret = random_scalar();
+    let public = secret * ED25519_BASEPOINT_POINT;
+
+    conn.send(&BridgeMessage::MpcKeygenCommit { commitment: commit(&public) }).await?;
+    let peer_public = match recv_within(conn, APPROVAL_TIMEOUT).await? {
+        BridgeMessage::MpcKeygenReveal { public_share } => decode_point(&public_share)?,
+        other => return Err(unexpected(other)),
+    };
+    conn.send(&BridgeMessage::MpcKeygenReveal { public_share: encode_point(&public) }).await?;
+
+    let share = finish_keygen(secret, public, peer_public, name, conn.peer_name())?;
+    println!("🔑 Created shared wallet {}", share.group_address);
+    Ok(share)
+}
+
+/// Answer a `MpcKeygenCommit` received from the paired device
+pub async fn respond_keygen(
+    conn: &mut BridgeConnection,
+    peer_commitment: &str,
+    name: &str,
+) -> Result<MpcKeyShare, String> {
+    let secret = random_scalar();
+    let public = secret * ED25519_BASEPOINT_POINT;
+
+    conn.send(&BridgeMessage::MpcKeygenReveal { public_share: encode_point(&public) }).await?;
+    let peer_public = match recv_within(conn, REPLY_TIMEOUT).await? {
+        BridgeMessage::MpcKeygenReveal { public_share } => decode_point(&public_share)?,
+        other => return Err(unexpected(other)),
+    };
+    if commit(&peer_public) != peer_commitment {
+        return Err("Peer key share does not match its commitment".to_string());
+    }
+
+    let share = finish_keygen(secret, public, peer_public, name, conn.peer_name())?;
+    println!("🔑 Joined shared wallet {}", share.group_address);
+    Ok(share)
+}
+
+// ── Signing ─────────────────────────────────────────────────────────────────
+
+/// Run the signing exchange as the requester and return the full signature
+pub async fn request_signature(
+    conn: &mut BridgeConnection,
+    share: &MpcKeyShare,
+    message: &[u8],
+) -> Result<[u8; 64], String> {
+    let secret = share.secret()?;
+    let group = share.group_point()?;
+    let peer_public = share.peer_point()?;
+
+    let nonce = random_scalar();
+    let nonce_point = nonce * ED25519_BASEPOINT_POINT;
+    let mut session_bytes = [0u8; 8];
+    OsRng.fill_bytes(&mut session_bytes);
+    let session_id = hex::encode(session_bytes);
+
+    conn.send(&BridgeMessage::MpcSignRequest {
+        session_id: session_id.clone(),
+        group_address: share.group_address.clone(),
+        message: base64::encode(message),
+        nonce_commitment: commit(&nonce_point),
+    }).await?;
+
+    let peer_nonce = match recv_within(conn, APPROVAL_TIMEOUT).await? {
+        BridgeMessage::MpcNonce { session_id: id, nonce_point } if id == session_id => decode_point(&nonce_point)?,
+        other => return Err(unexpected(other)),
+    };
+    conn.send(&BridgeMessage::MpcNonce {
+        session_id: session_id.clone(),
+        nonce_point: encode_point(&nonce_point),
+    }).await?;
+
+    let peer_partial = match recv_within(conn, REPLY_TIMEOUT).await? {
+        BridgeMessage::MpcPartialSignature { session_id: id, partial } if id == session_id => decode_scalar(&partial)?,
+        other => return Err(unexpected(other)),
+    };
+
+    let r = nonce_point + peer_nonce;
+    let c = challenge(&r, &group, message);
+
+    // A bad partial from the peer would otherwise only show up on-chain
+    if peer_partial * ED25519_BASEPOINT_POINT != peer_nonce + c * peer_public {
+        return Err("Co-signer returned an invalid partial signature".to_string());
+    }
+
+    let s = nonce + c * secret + peer_partial;
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(r.compress().as_bytes());
+    signature[32..].copy_from_slice(s.as_bytes());
+
+    verify_group_signature(&share.group_address, message, &signature)?;
+    Ok(signature)
+}
+
+fn verify_group_signature(group_address: &str, message: &[u8], signature: &[u8; 64]) -> Result<(), String> {
+    let key_bytes: [u8; 32] = bs58::decode(group_address)
+        .into_vec()
+        .map_err(|e| format!("Invalid group address: {}", e))?
+        .try_into()
+        .map_err(|_| "Invalid group address length".to_string())?;
+    let key = VerifyingKey::from_bytes(&key_bytes).map_err(|e| format!("Invalid group key: {}", e))?;
+    key.verify(message, &Signature::from_bytes(signature))
+        .map_err(|_| "Joint signature failed verification".to_string())
+}
+
+/// A signing request waiting for the user's approval on the co-signer
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingSignRequest {
+    pub session_id: String,
+    pub group_address: String,
+    pub message: Vec<u8>,
+    nonce_commitment: String,
+}
+
+impl PendingSignRequest {
+    /// Parse a `MpcSignRequest` message, if that is what `message` is
+    pub fn from_message(message: &BridgeMessage) -> Option<Self> {
+        match message {
+            BridgeMessage::MpcSignRequest { session_id, group_address, message, nonce_commitment } => {
+                Some(Self {
+                    session_id: session_id.clone(),
+                    group_address: group_address.clone(),
+                    message: base64::decode(message).ok()?,
+                    nonce_commitment: nonce_commitment.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Short human-readable description of what is being signed
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = describe_message(&self.message);
+        lines.extend(firewall::finding_lines(&self.message, &self.group_address));
+        lines
+    }
+}
+
+/// Co-sign an approved request
+pub async fn approve_sign_request(
+    conn: &mut BridgeConnection,
+    share: &MpcKeyShare,
+    request: &PendingSignRequest,
+) -> Result<(), String> {
+    if request.group_address != share.group_address {
+        reject_sign_request(conn, request, "Unknown shared wallet").await?;
+        return Err("Request is for a different shared wallet".to_string());
+    }
+    if let Err(e) = firewall::enforce(&request.message, &share.group_address) {
+        reject_sign_request(conn, request, "Blocked by the signing firewall").await?;
+        return Err(e.to_string());
+    }
+    let secret = share.secret()?;
+    let group = share.group_point()?;
+
+    let nonce = random_scalar();
+    let nonce_point = nonce * ED25519_BASEPOINT_POINT;
+    conn.send(&BridgeMessage::MpcNonce {
+        session_id: request.session_id.clone(),
+        nonce_point: encode_point(&nonce_point),
+    }).await?;
+
+    let peer_nonce = match recv_within(conn, REPLY_TIMEOUT).await? {
+        BridgeMessage::MpcNonce { session_id, nonce_point } if session_id == request.session_id => {
+            decode_point(&nonce_point)?
+        }
+        other => return Err(unexpected(other)),
+    };
+    if commit(&peer_nonce) != request.nonce_commitment {
+        return Err("Requester nonce does not match its commitment".to_string());
+    }
+
+    let c = challenge(&(peer_nonce + nonce_point), &group, &request.message);
+    let partial = nonce + c * secret;
+    conn.send(&BridgeMessage::MpcPartialSignature {
+        session_id: request.session_id.clone(),
+        partial: encode_scalar(&partial),
+    }).await?;
+
+    println!("✅ Co-signed session {}", request.session_id);
+    Ok(())
+}
+
+/// Decline a request
+pub async fn reject_sign_request(
+    conn: &mut BridgeConnection,
+    request: &PendingSignRequest,
+    reason: &str,
+) -> Result<(), String> {
+    conn.send(&BridgeMessage::Reject {
+        session_id: Some(request.session_id.clone()),
+        reason: reason.to_string(),
+    }).await
+}
+
+/// Signer for a shared wallet; needs the paired device connected over the bridge
+#[derive(Clone)]
+pub struct MpcSigner {
+    share: MpcKeyShare,
+}
+
+impl MpcSigner {
+    pub fn new(share: MpcKeyShare) -> Self {
+        Self { share }
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for MpcSigner {
+    async fn get_public_key(&self) -> Result<String, Box<dyn Error>> {
+        Ok(self.share.group_address.clone())
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        firewall::enforce(message, &self.share.group_address)?;
+        let bridge = crate::bridge::active_bridge()
+            .ok_or(format!("Connect {} to co-sign with this wallet", self.share.peer_name))?;
+        let mut conn = bridge.lock().await;
+        let signature = request_signature(&mut conn, &self.share, message).await?;
+        Ok(signature.to_vec())
+    }
+
+    fn get_name(&self) -> String {
+        format!("Shared Wallet (2-of-2 with {})", self.share.peer_name)
+    }
+
+    async fn is_available(&self) -> bool {
+        crate::bridge::active_bridge().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Both shares of one wallet, as the two devices would end up with
+    fn shares() -> (Scalar, Scalar, MpcKeyShare) {
+        let x1 = random_scalar();
+        let x2 = random_scalar();
+        let share = finish_keygen(
+            x1,
+            x1 * ED25519_BASEPOINT_POINT,
+            x2 * ED25519_BASEPOINT_POINT,
+            "test",
+            "peer",
+        ).unwrap();
+        (x1, x2, share)
+    }
+
+    #[test]
+    fn test_joint_signature_verifies() {
+        let (x1, x2, share) = shares();
+        let message = b"transfer 1 SOL";
+
+        let (r1, r2) = (random_scalar(), random_scalar());
+        let r = (r1 + r2) * ED25519_BASEPOINT_POINT;
+        let c = challenge(&r, &share.group_point().unwrap(), message);
+        let s = (r1 + c * x1) + (r2 + c * x2);
+
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(r.compress().as_bytes());
+        signature[32..].copy_from_slice(s.as_bytes());
+        assert!(verify_group_signature(&share.group_address, message, &signature).is_ok());
+
+        // One share alone cannot produce a valid signature
+        let s_single = r1 + c * x1;
+        signature[32..].copy_from_slice(s_single.as_bytes());
+        assert!(verify_group_signature(&share.group_address, message, &signature).is_err());
+    }
+
+    #[test]
+    fn test_share_encoding_roundtrip() {
+        let (x1, _, share) = shares();
+        assert_eq!(share.secret().unwrap(), x1);
+        assert!(decode_point(&base64::encode([0u8; 32])).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+// src/signing/offchain.rs
+//! Off-chain message signing
+//!
+//! Messages are wrapped in the Solana off-chain message envelope (version 0)
+//! before signing, so the signature can never be mistaken for a transaction
+//! signature and any verifier that follows the standard can check it:
+//!
+//! `"\xffsolana offchain" | version (0) | format | length (u16 LE) | message`
+
+use crate::signing::TransactionSigner;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use solana_sdk::message::VersionedMessage;
+use std::error::Error;
+
+pub const SIGNING_DOMAIN: &[u8; 16] = b"\xffsolana offchain";
+const HEADER_LEN: usize = SIGNING_DOMAIN.len() + 4;
+/// Longest message a Ledger-style device can display
+pub const MAX_LEN_LEDGER: usize = 1232 - HEADER_LEN;
+pub const MAX_LEN: usize = u16::MAX as usize - HEADER_LEN;
+
+/// Message format byte of the envelope
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFormat {
+    RestrictedAscii = 0,
+    LimitedUtf8 = 1,
+    ExtendedUtf8 = 2,
+}
+
+impl MessageFormat {
+    fn for_message(message: &str) -> Result<Self, String> {
+        let len = message.len();
+        if len == 0 {
+            Err("Message is empty".to_string())
+        } else if len <= MAX_LEN_LEDGER && message.bytes().all(|b| (0x20..=0x7e).contains(&b)) {
+            Ok(MessageFormat::RestrictedAscii)
+        } else if len <= MAX_LEN_LEDGER {
+            Ok(MessageFormat::LimitedUtf8)
+        } else if len <= MAX_LEN {
+            Ok(MessageFormat::ExtendedUtf8)
+        } else {
+            Err(format!("Message is too long ({} bytes, max {})", len, MAX_LEN))
+        }
+    }
+}
+
+/// Wrap `message` in the off-chain message envelope
+pub fn encode_offchain_message(message: &str) -> Result<Vec<u8>, String> {
+    let format = MessageFormat::for_message(message)?;
+    let mut bytes = Vec::with_capacity(HEADER_LEN + message.len());
+    bytes.extend_from_slice(SIGNING_DOMAIN);
+    bytes.push(0);
+    bytes.push(format as u8);
+    bytes.extend_from_slice(&(message.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(message.as_bytes());
+    Ok(bytes)
+}
+
+/// The text of an off-chain message envelope, if `bytes` is one
+pub fn decode_offchain_message(bytes: &[u8]) -> Option<String> {
+    let body = bytes.strip_prefix(SIGNING_DOMAIN.as_slice())?;
+    let (header, message) = body.split_first_chunk::<4>()?;
+    let [version, format, len_lo, len_hi] = *header;
+    if version != 0 || usize::from(u16::from_le_bytes([len_lo, len_hi])) != message.len() {
+        return None;
+    }
+    let text = String::from_utf8(message.to_vec()).ok()?;
+    (MessageFormat::for_message(&text).ok()? as u8 == format).then_some(text)
+}
+
+/// A signature over a message, in the form dApps and exchanges expect
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedMessage {
+    pub signer: String,
+    /// Base58 encoded ed25519 signature
+    pub signature: String,
+}
+
+fn verify(address: &str, signed_bytes: &[u8], signature: &[u8]) -> Result<bool, String> {
+    let key_bytes: [u8; 32] = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| format!("Invalid address: {}", e))?
+        .try_into()
+        .map_err(|_| "Invalid address length".to_string())?;
+    let signature: [u8; 64] = signature
+        .try_into()
+        .map_err(|_| format!("Invalid signature length: {}", signature.len()))?;
+    let key = VerifyingKey::from_bytes(&key_bytes).map_err(|e| format!("Invalid public key: {}", e))?;
+    Ok(key.verify(signed_bytes, &Signature::from_bytes(&signature)).is_ok())
+}
+
+async fn sign_bytes(signer: &dyn TransactionSigner, bytes: &[u8]) -> Result<SignedMessage, Box<dyn Error>> {
+    let address = signer.get_public_key().await?;
+    let signature = signer.sign_message(bytes).await?;
+    if !verify(&address, bytes, &signature)? {
+        return Err(format!("{} returned an invalid signature", signer.get_name()).into());
+    }
+    Ok(SignedMessage {
+        signer: address,
+        signature: bs58::encode(signature).into_string(),
+    })
+}
+
+/// Sign text following the off-chain message standard
+pub async fn sign_offchain_message(
+    signer: &dyn TransactionSigner,
+    message: &str,
+) -> Result<SignedMessage, Box<dyn Error>> {
+    let envelope = encode_offchain_message(message)?;
+    println!("✍️ Signing off-chain message ({} bytes) with {}", message.len(), signer.get_name());
+    sign_bytes(signer, &envelope).await
+}
+
+/// Sign raw bytes as-is, for dApps that verify plain `signMessage` output.
+///
+/// Refuses anything that decodes as a transaction message, so this can't be
+/// used to blind-sign a transaction.
+pub async fn sign_raw_message(
+    signer: &dyn TransactionSigner,
+    message: &[u8],
+) -> Result<SignedMessage, Box<dyn Error>> {
+    if message.is_empty() {
+        return Err("Message is empty".into());
+    }
+    if bincode::deserialize::<VersionedMessage>(message).is_ok() {
+        return Err("This payload is a transaction, not a message. Refusing to sign it.".into());
+    }
+    println!("✍️ Signing raw message ({} bytes) with {}", message.len(), signer.get_name());
+    sign_bytes(signer, message).await
+}
+
+/// Check a base58 signature over text signed with [`sign_offchain_message`]
+pub fn verify_offchain_message(address: &str, message: &str, signature: &str) -> Result<bool, String> {
+    let signature = bs58::decode(signature.trim())
+        .into_vec()
+        .map_err(|e| format!("Invalid signature: {}", e))?;
+    verify(address, &encode_offchain_message(message)?, &signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::software::SoftwareSigner;
+    use crate::wallet::Wallet;
+
+    #[test]
+    fn test_envelope_format() {
+        let ascii = encode_offchain_message("Hello").unwrap();
+        assert_eq!(&ascii[..16], SIGNING_DOMAIN);
+        assert_eq!(&ascii[16..20], &[0, MessageFormat::RestrictedAscii as u8, 5, 0]);
+        assert_eq!(&ascii[20..], b"Hello");
+
+        let utf8 = encode_offchain_message("Grüße\n").unwrap();
+        assert_eq!(utf8[17], MessageFormat::LimitedUtf8 as u8);
+
+        let long = "a".repeat(MAX_LEN_LEDGER + 1);
+        assert_eq!(encode_offchain_message(&long).unwrap()[17], MessageFormat::ExtendedUtf8 as u8);
+
+        assert!(encode_offchain_message("").is_err());
+        assert!(encode_offchain_message(&"a".repeat(MAX_LEN + 1)).is_err());
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    #[test]
+    fn test_matches_reference_encoding() {
+        for text in ["Hello", "Grüße\n", &"b".repeat(2000)] {
+            let reference = solana_offchain_message::OffchainMessage::new(0, text.as_bytes())
+                .unwrap()
+                .serialize()
+                .unwrap();
+            assert_eq!(encode_offchain_message(text).unwrap(), reference);
+        }
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        let envelope = encode_offchain_message("Sign in to example.com").unwrap();
+        assert_eq!(decode_offchain_message(&envelope).as_deref(), Some("Sign in to example.com"));
+        assert!(decode_offchain_message(&envelope[..envelope.len() - 1]).is_none());
+        assert!(decode_offchain_message(b"not a message").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_verify() {
+        let wallet = Wallet::new("signer".to_string());
+        let signer = SoftwareSigner::new(wallet);
+
+        let signed = sign_offchain_message(&signer, "I own this wallet").await.unwrap();
+        assert!(verify_offchain_message(&signed.signer, "I own this wallet", &signed.signature).unwrap());
+        assert!(!verify_offchain_message(&signed.signer, "Something else", &signed.signature).unwrap());
+
+        assert!(sign_raw_message(&signer, b"raw payload").await.is_ok());
+    }
+}
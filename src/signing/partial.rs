@@ -0,0 +1,161 @@
+// src/signing/partial.rs
+//! Partial signing for multisig and shared-custody transactions
+//!
+//! A transaction that needs several signers is passed around as base64: each
+//! party adds its own signature, exports the result, and whoever collects the
+//! copies merges their signatures before broadcasting.
+
+use crate::signing::{sign_transaction_slot, TransactionSigner};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Signature as SolanaSignature,
+    transaction::VersionedTransaction,
+};
+use std::error::Error;
+use std::str::FromStr;
+
+/// Signature state of one required signer
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignerStatus {
+    pub pubkey: Pubkey,
+    pub signed: bool,
+}
+
+/// Serialize a (partially) signed transaction to base64 for sharing
+pub fn encode_transaction(transaction: &VersionedTransaction) -> Result<String, Box<dyn Error>> {
+    Ok(base64::encode(bincode::serialize(transaction)?))
+}
+
+/// Parse a shared transaction; accepts base64 or base58
+pub fn decode_transaction(encoded: &str) -> Result<VersionedTransaction, Box<dyn Error>> {
+    let encoded: String = encoded.chars().filter(|c| !c.is_whitespace()).collect();
+    // Base58 text is often valid base64 too, so fall back on a failed parse
+    let parse = |bytes: Vec<u8>| bincode::deserialize::<VersionedTransaction>(&bytes).ok();
+    let mut transaction = base64::decode(&encoded).ok().and_then(parse)
+        .or_else(|| bs58::decode(&encoded).into_vec().ok().and_then(parse))
+        .ok_or("Not a valid base64 or base58 encoded transaction")?;
+
+    // Transactions built by other tools may omit empty slots
+    let required = transaction.message.header().num_required_signatures as usize;
+    if transaction.signatures.len() < required {
+        transaction.signatures.resize(required, SolanaSignature::default());
+    }
+    Ok(transaction)
+}
+
+/// Required signers and whether each has a valid signature
+pub fn signer_statuses(transaction: &VersionedTransaction) -> Vec<SignerStatus> {
+    let message_bytes = transaction.message.serialize();
+    let required = transaction.message.header().num_required_signatures as usize;
+    transaction.message
+        .static_account_keys()
+        .iter()
+        .take(required)
+        .enumerate()
+        .map(|(i, pubkey)| {
+            let signed = transaction.signatures
+                .get(i)
+                .map(|sig| sig.verify(pubkey.as_ref(), &message_bytes))
+                .unwrap_or(false);
+            SignerStatus { pubkey: *pubkey, signed }
+        })
+        .collect()
+}
+
+/// Whether every required signer has signed
+pub fn is_fully_signed(transaction: &VersionedTransaction) -> bool {
+    signer_statuses(transaction).iter().all(|s| s.signed)
+}
+
+/// Add only our signature, leaving the other slots untouched
+pub async fn partially_sign(
+    transaction: &mut VersionedTransaction,
+    signer: &dyn TransactionSigner,
+) -> Result<(), Box<dyn Error>> {
+    let pubkey = Pubkey::from_str(&signer.get_public_key().await?)?;
+    if !signer_statuses(transaction).iter().any(|s| s.pubkey == pubkey) {
+        return Err(format!("{} is not a required signer of this transaction", pubkey).into());
+    }
+    sign_transaction_slot(transaction, signer).await
+}
+
+/// Copy valid signatures from a co-signer's copy of the same transaction.
+///
+/// Returns the number of slots that were newly filled.
+pub fn merge_signatures(
+    target: &mut VersionedTransaction,
+    other: &VersionedTransaction,
+) -> Result<usize, Box<dyn Error>> {
+    let message_bytes = target.message.serialize();
+    if other.message.serialize() != message_bytes {
+        return Err("Signatures are for a different transaction".into());
+    }
+
+    let required = target.message.header().num_required_signatures as usize;
+    if target.signatures.len() < required {
+        target.signatures.resize(required, SolanaSignature::default());
+    }
+    let already_signed: Vec<bool> = signer_statuses(target).iter().map(|s| s.signed).collect();
+
+    let mut merged = 0;
+    for (i, status) in signer_statuses(other).into_iter().enumerate() {
+        if !status.signed || already_signed[i] {
+            continue;
+        }
+        target.signatures[i] = other.signatures[i];
+        merged += 1;
+        println!("🔏 Merged signature from {}", status.pubkey);
+    }
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::software::SoftwareSigner;
+    use crate::wallet::Wallet;
+    use solana_sdk::{hash::Hash, message::{v0, VersionedMessage}, system_instruction};
+
+    /// Transfer paid by one wallet and authorized by another, so both must sign
+    fn two_signer_transaction(payer: &Wallet, owner: &Wallet) -> VersionedTransaction {
+        let payer_key = Pubkey::from_str(&payer.get_public_key()).unwrap();
+        let owner_key = Pubkey::from_str(&owner.get_public_key()).unwrap();
+        let ix = system_instruction::transfer(&owner_key, &Pubkey::new_unique(), 1_000);
+        let message = v0::Message::try_compile(&payer_key, &[ix], &[], Hash::new_unique()).unwrap();
+        VersionedTransaction {
+            signatures: vec![SolanaSignature::default(); 2],
+            message: VersionedMessage::V0(message),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_partial_sign_export_and_merge() {
+        let payer = Wallet::new("payer".to_string());
+        let owner = Wallet::new("owner".to_string());
+        let mut ours = two_signer_transaction(&payer, &owner);
+
+        partially_sign(&mut ours, &SoftwareSigner::new(payer.clone())).await.unwrap();
+        assert!(!is_fully_signed(&ours));
+
+        // Co-signer imports our export and signs their slot
+        let mut theirs = decode_transaction(&encode_transaction(&ours).unwrap()).unwrap();
+        partially_sign(&mut theirs, &SoftwareSigner::new(owner)).await.unwrap();
+
+        assert_eq!(merge_signatures(&mut ours, &theirs).unwrap(), 1);
+        assert!(is_fully_signed(&ours));
+        assert_eq!(merge_signatures(&mut ours, &theirs).unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_foreign_signer_and_transaction() {
+        let payer = Wallet::new("payer".to_string());
+        let owner = Wallet::new("owner".to_string());
+        let mut tx = two_signer_transaction(&payer, &owner);
+
+        let stranger = SoftwareSigner::new(Wallet::new("stranger".to_string()));
+        assert!(partially_sign(&mut tx, &stranger).await.is_err());
+
+        let other = two_signer_transaction(&payer, &owner);
+        assert!(merge_signatures(&mut tx, &other).is_err());
+    }
+}
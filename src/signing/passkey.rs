@@ -0,0 +1,431 @@
+// src/signing/passkey.rs
+//! Passkey-protected software signer
+//!
+//! The ed25519 keypair is encrypted with a 256-bit secret that only the
+//! platform authenticator can produce (WebAuthn PRF / Android Keystore key
+//! gated by biometrics / Secure Enclave). The secret is requested every time
+//! a signature is needed, so the key is only decrypted after a successful
+//! Touch ID / Windows Hello / biometric prompt and is wiped right after use.
+
+use crate::pin::{decrypt_with_key, encrypt_with_key, generate_salt};
+use crate::signing::{firewall, TransactionSigner};
+use crate::wallet::{Wallet, WalletInfo};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::sync::Arc;
+
+/// Platform authenticator able to create a passkey and derive a secret from it
+#[async_trait]
+pub trait PasskeyAuthenticator: Send + Sync {
+    /// Whether a platform authenticator with user verification is present
+    async fn is_available(&self) -> bool;
+
+    /// Create a new platform credential and return its id (base64)
+    async fn create_credential(&self, user_name: &str) -> Result<String, Box<dyn Error>>;
+
+    /// Prompt the user and derive the 32-byte wrapping secret for `salt`
+    async fn derive_secret(&self, credential_id: &str, salt: &[u8]) -> Result<[u8; 32], Box<dyn Error>>;
+
+    /// Display name of the authenticator, e.g. "Android Biometric"
+    fn get_name(&self) -> String;
+}
+
+/// Keypair encrypted under a passkey-derived secret, safe to persist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WrappedKey {
+    pub address: String,
+    pub credential_id: String,
+    /// Base64 salt fed to the authenticator secret derivation
+    pub salt: String,
+    /// Base64 nonce + AES-256-GCM ciphertext of the 64-byte keypair
+    pub ciphertext: String,
+}
+
+/// Encrypt a wallet's keypair under a newly created passkey
+pub async fn wrap_wallet(
+    wallet: &Wallet,
+    authenticator: &dyn PasskeyAuthenticator,
+) -> Result<WrappedKey, Box<dyn Error>> {
+    if !authenticator.is_available().await {
+        return Err("No platform authenticator available".into());
+    }
+
+    let credential_id = authenticator.create_credential(&wallet.get_public_key()).await?;
+    let salt = generate_salt();
+    let mut secret = authenticator.derive_secret(&credential_id, &salt).await?;
+
+    let mut keypair = bs58::decode(wallet.get_private_key()).into_vec()?;
+    let ciphertext = encrypt_with_key(&keypair, &secret);
+    keypair.fill(0);
+    secret.fill(0);
+
+    println!("🔐 Wrapped wallet {} with {}", wallet.get_public_key(), authenticator.get_name());
+
+    Ok(WrappedKey {
+        address: wallet.get_public_key(),
+        credential_id,
+        salt: base64::encode(salt),
+        ciphertext: base64::encode(ciphertext?),
+    })
+}
+
+/// Move a stored software wallet's key behind the platform passkey
+pub async fn protect_wallet(info: &WalletInfo) -> Result<(), Box<dyn Error>> {
+    let authenticator = platform_authenticator()
+        .ok_or("Passkeys are not supported on this device")?;
+    let wallet = Wallet::from_wallet_info(info)?;
+    let wrapped = wrap_wallet(&wallet, authenticator.as_ref()).await?;
+
+    // Check the passkey can unlock the key before the plain copy is removed
+    PasskeySigner::new(wrapped.clone(), authenticator).unlock().await?;
+
+    crate::storage::save_passkey_wrapped_key(&wrapped)?;
+    Ok(())
+}
+
+/// Signer that unlocks the keypair with the platform authenticator on every signature
+#[derive(Clone)]
+pub struct PasskeySigner {
+    wrapped: WrappedKey,
+    authenticator: Arc<dyn PasskeyAuthenticator>,
+}
+
+impl PasskeySigner {
+    pub fn new(wrapped: WrappedKey, authenticator: Arc<dyn PasskeyAuthenticator>) -> Self {
+        Self { wrapped, authenticator }
+    }
+
+    /// Authenticate and decrypt the wallet. Callers must drop it as soon as possible.
+    pub async fn unlock(&self) -> Result<Wallet, Box<dyn Error>> {
+        let salt = base64::decode(&self.wrapped.salt)?;
+        let ciphertext = base64::decode(&self.wrapped.ciphertext)?;
+
+        let mut secret = self.authenticator.derive_secret(&self.wrapped.credential_id, &salt).await?;
+        let decrypted = decrypt_with_key(&ciphertext, &secret);
+        secret.fill(0);
+
+        let mut keypair = decrypted.map_err(|_| "Passkey did not unlock this wallet")?;
+        let wallet = Wallet::from_private_key(&keypair, "Passkey Wallet".to_string());
+        keypair.fill(0);
+        let wallet = wallet?;
+
+        if wallet.get_public_key() != self.wrapped.address {
+            return Err("Decrypted key does not match wallet address".into());
+        }
+        Ok(wallet)
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for PasskeySigner {
+    async fn get_public_key(&self) -> Result<String, Box<dyn Error>> {
+        Ok(self.wrapped.address.clone())
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        firewall::enforce(message, &self.wrapped.address)?;
+        let wallet = self.unlock().await?;
+        Ok(wallet.sign_message_bytes(message))
+    }
+
+    fn get_name(&self) -> String {
+        format!("Passkey Wallet ({})", self.authenticator.get_name())
+    }
+
+    async fn is_available(&self) -> bool {
+        self.authenticator.is_available().await
+    }
+}
+
+/// The authenticator for the current platform, if one is supported
+pub fn platform_authenticator() -> Option<Arc<dyn PasskeyAuthenticator>> {
+    #[cfg(target_os = "android")]
+    {
+        Some(Arc::new(android::AndroidBiometricAuthenticator))
+    }
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    {
+        Some(Arc::new(apple::KeychainBiometricAuthenticator))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Some(Arc::new(windows_hello::WindowsHelloAuthenticator))
+    }
+    #[cfg(not(any(target_os = "android", target_os = "macos", target_os = "ios", target_os = "windows")))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "android")]
+mod android {
+    //! Android Keystore backed authenticator.
+    //!
+    //! Calls into `scripts/android/PasskeyBridge.java`, which
+    //! `scripts/android.bundle.sh` adds to the Android project. The bridge
+    //! keeps an HMAC key in the Android Keystore that needs a strong biometric
+    //! check for each use, shows a BiometricPrompt and returns HMAC(salt) as
+    //! the wrapping secret. Calls block until the prompt completes, so they
+    //! run on the calling thread rather than the UI thread.
+
+    use super::PasskeyAuthenticator;
+    use async_trait::async_trait;
+    use dioxus::mobile::wry::prelude::dispatch;
+    use jni::objects::{GlobalRef, JByteArray, JObject, JString, JValue};
+    use jni::JavaVM;
+    use std::error::Error;
+
+    const BRIDGE_CLASS: &str = "com/unruggable/app/PasskeyBridge";
+
+    pub struct AndroidBiometricAuthenticator;
+
+    /// Grab the JVM and a global ref to the activity from the UI thread
+    fn vm_and_activity() -> Result<(JavaVM, GlobalRef), Box<dyn Error>> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        dispatch(move |env, activity, _webview| {
+            let result = env
+                .get_java_vm()
+                .and_then(|vm| env.new_global_ref(activity).map(|activity| (vm, activity)));
+            tx.send(result).unwrap();
+        });
+        Ok(rx.recv()??)
+    }
+
+    #[async_trait]
+    impl PasskeyAuthenticator for AndroidBiometricAuthenticator {
+        async fn is_available(&self) -> bool {
+            let run = || -> Result<bool, Box<dyn Error>> {
+                let (vm, activity) = vm_and_activity()?;
+                let mut env = vm.attach_current_thread()?;
+                let available = env.call_static_method(
+                    BRIDGE_CLASS,
+                    "isAvailable",
+                    "(Landroid/app/Activity;)Z",
+                    &[JValue::Object(activity.as_obj())],
+                )?.z()?;
+                Ok(available)
+            };
+            run().unwrap_or(false)
+        }
+
+        async fn create_credential(&self, user_name: &str) -> Result<String, Box<dyn Error>> {
+            let (vm, activity) = vm_and_activity()?;
+            let mut env = vm.attach_current_thread()?;
+            let name = env.new_string(user_name)?;
+            let credential: JString = env.call_static_method(
+                BRIDGE_CLASS,
+                "createCredential",
+                "(Landroid/app/Activity;Ljava/lang/String;)Ljava/lang/String;",
+                &[JValue::Object(activity.as_obj()), JValue::Object(&name)],
+            )?.l()?.into();
+            if credential.is_null() {
+                return Err("Passkey creation was cancelled".into());
+            }
+            Ok(env.get_string(&credential)?.into())
+        }
+
+        async fn derive_secret(&self, credential_id: &str, salt: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+            let (vm, activity) = vm_and_activity()?;
+            let mut env = vm.attach_current_thread()?;
+            let credential = env.new_string(credential_id)?;
+            let salt_array = env.byte_array_from_slice(salt)?;
+            let result: JByteArray = env.call_static_method(
+                BRIDGE_CLASS,
+                "deriveSecret",
+                "(Landroid/app/Activity;Ljava/lang/String;[B)[B",
+                &[
+                    JValue::Object(activity.as_obj()),
+                    JValue::Object(&credential),
+                    JValue::Object(&JObject::from(salt_array)),
+                ],
+            )?.l()?.into();
+            if result.is_null() {
+                return Err("Biometric authentication failed or was cancelled".into());
+            }
+            let mut bytes = env.convert_byte_array(&result)?;
+            if bytes.len() != 32 {
+                return Err(format!("Invalid secret length: {}", bytes.len()).into());
+            }
+            let mut secret = [0u8; 32];
+            secret.copy_from_slice(&bytes);
+            bytes.fill(0);
+            Ok(secret)
+        }
+
+        fn get_name(&self) -> String {
+            "Android Biometric".to_string()
+        }
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod apple {
+    //! Keychain backed authenticator.
+    //!
+    //! Each credential is a random 32-byte secret kept in the keychain with
+    //! the `biometryCurrentSet` access control, so reading it shows the
+    //! Face ID / Touch ID prompt and enrolling another finger or face
+    //! invalidates it. The wrapping secret is SHA-256(secret || salt).
+
+    use super::PasskeyAuthenticator;
+    use async_trait::async_trait;
+    use rand::RngCore;
+    use security_framework::access_control::AccessControlOptions;
+    use security_framework::passwords::{generic_password, set_generic_password_options, PasswordOptions};
+    use sha2::{Digest, Sha256};
+    use std::error::Error;
+
+    const KEYCHAIN_SERVICE: &str = "com.unruggable.app.biometric";
+
+    pub struct KeychainBiometricAuthenticator;
+
+    fn options(credential_id: &str) -> PasswordOptions {
+        let mut options = PasswordOptions::new_generic_password(KEYCHAIN_SERVICE, credential_id);
+        options.set_access_control_options(AccessControlOptions::BIOMETRY_CURRENT_SET);
+        #[cfg(target_os = "macos")]
+        options.use_protected_keychain();
+        options
+    }
+
+    #[async_trait]
+    impl PasskeyAuthenticator for KeychainBiometricAuthenticator {
+        /// The keychain reports missing biometrics when the item is written,
+        /// so availability is only known at `create_credential`
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        async fn create_credential(&self, _user_name: &str) -> Result<String, Box<dyn Error>> {
+            let mut id = [0u8; 16];
+            rand::thread_rng().fill_bytes(&mut id);
+            let credential_id = hex::encode(id);
+
+            let mut secret = [0u8; 32];
+            rand::thread_rng().fill_bytes(&mut secret);
+            let result = set_generic_password_options(&secret, options(&credential_id));
+            secret.fill(0);
+            result.map_err(|e| format!("Could not set up Face ID / Touch ID: {}", e))?;
+            Ok(credential_id)
+        }
+
+        async fn derive_secret(&self, credential_id: &str, salt: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+            let mut secret = generic_password(options(credential_id))
+                .map_err(|_| "Biometric authentication failed or was cancelled")?;
+            let derived = Sha256::new().chain_update(&secret).chain_update(salt).finalize();
+            secret.fill(0);
+            Ok(derived.into())
+        }
+
+        fn get_name(&self) -> String {
+            if cfg!(target_os = "ios") { "Face ID / Touch ID" } else { "Touch ID" }.to_string()
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows_hello {
+    //! Windows Hello backed authenticator.
+    //!
+    //! Each credential is a Windows Hello key pair kept by the TPM, named by a
+    //! random id so wallets sharing a name never replace each other's key.
+    //! Signing the salt shows the Windows Hello prompt; RSA PKCS#1 v1.5 signatures
+    //! are deterministic, so SHA-256 of the signature is a stable secret.
+
+    use super::PasskeyAuthenticator;
+    use async_trait::async_trait;
+    use rand::RngCore;
+    use sha2::{Digest, Sha256};
+    use std::error::Error;
+    use windows::core::{Array, HSTRING};
+    use windows::Security::Credentials::{KeyCredentialCreationOption, KeyCredentialManager, KeyCredentialStatus};
+    use windows::Security::Cryptography::CryptographicBuffer;
+
+    pub struct WindowsHelloAuthenticator;
+
+    #[async_trait]
+    impl PasskeyAuthenticator for WindowsHelloAuthenticator {
+        async fn is_available(&self) -> bool {
+            KeyCredentialManager::IsSupportedAsync()
+                .and_then(|operation| operation.get())
+                .unwrap_or(false)
+        }
+
+        async fn create_credential(&self, _user_name: &str) -> Result<String, Box<dyn Error>> {
+            let mut id = [0u8; 16];
+            rand::thread_rng().fill_bytes(&mut id);
+            let credential_id = format!("unruggable:{}", hex::encode(id));
+            let result = KeyCredentialManager::RequestCreateAsync(
+                &HSTRING::from(credential_id.as_str()),
+                KeyCredentialCreationOption::FailIfExists,
+            )?
+            .get()?;
+            if result.Status()? != KeyCredentialStatus::Success {
+                return Err("Windows Hello setup was cancelled".into());
+            }
+            Ok(credential_id)
+        }
+
+        async fn derive_secret(&self, credential_id: &str, salt: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+            let opened = KeyCredentialManager::OpenAsync(&HSTRING::from(credential_id))?.get()?;
+            if opened.Status()? != KeyCredentialStatus::Success {
+                return Err("Windows Hello credential not found".into());
+            }
+            let data = CryptographicBuffer::CreateFromByteArray(salt)?;
+            let signed = opened.Credential()?.RequestSignAsync(&data)?.get()?;
+            if signed.Status()? != KeyCredentialStatus::Success {
+                return Err("Windows Hello verification failed or was cancelled".into());
+            }
+            let mut signature = Array::<u8>::new();
+            CryptographicBuffer::CopyToByteArray(&signed.Result()?, &mut signature)?;
+            Ok(Sha256::digest(&signature[..]).into())
+        }
+
+        fn get_name(&self) -> String {
+            "Windows Hello".to_string()
+        }
+    }
+}
+
+/// Deterministic authenticator standing in for the platform prompt. Its
+/// secrets depend on the enrolled biometric it was built with.
+#[cfg(test)]
+pub(crate) struct FakeAuthenticator(pub &'static str);
+
+#[cfg(test)]
+#[async_trait]
+impl PasskeyAuthenticator for FakeAuthenticator {
+    async fn is_available(&self) -> bool {
+        true
+    }
+
+    async fn create_credential(&self, _user_name: &str) -> Result<String, Box<dyn Error>> {
+        Ok("test-credential".to_string())
+    }
+
+    async fn derive_secret(&self, credential_id: &str, salt: &[u8]) -> Result<[u8; 32], Box<dyn Error>> {
+        Ok(crate::pin::derive_key_from_pin(&format!("{}:{}", self.0, credential_id), salt))
+    }
+
+    fn get_name(&self) -> String {
+        "Test".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wrap_and_sign() {
+        let wallet = Wallet::new("test".to_string());
+        let wrapped = wrap_wallet(&wallet, &FakeAuthenticator("enrolled")).await.unwrap();
+        assert!(!wrapped.ciphertext.contains(&wallet.get_private_key()));
+
+        let signer = PasskeySigner::new(wrapped, Arc::new(FakeAuthenticator("enrolled")));
+        assert_eq!(signer.get_public_key().await.unwrap(), wallet.get_public_key());
+
+        let signature = signer.sign_message(b"hello").await.unwrap();
+        assert_eq!(signature, wallet.sign_message_bytes(b"hello"));
+    }
+}
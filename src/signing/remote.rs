@@ -0,0 +1,254 @@
+// src/signing/remote.rs
+//! Remote signer over the device bridge
+//!
+//! The desktop builds a transaction and forwards its message to the paired
+//! phone, which shows it, signs with its own wallet once the user approves,
+//! and returns only the signature. The key never leaves the phone.
+
+use crate::bridge::{BridgeConnection, BridgeMessage, RemoteWallet};
+use crate::signing::{describe_message, firewall, SignerType, TransactionSigner};
+use crate::signing::offchain::{decode_offchain_message, encode_offchain_message};
+use async_trait::async_trait;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::error::Error;
+use std::time::Duration;
+
+/// How long to wait for the user to approve on the phone
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(180);
+const REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+
+async fn recv_within(conn: &mut BridgeConnection, timeout: Duration) -> Result<BridgeMessage, String> {
+    match tokio::time::timeout(timeout, conn.recv()).await {
+        Ok(result) => result,
+        Err(_) => Err("Timed out waiting for the other device".to_string()),
+    }
+}
+
+/// Ask the paired device which wallets it can sign with
+pub async fn fetch_remote_wallets(conn: &mut BridgeConnection) -> Result<Vec<RemoteWallet>, String> {
+    conn.send(&BridgeMessage::ListWallets).await?;
+    match recv_within(conn, REPLY_TIMEOUT).await? {
+        BridgeMessage::Wallets { wallets } => Ok(wallets),
+        BridgeMessage::Reject { reason, .. } => Err(format!("Paired device refused: {}", reason)),
+        other => Err(format!("Unexpected reply: {:?}", other)),
+    }
+}
+
+/// Wallets on this device that hold their own key and can be offered to the peer
+pub fn local_signing_wallets(device_name: &str) -> Vec<RemoteWallet> {
+    crate::storage::load_wallets_from_storage()
+        .into_iter()
+        .filter(|w| {
+            crate::storage::load_remote_wallet(&w.address).is_none()
+                && crate::storage::load_mpc_key_share(&w.address).is_none()
+        })
+        .map(|w| RemoteWallet {
+            name: w.name,
+            address: w.address,
+            device: device_name.to_string(),
+        })
+        .collect()
+}
+
+fn verify_signature(address: &str, message: &[u8], signature: &[u8]) -> Result<(), String> {
+    let key_bytes: [u8; 32] = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| format!("Invalid address: {}", e))?
+        .try_into()
+        .map_err(|_| "Invalid address length".to_string())?;
+    let signature: [u8; 64] = signature
+        .try_into()
+        .map_err(|_| format!("Invalid signature length: {}", signature.len()))?;
+    VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| format!("Invalid public key: {}", e))?
+        .verify(message, &Signature::from_bytes(&signature))
+        .map_err(|_| "Paired device returned an invalid signature".to_string())
+}
+
+/// Forward `message` to the paired device and wait for its signature
+pub async fn request_remote_signature(
+    conn: &mut BridgeConnection,
+    signer_address: &str,
+    message: &[u8],
+) -> Result<Vec<u8>, String> {
+    let mut id_bytes = [0u8; 8];
+    OsRng.fill_bytes(&mut id_bytes);
+    let request_id = hex::encode(id_bytes);
+
+    // Off-chain messages travel as text so the peer rebuilds the envelope itself
+    let request = match decode_offchain_message(message) {
+        Some(text) => BridgeMessage::SignMessageRequest {
+            request_id: request_id.clone(),
+            signer_address: signer_address.to_string(),
+            message: text,
+        },
+        None => BridgeMessage::SignRequest {
+            request_id: request_id.clone(),
+            signer_address: signer_address.to_string(),
+            message: base64::encode(message),
+        },
+    };
+    conn.send(&request).await?;
+    println!("📲 Sent signing request {} to {}", request_id, conn.peer_name());
+
+    let signature = match recv_within(conn, APPROVAL_TIMEOUT).await? {
+        BridgeMessage::SignResponse { request_id: id, signature } if id == request_id => {
+            base64::decode(&signature).map_err(|e| format!("Invalid signature encoding: {}", e))?
+        }
+        BridgeMessage::Reject { reason, .. } => return Err(format!("Rejected on {}: {}", conn.peer_name(), reason)),
+        other => return Err(format!("Unexpected reply: {:?}", other)),
+    };
+
+    // Never trust the peer blindly; a wrong signature would only fail on-chain
+    verify_signature(signer_address, message, &signature)?;
+    Ok(signature)
+}
+
+/// A signing request from the paired device waiting for approval here
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteSignRequest {
+    pub request_id: String,
+    pub signer_address: String,
+    /// Bytes to sign: a transaction message or an off-chain message envelope
+    pub message: Vec<u8>,
+}
+
+impl RemoteSignRequest {
+    /// Parse a `SignRequest` or `SignMessageRequest`, if that is what `message` is
+    pub fn from_message(message: &BridgeMessage) -> Option<Self> {
+        match message {
+            BridgeMessage::SignRequest { request_id, signer_address, message } => Some(Self {
+                request_id: request_id.clone(),
+                signer_address: signer_address.clone(),
+                message: base64::decode(message).ok()?,
+            }),
+            BridgeMessage::SignMessageRequest { request_id, signer_address, message } => Some(Self {
+                request_id: request_id.clone(),
+                signer_address: signer_address.clone(),
+                message: encode_offchain_message(message).ok()?,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = describe_message(&self.message);
+        lines.extend(firewall::finding_lines(&self.message, &self.signer_address));
+        lines
+    }
+}
+
+/// Sign an approved request with the local wallet and send the signature back
+pub async fn approve_remote_request(conn: &mut BridgeConnection, request: &RemoteSignRequest) -> Result<(), String> {
+    let wallet = crate::storage::load_wallets_from_storage()
+        .into_iter()
+        .find(|w| w.address == request.signer_address);
+
+    // Only wallets with a key on this device; never forward to another device
+    let signer = match wallet {
+        Some(w) if crate::storage::load_remote_wallet(&w.address).is_none()
+            && crate::storage::load_mpc_key_share(&w.address).is_none() => {
+            SignerType::for_wallet_info(&w).map_err(|e| e.to_string())
+        }
+        _ => Err("Wallet is not available on this device".to_string()),
+    };
+    let signer = match signer {
+        Ok(signer) => signer,
+        Err(e) => {
+            reject_remote_request(conn, request, &e).await?;
+            return Err(e);
+        }
+    };
+
+    let signed = signer.sign_message(&request.message).await
+        .map_err(|e| format!("Signing failed: {}", e));
+    let signature = match signed {
+        Ok(signature) => signature,
+        Err(reason) => {
+            reject_remote_request(conn, request, &reason).await?;
+            return Err(reason);
+        }
+    };
+
+    conn.send(&BridgeMessage::SignResponse {
+        request_id: request.request_id.clone(),
+        signature: base64::encode(signature),
+    }).await?;
+    println!("✅ Signed request {} for {}", request.request_id, conn.peer_name());
+    Ok(())
+}
+
+/// Decline a request
+pub async fn reject_remote_request(
+    conn: &mut BridgeConnection,
+    request: &RemoteSignRequest,
+    reason: &str,
+) -> Result<(), String> {
+    conn.send(&BridgeMessage::Reject {
+        session_id: Some(request.request_id.clone()),
+        reason: reason.to_string(),
+    }).await
+}
+
+/// Signer for a wallet whose key lives on the paired device
+#[derive(Clone)]
+pub struct RemoteSigner {
+    wallet: RemoteWallet,
+}
+
+impl RemoteSigner {
+    pub fn new(wallet: RemoteWallet) -> Self {
+        Self { wallet }
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for RemoteSigner {
+    async fn get_public_key(&self) -> Result<String, Box<dyn Error>> {
+        Ok(self.wallet.address.clone())
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        firewall::enforce(message, &self.wallet.address)?;
+        let bridge = crate::bridge::active_bridge()
+            .ok_or(format!("Connect {} to approve this transaction", self.wallet.device))?;
+        let mut conn = bridge.lock().await;
+        Ok(request_remote_signature(&mut conn, &self.wallet.address, message).await?)
+    }
+
+    fn get_name(&self) -> String {
+        format!("Remote Wallet ({})", self.wallet.device)
+    }
+
+    async fn is_available(&self) -> bool {
+        crate::bridge::active_bridge().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::Wallet;
+
+    #[test]
+    fn test_verify_signature() {
+        let wallet = Wallet::new("phone".to_string());
+        let signature = wallet.sign_message_bytes(b"message");
+        assert!(verify_signature(&wallet.get_public_key(), b"message", &signature).is_ok());
+        assert!(verify_signature(&wallet.get_public_key(), b"other", &signature).is_err());
+        assert!(verify_signature(&wallet.get_public_key(), b"message", &signature[..10]).is_err());
+    }
+
+    #[test]
+    fn test_message_request_is_signed_as_offchain_message() {
+        let request = RemoteSignRequest::from_message(&BridgeMessage::SignMessageRequest {
+            request_id: "1".to_string(),
+            signer_address: "addr".to_string(),
+            message: "Prove ownership".to_string(),
+        }).unwrap();
+        assert_eq!(request.message, encode_offchain_message("Prove ownership").unwrap());
+        assert_eq!(request.describe(), vec!["Off-chain message:".to_string(), "Prove ownership".to_string()]);
+    }
+}
@@ -0,0 +1,455 @@
+// src/signing/session.rs
+//! Short-lived session keys for small automated actions
+//!
+//! A session key is a throwaway keypair kept on this device. Starting a
+//! session (signed once by the main wallet) funds it with the SOL cap and,
+//! optionally, approves it as delegate on one of the wallet's token accounts
+//! up to the token cap, so the chain itself enforces the limits. Until it
+//! expires the session key can pay for and sign tips, reward claims and DCA
+//! executions without a prompt; each transaction is also checked here against
+//! the per-action limit and the remaining allowance. Anything larger still
+//! goes through the main signer. Ending a session revokes the delegation and
+//! sweeps the leftover SOL back.
+
+use crate::signing::{firewall, sign_all_slots, TransactionSigner};
+use crate::signing::software::KeypairSigner;
+use crate::transaction::TransactionClient;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    instruction::Instruction,
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature as SolanaSignature, Signer},
+    system_instruction::{self, SystemInstruction},
+    system_program,
+    transaction::VersionedTransaction,
+};
+use spl_token::instruction::TokenInstruction;
+use std::error::Error;
+use std::str::FromStr;
+
+/// Extra SOL sent to the session key to pay its own network fees
+pub const FEE_RESERVE_LAMPORTS: u64 = 1_000_000;
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// Kinds of transactions a session key may sign on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionAction {
+    Tip,
+    ClaimRewards,
+    Dca,
+}
+
+impl SessionAction {
+    pub const ALL: [SessionAction; 3] = [SessionAction::Tip, SessionAction::ClaimRewards, SessionAction::Dca];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionAction::Tip => "Tips",
+            SessionAction::ClaimRewards => "Claim rewards",
+            SessionAction::Dca => "DCA executions",
+        }
+    }
+}
+
+/// Token account the session key is delegate on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenAllowance {
+    pub mint: String,
+    pub token_account: String,
+    pub token_program: String,
+    pub decimals: u8,
+    /// Base units
+    pub cap: u64,
+    pub spent: u64,
+}
+
+impl TokenAllowance {
+    pub fn remaining(&self) -> u64 {
+        self.cap.saturating_sub(self.spent)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionKey {
+    /// Main wallet the session acts for
+    pub owner: String,
+    pub address: String,
+    /// Base58 keypair of the session key
+    pub secret: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub sol_cap_lamports: u64,
+    /// Largest amount a single transaction may move
+    pub max_per_action_lamports: u64,
+    pub spent_lamports: u64,
+    #[serde(default)]
+    pub tokens: Vec<TokenAllowance>,
+    pub actions: Vec<SessionAction>,
+}
+
+impl SessionKey {
+    /// Generate a session key for `owner` valid for `duration_secs` from `now`
+    pub fn generate(
+        owner: &str,
+        now: u64,
+        duration_secs: u64,
+        sol_cap_lamports: u64,
+        max_per_action_lamports: u64,
+        actions: Vec<SessionAction>,
+    ) -> Self {
+        let keypair = Keypair::new();
+        Self {
+            owner: owner.to_string(),
+            address: keypair.pubkey().to_string(),
+            secret: keypair.to_base58_string(),
+            created_at: now,
+            expires_at: now + duration_secs,
+            sol_cap_lamports,
+            max_per_action_lamports,
+            spent_lamports: 0,
+            tokens: Vec::new(),
+            actions,
+        }
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn remaining_lamports(&self) -> u64 {
+        self.sol_cap_lamports.saturating_sub(self.spent_lamports)
+    }
+
+    fn keypair(&self) -> Result<Keypair, String> {
+        let bytes = bs58::decode(&self.secret).into_vec().map_err(|e| format!("Invalid session key: {}", e))?;
+        Keypair::from_bytes(&bytes).map_err(|e| format!("Invalid session key: {}", e))
+    }
+
+    /// Check a transaction's spend against this session's limits
+    pub fn authorize(&self, action: SessionAction, spend: &SessionSpend, now: u64) -> Result<(), String> {
+        if self.is_expired(now) {
+            return Err("The session has expired".to_string());
+        }
+        if !self.actions.contains(&action) {
+            return Err(format!("{} are not allowed for this session", action.label()));
+        }
+        if spend.lamports > self.max_per_action_lamports {
+            return Err(format!(
+                "{:.6} SOL is above the per-action limit of {:.6} SOL",
+                spend.lamports as f64 / 1e9,
+                self.max_per_action_lamports as f64 / 1e9
+            ));
+        }
+        if spend.lamports > self.remaining_lamports() {
+            return Err("The session's SOL allowance is used up".to_string());
+        }
+        for (token_account, amount) in &spend.tokens {
+            let allowance = self.tokens.iter()
+                .find(|t| t.token_account == token_account.to_string())
+                .ok_or_else(|| format!("The session may not spend from {}", token_account))?;
+            if *amount > allowance.remaining() {
+                return Err(format!("The session's {} allowance is used up", allowance.mint));
+            }
+        }
+        Ok(())
+    }
+
+    /// "SOL used: ..." and per-token usage for display
+    pub fn usage_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "SOL used: {:.6} of {:.6} (max {:.6} per action)",
+            self.spent_lamports as f64 / 1e9,
+            self.sol_cap_lamports as f64 / 1e9,
+            self.max_per_action_lamports as f64 / 1e9
+        )];
+        for allowance in &self.tokens {
+            let scale = 10_f64.powi(allowance.decimals as i32);
+            lines.push(format!(
+                "{}: {} of {} used",
+                allowance.mint,
+                allowance.spent as f64 / scale,
+                allowance.cap as f64 / scale
+            ));
+        }
+        let actions: Vec<&str> = self.actions.iter().map(|a| a.label()).collect();
+        lines.push(format!("Allowed: {}", actions.join(", ")));
+        lines
+    }
+
+    /// Count a signed transaction against the allowance
+    pub fn record(&mut self, spend: &SessionSpend) {
+        self.spent_lamports += spend.lamports;
+        for (token_account, amount) in &spend.tokens {
+            if let Some(allowance) = self.tokens.iter_mut().find(|t| t.token_account == token_account.to_string()) {
+                allowance.spent += amount;
+            }
+        }
+    }
+}
+
+/// What a transaction signed by the session key would take from it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionSpend {
+    /// SOL leaving the session key, excluding network fees
+    pub lamports: u64,
+    /// Token amounts moved as delegate, by source token account
+    pub tokens: Vec<(Pubkey, u64)>,
+}
+
+/// Work out what `message` spends on behalf of `session`. Fails when someone
+/// else also has to sign, since a session transaction must be self-contained.
+pub fn session_spend(message: &VersionedMessage, session: &Pubkey) -> Result<SessionSpend, String> {
+    let keys = message.static_account_keys();
+    let required = message.header().num_required_signatures as usize;
+    if required != 1 || keys.first() != Some(session) {
+        return Err("Session transactions must be paid for and signed by the session key alone".to_string());
+    }
+
+    let mut spend = SessionSpend::default();
+    for ix in message.instructions() {
+        let Some(program_id) = keys.get(ix.program_id_index as usize) else { continue };
+        let account = |position: usize| ix.accounts.get(position).and_then(|&i| keys.get(i as usize)).copied();
+
+        if *program_id == system_program::id() {
+            let lamports = match bincode::deserialize::<SystemInstruction>(&ix.data) {
+                Ok(SystemInstruction::Transfer { lamports }) => lamports,
+                Ok(SystemInstruction::CreateAccount { lamports, .. }) => lamports,
+                _ => 0,
+            };
+            if account(0) == Some(*session) {
+                spend.lamports += lamports;
+            }
+        } else if *program_id == spl_token::id() || program_id.to_string() == TOKEN_2022_PROGRAM_ID {
+            let transfer = match TokenInstruction::unpack(&ix.data) {
+                Ok(TokenInstruction::Transfer { amount }) => account(0).zip(account(2)).map(|t| (t, amount)),
+                Ok(TokenInstruction::TransferChecked { amount, .. }) => account(0).zip(account(3)).map(|t| (t, amount)),
+                _ => None,
+            };
+            if let Some(((source, authority), amount)) = transfer {
+                if authority == *session {
+                    spend.tokens.push((source, amount));
+                }
+            }
+        }
+    }
+    Ok(spend)
+}
+
+/// Instructions the main wallet signs to start a session
+pub fn funding_instructions(session: &SessionKey) -> Result<Vec<Instruction>, Box<dyn Error>> {
+    let owner = Pubkey::from_str(&session.owner)?;
+    let session_key = Pubkey::from_str(&session.address)?;
+    let mut instructions = vec![system_instruction::transfer(
+        &owner,
+        &session_key,
+        session.sol_cap_lamports + FEE_RESERVE_LAMPORTS,
+    )];
+    for allowance in &session.tokens {
+        instructions.push(spl_token::instruction::approve(
+            &Pubkey::from_str(&allowance.token_program)?,
+            &Pubkey::from_str(&allowance.token_account)?,
+            &session_key,
+            &owner,
+            &[],
+            allowance.cap,
+        )?);
+    }
+    Ok(instructions)
+}
+
+/// Instructions ending a session: revoke every delegation and return what is
+/// left of the session key's SOL (`session_balance`) to the main wallet
+pub fn closing_instructions(session: &SessionKey, session_balance: u64) -> Result<Vec<Instruction>, Box<dyn Error>> {
+    let owner = Pubkey::from_str(&session.owner)?;
+    let session_key = Pubkey::from_str(&session.address)?;
+    let mut instructions = Vec::new();
+    for allowance in &session.tokens {
+        instructions.push(spl_token::instruction::revoke(
+            &Pubkey::from_str(&allowance.token_program)?,
+            &Pubkey::from_str(&allowance.token_account)?,
+            &owner,
+            &[],
+        )?);
+    }
+    if session_balance > 0 {
+        instructions.push(system_instruction::transfer(&session_key, &owner, session_balance));
+    }
+    Ok(instructions)
+}
+
+async fn send_owner_transaction(
+    client: &TransactionClient,
+    owner_signer: &dyn TransactionSigner,
+    session: &SessionKey,
+    instructions: &[Instruction],
+) -> Result<String, Box<dyn Error>> {
+    let owner = Pubkey::from_str(&session.owner)?;
+    let blockhash = client.get_recent_blockhash().await?;
+    let message = Message::new_with_blockhash(instructions, Some(&owner), &blockhash);
+    let mut transaction = VersionedTransaction {
+        signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::Legacy(message),
+    };
+    let session_signer = KeypairSigner::new(session.keypair()?);
+    sign_all_slots(&mut transaction, &[owner_signer, &session_signer]).await?;
+    client.send_transaction(&bs58::encode(bincode::serialize(&transaction)?).into_string()).await
+}
+
+/// Fund the session key and approve its token allowances
+pub async fn start_session(
+    client: &TransactionClient,
+    owner_signer: &dyn TransactionSigner,
+    session: &SessionKey,
+) -> Result<String, Box<dyn Error>> {
+    let signature = send_owner_transaction(client, owner_signer, session, &funding_instructions(session)?).await?;
+    println!("🔑 Session key {} started for {}", session.address, session.owner);
+    Ok(signature)
+}
+
+/// Revoke the session's allowances and sweep its SOL back to the main wallet
+pub async fn end_session(
+    client: &TransactionClient,
+    owner_signer: &dyn TransactionSigner,
+    session: &SessionKey,
+    rpc_url: Option<&str>,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let balance = crate::rpc::get_balance(&session.address, rpc_url).await?;
+    let lamports = (balance * 1_000_000_000.0).round() as u64;
+    let instructions = closing_instructions(session, lamports)?;
+    if instructions.is_empty() {
+        return Ok(None);
+    }
+    let signature = send_owner_transaction(client, owner_signer, session, &instructions).await?;
+    println!("🔑 Session key {} ended, returned {} lamports", session.address, lamports);
+    Ok(Some(signature))
+}
+
+/// Signs small automated transactions with the session key, within its limits
+pub struct SessionSigner {
+    session: SessionKey,
+    action: SessionAction,
+}
+
+impl SessionSigner {
+    /// The active session of `owner` when it allows `action`
+    pub fn for_action(owner: &str, action: SessionAction) -> Option<Self> {
+        let session = crate::storage::load_session_key(owner)?;
+        let now = chrono::Utc::now().timestamp() as u64;
+        (!session.is_expired(now) && session.actions.contains(&action)).then_some(Self { session, action })
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for SessionSigner {
+    async fn get_public_key(&self) -> Result<String, Box<dyn Error>> {
+        Ok(self.session.address.clone())
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        firewall::enforce(message, &self.session.address)?;
+        let parsed: VersionedMessage = bincode::deserialize(message)
+            .map_err(|_| "Session keys only sign transactions")?;
+        let spend = session_spend(&parsed, &Pubkey::from_str(&self.session.address)?)?;
+
+        // Check against the stored allowance, which other signers may have used
+        let mut current = crate::storage::load_session_key(&self.session.owner)
+            .filter(|s| s.address == self.session.address)
+            .ok_or("The session has ended")?;
+        current.authorize(self.action, &spend, chrono::Utc::now().timestamp() as u64)?;
+
+        let signature = current.keypair()?.sign_message(message);
+        current.record(&spend);
+        crate::storage::save_session_key(&current)?;
+        println!("🔑 Session key signed {} ({} lamports)", self.action.label(), spend.lamports);
+        Ok(signature.as_ref().to_vec())
+    }
+
+    fn get_name(&self) -> String {
+        format!("Session Key ({})", self.action.label())
+    }
+
+    async fn is_available(&self) -> bool {
+        !self.session.is_expired(chrono::Utc::now().timestamp() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+
+    fn session(now: u64) -> SessionKey {
+        let mut session = SessionKey::generate(
+            &Pubkey::new_unique().to_string(),
+            now,
+            3_600,
+            50_000_000,
+            10_000_000,
+            vec![SessionAction::Tip],
+        );
+        session.tokens.push(TokenAllowance {
+            mint: Pubkey::new_unique().to_string(),
+            token_account: Pubkey::new_unique().to_string(),
+            token_program: spl_token::id().to_string(),
+            decimals: 6,
+            cap: 1_000,
+            spent: 0,
+        });
+        session
+    }
+
+    fn message(instructions: &[Instruction], payer: &Pubkey) -> VersionedMessage {
+        VersionedMessage::Legacy(Message::new_with_blockhash(instructions, Some(payer), &Hash::new_unique()))
+    }
+
+    #[test]
+    fn test_spend_and_limits() {
+        let now = 1_000;
+        let mut session = session(now);
+        let key = Pubkey::from_str(&session.address).unwrap();
+        let token_account = Pubkey::from_str(&session.tokens[0].token_account).unwrap();
+        let owner = Pubkey::from_str(&session.owner).unwrap();
+
+        let tip = message(&[system_instruction::transfer(&key, &Pubkey::new_unique(), 5_000_000)], &key);
+        let spend = session_spend(&tip, &key).unwrap();
+        assert_eq!(spend.lamports, 5_000_000);
+        assert!(session.authorize(SessionAction::Tip, &spend, now).is_ok());
+        assert!(session.authorize(SessionAction::Dca, &spend, now).is_err());
+        assert!(session.authorize(SessionAction::Tip, &spend, now + 3_600).is_err());
+
+        let large = message(&[system_instruction::transfer(&key, &Pubkey::new_unique(), 20_000_000)], &key);
+        assert!(session.authorize(SessionAction::Tip, &session_spend(&large, &key).unwrap(), now).is_err());
+
+        let token = spl_token::instruction::transfer(
+            &spl_token::id(), &token_account, &Pubkey::new_unique(), &key, &[], 800,
+        ).unwrap();
+        let spend = session_spend(&message(&[token], &key), &key).unwrap();
+        assert_eq!(spend.tokens, vec![(token_account, 800)]);
+        session.authorize(SessionAction::Tip, &spend, now).unwrap();
+        session.record(&spend);
+        assert_eq!(session.tokens[0].remaining(), 200);
+        assert!(session.authorize(SessionAction::Tip, &spend, now).is_err());
+
+        // The main wallet must never be a signer of a session transaction
+        let foreign = message(&[system_instruction::transfer(&owner, &key, 1)], &key);
+        assert!(session_spend(&foreign, &key).is_err());
+    }
+
+    #[test]
+    fn test_funding_and_closing() {
+        let session = session(0);
+        let funding = funding_instructions(&session).unwrap();
+        assert_eq!(funding.len(), 2);
+        assert_eq!(funding[0], system_instruction::transfer(
+            &Pubkey::from_str(&session.owner).unwrap(),
+            &Pubkey::from_str(&session.address).unwrap(),
+            session.sol_cap_lamports + FEE_RESERVE_LAMPORTS,
+        ));
+
+        assert_eq!(closing_instructions(&session, 0).unwrap().len(), 1);
+        assert_eq!(closing_instructions(&session, 42).unwrap().len(), 2);
+    }
+}
@@ -1,7 +1,8 @@
 // src/signing/software.rs
 use crate::wallet::Wallet;
-use crate::signing::TransactionSigner;
+use crate::signing::{firewall, TransactionSigner};
 use async_trait::async_trait;
+use solana_sdk::signature::{Keypair, Signer};
 use std::error::Error;
 
 #[derive(Clone)]
@@ -22,6 +23,7 @@ impl TransactionSigner for SoftwareSigner {
     }
     
     async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        firewall::enforce(message, &self.wallet.get_public_key())?;
         let signature = self.wallet.sign_message(message);
         Ok(signature.to_bytes().to_vec())
     }
@@ -33,4 +35,35 @@ impl TransactionSigner for SoftwareSigner {
     async fn is_available(&self) -> bool {
         true // Software wallet is always available
     }
+}
+
+/// Signer for a throwaway keypair generated for a single transaction, such as
+/// a new stake or token account that must co-sign its own creation
+pub struct KeypairSigner {
+    keypair: Keypair,
+}
+
+impl KeypairSigner {
+    pub fn new(keypair: Keypair) -> Self {
+        Self { keypair }
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for KeypairSigner {
+    async fn get_public_key(&self) -> Result<String, Box<dyn Error>> {
+        Ok(self.keypair.pubkey().to_string())
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(self.keypair.sign_message(message).as_ref().to_vec())
+    }
+
+    fn get_name(&self) -> String {
+        format!("Generated Keypair: {}", self.keypair.pubkey())
+    }
+
+    async fn is_available(&self) -> bool {
+        true
+    }
 }
\ No newline at end of file
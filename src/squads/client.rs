@@ -2,6 +2,7 @@
 //! Squads client implementation following the TransactionClient pattern
 
 use crate::signing::TransactionSigner;
+use crate::transaction::TransactionClient;
 use crate::squads::types::{MultisigInfo, PendingTransaction, ApprovalResult, Member, Permissions};
 use solana_sdk::{
     pubkey::Pubkey,
@@ -145,13 +146,7 @@ impl SquadsClient {
             "params": [address.to_string()]
         });
 
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.client, &self.rpc_url, &request).await?;
 
         if let Some(lamports) = json["result"]["value"].as_u64() {
             Ok(lamports as f64 / 1_000_000_000.0)
@@ -248,6 +243,25 @@ impl SquadsClient {
         Ok(pending)
     }
 
+    /// The newest proposals of a multisig (up to `limit`), newest first.
+    /// Indexes without a proposal account (closed or never proposed) are skipped.
+    pub async fn get_recent_proposals(
+        &self,
+        multisig_address: &Pubkey,
+        transaction_index: u64,
+        limit: u64,
+    ) -> Result<Vec<Proposal>, Box<dyn Error>> {
+        let oldest = transaction_index.saturating_sub(limit.saturating_sub(1)).max(1);
+        let mut proposals = Vec::new();
+        for tx_index in (oldest..=transaction_index).rev() {
+            let (proposal_pda, _) = pda::get_proposal_pda(multisig_address, tx_index, Some(&self.program_id));
+            if let Ok(proposal_data) = self.get_account(&proposal_pda).await {
+                proposals.push(Proposal::try_from_slice(&proposal_data)?);
+            }
+        }
+        Ok(proposals)
+    }
+
     /// Approve a pending transaction with the given signer
     /// This is the main method that integrates with the existing TransactionSigner infrastructure
     pub async fn approve_transaction_with_signer(
@@ -286,12 +300,15 @@ impl SquadsClient {
             Some(self.program_id),
         );
 
+        let mut vote_instructions = vec![approval_ix];
+        TransactionClient::new(Some(&self.rpc_url)).size_compute_budget(&mut vote_instructions, &member_pubkey).await;
+
         // Get recent blockhash
         let recent_blockhash = self.get_recent_blockhash().await?;
 
         // Create transaction
         let message = solana_sdk::message::Message::new(
-            &[approval_ix],
+            &vote_instructions,
             Some(&member_pubkey),
         );
 
@@ -417,6 +434,9 @@ impl SquadsClient {
         );
         println!("[Execute] Execute instruction created with {} accounts", execute_ix.accounts.len());
 
+        let mut execute_instructions = vec![execute_ix];
+        TransactionClient::new(Some(&self.rpc_url)).size_compute_budget(&mut execute_instructions, &member_pubkey).await;
+
         // Get recent blockhash
         println!("[Execute] Getting recent blockhash...");
         let recent_blockhash = self.get_recent_blockhash().await?;
@@ -425,7 +445,7 @@ impl SquadsClient {
         // Create transaction
         println!("[Execute] Creating transaction message...");
         let message = solana_sdk::message::Message::new(
-            &[execute_ix],
+            &execute_instructions,
             Some(&member_pubkey),
         );
 
@@ -480,13 +500,7 @@ impl SquadsClient {
             ]
         });
 
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.client, &self.rpc_url, &request).await?;
 
         if let Some(data) = json["result"]["value"]["data"][0].as_str() {
             let decoded = base64::decode(data)?;
@@ -509,13 +523,7 @@ impl SquadsClient {
             ]
         });
 
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.client, &self.rpc_url, &request).await?;
 
         if let Some(blockhash_str) = json["result"]["value"]["blockhash"].as_str() {
             let hash = solana_sdk::hash::Hash::from_str(blockhash_str)?;
@@ -543,14 +551,7 @@ impl SquadsClient {
         });
 
         println!("[RPC] Sending transaction to RPC: {}", self.rpc_url);
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        println!("[RPC] Received response with status: {}", response.status());
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&self.client, &self.rpc_url, &request).await?;
         println!("[RPC] Response JSON: {}", serde_json::to_string_pretty(&json).unwrap_or_else(|_| format!("{:?}", json)));
 
         if let Some(error) = json.get("error") {
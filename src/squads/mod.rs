@@ -7,6 +7,7 @@
 
 pub mod client;
 pub mod types;
+pub mod report;
 
 pub use client::SquadsClient;
 pub use types::*;
\ No newline at end of file
@@ -0,0 +1,516 @@
+// src/squads/report.rs
+//! Treasury reports for a Squads multisig
+//!
+//! A report covers the default vault over a recent period ending at the end
+//! of yesterday (UTC): holdings at the end of the period valued at that day's
+//! close, SOL and token inflows/outflows from the vault's history, what
+//! happened to proposals during the period and how often each member voted.
+//! Reports can be saved as CSV or as a plain text PDF.
+
+use crate::balance_history::{self, BalanceChange, HistoricalBalances, ValuedBalance};
+use crate::prices::get_token_metadata;
+use crate::squads::types::{MultisigInfo, Proposal, ProposalStatus};
+use crate::squads::SquadsClient;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+/// Proposals are fetched one account at a time, so only the newest are read
+const MAX_REPORT_PROPOSALS: u64 = 50;
+const PDF_LINES_PER_PAGE: usize = 54;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportPeriod {
+    Week,
+    Month,
+    Quarter,
+}
+
+impl ReportPeriod {
+    pub const ALL: [ReportPeriod; 3] = [ReportPeriod::Week, ReportPeriod::Month, ReportPeriod::Quarter];
+
+    pub fn days(&self) -> i64 {
+        match self {
+            ReportPeriod::Week => 7,
+            ReportPeriod::Month => 30,
+            ReportPeriod::Quarter => 90,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportPeriod::Week => "Last 7 days",
+            ReportPeriod::Month => "Last 30 days",
+            ReportPeriod::Quarter => "Last 90 days",
+        }
+    }
+
+    /// `(start, end)`: the period ends at the last second of yesterday (UTC)
+    pub fn bounds(&self, now: i64) -> (i64, i64) {
+        let end = now - now.rem_euclid(SECONDS_PER_DAY) - 1;
+        (end - self.days() * SECONDS_PER_DAY, end)
+    }
+}
+
+/// The multisig a report is for
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportTarget {
+    pub name: String,
+    pub multisig: Pubkey,
+    pub vault: Pubkey,
+    pub transaction_index: u64,
+    pub members: Vec<Pubkey>,
+}
+
+impl From<&MultisigInfo> for ReportTarget {
+    fn from(info: &MultisigInfo) -> Self {
+        Self {
+            name: info.name.clone(),
+            multisig: info.address,
+            vault: info.vault_address,
+            transaction_index: info.transaction_index,
+            members: info.members.iter().map(|m| m.key).collect(),
+        }
+    }
+}
+
+/// Amounts moved into and out of the vault for one asset (UI units)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetFlow {
+    pub label: String,
+    pub inflow: f64,
+    pub outflow: f64,
+}
+
+impl AssetFlow {
+    pub fn net(&self) -> f64 {
+        self.inflow - self.outflow
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProposalState {
+    Draft,
+    Active,
+    Approved,
+    Executed,
+    Rejected,
+    Cancelled,
+}
+
+impl ProposalState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProposalState::Draft => "Draft",
+            ProposalState::Active => "Awaiting votes",
+            ProposalState::Approved => "Approved, not executed",
+            ProposalState::Executed => "Executed",
+            ProposalState::Rejected => "Rejected",
+            ProposalState::Cancelled => "Cancelled",
+        }
+    }
+}
+
+/// The parts of a proposal a report needs
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProposalRecord {
+    pub state: ProposalState,
+    /// When it reached its current state
+    pub timestamp: i64,
+    pub approved: Vec<Pubkey>,
+    pub rejected: Vec<Pubkey>,
+}
+
+impl ProposalRecord {
+    fn from_proposal(proposal: &Proposal) -> Option<Self> {
+        let (state, timestamp) = match proposal.status {
+            ProposalStatus::Draft { timestamp } => (ProposalState::Draft, timestamp),
+            ProposalStatus::Active { timestamp } => (ProposalState::Active, timestamp),
+            ProposalStatus::Approved { timestamp } => (ProposalState::Approved, timestamp),
+            ProposalStatus::Executed { timestamp } => (ProposalState::Executed, timestamp),
+            ProposalStatus::Rejected { timestamp } => (ProposalState::Rejected, timestamp),
+            ProposalStatus::Cancelled { timestamp } => (ProposalState::Cancelled, timestamp),
+            _ => return None,
+        };
+        Some(Self {
+            state,
+            timestamp,
+            approved: proposal.approved.clone(),
+            rejected: proposal.rejected.clone(),
+        })
+    }
+}
+
+/// How often a member voted on the proposals of the period
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignerParticipation {
+    pub member: Pubkey,
+    pub approved: usize,
+    pub rejected: usize,
+    /// Proposals in the period that could be voted on
+    pub eligible: usize,
+}
+
+impl SignerParticipation {
+    /// Share of eligible proposals voted on, 0-100
+    pub fn rate(&self) -> f64 {
+        if self.eligible == 0 {
+            return 0.0;
+        }
+        (self.approved + self.rejected) as f64 * 100.0 / self.eligible as f64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreasuryReport {
+    pub target: ReportTarget,
+    pub period: ReportPeriod,
+    pub start: i64,
+    pub end: i64,
+    pub holdings: Vec<ValuedBalance>,
+    pub flows: Vec<AssetFlow>,
+    pub transactions: usize,
+    /// False when the vault had more activity than could be fetched
+    pub flows_complete: bool,
+    pub proposals: Vec<(ProposalState, usize)>,
+    pub participation: Vec<SignerParticipation>,
+}
+
+fn format_day(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn short_key(key: &Pubkey) -> String {
+    let key = key.to_string();
+    format!("{}...{}", &key[..4], &key[key.len() - 4..])
+}
+
+impl TreasuryReport {
+    pub fn total_usd(&self) -> f64 {
+        self.holdings.iter().filter_map(|h| h.usd_value()).sum()
+    }
+
+    /// e.g. "2026-09-15 to 2026-10-15"
+    pub fn period_label(&self) -> String {
+        format!("{} to {}", format_day(self.start + 1), format_day(self.end))
+    }
+
+    pub fn holding_line(holding: &ValuedBalance) -> String {
+        match holding.usd_value() {
+            Some(value) => format!("{:.6} {} (${:.2})", holding.amount, holding.label, value),
+            None => format!("{:.6} {} (no price)", holding.amount, holding.label),
+        }
+    }
+
+    pub fn flow_line(flow: &AssetFlow) -> String {
+        format!("{}: +{:.6} in, -{:.6} out (net {:+.6})", flow.label, flow.inflow, flow.outflow, flow.net())
+    }
+
+    pub fn participation_line(signer: &SignerParticipation) -> String {
+        format!(
+            "{}: {} approved, {} rejected of {} ({:.0}%)",
+            short_key(&signer.member),
+            signer.approved,
+            signer.rejected,
+            signer.eligible,
+            signer.rate()
+        )
+    }
+
+    /// The report as plain lines, for the PDF
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Treasury report: {}", self.target.name),
+            format!("Multisig: {}", self.target.multisig),
+            format!("Vault: {}", self.target.vault),
+            format!("Period: {} ({})", self.period_label(), self.period.label()),
+            String::new(),
+            format!("Holdings at end of period (total ${:.2})", self.total_usd()),
+        ];
+        lines.extend(self.holdings.iter().map(|h| format!("  {}", Self::holding_line(h))));
+
+        lines.push(String::new());
+        lines.push(format!("Inflows and outflows ({} transactions)", self.transactions));
+        if self.flows.is_empty() {
+            lines.push("  No movements".to_string());
+        }
+        lines.extend(self.flows.iter().map(|f| format!("  {}", Self::flow_line(f))));
+        if !self.flows_complete {
+            lines.push("  Busy vault: only the most recent transactions are included".to_string());
+        }
+
+        lines.push(String::new());
+        lines.push("Proposal activity".to_string());
+        if self.proposals.is_empty() {
+            lines.push("  No proposals changed state".to_string());
+        }
+        lines.extend(self.proposals.iter().map(|(state, count)| format!("  {}: {}", state.label(), count)));
+
+        lines.push(String::new());
+        lines.push("Signer participation".to_string());
+        lines.extend(self.participation.iter().map(|p| format!("  {}", Self::participation_line(p))));
+        lines
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut rows = vec!["section,item,value,detail".to_string()];
+        rows.push(format!("period,{},{},{}", self.period.label(), format_day(self.start + 1), format_day(self.end)));
+        for holding in &self.holdings {
+            let usd = holding.usd_value().map(|v| format!("{:.2}", v)).unwrap_or_default();
+            rows.push(format!("holding,{},{},{}", csv_field(&holding.label), holding.amount, usd));
+        }
+        for flow in &self.flows {
+            rows.push(format!("inflow,{},{},", csv_field(&flow.label), flow.inflow));
+            rows.push(format!("outflow,{},{},", csv_field(&flow.label), flow.outflow));
+        }
+        for (state, count) in &self.proposals {
+            rows.push(format!("proposals,{},{},", state.label(), count));
+        }
+        for signer in &self.participation {
+            rows.push(format!(
+                "signer,{},{},{}",
+                signer.member,
+                signer.approved + signer.rejected,
+                signer.eligible
+            ));
+        }
+        rows.join("\n") + "\n"
+    }
+
+    pub fn to_pdf(&self) -> String {
+        render_pdf(&self.lines())
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Total SOL and per-mint token movements of the vault. `labels` maps mints
+/// to symbols; unknown mints are shown shortened.
+pub fn summarize_flows(changes: &[BalanceChange], labels: &HashMap<String, String>) -> Vec<AssetFlow> {
+    let mut sol = AssetFlow { label: "SOL".to_string(), inflow: 0.0, outflow: 0.0 };
+    let mut tokens: HashMap<String, AssetFlow> = HashMap::new();
+
+    for change in changes {
+        let sol_amount = change.sol_delta.unsigned_abs() as f64 / 1_000_000_000.0;
+        if change.sol_delta > 0 {
+            sol.inflow += sol_amount;
+        } else {
+            sol.outflow += sol_amount;
+        }
+        for (mint, delta, decimals) in &change.token_deltas {
+            let flow = tokens.entry(mint.clone()).or_insert_with(|| AssetFlow {
+                label: labels.get(mint).cloned().unwrap_or_else(|| format!("{}...", &mint[..mint.len().min(8)])),
+                inflow: 0.0,
+                outflow: 0.0,
+            });
+            let amount = delta.unsigned_abs() as f64 / 10_f64.powi(*decimals as i32);
+            if *delta > 0 {
+                flow.inflow += amount;
+            } else {
+                flow.outflow += amount;
+            }
+        }
+    }
+
+    let mut flows: Vec<AssetFlow> = tokens.into_values().collect();
+    flows.sort_by(|a, b| a.label.cmp(&b.label));
+    if sol.inflow > 0.0 || sol.outflow > 0.0 {
+        flows.insert(0, sol);
+    }
+    flows
+}
+
+/// Count the proposals that reached their current state in `(start, end]`
+/// and how each member voted on them
+pub fn summarize_proposals(
+    records: &[ProposalRecord],
+    members: &[Pubkey],
+    start: i64,
+    end: i64,
+) -> (Vec<(ProposalState, usize)>, Vec<SignerParticipation>) {
+    let in_period: Vec<&ProposalRecord> = records
+        .iter()
+        .filter(|r| r.timestamp > start && r.timestamp <= end)
+        .collect();
+
+    let mut counts: HashMap<ProposalState, usize> = HashMap::new();
+    for record in &in_period {
+        *counts.entry(record.state).or_default() += 1;
+    }
+    let mut activity: Vec<(ProposalState, usize)> = counts.into_iter().collect();
+    activity.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.label().cmp(b.0.label())));
+
+    // Drafts can't be voted on yet
+    let votable: Vec<&ProposalRecord> = in_period.iter().copied().filter(|r| r.state != ProposalState::Draft).collect();
+    let participation = members
+        .iter()
+        .map(|member| SignerParticipation {
+            member: *member,
+            approved: votable.iter().filter(|r| r.approved.contains(member)).count(),
+            rejected: votable.iter().filter(|r| r.rejected.contains(member)).count(),
+            eligible: votable.len(),
+        })
+        .collect();
+    (activity, participation)
+}
+
+fn pdf_text(line: &str) -> String {
+    line.chars()
+        .map(|c| if c.is_ascii() && !c.is_ascii_control() { c } else { '?' })
+        .collect::<String>()
+        .replace('\\', "\\\\")
+        .replace('(', "\\(")
+        .replace(')', "\\)")
+}
+
+/// A minimal A4 PDF showing `lines` in Helvetica, one object per page
+pub fn render_pdf(lines: &[String]) -> String {
+    let pages: Vec<&[String]> = if lines.is_empty() { vec![&[]] } else { lines.chunks(PDF_LINES_PER_PAGE).collect() };
+    let kids: Vec<String> = (0..pages.len()).map(|i| format!("{} 0 R", 4 + i * 2)).collect();
+
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), pages.len()),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+    ];
+    for (index, page) in pages.iter().enumerate() {
+        let mut stream = String::from("BT /F1 10 Tf 14 TL 50 806 Td\n");
+        for line in page.iter() {
+            stream.push_str(&format!("({}) '\n", pdf_text(line)));
+        }
+        stream.push_str("ET");
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            5 + index * 2
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}\nendstream", stream.len(), stream));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", index + 1, object));
+    }
+    let xref = pdf.len();
+    pdf.push_str(&format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1));
+    for offset in offsets {
+        pdf.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    pdf.push_str(&format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref));
+    pdf
+}
+
+/// Build the report for `target` over `period`
+pub async fn generate_report(target: &ReportTarget, period: ReportPeriod, rpc_url: Option<&str>) -> Result<TreasuryReport, String> {
+    let (start, end) = period.bounds(chrono::Utc::now().timestamp());
+    let vault = target.vault.to_string();
+    println!("📊 Building {} treasury report for {}", period.label(), vault);
+
+    let balances: HistoricalBalances = balance_history::reconstruct_balances(&vault, end, rpc_url).await?;
+    let holdings = balance_history::value_balances(&balances).await;
+
+    let (changes, flows_complete) = balance_history::balance_changes_between(&vault, start, end, rpc_url).await?;
+    let mut mints: Vec<String> = changes
+        .iter()
+        .flat_map(|c| c.token_deltas.iter().map(|(mint, _, _)| mint.clone()))
+        .collect();
+    mints.sort();
+    mints.dedup();
+    let labels: HashMap<String, String> = match get_token_metadata(mints).await {
+        Ok(metadata) => metadata.into_iter().map(|(mint, m)| (mint, m.symbol)).collect(),
+        Err(e) => {
+            println!("⚠️ Token metadata unavailable: {}", e);
+            HashMap::new()
+        }
+    };
+    let flows = summarize_flows(&changes, &labels);
+
+    let proposals = SquadsClient::new(rpc_url)
+        .get_recent_proposals(&target.multisig, target.transaction_index, MAX_REPORT_PROPOSALS)
+        .await
+        .map_err(|e| format!("Failed to load proposals: {}", e))?;
+    let records: Vec<ProposalRecord> = proposals.iter().filter_map(ProposalRecord::from_proposal).collect();
+    let (proposals, participation) = summarize_proposals(&records, &target.members, start, end);
+
+    Ok(TreasuryReport {
+        target: target.clone(),
+        period,
+        start,
+        end,
+        holdings,
+        flows,
+        transactions: changes.len(),
+        flows_complete,
+        proposals,
+        participation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_period_ends_yesterday() {
+        // 2026-10-16 13:00:00 UTC
+        let now = 1_792_155_600;
+        let (start, end) = ReportPeriod::Week.bounds(now);
+        assert_eq!(format_day(end), "2026-10-15");
+        assert_eq!(format_day(end + 1), "2026-10-16");
+        assert_eq!(end - start, 7 * SECONDS_PER_DAY);
+    }
+
+    #[test]
+    fn test_summarize_flows() {
+        let mint = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string();
+        let changes = vec![
+            BalanceChange { sol_delta: 2_000_000_000, token_deltas: vec![(mint.clone(), 5_000_000, 6)], ..Default::default() },
+            BalanceChange { sol_delta: -500_000_000, token_deltas: vec![(mint.clone(), -1_500_000, 6)], ..Default::default() },
+        ];
+        let labels = HashMap::from([(mint, "USDC".to_string())]);
+        let flows = summarize_flows(&changes, &labels);
+        assert_eq!(flows[0], AssetFlow { label: "SOL".to_string(), inflow: 2.0, outflow: 0.5 });
+        assert_eq!(flows[1], AssetFlow { label: "USDC".to_string(), inflow: 5.0, outflow: 1.5 });
+        assert!(summarize_flows(&[], &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_summarize_proposals() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let record = |state, timestamp, approved: Vec<Pubkey>| ProposalRecord { state, timestamp, approved, rejected: vec![] };
+        let records = vec![
+            record(ProposalState::Executed, 150, vec![alice, bob]),
+            record(ProposalState::Active, 160, vec![alice]),
+            record(ProposalState::Draft, 170, vec![]),
+            // Outside the period
+            record(ProposalState::Executed, 50, vec![bob]),
+        ];
+        let (activity, participation) = summarize_proposals(&records, &[alice, bob], 100, 200);
+        assert_eq!(activity.iter().map(|(_, n)| n).sum::<usize>(), 3);
+        assert_eq!(participation[0].approved, 2);
+        assert_eq!(participation[0].eligible, 2);
+        assert_eq!(participation[1].rate(), 50.0);
+    }
+
+    #[test]
+    fn test_pdf_structure() {
+        let lines: Vec<String> = (0..60).map(|i| format!("Line (ü) {}", i)).collect();
+        let pdf = render_pdf(&lines);
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.is_ascii());
+        assert!(pdf.contains("/Count 2"));
+        assert!(pdf.contains("(Line \\(?\\) 0) '"));
+
+        let xref: usize = pdf.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert!(pdf[xref..].starts_with("xref"));
+    }
+}
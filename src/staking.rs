@@ -8,14 +8,16 @@ use solana_sdk::{
     hash::Hash,
     commitment_config::CommitmentConfig,
 };
-use solana_sdk::stake::instruction::merge;
+use solana_sdk::stake::instruction::{merge, split};
 use crate::wallet::{Wallet, WalletInfo};
 use crate::hardware::HardwareWallet;
-use crate::signing::{TransactionSigner, software::SoftwareSigner, hardware::HardwareSigner};
+use crate::signing::{sign_all_slots, TransactionSigner, software::{KeypairSigner, SoftwareSigner}, hardware::HardwareSigner};
 use crate::storage::get_current_jito_settings;
 use crate::transaction::TransactionClient;
 use crate::rpc::{ get_balance, get_minimum_balance_for_rent_exemption };
-use crate::rpc::{get_stake_accounts_by_owner, get_epoch_info, StakeAccountRpcData, EpochInfo};
+use crate::rpc::{get_stake_accounts_by_owner, get_epoch_info, get_inflation_rewards, StakeAccountRpcData, EpochInfo};
+use crate::rpc::{get_stake_accounts, get_stake_accounts_by_staker};
+use crate::storage::{load_imported_stake_accounts, save_imported_stake_accounts};
 use crate::timeout;
 use std::sync::Arc;
 use std::str::FromStr;
@@ -41,7 +43,7 @@ pub struct StakeAccountInfo {
 }
 
 // Extended struct for stake_modal.rs compatibility
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DetailedStakeAccount {
     pub pubkey: Pubkey,
     pub balance: u64,
@@ -86,6 +88,8 @@ pub enum StakeAccountState {
     Uninitialized,
     Initialized,
     Delegated,
+    /// Deactivated this epoch, withdrawable from the next one
+    Deactivating,
     RewardsPool,
 }
 
@@ -95,6 +99,7 @@ impl std::fmt::Display for StakeAccountState {
             StakeAccountState::Uninitialized => write!(f, "Uninitialized"),
             StakeAccountState::Initialized => write!(f, "Initialized"),
             StakeAccountState::Delegated => write!(f, "Active"),
+            StakeAccountState::Deactivating => write!(f, "Deactivating"),
             StakeAccountState::RewardsPool => write!(f, "Rewards Pool"),
         }
     }
@@ -149,28 +154,11 @@ impl StakingClient {
         from_pubkey: &Pubkey,
         instructions: &mut Vec<solana_sdk::instruction::Instruction>,
     ) -> Result<(), Box<dyn Error>> {
-        // First Jito address (as per your existing implementation)
-        let jito_address1 = Pubkey::from_str("juLesoSmdTcRtzjCzYzRoHrnF8GhVu6KCV7uxq7nJGp")?;
-        
-        // Second Jito address (as per your existing implementation)
-        let jito_address2 = Pubkey::from_str("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL")?;
-
-        // Add two transfer instructions as tips to Jito (same as transfers)
-        let tip_instruction1 = system_instruction::transfer(
+        // App tip and Jito tip (same as transfers)
+        instructions.extend(crate::tips::tip_instructions(
             from_pubkey,
-            &jito_address1,
-            100_000, // 0.0001 SOL in lamports
-        );
-
-        let tip_instruction2 = system_instruction::transfer(
-            from_pubkey,
-            &jito_address2,
-            100_000, // 0.0001 SOL in lamports
-        );
-
-        // Add the tip instructions to the existing instructions list
-        instructions.push(tip_instruction1);
-        instructions.push(tip_instruction2);
+            Some(crate::tips::DEFAULT_JITO_TIP_LAMPORTS),
+        )?);
 
         println!("Added Jito tip instructions to staking transaction");
         Ok(())
@@ -217,19 +205,19 @@ impl StakingClient {
             })
         };
 
-        let response = client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = crate::rpc_pool::post_json(&client, &self.rpc_url, &request).await?;
         
         println!("Send staking transaction response: {:?}", json);
         
         if let Some(error) = json.get("error") {
             Err(format!("Staking transaction error: {:?}", error).into())
         } else if let Some(result) = json["result"].as_str() {
+            // Track it so the confirmation tracker keeps re-sending it
+            if let Some(tx) = bs58::decode(signed_tx).into_vec().ok()
+                .and_then(|bytes| bincode::deserialize::<VersionedTransaction>(&bytes).ok())
+            {
+                crate::submission::track(result, tx);
+            }
             Ok(result.to_string())
         } else {
             Err(format!("Unknown error sending staking transaction: {:?}", json).into())
@@ -345,6 +333,8 @@ impl StakingClient {
                 .map_err(|e| StakingError::TransactionFailed(format!("Failed to apply Jito modifications: {}", e)))?;
         }
 
+        self.transaction_client.size_compute_budget(&mut instructions, &authority_pubkey).await;
+
         // Create a message with all instructions
         let mut message = Message::new(&instructions, Some(&authority_pubkey));
         message.recent_blockhash = recent_blockhash;
@@ -357,43 +347,13 @@ impl StakingClient {
         
         println!("Number of signatures expected for staking transaction: {}", transaction.message.header().num_required_signatures);
         
-        // Serialize the transaction message for signing
-        let message_bytes = transaction.message.serialize();
-        
-        // Sign the message with our signer (wallet or hardware wallet)
-        let signature_bytes = signer.sign_message(&message_bytes).await
+        // The new stake account co-signs its own creation alongside the wallet
+        let stake_account_signer = KeypairSigner::new(stake_account_keypair);
+        sign_all_slots(&mut transaction, &[signer, &stake_account_signer]).await
             .map_err(|e| StakingError::WalletError(format!("Failed to sign transaction: {}", e)))?;
         
-        // Convert to solana signature (expect exactly 64 bytes)
-        if signature_bytes.len() != 64 {
-            return Err(StakingError::WalletError(format!("Invalid signature length: expected 64, got {}", signature_bytes.len())));
-        }
-        
-        let mut sig_array = [0u8; 64];
-        sig_array.copy_from_slice(&signature_bytes);
-        let solana_signature = SolanaSignature::from(sig_array);
-        
-        // We need to handle the stake account keypair separately since it's generated locally
-        // Create a transaction and sign with BOTH the wallet signer AND the stake account keypair
-        let legacy_message = match &transaction.message {
-            VersionedMessage::Legacy(msg) => msg.clone(),
-            _ => return Err(StakingError::TransactionFailed("Expected legacy message".to_string())),
-        };
-        
-        let mut legacy_transaction = solana_sdk::transaction::Transaction {
-            signatures: vec![SolanaSignature::default(); legacy_message.header.num_required_signatures as usize],
-            message: legacy_message,
-        };
-        
-        // Sign with the stake account keypair first
-        legacy_transaction.partial_sign(&[&stake_account_keypair], recent_blockhash);
-        
-        // Then manually add the wallet signature
-        // The wallet signature should be the first signature since the wallet is the fee payer
-        legacy_transaction.signatures[0] = solana_signature;
-        
         // Serialize the entire transaction with signatures
-        let serialized_transaction = bincode::serialize(&legacy_transaction)
+        let serialized_transaction = bincode::serialize(&transaction)
             .map_err(|e| StakingError::TransactionFailed(format!("Failed to serialize transaction: {}", e)))?;
         let encoded_transaction = bs58::encode(serialized_transaction).into_string();
         
@@ -410,6 +370,92 @@ impl StakingClient {
             staked_amount: stake_amount_lamports,
         })
     }
+
+    /// Create and delegate one stake account per `(vote account, lamports)`
+    /// allocation in a single transaction
+    pub async fn create_stake_accounts_batch(
+        &self,
+        signer: &dyn TransactionSigner,
+        allocations: &[(Pubkey, u64)],
+    ) -> Result<Vec<StakeAccountInfo>, StakingError> {
+        if allocations.iter().any(|(_, lamports)| *lamports < 10_000_000) {
+            return Err(StakingError::InvalidAmount("Minimum stake amount is 0.01 SOL per validator".to_string()));
+        }
+
+        let authority_pubkey_str = signer.get_public_key().await
+            .map_err(|e| StakingError::WalletError(format!("Failed to get public key: {}", e)))?;
+        let authority_pubkey = Pubkey::from_str(&authority_pubkey_str)
+            .map_err(|_| StakingError::WalletError("Invalid wallet address".to_string()))?;
+
+        let rent_exemption = get_minimum_balance_for_rent_exemption(200, Some(&self.rpc_url))
+            .await
+            .map_err(|e| StakingError::RpcError(format!("Failed to get rent exemption: {}", e)))?;
+
+        let current_slot = self.transaction_client.get_current_slot().await
+            .map_err(|e| StakingError::RpcError(format!("Failed to get current slot: {}", e)))?;
+        let timeout_ix = timeout::build_timeout_instruction_from_current(current_slot, timeout::DEFAULT_SLOT_WINDOW)
+            .map_err(|e| StakingError::TransactionFailed(format!("Failed to build timeout instruction: {}", e)))?;
+        let recent_blockhash = self.transaction_client.get_recent_blockhash().await
+            .map_err(|e| StakingError::RpcError(format!("Failed to get recent blockhash: {}", e)))?;
+
+        let mut instructions = vec![timeout_ix];
+        let mut stake_accounts = Vec::with_capacity(allocations.len());
+        let mut stake_account_pubkeys = Vec::with_capacity(allocations.len());
+        for (validator_pubkey, lamports) in allocations {
+            let keypair = Keypair::new();
+            let stake_account_pubkey = keypair.pubkey();
+            instructions.push(system_instruction::create_account(
+                &authority_pubkey,
+                &stake_account_pubkey,
+                rent_exemption + lamports,
+                200,
+                &solana_sdk::stake::program::id(),
+            ));
+            instructions.push(initialize(
+                &stake_account_pubkey,
+                &Authorized { staker: authority_pubkey, withdrawer: authority_pubkey },
+                &Lockup::default(),
+            ));
+            instructions.push(delegate_stake(&stake_account_pubkey, &authority_pubkey, validator_pubkey));
+            stake_accounts.push(KeypairSigner::new(keypair));
+            stake_account_pubkeys.push(stake_account_pubkey);
+        }
+
+        if get_current_jito_settings().jito_tx {
+            self.apply_jito_modifications(&authority_pubkey, &mut instructions)
+                .map_err(|e| StakingError::TransactionFailed(format!("Failed to apply Jito modifications: {}", e)))?;
+        }
+        self.transaction_client.size_compute_budget(&mut instructions, &authority_pubkey).await;
+
+        let mut message = Message::new(&instructions, Some(&authority_pubkey));
+        message.recent_blockhash = recent_blockhash;
+        let mut transaction = VersionedTransaction {
+            signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
+            message: VersionedMessage::Legacy(message),
+        };
+
+        // Every new stake account co-signs its own creation
+        let mut signers: Vec<&dyn TransactionSigner> = vec![signer];
+        signers.extend(stake_accounts.iter().map(|s| s as &dyn TransactionSigner));
+        sign_all_slots(&mut transaction, &signers).await
+            .map_err(|e| StakingError::WalletError(format!("Failed to sign transaction: {}", e)))?;
+
+        let serialized_transaction = bincode::serialize(&transaction)
+            .map_err(|e| StakingError::TransactionFailed(format!("Failed to serialize transaction: {}", e)))?;
+        let signature = self.send_staking_transaction(&bs58::encode(serialized_transaction).into_string()).await
+            .map_err(|e| StakingError::TransactionFailed(format!("Failed to send staking transaction: {}", e)))?;
+
+        Ok(stake_account_pubkeys
+            .into_iter()
+            .zip(allocations)
+            .map(|(stake_account_pubkey, (validator_pubkey, lamports))| StakeAccountInfo {
+                stake_account_pubkey,
+                transaction_signature: signature.clone(),
+                validator_vote_account: *validator_pubkey,
+                staked_amount: *lamports,
+            })
+            .collect())
+    }
 }
 
 /// Create and delegate a stake account (updated to use Jito)
@@ -438,6 +484,76 @@ pub async fn create_stake_account(
     staking_client.create_stake_account_with_jito(signer.as_ref(), validator_vote_account, stake_amount_sol).await
 }
 
+/// Simulate the transaction `create_stake_account` sends, for the
+/// confirmation before signing
+pub async fn preview_stake(
+    wallet_address: &str,
+    validator_vote_account: &str,
+    stake_amount_sol: f64,
+    rpc_url: Option<&str>,
+) -> Result<crate::transaction::SimulationPreview, String> {
+    use crate::composer::{compose, ComposerAction, STAKE_ACCOUNT_SIZE};
+
+    let authority = Pubkey::from_str(wallet_address).map_err(|_| "Invalid wallet address".to_string())?;
+    let vote_account = Pubkey::from_str(validator_vote_account).map_err(|_| "Invalid validator public key".to_string())?;
+    let rent = get_minimum_balance_for_rent_exemption(STAKE_ACCOUNT_SIZE, rpc_url)
+        .await
+        .map_err(|e| format!("Failed to get rent exemption: {}", e))?;
+
+    // Same instructions and tips as the stake itself
+    let mut actions = vec![ComposerAction::Stake { vote_account, lamports: (stake_amount_sol * 1_000_000_000.0) as u64 }];
+    if get_current_jito_settings().jito_tx {
+        actions.push(ComposerAction::Tip { lamports: crate::tips::DEFAULT_JITO_TIP_LAMPORTS });
+    }
+    let builder = compose(&authority, &actions, rent)?;
+    TransactionClient::new(rpc_url).preview_built(&builder).await.map_err(|e| e.to_string())
+}
+
+/// Stake accounts created per transaction when spreading stake; each adds a
+/// signature and three instructions, and two leave room for the tips
+pub const SPREAD_ACCOUNTS_PER_TX: usize = 2;
+
+/// `total` lamports in `parts` near-equal amounts, the remainder spread over
+/// the first ones
+pub fn split_evenly(total: u64, parts: usize) -> Vec<u64> {
+    if parts == 0 {
+        return Vec::new();
+    }
+    let share = total / parts as u64;
+    let remainder = (total % parts as u64) as usize;
+    (0..parts).map(|i| share + u64::from(i < remainder)).collect()
+}
+
+/// Create one batch of a spread: a stake account per `(vote account,
+/// lamports)` allocation, in one transaction
+pub async fn create_stake_batch(
+    wallet_info: Option<&WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    allocations: &[(String, u64)],
+    rpc_url: Option<&str>,
+) -> Result<Vec<StakeAccountInfo>, StakingError> {
+    let allocations = allocations
+        .iter()
+        .map(|(vote_account, lamports)| {
+            Pubkey::from_str(vote_account)
+                .map(|pubkey| (pubkey, *lamports))
+                .map_err(|_| StakingError::InvalidValidator(format!("Invalid validator {}", vote_account)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let signer: Box<dyn TransactionSigner> = if let Some(hw) = hardware_wallet {
+        Box::new(HardwareSigner::from_wallet(hw))
+    } else if let Some(w) = wallet_info {
+        let wallet = Wallet::from_wallet_info(w)
+            .map_err(|e| StakingError::WalletError(format!("Failed to create wallet: {}", e)))?;
+        Box::new(SoftwareSigner::new(wallet))
+    } else {
+        return Err(StakingError::WalletError("No wallet or hardware wallet provided".to_string()));
+    };
+
+    StakingClient::new(rpc_url).create_stake_accounts_batch(signer.as_ref(), &allocations).await
+}
+
 /// Convert RPC stake account data to DetailedStakeAccount format
 fn convert_rpc_to_detailed_stake_account(
     rpc_data: &StakeAccountRpcData,
@@ -470,23 +586,12 @@ fn convert_rpc_to_detailed_stake_account(
     };
     
     // Determine stake account state
-    let state = if let Some(stake_details) = &rpc_data.account.data.parsed.info.stake {
-        let activation_epoch_num = stake_details.delegation.activation_epoch
-            .parse::<u64>()
-            .unwrap_or(u64::MAX);
-        let deactivation_epoch_num = stake_details.delegation.deactivation_epoch
-            .parse::<u64>()
-            .unwrap_or(u64::MAX);
-        
-        if deactivation_epoch_num != u64::MAX && deactivation_epoch_num <= current_epoch {
-            StakeAccountState::Uninitialized // Deactivated
-        } else if activation_epoch_num <= current_epoch {
-            StakeAccountState::Delegated // Active
-        } else {
-            StakeAccountState::Initialized // Activating
-        }
-    } else {
-        StakeAccountState::Initialized
+    let state = match stake_phase(activation_epoch, deactivation_epoch, current_epoch) {
+        StakePhase::Active => StakeAccountState::Delegated,
+        StakePhase::Activating => StakeAccountState::Initialized,
+        StakePhase::Deactivating => StakeAccountState::Deactivating,
+        // Nothing delegated, the whole balance can be withdrawn
+        StakePhase::Inactive | StakePhase::Undelegated => StakeAccountState::Uninitialized,
     };
     
     Ok(DetailedStakeAccount {
@@ -551,6 +656,430 @@ pub async fn scan_stake_accounts(
     Ok(detailed_accounts)
 }
 
+/// Epochs of rewards shown per stake account
+pub const REWARD_EPOCHS: u64 = 5;
+
+/// Where a delegation is in its lifecycle. Stake changes state at epoch
+/// boundaries: delegated in epoch E it's active from E+1, deactivated in
+/// epoch E it cools down until E+1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StakePhase {
+    Undelegated,
+    Activating,
+    Active,
+    Deactivating,
+    Inactive,
+}
+
+impl std::fmt::Display for StakePhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StakePhase::Undelegated => write!(f, "Undelegated"),
+            StakePhase::Activating => write!(f, "Activating"),
+            StakePhase::Active => write!(f, "Active"),
+            StakePhase::Deactivating => write!(f, "Deactivating"),
+            StakePhase::Inactive => write!(f, "Inactive"),
+        }
+    }
+}
+
+/// Phase of a delegation from its activation and deactivation epochs
+pub fn stake_phase(
+    activation_epoch: Option<u64>,
+    deactivation_epoch: Option<u64>,
+    current_epoch: u64,
+) -> StakePhase {
+    match (activation_epoch, deactivation_epoch) {
+        (None, _) => StakePhase::Undelegated,
+        (Some(_), Some(deactivation)) if deactivation < current_epoch => StakePhase::Inactive,
+        (Some(_), Some(_)) => StakePhase::Deactivating,
+        (Some(activation), None) if activation >= current_epoch => StakePhase::Activating,
+        (Some(_), None) => StakePhase::Active,
+    }
+}
+
+/// Reward paid to a stake account at the end of an epoch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StakeReward {
+    pub epoch: u64,
+    pub lamports: u64,
+}
+
+/// How a stake account was found for a wallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StakeOrigin {
+    /// The wallet is its withdraw authority
+    Withdrawer,
+    /// The wallet is only its stake authority, as with stake created by
+    /// another wallet and delegated to this one's management
+    Staker,
+    /// Added by address, for RPCs that refuse to scan the stake program
+    Imported,
+}
+
+/// Which of a stake account's authorities `wallet` holds, if any
+pub fn authority_origin(staker: &str, withdrawer: &str, wallet: &str) -> Option<StakeOrigin> {
+    if withdrawer == wallet {
+        Some(StakeOrigin::Withdrawer)
+    } else if staker == wallet {
+        Some(StakeOrigin::Staker)
+    } else {
+        None
+    }
+}
+
+/// A stake account with everything the positions view shows about it
+#[derive(Debug, Clone)]
+pub struct StakePosition {
+    pub account: DetailedStakeAccount,
+    pub origin: StakeOrigin,
+    pub phase: StakePhase,
+    /// Vote account the stake is delegated to
+    pub voter: Option<String>,
+    /// Lamports delegated; rewards are added to it every epoch
+    pub delegated: u64,
+    pub staker: String,
+    pub withdrawer: String,
+    /// Rewards over the last `REWARD_EPOCHS` epochs, newest first
+    pub rewards: Vec<StakeReward>,
+}
+
+impl StakePosition {
+    pub fn recent_rewards(&self) -> u64 {
+        self.rewards.iter().map(|reward| reward.lamports).sum()
+    }
+
+    /// Deactivating needs the stake authority
+    pub fn can_deactivate(&self, authority: &str) -> bool {
+        self.phase == StakePhase::Active && self.staker == authority
+    }
+
+    /// Withdrawing needs the withdraw authority and stake that's fully cooled down
+    pub fn can_withdraw(&self, authority: &str) -> bool {
+        matches!(self.phase, StakePhase::Inactive | StakePhase::Undelegated)
+            && self.withdrawer == authority
+            && self.account.balance > 0
+    }
+}
+
+/// All stake accounts of `wallet_address` with their phase, validator and
+/// recent rewards, plus the current epoch
+pub async fn fetch_stake_positions(
+    wallet_address: &str,
+    rpc_url: Option<&str>,
+) -> Result<(u64, Vec<StakePosition>), StakingError> {
+    let epoch_info = get_epoch_info(rpc_url).await
+        .map_err(|e| StakingError::RpcError(format!("Failed to get epoch info: {}", e)))?;
+    let current_epoch = epoch_info.epoch;
+
+    // Stake created elsewhere (the CLI, other wallets) shows up by either
+    // authority; scans that the RPC refuses fall back on imported accounts
+    let mut found: Vec<(StakeAccountRpcData, StakeOrigin)> = Vec::new();
+    let mut scan_errors = Vec::new();
+    match get_stake_accounts_by_owner(wallet_address, rpc_url).await {
+        Ok(accounts) => found.extend(accounts.into_iter().map(|a| (a, StakeOrigin::Withdrawer))),
+        Err(e) => scan_errors.push(e),
+    }
+    match get_stake_accounts_by_staker(wallet_address, rpc_url).await {
+        Ok(accounts) => {
+            for account in accounts {
+                if !found.iter().any(|(known, _)| known.pubkey == account.pubkey) {
+                    found.push((account, StakeOrigin::Staker));
+                }
+            }
+        }
+        Err(e) => scan_errors.push(e),
+    }
+    let imported: Vec<String> = load_imported_stake_accounts(wallet_address)
+        .into_iter()
+        .filter(|address| !found.iter().any(|(known, _)| known.pubkey == *address))
+        .collect();
+    if !imported.is_empty() {
+        match get_stake_accounts(&imported, rpc_url).await {
+            Ok(accounts) => found.extend(accounts.into_iter().flatten().map(|a| (a, StakeOrigin::Imported))),
+            Err(e) => println!("⚠️  Failed to get imported stake accounts: {}", e),
+        }
+    }
+    if scan_errors.len() == 2 && found.is_empty() {
+        return Err(StakingError::RpcError(format!(
+            "Failed to get stake accounts: {}. If this RPC doesn't allow stake account scans, import them by address",
+            scan_errors[0]
+        )));
+    }
+
+    let mut positions = Vec::new();
+    for (rpc_account, origin) in &found {
+        let account = match convert_rpc_to_detailed_stake_account(rpc_account, current_epoch) {
+            Ok(account) => account,
+            Err(e) => {
+                println!("⚠️  Failed to convert stake account {}: {}", rpc_account.pubkey, e);
+                continue;
+            }
+        };
+        let info = &rpc_account.account.data.parsed.info;
+        let delegation = info.stake.as_ref().map(|stake| &stake.delegation);
+        positions.push(StakePosition {
+            phase: stake_phase(account.activation_epoch, account.deactivation_epoch, current_epoch),
+            origin: *origin,
+            voter: delegation.map(|d| d.voter.clone()),
+            delegated: delegation.and_then(|d| d.stake.parse().ok()).unwrap_or(0),
+            staker: info.meta.authorized.staker.clone(),
+            withdrawer: info.meta.authorized.withdrawer.clone(),
+            rewards: Vec::new(),
+            account,
+        });
+    }
+
+    // Rewards for the last few completed epochs, one request per epoch
+    let addresses: Vec<String> = positions.iter().map(|p| p.account.pubkey.to_string()).collect();
+    if !addresses.is_empty() {
+        let epochs: Vec<u64> = (1..=REWARD_EPOCHS)
+            .filter_map(|back| current_epoch.checked_sub(back))
+            .collect();
+        let results = futures_util::future::join_all(
+            epochs.iter().map(|epoch| get_inflation_rewards(&addresses, *epoch, rpc_url)),
+        )
+        .await;
+        for (epoch, result) in epochs.iter().zip(results) {
+            match result {
+                Ok(rewards) => {
+                    for (position, reward) in positions.iter_mut().zip(rewards) {
+                        if let Some(reward) = reward.filter(|r| r.amount > 0) {
+                            position.rewards.push(StakeReward { epoch: *epoch, lamports: reward.amount });
+                        }
+                    }
+                }
+                Err(e) => println!("⚠️  Failed to get rewards for epoch {}: {}", epoch, e),
+            }
+        }
+    }
+
+    println!("📈 Loaded {} stake positions at epoch {}", positions.len(), current_epoch);
+    Ok((current_epoch, positions))
+}
+
+/// Add a stake account to `wallet_address` by address. The wallet has to
+/// hold its stake or withdraw authority.
+pub async fn import_stake_account(wallet_address: &str, address: &str, rpc_url: Option<&str>) -> Result<(), StakingError> {
+    let address = address.trim();
+    Pubkey::from_str(address).map_err(|_| StakingError::InvalidAmount(format!("{} isn't a valid address", address)))?;
+    let account = get_stake_accounts(&[address.to_string()], rpc_url)
+        .await
+        .map_err(|e| StakingError::RpcError(format!("Failed to get the stake account: {}", e)))?
+        .into_iter()
+        .next()
+        .flatten()
+        .ok_or_else(|| StakingError::InvalidAmount(format!("{} isn't a stake account", address)))?;
+
+    let authorized = &account.account.data.parsed.info.meta.authorized;
+    if authority_origin(&authorized.staker, &authorized.withdrawer, wallet_address).is_none() {
+        return Err(StakingError::WalletError(format!(
+            "This wallet is neither the stake nor the withdraw authority of {}",
+            address
+        )));
+    }
+
+    let mut imported = load_imported_stake_accounts(wallet_address);
+    if !imported.iter().any(|known| known == address) {
+        imported.push(address.to_string());
+        save_imported_stake_accounts(wallet_address, &imported).map_err(StakingError::WalletError)?;
+    }
+    println!("📥 Imported stake account {} for {}", address, wallet_address);
+    Ok(())
+}
+
+/// Stop showing an imported stake account
+pub fn forget_stake_account(wallet_address: &str, address: &str) -> Result<(), StakingError> {
+    let mut imported = load_imported_stake_accounts(wallet_address);
+    imported.retain(|known| known != address);
+    save_imported_stake_accounts(wallet_address, &imported).map_err(StakingError::WalletError)
+}
+
+/// Least stake left on either side of a split, 0.01 SOL
+pub const MIN_SPLIT_LAMPORTS: u64 = 10_000_000;
+
+/// Whether the stake program merges `source` into `destination`: both need
+/// the same authorities, and the stake must either be undelegated or be
+/// delegated to the same validator in the same phase. Stake that's still
+/// cooling down can't be merged.
+pub fn merge_compatible(destination: &StakePosition, source: &StakePosition) -> bool {
+    if destination.account.pubkey == source.account.pubkey
+        || destination.staker != source.staker
+        || destination.withdrawer != source.withdrawer
+    {
+        return false;
+    }
+    let inactive = |phase: StakePhase| matches!(phase, StakePhase::Inactive | StakePhase::Undelegated);
+    match (destination.phase, source.phase) {
+        (a, b) if inactive(a) && inactive(b) => true,
+        (a, StakePhase::Activating) | (StakePhase::Activating, a) if inactive(a) => true,
+        (StakePhase::Activating, StakePhase::Activating) | (StakePhase::Active, StakePhase::Active) => {
+            destination.voter.is_some() && destination.voter == source.voter
+        }
+        _ => false,
+    }
+}
+
+/// Instructions splitting `lamports` of `stake_account` into the new account
+/// `new_stake_account`, which must sign. The new account is funded with its
+/// rent-exempt reserve first, as the stake program requires.
+pub fn build_split_instructions(
+    stake_account: &Pubkey,
+    authority: &Pubkey,
+    lamports: u64,
+    new_stake_account: &Pubkey,
+    rent_exempt_reserve: u64,
+) -> Vec<solana_sdk::instruction::Instruction> {
+    let mut instructions = vec![system_instruction::transfer(authority, new_stake_account, rent_exempt_reserve)];
+    instructions.extend(split(stake_account, authority, lamports, new_stake_account));
+    instructions
+}
+
+/// Check a split of `lamports` leaves enough stake on both sides
+pub fn validate_split(account: &DetailedStakeAccount, lamports: u64) -> Result<(), StakingError> {
+    let staked = account.balance.saturating_sub(account.rent_exempt_reserve);
+    if lamports < MIN_SPLIT_LAMPORTS {
+        return Err(StakingError::InvalidAmount("Split at least 0.01 SOL".to_string()));
+    }
+    if lamports > staked {
+        return Err(StakingError::InvalidAmount(format!(
+            "Only {} SOL can be split off (excluding rent)",
+            staked as f64 / 1_000_000_000.0
+        )));
+    }
+    let remaining = staked - lamports;
+    if remaining > 0 && remaining < MIN_SPLIT_LAMPORTS {
+        return Err(StakingError::InvalidAmount(
+            "Leave at least 0.01 SOL in the original account, or split it all".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn stake_signer(
+    wallet_info: Option<&WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+) -> Result<Box<dyn TransactionSigner>, StakingError> {
+    if let Some(hw) = hardware_wallet {
+        Ok(Box::new(HardwareSigner::from_wallet(hw)))
+    } else if let Some(w) = wallet_info {
+        let wallet = Wallet::from_wallet_info(w)
+            .map_err(|e| StakingError::WalletError(format!("Failed to create wallet: {}", e)))?;
+        Ok(Box::new(SoftwareSigner::new(wallet)))
+    } else {
+        Err(StakingError::WalletError("No wallet provided".to_string()))
+    }
+}
+
+impl StakingClient {
+    /// Add timeout protection, tips and a compute budget to `instructions`,
+    /// sign them with `signers` (the wallet first, paying the fee) and send
+    async fn send_stake_instructions(
+        &self,
+        mut instructions: Vec<solana_sdk::instruction::Instruction>,
+        signers: &[&dyn TransactionSigner],
+        authority: &Pubkey,
+    ) -> Result<String, StakingError> {
+        let current_slot = self.transaction_client.get_current_slot().await
+            .map_err(|e| StakingError::RpcError(format!("Failed to get current slot: {}", e)))?;
+        let timeout_ix = timeout::build_timeout_instruction_from_current(current_slot, timeout::DEFAULT_SLOT_WINDOW)
+            .map_err(|e| StakingError::TransactionFailed(format!("Failed to build timeout instruction: {}", e)))?;
+        instructions.insert(0, timeout_ix);
+
+        if get_current_jito_settings().jito_tx {
+            self.apply_jito_modifications(authority, &mut instructions)
+                .map_err(|e| StakingError::TransactionFailed(format!("Jito error: {}", e)))?;
+        }
+
+        let recent_blockhash = self.transaction_client.get_recent_blockhash().await
+            .map_err(|e| StakingError::RpcError(format!("Failed to get blockhash: {}", e)))?;
+        self.transaction_client.size_compute_budget(&mut instructions, authority).await;
+
+        let mut message = Message::new(&instructions, Some(authority));
+        message.recent_blockhash = recent_blockhash;
+        let mut transaction = VersionedTransaction {
+            signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
+            message: VersionedMessage::Legacy(message),
+        };
+        sign_all_slots(&mut transaction, signers).await
+            .map_err(|e| StakingError::WalletError(format!("Failed to sign transaction: {}", e)))?;
+
+        let serialized = bincode::serialize(&transaction)
+            .map_err(|e| StakingError::TransactionFailed(format!("Serialization failed: {}", e)))?;
+        self.send_staking_transaction(&bs58::encode(serialized).into_string()).await
+            .map_err(|e| StakingError::TransactionFailed(format!("Send failed: {}", e)))
+    }
+}
+
+/// Split `lamports` of a stake account into a new stake account with the
+/// same delegation. Returns the new account and the signature.
+pub async fn split_stake_account(
+    stake_account: &DetailedStakeAccount,
+    lamports: u64,
+    wallet_info: Option<&WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    rpc_url: Option<&str>,
+) -> Result<(Pubkey, String), StakingError> {
+    validate_split(stake_account, lamports)?;
+    let signer = stake_signer(wallet_info, hardware_wallet)?;
+    let authority = Pubkey::from_str(
+        &signer.get_public_key().await
+            .map_err(|e| StakingError::WalletError(format!("Failed to get public key: {}", e)))?,
+    )
+    .map_err(|_| StakingError::WalletError("Invalid wallet address".to_string()))?;
+
+    let rent_exempt_reserve = get_minimum_balance_for_rent_exemption(200, rpc_url)
+        .await
+        .map_err(|e| StakingError::RpcError(format!("Failed to get rent exemption: {}", e)))?;
+
+    let new_stake_keypair = Keypair::new();
+    let new_stake_account = new_stake_keypair.pubkey();
+    println!("✂️ Splitting {} lamports of {} into {}", lamports, stake_account.pubkey, new_stake_account);
+
+    let instructions = build_split_instructions(
+        &stake_account.pubkey,
+        &authority,
+        lamports,
+        &new_stake_account,
+        rent_exempt_reserve,
+    );
+    let new_stake_signer = KeypairSigner::new(new_stake_keypair);
+    let signature = StakingClient::new(rpc_url)
+        .send_stake_instructions(instructions, &[signer.as_ref(), &new_stake_signer], &authority)
+        .await?;
+    println!("✅ Split completed: {}", signature);
+    Ok((new_stake_account, signature))
+}
+
+/// Merge `source` into `destination`, closing `source`
+pub async fn merge_stake_pair(
+    destination: &StakePosition,
+    source: &StakePosition,
+    wallet_info: Option<&WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    rpc_url: Option<&str>,
+) -> Result<String, StakingError> {
+    if !merge_compatible(destination, source) {
+        return Err(StakingError::InvalidAmount(format!(
+            "{} ({}) can't be merged into {} ({})",
+            source.account.pubkey, source.phase, destination.account.pubkey, destination.phase
+        )));
+    }
+    let signer = stake_signer(wallet_info, hardware_wallet)?;
+    let authority = Pubkey::from_str(
+        &signer.get_public_key().await
+            .map_err(|e| StakingError::WalletError(format!("Failed to get public key: {}", e)))?,
+    )
+    .map_err(|_| StakingError::WalletError("Invalid wallet address".to_string()))?;
+
+    let instructions = merge(&destination.account.pubkey, &source.account.pubkey, &authority);
+    let signature = StakingClient::new(rpc_url)
+        .send_stake_instructions(instructions, &[signer.as_ref()], &authority)
+        .await?;
+    println!("✅ Merged {} into {}: {}", source.account.pubkey, destination.account.pubkey, signature);
+    Ok(signature)
+}
+
 /// Get stake account information
 pub async fn get_stake_account_info(
     _stake_account_pubkey: &Pubkey,
@@ -702,6 +1231,8 @@ pub async fn merge_stake_accounts(
     let recent_blockhash = staking_client.transaction_client.get_recent_blockhash().await
         .map_err(|e| StakingError::RpcError(format!("Failed to get blockhash: {}", e)))?;
 
+    staking_client.transaction_client.size_compute_budget(&mut instructions, &authority_pubkey).await;
+
     let mut message = Message::new(&instructions, Some(&authority_pubkey));
     message.recent_blockhash = recent_blockhash;
     
@@ -738,3 +1269,87 @@ pub async fn merge_stake_accounts(
     Ok(signature)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(phase: StakePhase, voter: Option<&str>) -> StakePosition {
+        StakePosition {
+            account: DetailedStakeAccount {
+                pubkey: Pubkey::new_unique(),
+                balance: 2_000_000_000,
+                rent_exempt_reserve: 2_282_880,
+                state: StakeAccountState::Delegated,
+                validator_name: String::new(),
+                activation_epoch: None,
+                deactivation_epoch: None,
+            },
+            phase,
+            origin: StakeOrigin::Withdrawer,
+            voter: voter.map(str::to_string),
+            delegated: 0,
+            staker: "owner".to_string(),
+            withdrawer: "owner".to_string(),
+            rewards: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_split_evenly() {
+        assert_eq!(split_evenly(10, 3), vec![4, 3, 3]);
+        assert_eq!(split_evenly(9, 3), vec![3, 3, 3]);
+        assert_eq!(split_evenly(5, 0), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_authority_origin() {
+        assert_eq!(authority_origin("owner", "owner", "owner"), Some(StakeOrigin::Withdrawer));
+        assert_eq!(authority_origin("other", "owner", "owner"), Some(StakeOrigin::Withdrawer));
+        assert_eq!(authority_origin("owner", "other", "owner"), Some(StakeOrigin::Staker));
+        assert_eq!(authority_origin("other", "other", "owner"), None);
+    }
+
+    #[test]
+    fn test_merge_compatible() {
+        let active = position(StakePhase::Active, Some("vote"));
+        assert!(merge_compatible(&active, &position(StakePhase::Active, Some("vote"))));
+        assert!(!merge_compatible(&active, &position(StakePhase::Active, Some("other"))));
+        assert!(!merge_compatible(&active, &active));
+        assert!(!merge_compatible(&active, &position(StakePhase::Deactivating, Some("vote"))));
+
+        let inactive = position(StakePhase::Inactive, Some("vote"));
+        assert!(merge_compatible(&inactive, &position(StakePhase::Undelegated, None)));
+        assert!(merge_compatible(&position(StakePhase::Activating, Some("vote")), &inactive));
+        assert!(merge_compatible(&inactive, &position(StakePhase::Activating, Some("other"))));
+        assert!(!merge_compatible(&active, &inactive));
+
+        let mut other_owner = position(StakePhase::Active, Some("vote"));
+        other_owner.withdrawer = "someone".to_string();
+        assert!(!merge_compatible(&active, &other_owner));
+    }
+
+    #[test]
+    fn test_split_validation_and_instructions() {
+        let account = position(StakePhase::Active, Some("vote")).account;
+        assert!(validate_split(&account, 1_000_000_000).is_ok());
+        assert!(validate_split(&account, account.balance - account.rent_exempt_reserve).is_ok());
+        assert!(validate_split(&account, 1_000).is_err());
+        assert!(validate_split(&account, account.balance - account.rent_exempt_reserve - 1_000).is_err());
+        assert!(validate_split(&account, account.balance).is_err());
+
+        let (authority, new_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let instructions = build_split_instructions(&account.pubkey, &authority, 1_000_000_000, &new_account, 2_282_880);
+        assert_eq!(instructions[0].program_id, solana_sdk::system_program::id());
+        assert_eq!(instructions.last().unwrap().program_id, solana_sdk::stake::program::id());
+    }
+
+    #[test]
+    fn test_stake_phase() {
+        assert_eq!(stake_phase(None, None, 600), StakePhase::Undelegated);
+        assert_eq!(stake_phase(Some(600), None, 600), StakePhase::Activating);
+        assert_eq!(stake_phase(Some(599), None, 600), StakePhase::Active);
+        assert_eq!(stake_phase(Some(500), Some(600), 600), StakePhase::Deactivating);
+        assert_eq!(stake_phase(Some(500), Some(599), 600), StakePhase::Inactive);
+    }
+}
@@ -0,0 +1,244 @@
+// src/staking_rewards.rs
+//! Staking rewards history, for tax reporting
+//!
+//! Rewards are paid to every stake account at the first block of each
+//! epoch. `getInflationReward` gives them per account and epoch; the block
+//! time of the payout slot dates them, and the SOL close of that day values
+//! them in USD and the selected currency.
+
+use crate::balance_history::historical_price;
+use crate::rpc::{get_block_time, get_epoch_info, get_inflation_rewards, get_stake_accounts_by_owner};
+use std::collections::HashMap;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Choices of how far back to look, in epochs (about two days each)
+pub const REWARD_HISTORY_EPOCHS: &[u64] = &[10, 45, 180];
+
+/// One reward paid to one stake account
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakingRewardEntry {
+    pub epoch: u64,
+    pub stake_account: String,
+    pub lamports: u64,
+    /// Stake account balance after the reward
+    pub post_balance: u64,
+    /// When the reward was paid, if the node still has the block
+    pub paid_at: Option<i64>,
+    /// SOL close on the day it was paid
+    pub sol_usd: Option<f64>,
+}
+
+impl StakingRewardEntry {
+    pub fn sol(&self) -> f64 {
+        self.lamports as f64 / LAMPORTS_PER_SOL
+    }
+
+    pub fn usd(&self) -> Option<f64> {
+        self.sol_usd.map(|price| self.sol() * price)
+    }
+}
+
+/// Total paid in one epoch across the wallet's stake accounts
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpochRewards {
+    pub epoch: u64,
+    pub paid_at: Option<i64>,
+    pub lamports: u64,
+    pub usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewardsHistory {
+    pub wallet: String,
+    /// Oldest first
+    pub entries: Vec<StakingRewardEntry>,
+    /// Epochs the RPC couldn't return rewards for
+    pub unavailable_epochs: Vec<u64>,
+}
+
+fn format_date(timestamp: Option<i64>) -> String {
+    timestamp
+        .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+        .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+impl RewardsHistory {
+    pub fn total_lamports(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.lamports).sum()
+    }
+
+    /// USD value of the rewards that have a price
+    pub fn total_usd(&self) -> f64 {
+        self.entries.iter().filter_map(StakingRewardEntry::usd).sum()
+    }
+
+    /// Whether some rewards couldn't be priced
+    pub fn missing_prices(&self) -> bool {
+        self.entries.iter().any(|entry| entry.sol_usd.is_none())
+    }
+
+    /// Rewards summed per epoch, newest first
+    pub fn by_epoch(&self) -> Vec<EpochRewards> {
+        let mut epochs: Vec<EpochRewards> = Vec::new();
+        for entry in &self.entries {
+            match epochs.iter_mut().find(|e| e.epoch == entry.epoch) {
+                Some(epoch) => {
+                    epoch.lamports += entry.lamports;
+                    epoch.usd = match (epoch.usd, entry.usd()) {
+                        (Some(total), Some(usd)) => Some(total + usd),
+                        (total, usd) => total.or(usd),
+                    };
+                }
+                None => epochs.push(EpochRewards {
+                    epoch: entry.epoch,
+                    paid_at: entry.paid_at,
+                    lamports: entry.lamports,
+                    usd: entry.usd(),
+                }),
+            }
+        }
+        epochs.sort_by(|a, b| b.epoch.cmp(&a.epoch));
+        epochs
+    }
+
+    /// One row per reward; `usd_rate` converts USD into `currency`
+    pub fn to_csv(&self, currency: &str, usd_rate: f64) -> String {
+        let currency = currency.to_lowercase();
+        let mut rows = vec![format!(
+            "epoch,date_utc,stake_account,reward_sol,post_balance_sol,sol_price_usd,value_usd,value_{}",
+            currency
+        )];
+        for entry in &self.entries {
+            let optional = |value: Option<f64>| value.map(|v| format!("{:.2}", v)).unwrap_or_default();
+            rows.push(format!(
+                "{},{},{},{:.9},{:.9},{},{},{}",
+                entry.epoch,
+                format_date(entry.paid_at),
+                entry.stake_account,
+                entry.sol(),
+                entry.post_balance as f64 / LAMPORTS_PER_SOL,
+                optional(entry.sol_usd),
+                optional(entry.usd()),
+                optional(entry.usd().map(|usd| usd * usd_rate))
+            ));
+        }
+        rows.join("\n")
+    }
+}
+
+/// Rewards paid to the wallet's current stake accounts over the last
+/// `epochs` completed epochs
+pub async fn fetch_rewards_history(wallet: &str, epochs: u64, rpc_url: Option<&str>) -> Result<RewardsHistory, String> {
+    let current_epoch = get_epoch_info(rpc_url).await?.epoch;
+    let accounts: Vec<String> = get_stake_accounts_by_owner(wallet, rpc_url)
+        .await?
+        .into_iter()
+        .map(|account| account.pubkey)
+        .collect();
+
+    let mut history = RewardsHistory { wallet: wallet.to_string(), entries: Vec::new(), unavailable_epochs: Vec::new() };
+    if accounts.is_empty() {
+        return Ok(history);
+    }
+
+    let first_epoch = current_epoch.saturating_sub(epochs);
+    let mut prices: HashMap<String, Option<f64>> = HashMap::new();
+    for epoch in first_epoch..current_epoch {
+        let rewards = match get_inflation_rewards(&accounts, epoch, rpc_url).await {
+            Ok(rewards) => rewards,
+            Err(e) => {
+                println!("⚠️ No inflation rewards for epoch {}: {}", epoch, e);
+                history.unavailable_epochs.push(epoch);
+                continue;
+            }
+        };
+        let paid: Vec<(&String, _)> = accounts
+            .iter()
+            .zip(rewards)
+            .filter_map(|(account, reward)| reward.filter(|r| r.amount > 0).map(|r| (account, r)))
+            .collect();
+        let Some((_, first)) = paid.first() else { continue };
+
+        // Every account's reward lands in the same slot
+        let paid_at = match get_block_time(first.effective_slot, rpc_url).await {
+            Ok(time) => time,
+            Err(e) => {
+                println!("⚠️ No block time for epoch {}: {}", epoch, e);
+                None
+            }
+        };
+        let sol_usd = match paid_at {
+            Some(time) => {
+                let day: String = format_date(Some(time)).chars().take(10).collect();
+                match prices.get(&day) {
+                    Some(price) => *price,
+                    None => {
+                        let price = historical_price("SOL", time).await;
+                        prices.insert(day, price);
+                        price
+                    }
+                }
+            }
+            None => None,
+        };
+
+        for (account, reward) in paid {
+            history.entries.push(StakingRewardEntry {
+                epoch,
+                stake_account: account.clone(),
+                lamports: reward.amount,
+                post_balance: reward.post_balance,
+                paid_at,
+                sol_usd,
+            });
+        }
+    }
+
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(epoch: u64, account: &str, lamports: u64, sol_usd: Option<f64>) -> StakingRewardEntry {
+        StakingRewardEntry {
+            epoch,
+            stake_account: account.to_string(),
+            lamports,
+            post_balance: 10_000_000_000 + lamports,
+            paid_at: Some(1_700_000_000),
+            sol_usd,
+        }
+    }
+
+    #[test]
+    fn test_rewards_history() {
+        let history = RewardsHistory {
+            wallet: "wallet".to_string(),
+            entries: vec![
+                entry(500, "a", 1_000_000, Some(100.0)),
+                entry(500, "b", 2_000_000, Some(100.0)),
+                entry(501, "a", 1_500_000, None),
+            ],
+            unavailable_epochs: Vec::new(),
+        };
+        assert_eq!(history.total_lamports(), 4_500_000);
+        assert!((history.total_usd() - 0.3).abs() < 1e-9);
+        assert!(history.missing_prices());
+
+        let epochs = history.by_epoch();
+        assert_eq!(epochs.len(), 2);
+        assert_eq!(epochs[0].epoch, 501);
+        assert_eq!(epochs[0].usd, None);
+        assert_eq!(epochs[1].lamports, 3_000_000);
+
+        let csv = history.to_csv("EUR", 0.5);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "epoch,date_utc,stake_account,reward_sol,post_balance_sol,sol_price_usd,value_usd,value_eur");
+        assert_eq!(lines[1], "500,2023-11-14 22:13:20,a,0.001000000,10.001000000,100.00,0.10,0.05");
+        assert_eq!(lines[3], "501,2023-11-14 22:13:20,a,0.001500000,10.001500000,,,");
+    }
+}
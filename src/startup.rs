@@ -0,0 +1,115 @@
+// src/startup.rs
+//! Startup timeline
+//!
+//! Records how long each part of startup takes, measured from `main`, and
+//! reports the timeline once the wallet view has painted its first frame.
+//! Work that isn't needed for that frame (exchange rates, price polling,
+//! background loops) waits for `after_first_frame` instead of running eagerly.
+
+use dioxus::prelude::*;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// First render should land within this on mid-range hardware
+pub const FIRST_RENDER_TARGET: Duration = Duration::from_secs(1);
+
+static TIMELINE: OnceLock<Mutex<Timeline>> = OnceLock::new();
+
+#[derive(Debug)]
+struct Timeline {
+    started: Instant,
+    spans: Vec<(&'static str, Duration, Duration)>,
+    first_frame: Option<Duration>,
+}
+
+impl Timeline {
+    fn new(started: Instant) -> Self {
+        Self { started, spans: Vec::new(), first_frame: None }
+    }
+
+    fn report(&self) -> String {
+        let mut lines = Vec::new();
+        for (name, start, length) in &self.spans {
+            lines.push(format!("  {:>6} ms  +{:>5} ms  {}", start.as_millis(), length.as_millis(), name));
+        }
+        if let Some(first_frame) = self.first_frame {
+            lines.push(format!("  {:>6} ms  first frame", first_frame.as_millis()));
+        }
+        lines.join("\n")
+    }
+}
+
+fn timeline() -> &'static Mutex<Timeline> {
+    TIMELINE.get_or_init(|| Mutex::new(Timeline::new(Instant::now())))
+}
+
+/// Start the clock; call first thing in `main`
+pub fn begin() {
+    let _ = timeline();
+}
+
+/// Time since `begin`
+pub fn elapsed() -> Duration {
+    timeline().lock().map(|t| t.started.elapsed()).unwrap_or_default()
+}
+
+/// A timed part of startup, recorded when dropped
+pub struct StartupSpan {
+    name: &'static str,
+    start: Duration,
+}
+
+pub fn span(name: &'static str) -> StartupSpan {
+    StartupSpan { name, start: elapsed() }
+}
+
+impl Drop for StartupSpan {
+    fn drop(&mut self) {
+        let end = elapsed();
+        if let Ok(mut timeline) = timeline().lock() {
+            timeline.spans.push((self.name, self.start, end.saturating_sub(self.start)));
+        }
+    }
+}
+
+/// Record the first frame and print the timeline. Later calls do nothing.
+pub fn mark_first_frame() {
+    let Ok(mut timeline) = timeline().lock() else { return };
+    if timeline.first_frame.is_some() {
+        return;
+    }
+    let first_frame = timeline.started.elapsed();
+    timeline.first_frame = Some(first_frame);
+    println!("⏱️ Startup timeline:\n{}", timeline.report());
+    if first_frame > FIRST_RENDER_TARGET {
+        println!(
+            "⚠️ First frame took {} ms (target {} ms)",
+            first_frame.as_millis(),
+            FIRST_RENDER_TARGET.as_millis()
+        );
+    }
+}
+
+/// Resolves once the webview has painted the current frame
+pub async fn after_first_frame() {
+    let mut frame = document::eval(r#"
+        requestAnimationFrame(() => requestAnimationFrame(() => dioxus.send(true)));
+    "#);
+    let _ = frame.recv::<bool>().await;
+    mark_first_frame();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_report() {
+        let mut timeline = Timeline::new(Instant::now());
+        timeline.spans.push(("load wallets", Duration::from_millis(3), Duration::from_millis(12)));
+        timeline.first_frame = Some(Duration::from_millis(420));
+        let report = timeline.report();
+        assert!(report.contains("+   12 ms  load wallets"));
+        assert!(report.ends_with("420 ms  first frame"));
+    }
+}
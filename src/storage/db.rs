@@ -0,0 +1,534 @@
+// src/storage/db.rs
+//! Embedded SQLite database for the datasets that grow with use
+//!
+//! Transaction history, parsed transaction details, price caches and address
+//! names are updated a few rows at a time, which used to rewrite whole JSON
+//! files. They live in `unruggable.db` next to the other files instead. JSON
+//! files written by earlier versions are imported the first time the
+//! database is opened and then removed. The web build keeps localStorage.
+
+use crate::balance_history::HistoricalPrice;
+use crate::history::{CachedTransaction, HistoryIndex};
+use crate::prices::CandlestickData;
+use crate::rpc::TransactionHistoryItem;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+const DB_FILE: &str = "unruggable.db";
+/// Bumped with every schema change; stored as the database's `user_version`
+const SCHEMA_VERSION: i32 = 1;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS history_indexes (
+        address TEXT PRIMARY KEY,
+        reached_start INTEGER NOT NULL,
+        synced_at INTEGER,
+        categories TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS history_entries (
+        address TEXT NOT NULL,
+        signature TEXT NOT NULL,
+        seq INTEGER NOT NULL,
+        slot INTEGER NOT NULL,
+        block_time INTEGER,
+        confirmation_status TEXT,
+        err TEXT,
+        memo TEXT,
+        PRIMARY KEY (address, signature)
+    );
+    CREATE TABLE IF NOT EXISTS cached_transactions (
+        address TEXT NOT NULL,
+        signature TEXT NOT NULL,
+        seq INTEGER NOT NULL,
+        details TEXT NOT NULL,
+        PRIMARY KEY (address, signature)
+    );
+    CREATE TABLE IF NOT EXISTS historical_prices (
+        symbol TEXT NOT NULL,
+        day TEXT NOT NULL,
+        usd REAL NOT NULL,
+        PRIMARY KEY (symbol, day)
+    );
+    CREATE TABLE IF NOT EXISTS candles (
+        symbol TEXT NOT NULL,
+        timeframe TEXT NOT NULL,
+        timestamp INTEGER NOT NULL,
+        open REAL NOT NULL,
+        high REAL NOT NULL,
+        low REAL NOT NULL,
+        close REAL NOT NULL,
+        volume REAL,
+        PRIMARY KEY (symbol, timeframe, timestamp)
+    );
+    CREATE TABLE IF NOT EXISTS address_labels (
+        address TEXT PRIMARY KEY,
+        label TEXT NOT NULL
+    );
+";
+
+static DB: OnceLock<Option<Mutex<Connection>>> = OnceLock::new();
+
+fn db() -> Option<&'static Mutex<Connection>> {
+    DB.get_or_init(|| match open(&super::get_storage_dir_simple()) {
+        Ok(conn) => Some(Mutex::new(conn)),
+        Err(e) => {
+            log::error!("❌ Database unavailable, keeping JSON files: {}", e);
+            None
+        }
+    })
+    .as_ref()
+}
+
+/// Whether the database opened; when it didn't, callers use JSON files
+pub fn is_available() -> bool {
+    db().is_some()
+}
+
+fn with_db<T>(f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let db = db().ok_or_else(|| "Database unavailable".to_string())?;
+    let mut conn = db.lock().map_err(|_| "Database lock poisoned".to_string())?;
+    f(&mut conn).map_err(|e| format!("Database error: {}", e))
+}
+
+fn open(dir: &str) -> Result<Connection, String> {
+    super::ensure_storage_dir().map_err(|e| format!("Failed to ensure storage directory: {}", e))?;
+    let path = format!("{}/{}", dir, DB_FILE);
+    let mut conn = Connection::open(&path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    migrate(&mut conn).map_err(|e| format!("Failed to migrate {}: {}", path, e))?;
+    import_json_files(&mut conn, dir);
+    log::info!("✅ Opened database {}", path);
+    Ok(conn)
+}
+
+fn migrate(conn: &mut Connection) -> rusqlite::Result<()> {
+    let version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version < SCHEMA_VERSION {
+        conn.execute_batch(SCHEMA)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    }
+    Ok(())
+}
+
+/// Move JSON datasets of earlier versions into the database, removing each
+/// file once its rows are in
+fn import_json_files(conn: &mut Connection, dir: &str) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(key) = file_name.strip_suffix(".json") else { continue };
+        let Ok(data) = std::fs::read_to_string(entry.path()) else { continue };
+        let imported = if key == "historical_prices" {
+            serde_json::from_str::<Vec<HistoricalPrice>>(&data)
+                .map_err(|e| e.to_string())
+                .and_then(|prices| {
+                    prices.iter().try_for_each(|p| write_historical_price(conn, p)).map_err(|e| e.to_string())
+                })
+        } else if key == "address_labels" {
+            serde_json::from_str::<HashMap<String, String>>(&data)
+                .map_err(|e| e.to_string())
+                .and_then(|labels| write_address_labels(conn, &labels).map_err(|e| e.to_string()))
+        } else if let Some(address) = key.strip_prefix("history_details_") {
+            serde_json::from_str::<Vec<CachedTransaction>>(&data)
+                .map_err(|e| e.to_string())
+                .and_then(|cached| write_cached_transactions(conn, address, &cached).map_err(|e| e.to_string()))
+        } else if let Some(address) = key.strip_prefix("history_") {
+            serde_json::from_str::<HistoryIndex>(&data)
+                .map_err(|e| e.to_string())
+                .and_then(|index| {
+                    let index = HistoryIndex { address: address.to_string(), ..index };
+                    write_history_index(conn, &index).map_err(|e| e.to_string())
+                })
+        } else if let Some((symbol, timeframe)) = key.strip_prefix("candles_").and_then(|rest| rest.rsplit_once('_')) {
+            serde_json::from_str::<Vec<CandlestickData>>(&data)
+                .map_err(|e| e.to_string())
+                .and_then(|candles| write_candles(conn, symbol, timeframe, &candles).map_err(|e| e.to_string()))
+        } else {
+            continue;
+        };
+        match imported {
+            Ok(()) => {
+                let _ = std::fs::remove_file(entry.path());
+                log::info!("📦 Moved {} into the database", file_name);
+            }
+            Err(e) => log::error!("❌ Failed to import {}: {}", file_name, e),
+        }
+    }
+}
+
+fn read_history_index(conn: &Connection, address: &str) -> rusqlite::Result<Option<HistoryIndex>> {
+    let header = conn
+        .query_row(
+            "SELECT reached_start, synced_at, categories FROM history_indexes WHERE address = ?1",
+            params![address],
+            |row| Ok((row.get::<_, bool>(0)?, row.get::<_, Option<i64>>(1)?, row.get::<_, String>(2)?)),
+        )
+        .optional()?;
+    let Some((reached_start, synced_at, categories)) = header else { return Ok(None) };
+    let mut statement = conn.prepare(
+        "SELECT signature, slot, block_time, confirmation_status, err, memo
+         FROM history_entries WHERE address = ?1 ORDER BY seq DESC",
+    )?;
+    let entries = statement
+        .query_map(params![address], |row| {
+            Ok(TransactionHistoryItem {
+                signature: row.get(0)?,
+                slot: row.get::<_, i64>(1)? as u64,
+                block_time: row.get(2)?,
+                confirmation_status: row.get(3)?,
+                err: row.get::<_, Option<String>>(4)?.and_then(|err| serde_json::from_str(&err).ok()),
+                memo: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(Some(HistoryIndex {
+        address: address.to_string(),
+        entries,
+        reached_start,
+        synced_at,
+        categories: serde_json::from_str(&categories).unwrap_or_default(),
+    }))
+}
+
+/// Ordering keys of the rows an address has in `table`, by signature
+fn stored_seqs(conn: &Connection, table: &str, address: &str) -> rusqlite::Result<HashMap<String, i64>> {
+    let mut statement = conn.prepare(&format!("SELECT signature, seq FROM {} WHERE address = ?1", table))?;
+    let seqs = statement.query_map(params![address], |row| Ok((row.get(0)?, row.get(1)?)))?.collect();
+    seqs
+}
+
+/// Ordering keys for `signatures` (newest first), higher for newer. Stored
+/// rows keep theirs, so new rows before or after them are written without
+/// renumbering the rest.
+fn seqs_for(signatures: &[&str], stored: &HashMap<String, i64>) -> Vec<i64> {
+    let Some(first_stored) = signatures.iter().position(|s| stored.contains_key(*s)) else {
+        return (0..signatures.len() as i64).rev().collect();
+    };
+    let mut next = stored[signatures[first_stored]] + first_stored as i64;
+    signatures
+        .iter()
+        .map(|signature| {
+            let seq = stored.get(*signature).copied().unwrap_or(next);
+            next = seq - 1;
+            seq
+        })
+        .collect()
+}
+
+/// Delete an address's rows in `table` whose signature is no longer listed
+fn delete_unlisted(
+    conn: &Connection,
+    table: &str,
+    address: &str,
+    stored: &HashMap<String, i64>,
+    listed: &[&str],
+) -> rusqlite::Result<()> {
+    let listed: std::collections::HashSet<&str> = listed.iter().copied().collect();
+    let mut delete = conn.prepare(&format!("DELETE FROM {} WHERE address = ?1 AND signature = ?2", table))?;
+    for signature in stored.keys().filter(|s| !listed.contains(s.as_str())) {
+        delete.execute(params![address, signature])?;
+    }
+    Ok(())
+}
+
+fn write_history_index(conn: &mut Connection, index: &HistoryIndex) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT OR REPLACE INTO history_indexes (address, reached_start, synced_at, categories) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            index.address,
+            index.reached_start,
+            index.synced_at,
+            serde_json::to_string(&index.categories).unwrap_or_default()
+        ],
+    )?;
+    let stored = stored_seqs(&tx, "history_entries", &index.address)?;
+    let signatures: Vec<&str> = index.entries.iter().map(|e| e.signature.as_str()).collect();
+    delete_unlisted(&tx, "history_entries", &index.address, &stored, &signatures)?;
+    {
+        let mut upsert = tx.prepare(
+            "INSERT INTO history_entries (address, signature, seq, slot, block_time, confirmation_status, err, memo)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(address, signature) DO UPDATE SET
+                 slot = excluded.slot,
+                 block_time = excluded.block_time,
+                 confirmation_status = excluded.confirmation_status,
+                 err = excluded.err,
+                 memo = excluded.memo",
+        )?;
+        for (entry, seq) in index.entries.iter().zip(seqs_for(&signatures, &stored)) {
+            upsert.execute(params![
+                index.address,
+                entry.signature,
+                seq,
+                entry.slot as i64,
+                entry.block_time,
+                entry.confirmation_status,
+                entry.err.as_ref().map(|err| err.to_string()),
+                entry.memo,
+            ])?;
+        }
+    }
+    tx.commit()
+}
+
+fn read_cached_transactions(conn: &Connection, address: &str) -> rusqlite::Result<Vec<CachedTransaction>> {
+    let mut statement =
+        conn.prepare("SELECT signature, details FROM cached_transactions WHERE address = ?1 ORDER BY seq DESC")?;
+    let cached = statement
+        .query_map(params![address], |row| {
+            Ok(CachedTransaction {
+                signature: row.get(0)?,
+                details: serde_json::from_str(&row.get::<_, String>(1)?).unwrap_or_default(),
+            })
+        })?
+        .collect();
+    cached
+}
+
+/// Details of a signature never change, so only new transactions are
+/// inserted and ones dropped from the list deleted
+fn write_cached_transactions(conn: &mut Connection, address: &str, cached: &[CachedTransaction]) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    let stored = stored_seqs(&tx, "cached_transactions", address)?;
+    let signatures: Vec<&str> = cached.iter().map(|c| c.signature.as_str()).collect();
+    delete_unlisted(&tx, "cached_transactions", address, &stored, &signatures)?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO cached_transactions (address, signature, seq, details) VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for (transaction, seq) in cached.iter().zip(seqs_for(&signatures, &stored)) {
+            if stored.contains_key(&transaction.signature) {
+                continue;
+            }
+            insert.execute(params![
+                address,
+                transaction.signature,
+                seq,
+                serde_json::to_string(&transaction.details).unwrap_or_default(),
+            ])?;
+        }
+    }
+    tx.commit()
+}
+
+fn read_historical_price(conn: &Connection, symbol: &str, day: &str) -> rusqlite::Result<Option<f64>> {
+    conn.query_row(
+        "SELECT usd FROM historical_prices WHERE symbol = ?1 AND day = ?2",
+        params![symbol, day],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+fn write_historical_price(conn: &Connection, price: &HistoricalPrice) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO historical_prices (symbol, day, usd) VALUES (?1, ?2, ?3)",
+        params![price.symbol, price.day, price.usd],
+    )?;
+    Ok(())
+}
+
+fn read_candles(conn: &Connection, symbol: &str, timeframe: &str) -> rusqlite::Result<Vec<CandlestickData>> {
+    let mut statement = conn.prepare(
+        "SELECT timestamp, open, high, low, close, volume FROM candles
+         WHERE symbol = ?1 AND timeframe = ?2 ORDER BY timestamp",
+    )?;
+    let candles = statement
+        .query_map(params![symbol, timeframe], |row| {
+            Ok(CandlestickData {
+                timestamp: row.get(0)?,
+                open: row.get(1)?,
+                high: row.get(2)?,
+                low: row.get(3)?,
+                close: row.get(4)?,
+                volume: row.get(5)?,
+            })
+        })?
+        .collect();
+    candles
+}
+
+fn write_candles(conn: &mut Connection, symbol: &str, timeframe: &str, candles: &[CandlestickData]) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM candles WHERE symbol = ?1 AND timeframe = ?2", params![symbol, timeframe])?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT OR REPLACE INTO candles (symbol, timeframe, timestamp, open, high, low, close, volume)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for candle in candles {
+            insert.execute(params![
+                symbol,
+                timeframe,
+                candle.timestamp,
+                candle.open,
+                candle.high,
+                candle.low,
+                candle.close,
+                candle.volume,
+            ])?;
+        }
+    }
+    tx.commit()
+}
+
+fn read_address_labels(conn: &Connection) -> rusqlite::Result<HashMap<String, String>> {
+    let mut statement = conn.prepare("SELECT address, label FROM address_labels")?;
+    let labels = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect();
+    labels
+}
+
+fn write_address_labels(conn: &mut Connection, labels: &HashMap<String, String>) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM address_labels", [])?;
+    {
+        let mut insert = tx.prepare("INSERT INTO address_labels (address, label) VALUES (?1, ?2)")?;
+        for (address, label) in labels {
+            insert.execute(params![address, label])?;
+        }
+    }
+    tx.commit()
+}
+
+pub fn history_index(address: &str) -> Result<Option<HistoryIndex>, String> {
+    with_db(|conn| read_history_index(conn, address))
+}
+
+pub fn save_history_index(index: &HistoryIndex) -> Result<(), String> {
+    with_db(|conn| write_history_index(conn, index))
+}
+
+pub fn cached_transactions(address: &str) -> Result<Vec<CachedTransaction>, String> {
+    with_db(|conn| read_cached_transactions(conn, address))
+}
+
+pub fn save_cached_transactions(address: &str, cached: &[CachedTransaction]) -> Result<(), String> {
+    with_db(|conn| write_cached_transactions(conn, address, cached))
+}
+
+pub fn historical_price(symbol: &str, day: &str) -> Result<Option<f64>, String> {
+    with_db(|conn| read_historical_price(conn, symbol, day))
+}
+
+pub fn save_historical_price(price: &HistoricalPrice) -> Result<(), String> {
+    with_db(|conn| write_historical_price(conn, price))
+}
+
+pub fn candles(symbol: &str, timeframe: &str) -> Result<Vec<CandlestickData>, String> {
+    with_db(|conn| read_candles(conn, symbol, timeframe))
+}
+
+pub fn save_candles(symbol: &str, timeframe: &str, candles: &[CandlestickData]) -> Result<(), String> {
+    with_db(|conn| write_candles(conn, symbol, timeframe, candles))
+}
+
+pub fn address_labels() -> Result<HashMap<String, String>, String> {
+    with_db(|conn| read_address_labels(conn))
+}
+
+pub fn save_address_labels(labels: &HashMap<String, String>) -> Result<(), String> {
+    with_db(|conn| write_address_labels(conn, labels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_db() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let mut conn = memory_db();
+        assert_eq!(read_history_index(&conn, "addr").unwrap().map(|i| i.entries.len()), None);
+
+        let item = |signature: &str, err: Option<serde_json::Value>| TransactionHistoryItem {
+            signature: signature.to_string(),
+            slot: 7,
+            block_time: Some(1_700_000_000),
+            confirmation_status: Some("finalized".to_string()),
+            err,
+            memo: None,
+        };
+        let mut index = HistoryIndex {
+            address: "addr".to_string(),
+            entries: vec![item("b", None), item("a", Some(serde_json::json!({ "InstructionError": [0, "Custom"] })))],
+            reached_start: true,
+            synced_at: Some(1),
+            ..Default::default()
+        };
+        index.categories.insert("a".to_string(), "Rent".to_string());
+        write_history_index(&mut conn, &index).unwrap();
+        index.entries.truncate(1);
+        write_history_index(&mut conn, &index).unwrap();
+
+        let read = read_history_index(&conn, "addr").unwrap().unwrap();
+        assert_eq!(read.entries.len(), 1);
+        assert_eq!(read.entries[0].signature, "b");
+        assert!(read.reached_start);
+        assert_eq!(read.category("a"), Some("Rent"));
+    }
+
+    #[test]
+    fn test_history_grows_in_place() {
+        let mut conn = memory_db();
+        let item = |signature: &str| TransactionHistoryItem {
+            signature: signature.to_string(),
+            slot: 7,
+            block_time: None,
+            confirmation_status: None,
+            err: None,
+            memo: None,
+        };
+        let signatures = |conn: &Connection| -> Vec<String> {
+            read_history_index(conn, "addr").unwrap().unwrap().entries.into_iter().map(|e| e.signature).collect()
+        };
+        let mut index = HistoryIndex { address: "addr".to_string(), entries: vec![item("b"), item("a")], ..Default::default() };
+        write_history_index(&mut conn, &index).unwrap();
+        let seq_of_b = stored_seqs(&conn, "history_entries", "addr").unwrap()["b"];
+
+        // Newer entries go in front and older ones behind, stored rows keep their keys
+        index.entries.splice(0..0, [item("d"), item("c")]);
+        index.entries.push(item("z"));
+        write_history_index(&mut conn, &index).unwrap();
+        assert_eq!(signatures(&conn), vec!["d", "c", "b", "a", "z"]);
+        assert_eq!(stored_seqs(&conn, "history_entries", "addr").unwrap()["b"], seq_of_b);
+
+        index.entries = vec![item("e")];
+        write_history_index(&mut conn, &index).unwrap();
+        assert_eq!(signatures(&conn), vec!["e"]);
+    }
+
+    #[test]
+    fn test_cached_transactions() {
+        let mut conn = memory_db();
+        let cached = |signature: &str| CachedTransaction { signature: signature.to_string(), details: HashMap::new() };
+        let signatures = |conn: &Connection| -> Vec<String> {
+            read_cached_transactions(conn, "addr").unwrap().into_iter().map(|c| c.signature).collect()
+        };
+        write_cached_transactions(&mut conn, "addr", &[cached("x")]).unwrap();
+        write_cached_transactions(&mut conn, "addr", &[cached("y"), cached("x")]).unwrap();
+        assert_eq!(signatures(&conn), vec!["y", "x"]);
+        write_cached_transactions(&mut conn, "addr", &[cached("z"), cached("y")]).unwrap();
+        assert_eq!(signatures(&conn), vec!["z", "y"]);
+    }
+
+    #[test]
+    fn test_price_caches() {
+        let mut conn = memory_db();
+        let price = HistoricalPrice { symbol: "SOL".to_string(), day: "2024-01-01".to_string(), usd: 100.0 };
+        write_historical_price(&conn, &price).unwrap();
+        write_historical_price(&conn, &HistoricalPrice { usd: 101.0, ..price }).unwrap();
+        assert_eq!(read_historical_price(&conn, "SOL", "2024-01-01").unwrap(), Some(101.0));
+        assert_eq!(read_historical_price(&conn, "SOL", "2024-01-02").unwrap(), None);
+
+        let candle = |timestamp: i64| CandlestickData { timestamp, open: 1.0, high: 2.0, low: 0.5, close: 1.5, volume: None };
+        write_candles(&mut conn, "SOL", "1D", &[candle(2), candle(1)]).unwrap();
+        assert_eq!(read_candles(&conn, "SOL", "1D").unwrap(), vec![candle(1), candle(2)]);
+        assert!(read_candles(&conn, "SOL", "1H").unwrap().is_empty());
+    }
+}
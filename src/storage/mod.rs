@@ -0,0 +1,2640 @@
+use crate::wallet::{Wallet, WalletInfo};
+use crate::quantum_vault::StoredVault;
+use crate::config::network::Network;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub mod secrets;
+#[cfg(not(feature = "web"))]
+mod db;
+
+// Android-specific imports
+#[cfg(target_os = "android")]
+use std::path::PathBuf;
+
+// Custom error type that implements Send
+#[derive(Debug, Clone)]
+pub struct StorageError(String);
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<String> for StorageError {
+    fn from(s: String) -> Self {
+        StorageError(s)
+    }
+}
+
+impl From<&str> for StorageError {
+    fn from(s: &str) -> Self {
+        StorageError(s.to_string())
+    }
+}
+
+#[cfg(target_os = "android")]
+impl From<jni::errors::Error> for StorageError {
+    fn from(e: jni::errors::Error) -> Self {
+        StorageError(format!("JNI Error: {}", e))
+    }
+}
+
+// Android-specific function to get the proper files directory
+#[cfg(target_os = "android")]
+fn get_android_files_dir() -> Result<String, StorageError> {
+    use dioxus::mobile::wry::prelude::dispatch;
+    use jni::objects::{JObject, JString};
+    use jni::JNIEnv;
+    
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    fn run(env: &mut JNIEnv<'_>, activity: &JObject<'_>) -> Result<String, StorageError> {
+        // Get the files directory (internal storage)
+        let files_dir = env
+            .call_method(activity, "getFilesDir", "()Ljava/io/File;", &[])?
+            .l()?;
+        
+        // Get the absolute path
+        let files_dir_path: JString<'_> = env
+            .call_method(files_dir, "getAbsolutePath", "()Ljava/lang/String;", &[])?
+            .l()?
+            .into();
+        
+        // Convert to Rust string
+        let files_dir_str: String = env.get_string(&files_dir_path)?.into();
+        
+        Ok(files_dir_str)
+    }
+
+    dispatch(move |env, activity, _webview| {
+        let result = run(env, activity);
+        tx.send(result).unwrap();
+    });
+
+    match rx.recv() {
+        Ok(result) => result,
+        Err(e) => Err(StorageError::from(format!("Channel receive error: {}", e))),
+    }
+}
+
+// Use OnceLock instead of lazy_static for Android
+#[cfg(target_os = "android")]
+fn get_android_files_dir_cached() -> &'static Option<String> {
+    use std::sync::OnceLock;
+    static ANDROID_FILES_DIR: OnceLock<Option<String>> = OnceLock::new();
+    ANDROID_FILES_DIR.get_or_init(|| {
+        match get_android_files_dir() {
+            Ok(dir) => {
+                log::info!("✅ Android files directory initialized: {}", dir);
+                Some(dir)
+            }
+            Err(e) => {
+                log::error!("❌ Failed to initialize Android files directory: {}", e);
+                None
+            }
+        }
+    })
+}
+
+// Use lazy_static only on non-Android platforms
+#[cfg(not(target_os = "android"))]
+lazy_static::lazy_static! {
+    static ref ANDROID_FILES_DIR: Option<String> = None;
+}
+
+// Get the appropriate storage directory for the current platform
+fn get_storage_dir() -> String {
+    #[cfg(target_os = "android")]
+    {
+        match get_android_files_dir() {
+            Ok(dir) => {
+                log::info!("✅ Using Android files directory: {}", dir);
+                dir
+            }
+            Err(e) => {
+                log::error!("❌ Failed to get Android files directory: {}", e);
+                log::warn!("⚠️ Falling back to current directory");
+                ".".to_string()
+            }
+        }
+    }
+    #[cfg(not(target_os = "android"))]
+    {
+        let home_dir = std::env::var("HOME")
+            .or_else(|_| std::env::var("USERPROFILE"))
+            .unwrap_or_else(|_| ".".to_string());
+        format!("{home_dir}/.solana_wallet_app")
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Storage directory of the running test, in place of the real one
+    static TEST_STORAGE_DIR: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+}
+
+fn get_storage_dir_simple() -> String {
+    #[cfg(test)]
+    if let Some(dir) = TEST_STORAGE_DIR.with(|dir| dir.borrow().clone()) {
+        return dir;
+    }
+    #[cfg(target_os = "android")]
+    {
+        if let Some(ref dir) = *get_android_files_dir_cached() {
+            dir.clone()
+        } else {
+            log::warn!("⚠️ Using fallback storage directory");
+            "/data/data/com.unruggable/files".to_string() // Hardcoded fallback
+        }
+    }
+    #[cfg(target_os = "ios")]
+    {
+        // Use iOS Application Support directory (better than Documents for app data)
+        if let Some(home) = std::env::var_os("HOME") {
+            let app_support = std::path::PathBuf::from(home)
+                .join("Library")
+                .join("Application Support")
+                .join("WalletData");
+            
+            let app_support_str = app_support.to_string_lossy().to_string();
+            log::info!("🍎 Using iOS Application Support: {}", app_support_str);
+            app_support_str
+        } else {
+            log::warn!("⚠️ iOS HOME not found, using fallback");
+            "./WalletData".to_string()
+        }
+    }
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    {
+        let home_dir = std::env::var("HOME")
+            .or_else(|_| std::env::var("USERPROFILE"))
+            .unwrap_or_else(|_| ".".to_string());
+        format!("{home_dir}/.solana_wallet_app")
+    }
+}
+
+// Add iOS-specific initialization function (add this new function)
+#[cfg(target_os = "ios")]
+pub fn init_ios_storage() -> Result<(), String> {
+    log::info!("🍎 Initializing iOS storage...");
+    
+    // Log environment info for debugging
+    if let Some(home) = std::env::var_os("HOME") {
+        log::info!("📱 iOS HOME: {}", home.to_string_lossy());
+    } else {
+        log::warn!("⚠️ iOS HOME environment variable not found");
+    }
+    
+    // Get and create storage directory
+    let storage_dir = get_storage_dir_simple();
+    log::info!("📁 iOS storage directory: {}", storage_dir);
+    
+    // Ensure directory exists
+    match ensure_storage_dir() {
+        Ok(_) => {
+            log::info!("✅ iOS storage directory ready");
+            
+            // Test read/write capabilities
+            let test_file = format!("{}/ios_test.txt", storage_dir);
+            match std::fs::write(&test_file, "iOS storage test") {
+                Ok(_) => {
+                    log::info!("✅ iOS write test successful");
+                    
+                    // Verify we can read it back
+                    match std::fs::read_to_string(&test_file) {
+                        Ok(content) => {
+                            if content == "iOS storage test" {
+                                log::info!("✅ iOS read-write verification successful");
+                                let _ = std::fs::remove_file(&test_file); // cleanup
+                                Ok(())
+                            } else {
+                                Err("iOS read-write verification failed".to_string())
+                            }
+                        }
+                        Err(e) => Err(format!("iOS read test failed: {}", e))
+                    }
+                }
+                Err(e) => {
+                    Err(format!("iOS write test failed: {}", e))
+                }
+            }
+        }
+        Err(e) => {
+            Err(format!("iOS storage directory creation failed: {}", e))
+        }
+    }
+}
+
+// Get file paths
+fn get_wallets_file_path() -> String {
+    let storage_dir = get_storage_dir_simple();
+    format!("{storage_dir}/wallets.json")
+}
+
+fn get_rpc_file_path() -> String {
+    let storage_dir = get_storage_dir_simple();
+    format!("{storage_dir}/rpc.txt")
+}
+
+fn get_jito_settings_file_path() -> String {
+    let storage_dir = get_storage_dir_simple();
+    format!("{storage_dir}/jito_settings.json")
+}
+
+// Ensure storage directory exists with logging
+fn ensure_storage_dir() -> Result<(), std::io::Error> {
+    let storage_dir = get_storage_dir_simple();
+    log::info!("Ensuring storage directory exists: {}", storage_dir);
+    
+    match std::fs::create_dir_all(&storage_dir) {
+        Ok(_) => {
+            log::info!("✅ Storage directory created/verified: {}", storage_dir);
+            
+            // Verify permissions by writing a test file
+            let test_file = format!("{}/permission_test.txt", storage_dir);
+            match std::fs::write(&test_file, "permission_test") {
+                Ok(_) => {
+                    log::info!("✅ Storage directory is writable");
+                    let _ = std::fs::remove_file(&test_file);
+                    Ok(())
+                }
+                Err(e) => {
+                    log::error!("❌ Storage directory exists but is not writable: {}", e);
+                    Err(e)
+                }
+            }
+        }
+        Err(e) => {
+            log::error!("❌ Failed to create storage directory {}: {}", storage_dir, e);
+            Err(e)
+        }
+    }
+}
+
+// Add this function for testing Android storage
+#[cfg(target_os = "android")]
+pub fn ensure_android_storage_works() -> Result<(), String> {
+    log::info!("🔧 Testing Android storage...");
+    
+    // Try to write a simple test file
+    let test_dir = "/data/data/com.unruggable/files";
+    
+    match std::fs::create_dir_all(test_dir) {
+        Ok(_) => log::info!("✅ Created storage directory: {}", test_dir),
+        Err(e) => {
+            log::error!("❌ Failed to create storage directory: {}", e);
+            return Err(format!("Storage directory creation failed: {}", e));
+        }
+    }
+    
+    let test_file = format!("{}/test.txt", test_dir);
+    match std::fs::write(&test_file, "test") {
+        Ok(_) => {
+            log::info!("✅ Storage write test successful");
+            let _ = std::fs::remove_file(&test_file);
+            Ok(())
+        }
+        Err(e) => {
+            log::error!("❌ Storage write test failed: {}", e);
+            Err(format!("Storage write failed: {}", e))
+        }
+    }
+}
+
+pub fn save_wallet_to_storage(wallet_info: &WalletInfo) {
+    log::info!("🔄 Attempting to save wallet: {}", wallet_info.name);
+    
+    let mut wallets = load_wallets_from_storage();
+    wallets.push(wallet_info.clone());
+    
+    // Only the decoy list, never the real wallets file or secret store
+    if crate::pin::is_duress_session() {
+        save_wallets_to_storage(&wallets);
+        return;
+    }
+    seal_wallet_keys(&mut wallets);
+    stash_wallet_keys(&mut wallets);
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let serialized = serde_json::to_string(&wallets).unwrap();
+        storage.set_item("wallets", &serialized).unwrap();
+        log::info!("✅ Wallet saved to web storage");
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        match ensure_storage_dir() {
+            Ok(_) => {
+                let wallet_file = get_wallets_file_path();
+                log::info!("📁 Saving to file: {}", wallet_file);
+                
+                match serde_json::to_string_pretty(&wallets) {
+                    Ok(serialized) => {
+                        match std::fs::write(&wallet_file, &serialized) {
+                            Ok(_) => {
+                                log::info!("✅ Wallet successfully saved to: {}", wallet_file);
+                                log::info!("📊 Saved {} wallets total", wallets.len());
+                                
+                                // Verify the save by reading it back
+                                match std::fs::read_to_string(&wallet_file) {
+                                    Ok(read_back) => {
+                                        if read_back == serialized {
+                                            log::info!("✅ Write verification successful");
+                                        } else {
+                                            log::error!("❌ Write verification failed - content mismatch");
+                                        }
+                                    }
+                                    Err(e) => {
+                                        log::error!("❌ Write verification failed - cannot read back: {}", e);
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                log::error!("❌ Failed to write wallets to {}: {}", wallet_file, e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("❌ Failed to serialize wallets: {}", e);
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("❌ Failed to ensure storage directory: {}", e);
+            }
+        }
+    }
+}
+
+pub fn load_wallets_from_storage() -> Vec<WalletInfo> {
+    log::info!("🔄 Attempting to load wallets from storage");
+    
+    // The duress PIN opens only the decoy wallets
+    if crate::pin::is_duress_session() {
+        return load_json_dataset(DURESS_WALLETS);
+    }
+    
+    // iOS-specific initialization
+    #[cfg(target_os = "ios")]
+    {
+        if let Err(e) = init_ios_storage() {
+            log::error!("❌ iOS storage init failed: {}", e);
+        }
+    }
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let result = storage.get_item("wallets")
+            .unwrap()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        log::info!("📱 Loaded {} wallets from web storage", result.len());
+        result
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        let wallet_file = get_wallets_file_path();
+        log::info!("📁 Looking for wallets at: {}", wallet_file);
+        
+        // Ensure storage directory exists
+        if let Err(e) = ensure_storage_dir() {
+            log::error!("❌ Storage directory error: {}", e);
+            return Vec::new();
+        }
+        
+        // Check if file exists
+        if !Path::new(&wallet_file).exists() {
+            log::info!("ℹ️ No existing wallet file found at: {}", wallet_file);
+            
+            // Debug: List directory contents
+            let storage_dir = get_storage_dir_simple();
+            if let Ok(entries) = std::fs::read_dir(&storage_dir) {
+                log::info!("📂 Directory contents of {}:", storage_dir);
+                for entry in entries {
+                    if let Ok(entry) = entry {
+                        log::info!("  - {}", entry.file_name().to_string_lossy());
+                    }
+                }
+            }
+            
+            return Vec::new();
+        }
+        
+        match std::fs::read_to_string(&wallet_file) {
+            Ok(data) => {
+                log::info!("📄 Read {} bytes from wallet file", data.len());
+                match serde_json::from_str::<Vec<WalletInfo>>(&data) {
+                    Ok(mut wallets) => {
+                        log::info!("✅ Successfully loaded {} wallets", wallets.len());
+                        if let Err(e) = fetch_stashed_wallet_keys(&mut wallets) {
+                            log::error!("❌ {}", e);
+                        }
+                        for (i, wallet) in wallets.iter().enumerate() {
+                            log::info!("  Wallet {}: {} ({}...)", i + 1, wallet.name, &wallet.address[..8]);
+                        }
+                        wallets
+                    }
+                    Err(e) => {
+                        log::error!("❌ Failed to parse wallets from {}: {}", wallet_file, e);
+                        log::error!("📄 File contents preview: {}", &data.chars().take(200).collect::<String>());
+                        Vec::new()
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("❌ Failed to read wallets from {}: {}", wallet_file, e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+pub fn import_wallet_from_key(private_key: &str, name: String) -> Result<WalletInfo, String> {
+    let private_key = private_key.trim();
+    
+    // Try to parse the key based on format
+    let key_bytes = if private_key.starts_with('{') {
+        // JSON keystore: {"publicKey": "...", "secretKey": [...]}
+        crate::wallet_import::parse_json_keystore(private_key)?
+    } else if private_key.starts_with('[') && private_key.ends_with(']') {
+        // JSON array format: [252,183,...159,189]
+        parse_json_array_key(private_key)?
+    } else if private_key.contains(',') {
+        // Comma-separated format: 252,183,...159,189
+        parse_comma_separated_key(private_key)?
+    } else {
+        // Base58 format (original)
+        bs58::decode(private_key)
+            .into_vec()
+            .map_err(|e| format!("Invalid base58 format: {}", e))?
+    };
+    
+    let wallet_name = if name.is_empty() { 
+        "Imported Wallet".to_string() 
+    } else { 
+        name 
+    };
+    
+    let wallet = Wallet::from_private_key(&key_bytes, wallet_name)?;
+    
+    Ok(wallet.to_wallet_info())
+}
+
+/// Import a wallet from a BIP39 recovery phrase and optional passphrase, on
+/// the derivation path the exporting wallet used
+pub fn import_wallet_from_mnemonic(
+    phrase: &str,
+    passphrase: &str,
+    scheme: crate::wallet_import::DerivationScheme,
+    account: u32,
+    name: String,
+) -> Result<WalletInfo, String> {
+    let wallet_name = if name.is_empty() { 
+        "Imported Wallet".to_string() 
+    } else { 
+        name 
+    };
+    
+    let wallet = crate::wallet_import::wallet_from_phrase(phrase, passphrase, scheme, account, wallet_name)?;
+    
+    Ok(WalletInfo {
+        passphrase_protected: !passphrase.is_empty(),
+        ..wallet.to_wallet_info()
+    })
+}
+
+/// Import a wallet from an encrypted keystore file's text
+pub fn import_wallet_from_keystore(keystore: &str, password: &str, name: String) -> Result<WalletInfo, String> {
+    let wallet_name = if name.is_empty() { 
+        "Imported Wallet".to_string() 
+    } else { 
+        name 
+    };
+    
+    let wallet = crate::keystore::decrypt_keystore(keystore, password, wallet_name)?;
+    
+    Ok(wallet.to_wallet_info())
+}
+
+/// Rebuild a wallet from SLIP-39 shares, one per line
+pub fn import_wallet_from_shares(shares: &str, name: String) -> Result<WalletInfo, String> {
+    let wallet_name = if name.is_empty() { 
+        "Imported Wallet".to_string() 
+    } else { 
+        name 
+    };
+    
+    let wallet = crate::shamir::wallet_from_shares(shares, wallet_name)?;
+    
+    Ok(wallet.to_wallet_info())
+}
+
+// Helper function to parse JSON array format
+fn parse_json_array_key(key_str: &str) -> Result<Vec<u8>, String> {
+    serde_json::from_str::<Vec<u8>>(key_str)
+        .map_err(|e| format!("Invalid JSON array format: {}", e))
+}
+
+// Helper function to parse comma-separated format
+fn parse_comma_separated_key(key_str: &str) -> Result<Vec<u8>, String> {
+    key_str
+        .split(',')
+        .map(|s| {
+            s.trim()
+                .parse::<u8>()
+                .map_err(|e| format!("Invalid number in key: {}", e))
+        })
+        .collect::<Result<Vec<u8>, String>>()
+}
+
+// Optional: Add a validation function to check key format before import
+pub fn validate_key_format(private_key: &str) -> Result<String, String> {
+    let private_key = private_key.trim();
+    
+    if private_key.is_empty() {
+        return Err("Private key is empty".to_string());
+    }
+    
+    if private_key.starts_with('{') {
+        return Ok("JSON keystore format".to_string());
+    } else if private_key.starts_with('[') && private_key.ends_with(']') {
+        return Ok("JSON array format".to_string());
+    } else if private_key.contains(',') {
+        return Ok("Comma-separated format".to_string());
+    } else {
+        // Check if it's valid base58
+        bs58::decode(private_key)
+            .into_vec()
+            .map_err(|e| format!("Invalid base58 format: {}", e))?;
+        return Ok("Base58 format".to_string());
+    }
+}
+
+pub fn save_rpc_to_storage(rpc_url: &str) {
+    log::info!("🔄 Saving RPC URL to storage");
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        storage.set_item("custom_rpc", rpc_url).unwrap();
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        if let Ok(_) = ensure_storage_dir() {
+            let rpc_file = get_rpc_file_path();
+            match std::fs::write(&rpc_file, rpc_url) {
+                Ok(_) => log::info!("✅ RPC URL saved to: {}", rpc_file),
+                Err(e) => log::error!("❌ Failed to write RPC to {}: {}", rpc_file, e),
+            }
+        }
+    }
+}
+
+pub fn load_rpc_from_storage() -> Option<String> {
+    log::info!("🔄 Loading RPC URL from storage");
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        storage.get_item("custom_rpc").unwrap()
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        let rpc_file = get_rpc_file_path();
+        match std::fs::read_to_string(&rpc_file) {
+            Ok(data) => {
+                let result = Some(data.trim().to_string());
+                log::info!("✅ RPC URL loaded from storage");
+                result
+            }
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    log::error!("❌ Failed to read RPC from {}: {}", rpc_file, e);
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Fallback RPC endpoints tried when the selected one fails
+pub fn load_rpc_fallbacks() -> Vec<String> {
+    load_json_dataset("rpc_fallbacks")
+}
+
+pub fn save_rpc_fallbacks(endpoints: &[String]) -> Result<(), String> {
+    save_json_dataset("rpc_fallbacks", &endpoints)
+}
+
+/// Extra headers sent to each RPC endpoint, by URL
+pub fn load_rpc_headers() -> std::collections::HashMap<String, Vec<(String, String)>> {
+    load_json_dataset("rpc_headers")
+}
+
+pub fn save_rpc_headers(headers: &std::collections::HashMap<String, Vec<(String, String)>>) -> Result<(), String> {
+    save_json_dataset("rpc_headers", headers)
+}
+
+/// Whether requests go to the fastest benchmarked endpoint first
+pub fn load_rpc_auto_select() -> bool {
+    load_json_dataset("rpc_auto_select")
+}
+
+pub fn save_rpc_auto_select(enabled: bool) -> Result<(), String> {
+    save_json_dataset("rpc_auto_select", &enabled)
+}
+
+/// Cluster the app runs against
+pub fn load_network() -> Network {
+    load_json_dataset("network")
+}
+
+pub fn save_network(network: &Network) -> Result<(), String> {
+    save_json_dataset("network", network)
+}
+
+/// Tip paid to the app per swap or JitoTx transaction
+pub fn load_app_tip_settings() -> crate::config::app_tip::AppTipSettings {
+    load_json_dataset("app_tip")
+}
+
+pub fn save_app_tip_settings(settings: &crate::config::app_tip::AppTipSettings) -> Result<(), String> {
+    save_json_dataset("app_tip", settings)
+}
+
+pub fn clear_rpc_storage() {
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        storage.remove_item("custom_rpc").unwrap();
+    }
+    
+    #[cfg(not(target_os = "android"))]
+    {
+        let rpc_file = get_rpc_file_path();
+        match std::fs::remove_file(&rpc_file) {
+            Ok(_) => log::info!("✅ RPC file removed"),
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    log::error!("❌ Failed to remove RPC file {}: {}", rpc_file, e);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct JitoSettings {
+    pub jito_tx: bool,
+    pub jito_bundles: bool,
+    /// Tip used unless a transaction picks its own
+    #[serde(default)]
+    pub tip_level: crate::tips::TipLevel,
+}
+
+impl Default for JitoSettings {
+    fn default() -> Self {
+        Self {
+            jito_tx: true,
+            jito_bundles: false,
+            tip_level: crate::tips::TipLevel::default(),
+        }
+    }
+}
+
+pub fn save_jito_settings_to_storage(settings: &JitoSettings) {
+    log::info!("🔄 Saving Jito settings to storage");
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let serialized = serde_json::to_string(settings).unwrap();
+        storage.set_item("jito_settings", &serialized).unwrap();
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        if let Ok(_) = ensure_storage_dir() {
+            let jito_file = get_jito_settings_file_path();
+            match serde_json::to_string_pretty(settings) {
+                Ok(serialized) => {
+                    match std::fs::write(&jito_file, serialized) {
+                        Ok(_) => log::info!("✅ Jito settings saved to: {}", jito_file),
+                        Err(e) => log::error!("❌ Failed to write Jito settings to {}: {}", jito_file, e),
+                    }
+                }
+                Err(e) => log::error!("❌ Failed to serialize Jito settings: {}", e),
+            }
+        }
+    }
+}
+
+pub fn load_jito_settings_from_storage() -> JitoSettings {
+    log::info!("🔄 Loading Jito settings from storage");
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        storage
+            .get_item("jito_settings")
+            .unwrap()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        let jito_file = get_jito_settings_file_path();
+        match std::fs::read_to_string(&jito_file) {
+            Ok(data) => {
+                match serde_json::from_str(&data) {
+                    Ok(settings) => {
+                        log::info!("✅ Jito settings loaded from storage");
+                        settings
+                    }
+                    Err(e) => {
+                        log::error!("❌ Failed to parse Jito settings from {}: {}", jito_file, e);
+                        JitoSettings::default()
+                    }
+                }
+            }
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    log::error!("❌ Failed to read Jito settings from {}: {}", jito_file, e);
+                }
+                JitoSettings::default()
+            }
+        }
+    }
+}
+
+pub fn get_current_jito_settings() -> JitoSettings {
+    load_jito_settings_from_storage()
+}
+
+/// Delete a wallet by address from storage
+pub fn delete_wallet_from_storage(wallet_address: &str) {
+    log::info!("🔄 Attempting to delete wallet: {}", wallet_address);
+    
+    let mut wallets = load_wallets_from_storage();
+    let original_count = wallets.len();
+    
+    // Remove wallet with matching address
+    wallets.retain(|wallet| wallet.address != wallet_address);
+    
+    if wallets.len() < original_count {
+        log::info!("✅ Wallet {} removed from memory", wallet_address);
+        if secrets::is_available() && !crate::pin::is_duress_session() {
+            if let Err(e) = secrets::delete_secret(&wallet_key_secret_name(wallet_address)) {
+                log::error!("❌ {}", e);
+            }
+        }
+        
+        // Save updated wallet list
+        save_wallets_to_storage(&wallets);
+        log::info!("✅ Wallet deletion completed. {} wallets remaining.", wallets.len());
+    } else {
+        log::warn!("⚠️ Wallet {} not found in storage", wallet_address);
+    }
+}
+
+/// Save wallets list to storage (only add this if it doesn't already exist in your storage.rs)
+pub fn save_wallets_to_storage(wallets: &Vec<WalletInfo>) {
+    log::info!("🔄 Saving {} wallets to storage", wallets.len());
+    let mut wallets = wallets.clone();
+    seal_wallet_keys(&mut wallets);
+    
+    // Decoy keys stay in their own file so they never replace the real
+    // wallets' keys in the secret store
+    if crate::pin::is_duress_session() {
+        if let Err(e) = save_json_dataset(DURESS_WALLETS, &wallets) {
+            log::error!("❌ {}", e);
+        }
+        return;
+    }
+    stash_wallet_keys(&mut wallets);
+    let wallets = &wallets;
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let serialized = serde_json::to_string(wallets).unwrap();
+        storage.set_item("wallets", &serialized).unwrap();
+        log::info!("✅ Wallets saved to web storage");
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        match ensure_storage_dir() {
+            Ok(_) => {
+                let wallet_file = get_wallets_file_path();
+                match serde_json::to_string_pretty(wallets) {
+                    Ok(serialized) => {
+                        match std::fs::write(&wallet_file, &serialized) {
+                            Ok(_) => {
+                                log::info!("✅ Wallets successfully saved to: {}", wallet_file);
+                            }
+                            Err(e) => {
+                                log::error!("❌ Failed to write wallets to {}: {}", wallet_file, e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("❌ Failed to serialize wallets: {}", e);
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("❌ Failed to ensure storage directory: {}", e);
+            }
+        }
+    }
+}
+
+/// Seal any raw private keys with the session's vault key. Without a PIN
+/// there is nothing to derive a key from and keys stay as they are; with a
+/// PIN but no unlocked session they are sealed at the next unlock.
+fn seal_wallet_keys(wallets: &mut [WalletInfo]) {
+    if crate::pin::vault_key().is_none() {
+        if has_pin() && wallets.iter().any(|w| has_raw_key(w)) {
+            log::warn!("⚠️ Wallet keys saved unsealed while locked, they will be sealed at the next unlock");
+        }
+        return;
+    }
+    for wallet in wallets.iter_mut().filter(|w| has_raw_key(w)) {
+        match crate::pin::seal_wallet_key(&wallet.encrypted_key) {
+            Ok(sealed) => wallet.encrypted_key = sealed,
+            Err(e) => log::error!("❌ Failed to seal key of {}: {}", wallet.address, e),
+        }
+    }
+}
+
+/// Replace sealed keys with the raw keys behind them
+fn open_wallet_keys(wallets: &mut [WalletInfo]) -> Result<(), String> {
+    for wallet in wallets.iter_mut().filter(|w| crate::pin::is_sealed_key(&w.encrypted_key)) {
+        wallet.encrypted_key = crate::pin::open_wallet_key(&wallet.encrypted_key)?;
+    }
+    Ok(())
+}
+
+fn has_raw_key(wallet: &WalletInfo) -> bool {
+    is_raw_secret(&wallet.encrypted_key)
+}
+
+fn is_raw_secret(stored: &str) -> bool {
+    !stored.is_empty() && !crate::pin::is_sealed_key(stored) && stored != STASHED_KEY_MARKER
+}
+
+/// Stands in the wallets file for a key kept in the platform secret store
+const STASHED_KEY_MARKER: &str = "secret-store";
+
+fn wallet_key_secret_name(address: &str) -> String {
+    format!("wallet_key_{}", address)
+}
+
+/// Move stored keys into the platform secret store, leaving a marker in the
+/// wallets file. A key the store refuses stays in the file.
+fn stash_wallet_keys(wallets: &mut [WalletInfo]) {
+    if !secrets::is_available() {
+        return;
+    }
+    for wallet in wallets.iter_mut() {
+        if wallet.encrypted_key.is_empty() || wallet.encrypted_key == STASHED_KEY_MARKER {
+            continue;
+        }
+        match secrets::set_secret(&wallet_key_secret_name(&wallet.address), &wallet.encrypted_key) {
+            Ok(()) => wallet.encrypted_key = STASHED_KEY_MARKER.to_string(),
+            Err(e) => log::error!("❌ Keeping key of {} in app storage: {}", wallet.address, e),
+        }
+    }
+}
+
+/// Fill in keys kept in the platform secret store. A key that can't be read
+/// keeps its marker, so saving the wallets again never loses it.
+fn fetch_stashed_wallet_keys(wallets: &mut [WalletInfo]) -> Result<(), String> {
+    let mut unreadable = Vec::new();
+    for wallet in wallets.iter_mut().filter(|w| w.encrypted_key == STASHED_KEY_MARKER) {
+        match secrets::get_secret(&wallet_key_secret_name(&wallet.address)) {
+            Ok(Some(key)) => wallet.encrypted_key = key,
+            Ok(None) => unreadable.push(format!("{} (missing)", wallet.name)),
+            Err(e) => unreadable.push(format!("{} ({})", wallet.name, e)),
+        }
+    }
+    if unreadable.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed to read wallet keys from the secret store: {}", unreadable.join(", ")))
+    }
+}
+
+/// Whether a stored key is the marker of a key the secret store didn't release
+pub fn is_unreadable_stashed_key(stored: &str) -> bool {
+    stored == STASHED_KEY_MARKER
+}
+
+/// Protect a secret other than a wallet key (a key share, a session key)
+/// the way wallet keys are: sealed with the vault key once the session is
+/// unlocked, then moved into the platform secret store as `secret_name`.
+/// Decoy secrets stay in their profile's file, like decoy wallet keys.
+fn protect_secret(secret: &mut String, secret_name: &str) {
+    if is_raw_secret(secret) && crate::pin::vault_key().is_some() {
+        match crate::pin::seal_wallet_key(secret) {
+            Ok(sealed) => *secret = sealed,
+            Err(e) => log::error!("❌ Failed to seal {}: {}", secret_name, e),
+        }
+    }
+    if secret.is_empty() || *secret == STASHED_KEY_MARKER || !secrets::is_available() || crate::pin::is_duress_session() {
+        return;
+    }
+    match secrets::set_secret(secret_name, secret) {
+        Ok(()) => *secret = STASHED_KEY_MARKER.to_string(),
+        Err(e) => log::error!("❌ Keeping {} in app storage: {}", secret_name, e),
+    }
+}
+
+/// The secret behind what `protect_secret` stored. One that can't be read
+/// or opened stays as stored, so saving it again never loses it.
+fn reveal_secret(secret: &mut String, secret_name: &str) {
+    if *secret == STASHED_KEY_MARKER {
+        match secrets::get_secret(secret_name) {
+            Ok(Some(stored)) => *secret = stored,
+            Ok(None) => log::error!("❌ {} is missing from the secret store", secret_name),
+            Err(e) => log::error!("❌ Failed to read {} from the secret store: {}", secret_name, e),
+        }
+    }
+    if crate::pin::is_sealed_key(secret) {
+        match crate::pin::open_wallet_key(secret) {
+            Ok(opened) => *secret = opened,
+            Err(e) => log::error!("❌ Failed to open {}: {}", secret_name, e),
+        }
+    }
+}
+
+/// Drop a secret `protect_secret` moved into the platform secret store
+fn forget_secret(secret_name: &str) {
+    if secrets::is_available() && !crate::pin::is_duress_session() {
+        if let Err(e) = secrets::delete_secret(secret_name) {
+            log::error!("❌ {}", e);
+        }
+    }
+}
+
+pub fn has_completed_onboarding() -> bool {
+    log::info!("🔄 Checking onboarding status");
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        storage.get_item("onboarding_completed")
+            .unwrap()
+            .map(|val| val == "true")
+            .unwrap_or(false)
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        let storage_dir = get_storage_dir_simple();
+        let onboarding_file = format!("{}/onboarding_completed.txt", storage_dir);
+        
+        match std::fs::read_to_string(&onboarding_file) {
+            Ok(data) => {
+                let completed = data.trim() == "true";
+                log::info!("✅ Onboarding status: {}", completed);
+                completed
+            }
+            Err(_) => {
+                log::info!("📝 No onboarding file found - first launch");
+                false
+            }
+        }
+    }
+}
+
+pub fn mark_onboarding_completed() {
+    log::info!("✅ Marking onboarding as completed");
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        storage.set_item("onboarding_completed", "true").unwrap();
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        if let Ok(_) = ensure_storage_dir() {
+            let storage_dir = get_storage_dir_simple();
+            let onboarding_file = format!("{}/onboarding_completed.txt", storage_dir);
+            
+            match std::fs::write(&onboarding_file, "true") {
+                Ok(_) => log::info!("✅ Onboarding completion saved"),
+                Err(e) => log::error!("❌ Failed to save onboarding status: {}", e),
+            }
+        }
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// PIN Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinData {
+    pub pin_hash: String,
+    pub salt: Vec<u8>,
+    #[serde(flatten)]
+    pub attempts: crate::pin::PinAttempts,
+    /// Wipe the wallets after `MAX_PIN_ATTEMPTS` wrong PINs in a row
+    #[serde(default)]
+    pub wipe_after_max_attempts: bool,
+    #[serde(default)]
+    pub duress: Option<DuressPin>,
+}
+
+/// Second PIN that unlocks a decoy profile of designated wallets. Their keys
+/// are copied into `DURESS_WALLETS`, sealed under a key derived from it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuressPin {
+    pub pin_hash: String,
+    pub salt: Vec<u8>,
+}
+
+/// Wallets the duress PIN unlocks
+const DURESS_WALLETS: &str = "duress_wallets";
+
+fn get_pin_file_path() -> String {
+    let storage_dir = get_storage_dir_simple();
+    format!("{}/pin.json", storage_dir)
+}
+
+fn get_quantum_vaults_file_path() -> String {
+    let storage_dir = get_storage_dir_simple();
+    format!("{}/quantum_vaults.json", storage_dir)
+}
+
+/// Check if a PIN is set
+pub fn has_pin() -> bool {
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        storage.get_item("pin_data").unwrap().is_some()
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        let pin_file = get_pin_file_path();
+        Path::new(&pin_file).exists()
+    }
+}
+
+/// Save PIN hash and salt, and seal wallet keys under the new PIN
+pub fn save_pin(pin: &str) -> Result<(), String> {
+    use crate::pin::{hash_pin, generate_salt, unlock_vault, KdfParams, VaultKey};
+    
+    log::info!("🔐 Saving PIN to storage");
+    
+    // A decoy session's wallet list must not replace the real one
+    if crate::pin::is_duress_session() {
+        return Err("Enter your PIN before changing it".to_string());
+    }
+    
+    // Keys sealed under a previous PIN have to be opened before it is replaced
+    let mut wallets = load_wallets_from_storage();
+    open_wallet_keys(&mut wallets)?;
+    
+    let pin_hash = hash_pin(pin)?;
+    let salt = generate_salt();
+    let vault_key = VaultKey::derive(pin, &salt, KdfParams::DEFAULT)?;
+    
+    let pin_data = PinData {
+        pin_hash,
+        salt: salt.to_vec(),
+        attempts: Default::default(),
+        wipe_after_max_attempts: wipes_after_max_attempts(),
+        duress: load_pin_data().ok().and_then(|pin_data| pin_data.duress),
+    };
+    
+    // Biometric unlock releases the old PIN's vault key
+    save_biometric_unlock(None)?;
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let serialized = serde_json::to_string(&pin_data)
+            .map_err(|e| format!("Failed to serialize PIN data: {}", e))?;
+        storage.set_item("pin_data", &serialized)
+            .map_err(|_| "Failed to save PIN to web storage".to_string())?;
+        log::info!("✅ PIN saved to web storage");
+        unlock_vault(vault_key);
+        save_wallets_to_storage(&wallets);
+        Ok(())
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        ensure_storage_dir()
+            .map_err(|e| format!("Failed to ensure storage directory: {}", e))?;
+        
+        let pin_file = get_pin_file_path();
+        let serialized = serde_json::to_string_pretty(&pin_data)
+            .map_err(|e| format!("Failed to serialize PIN data: {}", e))?;
+        
+        std::fs::write(&pin_file, serialized)
+            .map_err(|e| format!("Failed to write PIN file: {}", e))?;
+        
+        log::info!("✅ PIN saved to: {}", pin_file);
+        unlock_vault(vault_key);
+        save_wallets_to_storage(&wallets);
+        Ok(())
+    }
+}
+
+/// Verify PIN and return salt if correct. Unlocks the wallet keys for the
+/// session and seals any still stored raw. Wrong PINs earn growing lockouts
+/// and, when turned on, wipe the wallets after `MAX_PIN_ATTEMPTS`.
+pub fn verify_pin(pin: &str) -> Result<Vec<u8>, String> {
+    use crate::pin::{format_lockout, hash_pin, is_legacy_pin_hash, verify_pin_hash, KdfParams, VaultKey, FREE_PIN_ATTEMPTS, MAX_PIN_ATTEMPTS};
+    
+    let mut pin_data = load_pin_data()?;
+    let now = chrono::Utc::now().timestamp() as u64;
+    if pin_data.attempts.restart_if_clock_went_back(now) {
+        let _ = save_pin_data(&pin_data);
+    }
+    let remaining = pin_data.attempts.remaining_lockout(now);
+    if remaining > 0 {
+        return Err(format!("Too many wrong PINs. Try again in {}", format_lockout(remaining)));
+    }
+    
+    if verify_pin_hash(pin, &pin_data.pin_hash) {
+        // Correct PIN - reset failed attempts
+        pin_data.attempts.reset();
+        if is_legacy_pin_hash(&pin_data.pin_hash) {
+            pin_data.pin_hash = hash_pin(pin)?;
+            log::info!("🔐 Upgraded PIN hash to Argon2id");
+        }
+        let _ = save_pin_data(&pin_data);
+        log::info!("✅ PIN verified successfully");
+        
+        open_vault(VaultKey::derive(pin, &pin_data.salt, KdfParams::DEFAULT)?, false);
+        Ok(pin_data.salt)
+    } else if let Some(duress) = pin_data.duress.clone().filter(|d| verify_pin_hash(pin, &d.pin_hash)) {
+        // Duress PIN - looks like a normal unlock but opens only the decoy wallets
+        pin_data.attempts.reset();
+        let _ = save_pin_data(&pin_data);
+        log::info!("✅ PIN verified successfully");
+        
+        open_vault(VaultKey::derive(pin, &duress.salt, KdfParams::DEFAULT)?, true);
+        Ok(pin_data.salt)
+    } else {
+        // Wrong PIN - count it and lock out for a while
+        let lockout = pin_data.attempts.record_failure(now);
+        let failed_attempts = pin_data.attempts.failed_attempts;
+        let wipe = pin_data.attempts.should_wipe(pin_data.wipe_after_max_attempts);
+        log::warn!("❌ PIN verification failed. Attempts: {}, locked for {}s", failed_attempts, lockout);
+        crate::notifications::notify(crate::notifications::CriticalEvent::FailedLogin {
+            failed_attempts,
+            locked: lockout > 0,
+            wiped: wipe,
+        });
+        
+        if wipe {
+            wipe_wallets();
+            pin_data.attempts.reset();
+            pin_data.duress = None;
+            let _ = save_pin_data(&pin_data);
+            return Err(format!("Wallets wiped after {} wrong PINs", MAX_PIN_ATTEMPTS));
+        }
+        let _ = save_pin_data(&pin_data);
+        
+        if lockout > 0 {
+            Err(format!("Incorrect PIN. Try again in {}", format_lockout(lockout)))
+        } else {
+            Err(format!(
+                "Incorrect PIN. {} attempt(s) before a lockout",
+                FREE_PIN_ATTEMPTS + 1 - failed_attempts
+            ))
+        }
+    }
+}
+
+/// Delete every wallet secret on this device, for the wipe after too many
+/// wrong PINs: wallet keys, passkey-wrapped keys, key shares and session
+/// keys, in the real and the duress profile. Wallets with nothing on this
+/// device (hardware, watch-only, paired devices) stay listed.
+fn wipe_wallets() {
+    log::warn!("🧨 Wiping wallets after too many wrong PINs");
+    crate::pin::lock_vault();
+    let shares: Vec<crate::signing::mpc::MpcKeyShare> = load_json_dataset(MPC_SHARES);
+    let passkey_keys: Vec<crate::signing::passkey::WrappedKey> = load_json_dataset(PASSKEY_KEYS);
+    for wallet in load_wallets_from_storage() {
+        let holds_secret = !wallet.encrypted_key.is_empty()
+            || shares.iter().any(|s| s.group_address == wallet.address)
+            || passkey_keys.iter().any(|k| k.address == wallet.address);
+        if holds_secret {
+            delete_wallet_from_storage(&wallet.address);
+        }
+    }
+    for share in &shares {
+        forget_secret(&mpc_share_secret_name(&share.group_address));
+    }
+    let sessions: Vec<crate::signing::session::SessionKey> = load_json_dataset(SESSION_KEYS);
+    for session in &sessions {
+        forget_secret(&session_key_secret_name(&session.owner));
+    }
+    for dataset in [MPC_SHARES, SESSION_KEYS, PASSKEY_KEYS] {
+        let _ = save_json_dataset(dataset, &Vec::<serde_json::Value>::new());
+        let _ = save_json_dataset(&format!("duress_{}", dataset), &Vec::<serde_json::Value>::new());
+    }
+    let _ = save_json_dataset(DURESS_WALLETS, &Vec::<WalletInfo>::new());
+    let _ = save_biometric_unlock(None);
+}
+
+/// Whether the wallets are wiped after `MAX_PIN_ATTEMPTS` wrong PINs
+pub fn wipes_after_max_attempts() -> bool {
+    load_pin_data().is_ok_and(|pin_data| pin_data.wipe_after_max_attempts)
+}
+
+pub fn set_wipe_after_max_attempts(enabled: bool) -> Result<(), String> {
+    let mut pin_data = load_pin_data()?;
+    pin_data.wipe_after_max_attempts = enabled;
+    save_pin_data(&pin_data)
+}
+
+/// Unlock the session with a vault key released by biometrics, in place of
+/// the PIN
+pub fn unlock_with_vault_key(key: crate::pin::VaultKey) -> Result<(), String> {
+    if is_pin_locked() {
+        return Err("Too many wrong PINs - wait before unlocking".to_string());
+    }
+    if key.salt() != load_pin_data()?.salt.as_slice() {
+        return Err("Biometric unlock belongs to an earlier PIN - enter your PIN".to_string());
+    }
+    open_vault(key, false);
+    Ok(())
+}
+
+/// Set the session's vault key and seal any wallet keys still stored raw
+fn open_vault(key: crate::pin::VaultKey, duress: bool) {
+    crate::pin::set_duress_session(duress);
+    crate::pin::unlock_vault(key);
+    let wallets = load_wallets_from_storage();
+    let raw = wallets.iter().filter(|w| has_raw_key(w)).count();
+    if raw > 0 {
+        log::info!("🔐 Sealing {} wallet key(s) stored raw", raw);
+        save_wallets_to_storage(&wallets);
+    }
+
+    // Key shares and session keys saved before they were sealed
+    let stored_shares: Vec<crate::signing::mpc::MpcKeyShare> = load_json_dataset(MPC_SHARES);
+    if stored_shares.iter().any(|s| is_raw_secret(&s.secret_share)) {
+        log::info!("🔐 Sealing key shares stored raw");
+        if let Err(e) = save_mpc_key_shares(load_mpc_key_shares()) {
+            log::error!("❌ {}", e);
+        }
+    }
+    let stored_sessions: Vec<crate::signing::session::SessionKey> = load_json_dataset(SESSION_KEYS);
+    if stored_sessions.iter().any(|s| is_raw_secret(&s.secret)) {
+        log::info!("🔐 Sealing session keys stored raw");
+        if let Err(e) = save_session_keys(load_session_keys()) {
+            log::error!("❌ {}", e);
+        }
+    }
+}
+
+pub fn has_duress_pin() -> bool {
+    load_pin_data().is_ok_and(|pin_data| pin_data.duress.is_some())
+}
+
+/// Addresses of the wallets the duress PIN opens
+pub fn load_duress_wallet_addresses() -> Vec<String> {
+    load_json_dataset::<Vec<WalletInfo>>(DURESS_WALLETS)
+        .into_iter()
+        .map(|wallet| wallet.address)
+        .collect()
+}
+
+/// Set a duress PIN that opens a decoy profile of the given wallets, copying
+/// their keys sealed under it. Needs the session unlocked with the real PIN.
+pub fn save_duress_pin(pin: &str, decoy_addresses: &[String]) -> Result<(), String> {
+    use crate::pin::{generate_salt, hash_pin, seal_wallet_key_with, verify_pin_hash, KdfParams, VaultKey};
+    
+    if crate::pin::is_duress_session() || crate::pin::vault_key().is_none() {
+        return Err("Enter your PIN before setting a duress PIN".to_string());
+    }
+    let mut pin_data = load_pin_data()?;
+    if verify_pin_hash(pin, &pin_data.pin_hash) {
+        return Err("The duress PIN must differ from your PIN".to_string());
+    }
+    
+    let mut decoys: Vec<WalletInfo> = load_wallets_from_storage()
+        .into_iter()
+        .filter(|w| decoy_addresses.contains(&w.address) && !w.encrypted_key.is_empty())
+        .collect();
+    if decoys.is_empty() {
+        return Err("Pick at least one wallet for the decoy profile".to_string());
+    }
+    open_wallet_keys(&mut decoys)?;
+    
+    let salt = generate_salt();
+    let duress_key = VaultKey::derive(pin, &salt, KdfParams::DEFAULT)?;
+    for wallet in decoys.iter_mut() {
+        wallet.encrypted_key = seal_wallet_key_with(&duress_key, &wallet.encrypted_key)?;
+    }
+    save_json_dataset(DURESS_WALLETS, &decoys)?;
+    
+    pin_data.duress = Some(DuressPin { pin_hash: hash_pin(pin)?, salt: salt.to_vec() });
+    save_pin_data(&pin_data)?;
+    log::info!("✅ Duress PIN saved with {} decoy wallet(s)", decoys.len());
+    Ok(())
+}
+
+pub fn remove_duress_pin() -> Result<(), String> {
+    let mut pin_data = load_pin_data()?;
+    pin_data.duress = None;
+    save_pin_data(&pin_data)?;
+    save_json_dataset(DURESS_WALLETS, &Vec::<WalletInfo>::new())
+}
+
+/// Check if PIN entry is locked out after wrong PINs
+pub fn is_pin_locked() -> bool {
+    pin_lockout_remaining() > 0
+}
+
+/// Seconds left before another PIN can be tried
+pub fn pin_lockout_remaining() -> u64 {
+    load_pin_data()
+        .map(|pin_data| pin_data.attempts.remaining_lockout(chrono::Utc::now().timestamp() as u64))
+        .unwrap_or(0)
+}
+
+/// Get salt for encryption (used when PIN is already verified)
+pub fn get_pin_salt() -> Result<Vec<u8>, String> {
+    let pin_data = load_pin_data()?;
+    Ok(pin_data.salt)
+}
+
+/// Load PIN data from storage
+fn load_pin_data() -> Result<PinData, String> {
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let data = storage.get_item("pin_data")
+            .map_err(|_| "Failed to access web storage".to_string())?
+            .ok_or_else(|| "No PIN data found".to_string())?;
+        
+        serde_json::from_str(&data)
+            .map_err(|e| format!("Failed to parse PIN data: {}", e))
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        let pin_file = get_pin_file_path();
+        let data = std::fs::read_to_string(&pin_file)
+            .map_err(|_| "No PIN data found".to_string())?;
+        
+        serde_json::from_str(&data)
+            .map_err(|e| format!("Failed to parse PIN data: {}", e))
+    }
+}
+
+/// Save PIN data to storage
+fn save_pin_data(pin_data: &PinData) -> Result<(), String> {
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let serialized = serde_json::to_string(pin_data)
+            .map_err(|e| format!("Failed to serialize PIN data: {}", e))?;
+        storage.set_item("pin_data", &serialized)
+            .map_err(|_| "Failed to save PIN data to web storage".to_string())?;
+        Ok(())
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        let pin_file = get_pin_file_path();
+        let serialized = serde_json::to_string_pretty(pin_data)
+            .map_err(|e| format!("Failed to serialize PIN data: {}", e))?;
+        
+        std::fs::write(&pin_file, serialized)
+            .map_err(|e| format!("Failed to write PIN file: {}", e))?;
+        
+        Ok(())
+    }
+}
+
+/// Remove PIN from storage. Wallet keys sealed under it are stored raw
+/// again, so the session has to be unlocked.
+pub fn remove_pin() -> Result<(), String> {
+    log::info!("🔐 Removing PIN from storage");
+    
+    // A decoy session's wallet list must not replace the real one
+    if crate::pin::is_duress_session() {
+        return Err("Enter your PIN before removing it".to_string());
+    }
+    
+    let mut wallets = load_wallets_from_storage();
+    open_wallet_keys(&mut wallets)?;
+    crate::pin::lock_vault();
+    save_wallets_to_storage(&wallets);
+    save_biometric_unlock(None)?;
+    save_json_dataset(DURESS_WALLETS, &Vec::<WalletInfo>::new())?;
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        storage.remove_item("pin_data")
+            .map_err(|_| "Failed to remove PIN from web storage".to_string())?;
+        log::info!("✅ PIN removed from web storage");
+        Ok(())
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        let pin_file = get_pin_file_path();
+        std::fs::remove_file(&pin_file)
+            .map_err(|e| format!("Failed to remove PIN file: {}", e))?;
+        log::info!("✅ PIN removed from storage");
+        Ok(())
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Quantum Vault Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Save a quantum vault to storage
+pub fn save_quantum_vault_to_storage(vault: &StoredVault) {
+    log::info!("🔐 Attempting to save quantum vault: {}", vault.name);
+    
+    let mut vaults = load_quantum_vaults_from_storage();
+    vaults.push(vault.clone());
+    
+    if crate::pin::is_duress_session() {
+        save_quantum_vaults_to_storage(&vaults);
+        return;
+    }
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let serialized = serde_json::to_string(&vaults).unwrap();
+        storage.set_item("quantum_vaults", &serialized).unwrap();
+        log::info!("✅ Quantum vault saved to web storage");
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        match ensure_storage_dir() {
+            Ok(_) => {
+                let vault_file = get_quantum_vaults_file_path();
+                log::info!("📁 Saving quantum vault to file: {}", vault_file);
+                
+                match serde_json::to_string_pretty(&vaults) {
+                    Ok(serialized) => {
+                        match std::fs::write(&vault_file, &serialized) {
+                            Ok(_) => {
+                                log::info!("✅ Quantum vault successfully saved to: {}", vault_file);
+                                log::info!("📊 Saved {} quantum vaults total", vaults.len());
+                            }
+                            Err(e) => {
+                                log::error!("❌ Failed to write quantum vaults to {}: {}", vault_file, e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("❌ Failed to serialize quantum vaults: {}", e);
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("❌ Failed to ensure storage directory: {}", e);
+            }
+        }
+    }
+}
+
+/// Load all quantum vaults from storage
+pub fn load_quantum_vaults_from_storage() -> Vec<StoredVault> {
+    log::info!("🔐 Attempting to load quantum vaults from storage");
+    
+    // The decoy profile keeps its vaults in a dataset of its own
+    if crate::pin::is_duress_session() {
+        return load_json_dataset("quantum_vaults");
+    }
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let result = storage.get_item("quantum_vaults")
+            .unwrap()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        log::info!("📱 Loaded {} quantum vaults from web storage", result.len());
+        result
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        let vault_file = get_quantum_vaults_file_path();
+        log::info!("📁 Looking for quantum vaults at: {}", vault_file);
+        
+        if let Err(e) = ensure_storage_dir() {
+            log::error!("❌ Storage directory error: {}", e);
+            return Vec::new();
+        }
+        
+        if !Path::new(&vault_file).exists() {
+            log::info!("ℹ️ No existing quantum vault file found");
+            return Vec::new();
+        }
+        
+        match std::fs::read_to_string(&vault_file) {
+            Ok(data) => {
+                log::info!("📄 Read {} bytes from quantum vault file", data.len());
+                match serde_json::from_str::<Vec<StoredVault>>(&data) {
+                    Ok(vaults) => {
+                        log::info!("✅ Successfully loaded {} quantum vaults", vaults.len());
+                        vaults
+                    }
+                    Err(e) => {
+                        log::error!("❌ Failed to parse quantum vaults: {}", e);
+                        Vec::new()
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("❌ Failed to read quantum vaults: {}", e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Mark a quantum vault as used after splitting
+pub fn mark_quantum_vault_as_used(vault_address: &str) {
+    log::info!("🔐 Marking quantum vault as used: {}", vault_address);
+    
+    let mut vaults = load_quantum_vaults_from_storage();
+    
+    if let Some(vault) = vaults.iter_mut().find(|v| v.address == vault_address) {
+        vault.used = true;
+        save_quantum_vaults_to_storage(&vaults);
+        log::info!("✅ Quantum vault marked as used");
+    } else {
+        log::warn!("⚠️ Quantum vault not found: {}", vault_address);
+    }
+}
+
+/// Delete a quantum vault from storage
+pub fn delete_quantum_vault_from_storage(vault_address: &str) {
+    log::info!("🔐 Attempting to delete quantum vault: {}", vault_address);
+    
+    let mut vaults = load_quantum_vaults_from_storage();
+    let original_count = vaults.len();
+    
+    vaults.retain(|vault| vault.address != vault_address);
+    
+    if vaults.len() < original_count {
+        log::info!("✅ Quantum vault {} removed from memory", vault_address);
+        save_quantum_vaults_to_storage(&vaults);
+        log::info!("✅ Quantum vault deletion completed. {} vaults remaining.", vaults.len());
+    } else {
+        log::warn!("⚠️ Quantum vault {} not found in storage", vault_address);
+    }
+}
+
+/// Save quantum vaults list to storage
+pub fn save_quantum_vaults_to_storage(vaults: &Vec<StoredVault>) {
+    log::info!("🔐 Saving {} quantum vaults to storage", vaults.len());
+    
+    if crate::pin::is_duress_session() {
+        if let Err(e) = save_json_dataset("quantum_vaults", vaults) {
+            log::error!("❌ {}", e);
+        }
+        return;
+    }
+    
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let serialized = serde_json::to_string(vaults).unwrap();
+        storage.set_item("quantum_vaults", &serialized).unwrap();
+        log::info!("✅ Quantum vaults saved to web storage");
+    }
+    
+    #[cfg(not(feature = "web"))]
+    {
+        match ensure_storage_dir() {
+            Ok(_) => {
+                let vault_file = get_quantum_vaults_file_path();
+                match serde_json::to_string_pretty(vaults) {
+                    Ok(serialized) => {
+                        match std::fs::write(&vault_file, &serialized) {
+                            Ok(_) => {
+                                log::info!("✅ Quantum vaults successfully saved to: {}", vault_file);
+                            }
+                            Err(e) => {
+                                log::error!("❌ Failed to write quantum vaults to {}: {}", vault_file, e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("❌ Failed to serialize quantum vaults: {}", e);
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("❌ Failed to ensure storage directory: {}", e);
+            }
+        }
+    }
+}
+// ══════════════════════════════════════════════════════════════════════════════
+// Generic JSON dataset helpers
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Datasets shared by the real and decoy profiles: device settings and
+/// market data. Every other dataset belongs to a profile, so a duress session
+/// (see `DuressPin`) starts from its own empty copy and never reads or
+/// changes the real one.
+const SHARED_DATASETS: &[&str] = &[
+    "rpc_fallbacks",
+    "rpc_headers",
+    "rpc_auto_select",
+    "network",
+    "app_tip",
+    "fee_bump_settings",
+    "price_providers",
+    "slippage",
+    "swap_providers",
+    "mev_protection",
+    "dismissed_update_version",
+    "usb_device_grants",
+    "notification_settings",
+    "notification_key",
+    "auto_lock",
+    "biometric_unlock",
+    "historical_prices",
+    DURESS_WALLETS,
+];
+
+/// Storage key of a dataset for the session's profile
+fn profile_dataset_key(key: &str) -> String {
+    let shared = SHARED_DATASETS.contains(&key) || key.starts_with("candles_");
+    if crate::pin::is_duress_session() && !shared {
+        format!("duress_{}", key)
+    } else {
+        key.to_string()
+    }
+}
+
+/// The database holds the real profile's history and names; a duress
+/// session keeps its own in datasets
+#[cfg(not(feature = "web"))]
+fn profile_db_available() -> bool {
+    db::is_available() && !crate::pin::is_duress_session()
+}
+
+/// Load a JSON dataset stored under `key` (`{key}.json` on disk), or its default
+fn load_json_dataset<T: serde::de::DeserializeOwned + Default>(key: &str) -> T {
+    let key = &profile_dataset_key(key);
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        storage.get_item(key)
+            .unwrap()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    #[cfg(not(feature = "web"))]
+    {
+        let file = format!("{}/{}.json", get_storage_dir_simple(), key);
+        match std::fs::read_to_string(&file) {
+            Ok(data) => match serde_json::from_str(&data) {
+                Ok(value) => value,
+                Err(e) => {
+                    log::error!("❌ Failed to parse {}: {}", file, e);
+                    T::default()
+                }
+            },
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    log::error!("❌ Failed to read {}: {}", file, e);
+                }
+                T::default()
+            }
+        }
+    }
+}
+
+/// Save a JSON dataset under `key` (`{key}.json` on disk)
+fn save_json_dataset<T: Serialize>(key: &str, value: &T) -> Result<(), String> {
+    let key = &profile_dataset_key(key);
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().unwrap();
+        let storage = window.local_storage().unwrap().unwrap();
+        let serialized = serde_json::to_string(value)
+            .map_err(|e| format!("Failed to serialize {}: {}", key, e))?;
+        storage.set_item(key, &serialized)
+            .map_err(|_| format!("Failed to save {} to web storage", key))?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "web"))]
+    {
+        ensure_storage_dir()
+            .map_err(|e| format!("Failed to ensure storage directory: {}", e))?;
+        let file = format!("{}/{}.json", get_storage_dir_simple(), key);
+        let serialized = serde_json::to_string_pretty(value)
+            .map_err(|e| format!("Failed to serialize {}: {}", key, e))?;
+        std::fs::write(&file, serialized)
+            .map_err(|e| format!("Failed to write {}: {}", file, e))?;
+        log::info!("✅ Saved {}", file);
+        Ok(())
+    }
+}
+
+/// A dataset as raw JSON, for copying settings in and out of backups
+pub fn load_dataset_value(key: &str) -> Option<serde_json::Value> {
+    load_json_dataset(key)
+}
+
+pub fn save_dataset_value(key: &str, value: &serde_json::Value) -> Result<(), String> {
+    save_json_dataset(key, value)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Long-running Operation Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Load all persisted multi-step operations
+pub fn load_operations_from_storage() -> Vec<crate::operations::Operation> {
+    load_json_dataset("operations")
+}
+
+/// Insert or replace a multi-step operation by id
+pub fn save_operation_to_storage(operation: &crate::operations::Operation) {
+    let mut operations = load_operations_from_storage();
+    match operations.iter_mut().find(|o| o.id == operation.id) {
+        Some(existing) => *existing = operation.clone(),
+        None => operations.push(operation.clone()),
+    }
+    if let Err(e) = save_json_dataset("operations", &operations) {
+        log::error!("❌ Failed to save operation {}: {}", operation.id, e);
+    }
+}
+
+/// Remove a multi-step operation by id
+pub fn delete_operation_from_storage(operation_id: &str) {
+    let mut operations = load_operations_from_storage();
+    operations.retain(|o| o.id != operation_id);
+    if let Err(e) = save_json_dataset("operations", &operations) {
+        log::error!("❌ Failed to delete operation {}: {}", operation_id, e);
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Passkey-protected Wallet Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+const PASSKEY_KEYS: &str = "passkey_keys";
+
+/// Load all passkey-wrapped keypairs
+pub fn load_passkey_wrapped_keys() -> Vec<crate::signing::passkey::WrappedKey> {
+    load_json_dataset(PASSKEY_KEYS)
+}
+
+pub fn load_auto_lock_settings() -> crate::auto_lock::AutoLockSettings {
+    load_json_dataset("auto_lock")
+}
+
+pub fn save_auto_lock_settings(settings: &crate::auto_lock::AutoLockSettings) -> Result<(), String> {
+    save_json_dataset("auto_lock", settings)?;
+    crate::auto_lock::set_current(*settings);
+    Ok(())
+}
+
+/// Vault key wrapped for biometric unlock, if enabled
+pub fn load_biometric_unlock() -> Option<crate::biometric::BiometricUnlock> {
+    load_json_dataset("biometric_unlock")
+}
+
+/// Store or, with `None`, remove the biometric unlock
+pub fn save_biometric_unlock(unlock: Option<&crate::biometric::BiometricUnlock>) -> Result<(), String> {
+    save_json_dataset("biometric_unlock", &unlock)
+}
+
+/// Passkey-wrapped keypair for a wallet, if it has been protected
+pub fn load_passkey_wrapped_key(wallet_address: &str) -> Option<crate::signing::passkey::WrappedKey> {
+    load_passkey_wrapped_keys()
+        .into_iter()
+        .find(|k| k.address == wallet_address)
+}
+
+/// Store a passkey-wrapped keypair and remove the plain key from the wallet list
+pub fn save_passkey_wrapped_key(wrapped: &crate::signing::passkey::WrappedKey) -> Result<(), String> {
+    let mut keys = load_passkey_wrapped_keys();
+    keys.retain(|k| k.address != wrapped.address);
+    keys.push(wrapped.clone());
+    save_json_dataset(PASSKEY_KEYS, &keys)?;
+
+    // Only drop the plain key once the wrapped copy is safely written
+    let mut wallets = load_wallets_from_storage();
+    for wallet in wallets.iter_mut().filter(|w| w.address == wrapped.address) {
+        wallet.encrypted_key.clear();
+    }
+    save_wallets_to_storage(&wallets);
+
+    log::info!("🔐 Wallet {} is now passkey protected", wrapped.address);
+    Ok(())
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Shared (MPC) Wallet Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+const MPC_SHARES: &str = "mpc_shares";
+
+fn mpc_share_secret_name(group_address: &str) -> String {
+    format!("mpc_share_{}", group_address)
+}
+
+/// Load this device's key shares of shared wallets
+pub fn load_mpc_key_shares() -> Vec<crate::signing::mpc::MpcKeyShare> {
+    let mut shares: Vec<crate::signing::mpc::MpcKeyShare> = load_json_dataset(MPC_SHARES);
+    for share in shares.iter_mut() {
+        reveal_secret(&mut share.secret_share, &mpc_share_secret_name(&share.group_address));
+    }
+    shares
+}
+
+/// Store key shares with their secrets sealed like wallet keys
+fn save_mpc_key_shares(mut shares: Vec<crate::signing::mpc::MpcKeyShare>) -> Result<(), String> {
+    for share in shares.iter_mut() {
+        protect_secret(&mut share.secret_share, &mpc_share_secret_name(&share.group_address));
+    }
+    save_json_dataset(MPC_SHARES, &shares)
+}
+
+/// This device's key share for a shared wallet, if it holds one
+pub fn load_mpc_key_share(wallet_address: &str) -> Option<crate::signing::mpc::MpcKeyShare> {
+    load_mpc_key_shares()
+        .into_iter()
+        .find(|s| s.group_address == wallet_address)
+}
+
+/// Store a key share and list the shared wallet alongside the others
+pub fn save_mpc_key_share(share: &crate::signing::mpc::MpcKeyShare) -> Result<(), String> {
+    let mut shares = load_mpc_key_shares();
+    shares.retain(|s| s.group_address != share.group_address);
+    shares.push(share.clone());
+    save_mpc_key_shares(shares)?;
+
+    // The wallet entry carries no key; signing goes through the share
+    if !load_wallets_from_storage().iter().any(|w| w.address == share.group_address) {
+        save_wallet_to_storage(&WalletInfo {
+            name: share.name.clone(),
+            address: share.group_address.clone(),
+            encrypted_key: String::new(),
+            passphrase_protected: false,
+        });
+    }
+
+    log::info!("🔑 Saved key share for shared wallet {}", share.group_address);
+    Ok(())
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Remote (paired device) Wallet Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Load wallets whose keys live on the paired device
+pub fn load_remote_wallets() -> Vec<crate::bridge::RemoteWallet> {
+    load_json_dataset("remote_wallets")
+}
+
+/// Remote wallet entry for an address, if the key lives on the paired device
+pub fn load_remote_wallet(wallet_address: &str) -> Option<crate::bridge::RemoteWallet> {
+    load_remote_wallets()
+        .into_iter()
+        .find(|w| w.address == wallet_address)
+}
+
+/// Remember a wallet of the paired device and list it alongside the others
+pub fn save_remote_wallet(wallet: &crate::bridge::RemoteWallet) -> Result<(), String> {
+    // A wallet with its key on this device must keep signing locally
+    if let Some(local) = load_wallets_from_storage().iter().find(|w| w.address == wallet.address) {
+        if !local.encrypted_key.is_empty() || load_passkey_wrapped_key(&local.address).is_some() {
+            return Err(format!("{} is already a local wallet", wallet.address));
+        }
+    }
+
+    let mut wallets = load_remote_wallets();
+    wallets.retain(|w| w.address != wallet.address);
+    wallets.push(wallet.clone());
+    save_json_dataset("remote_wallets", &wallets)?;
+
+    if !load_wallets_from_storage().iter().any(|w| w.address == wallet.address) {
+        save_wallet_to_storage(&WalletInfo {
+            name: format!("{} ({})", wallet.name, wallet.device),
+            address: wallet.address.clone(),
+            encrypted_key: String::new(),
+            passphrase_protected: false,
+        });
+    }
+
+    log::info!("📲 Saved remote wallet {} from {}", wallet.address, wallet.device);
+    Ok(())
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Wallet Profile (notes / risk tier / organization) Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Load notes and risk tiers of all wallets that have one set
+pub fn load_wallet_profiles() -> Vec<crate::policy::WalletProfile> {
+    load_json_dataset("wallet_profiles")
+}
+
+/// Profile of a wallet; wallets without one are hot with no notes
+pub fn load_wallet_profile(wallet_address: &str) -> crate::policy::WalletProfile {
+    load_wallet_profiles()
+        .into_iter()
+        .find(|p| p.address == wallet_address)
+        .unwrap_or_else(|| crate::policy::WalletProfile::new(wallet_address))
+}
+
+/// Store (or replace) a wallet's profile
+pub fn save_wallet_profile(profile: &crate::policy::WalletProfile) -> Result<(), String> {
+    let mut profiles = load_wallet_profiles();
+    profiles.retain(|p| p.address != profile.address);
+    profiles.push(profile.clone());
+    save_json_dataset("wallet_profiles", &profiles)?;
+    log::info!("🏷️ Saved {} tier for wallet {}", profile.tier.label(), profile.address);
+    Ok(())
+}
+
+/// Store the wallet list order, given every wallet's address in order
+pub fn save_wallet_order(addresses: &[String]) -> Result<(), String> {
+    let mut profiles = load_wallet_profiles();
+    for (position, address) in addresses.iter().enumerate() {
+        match profiles.iter_mut().find(|p| &p.address == address) {
+            Some(profile) => profile.position = Some(position as u32),
+            None => profiles.push(crate::policy::WalletProfile {
+                position: Some(position as u32),
+                ..crate::policy::WalletProfile::new(address)
+            }),
+        }
+    }
+    save_json_dataset("wallet_profiles", &profiles)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Fee Bump Settings Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn load_fee_bump_settings() -> crate::submission::FeeBumpSettings {
+    load_json_dataset("fee_bump_settings")
+}
+
+pub fn save_fee_bump_settings(settings: &crate::submission::FeeBumpSettings) -> Result<(), String> {
+    save_json_dataset("fee_bump_settings", settings)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Fee Sponsor Settings Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn load_fee_sponsor_settings() -> crate::signing::fee_payer::FeeSponsorSettings {
+    load_json_dataset("fee_sponsor")
+}
+
+pub fn save_fee_sponsor_settings(settings: &crate::signing::fee_payer::FeeSponsorSettings) -> Result<(), String> {
+    save_json_dataset("fee_sponsor", settings)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Session Key Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+const SESSION_KEYS: &str = "session_keys";
+
+fn session_key_secret_name(owner: &str) -> String {
+    format!("session_key_{}", owner)
+}
+
+fn load_session_keys() -> Vec<crate::signing::session::SessionKey> {
+    let mut sessions: Vec<crate::signing::session::SessionKey> = load_json_dataset(SESSION_KEYS);
+    for session in sessions.iter_mut() {
+        reveal_secret(&mut session.secret, &session_key_secret_name(&session.owner));
+    }
+    sessions
+}
+
+/// Store session keys with their secrets sealed like wallet keys
+fn save_session_keys(mut sessions: Vec<crate::signing::session::SessionKey>) -> Result<(), String> {
+    for session in sessions.iter_mut() {
+        protect_secret(&mut session.secret, &session_key_secret_name(&session.owner));
+    }
+    save_json_dataset(SESSION_KEYS, &sessions)
+}
+
+/// The session key of a wallet, if one has been started
+pub fn load_session_key(owner: &str) -> Option<crate::signing::session::SessionKey> {
+    load_session_keys().into_iter().find(|s| s.owner == owner)
+}
+
+/// Store (or replace) a wallet's session key; a wallet has at most one
+pub fn save_session_key(session: &crate::signing::session::SessionKey) -> Result<(), String> {
+    let mut sessions = load_session_keys();
+    sessions.retain(|s| s.owner != session.owner);
+    sessions.push(session.clone());
+    save_session_keys(sessions)
+}
+
+pub fn remove_session_key(owner: &str) -> Result<(), String> {
+    let mut sessions = load_session_keys();
+    sessions.retain(|s| s.owner != owner);
+    save_session_keys(sessions)?;
+    forget_secret(&session_key_secret_name(owner));
+    log::info!("🔑 Removed session key for {}", owner);
+    Ok(())
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Update Checker Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Release the user chose not to be reminded about again
+pub fn load_dismissed_update_version() -> Option<String> {
+    load_json_dataset("dismissed_update_version")
+}
+
+pub fn save_dismissed_update_version(version: &str) -> Result<(), String> {
+    save_json_dataset("dismissed_update_version", &Some(version.to_string()))
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Historical Price Cache Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Cached USD daily close for `symbol` on `day` ("YYYY-MM-DD")
+pub fn load_historical_price(symbol: &str, day: &str) -> Option<f64> {
+    #[cfg(not(feature = "web"))]
+    if db::is_available() {
+        return db::historical_price(symbol, day).unwrap_or_else(|e| {
+            log::error!("❌ Failed to load historical price: {}", e);
+            None
+        });
+    }
+    let prices: Vec<crate::balance_history::HistoricalPrice> = load_json_dataset("historical_prices");
+    prices.into_iter().find(|p| p.symbol == symbol && p.day == day).map(|p| p.usd)
+}
+
+/// Past closes never change, so entries are kept indefinitely
+pub fn save_historical_price(price: &crate::balance_history::HistoricalPrice) -> Result<(), String> {
+    #[cfg(not(feature = "web"))]
+    if db::is_available() {
+        return db::save_historical_price(price);
+    }
+    let mut prices: Vec<crate::balance_history::HistoricalPrice> = load_json_dataset("historical_prices");
+    prices.retain(|p| !(p.symbol == price.symbol && p.day == price.day));
+    prices.push(price.clone());
+    save_json_dataset("historical_prices", &prices)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// USB Device Grant Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn load_usb_device_grants() -> Vec<crate::hardware::usb_grants::UsbDeviceGrant> {
+    load_json_dataset("usb_device_grants")
+}
+
+/// Remember a device the user granted access to, replacing an older grant for it
+pub fn save_usb_device_grant(grant: &crate::hardware::usb_grants::UsbDeviceGrant) -> Result<(), String> {
+    let mut grants = load_usb_device_grants();
+    grants.retain(|g| !g.matches(grant.vendor_id, grant.product_id, grant.serial_number.as_deref()));
+    grants.push(grant.clone());
+    save_json_dataset("usb_device_grants", &grants)
+}
+
+pub fn remove_usb_device_grant(grant: &crate::hardware::usb_grants::UsbDeviceGrant) -> Result<(), String> {
+    let mut grants = load_usb_device_grants();
+    grants.retain(|g| g != grant);
+    save_json_dataset("usb_device_grants", &grants)?;
+    log::info!("🔌 Forgot USB device {}", grant.label());
+    Ok(())
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Notification Channel Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn load_notification_settings() -> crate::notifications::NotificationSettings {
+    load_json_dataset("notification_settings")
+}
+
+pub fn save_notification_settings(settings: &crate::notifications::NotificationSettings) -> Result<(), String> {
+    save_json_dataset("notification_settings", settings)
+}
+
+/// Local key that seals notification channel secrets; kept apart from the
+/// settings so the sealed secrets are useless on their own
+pub fn load_or_create_notification_key() -> Result<[u8; 32], String> {
+    let decode = |hex_key: String| hex::decode(hex_key).ok().and_then(|b| b.try_into().ok());
+    let in_secret_store = secrets::is_available();
+    if in_secret_store {
+        if let Some(key) = secrets::get_secret("notification_key")?.and_then(decode) {
+            return Ok(key);
+        }
+    }
+    let stored: Option<String> = load_json_dataset("notification_key");
+    let key = match stored.and_then(decode) {
+        Some(key) => key,
+        None => {
+            let mut key = [0u8; 32];
+            rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut key);
+            log::info!("🔐 Created notification secret key");
+            key
+        }
+    };
+    if in_secret_store {
+        // Move the key out of app storage
+        secrets::set_secret("notification_key", &hex::encode(key))?;
+        save_json_dataset("notification_key", &None::<String>)?;
+    } else {
+        save_json_dataset("notification_key", &Some(hex::encode(key)))?;
+    }
+    Ok(key)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Offline Outbox Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn load_outbox() -> Vec<crate::transaction::OutboxItem> {
+    load_json_dataset("outbox")
+}
+
+/// Add an outbox item, or update the one with the same signature
+pub fn save_outbox_item(item: &crate::transaction::OutboxItem) -> Result<(), String> {
+    let mut outbox = load_outbox();
+    match outbox.iter_mut().find(|i| i.signature == item.signature) {
+        Some(existing) => *existing = item.clone(),
+        None => outbox.push(item.clone()),
+    }
+    save_json_dataset("outbox", &outbox)
+}
+
+/// Stop a queued transaction from being broadcast
+pub fn cancel_outbox_item(signature: &str) -> Result<(), String> {
+    let mut outbox = load_outbox();
+    for item in outbox.iter_mut().filter(|i| i.signature == signature && i.is_pending()) {
+        item.status = crate::transaction::OutboxStatus::Cancelled;
+    }
+    save_json_dataset("outbox", &outbox)?;
+    log::info!("🚫 Cancelled queued transaction {}", signature);
+    Ok(())
+}
+
+/// Drop every item that is no longer waiting
+pub fn clear_finished_outbox_items() -> Result<(), String> {
+    let mut outbox = load_outbox();
+    outbox.retain(|i| i.is_pending());
+    save_json_dataset("outbox", &outbox)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Transaction History Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn load_history_index(address: &str) -> crate::history::HistoryIndex {
+    #[cfg(not(feature = "web"))]
+    if profile_db_available() {
+        return db::history_index(address)
+            .unwrap_or_else(|e| {
+                log::error!("❌ Failed to load history of {}: {}", address, e);
+                None
+            })
+            .unwrap_or_default();
+    }
+    load_json_dataset(&format!("history_{}", address))
+}
+
+pub fn save_history_index(index: &crate::history::HistoryIndex) -> Result<(), String> {
+    #[cfg(not(feature = "web"))]
+    if profile_db_available() {
+        return db::save_history_index(index);
+    }
+    save_json_dataset(&format!("history_{}", index.address), index)
+}
+
+/// Parsed transactions of an address, most recently viewed first
+pub fn load_cached_transactions(address: &str) -> Vec<crate::history::CachedTransaction> {
+    #[cfg(not(feature = "web"))]
+    if profile_db_available() {
+        return db::cached_transactions(address).unwrap_or_else(|e| {
+            log::error!("❌ Failed to load cached transactions of {}: {}", address, e);
+            Vec::new()
+        });
+    }
+    load_json_dataset(&format!("history_details_{}", address))
+}
+
+pub fn save_cached_transactions(address: &str, cached: &[crate::history::CachedTransaction]) -> Result<(), String> {
+    #[cfg(not(feature = "web"))]
+    if profile_db_available() {
+        return db::save_cached_transactions(address, cached);
+    }
+    save_json_dataset(&format!("history_details_{}", address), &cached)
+}
+
+/// User-entered transaction labels, by signature
+pub fn load_transaction_labels() -> std::collections::HashMap<String, String> {
+    load_json_dataset("transaction_labels")
+}
+
+pub fn save_transaction_labels(labels: &std::collections::HashMap<String, String>) -> Result<(), String> {
+    save_json_dataset("transaction_labels", labels)
+}
+
+/// User-entered transaction notes, by signature
+pub fn load_transaction_notes() -> std::collections::HashMap<String, String> {
+    load_json_dataset("transaction_notes")
+}
+
+pub fn save_transaction_notes(notes: &std::collections::HashMap<String, String>) -> Result<(), String> {
+    save_json_dataset("transaction_notes", notes)
+}
+
+/// User-entered names for addresses, by address
+pub fn load_address_labels() -> std::collections::HashMap<String, String> {
+    #[cfg(not(feature = "web"))]
+    if profile_db_available() {
+        return db::address_labels().unwrap_or_else(|e| {
+            log::error!("❌ Failed to load address names: {}", e);
+            Default::default()
+        });
+    }
+    load_json_dataset("address_labels")
+}
+
+pub fn save_address_labels(labels: &std::collections::HashMap<String, String>) -> Result<(), String> {
+    #[cfg(not(feature = "web"))]
+    if profile_db_available() {
+        return db::save_address_labels(labels);
+    }
+    save_json_dataset("address_labels", labels)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Goals & Budgets Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn load_budget_settings() -> crate::budget::BudgetSettings {
+    load_json_dataset("budgets")
+}
+
+pub fn save_budget_settings(settings: &crate::budget::BudgetSettings) -> Result<(), String> {
+    save_json_dataset("budgets", settings)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Candle Cache Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Chart candles of a symbol for one timeframe, oldest first
+pub fn load_candles(symbol: &str, timeframe: &str) -> Vec<crate::prices::CandlestickData> {
+    #[cfg(not(feature = "web"))]
+    if db::is_available() {
+        return db::candles(symbol, timeframe).unwrap_or_else(|e| {
+            log::error!("❌ Failed to load {} {} candles: {}", symbol, timeframe, e);
+            Vec::new()
+        });
+    }
+    load_json_dataset(&format!("candles_{}_{}", symbol, timeframe))
+}
+
+pub fn save_candles(symbol: &str, timeframe: &str, candles: &[crate::prices::CandlestickData]) -> Result<(), String> {
+    #[cfg(not(feature = "web"))]
+    if db::is_available() {
+        return db::save_candles(symbol, timeframe, candles);
+    }
+    save_json_dataset(&format!("candles_{}_{}", symbol, timeframe), &candles)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Price Provider Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+pub fn load_price_provider_settings() -> crate::prices::PriceProviderSettings {
+    load_json_dataset("price_providers")
+}
+
+pub fn save_price_provider_settings(settings: &crate::prices::PriceProviderSettings) -> Result<(), String> {
+    save_json_dataset("price_providers", settings)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Swap Settings, History, Limit Order and DCA Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Limit orders placed from a wallet, newest first
+pub fn load_limit_orders(address: &str) -> Vec<crate::swap::LimitOrder> {
+    load_json_dataset(&format!("limit_orders_{}", address))
+}
+
+pub fn save_limit_orders(address: &str, orders: &[crate::swap::LimitOrder]) -> Result<(), String> {
+    save_json_dataset(&format!("limit_orders_{}", address), &orders)
+}
+
+/// Slippage used by every swap quote and transaction
+pub fn load_slippage_setting() -> crate::swap::Slippage {
+    load_json_dataset("slippage")
+}
+
+pub fn save_slippage_setting(slippage: &crate::swap::Slippage) -> Result<(), String> {
+    save_json_dataset("slippage", slippage)
+}
+
+/// Swaps sent from a wallet, newest first
+pub fn load_swap_history(address: &str) -> Vec<crate::swap::SwapRecord> {
+    load_json_dataset(&format!("swap_history_{}", address))
+}
+
+pub fn save_swap_history(address: &str, history: &[crate::swap::SwapRecord]) -> Result<(), String> {
+    save_json_dataset(&format!("swap_history_{}", address), &history)
+}
+
+/// Swap aggregators that are disabled or pinned
+pub fn load_swap_provider_settings() -> crate::swap::SwapProviderSettings {
+    load_json_dataset("swap_providers")
+}
+
+pub fn save_swap_provider_settings(settings: &crate::swap::SwapProviderSettings) -> Result<(), String> {
+    save_json_dataset("swap_providers", settings)
+}
+
+/// Send swaps as Jito bundles instead of through the RPC
+pub fn load_mev_protection() -> bool {
+    load_json_dataset("mev_protection")
+}
+
+pub fn save_mev_protection(enabled: bool) -> Result<(), String> {
+    save_json_dataset("mev_protection", &enabled)
+}
+
+/// DCA schedules of a wallet, with the buys they have made
+pub fn load_dca_schedules(address: &str) -> Vec<crate::swap::DcaSchedule> {
+    load_json_dataset(&format!("dca_schedules_{}", address))
+}
+
+pub fn save_dca_schedules(address: &str, schedules: &[crate::swap::DcaSchedule]) -> Result<(), String> {
+    save_json_dataset(&format!("dca_schedules_{}", address), &schedules)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Cost Basis Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Positions from the last cost basis computation of a wallet
+pub fn load_cost_basis(address: &str) -> Option<crate::cost_basis::CostBasisReport> {
+    load_json_dataset(&format!("cost_basis_{}", address))
+}
+
+pub fn save_cost_basis(report: &crate::cost_basis::CostBasisReport) -> Result<(), String> {
+    save_json_dataset(&format!("cost_basis_{}", report.wallet), report)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Imported Stake Account Storage Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Stake accounts added to a wallet by address
+pub fn load_imported_stake_accounts(address: &str) -> Vec<String> {
+    load_json_dataset(&format!("imported_stake_{}", address))
+}
+
+pub fn save_imported_stake_accounts(address: &str, accounts: &[String]) -> Result<(), String> {
+    save_json_dataset(&format!("imported_stake_{}", address), &accounts)
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// File Export Functions
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Write `contents` to `{storage_dir}/exports/{file_name}` and return the path
+pub fn save_export_file(file_name: &str, contents: &str) -> Result<String, String> {
+    ensure_storage_dir()
+        .map_err(|e| format!("Failed to ensure storage directory: {}", e))?;
+    let dir = format!("{}/exports", get_storage_dir_simple());
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir, e))?;
+    let path = format!("{}/{}", dir, file_name);
+    std::fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    log::info!("✅ Exported {}", path);
+    Ok(path)
+}
+
+/// Read back a file previously exported or copied into the exports folder
+pub fn read_export_file(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pin::{generate_salt, lock_vault, set_duress_session, unlock_vault, VaultKey, TEST_PARAMS};
+    use crate::wallet::Wallet;
+    use std::sync::Mutex;
+
+    /// The vault key and duress flag are process-wide, so storage tests take turns
+    static SESSION: Mutex<()> = Mutex::new(());
+
+    fn use_test_storage_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("unruggable-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        TEST_STORAGE_DIR.with(|test_dir| *test_dir.borrow_mut() = Some(dir.to_string_lossy().to_string()));
+        dir
+    }
+
+    #[test]
+    fn test_duress_wallet_add_keeps_real_profile() {
+        let _session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = use_test_storage_dir("duress");
+
+        unlock_vault(VaultKey::derive("111111", &generate_salt(), TEST_PARAMS).unwrap());
+        let real = Wallet::new("Real".to_string()).to_wallet_info();
+        save_wallet_to_storage(&real);
+        let wallets_file = std::fs::read_to_string(get_wallets_file_path()).unwrap();
+        let real_secret = secrets::get_secret(&wallet_key_secret_name(&real.address)).unwrap();
+        assert!(real_secret.is_some());
+
+        // Adding a wallet in a duress session only touches the decoy profile
+        unlock_vault(VaultKey::derive("999999", &generate_salt(), TEST_PARAMS).unwrap());
+        set_duress_session(true);
+        let decoy = Wallet::new("Decoy".to_string()).to_wallet_info();
+        save_wallet_to_storage(&decoy);
+        let addresses: Vec<String> = load_wallets_from_storage().into_iter().map(|w| w.address).collect();
+        assert_eq!(addresses, vec![decoy.address.clone()]);
+        lock_vault();
+
+        assert_eq!(std::fs::read_to_string(get_wallets_file_path()).unwrap(), wallets_file);
+        assert_eq!(secrets::get_secret(&wallet_key_secret_name(&real.address)).unwrap(), real_secret);
+        assert_eq!(secrets::get_secret(&wallet_key_secret_name(&decoy.address)).unwrap(), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_unreadable_stashed_key_keeps_marker() {
+        let _session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = use_test_storage_dir("stashed");
+        lock_vault();
+
+        let mut wallet = Wallet::new("Stashed".to_string()).to_wallet_info();
+        wallet.encrypted_key = STASHED_KEY_MARKER.to_string();
+        save_wallets_to_storage(&vec![wallet.clone()]);
+
+        // No secret behind the marker: the wallet loads without a key and
+        // saving it again keeps the marker
+        let loaded = load_wallets_from_storage();
+        assert!(is_unreadable_stashed_key(&loaded[0].encrypted_key));
+        save_wallets_to_storage(&loaded);
+        assert_eq!(load_wallets_from_storage(), vec![wallet]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_key_shares_and_session_keys_are_sealed() {
+        let _session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = use_test_storage_dir("shares");
+        lock_vault();
+
+        // Stored raw, as before they were sealed
+        let owner = Wallet::new("Owner".to_string()).get_public_key();
+        let share = crate::signing::mpc::MpcKeyShare {
+            group_address: owner.clone(),
+            name: "Shared".to_string(),
+            peer_name: "Phone".to_string(),
+            secret_share: "c2VjcmV0IHNoYXJl".to_string(),
+            own_public: String::new(),
+            peer_public: String::new(),
+        };
+        let session = crate::signing::session::SessionKey::generate(&owner, 0, 3_600, 1_000, 1_000, Vec::new());
+        save_json_dataset(MPC_SHARES, &vec![share.clone()]).unwrap();
+        save_json_dataset(SESSION_KEYS, &vec![session.clone()]).unwrap();
+
+        // Unlocking seals them into the secret store
+        open_vault(VaultKey::derive("111111", &generate_salt(), TEST_PARAMS).unwrap(), false);
+        let stored_shares: Vec<crate::signing::mpc::MpcKeyShare> = load_json_dataset(MPC_SHARES);
+        let stored_sessions: Vec<crate::signing::session::SessionKey> = load_json_dataset(SESSION_KEYS);
+        assert_eq!(stored_shares[0].secret_share, STASHED_KEY_MARKER);
+        assert_eq!(stored_sessions[0].secret, STASHED_KEY_MARKER);
+        let stashed = secrets::get_secret(&session_key_secret_name(&owner)).unwrap().unwrap();
+        assert!(crate::pin::is_sealed_key(&stashed));
+
+        assert_eq!(load_mpc_key_share(&owner), Some(share));
+        assert_eq!(load_session_key(&owner), Some(session));
+
+        remove_session_key(&owner).unwrap();
+        assert_eq!(secrets::get_secret(&session_key_secret_name(&owner)).unwrap(), None);
+        lock_vault();
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_wipe_removes_every_secret() {
+        let _session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = use_test_storage_dir("wipe");
+        unlock_vault(VaultKey::derive("111111", &generate_salt(), TEST_PARAMS).unwrap());
+
+        let software = Wallet::new("Software".to_string()).to_wallet_info();
+        let watch_only = WalletInfo { encrypted_key: String::new(), ..Wallet::new("Watched".to_string()).to_wallet_info() };
+        save_wallets_to_storage(&vec![software.clone(), watch_only.clone()]);
+        let shared = Wallet::new("Shared".to_string()).get_public_key();
+        save_mpc_key_share(&crate::signing::mpc::MpcKeyShare {
+            group_address: shared.clone(),
+            name: "Shared".to_string(),
+            peer_name: "Phone".to_string(),
+            secret_share: "c2VjcmV0IHNoYXJl".to_string(),
+            own_public: String::new(),
+            peer_public: String::new(),
+        })
+        .unwrap();
+        let session = crate::signing::session::SessionKey::generate(&software.address, 0, 3_600, 1_000, 1_000, Vec::new());
+        save_session_key(&session).unwrap();
+        for dataset in [MPC_SHARES, SESSION_KEYS, PASSKEY_KEYS] {
+            save_json_dataset(&format!("duress_{}", dataset), &vec![serde_json::json!({ "secret": "decoy" })]).unwrap();
+        }
+
+        wipe_wallets();
+
+        let addresses: Vec<String> = load_wallets_from_storage().into_iter().map(|w| w.address).collect();
+        assert_eq!(addresses, vec![watch_only.address]);
+        for dataset in [MPC_SHARES, SESSION_KEYS, PASSKEY_KEYS] {
+            assert!(load_json_dataset::<Vec<serde_json::Value>>(dataset).is_empty());
+            assert!(load_json_dataset::<Vec<serde_json::Value>>(&format!("duress_{}", dataset)).is_empty());
+        }
+        assert_eq!(secrets::get_secret(&wallet_key_secret_name(&software.address)).unwrap(), None);
+        assert_eq!(secrets::get_secret(&mpc_share_secret_name(&shared)).unwrap(), None);
+        assert_eq!(secrets::get_secret(&session_key_secret_name(&software.address)).unwrap(), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
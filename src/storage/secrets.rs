@@ -0,0 +1,233 @@
+// src/storage/secrets.rs
+//! Secrets kept in the platform's secret store instead of app files
+//!
+//! The Keychain on macOS and iOS, Credential Manager on Windows, the Secret
+//! Service on Linux and the Android Keystore. Where there is none, as in the
+//! web build or on a Linux desktop without a Secret Service daemon,
+//! `is_available` is false and callers keep secrets in app storage as before.
+
+use std::sync::OnceLock;
+
+const PROBE_NAME: &str = "probe";
+
+/// Whether the platform secret store works here. Checked once by writing,
+/// reading back and deleting a probe entry.
+pub fn is_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        let probe = || -> Result<bool, String> {
+            platform::set(PROBE_NAME, "ok")?;
+            let read = platform::get(PROBE_NAME)?;
+            platform::delete(PROBE_NAME)?;
+            Ok(read.as_deref() == Some("ok"))
+        };
+        match probe() {
+            Ok(available) => {
+                log::info!("🔐 Platform secret store available: {}", available);
+                available
+            }
+            Err(e) => {
+                log::warn!("⚠️ Platform secret store unavailable, keeping secrets in app storage: {}", e);
+                false
+            }
+        }
+    })
+}
+
+pub fn get_secret(name: &str) -> Result<Option<String>, String> {
+    platform::get(name)
+}
+
+pub fn set_secret(name: &str, value: &str) -> Result<(), String> {
+    platform::set(name, value)
+}
+
+/// Delete a secret. Deleting one that isn't there is not an error.
+pub fn delete_secret(name: &str) -> Result<(), String> {
+    platform::delete(name)
+}
+
+#[cfg(all(
+    not(test),
+    not(feature = "web"),
+    any(target_os = "macos", target_os = "ios", target_os = "windows", target_os = "linux")
+))]
+mod platform {
+    //! Keychain, Credential Manager and Secret Service through `keyring`
+
+    use keyring::{Entry, Error};
+
+    /// Service name secrets are filed under
+    const SERVICE: &str = "com.unruggable.app";
+
+    fn entry(name: &str) -> Result<Entry, String> {
+        Entry::new(SERVICE, name).map_err(|e| format!("Secret store error: {}", e))
+    }
+
+    pub fn get(name: &str) -> Result<Option<String>, String> {
+        match entry(name)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(Error::NoEntry) => Ok(None),
+            Err(e) => Err(format!("Failed to read secret {}: {}", name, e)),
+        }
+    }
+
+    pub fn set(name: &str, value: &str) -> Result<(), String> {
+        entry(name)?
+            .set_password(value)
+            .map_err(|e| format!("Failed to store secret {}: {}", name, e))
+    }
+
+    pub fn delete(name: &str) -> Result<(), String> {
+        match entry(name)?.delete_credential() {
+            Ok(()) | Err(Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Failed to delete secret {}: {}", name, e)),
+        }
+    }
+}
+
+#[cfg(all(not(test), not(feature = "web"), target_os = "android"))]
+mod platform {
+    //! Android Keystore backed store.
+    //!
+    //! Calls into `scripts/android/SecretStoreBridge.java`, which
+    //! `scripts/android.bundle.sh` adds to the Android project. The bridge
+    //! keeps an AES key in the Android Keystore and stores values encrypted
+    //! under it in private shared preferences, returning null for names it
+    //! doesn't hold or can't decrypt.
+
+    use dioxus::mobile::wry::prelude::dispatch;
+    use jni::objects::{GlobalRef, JObject, JString, JValue};
+    use jni::JavaVM;
+    use std::error::Error;
+
+    const BRIDGE_CLASS: &str = "com/unruggable/app/SecretStoreBridge";
+
+    /// Grab the JVM and a global ref to the activity from the UI thread
+    fn vm_and_activity() -> Result<(JavaVM, GlobalRef), Box<dyn Error>> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        dispatch(move |env, activity, _webview| {
+            let result = env
+                .get_java_vm()
+                .and_then(|vm| env.new_global_ref(activity).map(|activity| (vm, activity)));
+            tx.send(result).unwrap();
+        });
+        Ok(rx.recv()??)
+    }
+
+    pub fn get(name: &str) -> Result<Option<String>, String> {
+        let run = || -> Result<Option<String>, Box<dyn Error>> {
+            let (vm, activity) = vm_and_activity()?;
+            let mut env = vm.attach_current_thread()?;
+            let name = env.new_string(name)?;
+            let value: JString = env.call_static_method(
+                BRIDGE_CLASS,
+                "get",
+                "(Landroid/app/Activity;Ljava/lang/String;)Ljava/lang/String;",
+                &[JValue::Object(activity.as_obj()), JValue::Object(&name)],
+            )?.l()?.into();
+            if value.is_null() {
+                return Ok(None);
+            }
+            Ok(Some(env.get_string(&value)?.into()))
+        };
+        run().map_err(|e| format!("Failed to read secret: {}", e))
+    }
+
+    pub fn set(name: &str, value: &str) -> Result<(), String> {
+        let run = || -> Result<bool, Box<dyn Error>> {
+            let (vm, activity) = vm_and_activity()?;
+            let mut env = vm.attach_current_thread()?;
+            let name = env.new_string(name)?;
+            let value = env.new_string(value)?;
+            Ok(env.call_static_method(
+                BRIDGE_CLASS,
+                "set",
+                "(Landroid/app/Activity;Ljava/lang/String;Ljava/lang/String;)Z",
+                &[
+                    JValue::Object(activity.as_obj()),
+                    JValue::Object(&name),
+                    JValue::Object(&JObject::from(value)),
+                ],
+            )?.z()?)
+        };
+        match run() {
+            Ok(true) => Ok(()),
+            Ok(false) => Err("Android Keystore refused the secret".to_string()),
+            Err(e) => Err(format!("Failed to store secret: {}", e)),
+        }
+    }
+
+    pub fn delete(name: &str) -> Result<(), String> {
+        let run = || -> Result<(), Box<dyn Error>> {
+            let (vm, activity) = vm_and_activity()?;
+            let mut env = vm.attach_current_thread()?;
+            let name = env.new_string(name)?;
+            env.call_static_method(
+                BRIDGE_CLASS,
+                "delete",
+                "(Landroid/app/Activity;Ljava/lang/String;)V",
+                &[JValue::Object(activity.as_obj()), JValue::Object(&name)],
+            )?;
+            Ok(())
+        };
+        run().map_err(|e| format!("Failed to delete secret: {}", e))
+    }
+}
+
+#[cfg(all(
+    not(test),
+    any(
+        feature = "web",
+        not(any(
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "windows",
+            target_os = "linux",
+            target_os = "android"
+        ))
+    )
+))]
+mod platform {
+    //! No secret store on this platform
+
+    const UNAVAILABLE: &str = "No platform secret store";
+
+    pub fn get(_name: &str) -> Result<Option<String>, String> {
+        Err(UNAVAILABLE.to_string())
+    }
+
+    pub fn set(_name: &str, _value: &str) -> Result<(), String> {
+        Err(UNAVAILABLE.to_string())
+    }
+
+    pub fn delete(_name: &str) -> Result<(), String> {
+        Err(UNAVAILABLE.to_string())
+    }
+}
+
+#[cfg(test)]
+mod platform {
+    //! In-memory store so tests never touch the developer's keychain
+
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    static SECRETS: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
+
+    pub fn get(name: &str) -> Result<Option<String>, String> {
+        Ok(SECRETS.lock().unwrap().as_ref().and_then(|secrets| secrets.get(name).cloned()))
+    }
+
+    pub fn set(name: &str, value: &str) -> Result<(), String> {
+        SECRETS.lock().unwrap().get_or_insert_with(HashMap::new).insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    pub fn delete(name: &str) -> Result<(), String> {
+        if let Some(secrets) = SECRETS.lock().unwrap().as_mut() {
+            secrets.remove(name);
+        }
+        Ok(())
+    }
+}
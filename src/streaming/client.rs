@@ -0,0 +1,645 @@
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signature as SolanaSignature, Signer},
+    transaction::VersionedTransaction,
+    message::VersionedMessage,
+    instruction::{AccountMeta, Instruction},
+    system_instruction,
+    system_program,
+    sysvar,
+};
+use sha2::{Digest, Sha256};
+use std::error::Error as StdError;
+use std::str::FromStr;
+use serde_json::{json, Value};
+use reqwest::Client as HttpClient;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use crate::signing::{TransactionSigner, sign_transaction_slot, place_signature};
+use crate::streaming::types::{CreateStreamParams, CreateStreamResult, StreamInfo};
+use crate::storage::get_current_jito_settings;
+use crate::transaction::TransactionClient;
+
+type Result<T> = std::result::Result<T, Box<dyn StdError>>;
+
+// Streamflow community program and fee accounts
+const STREAMFLOW_PROGRAM_ID: &str = "strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUxfmKg5m";
+const STREAMFLOW_TREASURY: &str = "5SEpbdjFK5FxwTvfsGMXVQTD2v4M2c5tyRTxhdsPkgDw";
+const STREAMFLOW_WITHDRAWOR: &str = "wdrwhnCv4pzW8beKsbPa4S2UDZrhenjg16KJdKSpb5u";
+const STREAMFLOW_FEE_ORACLE: &str = "B743wFVk2pCYhV91cn287e1xY7f1vt4gdY48hhNiuQmT";
+
+// Token-2022 Program ID
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+// Stream metadata layout offsets
+const OFFSET_CREATED_AT: usize = 9;
+const OFFSET_WITHDRAWN_AMOUNT: usize = 17;
+const OFFSET_CANCELED_AT: usize = 25;
+const OFFSET_END_TIME: usize = 33;
+const OFFSET_SENDER: usize = 49;
+const OFFSET_SENDER_TOKENS: usize = 81;
+const OFFSET_RECIPIENT: usize = 113;
+const OFFSET_RECIPIENT_TOKENS: usize = 145;
+const OFFSET_MINT: usize = 177;
+const OFFSET_ESCROW_TOKENS: usize = 209;
+const OFFSET_PARTNER: usize = 325;
+const OFFSET_PARTNER_TOKENS: usize = 357;
+const OFFSET_START_TIME: usize = 409;
+const OFFSET_NET_DEPOSITED: usize = 417;
+const OFFSET_PERIOD: usize = 425;
+const OFFSET_AMOUNT_PER_PERIOD: usize = 433;
+const OFFSET_CLIFF: usize = 441;
+const OFFSET_CLIFF_AMOUNT: usize = 449;
+const OFFSET_CANCELABLE_BY_SENDER: usize = 457;
+const OFFSET_CANCELABLE_BY_RECIPIENT: usize = 458;
+const OFFSET_CAN_TOPUP: usize = 462;
+const OFFSET_STREAM_NAME: usize = 463;
+const STREAM_NAME_LEN: usize = 64;
+const MIN_STREAM_ACCOUNT_LEN: usize = OFFSET_STREAM_NAME + STREAM_NAME_LEN;
+
+/// Anchor-style 8 byte instruction discriminator
+fn discriminator(name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("global:{}", name).as_bytes());
+    let mut out = [0u8; 8];
+    out.copy_from_slice(&hash[..8]);
+    out
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&data[offset..offset + 32]);
+    Pubkey::new_from_array(bytes)
+}
+
+/// Decode a stream metadata account
+pub(crate) fn decode_stream(id: Pubkey, data: &[u8]) -> Option<StreamInfo> {
+    if data.len() < MIN_STREAM_ACCOUNT_LEN {
+        return None;
+    }
+
+    let name_bytes = &data[OFFSET_STREAM_NAME..OFFSET_STREAM_NAME + STREAM_NAME_LEN];
+    let name = String::from_utf8_lossy(name_bytes)
+        .trim_end_matches('\0')
+        .to_string();
+
+    Some(StreamInfo {
+        id,
+        name,
+        sender: read_pubkey(data, OFFSET_SENDER),
+        sender_tokens: read_pubkey(data, OFFSET_SENDER_TOKENS),
+        recipient: read_pubkey(data, OFFSET_RECIPIENT),
+        recipient_tokens: read_pubkey(data, OFFSET_RECIPIENT_TOKENS),
+        mint: read_pubkey(data, OFFSET_MINT),
+        escrow_tokens: read_pubkey(data, OFFSET_ESCROW_TOKENS),
+        partner: read_pubkey(data, OFFSET_PARTNER),
+        partner_tokens: read_pubkey(data, OFFSET_PARTNER_TOKENS),
+        created_at: read_u64(data, OFFSET_CREATED_AT),
+        start_time: read_u64(data, OFFSET_START_TIME),
+        end_time: read_u64(data, OFFSET_END_TIME),
+        canceled_at: read_u64(data, OFFSET_CANCELED_AT),
+        net_amount_deposited: read_u64(data, OFFSET_NET_DEPOSITED),
+        withdrawn_amount: read_u64(data, OFFSET_WITHDRAWN_AMOUNT),
+        period: read_u64(data, OFFSET_PERIOD),
+        amount_per_period: read_u64(data, OFFSET_AMOUNT_PER_PERIOD),
+        cliff: read_u64(data, OFFSET_CLIFF),
+        cliff_amount: read_u64(data, OFFSET_CLIFF_AMOUNT),
+        cancelable_by_sender: data[OFFSET_CANCELABLE_BY_SENDER] != 0,
+        cancelable_by_recipient: data[OFFSET_CANCELABLE_BY_RECIPIENT] != 0,
+        can_topup: data[OFFSET_CAN_TOPUP] != 0,
+    })
+}
+
+/// Client for creating, topping up, canceling and withdrawing Streamflow streams
+pub struct StreamingClient {
+    rpc_url: String,
+    http_client: HttpClient,
+}
+
+impl StreamingClient {
+    /// Create a new StreamingClient with optional RPC URL
+    pub fn new(rpc_url: Option<&str>) -> Self {
+        let url = rpc_url
+            .unwrap_or("https://johna-k3cr1v-fast-mainnet.helius-rpc.com")
+            .to_string();
+
+        Self {
+            rpc_url: url,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    fn program_id() -> Pubkey {
+        Pubkey::from_str(STREAMFLOW_PROGRAM_ID).expect("Valid Streamflow program ID")
+    }
+
+    fn treasury() -> Pubkey {
+        Pubkey::from_str(STREAMFLOW_TREASURY).expect("Valid Streamflow treasury")
+    }
+
+    /// Escrow token account PDA holding the streamed tokens
+    fn escrow_address(metadata: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"strm", metadata.as_ref()], &Self::program_id()).0
+    }
+
+    /// Streams where `wallet` is the recipient
+    pub async fn get_incoming_streams(&self, wallet: &Pubkey) -> Result<Vec<StreamInfo>> {
+        self.get_streams_by_offset(wallet, OFFSET_RECIPIENT).await
+    }
+
+    /// Streams created by `wallet`
+    pub async fn get_outgoing_streams(&self, wallet: &Pubkey) -> Result<Vec<StreamInfo>> {
+        self.get_streams_by_offset(wallet, OFFSET_SENDER).await
+    }
+
+    async fn get_streams_by_offset(&self, wallet: &Pubkey, offset: usize) -> Result<Vec<StreamInfo>> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getProgramAccounts",
+            "params": [
+                STREAMFLOW_PROGRAM_ID,
+                {
+                    "encoding": "base64",
+                    "filters": [
+                        { "memcmp": { "offset": offset, "bytes": wallet.to_string() } }
+                    ]
+                }
+            ]
+        });
+
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
+
+        if let Some(error) = json.get("error") {
+            return Err(format!("Failed to load streams: {:?}", error).into());
+        }
+
+        let mut streams = Vec::new();
+        if let Some(accounts) = json["result"].as_array() {
+            for account in accounts {
+                let id = match account["pubkey"].as_str().and_then(|p| Pubkey::from_str(p).ok()) {
+                    Some(id) => id,
+                    None => continue,
+                };
+                let data = match account["account"]["data"][0].as_str().and_then(|d| base64::decode(d).ok()) {
+                    Some(data) => data,
+                    None => continue,
+                };
+                if let Some(stream) = decode_stream(id, &data) {
+                    streams.push(stream);
+                }
+            }
+        }
+
+        println!("[Streaming] Found {} streams for {}", streams.len(), wallet);
+        Ok(streams)
+    }
+
+    /// Load a single stream by its metadata address
+    pub async fn get_stream(&self, stream_id: &Pubkey) -> Result<StreamInfo> {
+        let data = self.get_account(stream_id).await?;
+        decode_stream(*stream_id, &data).ok_or_else(|| "Account is not a Streamflow stream".into())
+    }
+
+    /// Create a new payment stream
+    pub async fn create_stream_with_signer(
+        &self,
+        signer: &dyn TransactionSigner,
+        params: &CreateStreamParams,
+    ) -> Result<CreateStreamResult> {
+        let sender = Pubkey::from_str(&signer.get_public_key().await?)
+            .map_err(|e| format!("Invalid public key: {}", e))?;
+        let token_program = self.get_mint_program_id(&params.mint).await?;
+
+        // Each stream lives in a fresh metadata account that must co-sign the creation
+        let metadata = Keypair::new();
+        let escrow_tokens = Self::escrow_address(&metadata.pubkey());
+        let treasury = Self::treasury();
+
+        let sender_tokens = get_associated_token_address_with_program_id(&sender, &params.mint, &token_program);
+        let recipient_tokens = get_associated_token_address_with_program_id(&params.recipient, &params.mint, &token_program);
+        let treasury_tokens = get_associated_token_address_with_program_id(&treasury, &params.mint, &token_program);
+
+        println!("[Streaming] Creating stream {} -> {} ({} units)", sender, params.recipient, params.deposit_amount);
+
+        let mut instructions = Vec::new();
+
+        // Wrap SOL into the sender's WSOL account so it can be streamed
+        if params.mint == spl_token::native_mint::id() {
+            instructions.push(spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &sender,
+                &sender,
+                &params.mint,
+                &token_program,
+            ));
+            instructions.push(system_instruction::transfer(&sender, &sender_tokens, params.deposit_amount));
+            instructions.push(spl_token::instruction::sync_native(&token_program, &sender_tokens)?);
+        }
+
+        let start_time = if params.start_time == 0 {
+            // Streamflow rejects start times in the past, leave room for confirmation
+            chrono::Utc::now().timestamp() as u64 + 60
+        } else {
+            params.start_time
+        };
+
+        let mut name = [0u8; STREAM_NAME_LEN];
+        let name_bytes = params.name.as_bytes();
+        let name_len = name_bytes.len().min(STREAM_NAME_LEN);
+        name[..name_len].copy_from_slice(&name_bytes[..name_len]);
+
+        let mut data = discriminator("create").to_vec();
+        data.extend_from_slice(&start_time.to_le_bytes());
+        data.extend_from_slice(&params.deposit_amount.to_le_bytes());
+        data.extend_from_slice(&params.period.to_le_bytes());
+        data.extend_from_slice(&params.amount_per_period.to_le_bytes());
+        data.extend_from_slice(&start_time.to_le_bytes()); // cliff
+        data.extend_from_slice(&params.cliff_amount.to_le_bytes());
+        data.push(params.cancelable_by_sender as u8);
+        data.push(params.cancelable_by_recipient as u8);
+        data.push(0); // automatic_withdrawal
+        data.push(0); // transferable_by_sender
+        data.push(1); // transferable_by_recipient
+        data.push(params.can_topup as u8);
+        data.extend_from_slice(&name);
+        data.extend_from_slice(&params.period.to_le_bytes()); // withdraw_frequency
+        data.push(0); // pausable
+        data.push(0); // can_update_rate
+
+        instructions.push(Instruction {
+            program_id: Self::program_id(),
+            accounts: vec![
+                AccountMeta::new(sender, true),
+                AccountMeta::new(sender_tokens, false),
+                AccountMeta::new(params.recipient, false),
+                AccountMeta::new(metadata.pubkey(), true),
+                AccountMeta::new(escrow_tokens, false),
+                AccountMeta::new(recipient_tokens, false),
+                AccountMeta::new(treasury, false),
+                AccountMeta::new(treasury_tokens, false),
+                AccountMeta::new(Pubkey::from_str(STREAMFLOW_WITHDRAWOR)?, false),
+                AccountMeta::new(treasury, false), // partner
+                AccountMeta::new(treasury_tokens, false), // partner tokens
+                AccountMeta::new_readonly(params.mint, false),
+                AccountMeta::new_readonly(Pubkey::from_str(STREAMFLOW_FEE_ORACLE)?, false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+                AccountMeta::new_readonly(Self::program_id(), false), // timelock program
+                AccountMeta::new_readonly(token_program, false),
+                AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            data,
+        });
+
+        let signature = self.sign_and_send(signer, &sender, instructions, Some(&metadata)).await?;
+        println!("[Streaming] Stream created: {} ({})", metadata.pubkey(), signature);
+
+        Ok(CreateStreamResult {
+            signature,
+            stream_id: metadata.pubkey(),
+        })
+    }
+
+    /// Add more funds to an existing stream
+    pub async fn topup_with_signer(
+        &self,
+        signer: &dyn TransactionSigner,
+        stream: &StreamInfo,
+        amount: u64,
+    ) -> Result<String> {
+        let sender = Pubkey::from_str(&signer.get_public_key().await?)
+            .map_err(|e| format!("Invalid public key: {}", e))?;
+        if !stream.can_topup {
+            return Err("This stream cannot be topped up".into());
+        }
+
+        let token_program = self.get_mint_program_id(&stream.mint).await?;
+        let treasury = Self::treasury();
+        let treasury_tokens = get_associated_token_address_with_program_id(&treasury, &stream.mint, &token_program);
+        let sender_tokens = get_associated_token_address_with_program_id(&sender, &stream.mint, &token_program);
+
+        let mut instructions = Vec::new();
+        if stream.mint == spl_token::native_mint::id() {
+            instructions.push(system_instruction::transfer(&sender, &sender_tokens, amount));
+            instructions.push(spl_token::instruction::sync_native(&token_program, &sender_tokens)?);
+        }
+
+        let mut data = discriminator("topup").to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        instructions.push(Instruction {
+            program_id: Self::program_id(),
+            accounts: vec![
+                AccountMeta::new(sender, true),
+                AccountMeta::new(sender_tokens, false),
+                AccountMeta::new(stream.id, false),
+                AccountMeta::new(stream.escrow_tokens, false),
+                AccountMeta::new(treasury, false),
+                AccountMeta::new(treasury_tokens, false),
+                AccountMeta::new(Pubkey::from_str(STREAMFLOW_WITHDRAWOR)?, false),
+                AccountMeta::new(stream.partner, false),
+                AccountMeta::new(stream.partner_tokens, false),
+                AccountMeta::new_readonly(stream.mint, false),
+                AccountMeta::new_readonly(token_program, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            data,
+        });
+
+        println!("[Streaming] Topping up stream {} with {} units", stream.id, amount);
+        self.sign_and_send(signer, &sender, instructions, None).await
+    }
+
+    /// Cancel a stream; unlocked funds go to the recipient and the rest back to the sender
+    pub async fn cancel_with_signer(
+        &self,
+        signer: &dyn TransactionSigner,
+        stream: &StreamInfo,
+    ) -> Result<String> {
+        let authority = Pubkey::from_str(&signer.get_public_key().await?)
+            .map_err(|e| format!("Invalid public key: {}", e))?;
+
+        let allowed = (authority == stream.sender && stream.cancelable_by_sender)
+            || (authority == stream.recipient && stream.cancelable_by_recipient);
+        if !allowed {
+            return Err("You are not allowed to cancel this stream".into());
+        }
+
+        let token_program = self.get_mint_program_id(&stream.mint).await?;
+        let treasury = Self::treasury();
+        let treasury_tokens = get_associated_token_address_with_program_id(&treasury, &stream.mint, &token_program);
+
+        let data = discriminator("cancel").to_vec();
+
+        let instructions = vec![Instruction {
+            program_id: Self::program_id(),
+            accounts: vec![
+                AccountMeta::new(authority, true),
+                AccountMeta::new(stream.sender, false),
+                AccountMeta::new(stream.sender_tokens, false),
+                AccountMeta::new(stream.recipient, false),
+                AccountMeta::new(stream.recipient_tokens, false),
+                AccountMeta::new(stream.id, false),
+                AccountMeta::new(stream.escrow_tokens, false),
+                AccountMeta::new(treasury, false),
+                AccountMeta::new(treasury_tokens, false),
+                AccountMeta::new(stream.partner, false),
+                AccountMeta::new(stream.partner_tokens, false),
+                AccountMeta::new_readonly(stream.mint, false),
+                AccountMeta::new_readonly(token_program, false),
+            ],
+            data,
+        }];
+
+        println!("[Streaming] Canceling stream {}", stream.id);
+        self.sign_and_send(signer, &authority, instructions, None).await
+    }
+
+    /// Withdraw unlocked funds from a stream where the signer is the recipient
+    pub async fn withdraw_with_signer(
+        &self,
+        signer: &dyn TransactionSigner,
+        stream: &StreamInfo,
+        amount: Option<u64>,
+    ) -> Result<String> {
+        let authority = Pubkey::from_str(&signer.get_public_key().await?)
+            .map_err(|e| format!("Invalid public key: {}", e))?;
+        if authority != stream.recipient {
+            return Err("Only the stream recipient can withdraw".into());
+        }
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let available = stream.withdrawable_amount(now);
+        let amount = amount.unwrap_or(available);
+        if amount == 0 || amount > available {
+            return Err(format!("Nothing to withdraw (available: {})", available).into());
+        }
+
+        let token_program = self.get_mint_program_id(&stream.mint).await?;
+        let treasury = Self::treasury();
+        let treasury_tokens = get_associated_token_address_with_program_id(&treasury, &stream.mint, &token_program);
+
+        let mut data = discriminator("withdraw").to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let mut instructions = vec![
+            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &authority,
+                &authority,
+                &stream.mint,
+                &token_program,
+            ),
+            Instruction {
+                program_id: Self::program_id(),
+                accounts: vec![
+                    AccountMeta::new(authority, true),
+                    AccountMeta::new(stream.recipient, false),
+                    AccountMeta::new(stream.recipient_tokens, false),
+                    AccountMeta::new(stream.id, false),
+                    AccountMeta::new(stream.escrow_tokens, false),
+                    AccountMeta::new(treasury, false),
+                    AccountMeta::new(treasury_tokens, false),
+                    AccountMeta::new(stream.partner, false),
+                    AccountMeta::new(stream.partner_tokens, false),
+                    AccountMeta::new_readonly(stream.mint, false),
+                    AccountMeta::new_readonly(token_program, false),
+                ],
+                data,
+            },
+        ];
+
+        // Unwrap streamed SOL back to native SOL
+        if stream.mint == spl_token::native_mint::id() {
+            instructions.push(spl_token::instruction::close_account(
+                &token_program,
+                &stream.recipient_tokens,
+                &authority,
+                &authority,
+                &[],
+            )?);
+        }
+
+        println!("[Streaming] Withdrawing {} units from stream {}", amount, stream.id);
+        self.sign_and_send(signer, &authority, instructions, None).await
+    }
+
+    /// Build, sign (signer plus optional local co-signer) and send a transaction
+    async fn sign_and_send(
+        &self,
+        signer: &dyn TransactionSigner,
+        payer: &Pubkey,
+        mut instructions: Vec<Instruction>,
+        co_signer: Option<&Keypair>,
+    ) -> Result<String> {
+        // App tip and Jito tip when JitoTx is enabled
+        if get_current_jito_settings().jito_tx {
+            instructions.extend(crate::tips::tip_instructions(payer, Some(crate::tips::DEFAULT_JITO_TIP_LAMPORTS))?);
+            println!("[Streaming] Added Jito tip to transaction");
+        }
+
+        TransactionClient::new(Some(&self.rpc_url)).size_compute_budget(&mut instructions, payer).await;
+
+        let recent_blockhash = self.get_recent_blockhash().await?;
+
+        let mut message = solana_sdk::message::Message::new(&instructions, Some(payer));
+        message.recent_blockhash = recent_blockhash;
+
+        let mut transaction = VersionedTransaction {
+            signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
+            message: VersionedMessage::Legacy(message),
+        };
+
+        sign_transaction_slot(&mut transaction, signer).await?;
+        if let Some(co_signer) = co_signer {
+            let signature = co_signer.sign_message(&transaction.message.serialize());
+            place_signature(&mut transaction, &co_signer.pubkey(), signature.as_ref())?;
+        }
+
+        let serialized = bincode::serialize(&transaction)?;
+        let encoded = bs58::encode(serialized).into_string();
+        self.send_transaction(&encoded).await
+    }
+
+    /// Get account data from RPC
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Vec<u8>> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getAccountInfo",
+            "params": [
+                pubkey.to_string(),
+                {
+                    "encoding": "base64"
+                }
+            ]
+        });
+
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
+
+        if let Some(data) = json["result"]["value"]["data"][0].as_str() {
+            let decoded = base64::decode(data)
+                .map_err(|e| format!("Failed to decode account data: {}", e))?;
+            Ok(decoded)
+        } else {
+            Err("Account not found".into())
+        }
+    }
+
+    /// Get recent blockhash from RPC
+    async fn get_recent_blockhash(&self) -> Result<solana_sdk::hash::Hash> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getLatestBlockhash",
+            "params": [
+                {
+                    "commitment": "finalized"
+                }
+            ]
+        });
+
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
+
+        if let Some(blockhash_str) = json["result"]["value"]["blockhash"].as_str() {
+            let blockhash = solana_sdk::hash::Hash::from_str(blockhash_str)
+                .map_err(|e| format!("Invalid blockhash: {}", e))?;
+            Ok(blockhash)
+        } else {
+            Err("Failed to get blockhash".into())
+        }
+    }
+
+    /// Detect which token program owns a mint account (Token or Token-2022)
+    async fn get_mint_program_id(&self, mint_pubkey: &Pubkey) -> Result<Pubkey> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getAccountInfo",
+            "params": [
+                mint_pubkey.to_string(),
+                {
+                    "encoding": "base64"
+                }
+            ]
+        });
+
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
+
+        match json["result"]["value"]["owner"].as_str() {
+            Some(TOKEN_2022_PROGRAM_ID) => Ok(Pubkey::from_str(TOKEN_2022_PROGRAM_ID)?),
+            _ => Ok(spl_token::id()),
+        }
+    }
+
+    /// Send transaction to RPC
+    async fn send_transaction(&self, signed_tx: &str) -> Result<String> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendTransaction",
+            "params": [
+                signed_tx,
+                {
+                    "encoding": "base58",
+                    "skipPreflight": false,
+                    "preflightCommitment": "finalized"
+                }
+            ]
+        });
+
+        let json = crate::rpc_pool::post_json(&self.http_client, &self.rpc_url, &request).await?;
+
+        if let Some(error) = json.get("error") {
+            Err(format!("Transaction error: {:?}", error).into())
+        } else if let Some(result) = json["result"].as_str() {
+            Ok(result.to_string())
+        } else {
+            Err(format!("Unknown error: {:?}", json).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_stream() -> StreamInfo {
+        let mut data = vec![0u8; MIN_STREAM_ACCOUNT_LEN];
+        data[OFFSET_NET_DEPOSITED..OFFSET_NET_DEPOSITED + 8].copy_from_slice(&1_000u64.to_le_bytes());
+        data[OFFSET_PERIOD..OFFSET_PERIOD + 8].copy_from_slice(&10u64.to_le_bytes());
+        data[OFFSET_AMOUNT_PER_PERIOD..OFFSET_AMOUNT_PER_PERIOD + 8].copy_from_slice(&100u64.to_le_bytes());
+        data[OFFSET_CLIFF..OFFSET_CLIFF + 8].copy_from_slice(&1_000u64.to_le_bytes());
+        data[OFFSET_CLIFF_AMOUNT..OFFSET_CLIFF_AMOUNT + 8].copy_from_slice(&50u64.to_le_bytes());
+        data[OFFSET_WITHDRAWN_AMOUNT..OFFSET_WITHDRAWN_AMOUNT + 8].copy_from_slice(&150u64.to_le_bytes());
+        data[OFFSET_STREAM_NAME..OFFSET_STREAM_NAME + 6].copy_from_slice(b"salary");
+        decode_stream(Pubkey::new_unique(), &data).unwrap()
+    }
+
+    #[test]
+    fn test_decode_stream_name() {
+        assert_eq!(sample_stream().name, "salary");
+    }
+
+    #[test]
+    fn test_withdrawable_amount() {
+        let stream = sample_stream();
+        assert_eq!(stream.unlocked_amount(999), 0);
+        assert_eq!(stream.unlocked_amount(1_025), 250);
+        assert_eq!(stream.withdrawable_amount(1_025), 100);
+        // Capped at the deposited amount
+        assert_eq!(stream.unlocked_amount(10_000), 1_000);
+    }
+
+    #[test]
+    fn test_canceled_stream_stops_releasing() {
+        let mut stream = sample_stream();
+        stream.canceled_at = 1_010;
+        assert_eq!(stream.unlocked_amount(5_000), 150);
+        assert!(stream.is_finished(5_000));
+    }
+}
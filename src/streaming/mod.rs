@@ -0,0 +1,8 @@
+// src/streaming/mod.rs
+//! Streamflow token streaming integration
+
+mod client;
+mod types;
+
+pub use client::StreamingClient;
+pub use types::*;
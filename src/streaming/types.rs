@@ -0,0 +1,91 @@
+// src/streaming/types.rs
+//! Type definitions for Streamflow token streaming
+
+use solana_sdk::pubkey::Pubkey;
+
+/// Parameters for a new payment stream
+#[derive(Debug, Clone)]
+pub struct CreateStreamParams {
+    pub recipient: Pubkey,
+    /// Token mint; the native mint streams wrapped SOL
+    pub mint: Pubkey,
+    /// Total amount deposited into the stream, in base units
+    pub deposit_amount: u64,
+    /// Seconds between releases (1 = per second, 86_400 = per day)
+    pub period: u64,
+    /// Amount released every period, in base units
+    pub amount_per_period: u64,
+    /// Unix timestamp the stream starts at (0 = now)
+    pub start_time: u64,
+    /// Amount released immediately at the start time
+    pub cliff_amount: u64,
+    pub name: String,
+    pub cancelable_by_sender: bool,
+    pub cancelable_by_recipient: bool,
+    pub can_topup: bool,
+}
+
+/// Decoded Streamflow stream (contract metadata account)
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamInfo {
+    /// Address of the stream metadata account
+    pub id: Pubkey,
+    pub name: String,
+    pub sender: Pubkey,
+    pub sender_tokens: Pubkey,
+    pub recipient: Pubkey,
+    pub recipient_tokens: Pubkey,
+    pub mint: Pubkey,
+    pub escrow_tokens: Pubkey,
+    pub partner: Pubkey,
+    pub partner_tokens: Pubkey,
+    pub created_at: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub canceled_at: u64,
+    pub net_amount_deposited: u64,
+    pub withdrawn_amount: u64,
+    pub period: u64,
+    pub amount_per_period: u64,
+    pub cliff: u64,
+    pub cliff_amount: u64,
+    pub cancelable_by_sender: bool,
+    pub cancelable_by_recipient: bool,
+    pub can_topup: bool,
+}
+
+impl StreamInfo {
+    /// Total amount released to the recipient at `now`
+    pub fn unlocked_amount(&self, now: u64) -> u64 {
+        // A canceled stream stops releasing at the cancel time
+        let now = if self.canceled_at > 0 { now.min(self.canceled_at) } else { now };
+        if now < self.cliff {
+            return 0;
+        }
+        let periods = if self.period == 0 { 0 } else { (now - self.cliff) / self.period };
+        let streamed = self.cliff_amount
+            .saturating_add(periods.saturating_mul(self.amount_per_period));
+        streamed.min(self.net_amount_deposited)
+    }
+
+    /// Amount the recipient can withdraw right now
+    pub fn withdrawable_amount(&self, now: u64) -> u64 {
+        self.unlocked_amount(now).saturating_sub(self.withdrawn_amount)
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.canceled_at > 0
+    }
+
+    pub fn is_finished(&self, now: u64) -> bool {
+        self.is_canceled() || self.unlocked_amount(now) >= self.net_amount_deposited
+    }
+}
+
+/// Result of creating a stream
+#[derive(Debug, Clone)]
+pub struct CreateStreamResult {
+    pub signature: String,
+    /// Address of the new stream metadata account
+    pub stream_id: Pubkey,
+}
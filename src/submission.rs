@@ -0,0 +1,515 @@
+// src/submission.rs
+//! Submission tracking and stuck-transaction fee bumps
+//!
+//! Every transaction broadcast through `TransactionClient::send_transaction`
+//! is tracked here until it lands. When one hasn't landed after a while it can
+//! be rebuilt with a higher compute unit price, re-signed and broadcast again.
+//! The replacement keeps the durable nonce when the original used one (so
+//! only one of the two can ever execute) and otherwise takes a fresh
+//! blockhash; the original stops being tracked either way.
+//!
+//! While pending, the confirmation tracker in `transaction` re-sends the
+//! signed bytes every couple of seconds. If the blockhash expires first the
+//! transaction stays tracked, and its instructions (the original intent) are
+//! rebuilt on a fresh blockhash with a higher priority fee and signed again.
+
+use crate::signing::{sign_transaction_slot, TransactionSigner};
+use crate::timeout;
+use crate::transaction::TransactionClient;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    compute_budget::{self, ComputeBudgetInstruction},
+    instruction::{AccountMeta, CompiledInstruction, Instruction},
+    message::{v0, Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature as SolanaSignature,
+    system_program,
+    transaction::VersionedTransaction,
+};
+use std::error::Error;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Price used for the first bump of a transaction that set no priority fee
+pub const MIN_BUMP_MICRO_LAMPORTS: u64 = 10_000;
+
+/// `SetComputeUnitPrice` instruction tag of the compute budget program
+const SET_COMPUTE_UNIT_PRICE_TAG: u8 = 3;
+/// `AdvanceNonceAccount` instruction tag of the system program
+const ADVANCE_NONCE_TAG: u32 = 4;
+
+/// When and how aggressively stuck transactions are re-sent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeBumpSettings {
+    /// Re-sign and re-send without asking
+    pub auto_bump: bool,
+    /// Seconds without confirmation before a transaction counts as stuck
+    pub stuck_after_secs: u64,
+    /// Factor applied to the previous compute unit price
+    pub multiplier: f64,
+    /// Never bid more than this (micro-lamports per compute unit)
+    pub max_micro_lamports: u64,
+}
+
+impl Default for FeeBumpSettings {
+    fn default() -> Self {
+        Self {
+            auto_bump: false,
+            stuck_after_secs: 20,
+            multiplier: 2.0,
+            max_micro_lamports: 2_000_000,
+        }
+    }
+}
+
+impl FeeBumpSettings {
+    pub fn stuck_after(&self) -> Duration {
+        Duration::from_secs(self.stuck_after_secs)
+    }
+
+    /// The next price to bid, or `None` once the cap has been reached
+    pub fn next_price(&self, current: Option<u64>) -> Option<u64> {
+        let next = match current {
+            Some(price) if price > 0 => ((price as f64) * self.multiplier).ceil() as u64,
+            _ => MIN_BUMP_MICRO_LAMPORTS,
+        };
+        let next = next.max(MIN_BUMP_MICRO_LAMPORTS).min(self.max_micro_lamports);
+        (next > current.unwrap_or(0)).then_some(next)
+    }
+}
+
+/// A broadcast transaction that hasn't been seen on-chain yet
+#[derive(Debug, Clone)]
+pub struct TrackedSubmission {
+    pub signature: String,
+    pub transaction: VersionedTransaction,
+    pub submitted_at: Instant,
+    /// How many times this transaction has been replaced with a higher fee
+    pub bumps: u32,
+}
+
+static TRACKED: Mutex<Vec<TrackedSubmission>> = Mutex::new(Vec::new());
+
+/// Start tracking a transaction that was just broadcast
+pub fn track(signature: &str, transaction: VersionedTransaction) {
+    let mut tracked = TRACKED.lock().unwrap();
+    tracked.retain(|t| t.signature != signature);
+    tracked.push(TrackedSubmission {
+        signature: signature.to_string(),
+        transaction,
+        submitted_at: Instant::now(),
+        bumps: 0,
+    });
+}
+
+/// Stop tracking a transaction (landed, failed or replaced)
+pub fn cancel(signature: &str) {
+    TRACKED.lock().unwrap().retain(|t| t.signature != signature);
+}
+
+pub fn tracked(signature: &str) -> Option<TrackedSubmission> {
+    TRACKED.lock().unwrap().iter().find(|t| t.signature == signature).cloned()
+}
+
+/// Whether a tracked transaction has been pending for longer than allowed
+pub fn is_stuck(signature: &str, settings: &FeeBumpSettings) -> bool {
+    tracked(signature)
+        .map(|t| t.submitted_at.elapsed() >= settings.stuck_after())
+        .unwrap_or(false)
+}
+
+/// Compute unit price set by a message, if any
+pub fn compute_unit_price(message: &Message) -> Option<u64> {
+    message.instructions.iter().find_map(|ix| {
+        let program = message.account_keys.get(ix.program_id_index as usize)?;
+        if *program != compute_budget::id() || ix.data.first() != Some(&SET_COMPUTE_UNIT_PRICE_TAG) {
+            return None;
+        }
+        Some(u64::from_le_bytes(ix.data.get(1..9)?.try_into().ok()?))
+    })
+}
+
+/// `compute_unit_price` for any message version
+pub fn message_compute_unit_price(message: &VersionedMessage) -> Option<u64> {
+    match message {
+        VersionedMessage::Legacy(legacy) => compute_unit_price(legacy),
+        VersionedMessage::V0(v0) => v0.instructions.iter().find_map(|ix| {
+            let program = v0.account_keys.get(ix.program_id_index as usize)?;
+            if *program != compute_budget::id() || ix.data.first() != Some(&SET_COMPUTE_UNIT_PRICE_TAG) {
+                return None;
+            }
+            Some(u64::from_le_bytes(ix.data.get(1..9)?.try_into().ok()?))
+        }),
+    }
+}
+
+fn decompile(message: &Message) -> Vec<Instruction> {
+    message.instructions.iter().map(|ix| Instruction {
+        program_id: message.account_keys[ix.program_id_index as usize],
+        accounts: ix.accounts.iter().map(|&i| {
+            let i = i as usize;
+            AccountMeta {
+                pubkey: message.account_keys[i],
+                is_signer: message.is_signer(i),
+                is_writable: message.is_writable_index(i),
+            }
+        }).collect(),
+        data: ix.data.clone(),
+    }).collect()
+}
+
+fn is_advance_nonce(ix: &Instruction) -> bool {
+    ix.program_id == system_program::id() && ix.data.get(..4) == Some(&ADVANCE_NONCE_TAG.to_le_bytes())
+}
+
+/// Whether the message is anchored to a durable nonce instead of a blockhash
+pub fn uses_durable_nonce(message: &Message) -> bool {
+    decompile(message).first().map(is_advance_nonce).unwrap_or(false)
+}
+
+/// `uses_durable_nonce` for any message version. Only legacy messages are
+/// checked; versioned ones are treated as blockhash-based.
+pub fn message_uses_durable_nonce(message: &VersionedMessage) -> bool {
+    matches!(message, VersionedMessage::Legacy(legacy) if uses_durable_nonce(legacy))
+}
+
+/// Rebuild `message` bidding `micro_lamports` per compute unit.
+///
+/// `fresh_blockhash` replaces the blockhash unless the message uses a durable
+/// nonce, and `fresh_timeout` replaces a timeout instruction whose deadline
+/// has likely passed.
+pub fn rebuild_with_priority_fee(
+    message: &Message,
+    micro_lamports: u64,
+    fresh_blockhash: solana_sdk::hash::Hash,
+    fresh_timeout: Option<Instruction>,
+) -> Message {
+    let timeout_program = Pubkey::from_str(timeout::TIMEOUT_PROGRAM_ID).ok();
+    let mut instructions: Vec<Instruction> = decompile(message)
+        .into_iter()
+        .filter(|ix| !(ix.program_id == compute_budget::id() && ix.data.first() == Some(&SET_COMPUTE_UNIT_PRICE_TAG)))
+        .map(|ix| match &fresh_timeout {
+            Some(fresh) if Some(ix.program_id) == timeout_program => fresh.clone(),
+            _ => ix,
+        })
+        .collect();
+    instructions.push(ComputeBudgetInstruction::set_compute_unit_price(micro_lamports));
+
+    let blockhash = if uses_durable_nonce(message) { message.recent_blockhash } else { fresh_blockhash };
+    let payer = message.account_keys.first();
+    Message::new_with_blockhash(&instructions, payer, &blockhash)
+}
+
+/// Rebuild `message` on a fresh blockhash, keeping everything else.
+///
+/// Legacy messages also get a fresh timeout instruction; versioned messages
+/// keep their lookup tables and only the blockhash changes.
+pub fn rebuild_with_fresh_blockhash(
+    message: &VersionedMessage,
+    fresh_blockhash: solana_sdk::hash::Hash,
+    fresh_timeout: Option<Instruction>,
+) -> Result<VersionedMessage, String> {
+    match message {
+        VersionedMessage::Legacy(legacy) => {
+            if uses_durable_nonce(legacy) {
+                return Err("This transaction uses a durable nonce and doesn't expire".to_string());
+            }
+            let timeout_program = Pubkey::from_str(timeout::TIMEOUT_PROGRAM_ID).ok();
+            let instructions: Vec<Instruction> = decompile(legacy)
+                .into_iter()
+                .map(|ix| match &fresh_timeout {
+                    Some(fresh) if Some(ix.program_id) == timeout_program => fresh.clone(),
+                    _ => ix,
+                })
+                .collect();
+            Ok(VersionedMessage::Legacy(Message::new_with_blockhash(
+                &instructions,
+                legacy.account_keys.first(),
+                &fresh_blockhash,
+            )))
+        }
+        VersionedMessage::V0(v0) => {
+            let mut rebuilt = v0.clone();
+            rebuilt.recent_blockhash = fresh_blockhash;
+            // The timeout instruction only reads the clock, so swapping its
+            // data for the fresh deadline leaves the accounts untouched
+            if let (Some(fresh), Ok(timeout_program)) = (&fresh_timeout, Pubkey::from_str(timeout::TIMEOUT_PROGRAM_ID)) {
+                for ix in rebuilt.instructions.iter_mut() {
+                    if rebuilt.account_keys.get(ix.program_id_index as usize) == Some(&timeout_program) {
+                        ix.data = fresh.data.clone();
+                    }
+                }
+            }
+            Ok(VersionedMessage::V0(rebuilt))
+        }
+    }
+}
+
+/// Set the compute unit price of a v0 message, adding the instruction (and
+/// the compute budget program) when it has none.
+///
+/// Accounts loaded from lookup tables are indexed after the static keys, so
+/// adding a static key shifts every such index by one.
+pub fn set_v0_priority_fee(message: &v0::Message, micro_lamports: u64) -> v0::Message {
+    let data = ComputeBudgetInstruction::set_compute_unit_price(micro_lamports).data;
+    let mut rebuilt = message.clone();
+
+    let existing = rebuilt.instructions.iter_mut().find(|ix| {
+        rebuilt.account_keys.get(ix.program_id_index as usize) == Some(&compute_budget::id())
+            && ix.data.first() == Some(&SET_COMPUTE_UNIT_PRICE_TAG)
+    });
+    if let Some(ix) = existing {
+        ix.data = data;
+        return rebuilt;
+    }
+
+    let program_index = match rebuilt.account_keys.iter().position(|k| *k == compute_budget::id()) {
+        Some(index) => index as u8,
+        None => {
+            // Appended as the last read-only, unsigned static key
+            let index = rebuilt.account_keys.len() as u8;
+            for ix in rebuilt.instructions.iter_mut() {
+                if ix.program_id_index >= index {
+                    ix.program_id_index += 1;
+                }
+                for account in ix.accounts.iter_mut() {
+                    if *account >= index {
+                        *account += 1;
+                    }
+                }
+            }
+            rebuilt.account_keys.push(compute_budget::id());
+            rebuilt.header.num_readonly_unsigned_accounts += 1;
+            index
+        }
+    };
+    rebuilt.instructions.insert(0, CompiledInstruction { program_id_index: program_index, accounts: Vec::new(), data });
+    rebuilt
+}
+
+/// Bid `micro_lamports` per compute unit, keeping the blockhash
+fn with_priority_fee(message: &VersionedMessage, micro_lamports: u64) -> VersionedMessage {
+    match message {
+        VersionedMessage::Legacy(legacy) => VersionedMessage::Legacy(
+            rebuild_with_priority_fee(legacy, micro_lamports, legacy.recent_blockhash, None),
+        ),
+        VersionedMessage::V0(v0) => VersionedMessage::V0(set_v0_priority_fee(v0, micro_lamports)),
+    }
+}
+
+/// Make sure `signer` is the only signer of `message`
+async fn ensure_sole_signer(signer: &dyn TransactionSigner, message: &VersionedMessage) -> Result<(), Box<dyn Error>> {
+    let signer_pubkey = Pubkey::from_str(&signer.get_public_key().await?)?;
+    if message.header().num_required_signatures != 1 || message.static_account_keys().first() != Some(&signer_pubkey) {
+        return Err("Only transactions signed by this wallet alone can be re-signed".into());
+    }
+    Ok(())
+}
+
+/// Sign `message`, broadcast it and track it in place of `original`
+async fn send_replacement(
+    client: &TransactionClient,
+    signer: &dyn TransactionSigner,
+    original: &TrackedSubmission,
+    message: VersionedMessage,
+) -> Result<String, Box<dyn Error>> {
+    let mut replacement = VersionedTransaction {
+        signatures: vec![SolanaSignature::default(); 1],
+        message,
+    };
+    sign_transaction_slot(&mut replacement, signer).await?;
+
+    let new_signature = client.send_transaction(&bs58::encode(bincode::serialize(&replacement)?).into_string()).await?;
+    cancel(&original.signature);
+    if let Some(entry) = TRACKED.lock().unwrap().iter_mut().find(|t| t.signature == new_signature) {
+        entry.bumps = original.bumps + 1;
+    }
+    Ok(new_signature)
+}
+
+/// Replace a stuck transaction with a copy that pays a higher priority fee.
+///
+/// Only transactions signed by `signer` alone can be replaced; returns the
+/// signature of the replacement.
+pub async fn bump_fee(
+    client: &TransactionClient,
+    signer: &dyn TransactionSigner,
+    signature: &str,
+    settings: &FeeBumpSettings,
+) -> Result<String, Box<dyn Error>> {
+    let original = tracked(signature).ok_or("This transaction is no longer tracked")?;
+    if client.confirm_transaction(signature).await? {
+        cancel(signature);
+        return Err("The transaction has already landed".into());
+    }
+
+    let VersionedMessage::Legacy(message) = &original.transaction.message else {
+        return Err("Only legacy transactions can be re-signed with a higher fee".into());
+    };
+    ensure_sole_signer(signer, &original.transaction.message).await?;
+
+    let price = settings.next_price(compute_unit_price(message))
+        .ok_or("The priority fee is already at the configured maximum")?;
+    let blockhash = client.get_recent_blockhash().await?;
+    let fresh_timeout = timeout::build_timeout_instruction_from_current(
+        client.get_current_slot().await?,
+        timeout::DEFAULT_SLOT_WINDOW,
+    )?;
+    let rebuilt = rebuild_with_priority_fee(message, price, blockhash, Some(fresh_timeout));
+
+    println!("⛽ Re-sending {} at {} micro-lamports/CU", signature, price);
+    send_replacement(client, signer, &original, VersionedMessage::Legacy(rebuilt)).await
+}
+
+/// Rebuild a transaction whose blockhash expired before it landed from the
+/// instructions kept by the tracker, on a fresh blockhash and with a higher
+/// priority fee, sign it again and broadcast it. Returns the signature of the
+/// replacement.
+pub async fn rebuild_and_resend(
+    client: &TransactionClient,
+    signer: &dyn TransactionSigner,
+    signature: &str,
+    settings: &FeeBumpSettings,
+) -> Result<String, Box<dyn Error>> {
+    let original = tracked(signature).ok_or("This transaction is no longer tracked")?;
+    if client.get_transaction_status(signature).await?.is_some() {
+        cancel(signature);
+        return Err("The transaction has already landed".into());
+    }
+    ensure_sole_signer(signer, &original.transaction.message).await?;
+
+    let blockhash = client.get_recent_blockhash().await?;
+    let fresh_timeout = timeout::build_timeout_instruction_from_current(
+        client.get_current_slot().await?,
+        timeout::DEFAULT_SLOT_WINDOW,
+    )?;
+    let rebuilt = rebuild_with_fresh_blockhash(&original.transaction.message, blockhash, Some(fresh_timeout))?;
+
+    // It expired unseen, so bid higher; at the cap, keep the current price
+    let current = message_compute_unit_price(&rebuilt);
+    let price = settings.next_price(current).or(current).unwrap_or(MIN_BUMP_MICRO_LAMPORTS);
+    let rebuilt = with_priority_fee(&rebuilt, price);
+
+    println!("🔁 Rebuilding expired transaction {} with a fresh blockhash at {} micro-lamports/CU", signature, price);
+    send_replacement(client, signer, &original, rebuilt).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{hash::Hash, system_instruction};
+
+    #[test]
+    fn test_next_price() {
+        let settings = FeeBumpSettings::default();
+        assert_eq!(settings.next_price(None), Some(MIN_BUMP_MICRO_LAMPORTS));
+        assert_eq!(settings.next_price(Some(50_000)), Some(100_000));
+        assert_eq!(settings.next_price(Some(1_500_000)), Some(2_000_000));
+        assert_eq!(settings.next_price(Some(2_000_000)), None);
+    }
+
+    #[test]
+    fn test_rebuild_replaces_price_and_blockhash() {
+        let payer = Pubkey::new_unique();
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_price(5_000),
+            system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000),
+        ];
+        let message = Message::new_with_blockhash(&instructions, Some(&payer), &Hash::new_unique());
+        assert_eq!(compute_unit_price(&message), Some(5_000));
+
+        let fresh = Hash::new_unique();
+        let rebuilt = rebuild_with_priority_fee(&message, 10_000, fresh, None);
+        assert_eq!(compute_unit_price(&rebuilt), Some(10_000));
+        assert_eq!(rebuilt.recent_blockhash, fresh);
+        assert_eq!(rebuilt.account_keys[0], payer);
+        assert_eq!(rebuilt.instructions.len(), 2);
+    }
+
+    #[test]
+    fn test_rebuild_keeps_durable_nonce() {
+        let payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let nonce_value = Hash::new_unique();
+        let instructions = vec![
+            system_instruction::advance_nonce_account(&nonce_account, &payer),
+            system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000),
+        ];
+        let message = Message::new_with_blockhash(&instructions, Some(&payer), &nonce_value);
+        assert!(uses_durable_nonce(&message));
+
+        let rebuilt = rebuild_with_priority_fee(&message, 10_000, Hash::new_unique(), None);
+        assert_eq!(rebuilt.recent_blockhash, nonce_value);
+        assert!(uses_durable_nonce(&rebuilt));
+    }
+
+    #[test]
+    fn test_fresh_blockhash_keeps_instructions() {
+        let payer = Pubkey::new_unique();
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_price(5_000),
+            system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000),
+        ];
+        let message = VersionedMessage::Legacy(Message::new_with_blockhash(&instructions, Some(&payer), &Hash::new_unique()));
+
+        let fresh = Hash::new_unique();
+        let rebuilt = rebuild_with_fresh_blockhash(&message, fresh, None).unwrap();
+        assert_eq!(*rebuilt.recent_blockhash(), fresh);
+        let VersionedMessage::Legacy(rebuilt) = rebuilt else { panic!("expected a legacy message") };
+        assert_eq!(compute_unit_price(&rebuilt), Some(5_000));
+        assert_eq!(rebuilt.instructions.len(), 2);
+
+        let nonce = Message::new_with_blockhash(
+            &[system_instruction::advance_nonce_account(&Pubkey::new_unique(), &payer)],
+            Some(&payer),
+            &Hash::new_unique(),
+        );
+        assert!(rebuild_with_fresh_blockhash(&VersionedMessage::Legacy(nonce), fresh, None).is_err());
+    }
+
+    #[test]
+    fn test_v0_priority_fee_shifts_loaded_indices() {
+        let payer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        // payer, recipient, system program; index 3 is loaded from a lookup table
+        let message = v0::Message {
+            header: solana_sdk::message::MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![payer, recipient, system_program::id()],
+            recent_blockhash: Hash::new_unique(),
+            instructions: vec![CompiledInstruction { program_id_index: 2, accounts: vec![0, 1, 3], data: vec![2, 0, 0, 0] }],
+            address_table_lookups: Vec::new(),
+        };
+
+        let rebuilt = set_v0_priority_fee(&message, 20_000);
+        let versioned = VersionedMessage::V0(rebuilt.clone());
+        assert_eq!(message_compute_unit_price(&versioned), Some(20_000));
+        assert_eq!(rebuilt.account_keys[3], compute_budget::id());
+        assert_eq!(rebuilt.header.num_readonly_unsigned_accounts, 2);
+        assert_eq!(rebuilt.instructions[1].accounts, vec![0, 1, 4]);
+
+        // A second bump only rewrites the price
+        let bumped = set_v0_priority_fee(&rebuilt, 40_000);
+        assert_eq!(bumped.account_keys.len(), 4);
+        assert_eq!(bumped.instructions.len(), 2);
+        assert_eq!(message_compute_unit_price(&VersionedMessage::V0(bumped)), Some(40_000));
+    }
+
+    #[test]
+    fn test_tracking() {
+        let tx = VersionedTransaction {
+            signatures: vec![SolanaSignature::default()],
+            message: VersionedMessage::Legacy(Message::default()),
+        };
+        track("sig-1", tx);
+        assert!(tracked("sig-1").is_some());
+        assert!(!is_stuck("sig-1", &FeeBumpSettings::default()));
+        assert!(is_stuck("sig-1", &FeeBumpSettings { stuck_after_secs: 0, ..Default::default() }));
+        cancel("sig-1");
+        assert!(tracked("sig-1").is_none());
+    }
+}
@@ -0,0 +1,195 @@
+// src/swap/dca.rs
+//! Dollar-cost averaging: recurring buys scheduled on the device
+//!
+//! Each buy is a Jupiter swap of the schedule's fixed input amount. Buys
+//! paid in SOL run on their own when the wallet has an active session key
+//! that allows DCA executions: the session key swaps from its own (capped)
+//! SOL and has the output delivered to the wallet's token account. Any
+//! other due buy waits until the user confirms it with the wallet.
+
+use reqwest::Client as HttpClient;
+use serde_json::{json, Value};
+use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
+use std::error::Error as StdError;
+use std::str::FromStr;
+
+use crate::signing::session::{SessionAction, SessionSigner};
+use crate::signing::{sign_available_slots, TransactionSigner};
+use crate::storage::{load_dca_schedules, save_dca_schedules};
+use crate::swap::types::{DcaBuy, DcaSchedule};
+use crate::transaction::TransactionClient;
+
+type Result<T> = std::result::Result<T, Box<dyn StdError>>;
+
+const JUPITER_SWAP_URL: &str = "https://lite-api.jup.ag/swap/v1";
+pub const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// Interval choices: (label, seconds)
+pub const DCA_INTERVALS: &[(&str, i64)] = &[
+    ("Hourly", 3_600),
+    ("Daily", 86_400),
+    ("Weekly", 7 * 86_400),
+];
+
+/// Whether a session key can run this schedule's buys
+pub fn runs_with_session_key(schedule: &DcaSchedule) -> bool {
+    schedule.input_mint == SOL_MINT
+}
+
+/// Swap one buy's worth of the input token with `signer` and send it
+pub async fn execute_buy(
+    schedule: &DcaSchedule,
+    signer: &dyn TransactionSigner,
+    by_session_key: bool,
+    rpc_url: Option<&str>,
+) -> Result<DcaBuy> {
+    let http = HttpClient::new();
+    let user = signer.get_public_key().await?;
+    let slippage = crate::storage::load_slippage_setting();
+
+    let quote: Value = http
+        .get(format!("{}/quote", JUPITER_SWAP_URL))
+        .query(&[
+            ("inputMint", schedule.input_mint.clone()),
+            ("outputMint", schedule.output_mint.clone()),
+            ("amount", schedule.amount_per_buy.to_string()),
+            ("slippageBps", slippage.jupiter_bps().to_string()),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Jupiter quote request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Jupiter quote: {}", e))?;
+    let out_amount: u64 = quote["outAmount"]
+        .as_str()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| format!("No route for {} → {}", schedule.input_symbol, schedule.output_symbol))?;
+
+    let mut request = json!({
+        "quoteResponse": quote,
+        "userPublicKey": user,
+        "wrapAndUnwrapSol": true,
+        "dynamicComputeUnitLimit": true,
+        "dynamicSlippage": slippage.is_dynamic(),
+        "prioritizationFeeLamports": "auto",
+    });
+    if user != schedule.owner {
+        // Deliver to the wallet, not the session key
+        let destination = spl_associated_token_account::get_associated_token_address(
+            &Pubkey::from_str(&schedule.owner)?,
+            &Pubkey::from_str(&schedule.output_mint)?,
+        );
+        request["destinationTokenAccount"] = json!(destination.to_string());
+    }
+    let swap: Value = http
+        .post(format!("{}/swap", JUPITER_SWAP_URL))
+        .json(&request)
+        .send()
+        .await
+        .map_err(|e| format!("Jupiter swap request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Jupiter swap response: {}", e))?;
+    let unsigned = swap["swapTransaction"].as_str().ok_or("No transaction in Jupiter swap response")?;
+
+    let mut transaction: VersionedTransaction = bincode::deserialize(&base64::decode(unsigned)?)?;
+    sign_available_slots(&mut transaction, &[signer]).await?;
+    let signature = TransactionClient::new(rpc_url)
+        .send_transaction(&bs58::encode(bincode::serialize(&transaction)?).into_string())
+        .await?;
+    println!("📅 DCA buy {} → {}: {}", schedule.input_symbol, schedule.output_symbol, signature);
+
+    Ok(DcaBuy {
+        at: chrono::Utc::now().timestamp(),
+        signature,
+        spent: schedule.amount_per_buy as f64 / 10_f64.powi(schedule.input_decimals as i32),
+        received: out_amount as f64 / 10_f64.powi(schedule.output_decimals as i32),
+        by_session_key,
+    })
+}
+
+/// Store an executed buy on its schedule
+pub fn record_buy(owner: &str, schedule_id: &str, buy: DcaBuy) -> std::result::Result<(), String> {
+    let mut schedules = load_dca_schedules(owner);
+    if let Some(schedule) = schedules.iter_mut().find(|s| s.id == schedule_id) {
+        schedule.record(buy);
+    }
+    save_dca_schedules(owner, &schedules)
+}
+
+/// Run the owner's due buys that a session key can sign. Returns how many
+/// due buys are left waiting for the user to confirm them.
+pub async fn run_due_buys(owner: &str, rpc_url: Option<&str>) -> usize {
+    let now = chrono::Utc::now().timestamp();
+    let mut awaiting = 0;
+    for schedule in load_dca_schedules(owner).into_iter().filter(|s| s.is_due(now)) {
+        let session = runs_with_session_key(&schedule)
+            .then(|| SessionSigner::for_action(owner, SessionAction::Dca))
+            .flatten();
+        let Some(session) = session else {
+            awaiting += 1;
+            continue;
+        };
+        match execute_buy(&schedule, &session, true, rpc_url).await {
+            Ok(buy) => {
+                if let Err(e) = record_buy(owner, &schedule.id, buy) {
+                    println!("⚠️ Failed to store DCA buy: {}", e);
+                }
+            }
+            Err(e) => {
+                println!("⚠️ DCA buy with session key failed: {}", e);
+                awaiting += 1;
+            }
+        }
+    }
+    awaiting
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule() -> DcaSchedule {
+        DcaSchedule {
+            id: "dca".to_string(),
+            owner: "owner".to_string(),
+            input_mint: SOL_MINT.to_string(),
+            output_mint: "mint".to_string(),
+            input_symbol: "SOL".to_string(),
+            output_symbol: "JUP".to_string(),
+            input_decimals: 9,
+            output_decimals: 6,
+            amount_per_buy: 100_000_000,
+            interval_secs: 100,
+            total_buys: 2,
+            next_buy_at: 1_000,
+            paused: false,
+            created_at: 900,
+            buys: Vec::new(),
+        }
+    }
+
+    fn buy(at: i64, spent: f64, received: f64) -> DcaBuy {
+        DcaBuy { at, signature: "sig".to_string(), spent, received, by_session_key: false }
+    }
+
+    #[test]
+    fn test_schedule_progress() {
+        let mut schedule = schedule();
+        assert!(!schedule.is_due(999));
+        assert!(schedule.is_due(1_000));
+        assert!(runs_with_session_key(&schedule));
+
+        schedule.record(buy(1_010, 0.1, 10.0));
+        assert_eq!(schedule.next_buy_at, 1_100);
+        assert_eq!(schedule.remaining_buys(), 1);
+
+        // Missed periods are skipped
+        schedule.record(buy(1_500, 0.1, 30.0));
+        assert_eq!(schedule.next_buy_at, 1_600);
+        assert!(schedule.is_complete());
+        assert!(!schedule.is_due(2_000));
+        assert_eq!(schedule.average_price(), Some(0.2 / 40.0));
+    }
+}
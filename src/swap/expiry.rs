@@ -0,0 +1,33 @@
+// src/swap/expiry.rs
+//! Quote freshness
+//!
+//! An aggregator quote is only good for a short while: prices move and the
+//! route it priced stops being the best one. The swap modal shows how long
+//! the current quote has left, quotes again when it runs out and won't send
+//! a swap built on an expired quote.
+
+/// How long a quote is used for, seconds
+pub const QUOTE_TTL_SECS: i64 = 30;
+
+/// Seconds before a quote fetched at `quoted_at` expires, 0 once it has
+pub fn quote_seconds_left(quoted_at: i64, now: i64) -> i64 {
+    (quoted_at + QUOTE_TTL_SECS - now).clamp(0, QUOTE_TTL_SECS)
+}
+
+pub fn is_quote_expired(quoted_at: i64, now: i64) -> bool {
+    quote_seconds_left(quoted_at, now) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_expiry() {
+        assert_eq!(quote_seconds_left(100, 100), QUOTE_TTL_SECS);
+        assert_eq!(quote_seconds_left(100, 110), QUOTE_TTL_SECS - 10);
+        assert!(!is_quote_expired(100, 100 + QUOTE_TTL_SECS - 1));
+        assert!(is_quote_expired(100, 100 + QUOTE_TTL_SECS));
+        assert!(is_quote_expired(100, 1_000));
+    }
+}
@@ -0,0 +1,205 @@
+// src/swap/history.rs
+//! Swap history kept on the device
+//!
+//! Every swap sent from the swap modal is stored with the quote it was sent
+//! on and the fees and tips it paid, independent of the chain history. Once
+//! the transaction is finalized the output actually received is read from its
+//! balance changes, so execution can be compared per provider: how far fills
+//! fall short of their quotes and what swaps cost.
+
+use serde_json::Value;
+
+use crate::rpc::get_transaction_details;
+use crate::storage::{load_swap_history, save_swap_history};
+use crate::swap::dca::SOL_MINT;
+use crate::swap::types::SwapRecord;
+
+/// Swaps kept per wallet, older ones are dropped
+const MAX_SWAP_HISTORY: usize = 500;
+/// Stop trying to read the fill of a swap that never landed
+const RECEIVED_LOOKUP_WINDOW_SECS: i64 = 86_400;
+
+/// Execution summary of one provider's swaps
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderStats {
+    pub provider: String,
+    pub swaps: usize,
+    /// Swaps whose fill has been read
+    pub filled: usize,
+    /// Average shortfall of fills against quotes, bps
+    pub average_shortfall_bps: Option<f64>,
+    /// Lamports
+    pub average_fees: u64,
+}
+
+/// Store a sent swap, newest first
+pub fn record_swap(owner: &str, record: SwapRecord) -> Result<(), String> {
+    let mut history = load_swap_history(owner);
+    history.insert(0, record);
+    history.truncate(MAX_SWAP_HISTORY);
+    save_swap_history(owner, &history)
+}
+
+fn raw_amount(balance: &Value) -> i128 {
+    balance["uiTokenAmount"]["amount"].as_str().and_then(|s| s.parse().ok()).unwrap_or(0)
+}
+
+/// Base units of `mint` the owner gained in a landed transaction. SOL is
+/// read from the owner's lamports with the network fee and `tips` added
+/// back, as wrapped SOL is closed into the wallet by the swap.
+pub fn received_units(meta: &Value, account_keys: &[Value], owner: &str, mint: &str, tips: u64) -> Option<u64> {
+    if !meta["err"].is_null() {
+        return Some(0);
+    }
+    let gained = if mint == SOL_MINT {
+        let index = account_keys
+            .iter()
+            .position(|key| key["pubkey"].as_str().or(key.as_str()) == Some(owner))?;
+        let pre = meta["preBalances"][index].as_u64()? as i128;
+        let post = meta["postBalances"][index].as_u64()? as i128;
+        let fee = if index == 0 { meta["fee"].as_u64().unwrap_or(0) as i128 } else { 0 };
+        post - pre + fee + tips as i128
+    } else {
+        let total = |key: &str| -> i128 {
+            meta[key]
+                .as_array()
+                .map(|balances| {
+                    balances
+                        .iter()
+                        .filter(|b| b["owner"].as_str() == Some(owner) && b["mint"].as_str() == Some(mint))
+                        .map(raw_amount)
+                        .sum()
+                })
+                .unwrap_or(0)
+        };
+        total("postTokenBalances") - total("preTokenBalances")
+    };
+    Some(gained.max(0) as u64)
+}
+
+/// Read the fills of recent swaps that don't have one yet. Returns the
+/// updated history.
+pub async fn update_received(owner: &str, rpc_url: Option<&str>) -> Vec<SwapRecord> {
+    let mut history = load_swap_history(owner);
+    let now = chrono::Utc::now().timestamp();
+    let mut changed = false;
+    for record in history
+        .iter_mut()
+        .filter(|r| r.received.is_none() && now - r.at < RECEIVED_LOOKUP_WINDOW_SECS)
+    {
+        // Not finalized yet, or the RPC doesn't have it
+        let Ok(details) = get_transaction_details(&record.signature, rpc_url).await else {
+            continue;
+        };
+        let (Some(meta), Some(message)) = (details.get("meta"), details.get("message")) else {
+            continue;
+        };
+        let keys = message["accountKeys"].as_array().cloned().unwrap_or_default();
+        let tips = record.jito_tip + record.app_tip;
+        if let Some(units) = received_units(meta, &keys, owner, &record.output_mint, tips) {
+            record.received = Some(units as f64 / 10_f64.powi(record.output_decimals as i32));
+            changed = true;
+        }
+    }
+    if changed {
+        if let Err(e) = save_swap_history(owner, &history) {
+            println!("⚠️ Failed to store swap fills: {}", e);
+        }
+    }
+    history
+}
+
+/// Per-provider execution summary, providers with the most swaps first
+pub fn provider_stats(history: &[SwapRecord]) -> Vec<ProviderStats> {
+    let mut providers: Vec<&str> = Vec::new();
+    for record in history {
+        if !providers.contains(&record.provider.as_str()) {
+            providers.push(&record.provider);
+        }
+    }
+    let mut stats: Vec<ProviderStats> = providers
+        .into_iter()
+        .map(|provider| {
+            let swaps: Vec<&SwapRecord> = history.iter().filter(|r| r.provider == provider).collect();
+            let shortfalls: Vec<f64> = swaps.iter().filter_map(|r| r.shortfall_bps()).collect();
+            ProviderStats {
+                provider: provider.to_string(),
+                swaps: swaps.len(),
+                filled: shortfalls.len(),
+                average_shortfall_bps: (!shortfalls.is_empty())
+                    .then(|| shortfalls.iter().sum::<f64>() / shortfalls.len() as f64),
+                average_fees: swaps.iter().map(|r| r.total_fees()).sum::<u64>() / swaps.len() as u64,
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| b.swaps.cmp(&a.swaps));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swap::Slippage;
+    use serde_json::json;
+
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn record(provider: &str, quoted: f64, received: Option<f64>) -> SwapRecord {
+        SwapRecord {
+            signature: "sig".to_string(),
+            at: 0,
+            provider: provider.to_string(),
+            input_mint: SOL_MINT.to_string(),
+            output_mint: USDC.to_string(),
+            input_symbol: "SOL".to_string(),
+            output_symbol: "USDC".to_string(),
+            output_decimals: 6,
+            input_amount: 1.0,
+            quoted_output: quoted,
+            slippage: Slippage::default(),
+            mev_protected: false,
+            network_fee: 5_000,
+            jito_tip: 0,
+            app_tip: 100_000,
+            received,
+        }
+    }
+
+    #[test]
+    fn test_received_units() {
+        let keys = vec![json!({"pubkey": "owner"}), json!({"pubkey": "pool"})];
+        let meta = json!({
+            "err": null,
+            "fee": 5_000,
+            "preBalances": [1_000_000_000u64, 0],
+            "postBalances": [1_499_895_000u64, 0],
+            "preTokenBalances": [{"owner": "owner", "mint": USDC, "uiTokenAmount": {"amount": "1000"}}],
+            "postTokenBalances": [
+                {"owner": "owner", "mint": USDC, "uiTokenAmount": {"amount": "201000"}},
+                {"owner": "pool", "mint": USDC, "uiTokenAmount": {"amount": "5"}}
+            ],
+        });
+        assert_eq!(received_units(&meta, &keys, "owner", USDC, 0), Some(200_000));
+        assert_eq!(received_units(&meta, &keys, "owner", SOL_MINT, 100_000), Some(500_000_000));
+
+        let failed = json!({"err": {"InstructionError": [2, "Custom"]}});
+        assert_eq!(received_units(&failed, &keys, "owner", USDC, 0), Some(0));
+    }
+
+    #[test]
+    fn test_provider_stats() {
+        let history = vec![
+            record("Titan", 100.0, Some(99.0)),
+            record("Jupiter", 100.0, Some(99.5)),
+            record("Jupiter", 100.0, Some(100.5)),
+            record("Jupiter", 100.0, None),
+        ];
+        let stats = provider_stats(&history);
+        assert_eq!(stats[0].provider, "Jupiter");
+        assert_eq!(stats[0].swaps, 3);
+        assert_eq!(stats[0].filled, 2);
+        assert_eq!(stats[0].average_shortfall_bps, Some(0.0));
+        assert_eq!(stats[0].average_fees, 105_000);
+        assert_eq!(stats[1].average_shortfall_bps, Some(100.0));
+    }
+}
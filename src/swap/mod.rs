@@ -0,0 +1,32 @@
+// src/swap/mod.rs
+//! Swap orders beyond instant quotes
+//!
+//! Limit orders go through Jupiter's Trigger API: the API builds the create
+//! and cancel transactions, the wallet signs them and the API submits them.
+//! Orders are kept on the device as well, so they show up before Jupiter
+//! indexes them and while its API is unreachable.
+//!
+//! DCA schedules live on the device only; see `dca` for how their buys are
+//! signed. The slippage setting applies to every quote, instant or not, and
+//! `protected` sends swaps past the public mempool when MEV protection is on.
+//! Instant swaps are recorded in `history` with the quote they were sent on,
+//! and only quoted by the providers enabled in `providers`, for as long as
+//! `expiry` allows. SOL a swap leaves wrapped is found and unwrapped by `wsol`.
+
+pub mod dca;
+mod expiry;
+pub mod history;
+pub mod protected;
+mod providers;
+mod route;
+mod slippage;
+mod trigger;
+mod types;
+pub mod wsol;
+
+pub use expiry::*;
+pub use providers::*;
+pub use route::*;
+pub use slippage::*;
+pub use trigger::{merge_orders, TriggerClient};
+pub use types::*;
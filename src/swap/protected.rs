@@ -0,0 +1,91 @@
+// src/swap/protected.rs
+//! MEV-protected swap submission
+//!
+//! With MEV protection on, a signed swap goes to Jito's block engine as a
+//! single-transaction bundle instead of through the RPC's `sendTransaction`.
+//! The block engine forwards bundles straight to the leader, so the swap is
+//! never visible in the public mempool where it could be sandwiched. There is
+//! no fallback to the public path: if the block engine rejects the bundle,
+//! the swap fails. Protected swaps are also not handed to the submission
+//! tracker, which re-sends through the RPC.
+
+use reqwest::Client as HttpClient;
+use serde_json::{json, Value};
+use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
+use std::error::Error as StdError;
+use std::str::FromStr;
+
+use crate::tips::JITO_TIP_ACCOUNT;
+
+type Result<T> = std::result::Result<T, Box<dyn StdError>>;
+
+const JITO_BUNDLES_URL: &str = "https://mainnet.block-engine.jito.wtf/api/v1/bundles";
+
+/// Whether the transaction tips Jito; the block engine drops bundles that don't
+pub fn pays_jito_tip(transaction: &VersionedTransaction) -> bool {
+    let Ok(tip_account) = Pubkey::from_str(JITO_TIP_ACCOUNT) else {
+        return false;
+    };
+    transaction.message.static_account_keys().contains(&tip_account)
+}
+
+/// Submit a signed transaction as a Jito bundle. Returns the transaction's
+/// signature.
+pub async fn send_protected(signed_tx: &[u8]) -> Result<String> {
+    let transaction: VersionedTransaction = bincode::deserialize(signed_tx)?;
+    if !pays_jito_tip(&transaction) {
+        return Err("MEV-protected swaps must include a Jito tip".into());
+    }
+    let signature = transaction
+        .signatures
+        .first()
+        .ok_or("Transaction is not signed")?
+        .to_string();
+
+    let response: Value = HttpClient::new()
+        .post(JITO_BUNDLES_URL)
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendBundle",
+            "params": [[base64::encode(signed_tx)], {"encoding": "base64"}],
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Jito block engine request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Jito block engine response: {}", e))?;
+
+    if let Some(error) = response.get("error") {
+        return Err(format!("Jito rejected the bundle: {}", error).into());
+    }
+    let bundle_id = response["result"]
+        .as_str()
+        .ok_or_else(|| format!("Unknown Jito block engine response: {}", response))?;
+    println!("🛡️ Sent protected swap {} in Jito bundle {}", signature, bundle_id);
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{hash::Hash, message::Message, system_instruction, transaction::Transaction};
+
+    #[test]
+    fn test_pays_jito_tip() {
+        let payer = Pubkey::new_unique();
+        let tip = system_instruction::transfer(&payer, &Pubkey::from_str(JITO_TIP_ACCOUNT).unwrap(), 1_000);
+        let other = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000);
+
+        let tipped = VersionedTransaction::from(Transaction::new_unsigned(
+            Message::new_with_blockhash(&[other.clone(), tip], Some(&payer), &Hash::default()),
+        ));
+        assert!(pays_jito_tip(&tipped));
+
+        let untipped = VersionedTransaction::from(Transaction::new_unsigned(
+            Message::new_with_blockhash(&[other], Some(&payer), &Hash::default()),
+        ));
+        assert!(!pays_jito_tip(&untipped));
+    }
+}
@@ -0,0 +1,83 @@
+// src/swap/providers.rs
+//! Which aggregators the swap modal asks for quotes
+//!
+//! Disabled providers are never queried. A pinned provider is used whenever
+//! it returns a quote, even if another one quotes more; the best quote only
+//! decides when the pinned provider has none.
+
+use serde::{Deserialize, Serialize};
+
+/// Every aggregator the swap modal can route through
+pub const SWAP_PROVIDERS: [&str; 3] = ["Jupiter", "Dflow", "Titan"];
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SwapProviderSettings {
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    /// Provider used whenever it quotes, `None` for the best quote
+    #[serde(default)]
+    pub preferred: Option<String>,
+}
+
+impl SwapProviderSettings {
+    pub fn is_enabled(&self, provider: &str) -> bool {
+        !self.disabled.iter().any(|p| p == provider)
+    }
+
+    pub fn set_enabled(&mut self, provider: &str, enabled: bool) {
+        self.disabled.retain(|p| p != provider);
+        if !enabled {
+            self.disabled.push(provider.to_string());
+            if self.preferred.as_deref() == Some(provider) {
+                self.preferred = None;
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !SWAP_PROVIDERS.iter().any(|p| self.is_enabled(p)) {
+            return Err("Enable at least one swap provider".to_string());
+        }
+        if let Some(preferred) = &self.preferred {
+            if !self.is_enabled(preferred) {
+                return Err(format!("{} is pinned but disabled", preferred));
+            }
+        }
+        Ok(())
+    }
+
+    /// The quote to use out of `(provider, output amount)` quotes: the
+    /// pinned provider's if it has one, otherwise the highest output
+    pub fn pick<'a>(&self, quotes: &'a [(String, u64)]) -> Option<&'a (String, u64)> {
+        let quotes: Vec<&(String, u64)> = quotes.iter().filter(|(p, _)| self.is_enabled(p)).collect();
+        self.preferred
+            .as_ref()
+            .and_then(|preferred| quotes.iter().find(|(p, _)| p == preferred).copied())
+            .or_else(|| quotes.into_iter().max_by_key(|(_, output)| *output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_quote() {
+        let quotes = vec![("Jupiter".to_string(), 100), ("Dflow".to_string(), 120), ("Titan".to_string(), 110)];
+        let mut settings = SwapProviderSettings::default();
+        assert_eq!(settings.pick(&quotes).map(|(p, _)| p.as_str()), Some("Dflow"));
+
+        settings.preferred = Some("Titan".to_string());
+        assert_eq!(settings.pick(&quotes).map(|(p, _)| p.as_str()), Some("Titan"));
+        assert_eq!(settings.pick(&quotes[..2]).map(|(p, _)| p.as_str()), Some("Dflow"));
+
+        settings.set_enabled("Titan", false);
+        assert_eq!(settings.preferred, None);
+        settings.set_enabled("Dflow", false);
+        assert_eq!(settings.pick(&quotes).map(|(p, _)| p.as_str()), Some("Jupiter"));
+        assert!(settings.validate().is_ok());
+
+        settings.set_enabled("Jupiter", false);
+        assert!(settings.validate().is_err());
+    }
+}
@@ -0,0 +1,163 @@
+// src/swap/route.rs
+//! Route breakdown of swap quotes
+//!
+//! Jupiter, Dflow and Titan each describe a quote's route as a list of
+//! legs through individual AMMs. They're normalized here so the swap modal
+//! can show where each provider sends the swap and what every leg costs.
+
+use serde_json::Value;
+
+use crate::titan::codec::bytes_to_base58;
+use crate::titan::SwapRoute as TitanSwapRoute;
+
+/// One hop of a route through a single AMM, amounts in base units
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteLeg {
+    pub venue: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub fee_amount: Option<u64>,
+    pub fee_mint: Option<String>,
+    /// Share of the swap going through this leg when the route splits
+    pub percent: Option<f64>,
+}
+
+fn amount(value: &Value) -> u64 {
+    match value {
+        Value::String(s) => s.parse().unwrap_or(0),
+        other => other.as_u64().unwrap_or(0),
+    }
+}
+
+fn string(value: &Value) -> Option<String> {
+    value.as_str().filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+/// Legs of a Jupiter `routePlan` (`{swapInfo: {...}, percent}` entries)
+pub fn jupiter_route_legs(route_plan: &[Value]) -> Vec<RouteLeg> {
+    route_plan
+        .iter()
+        .filter_map(|step| {
+            let info = &step["swapInfo"];
+            Some(RouteLeg {
+                venue: string(&info["label"]).unwrap_or_else(|| "Unknown AMM".to_string()),
+                input_mint: string(&info["inputMint"])?,
+                output_mint: string(&info["outputMint"])?,
+                in_amount: amount(&info["inAmount"]),
+                out_amount: amount(&info["outAmount"]),
+                fee_amount: Some(amount(&info["feeAmount"])).filter(|fee| *fee > 0),
+                fee_mint: string(&info["feeMint"]),
+                percent: step["percent"].as_f64(),
+            })
+        })
+        .collect()
+}
+
+/// Legs of a Dflow `routePlan` (flat entries naming the `venue`)
+pub fn dflow_route_legs(route_plan: &[Value]) -> Vec<RouteLeg> {
+    route_plan
+        .iter()
+        .filter_map(|step| {
+            Some(RouteLeg {
+                venue: string(&step["venue"])
+                    .or_else(|| string(&step["label"]))
+                    .unwrap_or_else(|| "Unknown AMM".to_string()),
+                input_mint: string(&step["inputMint"])?,
+                output_mint: string(&step["outputMint"])?,
+                in_amount: amount(&step["inAmount"]),
+                out_amount: amount(&step["outAmount"]),
+                fee_amount: None,
+                fee_mint: None,
+                percent: None,
+            })
+        })
+        .collect()
+}
+
+/// Legs of a Titan route (`alloc_ppb` is the share in parts per billion)
+pub fn titan_route_legs(route: &TitanSwapRoute) -> Vec<RouteLeg> {
+    route
+        .steps
+        .iter()
+        .map(|step| RouteLeg {
+            venue: step.label.clone(),
+            input_mint: bytes_to_base58(&step.input_mint),
+            output_mint: bytes_to_base58(&step.output_mint),
+            in_amount: step.in_amount,
+            out_amount: step.out_amount,
+            fee_amount: step.fee_amount.filter(|fee| *fee > 0),
+            fee_mint: step.fee_mint.as_ref().map(bytes_to_base58),
+            percent: (step.alloc_ppb > 0 && step.alloc_ppb < 1_000_000_000).then(|| step.alloc_ppb as f64 / 10_000_000.0),
+        })
+        .collect()
+}
+
+/// "Orca → Raydium CLMM", the venues in route order without repeats
+pub fn route_summary(legs: &[RouteLeg]) -> String {
+    let mut venues: Vec<&str> = Vec::new();
+    for leg in legs {
+        if !venues.contains(&leg.venue.as_str()) {
+            venues.push(&leg.venue);
+        }
+    }
+    venues.join(" → ")
+}
+
+/// "Orca: 1.5 SOL → 210.3 USDC, fee 0.0003 SOL (60%)". `token` gives the
+/// symbol and decimals of a mint when they're known.
+pub fn describe_leg(leg: &RouteLeg, token: impl Fn(&str) -> Option<(String, u8)>) -> String {
+    let format_amount = |amount: u64, mint: &str| match token(mint) {
+        Some((symbol, decimals)) => format!("{} {}", amount as f64 / 10_f64.powi(decimals as i32), symbol),
+        None => format!("{} of {}…", amount, mint.chars().take(4).collect::<String>()),
+    };
+    let mut line = format!(
+        "{}: {} → {}",
+        leg.venue,
+        format_amount(leg.in_amount, &leg.input_mint),
+        format_amount(leg.out_amount, &leg.output_mint)
+    );
+    if let Some(fee) = leg.fee_amount {
+        let fee_mint = leg.fee_mint.as_deref().unwrap_or(&leg.input_mint);
+        line.push_str(&format!(", fee {}", format_amount(fee, fee_mint)));
+    }
+    if let Some(percent) = leg.percent.filter(|p| *p < 100.0) {
+        line.push_str(&format!(" ({}%)", percent));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SOL: &str = "So11111111111111111111111111111111111111112";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    #[test]
+    fn test_route_legs() {
+        let jupiter = vec![
+            json!({"swapInfo": {"label": "Orca", "inputMint": SOL, "outputMint": USDC, "inAmount": "600000000", "outAmount": "120000000", "feeAmount": "300000", "feeMint": SOL}, "percent": 60}),
+            json!({"swapInfo": {"label": "Raydium", "inputMint": SOL, "outputMint": USDC, "inAmount": "400000000", "outAmount": "80000000", "feeAmount": "0", "feeMint": SOL}, "percent": 40}),
+            json!({"swapInfo": {"label": "Orca"}}),
+        ];
+        let legs = jupiter_route_legs(&jupiter);
+        assert_eq!(legs.len(), 2);
+        assert_eq!(legs[1].fee_amount, None);
+        assert_eq!(route_summary(&legs), "Orca → Raydium");
+
+        let token = |mint: &str| match mint {
+            SOL => Some(("SOL".to_string(), 9)),
+            USDC => Some(("USDC".to_string(), 6)),
+            _ => None,
+        };
+        assert_eq!(describe_leg(&legs[0], token), "Orca: 0.6 SOL → 120 USDC, fee 0.0003 SOL (60%)");
+
+        let dflow = vec![json!({"venue": "Meteora DLMM", "inputMint": SOL, "outputMint": USDC, "inAmount": 1000, "outAmount": "2000"})];
+        let legs = dflow_route_legs(&dflow);
+        assert_eq!(legs[0].in_amount, 1000);
+        assert_eq!(describe_leg(&legs[0], |_| None), "Meteora DLMM: 1000 of So11… → 2000 of EPjF…");
+    }
+}
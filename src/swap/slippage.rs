@@ -0,0 +1,107 @@
+// src/swap/slippage.rs
+//! Slippage setting shared by every swap route
+//!
+//! A fixed tolerance is sent as-is to Jupiter, Dflow and Titan. Dynamic
+//! slippage lets each aggregator pick a tolerance for the route: Jupiter
+//! simulates the swap (capped at `DYNAMIC_MAX_BPS`), Dflow uses its `auto`
+//! mode and Titan its own default.
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_SLIPPAGE_BPS: u16 = 50;
+/// Highest fixed tolerance accepted, 50%
+pub const MAX_SLIPPAGE_BPS: u16 = 5_000;
+/// Upper bound Jupiter's dynamic slippage may pick
+pub const DYNAMIC_MAX_BPS: u16 = 300;
+/// Tolerances offered as one-tap choices
+pub const SLIPPAGE_PRESETS_BPS: [u16; 4] = [10, 50, 100, 300];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", content = "bps", rename_all = "lowercase")]
+pub enum Slippage {
+    Fixed(u16),
+    Dynamic,
+}
+
+impl Default for Slippage {
+    fn default() -> Self {
+        Slippage::Fixed(DEFAULT_SLIPPAGE_BPS)
+    }
+}
+
+impl Slippage {
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self, Slippage::Dynamic)
+    }
+
+    /// "0.5%" or "Dynamic"
+    pub fn label(&self) -> String {
+        match self {
+            Slippage::Fixed(bps) => format!("{}%", *bps as f64 / 100.0),
+            Slippage::Dynamic => "Dynamic".to_string(),
+        }
+    }
+
+    /// `slippageBps` for a Jupiter quote; with dynamic slippage this is the
+    /// cap the simulated tolerance may not exceed
+    pub fn jupiter_bps(&self) -> u16 {
+        match self {
+            Slippage::Fixed(bps) => *bps,
+            Slippage::Dynamic => DYNAMIC_MAX_BPS,
+        }
+    }
+
+    /// `slippageBps` query value for a Dflow quote
+    pub fn dflow_param(&self) -> String {
+        match self {
+            Slippage::Fixed(bps) => bps.to_string(),
+            Slippage::Dynamic => "auto".to_string(),
+        }
+    }
+
+    /// Titan's `slippage_bps`; `None` leaves it to Titan
+    pub fn titan_bps(&self) -> Option<u16> {
+        match self {
+            Slippage::Fixed(bps) => Some(*bps),
+            Slippage::Dynamic => None,
+        }
+    }
+}
+
+/// Tolerance in bps from a percentage typed by the user ("0.5" → 50)
+pub fn parse_slippage_percent(input: &str) -> Result<u16, String> {
+    let percent: f64 = input
+        .trim()
+        .trim_end_matches('%')
+        .trim()
+        .parse()
+        .map_err(|_| "Enter the slippage as a percentage".to_string())?;
+    let bps = (percent * 100.0).round();
+    if bps < 1.0 {
+        return Err("Slippage must be at least 0.01%".to_string());
+    }
+    if bps > MAX_SLIPPAGE_BPS as f64 {
+        return Err(format!("Slippage can't be above {}%", MAX_SLIPPAGE_BPS / 100));
+    }
+    Ok(bps as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slippage_params() {
+        assert_eq!(Slippage::default().label(), "0.5%");
+        assert_eq!(Slippage::Fixed(100).dflow_param(), "100");
+        assert_eq!(Slippage::Dynamic.dflow_param(), "auto");
+        assert_eq!(Slippage::Dynamic.jupiter_bps(), DYNAMIC_MAX_BPS);
+        assert_eq!(Slippage::Dynamic.titan_bps(), None);
+
+        assert_eq!(parse_slippage_percent("0.5"), Ok(50));
+        assert_eq!(parse_slippage_percent(" 1.25 % "), Ok(125));
+        assert!(parse_slippage_percent("0").is_err());
+        assert!(parse_slippage_percent("60").is_err());
+        assert!(parse_slippage_percent("abc").is_err());
+    }
+}
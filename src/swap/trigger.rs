@@ -0,0 +1,260 @@
+// src/swap/trigger.rs
+//! Jupiter Trigger API client for limit orders
+
+use reqwest::Client as HttpClient;
+use serde_json::{json, Value};
+use solana_sdk::transaction::VersionedTransaction;
+use std::error::Error as StdError;
+
+use crate::signing::{sign_available_slots, TransactionSigner};
+use crate::storage::{load_limit_orders, save_limit_orders};
+use crate::swap::types::{CreateOrderParams, LimitOrder, OrderStatus};
+
+type Result<T> = std::result::Result<T, Box<dyn StdError>>;
+
+const TRIGGER_API_URL: &str = "https://lite-api.jup.ag/trigger/v1";
+/// Orders requested per status when listing
+const ORDERS_PAGE: u32 = 1;
+
+fn short_mint(mint: &str) -> String {
+    mint.chars().take(4).collect::<String>() + "…"
+}
+
+fn amount(order: &Value, field: &str) -> f64 {
+    match &order[field] {
+        Value::String(s) => s.parse().unwrap_or(0.0),
+        value => value.as_f64().unwrap_or(0.0),
+    }
+}
+
+fn timestamp(order: &Value, field: &str) -> Option<i64> {
+    let value = order[field].as_str()?;
+    chrono::DateTime::parse_from_rfc3339(value).ok().map(|d| d.timestamp())
+}
+
+/// An order from a `getTriggerOrders` response
+fn parse_order(order: &Value) -> Option<LimitOrder> {
+    let input_mint = order["inputMint"].as_str()?.to_string();
+    let output_mint = order["outputMint"].as_str()?.to_string();
+    Some(LimitOrder {
+        order: order["orderKey"].as_str()?.to_string(),
+        input_symbol: short_mint(&input_mint),
+        output_symbol: short_mint(&output_mint),
+        input_mint,
+        output_mint,
+        making_amount: amount(order, "makingAmount"),
+        taking_amount: amount(order, "takingAmount"),
+        status: OrderStatus::from_api(order["status"].as_str().unwrap_or("open")),
+        created_at: timestamp(order, "createdAt").unwrap_or_default(),
+        expires_at: timestamp(order, "expiredAt"),
+        signature: order["openTx"].as_str().map(str::to_string),
+    })
+}
+
+/// Orders the API knows replace the stored ones (keeping their symbols);
+/// stored orders the API hasn't indexed yet are kept. Newest first.
+pub fn merge_orders(stored: Vec<LimitOrder>, remote: Vec<LimitOrder>) -> Vec<LimitOrder> {
+    let mut merged: Vec<LimitOrder> = remote
+        .into_iter()
+        .map(|mut order| {
+            if let Some(known) = stored.iter().find(|s| s.order == order.order) {
+                order.input_symbol = known.input_symbol.clone();
+                order.output_symbol = known.output_symbol.clone();
+                order.signature = order.signature.or_else(|| known.signature.clone());
+            }
+            order
+        })
+        .collect();
+    for order in stored {
+        if !merged.iter().any(|m| m.order == order.order) {
+            merged.push(order);
+        }
+    }
+    merged.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    merged
+}
+
+pub struct TriggerClient {
+    http: HttpClient,
+}
+
+impl TriggerClient {
+    pub fn new() -> Self {
+        Self { http: HttpClient::new() }
+    }
+
+    async fn post(&self, path: &str, body: Value) -> Result<Value> {
+        let response = self
+            .http
+            .post(format!("{}/{}", TRIGGER_API_URL, path))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("Trigger API request failed: {}", e))?;
+        let status = response.status();
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Trigger API response: {}", e))?;
+        if !status.is_success() {
+            let error = body["error"].as_str().or(body["cause"].as_str()).unwrap_or("unknown error");
+            return Err(format!("Trigger API error {}: {}", status, error).into());
+        }
+        Ok(body)
+    }
+
+    /// Sign the transaction the API built and have the API submit it
+    async fn sign_and_execute(&self, signer: &dyn TransactionSigner, built: &Value) -> Result<String> {
+        let unsigned = built["transaction"].as_str().ok_or("No transaction in Trigger API response")?;
+        let request_id = built["requestId"].as_str().ok_or("No request id in Trigger API response")?;
+
+        let mut transaction: VersionedTransaction = bincode::deserialize(&base64::decode(unsigned)?)?;
+        sign_available_slots(&mut transaction, &[signer]).await?;
+        let signed = base64::encode(bincode::serialize(&transaction)?);
+
+        let executed = self
+            .post("execute", json!({ "signedTransaction": signed, "requestId": request_id }))
+            .await?;
+        if executed["status"].as_str() != Some("Success") {
+            let error = executed["error"].as_str().unwrap_or("unknown error");
+            return Err(format!("Order transaction failed: {}", error).into());
+        }
+        Ok(executed["signature"].as_str().unwrap_or_default().to_string())
+    }
+
+    /// Create a limit order and store it
+    pub async fn create_order_with_signer(&self, signer: &dyn TransactionSigner, params: &CreateOrderParams) -> Result<LimitOrder> {
+        let maker = signer.get_public_key().await?;
+        let mut order_params = json!({
+            "makingAmount": params.making_amount.to_string(),
+            "takingAmount": params.taking_amount.to_string(),
+        });
+        if let Some(expires_at) = params.expires_at {
+            order_params["expiredAt"] = json!(expires_at.to_string());
+        }
+        let built = self
+            .post(
+                "createOrder",
+                json!({
+                    "inputMint": params.input_mint,
+                    "outputMint": params.output_mint,
+                    "maker": maker,
+                    "payer": maker,
+                    "params": order_params,
+                    "computeUnitPrice": "auto",
+                }),
+            )
+            .await?;
+        let order_key = built["order"].as_str().ok_or("No order address in Trigger API response")?.to_string();
+        let signature = self.sign_and_execute(signer, &built).await?;
+        println!("🎯 Limit order {} created: {}", order_key, signature);
+
+        let order = LimitOrder {
+            order: order_key,
+            input_mint: params.input_mint.clone(),
+            output_mint: params.output_mint.clone(),
+            input_symbol: params.input_symbol.clone(),
+            output_symbol: params.output_symbol.clone(),
+            making_amount: params.making_amount as f64 / 10_f64.powi(params.input_decimals as i32),
+            taking_amount: params.taking_amount as f64 / 10_f64.powi(params.output_decimals as i32),
+            status: OrderStatus::Open,
+            created_at: chrono::Utc::now().timestamp(),
+            expires_at: params.expires_at,
+            signature: Some(signature),
+        };
+        let mut stored = load_limit_orders(&maker);
+        stored.insert(0, order.clone());
+        save_limit_orders(&maker, &stored)?;
+        Ok(order)
+    }
+
+    /// Cancel an open order; the remaining input tokens go back to the wallet
+    pub async fn cancel_order_with_signer(&self, signer: &dyn TransactionSigner, order: &str) -> Result<String> {
+        let maker = signer.get_public_key().await?;
+        let built = self
+            .post("cancelOrder", json!({ "maker": maker, "order": order, "computeUnitPrice": "auto" }))
+            .await?;
+        let signature = self.sign_and_execute(signer, &built).await?;
+        println!("🎯 Limit order {} cancelled: {}", order, signature);
+
+        let mut stored = load_limit_orders(&maker);
+        for stored_order in stored.iter_mut().filter(|o| o.order == order) {
+            stored_order.status = OrderStatus::Cancelled;
+        }
+        save_limit_orders(&maker, &stored)?;
+        Ok(signature)
+    }
+
+    async fn fetch_orders(&self, wallet: &str, status: &str) -> Result<Vec<LimitOrder>> {
+        let page = ORDERS_PAGE.to_string();
+        let response = self
+            .http
+            .get(format!("{}/getTriggerOrders", TRIGGER_API_URL))
+            .query(&[("user", wallet), ("orderStatus", status), ("page", page.as_str())])
+            .send()
+            .await
+            .map_err(|e| format!("Trigger API request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Trigger API error {}", response.status()).into());
+        }
+        let body: Value = response.json().await?;
+        Ok(body["orders"].as_array().map(|orders| orders.iter().filter_map(parse_order).collect()).unwrap_or_default())
+    }
+
+    /// The wallet's open and past orders, updated from the API when it's
+    /// reachable. The stored orders are returned as they are otherwise.
+    pub async fn get_orders(&self, wallet: &str) -> (Vec<LimitOrder>, Option<String>) {
+        let stored = load_limit_orders(wallet);
+        let remote = match self.fetch_orders(wallet, "active").await {
+            Ok(mut active) => match self.fetch_orders(wallet, "history").await {
+                Ok(history) => {
+                    active.extend(history);
+                    active
+                }
+                Err(e) => return (stored, Some(e.to_string())),
+            },
+            Err(e) => return (stored, Some(e.to_string())),
+        };
+        let merged = merge_orders(stored, remote);
+        if let Err(e) = save_limit_orders(wallet, &merged) {
+            println!("⚠️ Failed to store limit orders: {}", e);
+        }
+        (merged, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_merge_orders() {
+        let api_order = json!({
+            "orderKey": "Order1",
+            "inputMint": "So11111111111111111111111111111111111111112",
+            "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "makingAmount": "2",
+            "takingAmount": "400",
+            "status": "Completed",
+            "createdAt": "2025-01-02T03:04:05Z",
+        });
+        let remote = parse_order(&api_order).unwrap();
+        assert_eq!(remote.status, OrderStatus::Filled);
+        assert_eq!(remote.limit_price(), 200.0);
+
+        let stored = LimitOrder {
+            input_symbol: "SOL".to_string(),
+            output_symbol: "USDC".to_string(),
+            status: OrderStatus::Open,
+            signature: Some("sig".to_string()),
+            ..remote.clone()
+        };
+        let pending = LimitOrder { order: "Order2".to_string(), created_at: remote.created_at + 10, ..stored.clone() };
+        let merged = merge_orders(vec![stored, pending], vec![remote]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].order, "Order2");
+        assert_eq!(merged[1].status, OrderStatus::Filled);
+        assert_eq!(merged[1].input_symbol, "SOL");
+        assert_eq!(merged[1].signature.as_deref(), Some("sig"));
+    }
+}
@@ -0,0 +1,198 @@
+// src/swap/types.rs
+//! Type definitions for swap orders
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    Open,
+    Filled,
+    Cancelled,
+    Expired,
+}
+
+impl OrderStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrderStatus::Open => "Open",
+            OrderStatus::Filled => "Filled",
+            OrderStatus::Cancelled => "Cancelled",
+            OrderStatus::Expired => "Expired",
+        }
+    }
+
+    /// Status from the Trigger API's order status
+    pub fn from_api(status: &str) -> Self {
+        match status.to_ascii_lowercase().as_str() {
+            "completed" | "filled" => OrderStatus::Filled,
+            "cancelled" | "canceled" => OrderStatus::Cancelled,
+            "expired" => OrderStatus::Expired,
+            _ => OrderStatus::Open,
+        }
+    }
+}
+
+/// A limit order: sell `making_amount` of the input token for at least
+/// `taking_amount` of the output token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LimitOrder {
+    /// Order account address
+    pub order: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub input_symbol: String,
+    pub output_symbol: String,
+    /// UI units
+    pub making_amount: f64,
+    /// UI units
+    pub taking_amount: f64,
+    pub status: OrderStatus,
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+    /// Signature of the transaction that created the order
+    pub signature: Option<String>,
+}
+
+impl LimitOrder {
+    /// Output tokens received per input token when the order fills
+    pub fn limit_price(&self) -> f64 {
+        if self.making_amount > 0.0 {
+            self.taking_amount / self.making_amount
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A limit order to create, amounts in base units
+#[derive(Debug, Clone)]
+pub struct CreateOrderParams {
+    pub input_mint: String,
+    pub output_mint: String,
+    pub input_symbol: String,
+    pub output_symbol: String,
+    pub input_decimals: u8,
+    pub output_decimals: u8,
+    pub making_amount: u64,
+    pub taking_amount: u64,
+    /// Unix time the order expires at, `None` to keep it open until filled or cancelled
+    pub expires_at: Option<i64>,
+}
+
+/// One executed DCA buy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DcaBuy {
+    pub at: i64,
+    pub signature: String,
+    /// Input tokens spent, UI units
+    pub spent: f64,
+    /// Output tokens quoted for the buy, UI units
+    pub received: f64,
+    /// Signed by the wallet's session key rather than the wallet itself
+    pub by_session_key: bool,
+}
+
+/// Recurring buys of `output_mint` with `amount_per_buy` of `input_mint`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DcaSchedule {
+    pub id: String,
+    /// Wallet the schedule buys for
+    pub owner: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub input_symbol: String,
+    pub output_symbol: String,
+    pub input_decimals: u8,
+    pub output_decimals: u8,
+    /// Base units
+    pub amount_per_buy: u64,
+    pub interval_secs: i64,
+    pub total_buys: u32,
+    pub next_buy_at: i64,
+    pub paused: bool,
+    pub created_at: i64,
+    /// Oldest first
+    #[serde(default)]
+    pub buys: Vec<DcaBuy>,
+}
+
+impl DcaSchedule {
+    pub fn remaining_buys(&self) -> u32 {
+        self.total_buys.saturating_sub(self.buys.len() as u32)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.remaining_buys() == 0
+    }
+
+    pub fn is_due(&self, now: i64) -> bool {
+        !self.paused && !self.is_complete() && now >= self.next_buy_at
+    }
+
+    /// Input tokens paid per output token over all buys
+    pub fn average_price(&self) -> Option<f64> {
+        let spent: f64 = self.buys.iter().map(|b| b.spent).sum();
+        let received: f64 = self.buys.iter().map(|b| b.received).sum();
+        (received > 0.0).then(|| spent / received)
+    }
+
+    /// Add an executed buy and schedule the next one. Periods missed while
+    /// the app was closed are skipped rather than bought back to back.
+    pub fn record(&mut self, buy: DcaBuy) {
+        let mut next = self.next_buy_at + self.interval_secs;
+        if next <= buy.at {
+            next = buy.at + self.interval_secs;
+        }
+        self.next_buy_at = next;
+        self.buys.push(buy);
+    }
+}
+
+/// A swap sent from the swap modal, with the quote it was sent on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapRecord {
+    pub signature: String,
+    pub at: i64,
+    /// "Jupiter", "Dflow" or "Titan"
+    pub provider: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub input_symbol: String,
+    pub output_symbol: String,
+    pub output_decimals: u8,
+    /// Input tokens sold, UI units
+    pub input_amount: f64,
+    /// Output tokens quoted, UI units
+    pub quoted_output: f64,
+    pub slippage: crate::swap::Slippage,
+    pub mev_protected: bool,
+    /// Signature and priority fees, lamports
+    pub network_fee: u64,
+    pub jito_tip: u64,
+    pub app_tip: u64,
+    /// Output tokens actually received, UI units, once read from the landed transaction
+    #[serde(default)]
+    pub received: Option<f64>,
+}
+
+impl SwapRecord {
+    /// Output tokens quoted per input token
+    pub fn quoted_price(&self) -> f64 {
+        if self.input_amount > 0.0 {
+            self.quoted_output / self.input_amount
+        } else {
+            0.0
+        }
+    }
+
+    /// How far the fill fell short of the quote in bps, negative when it beat it
+    pub fn shortfall_bps(&self) -> Option<f64> {
+        let received = self.received?;
+        (self.quoted_output > 0.0).then(|| (self.quoted_output - received) / self.quoted_output * 10_000.0)
+    }
+
+    /// Fees and tips paid, lamports
+    pub fn total_fees(&self) -> u64 {
+        self.network_fee + self.jito_tip + self.app_tip
+    }
+}
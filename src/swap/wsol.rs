@@ -0,0 +1,87 @@
+// src/swap/wsol.rs
+//! Wrapped SOL cleanup
+//!
+//! Swaps through SOL wrap it into a WSOL token account and normally unwrap
+//! it again in the same transaction. A swap that fails halfway, or a route
+//! that leaves the account open, strands SOL there where it doesn't show as
+//! SOL. Closing the account unwraps it: the balance and the rent come back
+//! to the wallet as SOL.
+
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use std::str::FromStr;
+
+use crate::rpc::{get_token_accounts_by_owner, TokenAccountFilter, TokenAccountInfo};
+use crate::signing::TransactionSigner;
+use crate::swap::dca::SOL_MINT;
+use crate::transaction::{pack_instruction_groups, TransactionClient};
+
+/// The owner's wrapped SOL accounts, with or without a balance
+pub async fn find_wrapped_sol(owner: &str, rpc_url: Option<&str>) -> Result<Vec<TokenAccountInfo>, String> {
+    get_token_accounts_by_owner(owner, Some(TokenAccountFilter::Mint(SOL_MINT.to_string())), rpc_url).await
+}
+
+/// SOL held in wrapped SOL accounts, excluding their rent
+pub fn wrapped_total(accounts: &[TokenAccountInfo]) -> f64 {
+    accounts.iter().map(|account| account.amount).sum()
+}
+
+/// Close every account, sending its SOL back to the owner
+pub fn unwrap_instructions(owner: &Pubkey, accounts: &[TokenAccountInfo]) -> Result<Vec<Instruction>, String> {
+    accounts
+        .iter()
+        .map(|account| {
+            let address = Pubkey::from_str(&account.pubkey).map_err(|e| format!("Invalid token account: {}", e))?;
+            let program = Pubkey::from_str(&account.program_id).map_err(|e| format!("Invalid token program: {}", e))?;
+            spl_token::instruction::close_account(&program, &address, owner, owner, &[])
+                .map_err(|e| format!("Failed to build close instruction: {}", e))
+        })
+        .collect()
+}
+
+/// Unwrap all of the accounts, in as few transactions as fit. Returns the
+/// signatures.
+pub async fn unwrap_all(
+    client: &TransactionClient,
+    signer: &dyn TransactionSigner,
+    accounts: &[TokenAccountInfo],
+) -> Result<Vec<String>, String> {
+    let owner = signer.get_public_key().await.map_err(|e| e.to_string())?;
+    let owner = Pubkey::from_str(&owner).map_err(|e| format!("Invalid wallet address: {}", e))?;
+    let groups = unwrap_instructions(&owner, accounts)?.into_iter().map(|ix| vec![ix]).collect();
+    let mut signatures = Vec::new();
+    for builder in pack_instruction_groups(&owner, groups)? {
+        let signature = client.send_built(builder, signer).await.map_err(|e| e.to_string())?;
+        println!("🌯 Unwrapped SOL: {}", signature);
+        signatures.push(signature);
+    }
+    Ok(signatures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unwrap_instructions() {
+        let owner = Pubkey::new_unique();
+        let account = TokenAccountInfo {
+            pubkey: Pubkey::new_unique().to_string(),
+            mint: SOL_MINT.to_string(),
+            owner: owner.to_string(),
+            amount: 0.25,
+            decimals: 9,
+            state: "initialized".to_string(),
+            program_id: spl_token::id().to_string(),
+        };
+        let empty = TokenAccountInfo { amount: 0.0, pubkey: Pubkey::new_unique().to_string(), ..account.clone() };
+        let accounts = vec![account, empty];
+        assert_eq!(wrapped_total(&accounts), 0.25);
+
+        let instructions = unwrap_instructions(&owner, &accounts).unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].program_id, spl_token::id());
+        // Destination and authority are both the owner
+        assert_eq!(instructions[0].accounts[1].pubkey, owner);
+        assert_eq!(instructions[0].accounts[2].pubkey, owner);
+    }
+}
@@ -0,0 +1,235 @@
+// src/tips.rs
+//! Jito tip sizing
+//!
+//! Transactions sent with JitoTx enabled pay a tip to a Jito tip account.
+//! Instead of a fixed amount the user can pick a percentile of recently
+//! landed tips, taken from Jito's tip floor feed, either as the default in
+//! Jito settings or per transaction. The app's own (jules) tip is a setting
+//! in `config::app_tip`; `tip_instructions` adds both tips to a transaction.
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_instruction};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const TIP_FLOOR_URL: &str = "https://bundles.jito.wtf/api/v1/bundles/tip_floor";
+/// Tip floor data is refreshed at most this often
+const TIP_FLOOR_CACHE: Duration = Duration::from_secs(30);
+
+/// Jito tip account the tip is paid to
+pub const JITO_TIP_ACCOUNT: &str = "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL";
+/// Every Jito tip account; transactions built elsewhere (e.g. by a swap API)
+/// may tip any of them
+pub const JITO_TIP_ACCOUNTS: [&str; 8] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+/// App tip paid on sends (with JitoTx) and swaps
+pub const JULES_TIP_ACCOUNT: &str = "juLesoSmdTcRtzjCzYzRoHrnF8GhVu6KCV7uxq7nJGp";
+
+/// Tip used for `TipLevel::Fixed` and when the tip floor can't be fetched
+pub const DEFAULT_JITO_TIP_LAMPORTS: u64 = 100_000; // 0.0001 SOL
+/// Jito ignores bundles tipping less than this
+const MIN_JITO_TIP_LAMPORTS: u64 = 1_000;
+/// Never tip more than this, whatever the floor says
+const MAX_JITO_TIP_LAMPORTS: u64 = 10_000_000; // 0.01 SOL
+
+static TIP_FLOOR: OnceLock<Mutex<Option<(Instant, TipFloor)>>> = OnceLock::new();
+
+/// How much to tip
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TipLevel {
+    #[default]
+    Fixed,
+    P25,
+    P50,
+    P75,
+    P95,
+    P99,
+}
+
+impl TipLevel {
+    pub const ALL: [TipLevel; 6] = [
+        TipLevel::Fixed,
+        TipLevel::P25,
+        TipLevel::P50,
+        TipLevel::P75,
+        TipLevel::P95,
+        TipLevel::P99,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TipLevel::Fixed => "Fixed (0.0001 SOL)",
+            TipLevel::P25 => "Low (25th percentile)",
+            TipLevel::P50 => "Normal (median)",
+            TipLevel::P75 => "High (75th percentile)",
+            TipLevel::P95 => "Very high (95th percentile)",
+            TipLevel::P99 => "Urgent (99th percentile)",
+        }
+    }
+
+    /// Stable key for select inputs
+    pub fn key(&self) -> &'static str {
+        match self {
+            TipLevel::Fixed => "fixed",
+            TipLevel::P25 => "p25",
+            TipLevel::P50 => "p50",
+            TipLevel::P75 => "p75",
+            TipLevel::P95 => "p95",
+            TipLevel::P99 => "p99",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL.into_iter().find(|level| level.key() == key).unwrap_or_default()
+    }
+}
+
+/// Landed tip percentiles, in lamports
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TipFloor {
+    pub p25: u64,
+    pub p50: u64,
+    pub p75: u64,
+    pub p95: u64,
+    pub p99: u64,
+}
+
+impl TipFloor {
+    /// Tip for `level`, clamped to sane bounds
+    pub fn lamports_for(&self, level: TipLevel) -> u64 {
+        let raw = match level {
+            TipLevel::Fixed => return DEFAULT_JITO_TIP_LAMPORTS,
+            TipLevel::P25 => self.p25,
+            TipLevel::P50 => self.p50,
+            TipLevel::P75 => self.p75,
+            TipLevel::P95 => self.p95,
+            TipLevel::P99 => self.p99,
+        };
+        raw.clamp(MIN_JITO_TIP_LAMPORTS, MAX_JITO_TIP_LAMPORTS)
+    }
+}
+
+/// Parse the tip floor feed, an array whose first entry holds the
+/// percentiles in SOL
+pub fn parse_tip_floor(json: &Value) -> Option<TipFloor> {
+    let entry = json.as_array()?.first()?;
+    let lamports = |field: &str| -> Option<u64> {
+        entry[field].as_f64().map(|sol| (sol * 1_000_000_000.0).round() as u64)
+    };
+    Some(TipFloor {
+        p25: lamports("landed_tips_25th_percentile")?,
+        p50: lamports("landed_tips_50th_percentile")?,
+        p75: lamports("landed_tips_75th_percentile")?,
+        p95: lamports("landed_tips_95th_percentile")?,
+        p99: lamports("landed_tips_99th_percentile")?,
+    })
+}
+
+fn tip_floor_cache() -> &'static Mutex<Option<(Instant, TipFloor)>> {
+    TIP_FLOOR.get_or_init(|| Mutex::new(None))
+}
+
+/// Current tip floor, cached for a short while
+pub async fn fetch_tip_floor() -> Result<TipFloor, String> {
+    if let Ok(cache) = tip_floor_cache().lock() {
+        if let Some((fetched, floor)) = *cache {
+            if fetched.elapsed() < TIP_FLOOR_CACHE {
+                return Ok(floor);
+            }
+        }
+    }
+
+    let json: Value = Client::new()
+        .get(TIP_FLOOR_URL)
+        .send()
+        .await
+        .map_err(|e| format!("Tip floor request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid tip floor response: {}", e))?;
+    let floor = parse_tip_floor(&json).ok_or("Tip floor response is missing percentiles")?;
+
+    if let Ok(mut cache) = tip_floor_cache().lock() {
+        *cache = Some((Instant::now(), floor));
+    }
+    Ok(floor)
+}
+
+/// Tip transfers paid by `payer`: the app tip, unless it's set to zero, and
+/// the Jito tip when given
+pub fn tip_instructions(payer: &Pubkey, jito_tip: Option<u64>) -> Result<Vec<Instruction>, String> {
+    let mut instructions = Vec::new();
+    let app_tip = crate::config::app_tip::app_tip_lamports();
+    if app_tip > 0 {
+        let jules = Pubkey::from_str(JULES_TIP_ACCOUNT).map_err(|e| format!("Invalid app tip address: {}", e))?;
+        instructions.push(system_instruction::transfer(payer, &jules, app_tip));
+    }
+    if let Some(jito_tip) = jito_tip {
+        let jito = Pubkey::from_str(JITO_TIP_ACCOUNT).map_err(|e| format!("Invalid Jito tip address: {}", e))?;
+        instructions.push(system_instruction::transfer(payer, &jito, jito_tip));
+    }
+    Ok(instructions)
+}
+
+pub fn is_jito_tip_account(account: &Pubkey) -> bool {
+    JITO_TIP_ACCOUNTS.iter().any(|tip_account| Pubkey::from_str(tip_account).ok().as_ref() == Some(account))
+}
+
+/// Tip in lamports for `level`, falling back to the fixed tip when the tip
+/// floor is unavailable
+pub async fn tip_for(level: TipLevel) -> u64 {
+    if level == TipLevel::Fixed {
+        return DEFAULT_JITO_TIP_LAMPORTS;
+    }
+    match fetch_tip_floor().await {
+        Ok(floor) => floor.lamports_for(level),
+        Err(e) => {
+            println!("⚠️ {}; using the fixed Jito tip", e);
+            DEFAULT_JITO_TIP_LAMPORTS
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_tip_floor() {
+        let json = json!([{
+            "time": "2024-09-01T12:58:00Z",
+            "landed_tips_25th_percentile": 0.000006,
+            "landed_tips_50th_percentile": 0.00001,
+            "landed_tips_75th_percentile": 0.000036,
+            "landed_tips_95th_percentile": 0.0014,
+            "landed_tips_99th_percentile": 0.05,
+            "ema_landed_tips_50th_percentile": 0.000018
+        }]);
+        let floor = parse_tip_floor(&json).unwrap();
+        assert_eq!(floor.p50, 10_000);
+        assert_eq!(floor.lamports_for(TipLevel::P25), 6_000);
+        assert_eq!(floor.lamports_for(TipLevel::P99), MAX_JITO_TIP_LAMPORTS);
+        assert_eq!(floor.lamports_for(TipLevel::Fixed), DEFAULT_JITO_TIP_LAMPORTS);
+        assert!(parse_tip_floor(&json!([])).is_none());
+    }
+
+    #[test]
+    fn test_tip_level_keys() {
+        for level in TipLevel::ALL {
+            assert_eq!(TipLevel::from_key(level.key()), level);
+        }
+        assert_eq!(TipLevel::from_key("unknown"), TipLevel::Fixed);
+    }
+}
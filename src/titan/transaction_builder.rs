@@ -5,21 +5,15 @@ use solana_sdk::{
     instruction::Instruction as SolanaInstruction,
     instruction::AccountMeta as SolanaAccountMeta,
     pubkey::Pubkey as SolanaPubkey,
-    message::{v0, VersionedMessage},
-    transaction::VersionedTransaction,
-    signature::Signature as SolanaSignature,
     hash::Hash,
     address_lookup_table::AddressLookupTableAccount,
-    system_instruction,
 };
-use serde_json::{json, Value};
-use reqwest::Client;
-use std::str::FromStr;
 
 use super::types::{SwapRoute, Instruction, AccountMeta, Pubkey};
-use crate::storage::get_current_jito_settings;
+use crate::tips;
 use crate::transaction::TransactionClient;
 use crate::timeout;
+use crate::token_approvals::{compile_swap_transaction, ApprovalCheck};
 
 /// Convert Titan's 32-byte pubkey to Solana Pubkey
 fn titan_pubkey_to_solana(pubkey: &Pubkey) -> Result<SolanaPubkey, String> {
@@ -58,50 +52,24 @@ async fn fetch_lookup_table_accounts(
     lookup_table_pubkeys: &[SolanaPubkey],
     rpc_url: &str,
 ) -> Result<Vec<AddressLookupTableAccount>, String> {
-    let client = Client::new();
+    println!("   Fetching {} lookup tables", lookup_table_pubkeys.len());
+    let addresses: Vec<String> = lookup_table_pubkeys.iter().map(|p| p.to_string()).collect();
+    let accounts = crate::rpc::get_multiple_accounts(&addresses, Some(rpc_url))
+        .await
+        .map_err(|e| format!("Failed to fetch lookup tables: {}", e))?;
     let mut lookup_table_accounts = Vec::new();
     
-    for pubkey in lookup_table_pubkeys {
-        println!("   Fetching lookup table: {}", pubkey);
+    for (pubkey, account) in lookup_table_pubkeys.iter().zip(accounts) {
+        let Some(account) = account else { continue };
         
-        let request = json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getAccountInfo",
-            "params": [
-                pubkey.to_string(),
-                {
-                    "encoding": "base64"
-                }
-            ]
-        });
+        // Parse the lookup table account
+        let lookup_table = AddressLookupTableAccount {
+            key: *pubkey,
+            addresses: parse_lookup_table_addresses(&account.data)?,
+        };
         
-        let response = client
-            .post(rpc_url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch lookup table: {}", e))?;
-        
-        let json: Value = response.json().await
-            .map_err(|e| format!("Failed to parse lookup table response: {}", e))?;
-        
-        // Extract account data
-        if let Some(data_array) = json["result"]["value"]["data"].as_array() {
-            if let Some(data_str) = data_array.get(0).and_then(|v| v.as_str()) {
-                let data = base64::decode(data_str)
-                    .map_err(|e| format!("Failed to decode lookup table data: {}", e))?;
-                
-                // Parse the lookup table account
-                let lookup_table = AddressLookupTableAccount {
-                    key: *pubkey,
-                    addresses: parse_lookup_table_addresses(&data)?,
-                };
-                
-                println!("   ✓ Loaded lookup table with {} addresses", lookup_table.addresses.len());
-                lookup_table_accounts.push(lookup_table);
-            }
-        }
+        println!("   ✓ Loaded lookup table with {} addresses", lookup_table.addresses.len());
+        lookup_table_accounts.push(lookup_table);
     }
     
     Ok(lookup_table_accounts)
@@ -139,15 +107,19 @@ fn parse_lookup_table_addresses(data: &[u8]) -> Result<Vec<SolanaPubkey>, String
 /// * `payer` - The transaction fee payer pubkey
 /// * `recent_blockhash` - Recent blockhash for the transaction
 /// * `rpc_url` - RPC endpoint to fetch lookup table accounts
+/// * `revoke_approvals` - Revoke token approvals the route grants at the end
 /// 
 /// # Returns
-/// Serialized transaction bytes ready for signing
+/// Serialized transaction bytes ready for signing, and the token approvals
+/// found in the route
 pub async fn build_transaction_from_route(
     route: &SwapRoute,
     payer: SolanaPubkey,
     recent_blockhash: Hash,
     rpc_url: &str,
-) -> Result<Vec<u8>, String> {
+    revoke_approvals: bool,
+    jito_tip: Option<u64>,
+) -> Result<(Vec<u8>, ApprovalCheck), String> {
     println!("Building transaction from Titan route");
     println!("   Instructions: {}", route.instructions.len());
     println!("   Lookup tables: {}", route.address_lookup_tables.len());
@@ -176,18 +148,9 @@ pub async fn build_transaction_from_route(
     let mut instructions = vec![timeout_ix];
     instructions.extend(titan_instructions);
     
-    // Add Jito tip if enabled
-    let jito_settings = get_current_jito_settings();
-    if jito_settings.jito_tx {
-        let jito_tip_address = SolanaPubkey::from_str("juLesoSmdTcRtzjCzYzRoHrnF8GhVu6KCV7uxq7nJGp")
-            .map_err(|e| format!("Invalid Jito tip address: {}", e))?;
-        
-        // Add 0.0001 SOL tip (100,000 lamports)
-        let tip_ix = system_instruction::transfer(&payer, &jito_tip_address, 100_000);
-        instructions.push(tip_ix);
-        
-        println!("   Added Jito tip (0.0001 SOL) to Titan swap");
-    }
+    // Add the app tip, and the Jito tip if JitoTx is enabled
+    instructions.extend(tips::tip_instructions(&payer, jito_tip)?);
+    println!("   Added tips to Titan swap (Jito tip {:?} lamports)", jito_tip);
     
     // Fetch lookup table accounts if any are provided
     let lookup_table_accounts = if !route.address_lookup_tables.is_empty() {
@@ -204,31 +167,23 @@ pub async fn build_transaction_from_route(
     };
     
     // Build V0 message with lookup tables
-    let message = v0::Message::try_compile(
+    let (transaction, approval_check) = compile_swap_transaction(
         &payer,
         &instructions,
         &lookup_table_accounts,
         recent_blockhash,
+        revoke_approvals,
     ).map_err(|e| format!("Failed to compile V0 message: {}", e))?;
     
     println!("   ✓ Compiled V0 message with {} lookup tables", lookup_table_accounts.len());
     
-    // Create versioned message
-    let versioned_message = VersionedMessage::V0(message);
-    
-    // Create transaction with placeholder signature
-    let transaction = VersionedTransaction {
-        signatures: vec![SolanaSignature::default()],
-        message: versioned_message,
-    };
-    
     // Serialize to bytes
     let serialized = bincode::serialize(&transaction)
         .map_err(|e| format!("Failed to serialize transaction: {}", e))?;
     
     println!("   ✓ Transaction built: {} bytes", serialized.len());
     
-    Ok(serialized)
+    Ok((serialized, approval_check))
 }
 
 #[cfg(test)]
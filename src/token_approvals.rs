@@ -0,0 +1,257 @@
+// src/token_approvals.rs
+//! Token delegate approvals inside swap routes
+//!
+//! Some routers need a delegate approval on the wallet's input token account
+//! to move funds. The approvals in a route are listed before signing, and a
+//! `Revoke` for each approved account can be appended after the swap so the
+//! delegate doesn't outlive the transaction, as long as the transaction still
+//! fits in a packet with the extra instructions.
+
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::Signature as SolanaSignature,
+    transaction::VersionedTransaction,
+};
+use spl_token::instruction::TokenInstruction;
+
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == spl_token::id() || program_id.to_string() == TOKEN_2022_PROGRAM_ID
+}
+
+/// One `Approve`/`ApproveChecked` instruction signed by the wallet
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenApproval {
+    pub token_program: Pubkey,
+    /// Token account the delegate may spend from
+    pub source: Pubkey,
+    pub delegate: Pubkey,
+    pub owner: Pubkey,
+    /// Base units
+    pub amount: u64,
+    /// Known for `ApproveChecked` only
+    pub mint: Option<Pubkey>,
+    pub decimals: Option<u8>,
+}
+
+impl TokenApproval {
+    pub fn is_unlimited(&self) -> bool {
+        self.amount == u64::MAX
+    }
+
+    /// e.g. "Delegate 9xQe... may spend 12.5 from token account 4k3D..."
+    pub fn describe(&self) -> String {
+        let amount = if self.is_unlimited() {
+            "an unlimited amount".to_string()
+        } else if let Some(decimals) = self.decimals {
+            format!("{}", self.amount as f64 / 10_f64.powi(decimals as i32))
+        } else {
+            format!("{} base units", self.amount)
+        };
+        let mint = self.mint.map(|m| format!(" ({})", m)).unwrap_or_default();
+        format!(
+            "Delegate {} may spend {} from token account {}{}",
+            self.delegate, amount, self.source, mint
+        )
+    }
+
+    pub fn revoke_instruction(&self) -> Result<Instruction, String> {
+        spl_token::instruction::revoke(&self.token_program, &self.source, &self.owner, &[])
+            .map_err(|e| format!("Failed to build revoke instruction: {}", e))
+    }
+}
+
+/// Delegate approvals granted by `owner` in a list of instructions
+pub fn find_approvals(instructions: &[Instruction], owner: &Pubkey) -> Vec<TokenApproval> {
+    instructions
+        .iter()
+        .filter(|ix| is_token_program(&ix.program_id))
+        .filter_map(|ix| {
+            let account = |position: usize| ix.accounts.get(position).map(|meta| meta.pubkey);
+            let approval = match TokenInstruction::unpack(&ix.data).ok()? {
+                TokenInstruction::Approve { amount } => TokenApproval {
+                    token_program: ix.program_id,
+                    source: account(0)?,
+                    delegate: account(1)?,
+                    owner: account(2)?,
+                    amount,
+                    mint: None,
+                    decimals: None,
+                },
+                TokenInstruction::ApproveChecked { amount, decimals } => TokenApproval {
+                    token_program: ix.program_id,
+                    source: account(0)?,
+                    mint: account(1),
+                    delegate: account(2)?,
+                    owner: account(3)?,
+                    amount,
+                    decimals: Some(decimals),
+                },
+                _ => return None,
+            };
+            (approval.owner == *owner).then_some(approval)
+        })
+        .collect()
+}
+
+/// What was found in a route and whether the approvals get revoked
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApprovalCheck {
+    pub approvals: Vec<TokenApproval>,
+    /// A revoke was appended for every approved account
+    pub revoke_appended: bool,
+    /// Revoking was requested but the transaction had no room for it
+    pub revoke_skipped_for_size: bool,
+}
+
+impl ApprovalCheck {
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.approvals.iter().map(|a| a.describe()).collect();
+        if self.revoke_appended {
+            lines.push("The approval is revoked again at the end of this transaction.".to_string());
+        } else if self.revoke_skipped_for_size {
+            lines.push("There is no room to revoke it in this transaction. Revoke it afterwards.".to_string());
+        }
+        lines
+    }
+}
+
+fn compile(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedTransaction, String> {
+    let message = v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)
+        .map_err(|e| format!("Failed to compile message: {}", e))?;
+    Ok(VersionedTransaction {
+        signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::V0(message),
+    })
+}
+
+fn fits_in_packet(transaction: &VersionedTransaction) -> bool {
+    bincode::serialized_size(transaction)
+        .map(|size| size as usize <= PACKET_DATA_SIZE)
+        .unwrap_or(false)
+}
+
+/// Compile a swap into a V0 transaction, appending a revoke after the swap
+/// for every approval the wallet grants when `revoke` is set and the result
+/// still fits in a packet
+pub fn compile_swap_transaction(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+    revoke: bool,
+) -> Result<(VersionedTransaction, ApprovalCheck), String> {
+    let mut check = ApprovalCheck {
+        approvals: find_approvals(instructions, payer),
+        ..Default::default()
+    };
+
+    if revoke && !check.approvals.is_empty() {
+        let mut with_revokes = instructions.to_vec();
+        let mut revoked = Vec::new();
+        for approval in &check.approvals {
+            if !revoked.contains(&approval.source) {
+                with_revokes.push(approval.revoke_instruction()?);
+                revoked.push(approval.source);
+            }
+        }
+        match compile(payer, &with_revokes, lookup_tables, recent_blockhash) {
+            Ok(transaction) if fits_in_packet(&transaction) => {
+                println!("🔒 Appended {} revoke instruction(s) after the swap", revoked.len());
+                check.revoke_appended = true;
+                return Ok((transaction, check));
+            }
+            _ => {
+                println!("⚠️ No room to revoke token approvals in this transaction");
+                check.revoke_skipped_for_size = true;
+            }
+        }
+    }
+
+    Ok((compile(payer, instructions, lookup_tables, recent_blockhash)?, check))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{instruction::AccountMeta, system_instruction};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_finds_only_the_wallets_approvals() {
+        let owner = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let instructions = vec![
+            system_instruction::transfer(&owner, &Pubkey::new_unique(), 1),
+            spl_token::instruction::approve(&spl_token::id(), &source, &delegate, &owner, &[], 500).unwrap(),
+            spl_token::instruction::approve_checked(
+                &Pubkey::from_str(TOKEN_2022_PROGRAM_ID).unwrap(), &source, &mint, &delegate, &owner, &[], u64::MAX, 6,
+            ).unwrap(),
+            spl_token::instruction::approve(&spl_token::id(), &source, &delegate, &Pubkey::new_unique(), &[], 1).unwrap(),
+        ];
+
+        let approvals = find_approvals(&instructions, &owner);
+        assert_eq!(approvals.len(), 2);
+        assert_eq!(approvals[0].amount, 500);
+        assert_eq!(approvals[0].delegate, delegate);
+        assert_eq!(approvals[1].mint, Some(mint));
+        assert_eq!(approvals[1].decimals, Some(6));
+        assert!(approvals[1].is_unlimited());
+    }
+
+    #[test]
+    fn test_revoke_is_appended_once_per_account() {
+        let owner = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let instructions = vec![
+            spl_token::instruction::approve(&spl_token::id(), &source, &Pubkey::new_unique(), &owner, &[], 10).unwrap(),
+            spl_token::instruction::approve(&spl_token::id(), &source, &Pubkey::new_unique(), &owner, &[], 20).unwrap(),
+        ];
+
+        let (transaction, check) = compile_swap_transaction(&owner, &instructions, &[], Hash::new_unique(), true).unwrap();
+        assert!(check.revoke_appended);
+        assert_eq!(transaction.message.instructions().len(), 3);
+
+        let (transaction, check) = compile_swap_transaction(&owner, &instructions, &[], Hash::new_unique(), false).unwrap();
+        assert!(!check.revoke_appended);
+        assert_eq!(check.approvals.len(), 2);
+        assert_eq!(transaction.message.instructions().len(), 2);
+    }
+
+    #[test]
+    fn test_revoke_skipped_when_too_large() {
+        let owner = Pubkey::new_unique();
+        let approve = spl_token::instruction::approve(
+            &spl_token::id(), &Pubkey::new_unique(), &Pubkey::new_unique(), &owner, &[], 10,
+        ).unwrap();
+        // Pad a route instruction until the transaction is exactly one packet
+        let mut padding = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![AccountMeta::new(owner, true)]);
+        loop {
+            let instructions = vec![approve.clone(), padding.clone()];
+            let size = bincode::serialized_size(&compile(&owner, &instructions, &[], Hash::default()).unwrap()).unwrap() as usize;
+            if size >= PACKET_DATA_SIZE {
+                break;
+            }
+            padding.data.push(0);
+        }
+        let instructions = vec![approve, padding];
+
+        let (transaction, check) = compile_swap_transaction(&owner, &instructions, &[], Hash::default(), true).unwrap();
+        assert!(check.revoke_skipped_for_size);
+        assert!(!check.revoke_appended);
+        assert_eq!(transaction.message.instructions().len(), 2);
+    }
+}
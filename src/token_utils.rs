@@ -1,10 +1,144 @@
 // src/token_utils.rs
 use crate::components::common::{Token, TokenDisplayData, TokenCategory, SortCriteria, TokenSortConfig, TokenFilter};
+use crate::prices::JupiterTokenInfo;
+use crate::transaction::{TransactionClient, TransferFee};
+use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
+use std::str::FromStr;
 
 /// Default icon for tokens without specific icons
 const ICON_32: &str = "https://cdn.jsdelivr.net/gh/hogyzen12/solana-mobile@main/assets/icons/32x32.png";
 
+/// A token found in Jupiter's token list, not held by the wallet
+pub fn token_from_jupiter_info(info: &JupiterTokenInfo) -> Token {
+    Token {
+        mint: info.id.clone(),
+        symbol: info.symbol.clone(),
+        name: info.name.clone(),
+        icon_type: info.icon.clone().unwrap_or_else(|| ICON_32.to_string()),
+        balance: 0.0,
+        value_usd: 0.0,
+        price: info.usd_price.unwrap_or(0.0),
+        price_change: 0.0,
+        price_change_1d: 0.0,
+        price_change_3d: 0.0,
+        price_change_7d: 0.0,
+        decimals: info.decimals,
+    }
+}
+
+/// A token known only by its mint, e.g. a pasted address Jupiter doesn't list
+pub fn token_from_mint(mint: &str, decimals: u8) -> Token {
+    let short: String = mint.chars().take(4).collect();
+    Token {
+        mint: mint.to_string(),
+        symbol: format!("{}…", short),
+        name: "Unknown token".to_string(),
+        icon_type: ICON_32.to_string(),
+        balance: 0.0,
+        value_usd: 0.0,
+        price: 0.0,
+        price_change: 0.0,
+        price_change_1d: 0.0,
+        price_change_3d: 0.0,
+        price_change_7d: 0.0,
+        decimals,
+    }
+}
+
+/// Rename `token` when another token in `tokens` already uses its symbol,
+/// so symbol lookups stay unambiguous
+pub fn with_unique_symbol(mut token: Token, tokens: &[Token]) -> Token {
+    if tokens.iter().any(|t| t.symbol == token.symbol && t.mint != token.mint) {
+        let short: String = token.mint.chars().take(4).collect();
+        token.symbol = format!("{} ({}…)", token.symbol, short);
+    }
+    token
+}
+
+/// Transfer fee a Token-2022 mint withholds in the current epoch, read from
+/// its transfer-fee extension. `None` for mints that don't charge one.
+pub async fn active_transfer_fee(client: &TransactionClient, mint: &str) -> Result<Option<TransferFee>, String> {
+    let mint = Pubkey::from_str(mint).map_err(|e| format!("Invalid mint {}: {}", mint, e))?;
+    let info = client.get_mint_info(&mint).await.map_err(|e| e.to_string())?;
+    let Some(config) = info.transfer_fee else {
+        return Ok(None);
+    };
+    let epoch = client.get_current_epoch().await.map_err(|e| e.to_string())?;
+    Ok(Some(*config.fee_for_epoch(epoch)).filter(|fee| fee.basis_points > 0))
+}
+
+/// Base units left of `amount` once `fee` is withheld
+pub fn amount_after_fee(fee: Option<&TransferFee>, amount: u64) -> u64 {
+    amount.saturating_sub(fee.map(|fee| fee.calculate_fee(amount)).unwrap_or(0))
+}
+
+/// Smallest amount to send so that `received` arrives after `fee`
+pub fn amount_before_fee(fee: Option<&TransferFee>, received: u64) -> u64 {
+    let Some(fee) = fee.filter(|fee| fee.basis_points > 0 && received > 0) else {
+        return received;
+    };
+    let capped = received.saturating_add(fee.maximum_fee);
+    let mut amount = if fee.basis_points >= 10_000 {
+        capped
+    } else {
+        let uncapped = (received as u128 * 10_000).div_ceil(10_000 - fee.basis_points as u128);
+        capped.min(uncapped.min(u64::MAX as u128) as u64)
+    };
+    // The fee rounds up, so the estimate can fall a unit or two short
+    while amount_after_fee(Some(fee), amount) < received && amount < capped {
+        amount += 1;
+    }
+    amount
+}
+
+/// A swap quote corrected for Token-2022 transfer fees on either side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeAdjustedQuote {
+    /// Output the provider quoted, in base units
+    pub quoted: u64,
+    /// Withheld from the amount sold before it reaches the route
+    pub input_fee: u64,
+    /// Withheld from the output on its way to the wallet
+    pub output_fee: u64,
+    /// Expected to arrive in the wallet
+    pub received: u64,
+    /// Arrives at worst, after slippage and the output fee
+    pub minimum_received: u64,
+}
+
+impl FeeAdjustedQuote {
+    pub fn has_fees(&self) -> bool {
+        self.input_fee > 0 || self.output_fee > 0
+    }
+}
+
+/// Correct a provider's quote of `quoted` for selling `input_amount`: the
+/// route only gets what's left after the input mint's fee, and the output
+/// mint's fee comes off what it delivers. `slippage_bps` bounds the minimum.
+pub fn fee_adjusted_quote(
+    input_amount: u64,
+    input_fee: Option<&TransferFee>,
+    quoted: u64,
+    output_fee: Option<&TransferFee>,
+    slippage_bps: u16,
+) -> FeeAdjustedQuote {
+    let input_fee_units = input_fee.map(|fee| fee.calculate_fee(input_amount)).unwrap_or(0);
+    let delivered = if input_amount == 0 {
+        quoted
+    } else {
+        (quoted as u128 * input_amount.saturating_sub(input_fee_units) as u128 / input_amount as u128) as u64
+    };
+    let worst = (delivered as u128 * 10_000u128.saturating_sub(slippage_bps as u128) / 10_000) as u64;
+    FeeAdjustedQuote {
+        quoted,
+        input_fee: input_fee_units,
+        output_fee: output_fee.map(|fee| fee.calculate_fee(delivered)).unwrap_or(0),
+        received: amount_after_fee(output_fee, delivered),
+        minimum_received: amount_after_fee(output_fee, worst),
+    }
+}
+
 /// Enhance token with display metadata
 pub fn enhance_token_data(token: Token, token_prices: &HashMap<String, f64>) -> TokenDisplayData {
     let has_price_data = token_prices.contains_key(&token.symbol) && token.price > 0.0;
@@ -142,4 +276,31 @@ pub fn process_tokens_for_display(
     
     // 3. Apply filtering
     filter_tokens(&enhanced_tokens, filter)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_fee_math() {
+        // 1% capped at 5 tokens (6 decimals)
+        let fee = TransferFee { epoch: 0, maximum_fee: 5_000_000, basis_points: 100 };
+        assert_eq!(amount_after_fee(Some(&fee), 1_000_000), 990_000);
+        assert_eq!(amount_after_fee(None, 1_000_000), 1_000_000);
+
+        for received in [1, 990_000, 999_999, 123_456_789, 2_000_000_000] {
+            let amount = amount_before_fee(Some(&fee), received);
+            assert!(amount_after_fee(Some(&fee), amount) >= received);
+            assert!(amount_after_fee(Some(&fee), amount - 1) < received);
+        }
+        // Past the cap only the maximum fee is added
+        assert_eq!(amount_before_fee(Some(&fee), 2_000_000_000), 2_005_000_000);
+
+        // Selling 100 units of a 1% token quoted at 1000 output units, 0.5% slippage
+        let quote = fee_adjusted_quote(100, Some(&fee), 1_000, None, 50);
+        assert_eq!((quote.input_fee, quote.received, quote.minimum_received), (1, 990, 985));
+        let quote = fee_adjusted_quote(100, None, 1_000, Some(&fee), 50);
+        assert_eq!((quote.output_fee, quote.received, quote.minimum_received), (10, 990, 985));
+        assert!(!fee_adjusted_quote(100, None, 1_000, None, 50).has_fees());
+    }
+}
@@ -1,14 +1,15 @@
 // src/transaction.rs
 use crate::wallet::Wallet;
 use crate::signing::{TransactionSigner, SignerType};
+use crate::signing::fee_payer::FeePayer;
 use crate::storage::get_current_jito_settings;
-use crate::components::modals::bulk_send_modal::SelectedTokenForBulkSend;
 use crate::timeout;
 use solana_sdk::{
     pubkey::Pubkey,
     hash::Hash,
     signature::Signature as SolanaSignature,
     system_instruction,
+    instruction::{AccountMeta, Instruction},
     message::{Message, VersionedMessage},
     transaction::VersionedTransaction,
 };
@@ -16,11 +17,11 @@ use bs58;
 use reqwest::Client;
 use std::error::Error;
 use std::str::FromStr;
+use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
-use spl_token::instruction as token_instruction;
 use spl_associated_token_account::{
-    get_associated_token_address,
-    instruction::create_associated_token_account,
+    get_associated_token_address_with_program_id,
+    instruction::{create_associated_token_account, create_associated_token_account_idempotent},
 };
 use std::collections::HashMap;
 
@@ -28,188 +29,17 @@ use std::collections::HashMap;
 const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
 const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
 
-// Add these constants for transaction size management
-const MAX_TRANSACTION_SIZE: usize = 1200; // Conservative limit (actual is ~1232)
-const ESTIMATED_INSTRUCTION_SIZE: usize = 150; // Estimated bytes per instruction
-const HEADER_OVERHEAD: usize = 200; // Transaction header and signature overhead
+/// Longest memo accepted on sends, leaving room for the transfer itself
+pub const MAX_MEMO_BYTES: usize = 256;
 
 /// Transaction client for sending transactions
 pub struct TransactionClient {
     client: Client,
     rpc_url: String,
-}
-
-/// Bulk transaction builder for atomic multi-token sends
-pub struct BulkTransactionBuilder {
-    /// The sender's public key
-    from_pubkey: Pubkey,
-    /// The recipient's public key  
-    to_pubkey: Pubkey,
-    /// List of SOL transfer amounts (in SOL, not lamports)
-    sol_transfers: Vec<f64>,
-    /// List of SPL token transfers (mint, amount, decimals)
-    spl_transfers: Vec<(String, f64, u8)>,
-    /// Instructions to be included in the transaction
-    instructions: Vec<solana_sdk::instruction::Instruction>,
-    /// Track required account creations
-    required_ata_creations: Vec<Pubkey>,
-}
-
-impl BulkTransactionBuilder {
-    /// Create a new bulk transaction builder
-    pub fn new(from_pubkey: Pubkey, to_pubkey: Pubkey) -> Self {
-        Self {
-            from_pubkey,
-            to_pubkey,
-            sol_transfers: Vec::new(),
-            spl_transfers: Vec::new(),
-            instructions: Vec::new(),
-            required_ata_creations: Vec::new(),
-        }
-    }
-
-    /// Add a SOL transfer to the bulk transaction
-    pub fn add_sol_transfer(&mut self, amount_sol: f64) -> Result<(), Box<dyn Error>> {
-        if amount_sol <= 0.0 {
-            return Err("SOL amount must be positive".into());
-        }
-        self.sol_transfers.push(amount_sol);
-        Ok(())
-    }
-
-    /// Add an SPL token transfer to the bulk transaction
-    pub fn add_spl_transfer(
-        &mut self, 
-        mint: &str, 
-        amount: f64
-    ) -> Result<(), Box<dyn Error>> {
-        if amount <= 0.0 {
-            return Err("Token amount must be positive".into());
-        }
-        // Store mint and amount, decimals will be fetched during build
-        self.spl_transfers.push((mint.to_string(), amount, 0)); // 0 as placeholder
-        Ok(())
-    }
-
-    /// Check if the current instruction set would fit in a single transaction
-    fn estimate_transaction_size(&self) -> usize {
-        let instruction_count = self.sol_transfers.len() + 
-                              self.spl_transfers.len() + 
-                              self.required_ata_creations.len();
-        
-        HEADER_OVERHEAD + (instruction_count * ESTIMATED_INSTRUCTION_SIZE)
-    }
-
-    /// Build the transaction instructions (async to check account existence)
-    pub async fn build_instructions(
-        &mut self,
-        client: &TransactionClient,
-    ) -> Result<Vec<solana_sdk::instruction::Instruction>, Box<dyn Error>> {
-        let mut instructions = Vec::new();
-
-        // First, check which ATA accounts need to be created
-        for (mint_str, _, _) in &self.spl_transfers {
-            let mint_pubkey = Pubkey::from_str(mint_str)?;
-            let to_token_account = get_associated_token_address(&self.to_pubkey, &mint_pubkey);
-            
-            if !client.account_exists(&to_token_account).await? {
-                println!("Will create ATA for mint {} -> {}", mint_str, to_token_account);
-                self.required_ata_creations.push(mint_pubkey);
-                
-                // Detect which token program this mint uses
-                let token_program_id = client.get_mint_program_id(&mint_pubkey).await
-                    .unwrap_or_else(|_| spl_token::id()); // Fallback to standard Token program
-                
-                let create_ata_instruction = create_associated_token_account(
-                    &self.from_pubkey, // Payer
-                    &self.to_pubkey,   // Owner
-                    &mint_pubkey,      // Token mint
-                    &token_program_id, // Token program ID (Token or Token-2022)
-                );
-                instructions.push(create_ata_instruction);
-            }
-        }
-
-        // Add SOL transfer instructions
-        for &amount_sol in &self.sol_transfers {
-            let amount_lamports = (amount_sol * 1_000_000_000.0) as u64;
-            let transfer_instruction = system_instruction::transfer(
-                &self.from_pubkey,
-                &self.to_pubkey,
-                amount_lamports,
-            );
-            instructions.push(transfer_instruction);
-        }
-
-        // Add SPL token transfer instructions
-        for (mint_str, amount, _) in &self.spl_transfers {
-            let mint_pubkey = Pubkey::from_str(mint_str)?;
-            
-            // Fetch decimals dynamically like single token send does
-            let decimals = client.get_token_decimals(&mint_pubkey).await.unwrap_or(6);
-            let amount_units = (*amount * 10_f64.powi(decimals as i32)) as u64;
-            
-            let from_token_account = get_associated_token_address(&self.from_pubkey, &mint_pubkey);
-            let to_token_account = get_associated_token_address(&self.to_pubkey, &mint_pubkey);
-            
-            let transfer_instruction = token_instruction::transfer(
-                &spl_token::id(),
-                &from_token_account,
-                &to_token_account,
-                &self.from_pubkey,
-                &[&self.from_pubkey],
-                amount_units,
-            )?;
-            instructions.push(transfer_instruction);
-        }
-
-        self.instructions = instructions.clone();
-        Ok(instructions)
-    }
-
-    /// Get the estimated number of transactions needed
-    pub fn get_estimated_transaction_count(&self) -> usize {
-        let estimated_size = self.estimate_transaction_size();
-        if estimated_size <= MAX_TRANSACTION_SIZE {
-            1
-        } else {
-            // Rough estimate - could be more sophisticated
-            (estimated_size + MAX_TRANSACTION_SIZE - 1) / MAX_TRANSACTION_SIZE
-        }
-    }
-
-    /// Split instructions into multiple transactions if needed
-    pub fn split_for_transaction_limits(&self) -> Vec<Vec<solana_sdk::instruction::Instruction>> {
-        if self.estimate_transaction_size() <= MAX_TRANSACTION_SIZE {
-            return vec![self.instructions.clone()];
-        }
-
-        // For now, implement a simple split strategy
-        // In a more sophisticated implementation, you'd optimize the splits
-        let mut transactions = Vec::new();
-        let mut current_transaction = Vec::new();
-        let mut current_size = HEADER_OVERHEAD;
-
-        for instruction in &self.instructions {
-            if current_size + ESTIMATED_INSTRUCTION_SIZE > MAX_TRANSACTION_SIZE {
-                // Start a new transaction
-                if !current_transaction.is_empty() {
-                    transactions.push(current_transaction);
-                    current_transaction = Vec::new();
-                    current_size = HEADER_OVERHEAD;
-                }
-            }
-            
-            current_transaction.push(instruction.clone());
-            current_size += ESTIMATED_INSTRUCTION_SIZE;
-        }
-
-        if !current_transaction.is_empty() {
-            transactions.push(current_transaction);
-        }
-
-        transactions
-    }
+    /// Jito tip for transactions built by this client, see `with_jito_tip`
+    jito_tip_lamports: Option<u64>,
+    /// Memo attached to transfers built by this client, see `with_memo`
+    memo: Option<String>,
 }
 
 impl TransactionClient {
@@ -219,157 +49,49 @@ impl TransactionClient {
         Self {
             client: Client::new(),
             rpc_url: url,
+            jito_tip_lamports: None,
+            memo: None,
         }
     }
 
-    /// Send bulk transaction with multiple tokens/SOL
-    pub async fn send_bulk_tokens_with_signer(
-        &self,
-        signer: &dyn TransactionSigner,
-        to_address: &str,
-        selected_tokens: Vec<SelectedTokenForBulkSend>,
-    ) -> Result<String, Box<dyn Error>> {
-        // Validate recipient address early
-        let to_pubkey = Pubkey::from_str(to_address)?;
-        let from_pubkey_str = signer.get_public_key().await?;
-        let from_pubkey = Pubkey::from_str(&from_pubkey_str)?;
-
-        if selected_tokens.is_empty() {
-            return Err("No tokens selected for bulk send".into());
-        }
-
-        println!("Bulk sending {} tokens to {}", selected_tokens.len(), to_address);
-
-        // Create bulk transaction builder
-        let mut builder = BulkTransactionBuilder::new(from_pubkey, to_pubkey);
-
-        // Add all transfers to the builder
-        for selected_token in &selected_tokens {
-            let token = &selected_token.token;
-            
-            // Check if this is SOL (special case)
-            if token.mint == "So11111111111111111111111111111111111111112" || 
-               token.symbol.to_uppercase() == "SOL" {
-                builder.add_sol_transfer(selected_token.amount)?;
-                println!("Added SOL transfer: {} SOL", selected_token.amount);
-            } else {
-                // Use existing pattern - let transaction client fetch decimals
-                builder.add_spl_transfer(&token.mint, selected_token.amount)?;
-                println!("Added SPL transfer: {} {} (mint: {})", 
-                    selected_token.amount, token.symbol, token.mint);
-            }
-        }
-
-        // Build the instructions (this will check for ATA creation needs)
-        let instructions = builder.build_instructions(self).await?;
-        
-        println!("Built {} instructions for bulk transaction", instructions.len());
-
-        // Check if we need to split into multiple transactions
-        let transaction_batches = builder.split_for_transaction_limits();
-        
-        if transaction_batches.len() > 1 {
-            println!("Transaction too large, splitting into {} batches", transaction_batches.len());
-            // For now, return an error - you could implement batch sending
-            return Err("Transaction too large for single batch. Multi-batch sending not yet implemented.".into());
-        }
-
-        // Send as single transaction
-        self.send_bulk_transaction_single(signer, instructions).await
+    /// Tip this much instead of the fixed tip when JitoTx is enabled
+    pub fn with_jito_tip(mut self, lamports: u64) -> Self {
+        self.jito_tip_lamports = Some(lamports);
+        self
     }
 
-    /// Send a single bulk transaction with all instructions
-    async fn send_bulk_transaction_single(
-        &self,
-        signer: &dyn TransactionSigner,
-        mut instructions: Vec<solana_sdk::instruction::Instruction>,
-    ) -> Result<String, Box<dyn Error>> {
-        // Get current slot and build timeout instruction (FIRST)
-        let current_slot = self.get_current_slot().await?;
-        let timeout_ix = timeout::build_timeout_instruction_from_current(
-            current_slot,
-            timeout::DEFAULT_SLOT_WINDOW,
-        )?;
-        println!("Added timeout protection: current_slot={}, max_slot={}", 
-            current_slot, current_slot + timeout::DEFAULT_SLOT_WINDOW);
-        
-        // Prepend timeout instruction
-        instructions.insert(0, timeout_ix);
-        
-        // Check Jito settings and apply modifications if needed
-        let jito_settings = get_current_jito_settings();
-        let from_pubkey_str = signer.get_public_key().await?;
-        let from_pubkey = Pubkey::from_str(&from_pubkey_str)?;
-
-        if jito_settings.jito_tx {
-            println!("JitoTx is enabled, applying Jito modifications to bulk transaction");
-            self.apply_jito_modifications(&from_pubkey, &mut instructions)?;
-        }
-
-        // Get recent blockhash
-        let recent_blockhash = self.get_recent_blockhash().await?;
-        println!("Using blockhash: {}", recent_blockhash);
-
-        // Create a message with all instructions
-        let mut message = Message::new(&instructions, Some(&from_pubkey));
-        message.recent_blockhash = recent_blockhash;
-
-        // Create a VersionedTransaction with empty signatures
-        let mut transaction = VersionedTransaction {
-            signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
-            message: VersionedMessage::Legacy(message),
-        };
-
-        println!("Number of signatures expected: {}", transaction.message.header().num_required_signatures);
-
-        // Serialize the transaction message for signing
-        let message_bytes = transaction.message.serialize();
-
-        // Sign the message with our signer
-        let signature_bytes = signer.sign_message(&message_bytes).await?;
-
-        // Convert to solana signature (expect exactly 64 bytes)
-        if signature_bytes.len() != 64 {
-            return Err(format!("Invalid signature length: expected 64, got {}", signature_bytes.len()).into());
-        }
-
-        let mut sig_array = [0u8; 64];
-        sig_array.copy_from_slice(&signature_bytes);
-        let solana_signature = SolanaSignature::from(sig_array);
-
-        // Assign the signature to the transaction
-        if transaction.signatures.len() != 1 {
-            return Err(format!("Expected 1 signature slot, found {}", transaction.signatures.len()).into());
-        }
-        transaction.signatures[0] = solana_signature;
-
-        // Serialize the entire transaction with signature
-        let serialized_transaction = bincode::serialize(&transaction)?;
-        let encoded_transaction = bs58::encode(serialized_transaction).into_string();
-
-        println!("Serialized bulk transaction: {} bytes", encoded_transaction.len());
+    /// Attach an SPL Memo to the transfers this client builds. Blank memos
+    /// are ignored.
+    pub fn with_memo(mut self, memo: &str) -> Self {
+        let memo = memo.trim();
+        self.memo = (!memo.is_empty()).then(|| memo.to_string());
+        self
+    }
 
-        // Send the transaction
-        self.send_transaction(&encoded_transaction).await
+    /// POST a JSON-RPC request, failing over to the fallback endpoints
+    async fn rpc_call(&self, request: &Value) -> Result<Value, reqwest::Error> {
+        crate::rpc_pool::post_json(&self.client, &self.rpc_url, request).await
     }
 
-    /// Get token decimals for multiple mints (batch operation)
+    /// Get token decimals for multiple mints in one batched lookup
     pub async fn get_token_decimals_batch(&self, mints: &[String]) -> HashMap<String, u8> {
-        let mut decimals_map = HashMap::new();
-        
-        // For now, fetch individually - could be optimized with batch requests
-        for mint_str in mints {
-            if let Ok(mint_pubkey) = Pubkey::from_str(mint_str) {
-                if let Ok(decimals) = self.get_token_decimals(&mint_pubkey).await {
-                    decimals_map.insert(mint_str.clone(), decimals);
-                } else {
-                    // Default to 6 decimals if we can't fetch
-                    decimals_map.insert(mint_str.clone(), 6);
-                }
-            }
-        }
-        
-        decimals_map
+        let accounts = crate::rpc::get_multiple_accounts(mints, Some(&self.rpc_url))
+            .await
+            .unwrap_or_else(|e| {
+                println!("⚠️ Failed to load mints: {}", e);
+                vec![None; mints.len()]
+            });
+
+        mints
+            .iter()
+            .zip(accounts)
+            .map(|(mint, account)| {
+                // Mint layout: decimals is the byte after authority, supply
+                let decimals = account.and_then(|a| a.data.get(MINT_DECIMALS_OFFSET).copied());
+                // Default to 6 decimals if we can't fetch
+                (mint.clone(), decimals.unwrap_or(6))
+            })
+            .collect()
     }
     
     /// Get recent blockhash from the network
@@ -380,18 +102,12 @@ impl TransactionClient {
             "method": "getLatestBlockhash",
             "params": [
                 {
-                    "commitment": "finalized"
+                    "commitment": crate::config::network::active().commitment()
                 }
             ]
         });
 
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = self.rpc_call(&request).await?;
         
         println!("Blockhash response: {:?}", json);
         
@@ -420,13 +136,7 @@ impl TransactionClient {
             ]
         });
 
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = self.rpc_call(&request).await?;
         
         if let Some(error) = json.get("error") {
             return Err(format!("RPC error getting slot: {:?}", error).into());
@@ -457,7 +167,7 @@ impl TransactionClient {
                     {
                         "encoding": "base58", // We're still using base58 as that's what our code produces
                         "skipPreflight": true, // Jito requires skipPreflight=true
-                        "preflightCommitment": "finalized"
+                        "preflightCommitment": crate::config::network::active().commitment()
                     }
                 ]
             })
@@ -472,25 +182,25 @@ impl TransactionClient {
                     {
                         "encoding": "base58",
                         "skipPreflight": false,
-                        "preflightCommitment": "finalized"
+                        "preflightCommitment": crate::config::network::active().commitment()
                     }
                 ]
             })
         };
 
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
+        let json = self.rpc_call(&request).await?;
         
         println!("Send transaction response: {:?}", json);
         
         if let Some(error) = json.get("error") {
             Err(format!("Transaction error: {:?}", error).into())
         } else if let Some(result) = json["result"].as_str() {
+            // Track it so a stuck transaction can be re-sent with a higher fee
+            if let Some(tx) = bs58::decode(signed_tx).into_vec().ok()
+                .and_then(|bytes| bincode::deserialize::<VersionedTransaction>(&bytes).ok())
+            {
+                crate::submission::track(result, tx);
+            }
             Ok(result.to_string())
         } else {
             Err(format!("Unknown error sending transaction: {:?}", json).into())
@@ -552,12 +262,16 @@ impl TransactionClient {
         // Build instructions with timeout FIRST
         let mut instructions = vec![timeout_ix, transfer_instruction];
         
+        self.apply_memo(&from_pubkey, &mut instructions)?;
+
         // Apply Jito modifications if JitoTx is enabled
         if jito_settings.jito_tx {
             println!("JitoTx is enabled, applying Jito modifications");
             self.apply_jito_modifications(&from_pubkey, &mut instructions)?;
         }
         
+        self.size_compute_budget(&mut instructions, &from_pubkey).await;
+
         // Create a message with all instructions
         let mut message = Message::new(&instructions, Some(&from_pubkey));
         message.recent_blockhash = recent_blockhash;
@@ -641,65 +355,31 @@ impl TransactionClient {
         println!("Added timeout protection: current_slot={}, max_slot={}", 
             current_slot, current_slot + timeout::DEFAULT_SLOT_WINDOW);
         
-        // Get token info to determine decimals
-        let token_decimals = self.get_token_decimals(&mint_pubkey).await
-            .unwrap_or(6); // Default to 6 decimals if we can't fetch
-            
-        // Convert amount to token units (accounting for decimals)
-        let amount_units = (amount * 10_f64.powi(token_decimals as i32)) as u64;
-        
-        println!("Token amount in units: {} (decimals: {})", amount_units, token_decimals);
-        
-        // Get associated token accounts
-        let from_token_account = get_associated_token_address(&from_pubkey, &mint_pubkey);
-        let to_token_account = get_associated_token_address(&to_pubkey, &mint_pubkey);
-        
-        println!("From token account: {}", from_token_account);
-        println!("To token account: {}", to_token_account);
-        
         // Get recent blockhash
         let recent_blockhash = self.get_recent_blockhash().await?;
         println!("Using blockhash: {}", recent_blockhash);
         
-        // Build instructions starting with timeout
+        // Build instructions starting with timeout, then the transfer for the mint's token program
         let mut instructions = vec![timeout_ix];
+        let (transfer_instructions, _) = self.build_token_transfer_instructions(
+            &from_pubkey,
+            &from_pubkey,
+            &to_pubkey,
+            &mint_pubkey,
+            amount,
+        ).await?;
+        instructions.extend(transfer_instructions);
         
-        if !self.account_exists(&to_token_account).await? {
-            println!("Creating destination token account: {}", to_token_account);
-            
-            // Detect which token program this mint uses
-            let token_program_id = self.get_mint_program_id(&mint_pubkey).await
-                .unwrap_or_else(|_| spl_token::id()); // Fallback to standard Token program
-            
-            // Create associated token account for recipient
-            let create_ata_instruction = create_associated_token_account(
-                &from_pubkey, // Payer (sender pays for account creation)
-                &to_pubkey,   // Owner of the new account
-                &mint_pubkey, // Token mint
-                &token_program_id, // Token program ID (Token or Token-2022)
-            );
-            
-            instructions.push(create_ata_instruction);
-        }
-        
-        // Create the token transfer instruction
-        let transfer_instruction = token_instruction::transfer(
-            &spl_token::id(),                    // Token program ID
-            &from_token_account,                 // Source token account
-            &to_token_account,                   // Destination token account  
-            &from_pubkey,                        // Authority (owner of source account)
-            &[&from_pubkey],                     // Signers
-            amount_units,                        // Amount in token units
-        )?;
-        
-        instructions.push(transfer_instruction);
-        
+        self.apply_memo(&from_pubkey, &mut instructions)?;
+
         // Apply Jito modifications if JitoTx is enabled
         if jito_settings.jito_tx {
             println!("JitoTx is enabled, applying Jito modifications");
             self.apply_jito_modifications(&from_pubkey, &mut instructions)?;
         }
         
+        self.size_compute_budget(&mut instructions, &from_pubkey).await;
+
         // Create a message with all instructions
         let mut message = Message::new(&instructions, Some(&from_pubkey));
         message.recent_blockhash = recent_blockhash;
@@ -743,49 +423,137 @@ impl TransactionClient {
         self.send_transaction(&encoded_transaction).await
     }
 
-    /// Detect which token program owns a mint account (Token or Token-2022)
-    async fn get_mint_program_id(&self, mint_pubkey: &Pubkey) -> Result<Pubkey, Box<dyn Error>> {
-        let request = json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getAccountInfo",
-            "params": [
-                mint_pubkey.to_string(),
-                {
-                    "encoding": "base64"
-                }
-            ]
-        });
+    /// Build, sign and send a transaction where `fee_payer` pays fees instead of the owner.
+    ///
+    /// The timeout instruction is prepended and Jito tips (if enabled) are paid by the
+    /// fee payer, so an owner with zero SOL can still move tokens.
+    pub async fn sign_and_send_with_fee_payer(
+        &self,
+        owner: &dyn TransactionSigner,
+        fee_payer: &FeePayer,
+        mut instructions: Vec<solana_sdk::instruction::Instruction>,
+    ) -> Result<String, Box<dyn Error>> {
+        let owner_pubkey = Pubkey::from_str(&owner.get_public_key().await?)?;
+        let payer_pubkey = fee_payer.pubkey(&owner_pubkey).await?;
+
+        println!("Fee payer: {} (owner: {})", payer_pubkey, owner_pubkey);
+
+        // Get current slot and build timeout instruction (FIRST)
+        let current_slot = self.get_current_slot().await?;
+        let timeout_ix = timeout::build_timeout_instruction_from_current(
+            current_slot,
+            timeout::DEFAULT_SLOT_WINDOW,
+        )?;
+        instructions.insert(0, timeout_ix);
+        self.apply_memo(&owner_pubkey, &mut instructions)?;
+
+        let jito_settings = get_current_jito_settings();
+        if jito_settings.jito_tx {
+            println!("JitoTx is enabled, tips paid by fee payer");
+            self.apply_jito_modifications(&payer_pubkey, &mut instructions)?;
+        }
+
+        let recent_blockhash = self.get_recent_blockhash().await?;
+
+        self.size_compute_budget(&mut instructions, &payer_pubkey).await;
+
+        // The fee payer is always the first account key
+        let mut message = Message::new(&instructions, Some(&payer_pubkey));
+        message.recent_blockhash = recent_blockhash;
+
+        let mut transaction = VersionedTransaction {
+            signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
+            message: VersionedMessage::Legacy(message),
+        };
+
+        println!("Number of signatures expected: {}", transaction.message.header().num_required_signatures);
+
+        fee_payer.sign(&mut transaction, owner).await?;
 
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json: Value = response.json().await?;
-        
-        if let Some(owner_str) = json["result"]["value"]["owner"].as_str() {
-            let owner = Pubkey::from_str(owner_str)?;
-            
-            // Check if it's Token-2022 program
-            let token_2022_id = Pubkey::from_str(TOKEN_2022_PROGRAM_ID)?;
-            if owner == token_2022_id {
-                println!("Mint {} uses Token-2022 program", mint_pubkey);
-                Ok(token_2022_id)
-            } else {
-                // Default to standard Token program
-                println!("Mint {} uses standard Token program", mint_pubkey);
-                Ok(spl_token::id())
+        let serialized_transaction = bincode::serialize(&transaction)?;
+        let encoded_transaction = bs58::encode(serialized_transaction).into_string();
+
+        self.send_transaction(&encoded_transaction).await
+    }
+
+    /// Send SOL with fees paid by `fee_payer`, or by the owner through
+    /// `send_sol_with_signer` when it is `FeePayer::Owner`
+    pub async fn send_sol_with_fee_payer(
+        &self,
+        owner: &dyn TransactionSigner,
+        fee_payer: &FeePayer,
+        to_address: &str,
+        amount_sol: f64,
+    ) -> Result<String, Box<dyn Error>> {
+        if !fee_payer.is_separate() {
+            return self.send_sol_with_signer(owner, to_address, amount_sol).await;
+        }
+
+        let from_pubkey = Pubkey::from_str(&owner.get_public_key().await?)?;
+        let to_pubkey = Pubkey::from_str(to_address)?;
+        let amount_lamports = (amount_sol * 1_000_000_000.0) as u64;
+
+        let transfer_instruction = system_instruction::transfer(&from_pubkey, &to_pubkey, amount_lamports);
+
+        self.sign_and_send_with_fee_payer(owner, fee_payer, vec![transfer_instruction]).await
+    }
+
+    /// Send SPL tokens with fees and recipient ATA rent paid by `fee_payer`,
+    /// or by the owner through `send_spl_token_with_signer`
+    pub async fn send_spl_token_with_fee_payer(
+        &self,
+        owner: &dyn TransactionSigner,
+        fee_payer: &FeePayer,
+        to_address: &str,
+        amount: f64,
+        token_mint: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        if !fee_payer.is_separate() {
+            return self.send_spl_token_with_signer(owner, to_address, amount, token_mint).await;
+        }
+
+        let from_pubkey = Pubkey::from_str(&owner.get_public_key().await?)?;
+        let to_pubkey = Pubkey::from_str(to_address)?;
+        let mint_pubkey = Pubkey::from_str(token_mint)?;
+        let payer_pubkey = fee_payer.pubkey(&from_pubkey).await?;
+
+        // Fee payer covers the rent for the recipient's token account
+        let (instructions, _) = self.build_token_transfer_instructions(
+            &payer_pubkey,
+            &from_pubkey,
+            &to_pubkey,
+            &mint_pubkey,
+            amount,
+        ).await?;
+
+        self.sign_and_send_with_fee_payer(owner, fee_payer, instructions).await
+    }
+
+    /// Mint info for several mints, fetched with `getMultipleAccounts`
+    pub async fn get_mint_infos(&self, mints: &[Pubkey]) -> Result<HashMap<Pubkey, MintInfo>, Box<dyn Error>> {
+        let mut infos = HashMap::new();
+        for chunk in mints.chunks(100) {
+            let addresses: Vec<String> = chunk.iter().map(|m| m.to_string()).collect();
+            let request = json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "getMultipleAccounts",
+                "params": [addresses, { "encoding": "jsonParsed" }]
+            });
+            let json = self.rpc_call(&request).await?;
+            let values = json["result"]["value"]
+                .as_array()
+                .ok_or_else(|| format!("Failed to load mints: {:?}", json))?;
+            for (mint, value) in chunk.iter().zip(values) {
+                let info = parse_mint_info(value).map_err(|e| format!("Failed to load mint {}: {}", mint, e))?;
+                infos.insert(*mint, info);
             }
-        } else {
-            // Default to standard Token program if we can't determine
-            Ok(spl_token::id())
         }
+        Ok(infos)
     }
 
-    /// Get token decimals for a given mint
-    async fn get_token_decimals(&self, mint_pubkey: &Pubkey) -> Result<u8, Box<dyn Error>> {
+    /// Fetch a mint's token program, decimals and Token-2022 transfer fee settings
+    pub async fn get_mint_info(&self, mint_pubkey: &Pubkey) -> Result<MintInfo, Box<dyn Error>> {
         let request = json!({
             "jsonrpc": "2.0",
             "id": 1,
@@ -798,101 +566,2400 @@ impl TransactionClient {
             ]
         });
 
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
+        let json = self.rpc_call(&request).await?;
+        let mint_info = parse_mint_info(&json["result"]["value"])
+            .map_err(|e| format!("Failed to load mint {}: {}", mint_pubkey, e))?;
 
-        let json: Value = response.json().await?;
-        
-        if let Some(account_data) = json["result"]["value"]["data"]["parsed"]["info"]["decimals"].as_u64() {
-            Ok(account_data as u8)
-        } else {
-            Err("Failed to get token decimals".into())
+        if mint_info.is_token_2022() {
+            println!("Mint {} uses Token-2022 program (transfer fee: {})",
+                mint_pubkey, mint_info.transfer_fee.is_some());
         }
+        Ok(mint_info)
     }
 
-    /// Check if an account exists
-    async fn account_exists(&self, account_pubkey: &Pubkey) -> Result<bool, Box<dyn Error>> {
+    /// Current epoch, used to pick the active Token-2022 transfer fee
+    pub async fn get_current_epoch(&self) -> Result<u64, Box<dyn Error>> {
         let request = json!({
             "jsonrpc": "2.0",
             "id": 1,
-            "method": "getAccountInfo",
-            "params": [
-                account_pubkey.to_string(),
-                {
-                    "encoding": "base64"
-                }
-            ]
+            "method": "getEpochInfo",
+            "params": []
         });
 
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
+        let json = self.rpc_call(&request).await?;
+        json["result"]["epoch"]
+            .as_u64()
+            .ok_or_else(|| format!("Failed to get epoch info: {:?}", json).into())
+    }
 
-        let json: Value = response.json().await?;
-        
-        // Account exists if the result value is not null
-        Ok(!json["result"]["value"].is_null())
+    /// Work out what the recipient of a token transfer actually receives
+    pub async fn quote_token_transfer(
+        &self,
+        token_mint: &str,
+        amount: f64,
+    ) -> Result<TokenTransferQuote, Box<dyn Error>> {
+        let mint_info = self.get_mint_info(&Pubkey::from_str(token_mint)?).await?;
+        let amount_units = mint_info.to_units(amount);
+        self.quote_token_transfer_units(&mint_info, amount_units).await
     }
 
-    /// Confirm transaction status
-    pub async fn confirm_transaction(&self, signature: &str) -> Result<bool, Box<dyn Error>> {
-        let request = json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getSignatureStatuses",
-            "params": [[signature]]
-        });
+    /// Same as `quote_token_transfer` for an amount already in base units
+    pub async fn quote_token_transfer_units(
+        &self,
+        mint_info: &MintInfo,
+        amount_units: u64,
+    ) -> Result<TokenTransferQuote, Box<dyn Error>> {
+        let fee_units = match &mint_info.transfer_fee {
+            Some(config) => config.fee_for_epoch(self.get_current_epoch().await?).calculate_fee(amount_units),
+            None => 0,
+        };
+        Ok(TokenTransferQuote {
+            amount_units,
+            fee_units,
+            decimals: mint_info.decimals,
+        })
+    }
 
-        let response = self.client
-            .post(&self.rpc_url)
-            .json(&request)
-            .send()
-            .await?;
+    /// Instructions for an SPL transfer of `amount` (UI units), branching on the mint's
+    /// token program. `payer` funds the recipient token account if it has to be created.
+    async fn build_token_transfer_instructions(
+        &self,
+        payer: &Pubkey,
+        from_pubkey: &Pubkey,
+        to_pubkey: &Pubkey,
+        mint_pubkey: &Pubkey,
+        amount: f64,
+    ) -> Result<(Vec<Instruction>, MintInfo), Box<dyn Error>> {
+        let mint_info = self.get_mint_info(mint_pubkey).await?;
+        let amount_units = mint_info.to_units(amount);
+        println!("Token amount in units: {} (decimals: {})", amount_units, mint_info.decimals);
 
-        let json: Value = response.json().await?;
-        
-        if let Some(result) = json["result"]["value"][0]["confirmationStatus"].as_str() {
+        let from_token_account = mint_info.associated_token_address(from_pubkey, mint_pubkey);
+        let to_token_account = mint_info.associated_token_address(to_pubkey, mint_pubkey);
+        println!("From token account: {}", from_token_account);
+        println!("To token account: {}", to_token_account);
+
+        let mut instructions = Vec::new();
+        if !self.existing_accounts(&[to_token_account]).await?.contains(&to_token_account) {
+            println!("Creating destination token account: {}", to_token_account);
+            instructions.push(create_associated_token_account(
+                payer,
+                to_pubkey,
+                mint_pubkey,
+                &mint_info.program_id,
+            ));
+        }
+
+        instructions.push(mint_info.transfer_checked(
+            mint_pubkey,
+            &from_token_account,
+            &to_token_account,
+            from_pubkey,
+            amount_units,
+        ));
+        Ok((instructions, mint_info))
+    }
+
+    /// Which of `accounts` exist, in one batched lookup
+    async fn existing_accounts(&self, accounts: &[Pubkey]) -> Result<std::collections::HashSet<Pubkey>, Box<dyn Error>> {
+        let addresses: Vec<String> = accounts.iter().map(|a| a.to_string()).collect();
+        let found = crate::rpc::get_multiple_accounts(&addresses, Some(&self.rpc_url)).await?;
+        Ok(accounts
+            .iter()
+            .zip(found)
+            .filter_map(|(account, data)| data.map(|_| *account))
+            .collect())
+    }
+
+    /// Confirm transaction status
+    pub async fn confirm_transaction(&self, signature: &str) -> Result<bool, Box<dyn Error>> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getSignatureStatuses",
+            "params": [[signature]]
+        });
+
+        let json = self.rpc_call(&request).await?;
+        
+        if let Some(result) = json["result"]["value"][0]["confirmationStatus"].as_str() {
             Ok(result == "finalized" || result == "confirmed")
         } else {
             Ok(false)
         }
     }
 
+    /// Add the memo set with `with_memo`, signed by `signer`
+    fn apply_memo(
+        &self,
+        signer: &Pubkey,
+        instructions: &mut Vec<solana_sdk::instruction::Instruction>,
+    ) -> Result<(), Box<dyn Error>> {
+        let Some(memo) = &self.memo else { return Ok(()) };
+        if memo.len() > MAX_MEMO_BYTES {
+            return Err(format!("Memo is too long ({} of at most {} bytes)", memo.len(), MAX_MEMO_BYTES).into());
+        }
+        instructions.push(crate::composer::memo_instruction(signer, memo));
+        Ok(())
+    }
+
     //Jito tx options
     fn apply_jito_modifications(
         &self,
         from_pubkey: &Pubkey,
         instructions: &mut Vec<solana_sdk::instruction::Instruction>,
     ) -> Result<(), Box<dyn Error>> {
-        // First Jito address (as per JS example)
-        let jito_address1 = Pubkey::from_str("juLesoSmdTcRtzjCzYzRoHrnF8GhVu6KCV7uxq7nJGp")?;
-        
-        // Second Jito address (as per JS example)
-        let jito_address2 = Pubkey::from_str("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL")?;
+        let jito_tip = self.jito_tip_lamports.unwrap_or(crate::tips::DEFAULT_JITO_TIP_LAMPORTS);
 
-        // Add two transfer instructions as tips to Jito
-        let tip_instruction1 = system_instruction::transfer(
-            from_pubkey,
-            &jito_address1,
-            100_000, // 0.0001 SOL in lamports
-        );
+        // App tip and Jito tip
+        instructions.extend(crate::tips::tip_instructions(from_pubkey, Some(jito_tip))?);
 
-        let tip_instruction2 = system_instruction::transfer(
-            from_pubkey,
-            &jito_address2,
-            100_000, // 0.0001 SOL in lamports
-        );
+        println!("Added Jito tip instructions to transaction (Jito tip {} lamports)", jito_tip);
+        Ok(())
+    }
+}
+// ══════════════════════════════════════════════════════════════════════════════
+// Token program support (Token / Token-2022)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// `TransferChecked` instruction tag, identical in Token and Token-2022
+const TRANSFER_CHECKED_TAG: u8 = 12;
+/// Basis points denominator for Token-2022 transfer fees
+const MAX_FEE_BASIS_POINTS: u128 = 10_000;
+
+/// Transfer fee for one epoch range (Token-2022 transfer-fee extension)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TransferFee {
+    /// First epoch this fee applies to
+    pub epoch: u64,
+    /// Cap on the fee per transfer, in base units
+    pub maximum_fee: u64,
+    pub basis_points: u16,
+}
+
+impl TransferFee {
+    /// Fee withheld from a transfer of `amount` base units (rounded up, capped)
+    pub fn calculate_fee(&self, amount: u64) -> u64 {
+        if self.basis_points == 0 || amount == 0 {
+            return 0;
+        }
+        let fee = (amount as u128 * self.basis_points as u128 + MAX_FEE_BASIS_POINTS - 1) / MAX_FEE_BASIS_POINTS;
+        (fee as u64).min(self.maximum_fee)
+    }
+}
 
-        // Add the tip instructions to the existing instructions list
-        instructions.push(tip_instruction1);
-        instructions.push(tip_instruction2);
+/// Older and newer fee of a transfer-fee mint; the newer one takes over at its epoch
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TransferFeeConfig {
+    pub older: TransferFee,
+    pub newer: TransferFee,
+}
 
-        println!("Added Jito tip instructions to transaction");
-        Ok(())
+impl TransferFeeConfig {
+    pub fn fee_for_epoch(&self, epoch: u64) -> &TransferFee {
+        if epoch >= self.newer.epoch {
+            &self.newer
+        } else {
+            &self.older
+        }
+    }
+}
+
+/// What a transfer needs to know about a mint
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintInfo {
+    /// Owning token program (Token or Token-2022)
+    pub program_id: Pubkey,
+    pub decimals: u8,
+    pub transfer_fee: Option<TransferFeeConfig>,
+}
+
+impl MintInfo {
+    pub fn is_token_2022(&self) -> bool {
+        self.program_id.to_string() == TOKEN_2022_PROGRAM_ID
+    }
+
+    /// Convert a UI amount to base units
+    pub fn to_units(&self, amount: f64) -> u64 {
+        (amount * 10_f64.powi(self.decimals as i32)).round() as u64
+    }
+
+    /// Associated token account of `owner` under this mint's token program
+    pub fn associated_token_address(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, mint, &self.program_id)
+    }
+
+    /// `TransferChecked` for this mint's program. Token-2022 withholds any
+    /// transfer fee from the amount at the destination.
+    pub fn transfer_checked(
+        &self,
+        mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        let mut data = Vec::with_capacity(10);
+        data.push(TRANSFER_CHECKED_TAG);
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.push(self.decimals);
+
+        Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(*source, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new_readonly(*authority, true),
+            ],
+            data,
+        }
+    }
+}
+
+/// Amount sent versus amount received for a token transfer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenTransferQuote {
+    pub amount_units: u64,
+    /// Token-2022 transfer fee withheld from the amount (0 for other mints)
+    pub fee_units: u64,
+    pub decimals: u8,
+}
+
+impl TokenTransferQuote {
+    pub fn received_units(&self) -> u64 {
+        self.amount_units.saturating_sub(self.fee_units)
+    }
+
+    pub fn ui_fee(&self) -> f64 {
+        self.fee_units as f64 / 10_f64.powi(self.decimals as i32)
+    }
+
+    pub fn ui_received(&self) -> f64 {
+        self.received_units() as f64 / 10_f64.powi(self.decimals as i32)
+    }
+}
+
+fn parse_transfer_fee(value: &Value) -> Option<TransferFee> {
+    Some(TransferFee {
+        epoch: value["epoch"].as_u64()?,
+        maximum_fee: value["maximumFee"].as_u64()?,
+        basis_points: value["transferFeeBasisPoints"].as_u64()? as u16,
+    })
+}
+
+/// Parse a `jsonParsed` getAccountInfo value for a mint
+fn parse_mint_info(value: &Value) -> Result<MintInfo, String> {
+    if value.is_null() {
+        return Err("Mint account not found".to_string());
+    }
+
+    let owner = value["owner"].as_str().unwrap_or_default();
+    if owner != TOKEN_PROGRAM_ID && owner != TOKEN_2022_PROGRAM_ID {
+        return Err(format!("Account is not a token mint (owner: {})", owner));
+    }
+    let program_id = Pubkey::from_str(owner).map_err(|e| e.to_string())?;
+
+    let info = &value["data"]["parsed"]["info"];
+    let decimals = info["decimals"]
+        .as_u64()
+        .ok_or("Missing mint decimals")? as u8;
+
+    let transfer_fee = info["extensions"]
+        .as_array()
+        .and_then(|extensions| {
+            extensions.iter().find(|ext| ext["extension"] == "transferFeeConfig")
+        })
+        .and_then(|ext| {
+            Some(TransferFeeConfig {
+                older: parse_transfer_fee(&ext["state"]["olderTransferFee"])?,
+                newer: parse_transfer_fee(&ext["state"]["newerTransferFee"])?,
+            })
+        });
+
+    Ok(MintInfo { program_id, decimals, transfer_fee })
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Pre-sign simulation and balance-diff preview
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Size of an SPL token account (Token and Token-2022 share the base layout)
+const TOKEN_ACCOUNT_LEN: usize = 165;
+/// Mint layout: mint authority option (36), supply (8), then decimals
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Net change of one token balance owned by the wallet
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenBalanceChange {
+    pub mint: String,
+    pub token_account: String,
+    /// Change in base units (negative = tokens leave the wallet)
+    pub raw_change: i128,
+    pub decimals: u8,
+}
+
+impl TokenBalanceChange {
+    pub fn ui_change(&self) -> f64 {
+        self.raw_change as f64 / 10_f64.powi(self.decimals as i32)
+    }
+}
+
+/// Result of simulating a transaction before it is signed
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SimulationPreview {
+    pub success: bool,
+    pub error: Option<String>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    /// Network fee charged to the fee payer
+    pub fee_lamports: u64,
+    /// Wallet SOL change excluding the network fee (negative = SOL leaves the wallet)
+    pub sol_change_lamports: i64,
+    pub token_changes: Vec<TokenBalanceChange>,
+    /// What the signing firewall will warn about or refuse
+    pub firewall: Vec<crate::signing::firewall::Finding>,
+    /// Decoded instructions, one line each
+    pub instructions: Vec<String>,
+    /// Wallet-drain patterns that need an extra confirmation before signing
+    pub drain_risks: Vec<crate::signing::drain::DrainRisk>,
+    /// What the transaction costs on top of the amounts moved
+    pub fees: FeeBreakdown,
+}
+
+impl SimulationPreview {
+    /// Human readable "you will send / receive / pay" lines
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        if self.sol_change_lamports < 0 {
+            lines.push(format!("You will send {:.6} SOL", -self.sol_change_lamports as f64 / 1_000_000_000.0));
+        } else if self.sol_change_lamports > 0 {
+            lines.push(format!("You will receive {:.6} SOL", self.sol_change_lamports as f64 / 1_000_000_000.0));
+        }
+
+        for change in &self.token_changes {
+            let mint = &change.mint;
+            let short_mint = format!("{}...{}", &mint[..4.min(mint.len())], &mint[mint.len().saturating_sub(4)..]);
+            let amount = change.ui_change();
+            if amount < 0.0 {
+                lines.push(format!("You will send {} of {}", -amount, short_mint));
+            } else if amount > 0.0 {
+                lines.push(format!("You will receive {} of {}", amount, short_mint));
+            }
+        }
+
+        lines.extend(self.fees.lines());
+        lines
+    }
+}
+
+/// Rent-exempt minimum of a plain 165 byte token account
+pub const TOKEN_ACCOUNT_RENT_LAMPORTS: u64 = 2_039_280;
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+/// Compute units granted per instruction when no limit is set
+const DEFAULT_INSTRUCTION_UNITS: u64 = 200_000;
+const MAX_TRANSACTION_UNITS: u64 = 1_400_000;
+
+/// Everything a transaction costs besides the amounts it moves
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FeeBreakdown {
+    /// Signature fee
+    pub base_fee: u64,
+    /// Compute unit limit times compute unit price
+    pub priority_fee: u64,
+    pub jito_tip: u64,
+    /// App tip to the jules account
+    pub jules_tip: u64,
+    /// Rent deposited into associated token accounts the transaction creates
+    pub ata_rent: u64,
+    pub atas_created: usize,
+}
+
+impl FeeBreakdown {
+    pub fn total(&self) -> u64 {
+        self.base_fee + self.priority_fee + self.jito_tip + self.jules_tip + self.ata_rent
+    }
+
+    /// One line per non-zero item plus the estimated total
+    pub fn lines(&self) -> Vec<String> {
+        let sol = |lamports: u64| lamports as f64 / 1_000_000_000.0;
+        let mut lines = vec![format!("Base fee: {:.6} SOL", sol(self.base_fee))];
+        if self.priority_fee > 0 {
+            lines.push(format!("Priority fee: {:.6} SOL", sol(self.priority_fee)));
+        }
+        if self.jito_tip > 0 {
+            lines.push(format!("Jito tip: {:.6} SOL", sol(self.jito_tip)));
+        }
+        if self.jules_tip > 0 {
+            lines.push(format!("App tip: {:.6} SOL", sol(self.jules_tip)));
+        }
+        if self.atas_created > 0 {
+            lines.push(format!(
+                "Token account rent ({} new): {:.6} SOL",
+                self.atas_created,
+                sol(self.ata_rent)
+            ));
+        }
+        lines.push(format!("Estimated total cost: {:.6} SOL", sol(self.total())));
+        lines
+    }
+}
+
+/// Account state captured before or after simulation
+#[derive(Debug, Clone, PartialEq)]
+struct AccountSnapshot {
+    lamports: u64,
+    owner: String,
+    data: Vec<u8>,
+}
+
+/// Parse an account returned with `"encoding": "base64"`
+fn parse_account_snapshot(value: &Value) -> Option<AccountSnapshot> {
+    if value.is_null() {
+        return None;
+    }
+    let data = value["data"][0].as_str()
+        .and_then(|d| base64::decode(d).ok())
+        .unwrap_or_default();
+    Some(AccountSnapshot {
+        lamports: value["lamports"].as_u64().unwrap_or(0),
+        owner: value["owner"].as_str().unwrap_or_default().to_string(),
+        data,
+    })
+}
+
+/// Read (mint, owner, amount) from a token account snapshot
+fn parse_token_account(snapshot: &AccountSnapshot) -> Option<(Pubkey, Pubkey, u64)> {
+    if snapshot.owner != TOKEN_PROGRAM_ID && snapshot.owner != TOKEN_2022_PROGRAM_ID {
+        return None;
+    }
+    if snapshot.data.len() < TOKEN_ACCOUNT_LEN {
+        return None;
+    }
+    let mint = Pubkey::try_from(&snapshot.data[0..32]).ok()?;
+    let owner = Pubkey::try_from(&snapshot.data[32..64]).ok()?;
+    let amount = u64::from_le_bytes(snapshot.data[64..72].try_into().ok()?);
+    Some((mint, owner, amount))
+}
+
+/// Compute the wallet's SOL delta and per-token-account deltas.
+///
+/// Accounts created by the transaction have no `pre` snapshot and closed
+/// accounts have no `post` snapshot; both count as a zero balance.
+fn compute_balance_diff(
+    wallet: &Pubkey,
+    addresses: &[Pubkey],
+    pre: &[Option<AccountSnapshot>],
+    post: &[Option<AccountSnapshot>],
+) -> (i64, Vec<(String, String, i128)>) {
+    let mut sol_change: i64 = 0;
+    let mut token_changes = Vec::new();
+
+    for (index, address) in addresses.iter().enumerate() {
+        let before = pre.get(index).cloned().flatten();
+        let after = post.get(index).cloned().flatten();
+
+        if address == wallet {
+            let pre_lamports = before.as_ref().map(|a| a.lamports).unwrap_or(0) as i64;
+            let post_lamports = after.as_ref().map(|a| a.lamports).unwrap_or(0) as i64;
+            sol_change = post_lamports - pre_lamports;
+            continue;
+        }
+
+        let pre_token = before.as_ref().and_then(parse_token_account);
+        let post_token = after.as_ref().and_then(parse_token_account);
+
+        let (mint, owner) = match (&pre_token, &post_token) {
+            (_, Some((mint, owner, _))) | (Some((mint, owner, _)), None) => (*mint, *owner),
+            (None, None) => continue,
+        };
+        if &owner != wallet {
+            continue;
+        }
+
+        let pre_amount = pre_token.map(|(_, _, amount)| amount).unwrap_or(0) as i128;
+        let post_amount = post_token.map(|(_, _, amount)| amount).unwrap_or(0) as i128;
+        if pre_amount != post_amount {
+            token_changes.push((mint.to_string(), address.to_string(), post_amount - pre_amount));
+        }
+    }
+
+    (sol_change, token_changes)
+}
+
+/// Lookup table accounts start with a 56 byte header before the addresses
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// Addresses stored in a lookup table account
+fn lookup_table_addresses(data: &[u8]) -> Vec<Pubkey> {
+    data.get(LOOKUP_TABLE_META_SIZE..)
+        .unwrap_or_default()
+        .chunks_exact(32)
+        .map(|chunk| Pubkey::new_from_array(chunk.try_into().expect("chunk is 32 bytes")))
+        .collect()
+}
+
+/// Every account a message loads, in the order the runtime indexes them:
+/// static keys, then the writable and then the readonly lookup table entries.
+/// `tables` maps each lookup table to its addresses.
+pub fn loaded_account_keys(
+    message: &VersionedMessage,
+    tables: &HashMap<Pubkey, Vec<Pubkey>>,
+) -> Result<Vec<Pubkey>, String> {
+    let mut keys = message.static_account_keys().to_vec();
+    let lookups = message.address_table_lookups().unwrap_or_default();
+
+    let mut readonly = Vec::new();
+    for lookup in lookups {
+        let table = tables.get(&lookup.account_key)
+            .ok_or_else(|| format!("Lookup table {} not found", lookup.account_key))?;
+        let resolve = |index: &u8| {
+            table.get(*index as usize).copied()
+                .ok_or_else(|| format!("Index {} is past the end of lookup table {}", index, lookup.account_key))
+        };
+        for index in &lookup.writable_indexes {
+            keys.push(resolve(index)?);
+        }
+        for index in &lookup.readonly_indexes {
+            readonly.push(resolve(index)?);
+        }
+    }
+    keys.extend(readonly);
+    Ok(keys)
+}
+
+/// Work out the fees, tips and ATA rent a message pays. `account_exists`
+/// tells whether an account is already on chain, so idempotent ATA creates
+/// for existing accounts aren't counted.
+pub fn fee_breakdown(message: &VersionedMessage, account_exists: impl Fn(&Pubkey) -> bool) -> FeeBreakdown {
+    use solana_sdk::system_instruction::SystemInstruction;
+
+    let keys = message.static_account_keys();
+    let jules_tip_account = Pubkey::from_str(crate::tips::JULES_TIP_ACCOUNT).ok();
+    let mut fees = FeeBreakdown {
+        base_fee: message.header().num_required_signatures as u64 * LAMPORTS_PER_SIGNATURE,
+        ..Default::default()
+    };
+    let mut unit_limit = None;
+    let mut unit_price = 0u64;
+    let mut other_instructions = 0u64;
+
+    for ix in message.instructions() {
+        let Some(program_id) = keys.get(ix.program_id_index as usize) else { continue };
+        let account = |position: usize| -> Option<Pubkey> {
+            ix.accounts.get(position).and_then(|&i| keys.get(i as usize)).copied()
+        };
+
+        if *program_id == solana_sdk::compute_budget::id() {
+            match ix.data.first() {
+                Some(2) => unit_limit = ix.data.get(1..5)
+                    .and_then(|b| b.try_into().ok())
+                    .map(|b| u32::from_le_bytes(b) as u64),
+                Some(3) => unit_price = ix.data.get(1..9)
+                    .and_then(|b| b.try_into().ok())
+                    .map(u64::from_le_bytes)
+                    .unwrap_or(0),
+                _ => {}
+            }
+            continue;
+        }
+        other_instructions += 1;
+
+        if *program_id == solana_sdk::system_program::id() {
+            if let Ok(SystemInstruction::Transfer { lamports }) = bincode::deserialize(&ix.data) {
+                let recipient = account(1);
+                if recipient.as_ref().is_some_and(crate::tips::is_jito_tip_account) {
+                    fees.jito_tip += lamports;
+                } else if recipient.is_some() && recipient == jules_tip_account {
+                    fees.jules_tip += lamports;
+                }
+            }
+        } else if *program_id == spl_associated_token_account::id() {
+            // Create (empty data or 0) always creates, CreateIdempotent (1)
+            // only when the account is missing
+            let creates = match ix.data.first() {
+                None | Some(0) => true,
+                Some(1) => account(1).is_some_and(|ata| !account_exists(&ata)),
+                _ => false,
+            };
+            if creates {
+                fees.atas_created += 1;
+                fees.ata_rent += TOKEN_ACCOUNT_RENT_LAMPORTS;
+            }
+        }
+    }
+
+    let units = unit_limit
+        .unwrap_or(other_instructions * DEFAULT_INSTRUCTION_UNITS)
+        .min(MAX_TRANSACTION_UNITS);
+    // Price is in micro-lamports per unit
+    fees.priority_fee = (units as u128 * unit_price as u128).div_ceil(1_000_000) as u64;
+    fees
+}
+
+impl TransactionClient {
+    /// `loaded_account_keys` of a message, fetching its lookup tables
+    async fn resolve_account_keys(&self, message: &VersionedMessage) -> Result<Vec<Pubkey>, Box<dyn Error>> {
+        let table_keys: Vec<Pubkey> = message.address_table_lookups()
+            .unwrap_or_default()
+            .iter()
+            .map(|lookup| lookup.account_key)
+            .collect();
+        if table_keys.is_empty() {
+            return Ok(message.static_account_keys().to_vec());
+        }
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getMultipleAccounts",
+            "params": [
+                table_keys.iter().map(|key| key.to_string()).collect::<Vec<_>>(),
+                { "encoding": "base64", "commitment": "confirmed" }
+            ]
+        });
+        let response: Value = self.rpc_call(&request).await?;
+        if let Some(error) = response.get("error") {
+            return Err(format!("Failed to load lookup tables: {:?}", error).into());
+        }
+        let accounts = response["result"]["value"].as_array().cloned().unwrap_or_default();
+        let tables: HashMap<Pubkey, Vec<Pubkey>> = table_keys
+            .into_iter()
+            .zip(accounts.iter())
+            .filter_map(|(key, account)| Some((key, lookup_table_addresses(&parse_account_snapshot(account)?.data))))
+            .collect();
+
+        Ok(loaded_account_keys(message, &tables)?)
+    }
+
+    /// Simulate an unsigned (or signed) transaction and compute what it does to `wallet`.
+    ///
+    /// Accounts reached through address lookup tables are resolved first, so
+    /// they are tracked like static keys.
+    pub async fn simulate_balance_diff(
+        &self,
+        transaction: &VersionedTransaction,
+        wallet: &Pubkey,
+    ) -> Result<SimulationPreview, Box<dyn Error>> {
+        let addresses = self.resolve_account_keys(&transaction.message).await?;
+        let address_strings: Vec<String> = addresses.iter().map(|a| a.to_string()).collect();
+
+        // Snapshot accounts before the simulation
+        let pre_request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getMultipleAccounts",
+            "params": [
+                address_strings,
+                { "encoding": "base64", "commitment": "confirmed" }
+            ]
+        });
+        let pre_json: Value = self.rpc_call(&pre_request).await?;
+        if let Some(error) = pre_json.get("error") {
+            return Err(format!("Failed to load accounts: {:?}", error).into());
+        }
+        let pre: Vec<Option<AccountSnapshot>> = pre_json["result"]["value"]
+            .as_array()
+            .map(|accounts| accounts.iter().map(parse_account_snapshot).collect())
+            .unwrap_or_default();
+
+        // Simulate without signatures against the latest blockhash
+        let serialized = bincode::serialize(transaction)?;
+        let sim_request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "simulateTransaction",
+            "params": [
+                base64::encode(&serialized),
+                {
+                    "encoding": "base64",
+                    "sigVerify": false,
+                    "replaceRecentBlockhash": true,
+                    "commitment": "confirmed",
+                    "accounts": {
+                        "encoding": "base64",
+                        "addresses": address_strings
+                    }
+                }
+            ]
+        });
+        let sim_json: Value = self.rpc_call(&sim_request).await?;
+        if let Some(error) = sim_json.get("error") {
+            return Err(format!("Simulation request failed: {:?}", error).into());
+        }
+
+        let value = &sim_json["result"]["value"];
+        let logs: Vec<String> = value["logs"]
+            .as_array()
+            .map(|l| l.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        let units_consumed = value["unitsConsumed"].as_u64();
+        let quoted_fee = self.get_fee_for_message(&transaction.message).await.ok();
+        let firewall = crate::signing::firewall::inspect_message(&transaction.message, wallet);
+        let instructions = crate::decoder::describe_transaction(transaction);
+        let mut fees = fee_breakdown(&transaction.message, |account| {
+            addresses.iter().position(|a| a == account)
+                .and_then(|i| pre.get(i))
+                .is_some_and(|snapshot| snapshot.is_some())
+        });
+        // Prefer the node's quote, which already includes the priority fee
+        if let Some(quoted) = quoted_fee {
+            fees.base_fee = quoted.saturating_sub(fees.priority_fee);
+        }
+        let fee_lamports = fees.base_fee + fees.priority_fee;
+        let mut drain_risks = crate::signing::drain::instruction_risks(&transaction.message, wallet);
+        drain_risks.extend(self.fresh_program_risks(&transaction.message, &addresses, &pre).await);
+
+        if !value["err"].is_null() {
+            println!("⚠️ Simulation failed: {}", value["err"]);
+            return Ok(SimulationPreview {
+                success: false,
+                error: Some(value["err"].to_string()),
+                logs,
+                units_consumed,
+                fee_lamports,
+                firewall,
+                instructions,
+                drain_risks,
+                fees,
+                ..Default::default()
+            });
+        }
+
+        let post: Vec<Option<AccountSnapshot>> = value["accounts"]
+            .as_array()
+            .map(|accounts| accounts.iter().map(parse_account_snapshot).collect())
+            .unwrap_or_default();
+
+        let (mut sol_change, raw_token_changes) = compute_balance_diff(wallet, &addresses, &pre, &post);
+
+        // The simulated fee payer balance already has the fee deducted
+        if transaction.message.static_account_keys().first() == Some(wallet) {
+            sol_change += fee_lamports as i64;
+        }
+
+        let mints: Vec<String> = raw_token_changes.iter().map(|(mint, _, _)| mint.clone()).collect();
+        let decimals = self.get_token_decimals_batch(&mints).await;
+        let token_changes = raw_token_changes
+            .into_iter()
+            .map(|(mint, token_account, raw_change)| TokenBalanceChange {
+                decimals: decimals.get(&mint).copied().unwrap_or(0),
+                mint,
+                token_account,
+                raw_change,
+            })
+            .collect();
+
+        let wallet_balance = addresses.iter().position(|a| a == wallet)
+            .and_then(|i| pre.get(i).cloned().flatten())
+            .map(|snapshot| snapshot.lamports);
+        if let Some(balance) = wallet_balance {
+            drain_risks.extend(crate::signing::drain::balance_risk(balance, sol_change, fee_lamports));
+        }
+
+        println!("🔍 Simulation ok: sol_change={} fee={} units={:?}", sol_change, fee_lamports, units_consumed);
+
+        Ok(SimulationPreview {
+            success: true,
+            error: None,
+            logs,
+            units_consumed,
+            fee_lamports,
+            sol_change_lamports: sol_change,
+            token_changes,
+            firewall,
+            instructions,
+            drain_risks,
+            fees,
+        })
+    }
+
+    /// Flag unknown upgradeable programs deployed or upgraded recently. Best
+    /// effort: lookups that fail just don't flag anything.
+    async fn fresh_program_risks(
+        &self,
+        message: &VersionedMessage,
+        addresses: &[Pubkey],
+        pre: &[Option<AccountSnapshot>],
+    ) -> Vec<crate::signing::drain::DrainRisk> {
+        use crate::signing::drain;
+
+        let candidates: Vec<(Pubkey, Pubkey)> = drain::unknown_programs(message)
+            .into_iter()
+            .filter_map(|program| {
+                let snapshot = addresses.iter().position(|a| *a == program)
+                    .and_then(|i| pre.get(i))?
+                    .as_ref()?;
+                if snapshot.owner != drain::UPGRADEABLE_LOADER_ID {
+                    return None;
+                }
+                Some((program, drain::program_data_address(&snapshot.data)?))
+            })
+            .collect();
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        let program_data: Vec<String> = candidates.iter().map(|(_, data)| data.to_string()).collect();
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getMultipleAccounts",
+            "params": [
+                program_data,
+                // Only the header with the deployment slot is needed
+                { "encoding": "base64", "commitment": "confirmed", "dataSlice": { "offset": 0, "length": 12 } }
+            ]
+        });
+        let Ok(response) = self.rpc_call(&request).await else { return Vec::new() };
+        let Ok(current_slot) = self.get_current_slot().await.map_err(|e| e.to_string()) else {
+            return Vec::new();
+        };
+
+        let accounts = response["result"]["value"].as_array().cloned().unwrap_or_default();
+        candidates
+            .into_iter()
+            .zip(accounts.iter())
+            .filter_map(|((program, _), account)| {
+                let slot = drain::deployed_slot(&parse_account_snapshot(account)?.data)?;
+                drain::fresh_program_risk(program, slot, current_slot)
+            })
+            .collect()
+    }
+
+    /// `fee_breakdown` of a message, checking on chain which of its accounts
+    /// already exist
+    pub async fn fee_breakdown_on_chain(&self, message: &VersionedMessage) -> Result<FeeBreakdown, String> {
+        let keys = message.static_account_keys();
+        let addresses: Vec<String> = keys.iter().map(|key| key.to_string()).collect();
+        let accounts = crate::rpc::get_multiple_accounts(&addresses, Some(&self.rpc_url)).await?;
+        Ok(fee_breakdown(message, |account| {
+            keys.iter()
+                .position(|key| key == account)
+                .and_then(|i| accounts.get(i))
+                .is_some_and(|found| found.is_some())
+        }))
+    }
+
+    /// Network fee for a message, as quoted by the RPC node
+    pub async fn get_fee_for_message(&self, message: &VersionedMessage) -> Result<u64, Box<dyn Error>> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getFeeForMessage",
+            "params": [
+                base64::encode(message.serialize()),
+                { "commitment": "confirmed" }
+            ]
+        });
+
+        let json: Value = self.rpc_call(&request).await?;
+        json["result"]["value"]
+            .as_u64()
+            .ok_or_else(|| format!("Failed to get fee for message: {:?}", json).into())
+    }
+
+    /// Build the unsigned transaction a send would produce, including timeout and Jito tips
+    async fn build_preview_transaction(
+        &self,
+        payer: &Pubkey,
+        mut instructions: Vec<solana_sdk::instruction::Instruction>,
+    ) -> Result<VersionedTransaction, Box<dyn Error>> {
+        let current_slot = self.get_current_slot().await?;
+        instructions.insert(0, timeout::build_timeout_instruction_from_current(
+            current_slot,
+            timeout::DEFAULT_SLOT_WINDOW,
+        )?);
+        self.apply_memo(payer, &mut instructions)?;
+
+        if get_current_jito_settings().jito_tx {
+            self.apply_jito_modifications(payer, &mut instructions)?;
+        }
+        self.size_compute_budget(&mut instructions, payer).await;
+
+        let mut message = Message::new(&instructions, Some(payer));
+        message.recent_blockhash = self.get_recent_blockhash().await?;
+
+        Ok(VersionedTransaction {
+            signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
+            message: VersionedMessage::Legacy(message),
+        })
+    }
+
+    /// Preview a SOL transfer before asking for a signature
+    pub async fn preview_sol_transfer(
+        &self,
+        from_address: &str,
+        to_address: &str,
+        amount_sol: f64,
+    ) -> Result<SimulationPreview, Box<dyn Error>> {
+        let from_pubkey = Pubkey::from_str(from_address)?;
+        let transaction = self.unsigned_sol_transfer(from_address, to_address, amount_sol).await?;
+        self.simulate_balance_diff(&transaction, &from_pubkey).await
+    }
+
+    /// Preview an SPL token transfer before asking for a signature
+    pub async fn preview_spl_transfer(
+        &self,
+        from_address: &str,
+        to_address: &str,
+        amount: f64,
+        token_mint: &str,
+    ) -> Result<SimulationPreview, Box<dyn Error>> {
+        let from_pubkey = Pubkey::from_str(from_address)?;
+        let transaction = self.unsigned_spl_transfer(from_address, to_address, amount, token_mint).await?;
+        self.simulate_balance_diff(&transaction, &from_pubkey).await
+    }
+
+    /// The unsigned SOL transfer a send would broadcast, e.g. for export
+    pub async fn unsigned_sol_transfer(
+        &self,
+        from_address: &str,
+        to_address: &str,
+        amount_sol: f64,
+    ) -> Result<VersionedTransaction, Box<dyn Error>> {
+        let from_pubkey = Pubkey::from_str(from_address)?;
+        let to_pubkey = Pubkey::from_str(to_address)?;
+        let amount_lamports = (amount_sol * 1_000_000_000.0) as u64;
+
+        self.build_preview_transaction(
+            &from_pubkey,
+            vec![system_instruction::transfer(&from_pubkey, &to_pubkey, amount_lamports)],
+        ).await
     }
-}
\ No newline at end of file
+
+    /// The unsigned SPL token transfer a send would broadcast, e.g. for export
+    pub async fn unsigned_spl_transfer(
+        &self,
+        from_address: &str,
+        to_address: &str,
+        amount: f64,
+        token_mint: &str,
+    ) -> Result<VersionedTransaction, Box<dyn Error>> {
+        let from_pubkey = Pubkey::from_str(from_address)?;
+        let to_pubkey = Pubkey::from_str(to_address)?;
+        let mint_pubkey = Pubkey::from_str(token_mint)?;
+
+        let (instructions, _) = self.build_token_transfer_instructions(
+            &from_pubkey,
+            &from_pubkey,
+            &to_pubkey,
+            &mint_pubkey,
+            amount,
+        ).await?;
+
+        self.build_preview_transaction(&from_pubkey, instructions).await
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Compute budget sizing
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// `SetComputeUnitLimit` instruction tag of the compute budget program
+const SET_COMPUTE_UNIT_LIMIT_TAG: u8 = 2;
+/// Most compute units a transaction can request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+/// Headroom on top of the simulated consumption, in percent
+const COMPUTE_UNIT_MARGIN_PERCENT: u64 = 15;
+/// Headroom for small transactions, where the percentage is only a few units
+const MIN_COMPUTE_UNIT_MARGIN: u64 = 1_000;
+
+/// Limit to request for a transaction that consumed `units_consumed` in simulation
+pub fn compute_unit_limit_for(units_consumed: u64) -> u32 {
+    let margin = (units_consumed * COMPUTE_UNIT_MARGIN_PERCENT / 100).max(MIN_COMPUTE_UNIT_MARGIN);
+    (units_consumed + margin).min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+}
+
+fn is_compute_unit_limit(instruction: &Instruction) -> bool {
+    instruction.program_id == solana_sdk::compute_budget::id()
+        && instruction.data.first() == Some(&SET_COMPUTE_UNIT_LIMIT_TAG)
+}
+
+/// `instructions` with any existing `SetComputeUnitLimit` replaced by `limit`.
+/// The new instruction goes last so a leading nonce advance or timeout stays first.
+pub fn with_compute_unit_limit(instructions: &[Instruction], limit: u32) -> Vec<Instruction> {
+    let mut sized: Vec<Instruction> = instructions.iter().filter(|ix| !is_compute_unit_limit(ix)).cloned().collect();
+    sized.push(solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(limit));
+    sized
+}
+
+impl TransactionClient {
+    /// Compute units the instructions consume, simulated at the maximum limit
+    pub async fn simulate_compute_units(&self, instructions: &[Instruction], payer: &Pubkey) -> Result<u64, Box<dyn Error>> {
+        let message = Message::new(&with_compute_unit_limit(instructions, MAX_COMPUTE_UNIT_LIMIT), Some(payer));
+        let transaction = VersionedTransaction {
+            signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
+            message: VersionedMessage::Legacy(message),
+        };
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "simulateTransaction",
+            "params": [
+                base64::encode(bincode::serialize(&transaction)?),
+                {
+                    "encoding": "base64",
+                    "sigVerify": false,
+                    "replaceRecentBlockhash": true,
+                    "commitment": "confirmed"
+                }
+            ]
+        });
+        let json: Value = self.rpc_call(&request).await?;
+        if let Some(error) = json.get("error") {
+            return Err(format!("Simulation request failed: {:?}", error).into());
+        }
+        let value = &json["result"]["value"];
+        if !value["err"].is_null() {
+            return Err(format!("Simulation failed: {}", value["err"]).into());
+        }
+        value["unitsConsumed"]
+            .as_u64()
+            .ok_or_else(|| "Simulation returned no compute units".into())
+    }
+
+    /// Set the compute unit limit of `instructions` from a simulation.
+    ///
+    /// If the simulation fails the instructions are left as they are, so the
+    /// send itself reports the real error.
+    pub async fn size_compute_budget(&self, instructions: &mut Vec<Instruction>, payer: &Pubkey) {
+        match self.simulate_compute_units(instructions, payer).await {
+            Ok(units) => {
+                let limit = compute_unit_limit_for(units);
+                println!("⚙️ Simulated {} compute units, requesting {}", units, limit);
+                *instructions = with_compute_unit_limit(instructions, limit);
+            }
+            Err(e) => println!("⚠️ Compute budget not sized: {}", e),
+        }
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Transaction builder
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Largest serialized transaction the network accepts
+pub const PACKET_DATA_SIZE: usize = 1232;
+/// Most accounts a single transaction can lock
+pub const MAX_TRANSACTION_ACCOUNTS: usize = 64;
+
+/// Size and account count of a transaction as it would be sent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransactionLimits {
+    pub size: usize,
+    pub accounts: usize,
+}
+
+impl TransactionLimits {
+    pub fn check(&self) -> Result<(), String> {
+        if self.size > PACKET_DATA_SIZE {
+            return Err(format!("Transaction is {} bytes, over the {} byte limit", self.size, PACKET_DATA_SIZE));
+        }
+        if self.accounts > MAX_TRANSACTION_ACCOUNTS {
+            return Err(format!("Transaction uses {} accounts, over the limit of {}", self.accounts, MAX_TRANSACTION_ACCOUNTS));
+        }
+        Ok(())
+    }
+}
+
+/// Instructions for one transaction plus any keypairs that must co-sign
+/// them (e.g. a new stake account). `TransactionClient::send_built`
+/// adds timeout protection and a simulated compute budget before signing.
+pub struct TransactionBuilder {
+    payer: Pubkey,
+    instructions: Vec<Instruction>,
+    co_signers: Vec<solana_sdk::signature::Keypair>,
+}
+
+impl TransactionBuilder {
+    pub fn new(payer: Pubkey) -> Self {
+        Self { payer, instructions: Vec::new(), co_signers: Vec::new() }
+    }
+
+    pub fn add_instruction(&mut self, instruction: Instruction) {
+        self.instructions.push(instruction);
+    }
+
+    pub fn add_co_signer(&mut self, keypair: solana_sdk::signature::Keypair) {
+        self.co_signers.push(keypair);
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// The instructions as sent: timeout first, compute unit limit last
+    fn with_send_overhead(&self, timeout_ix: Instruction) -> Vec<Instruction> {
+        let mut instructions = vec![timeout_ix];
+        instructions.extend(self.instructions.iter().cloned());
+        with_compute_unit_limit(&instructions, MAX_COMPUTE_UNIT_LIMIT)
+    }
+
+    /// Limits of the transaction `send_built` would produce
+    pub fn limits(&self) -> TransactionLimits {
+        let timeout_ix = timeout::build_timeout_instruction(0).expect("timeout program id is valid");
+        let message = Message::new(&self.with_send_overhead(timeout_ix), Some(&self.payer));
+        let transaction = VersionedTransaction {
+            signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
+            message: VersionedMessage::Legacy(message),
+        };
+        TransactionLimits {
+            size: bincode::serialized_size(&transaction).map(|s| s as usize).unwrap_or(usize::MAX),
+            accounts: transaction.message.static_account_keys().len(),
+        }
+    }
+}
+
+impl TransactionClient {
+    /// Sign a built transaction with `signer` and its co-signers and send it
+    pub async fn send_built(&self, builder: TransactionBuilder, signer: &dyn TransactionSigner) -> Result<String, Box<dyn Error>> {
+        self.send_built_recording(builder, signer, |_, _| {}).await
+    }
+
+    /// `send_built`, handing the signature and blockhash to `on_signed`
+    /// before broadcasting so the transaction can be looked up after a crash
+    pub async fn send_built_recording(
+        &self,
+        builder: TransactionBuilder,
+        signer: &dyn TransactionSigner,
+        on_signed: impl FnOnce(&str, &str),
+    ) -> Result<String, Box<dyn Error>> {
+        let signer_pubkey = Pubkey::from_str(&signer.get_public_key().await?)?;
+        if signer_pubkey != builder.payer {
+            return Err("The transaction was built for a different wallet".into());
+        }
+        if builder.instructions.is_empty() {
+            return Err("Nothing to send".into());
+        }
+        builder.limits().check()?;
+        let mut transaction = self.unsigned_built(&builder).await?;
+
+        let co_signers: Vec<crate::signing::software::KeypairSigner> = builder
+            .co_signers
+            .into_iter()
+            .map(crate::signing::software::KeypairSigner::new)
+            .collect();
+        let mut signers: Vec<&dyn TransactionSigner> = vec![signer];
+        signers.extend(co_signers.iter().map(|s| s as &dyn TransactionSigner));
+        crate::signing::sign_all_slots(&mut transaction, &signers).await?;
+
+        on_signed(&transaction.signatures[0].to_string(), &transaction.message.recent_blockhash().to_string());
+
+        let encoded_transaction = bs58::encode(bincode::serialize(&transaction)?).into_string();
+        self.send_transaction(&encoded_transaction).await
+    }
+
+    /// Preview a built transaction before asking for a signature
+    pub async fn preview_built(&self, builder: &TransactionBuilder) -> Result<SimulationPreview, Box<dyn Error>> {
+        let transaction = self.unsigned_built(builder).await?;
+        self.simulate_balance_diff(&transaction, &builder.payer).await
+    }
+
+    /// The transaction `send_built` signs, with timeout protection and a
+    /// simulated compute budget
+    async fn unsigned_built(&self, builder: &TransactionBuilder) -> Result<VersionedTransaction, Box<dyn Error>> {
+        let current_slot = self.get_current_slot().await?;
+        let timeout_ix = timeout::build_timeout_instruction_from_current(current_slot, timeout::DEFAULT_SLOT_WINDOW)?;
+        let mut instructions = builder.with_send_overhead(timeout_ix);
+        self.size_compute_budget(&mut instructions, &builder.payer).await;
+
+        let mut message = Message::new(&instructions, Some(&builder.payer));
+        message.recent_blockhash = self.get_recent_blockhash().await?;
+        Ok(VersionedTransaction {
+            signatures: vec![SolanaSignature::default(); message.header.num_required_signatures as usize],
+            message: VersionedMessage::Legacy(message),
+        })
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Multi-recipient transfers
+// ══════════════════════════════════════════════════════════════════════════════
+
+const WRAPPED_SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// One line of a payroll-style send; `mint` is `None` for SOL
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecipientTransfer {
+    pub recipient: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: f64,
+}
+
+impl RecipientTransfer {
+    /// Form kept in an operation step's payload
+    pub fn to_json(&self) -> Value {
+        json!({
+            "recipient": self.recipient.to_string(),
+            "mint": self.mint.map(|mint| mint.to_string()),
+            "amount": self.amount,
+        })
+    }
+
+    pub fn from_json(value: &Value) -> Option<Self> {
+        let mint = match value["mint"].as_str() {
+            Some(mint) => Some(Pubkey::from_str(mint).ok()?),
+            None => None,
+        };
+        Some(Self {
+            recipient: Pubkey::from_str(value["recipient"].as_str()?).ok()?,
+            mint,
+            amount: value["amount"].as_f64()?,
+        })
+    }
+}
+
+/// Pack instruction groups into as few transactions as fit the size and
+/// account limits. A group (e.g. an ATA creation and the transfer into it)
+/// is never split across transactions.
+pub fn pack_instruction_groups(payer: &Pubkey, groups: Vec<Vec<Instruction>>) -> Result<Vec<TransactionBuilder>, String> {
+    Ok(pack_counted_groups(payer, groups)?.into_iter().map(|(builder, _)| builder).collect())
+}
+
+/// `pack_instruction_groups`, with how many groups went into each transaction
+fn pack_counted_groups(payer: &Pubkey, groups: Vec<Vec<Instruction>>) -> Result<Vec<(TransactionBuilder, usize)>, String> {
+    let mut batches: Vec<(TransactionBuilder, usize)> = Vec::new();
+    for group in groups {
+        if let Some((current, count)) = batches.last_mut() {
+            let mut candidate = TransactionBuilder::new(*payer);
+            for instruction in current.instructions().iter().chain(group.iter()) {
+                candidate.add_instruction(instruction.clone());
+            }
+            if candidate.limits().check().is_ok() {
+                *current = candidate;
+                *count += 1;
+                continue;
+            }
+        }
+        let mut fresh = TransactionBuilder::new(*payer);
+        for instruction in group {
+            fresh.add_instruction(instruction);
+        }
+        fresh.limits().check().map_err(|e| format!("A single transfer doesn't fit in a transaction: {}", e))?;
+        batches.push((fresh, 1));
+    }
+    Ok(batches)
+}
+
+impl TransactionClient {
+    /// Lower transfers into instruction groups. Missing recipient token
+    /// accounts are created (idempotently, once per recipient and mint) in
+    /// the group of their first transfer.
+    pub async fn multi_transfer_groups(
+        &self,
+        payer: &Pubkey,
+        transfers: &[RecipientTransfer],
+    ) -> Result<Vec<Vec<Instruction>>, Box<dyn Error>> {
+        // Look up every mint and recipient token account up front, batched
+        let token_mint = |transfer: &RecipientTransfer| transfer.mint.filter(|m| m.to_string() != WRAPPED_SOL_MINT);
+        let mut mints: Vec<Pubkey> = transfers.iter().filter_map(token_mint).collect();
+        mints.sort();
+        mints.dedup();
+        let mint_infos = self.get_mint_infos(&mints).await?;
+        let recipient_accounts: Vec<Pubkey> = transfers
+            .iter()
+            .filter_map(|t| token_mint(t).map(|mint| mint_infos[&mint].associated_token_address(&t.recipient, &mint)))
+            .collect();
+        let mut created = self.existing_accounts(&recipient_accounts).await?;
+        let mut groups = Vec::new();
+
+        for transfer in transfers {
+            if transfer.amount <= 0.0 {
+                return Err(format!("Amount for {} must be positive", transfer.recipient).into());
+            }
+            let Some(mint) = token_mint(transfer) else {
+                let lamports = (transfer.amount * 1_000_000_000.0) as u64;
+                groups.push(vec![system_instruction::transfer(payer, &transfer.recipient, lamports)]);
+                continue;
+            };
+
+            let mint_info = &mint_infos[&mint];
+            let from_token_account = mint_info.associated_token_address(payer, &mint);
+            let to_token_account = mint_info.associated_token_address(&transfer.recipient, &mint);
+
+            let mut group = Vec::new();
+            if !created.contains(&to_token_account) {
+                group.push(create_associated_token_account_idempotent(
+                    payer,
+                    &transfer.recipient,
+                    &mint,
+                    &mint_info.program_id,
+                ));
+            }
+            created.insert(to_token_account);
+            group.push(mint_info.transfer_checked(
+                &mint,
+                &from_token_account,
+                &to_token_account,
+                payer,
+                mint_info.to_units(transfer.amount),
+            ));
+            groups.push(group);
+        }
+        Ok(groups)
+    }
+
+    /// The transactions a multi-recipient send needs, usually one
+    pub async fn plan_multi_transfer(
+        &self,
+        payer: &Pubkey,
+        transfers: &[RecipientTransfer],
+    ) -> Result<Vec<TransactionBuilder>, Box<dyn Error>> {
+        if transfers.is_empty() {
+            return Err("No transfers to send".into());
+        }
+        let groups = self.multi_transfer_groups(payer, transfers).await?;
+        Ok(pack_instruction_groups(payer, groups)?)
+    }
+
+    /// Split transfers into the transactions they are sent in, for an
+    /// operation with a step per transaction. The memo, if any, goes in the
+    /// first one.
+    pub async fn plan_transfer_batches(
+        &self,
+        payer: &Pubkey,
+        transfers: &[RecipientTransfer],
+        memo: Option<&str>,
+    ) -> Result<Vec<Vec<RecipientTransfer>>, Box<dyn Error>> {
+        if transfers.is_empty() {
+            return Err("No transfers to send".into());
+        }
+        let mut groups = self.multi_transfer_groups(payer, transfers).await?;
+        if let Some(memo) = memo {
+            groups[0].push(crate::composer::memo_instruction(payer, memo));
+        }
+        let mut rest = transfers;
+        Ok(pack_counted_groups(payer, groups)?
+            .into_iter()
+            .map(|(_, count)| {
+                let (batch, tail) = rest.split_at(count);
+                rest = tail;
+                batch.to_vec()
+            })
+            .collect())
+    }
+
+    /// The transaction for one batch from `plan_transfer_batches`
+    pub async fn transfer_batch_builder(
+        &self,
+        payer: &Pubkey,
+        transfers: &[RecipientTransfer],
+        memo: Option<&str>,
+    ) -> Result<TransactionBuilder, Box<dyn Error>> {
+        let mut builder = TransactionBuilder::new(*payer);
+        for instruction in self.multi_transfer_groups(payer, transfers).await?.into_iter().flatten() {
+            builder.add_instruction(instruction);
+        }
+        if let Some(memo) = memo {
+            builder.add_instruction(crate::composer::memo_instruction(payer, memo));
+        }
+        Ok(builder)
+    }
+
+    /// Send every transfer with as few signatures as the limits allow.
+    /// Returns one signature per transaction sent; stops at the first failure.
+    pub async fn send_multi_transfer_with_signer(
+        &self,
+        signer: &dyn TransactionSigner,
+        transfers: &[RecipientTransfer],
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let payer = Pubkey::from_str(&signer.get_public_key().await?)?;
+        let batches = self.plan_multi_transfer(&payer, transfers).await?;
+        let total = batches.len();
+        let mut signatures = Vec::new();
+        for (index, builder) in batches.into_iter().enumerate() {
+            match self.send_built(builder, signer).await {
+                Ok(signature) => {
+                    println!("💸 Multi-transfer batch {}/{} sent: {}", index + 1, total, signature);
+                    signatures.push(signature);
+                }
+                Err(e) if signatures.is_empty() => return Err(e),
+                Err(e) => {
+                    return Err(format!(
+                        "Batch {} of {} failed after sending {}: {}",
+                        index + 1,
+                        total,
+                        signatures.join(", "),
+                        e
+                    ).into())
+                }
+            }
+        }
+        Ok(signatures)
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Confirmation tracking
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// How often the tracker polls, re-broadcasts and checks the blockhash
+const TRACKER_TICK: std::time::Duration = std::time::Duration::from_secs(2);
+/// Give up on a transaction that hasn't finalized after this long
+const TRACKER_MAX_DURATION: std::time::Duration = std::time::Duration::from_secs(180);
+
+/// Where a submitted transaction is on its way to finality
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionStatus {
+    /// Broadcast, not seen by the cluster yet
+    Submitted,
+    /// Voted on by a supermajority
+    Confirmed,
+    /// Rooted, can no longer be rolled back
+    Finalized,
+    /// Landed but the program returned an error
+    Failed(String),
+    /// The blockhash expired before the transaction landed; it never will
+    Expired,
+}
+
+impl TransactionStatus {
+    /// Nothing more will change once a transaction gets here
+    pub fn is_final(&self) -> bool {
+        matches!(self, TransactionStatus::Finalized | TransactionStatus::Failed(_) | TransactionStatus::Expired)
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            TransactionStatus::Submitted => 0,
+            TransactionStatus::Confirmed => 1,
+            _ => 2,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            TransactionStatus::Submitted => "⏳ Waiting for confirmation...".to_string(),
+            TransactionStatus::Confirmed => "✅ Confirmed, waiting for finalization...".to_string(),
+            TransactionStatus::Finalized => "✅ Finalized".to_string(),
+            TransactionStatus::Failed(error) => format!("❌ Failed on-chain: {}", error),
+            TransactionStatus::Expired => "⌛ Expired before landing. No funds were moved.".to_string(),
+        }
+    }
+
+    /// From an entry of `getSignatureStatuses`
+    fn from_signature_status(status: &Value) -> Option<Self> {
+        if status.is_null() {
+            return None;
+        }
+        if let Some(err) = status.get("err").filter(|e| !e.is_null()) {
+            return Some(TransactionStatus::Failed(err.to_string()));
+        }
+        match status["confirmationStatus"].as_str() {
+            Some("finalized") => Some(TransactionStatus::Finalized),
+            Some("confirmed") => Some(TransactionStatus::Confirmed),
+            _ => None,
+        }
+    }
+}
+
+/// WebSocket endpoint for an HTTP RPC URL
+pub fn websocket_url(rpc_url: &str) -> String {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        // Local validators serve WebSockets on the next port up
+        match rest.split_once(':') {
+            Some((host, port_and_path)) => {
+                let (port, path) = port_and_path.split_at(port_and_path.find('/').unwrap_or(port_and_path.len()));
+                match port.parse::<u16>() {
+                    Ok(port) => format!("ws://{}:{}{}", host, port + 1, path),
+                    Err(_) => format!("ws://{}", rest),
+                }
+            }
+            None => format!("ws://{}", rest),
+        }
+    } else {
+        rpc_url.to_string()
+    }
+}
+
+/// Request id used for each `signatureSubscribe` the tracker sends
+const CONFIRMED_SUBSCRIPTION_ID: u64 = 1;
+const FINALIZED_SUBSCRIPTION_ID: u64 = 2;
+
+/// Handle one WebSocket frame. Subscription acks are recorded in
+/// `subscriptions`; a `signatureNotification` yields the new status.
+fn parse_signature_message(text: &str, subscriptions: &mut HashMap<u64, TransactionStatus>) -> Option<TransactionStatus> {
+    let json: Value = serde_json::from_str(text).ok()?;
+
+    if let (Some(id), Some(subscription)) = (json["id"].as_u64(), json["result"].as_u64()) {
+        let status = match id {
+            CONFIRMED_SUBSCRIPTION_ID => TransactionStatus::Confirmed,
+            FINALIZED_SUBSCRIPTION_ID => TransactionStatus::Finalized,
+            _ => return None,
+        };
+        subscriptions.insert(subscription, status);
+        return None;
+    }
+
+    if json["method"].as_str() != Some("signatureNotification") {
+        return None;
+    }
+    let params = &json["params"];
+    let status = subscriptions.get(&params["subscription"].as_u64()?)?.clone();
+    match params["result"]["value"].get("err").filter(|e| !e.is_null()) {
+        Some(err) => Some(TransactionStatus::Failed(err.to_string())),
+        None => Some(status),
+    }
+}
+
+type SignatureSocket = crate::rpc_pool::WebSocket;
+
+/// Open a WebSocket and subscribe to `signature` at confirmed and finalized
+async fn subscribe_signature(rpc_url: &str, signature: &str) -> Result<SignatureSocket, String> {
+    use futures_util::SinkExt;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    let mut socket = crate::rpc_pool::connect_websocket(rpc_url)
+        .await
+        .map_err(|e| format!("WebSocket connection failed: {}", e))?;
+
+    for (id, commitment) in [(CONFIRMED_SUBSCRIPTION_ID, "confirmed"), (FINALIZED_SUBSCRIPTION_ID, "finalized")] {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "signatureSubscribe",
+            "params": [signature, { "commitment": commitment }]
+        });
+        socket
+            .send(WsMessage::Text(request.to_string()))
+            .await
+            .map_err(|e| format!("signatureSubscribe failed: {}", e))?;
+    }
+    Ok(socket)
+}
+
+/// Next text frame, `None` once the socket closes. Never resolves without a socket.
+async fn next_socket_text(socket: &mut Option<SignatureSocket>) -> Option<String> {
+    use futures_util::StreamExt;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    let Some(stream) = socket.as_mut() else {
+        return std::future::pending().await;
+    };
+    loop {
+        match stream.next().await? {
+            Ok(WsMessage::Text(text)) => return Some(text),
+            Ok(WsMessage::Close(_)) | Err(_) => return None,
+            Ok(_) => continue,
+        }
+    }
+}
+
+impl TransactionClient {
+    /// Current status of a signature via `getSignatureStatuses`, `None` if
+    /// the cluster hasn't seen it
+    pub async fn get_transaction_status(&self, signature: &str) -> Result<Option<TransactionStatus>, Box<dyn Error>> {
+        self.signature_status(signature, false).await
+    }
+
+    /// Whether the transaction recorded for an operation step landed. One
+    /// that isn't found only counts as not landed once its blockhash has
+    /// expired, so it can't land after being sent again.
+    pub async fn check_sent_step(&self, step: &crate::operations::OperationStep) -> Result<crate::operations::SentStep, String> {
+        use crate::operations::SentStep;
+
+        let Some(signature) = step.signature.as_deref() else {
+            return Ok(SentStep::NotLanded);
+        };
+        let landed = |status: Option<TransactionStatus>| match status {
+            Some(TransactionStatus::Confirmed | TransactionStatus::Finalized) => Some(SentStep::Landed(signature.to_string())),
+            Some(TransactionStatus::Failed(_)) => Some(SentStep::NotLanded),
+            _ => None,
+        };
+
+        let status = self.signature_status(signature, true).await.map_err(|e| e.to_string())?;
+        if let Some(outcome) = landed(status) {
+            return Ok(outcome);
+        }
+        let blockhash = step.blockhash.as_deref()
+            .and_then(|hash| Hash::from_str(hash).ok())
+            .ok_or("No blockhash was recorded for the step's transaction")?;
+        if self.is_blockhash_valid(&blockhash).await.map_err(|e| e.to_string())? {
+            return Err(format!("Transaction {} hasn't landed yet but still can; try again in a minute", signature));
+        }
+        // It may have landed between the two lookups
+        let status = self.signature_status(signature, true).await.map_err(|e| e.to_string())?;
+        Ok(landed(status).unwrap_or(SentStep::NotLanded))
+    }
+
+    async fn signature_status(&self, signature: &str, search_history: bool) -> Result<Option<TransactionStatus>, Box<dyn Error>> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getSignatureStatuses",
+            "params": [[signature], { "searchTransactionHistory": search_history }]
+        });
+
+        let json = self.rpc_call(&request).await?;
+
+        if let Some(error) = json.get("error") {
+            return Err(format!("RPC error getting signature status: {:?}", error).into());
+        }
+        Ok(TransactionStatus::from_signature_status(&json["result"]["value"][0]))
+    }
+
+    /// Whether a transaction using `blockhash` can still land
+    async fn is_blockhash_valid(&self, blockhash: &Hash) -> Result<bool, Box<dyn Error>> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "isBlockhashValid",
+            "params": [blockhash.to_string(), { "commitment": "processed" }]
+        });
+
+        let json = self.rpc_call(&request).await?;
+
+        if let Some(error) = json.get("error") {
+            return Err(format!("RPC error checking blockhash: {:?}", error).into());
+        }
+        json["result"]["value"]
+            .as_bool()
+            .ok_or_else(|| format!("Failed to check blockhash from response: {:?}", json).into())
+    }
+
+    /// Send the same signed bytes again. Leaders drop transactions under
+    /// load; re-sending can't double-spend since the signature is unchanged.
+    async fn rebroadcast(&self, transaction: &VersionedTransaction) -> Result<(), Box<dyn Error>> {
+        let encoded = bs58::encode(bincode::serialize(transaction)?).into_string();
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendTransaction",
+            "params": [
+                encoded,
+                {
+                    "encoding": "base58",
+                    "skipPreflight": true,
+                    "maxRetries": 0
+                }
+            ]
+        });
+
+        let json = self.rpc_call(&request).await?;
+        if let Some(error) = json.get("error") {
+            return Err(format!("Rebroadcast error: {:?}", error).into());
+        }
+        Ok(())
+    }
+
+    /// Track a submitted transaction until it finalizes, fails or expires.
+    ///
+    /// Listens for `signatureSubscribe` notifications (falling back to
+    /// polling when the RPC has no WebSocket), re-broadcasts the signed
+    /// transaction while its blockhash is still valid, and reports `Expired`
+    /// once it isn't. The receiver always holds the latest status; the
+    /// tracker stops when every receiver is dropped.
+    pub fn watch_transaction(&self, signature: &str) -> tokio::sync::watch::Receiver<TransactionStatus> {
+        let (sender, receiver) = tokio::sync::watch::channel(TransactionStatus::Submitted);
+        let client = TransactionClient::new(Some(&self.rpc_url));
+        let signature = signature.to_string();
+        tokio::spawn(async move {
+            client.run_status_tracker(&signature, sender).await;
+        });
+        receiver
+    }
+
+    async fn run_status_tracker(&self, signature: &str, sender: tokio::sync::watch::Sender<TransactionStatus>) {
+        let started = std::time::Instant::now();
+        // Only transactions sent through `send_transaction` can be re-sent
+        let was_tracked = crate::submission::tracked(signature).is_some();
+        let mut socket = match subscribe_signature(&self.rpc_url, signature).await {
+            Ok(socket) => Some(socket),
+            Err(e) => {
+                println!("⚠️ No signature subscription for {}, polling instead: {}", signature, e);
+                None
+            }
+        };
+        let mut subscriptions = HashMap::new();
+        let mut ticker = tokio::time::interval(TRACKER_TICK);
+        let mut current = TransactionStatus::Submitted;
+        let relaxed = crate::config::network::active().relaxed_confirmation();
+
+        loop {
+            let update = tokio::select! {
+                text = next_socket_text(&mut socket) => match text {
+                    Some(text) => parse_signature_message(&text, &mut subscriptions),
+                    None => {
+                        println!("⚠️ Signature subscription closed for {}, polling instead", signature);
+                        socket = None;
+                        None
+                    }
+                },
+                _ = ticker.tick() => self.status_tick(signature, &current, was_tracked).await,
+            };
+
+            if let Some(status) = update.filter(|s| s.rank() > current.rank()) {
+                println!("📡 Transaction {} is now {:?}", signature, status);
+                current = status;
+                sender.send_replace(current.clone());
+            }
+            if current.is_final() || (relaxed && current == TransactionStatus::Confirmed) {
+                // Expired transactions stay tracked so they can be re-signed
+                if was_tracked && current != TransactionStatus::Expired {
+                    crate::submission::cancel(signature);
+                }
+                break;
+            }
+            // Replaced by a fee bump; the replacement gets its own tracker
+            if was_tracked && current == TransactionStatus::Submitted && crate::submission::tracked(signature).is_none() {
+                break;
+            }
+            if sender.is_closed() || started.elapsed() >= TRACKER_MAX_DURATION {
+                break;
+            }
+        }
+    }
+
+    /// Poll once; while still pending, re-broadcast or detect expiry
+    async fn status_tick(&self, signature: &str, current: &TransactionStatus, was_tracked: bool) -> Option<TransactionStatus> {
+        let polled = self.get_transaction_status(signature).await.ok().flatten();
+        if polled.is_some() {
+            return polled;
+        }
+        if *current != TransactionStatus::Submitted || !was_tracked {
+            return None;
+        }
+        let submission = crate::submission::tracked(signature)?;
+
+        // A durable nonce never expires on its own
+        if !crate::submission::message_uses_durable_nonce(&submission.transaction.message) {
+            let blockhash = *submission.transaction.message.recent_blockhash();
+            let expired = matches!(self.is_blockhash_valid(&blockhash).await, Ok(false));
+            if expired {
+                // It may have landed in the last moment
+                let polled = self.get_transaction_status(signature).await.ok().flatten();
+                return Some(polled.unwrap_or(TransactionStatus::Expired));
+            }
+        }
+
+        if let Err(e) = self.rebroadcast(&submission.transaction).await {
+            println!("⚠️ Rebroadcast of {} failed: {}", signature, e);
+        }
+        None
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Offline outbox
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Where a queued transaction is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OutboxStatus {
+    /// Waiting for the network to come back
+    Queued,
+    Sent { signature: String },
+    /// The RPC answered but rejected it
+    Failed { error: String },
+    Cancelled,
+}
+
+/// A signed durable-nonce transaction waiting to be broadcast
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxItem {
+    /// First signature of the transaction; identifies the item
+    pub signature: String,
+    /// Base58 signed transaction
+    pub signed_tx: String,
+    pub description: String,
+    pub queued_at: u64,
+    #[serde(default)]
+    pub attempts: u32,
+    pub status: OutboxStatus,
+}
+
+impl OutboxItem {
+    pub fn is_pending(&self) -> bool {
+        self.status == OutboxStatus::Queued
+    }
+
+    pub fn status_label(&self) -> String {
+        match &self.status {
+            OutboxStatus::Queued if self.attempts == 0 => "⏸️ Queued until the network is back".to_string(),
+            OutboxStatus::Queued => format!("⏸️ Queued, {} attempt(s) so far", self.attempts),
+            OutboxStatus::Sent { signature } => format!("✅ Broadcast: {}", signature),
+            OutboxStatus::Failed { error } => format!("❌ Rejected: {}", error),
+            OutboxStatus::Cancelled => "🚫 Cancelled".to_string(),
+        }
+    }
+}
+
+/// Result of `send_or_queue`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SendOutcome {
+    /// Broadcast now; holds the signature
+    Sent(String),
+    /// Network unreachable; queued under this signature
+    Queued(String),
+}
+
+/// Whether an error from `send_transaction` means the RPC was never reached
+pub fn is_unreachable(error: &(dyn Error + 'static)) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .map(|e| e.is_connect() || e.is_timeout() || e.is_request())
+        .unwrap_or(false)
+}
+
+/// Check that `signed_tx` can wait in the outbox and return its signature.
+///
+/// Only durable-nonce transactions are accepted: one built on a recent
+/// blockhash would expire long before connectivity is likely to return.
+pub fn outbox_signature(signed_tx: &str) -> Result<String, String> {
+    let bytes = bs58::decode(signed_tx)
+        .into_vec()
+        .map_err(|e| format!("Invalid transaction encoding: {}", e))?;
+    let transaction: VersionedTransaction = bincode::deserialize(&bytes)
+        .map_err(|e| format!("Invalid transaction: {}", e))?;
+    if !crate::submission::message_uses_durable_nonce(&transaction.message) {
+        return Err("only durable-nonce transactions can wait for the network; this one would expire".to_string());
+    }
+    match transaction.signatures.first() {
+        Some(signature) if *signature != SolanaSignature::default() => Ok(signature.to_string()),
+        _ => Err("the transaction isn't signed".to_string()),
+    }
+}
+
+/// Put a signed durable-nonce transaction in the outbox
+pub fn queue_transaction(signed_tx: &str, description: &str) -> Result<OutboxItem, String> {
+    let item = OutboxItem {
+        signature: outbox_signature(signed_tx)?,
+        signed_tx: signed_tx.to_string(),
+        description: description.to_string(),
+        queued_at: chrono::Utc::now().timestamp() as u64,
+        attempts: 0,
+        status: OutboxStatus::Queued,
+    };
+    crate::storage::save_outbox_item(&item)?;
+    println!("📥 Queued {} for when the network is back", item.signature);
+    Ok(item)
+}
+
+impl TransactionClient {
+    /// Broadcast `signed_tx`, or queue it in the outbox when the RPC can't be
+    /// reached and the transaction uses a durable nonce
+    pub async fn send_or_queue(&self, signed_tx: &str, description: &str) -> Result<SendOutcome, Box<dyn Error>> {
+        let error = match self.send_transaction(signed_tx).await {
+            Ok(signature) => return Ok(SendOutcome::Sent(signature)),
+            Err(e) => e,
+        };
+        if !is_unreachable(error.as_ref()) {
+            return Err(error);
+        }
+        match queue_transaction(signed_tx, description) {
+            Ok(item) => Ok(SendOutcome::Queued(item.signature)),
+            Err(e) => Err(format!("Network unreachable, and {}", e).into()),
+        }
+    }
+
+    /// Broadcast everything waiting in the outbox. Stops at the first
+    /// unreachable error since the rest would fail the same way. Returns how
+    /// many were sent.
+    pub async fn flush_outbox(&self) -> usize {
+        let mut sent = 0;
+        for mut item in crate::storage::load_outbox().into_iter().filter(OutboxItem::is_pending) {
+            item.attempts += 1;
+            let (status, offline) = match self.send_transaction(&item.signed_tx).await {
+                Ok(signature) => (OutboxStatus::Sent { signature }, false),
+                Err(e) if is_unreachable(e.as_ref()) => (OutboxStatus::Queued, true),
+                Err(e) => (OutboxStatus::Failed { error: e.to_string() }, false),
+            };
+            if matches!(status, OutboxStatus::Sent { .. }) {
+                sent += 1;
+            }
+            item.status = status;
+            if let Err(e) = crate::storage::save_outbox_item(&item) {
+                println!("⚠️ Failed to update outbox item {}: {}", item.signature, e);
+            }
+            if offline {
+                break;
+            }
+        }
+        if sent > 0 {
+            println!("📤 Broadcast {} queued transaction(s)", sent);
+        }
+        sent
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Raw transaction broadcast
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// An externally signed transaction, checked before it is broadcast
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawTransactionCheck {
+    pub transaction: VersionedTransaction,
+    pub signers: Vec<crate::signing::partial::SignerStatus>,
+    pub uses_durable_nonce: bool,
+    /// `None` until checked against the network, or when the check failed
+    pub blockhash_valid: Option<bool>,
+}
+
+impl RawTransactionCheck {
+    pub fn fee_payer(&self) -> Option<Pubkey> {
+        self.transaction.message.static_account_keys().first().copied()
+    }
+
+    /// Reasons the transaction can't land; empty when it can be broadcast
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems: Vec<String> = self.signers
+            .iter()
+            .filter(|s| !s.signed)
+            .map(|s| format!("Missing or invalid signature for {}", s.pubkey))
+            .collect();
+        if self.blockhash_valid == Some(false) {
+            problems.push("The blockhash has expired; the transaction must be rebuilt and signed again".to_string());
+        }
+        problems
+    }
+
+    /// One line per instruction: program and number of accounts
+    pub fn instruction_summary(&self) -> Vec<String> {
+        crate::decoder::describe_transaction(&self.transaction)
+    }
+}
+
+/// Decode a pasted base58 or base64 transaction and verify its signatures
+pub fn inspect_raw_transaction(encoded: &str) -> Result<RawTransactionCheck, String> {
+    let transaction = crate::signing::partial::decode_transaction(encoded).map_err(|e| e.to_string())?;
+    transaction.sanitize().map_err(|e| format!("Malformed transaction: {}", e))?;
+    Ok(RawTransactionCheck {
+        signers: crate::signing::partial::signer_statuses(&transaction),
+        uses_durable_nonce: crate::submission::message_uses_durable_nonce(&transaction.message),
+        blockhash_valid: None,
+        transaction,
+    })
+}
+
+impl TransactionClient {
+    /// Decode, verify signatures, and check the blockhash is still recent.
+    /// Durable-nonce transactions don't expire, so their blockhash is skipped.
+    pub async fn check_raw_transaction(&self, encoded: &str) -> Result<RawTransactionCheck, String> {
+        let mut check = inspect_raw_transaction(encoded)?;
+        if check.uses_durable_nonce {
+            check.blockhash_valid = Some(true);
+        } else {
+            let blockhash = *check.transaction.message.recent_blockhash();
+            check.blockhash_valid = match self.is_blockhash_valid(&blockhash).await {
+                Ok(valid) => Some(valid),
+                Err(e) => {
+                    println!("⚠️ Couldn't check blockhash {}: {}", blockhash, e);
+                    None
+                }
+            };
+        }
+        Ok(check)
+    }
+
+    /// Broadcast a checked transaction through the usual sender
+    pub async fn broadcast_raw(&self, check: &RawTransactionCheck) -> Result<String, Box<dyn Error>> {
+        if let Some(problem) = check.problems().into_iter().next() {
+            return Err(problem.into());
+        }
+        let encoded = bs58::encode(bincode::serialize(&check.transaction)?).into_string();
+        self.send_transaction(&encoded).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_snapshot(mint: &Pubkey, owner: &Pubkey, amount: u64) -> AccountSnapshot {
+        let mut data = vec![0u8; TOKEN_ACCOUNT_LEN];
+        data[0..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        AccountSnapshot { lamports: 2_039_280, owner: TOKEN_PROGRAM_ID.to_string(), data }
+    }
+
+    fn system_snapshot(lamports: u64) -> AccountSnapshot {
+        AccountSnapshot { lamports, owner: "11111111111111111111111111111111".to_string(), data: vec![] }
+    }
+
+    #[test]
+    fn test_balance_diff_tracks_wallet_sol_and_tokens() {
+        let wallet = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let wallet_ata = Pubkey::new_unique();
+        let recipient_ata = Pubkey::new_unique();
+
+        let addresses = vec![wallet, wallet_ata, recipient_ata];
+        let pre = vec![
+            Some(system_snapshot(1_000_000_000)),
+            Some(token_snapshot(&mint, &wallet, 500)),
+            None,
+        ];
+        let post = vec![
+            Some(system_snapshot(997_955_720)),
+            Some(token_snapshot(&mint, &wallet, 200)),
+            Some(token_snapshot(&mint, &recipient, 300)),
+        ];
+
+        let (sol_change, tokens) = compute_balance_diff(&wallet, &addresses, &pre, &post);
+        assert_eq!(sol_change, -2_044_280);
+        assert_eq!(tokens, vec![(mint.to_string(), wallet_ata.to_string(), -300)]);
+    }
+
+    #[test]
+    fn test_closed_token_account_counts_as_zero() {
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let ata = Pubkey::new_unique();
+
+        let (_, tokens) = compute_balance_diff(
+            &wallet,
+            &[ata],
+            &[Some(token_snapshot(&mint, &wallet, 42))],
+            &[None],
+        );
+        assert_eq!(tokens, vec![(mint.to_string(), ata.to_string(), -42)]);
+    }
+
+    fn token_2022_mint_json(older_bps: u64, newer_bps: u64, newer_epoch: u64) -> Value {
+        json!({
+            "owner": TOKEN_2022_PROGRAM_ID,
+            "data": {
+                "parsed": {
+                    "type": "mint",
+                    "info": {
+                        "decimals": 6,
+                        "extensions": [
+                            { "extension": "metadataPointer", "state": {} },
+                            {
+                                "extension": "transferFeeConfig",
+                                "state": {
+                                    "olderTransferFee": { "epoch": 500, "maximumFee": 1_000_000, "transferFeeBasisPoints": older_bps },
+                                    "newerTransferFee": { "epoch": newer_epoch, "maximumFee": 5_000_000, "transferFeeBasisPoints": newer_bps },
+                                    "withheldAmount": 0
+                                }
+                            }
+                        ]
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_transfer_fee_rounds_up_and_caps() {
+        let fee = TransferFee { epoch: 0, maximum_fee: 5_000, basis_points: 50 };
+        assert_eq!(fee.calculate_fee(100_000), 500);
+        assert_eq!(fee.calculate_fee(1), 1);
+        assert_eq!(fee.calculate_fee(10_000_000), 5_000);
+        assert_eq!(TransferFee::default().calculate_fee(100_000), 0);
+    }
+
+    #[test]
+    fn test_parse_token_2022_mint_with_transfer_fee() {
+        let info = parse_mint_info(&token_2022_mint_json(100, 250, 600)).unwrap();
+        assert!(info.is_token_2022());
+        assert_eq!(info.decimals, 6);
+
+        let config = info.transfer_fee.unwrap();
+        assert_eq!(config.fee_for_epoch(599).basis_points, 100);
+        assert_eq!(config.fee_for_epoch(600).basis_points, 250);
+
+        // 10 tokens at 2.5% after the newer fee activates
+        let amount = info.to_units(10.0);
+        let quote = TokenTransferQuote {
+            amount_units: amount,
+            fee_units: config.fee_for_epoch(700).calculate_fee(amount),
+            decimals: info.decimals,
+        };
+        assert_eq!(quote.fee_units, 250_000);
+        assert_eq!(quote.received_units(), 9_750_000);
+        assert!((quote.ui_received() - 9.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_legacy_mint_and_reject_non_mint() {
+        let legacy = json!({
+            "owner": TOKEN_PROGRAM_ID,
+            "data": { "parsed": { "type": "mint", "info": { "decimals": 9 } } }
+        });
+        let info = parse_mint_info(&legacy).unwrap();
+        assert_eq!(info.program_id, spl_token::id());
+        assert_eq!(info.transfer_fee, None);
+
+        let system = json!({ "owner": "11111111111111111111111111111111", "data": ["", "base64"] });
+        assert!(parse_mint_info(&system).is_err());
+        assert!(parse_mint_info(&Value::Null).is_err());
+    }
+
+    #[test]
+    fn test_transfer_checked_per_program() {
+        let (owner, recipient, mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let legacy = MintInfo { program_id: spl_token::id(), decimals: 6, transfer_fee: None };
+        let source = legacy.associated_token_address(&owner, &mint);
+        let destination = legacy.associated_token_address(&recipient, &mint);
+
+        let expected = spl_token::instruction::transfer_checked(
+            &spl_token::id(), &source, &mint, &destination, &owner, &[], 1_500_000, 6,
+        ).unwrap();
+        assert_eq!(legacy.transfer_checked(&mint, &source, &destination, &owner, 1_500_000), expected);
+
+        // Token-2022 accounts live at different addresses and use the other program
+        let token_2022 = MintInfo {
+            program_id: Pubkey::from_str(TOKEN_2022_PROGRAM_ID).unwrap(),
+            ..legacy.clone()
+        };
+        let source_2022 = token_2022.associated_token_address(&owner, &mint);
+        assert_ne!(source_2022, source);
+        let ix = token_2022.transfer_checked(&mint, &source_2022, &destination, &owner, 1_500_000);
+        assert_eq!(ix.program_id, token_2022.program_id);
+        assert_eq!(ix.data, expected.data);
+    }
+
+    #[test]
+    fn test_compute_unit_limit_margin() {
+        assert_eq!(compute_unit_limit_for(450), 1_450);
+        assert_eq!(compute_unit_limit_for(100_000), 115_000);
+        assert_eq!(compute_unit_limit_for(1_390_000), MAX_COMPUTE_UNIT_LIMIT);
+    }
+
+    #[test]
+    fn test_compute_unit_limit_replaced_and_last() {
+        use solana_sdk::compute_budget::ComputeBudgetInstruction;
+        let payer = Pubkey::new_unique();
+        let instructions = vec![
+            timeout::build_timeout_instruction(100).unwrap(),
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            ComputeBudgetInstruction::set_compute_unit_price(5_000),
+            system_instruction::transfer(&payer, &Pubkey::new_unique(), 1),
+        ];
+        let sized = with_compute_unit_limit(&instructions, 1_450);
+        assert_eq!(sized.len(), 4);
+        assert_eq!(sized[0], instructions[0]);
+        assert_eq!(sized.iter().filter(|ix| is_compute_unit_limit(ix)).count(), 1);
+        assert_eq!(sized[3], ComputeBudgetInstruction::set_compute_unit_limit(1_450));
+    }
+
+    #[test]
+    fn test_builder_limits_include_send_overhead() {
+        let payer = Pubkey::new_unique();
+        let mut builder = TransactionBuilder::new(payer);
+        builder.add_instruction(system_instruction::transfer(&payer, &Pubkey::new_unique(), 1));
+        let limits = builder.limits();
+        // payer, recipient, system, timeout program, clock sysvar, compute budget
+        assert_eq!(limits.accounts, 6);
+        assert!(limits.check().is_ok());
+
+        for _ in 0..40 {
+            builder.add_instruction(system_instruction::transfer(&payer, &Pubkey::new_unique(), 1));
+        }
+        assert!(builder.limits().check().unwrap_err().contains("byte limit"));
+    }
+
+    #[test]
+    fn test_websocket_url() {
+        assert_eq!(websocket_url("https://api.mainnet-beta.solana.com"), "wss://api.mainnet-beta.solana.com");
+        assert_eq!(websocket_url("http://127.0.0.1:8899"), "ws://127.0.0.1:8900");
+        assert_eq!(websocket_url("http://localhost:8899/rpc"), "ws://localhost:8900/rpc");
+    }
+
+    #[test]
+    fn test_signature_notifications() {
+        let mut subscriptions = HashMap::new();
+        assert_eq!(parse_signature_message(r#"{"jsonrpc":"2.0","result":41,"id":1}"#, &mut subscriptions), None);
+        assert_eq!(parse_signature_message(r#"{"jsonrpc":"2.0","result":42,"id":2}"#, &mut subscriptions), None);
+
+        let confirmed = r#"{"jsonrpc":"2.0","method":"signatureNotification","params":{"result":{"context":{"slot":5},"value":{"err":null}},"subscription":41}}"#;
+        assert_eq!(parse_signature_message(confirmed, &mut subscriptions), Some(TransactionStatus::Confirmed));
+
+        let failed = r#"{"jsonrpc":"2.0","method":"signatureNotification","params":{"result":{"context":{"slot":6},"value":{"err":{"InstructionError":[0,"Custom"]}}},"subscription":42}}"#;
+        assert!(matches!(parse_signature_message(failed, &mut subscriptions), Some(TransactionStatus::Failed(_))));
+
+        // Unknown subscriptions are ignored
+        let other = confirmed.replace("41", "7");
+        assert_eq!(parse_signature_message(&other, &mut subscriptions), None);
+    }
+
+    #[test]
+    fn test_status_from_signature_statuses() {
+        assert_eq!(TransactionStatus::from_signature_status(&Value::Null), None);
+        let processed = json!({ "err": null, "confirmationStatus": "processed" });
+        assert_eq!(TransactionStatus::from_signature_status(&processed), None);
+        let finalized = json!({ "err": null, "confirmationStatus": "finalized" });
+        assert_eq!(TransactionStatus::from_signature_status(&finalized), Some(TransactionStatus::Finalized));
+        assert!(TransactionStatus::Finalized.is_final());
+        assert!(!TransactionStatus::Confirmed.is_final());
+    }
+
+    #[test]
+    fn test_only_signed_nonce_transactions_can_wait() {
+        let payer = solana_sdk::signature::Keypair::new();
+        let payer_pubkey = solana_sdk::signer::Signer::pubkey(&payer);
+        let transfer = system_instruction::transfer(&payer_pubkey, &Pubkey::new_unique(), 1_000);
+        let encode = |tx: &VersionedTransaction| bs58::encode(bincode::serialize(tx).unwrap()).into_string();
+
+        let nonce_message = Message::new_with_blockhash(
+            &[system_instruction::advance_nonce_account(&Pubkey::new_unique(), &payer_pubkey), transfer.clone()],
+            Some(&payer_pubkey),
+            &Hash::new_unique(),
+        );
+        let unsigned = VersionedTransaction {
+            signatures: vec![SolanaSignature::default()],
+            message: VersionedMessage::Legacy(nonce_message.clone()),
+        };
+        assert!(outbox_signature(&encode(&unsigned)).is_err());
+
+        let signed = VersionedTransaction::try_new(VersionedMessage::Legacy(nonce_message), &[&payer]).unwrap();
+        assert_eq!(outbox_signature(&encode(&signed)), Ok(signed.signatures[0].to_string()));
+
+        let blockhash_message = Message::new_with_blockhash(&[transfer], Some(&payer_pubkey), &Hash::new_unique());
+        let expiring = VersionedTransaction::try_new(VersionedMessage::Legacy(blockhash_message), &[&payer]).unwrap();
+        assert!(outbox_signature(&encode(&expiring)).is_err());
+    }
+
+    #[test]
+    fn test_inspect_raw_transaction() {
+        let payer = solana_sdk::signature::Keypair::new();
+        let payer_pubkey = solana_sdk::signer::Signer::pubkey(&payer);
+        let transfer = system_instruction::transfer(&payer_pubkey, &Pubkey::new_unique(), 1_000);
+        let message = Message::new_with_blockhash(&[transfer], Some(&payer_pubkey), &Hash::new_unique());
+
+        let signed = VersionedTransaction::try_new(VersionedMessage::Legacy(message.clone()), &[&payer]).unwrap();
+        let check = inspect_raw_transaction(&base64::encode(bincode::serialize(&signed).unwrap())).unwrap();
+        assert!(check.problems().is_empty());
+        assert!(!check.uses_durable_nonce);
+        assert_eq!(check.fee_payer(), Some(payer_pubkey));
+        assert_eq!(check.instruction_summary().len(), 1);
+
+        let expired = RawTransactionCheck { blockhash_valid: Some(false), ..check };
+        assert_eq!(expired.problems().len(), 1);
+
+        let unsigned = VersionedTransaction {
+            signatures: vec![SolanaSignature::default()],
+            message: VersionedMessage::Legacy(message),
+        };
+        let check = inspect_raw_transaction(&bs58::encode(bincode::serialize(&unsigned).unwrap()).into_string()).unwrap();
+        assert_eq!(check.problems().len(), 1);
+
+        assert!(inspect_raw_transaction("not a transaction").is_err());
+    }
+
+    #[test]
+    fn test_pack_instruction_groups() {
+        let payer = Pubkey::new_unique();
+        let groups: Vec<Vec<Instruction>> = (0..40)
+            .map(|_| {
+                let recipient = Pubkey::new_unique();
+                vec![
+                    system_instruction::transfer(&payer, &recipient, 1),
+                    system_instruction::transfer(&payer, &recipient, 2),
+                ]
+            })
+            .collect();
+        let counted = pack_counted_groups(&payer, groups.clone()).unwrap();
+        assert_eq!(counted.iter().map(|(_, count)| count).sum::<usize>(), 40);
+        assert!(counted.iter().all(|(b, count)| b.instructions().len() == count * 2));
+        let batches = pack_instruction_groups(&payer, groups).unwrap();
+        assert!(batches.len() > 1);
+        assert!(batches.iter().all(|b| b.limits().check().is_ok() && b.instructions().len() % 2 == 0));
+        assert_eq!(batches.iter().map(|b| b.instructions().len()).sum::<usize>(), 80);
+
+        let few = vec![vec![system_instruction::transfer(&payer, &Pubkey::new_unique(), 1)]; 3];
+        assert_eq!(pack_instruction_groups(&payer, few).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_recipient_transfer_json_round_trip() {
+        let token = RecipientTransfer { recipient: Pubkey::new_unique(), mint: Some(Pubkey::new_unique()), amount: 1.5 };
+        let sol = RecipientTransfer { recipient: Pubkey::new_unique(), mint: None, amount: 0.25 };
+        assert_eq!(RecipientTransfer::from_json(&token.to_json()), Some(token));
+        assert_eq!(RecipientTransfer::from_json(&sol.to_json()), Some(sol));
+        assert_eq!(RecipientTransfer::from_json(&json!({ "recipient": "nope", "amount": 1.0 })), None);
+    }
+
+    #[test]
+    fn test_fee_breakdown() {
+        use solana_sdk::compute_budget::ComputeBudgetInstruction;
+
+        let payer = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let existing_owner = Pubkey::new_unique();
+        let existing_ata = get_associated_token_address_with_program_id(&existing_owner, &mint, &spl_token::id());
+        let instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(100_000),
+            ComputeBudgetInstruction::set_compute_unit_price(10_000),
+            create_associated_token_account(&payer, &Pubkey::new_unique(), &mint, &spl_token::id()),
+            create_associated_token_account_idempotent(&payer, &existing_owner, &mint, &spl_token::id()),
+            system_instruction::transfer(&payer, &Pubkey::from_str(crate::tips::JITO_TIP_ACCOUNT).unwrap(), 50_000),
+            // Swap APIs may tip any of Jito's tip accounts
+            system_instruction::transfer(&payer, &Pubkey::from_str(crate::tips::JITO_TIP_ACCOUNTS[0]).unwrap(), 25_000),
+            system_instruction::transfer(&payer, &Pubkey::from_str(crate::tips::JULES_TIP_ACCOUNT).unwrap(), 100_000),
+        ];
+        let message = VersionedMessage::Legacy(Message::new(&instructions, Some(&payer)));
+        let fees = fee_breakdown(&message, |account| *account == existing_ata);
+
+        assert_eq!(fees.base_fee, 5_000);
+        assert_eq!(fees.priority_fee, 1_000);
+        assert_eq!(fees.jito_tip, 75_000);
+        assert_eq!(fees.jules_tip, 100_000);
+        assert_eq!(fees.atas_created, 1);
+        assert_eq!(fees.total(), 5_000 + 1_000 + 75_000 + 100_000 + TOKEN_ACCOUNT_RENT_LAMPORTS);
+        assert_eq!(fees.lines().len(), 6);
+    }
+
+    #[test]
+    fn test_balance_diff_resolves_lookup_tables() {
+        use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+        use solana_sdk::message::v0;
+
+        let wallet = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let wallet_ata = Pubkey::new_unique();
+        let recipient_ata = Pubkey::new_unique();
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![recipient_ata, mint, wallet_ata],
+        };
+        let transfer = spl_token::instruction::transfer_checked(
+            &spl_token::id(), &wallet_ata, &mint, &recipient_ata, &wallet, &[], 300, 6,
+        ).unwrap();
+        let message = VersionedMessage::V0(
+            v0::Message::try_compile(&wallet, &[transfer], &[table.clone()], Hash::default()).unwrap(),
+        );
+        assert!(!message.static_account_keys().contains(&wallet_ata));
+
+        let mut table_data = vec![0u8; LOOKUP_TABLE_META_SIZE];
+        for address in &table.addresses {
+            table_data.extend_from_slice(address.as_ref());
+        }
+        let tables = HashMap::from([(table.key, lookup_table_addresses(&table_data))]);
+        let addresses = loaded_account_keys(&message, &tables).unwrap();
+
+        // Instruction indexes point at the same accounts the runtime loads
+        let ix = &message.instructions()[0];
+        let accounts: Vec<Pubkey> = ix.accounts.iter().map(|&i| addresses[i as usize]).collect();
+        assert_eq!(accounts, vec![wallet_ata, mint, recipient_ata, wallet]);
+
+        let pre: Vec<Option<AccountSnapshot>> = addresses.iter().map(|address| match address {
+            a if *a == wallet => Some(system_snapshot(1_000_000_000)),
+            a if *a == wallet_ata => Some(token_snapshot(&mint, &wallet, 500)),
+            a if *a == recipient_ata => Some(token_snapshot(&mint, &recipient, 0)),
+            _ => None,
+        }).collect();
+        let post: Vec<Option<AccountSnapshot>> = addresses.iter().map(|address| match address {
+            a if *a == wallet => Some(system_snapshot(999_995_000)),
+            a if *a == wallet_ata => Some(token_snapshot(&mint, &wallet, 200)),
+            a if *a == recipient_ata => Some(token_snapshot(&mint, &recipient, 300)),
+            _ => None,
+        }).collect();
+        let (sol_change, tokens) = compute_balance_diff(&wallet, &addresses, &pre, &post);
+        assert_eq!(sol_change, -5_000);
+        assert_eq!(tokens, vec![(mint.to_string(), wallet_ata.to_string(), -300)]);
+
+        assert!(loaded_account_keys(&message, &HashMap::new()).is_err());
+        let short = HashMap::from([(table.key, vec![recipient_ata])]);
+        assert!(loaded_account_keys(&message, &short).is_err());
+    }
+}
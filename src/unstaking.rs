@@ -17,6 +17,7 @@ use crate::signing::{TransactionSigner, software::SoftwareSigner, hardware::Hard
 use crate::storage::get_current_jito_settings;
 use crate::transaction::TransactionClient;
 use crate::staking::{DetailedStakeAccount, StakeAccountState, StakingError};
+use crate::liquid_staking::LiquidStakingClient;
 use std::sync::Arc;
 use std::str::FromStr;
 use std::error::Error;
@@ -166,14 +167,10 @@ fn add_jito_tips(
     from_pubkey: &Pubkey,
     instructions: &mut Vec<Instruction>,
 ) -> Result<(), Box<dyn Error>> {
-    let jito_address1 = Pubkey::from_str("juLesoSmdTcRtzjCzYzRoHrnF8GhVu6KCV7uxq7nJGp")?;
-    let jito_address2 = Pubkey::from_str("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL")?;
-
-    let tip1 = system_instruction::transfer(from_pubkey, &jito_address1, 100_000);
-    let tip2 = system_instruction::transfer(from_pubkey, &jito_address2, 100_000);
-
-    instructions.push(tip1);
-    instructions.push(tip2);
+    instructions.extend(crate::tips::tip_instructions(
+        from_pubkey,
+        Some(crate::tips::DEFAULT_JITO_TIP_LAMPORTS),
+    )?);
 
     println!("Added Jito tip instructions");
     Ok(())
@@ -238,6 +235,9 @@ pub async fn instant_unstake_stake_account(
     let recent_blockhash = transaction_client.get_recent_blockhash().await
         .map_err(|e| StakingError::RpcError(format!("Failed to get blockhash: {}", e)))?;
 
+    // Replaces the fallback limit above with one sized from simulation
+    transaction_client.size_compute_budget(&mut instructions, &user_pubkey).await;
+
     // Create transaction message
     let mut message = Message::new(&instructions, Some(&user_pubkey));
     message.recent_blockhash = recent_blockhash;
@@ -386,6 +386,9 @@ pub async fn normal_unstake_stake_account(
     let recent_blockhash = transaction_client.get_recent_blockhash().await
         .map_err(|e| StakingError::RpcError(format!("Failed to get blockhash: {}", e)))?;
 
+    // Replaces the fallback limit above with one sized from simulation
+    transaction_client.size_compute_budget(&mut instructions, &user_pubkey).await;
+
     // Create transaction message
     let mut message = Message::new(&instructions, Some(&user_pubkey));
     message.recent_blockhash = recent_blockhash;
@@ -525,6 +528,11 @@ pub async fn partial_unstake_stake_account(
     
     println!("  Current staked: {:.6} SOL", current_staked as f64 / 1_000_000_000.0);
     println!("  Will remain: {:.6} SOL", remaining_balance as f64 / 1_000_000_000.0);
+
+    // Nothing would stay staked, so there's nothing to split
+    if remaining_balance == 0 {
+        return normal_unstake_stake_account(stake_account, wallet_info, hardware_wallet, rpc_url).await;
+    }
     
     // Create transaction client
     let transaction_client = TransactionClient::new(rpc_url);
@@ -601,6 +609,9 @@ pub async fn partial_unstake_stake_account(
     let recent_blockhash = transaction_client.get_recent_blockhash().await
         .map_err(|e| StakingError::RpcError(format!("Failed to get blockhash: {}", e)))?;
 
+    // Replaces the fallback limit above with one sized from simulation
+    transaction_client.size_compute_budget(&mut instructions, &user_pubkey).await;
+
     // Create transaction message
     let mut message = Message::new(&instructions, Some(&user_pubkey));
     message.recent_blockhash = recent_blockhash;
@@ -669,6 +680,133 @@ pub fn can_partial_unstake(stake_account: &DetailedStakeAccount) -> bool {
     stake_account.state == StakeAccountState::Delegated && available > 20_000_000 // > 0.02 SOL
 }
 
+/// Least SOL, in lamports, either side of a split may hold
+const MIN_SPLIT_LAMPORTS: u64 = 10_000_000;
+
+/// One transaction of an unstake by amount
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnstakeStep {
+    /// Deactivate the whole account
+    Deactivate(DetailedStakeAccount),
+    /// Split this many lamports off the account and deactivate them
+    Split(DetailedStakeAccount, u64),
+}
+
+impl UnstakeStep {
+    pub fn account(&self) -> &DetailedStakeAccount {
+        match self {
+            UnstakeStep::Deactivate(account) | UnstakeStep::Split(account, _) => account,
+        }
+    }
+
+    pub fn lamports(&self) -> u64 {
+        match self {
+            UnstakeStep::Deactivate(account) => staked_lamports(account),
+            UnstakeStep::Split(_, lamports) => *lamports,
+        }
+    }
+}
+
+fn staked_lamports(account: &DetailedStakeAccount) -> u64 {
+    account.balance.saturating_sub(account.rent_exempt_reserve)
+}
+
+/// The fewest transactions that unstake `lamports` from `accounts`: a whole
+/// account that matches, a split off one account that covers it, or whole
+/// accounts largest first with the rest split off another
+pub fn plan_unstake(accounts: &[DetailedStakeAccount], lamports: u64) -> Result<Vec<UnstakeStep>, StakingError> {
+    let active: Vec<&DetailedStakeAccount> = accounts.iter().filter(|a| can_normal_unstake(a)).collect();
+    let total: u64 = active.iter().map(|a| staked_lamports(a)).sum();
+    if lamports == 0 {
+        return Err(StakingError::InvalidAmount("Amount must be greater than 0".to_string()));
+    }
+    if lamports > total {
+        return Err(StakingError::InsufficientBalance(format!(
+            "Only {:.6} SOL is actively staked",
+            total as f64 / 1_000_000_000.0
+        )));
+    }
+
+    // Split `wanted` off the smallest account that keeps enough staked
+    let split_from = |wanted: u64, candidates: &[&DetailedStakeAccount]| {
+        candidates
+            .iter()
+            .filter(|a| wanted >= MIN_SPLIT_LAMPORTS && staked_lamports(a) >= wanted + MIN_SPLIT_LAMPORTS)
+            .min_by_key(|a| staked_lamports(a))
+            .map(|a| UnstakeStep::Split((*a).clone(), wanted))
+    };
+
+    if let Some(exact) = active.iter().find(|a| staked_lamports(a) == lamports) {
+        return Ok(vec![UnstakeStep::Deactivate((*exact).clone())]);
+    }
+    if let Some(split) = split_from(lamports, &active) {
+        return Ok(vec![split]);
+    }
+
+    let mut by_size = active;
+    by_size.sort_by_key(|a| std::cmp::Reverse(staked_lamports(a)));
+    let mut steps = Vec::new();
+    let mut remaining = lamports;
+    let mut unused = Vec::new();
+    for account in by_size {
+        if remaining > 0 && staked_lamports(account) <= remaining {
+            remaining -= staked_lamports(account);
+            steps.push(UnstakeStep::Deactivate(account.clone()));
+        } else {
+            unused.push(account);
+        }
+    }
+    if remaining > 0 {
+        let split = split_from(remaining, &unused).ok_or_else(|| {
+            StakingError::InvalidAmount(format!(
+                "Can't split off {:.6} SOL and leave at least 0.01 SOL on both sides, try a different amount",
+                remaining as f64 / 1_000_000_000.0
+            ))
+        })?;
+        steps.push(split);
+    }
+    Ok(steps)
+}
+
+/// Unstake `amount_sol` across the wallet's active stake accounts, splitting
+/// one when no whole accounts add up to it. Returns a signature per step.
+pub async fn unstake_amount(
+    accounts: &[DetailedStakeAccount],
+    amount_sol: f64,
+    wallet_info: Option<&WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    rpc_url: Option<&str>,
+) -> Result<Vec<String>, StakingError> {
+    let steps = plan_unstake(accounts, (amount_sol * 1_000_000_000.0).round() as u64)?;
+    println!("UNSTAKE AMOUNT: {:.6} SOL in {} step(s)", amount_sol, steps.len());
+
+    let mut signatures = Vec::new();
+    for step in &steps {
+        let signature = match step {
+            UnstakeStep::Deactivate(account) => {
+                normal_unstake_stake_account(account, wallet_info, hardware_wallet.clone(), rpc_url).await
+            }
+            UnstakeStep::Split(account, lamports) => {
+                let sol = *lamports as f64 / 1_000_000_000.0;
+                partial_unstake_stake_account(account, sol, wallet_info, hardware_wallet.clone(), rpc_url).await
+            }
+        };
+        match signature {
+            Ok(signature) => signatures.push(signature),
+            Err(e) if signatures.is_empty() => return Err(e),
+            Err(e) => {
+                return Err(StakingError::TransactionFailed(format!(
+                    "Unstaked in {} of {} transactions before one failed: {}",
+                    signatures.len(),
+                    steps.len(),
+                    e
+                )))
+            }
+        }
+    }
+    Ok(signatures)
+}
+
 /// Build a withdraw stake instruction
 fn build_withdraw_instruction(
     stake_account: &Pubkey,
@@ -719,10 +857,11 @@ fn build_withdraw_instruction(
 /// 
 /// This function:
 /// 1. Withdraws all SOL (including rent reserve) from an inactive stake account
-/// 2. Transfers the SOL to the user's wallet
+/// 2. Transfers the SOL to `destination`, or the user's wallet when `None`
 /// 3. Destroys the stake account (balance becomes 0)
 pub async fn withdraw_stake_account(
     stake_account: &DetailedStakeAccount,
+    destination: Option<&Pubkey>,
     wallet_info: Option<&WalletInfo>,
     hardware_wallet: Option<Arc<HardwareWallet>>,
     rpc_url: Option<&str>,
@@ -760,16 +899,17 @@ pub async fn withdraw_stake_account(
         return Err(StakingError::WalletError("No wallet provided".to_string()));
     };
 
-    // Get user pubkey (this will be both the destination and withdraw authority)
+    // Get user pubkey (the withdraw authority, and the default destination)
     let user_pubkey_str = signer.get_public_key().await
         .map_err(|e| StakingError::WalletError(format!("Failed to get public key: {}", e)))?;
     let user_pubkey = Pubkey::from_str(&user_pubkey_str)
         .map_err(|_| StakingError::WalletError("Invalid wallet address".to_string()))?;
+    let destination = destination.copied().unwrap_or(user_pubkey);
 
     // Build withdraw instruction
     let withdraw_ix = build_withdraw_instruction(
         &stake_account.pubkey,
-        &user_pubkey,  // Destination = user's wallet
+        &destination,
         &user_pubkey,  // Withdraw authority = user
         withdraw_amount, // Withdraw full balance
     )?;
@@ -797,6 +937,9 @@ pub async fn withdraw_stake_account(
     let recent_blockhash = transaction_client.get_recent_blockhash().await
         .map_err(|e| StakingError::RpcError(format!("Failed to get blockhash: {}", e)))?;
 
+    // Replaces the fallback limit above with one sized from simulation
+    transaction_client.size_compute_budget(&mut instructions, &user_pubkey).await;
+
     // Create transaction message
     let mut message = Message::new(&instructions, Some(&user_pubkey));
     message.recent_blockhash = recent_blockhash;
@@ -845,4 +988,191 @@ pub async fn withdraw_stake_account(
 pub fn can_withdraw(stake_account: &DetailedStakeAccount) -> bool {
     // Can only withdraw from inactive (uninitialized) stake accounts with a balance
     stake_account.state == StakeAccountState::Uninitialized && stake_account.balance > 0
-}
\ No newline at end of file
+}
+// Sanctum router: sell active stake for SOL right away
+//
+// The stake account is deposited into a listed stake pool that holds stake
+// with the same validator, and the pool tokens it mints are sold to SOL
+// through Sanctum's router. Both steps settle immediately, at a small
+// discount to the SOL the stake returns after the deactivation cooldown.
+
+/// What selling a stake account through Sanctum's router pays right now
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstantSaleQuote {
+    /// Liquid staking token the stake is converted through
+    pub via: &'static str,
+    /// SOL the stake returns after deactivating and waiting
+    pub fair_lamports: u64,
+    /// SOL paid now
+    pub out_lamports: u64,
+}
+
+impl InstantSaleQuote {
+    pub fn discount_percent(&self) -> f64 {
+        if self.fair_lamports == 0 {
+            return 0.0;
+        }
+        (self.fair_lamports.saturating_sub(self.out_lamports)) as f64 / self.fair_lamports as f64 * 100.0
+    }
+}
+
+/// Quote selling `stake_account`, delegated to `voter`, through Sanctum's router
+pub async fn quote_stake_account_sale(
+    stake_account: &DetailedStakeAccount,
+    voter: &str,
+    rpc_url: Option<&str>,
+) -> Result<InstantSaleQuote, StakingError> {
+    if stake_account.state != StakeAccountState::Delegated {
+        return Err(StakingError::InvalidAmount("Only active stake can be sold".to_string()));
+    }
+    let voter = Pubkey::from_str(voter).map_err(|_| StakingError::InvalidValidator(voter.to_string()))?;
+    let client = LiquidStakingClient::new(rpc_url);
+    let (token, pool) = client
+        .pool_for_validator(&voter)
+        .await
+        .map_err(|e| StakingError::RpcError(e.to_string()))?
+        .ok_or_else(|| no_pool_for(&voter))?;
+
+    let pool_tokens = pool.state.stake_deposit_quote(stake_account.balance);
+    let (out_lamports, _) = client
+        .quote_sale(token.mint, pool_tokens)
+        .await
+        .map_err(|e| StakingError::RpcError(e.to_string()))?;
+    Ok(InstantSaleQuote { via: token.symbol, fair_lamports: stake_account.balance, out_lamports })
+}
+
+/// Sell `stake_account`, delegated to `voter`, for SOL through Sanctum's
+/// router. Returns the signature of the final sale.
+pub async fn sell_stake_account_instantly(
+    stake_account: &DetailedStakeAccount,
+    voter: &str,
+    wallet_info: Option<&WalletInfo>,
+    hardware_wallet: Option<Arc<HardwareWallet>>,
+    rpc_url: Option<&str>,
+) -> Result<String, StakingError> {
+    println!("INSTANT SALE: Starting for stake account: {}", stake_account.pubkey);
+
+    if stake_account.state != StakeAccountState::Delegated {
+        return Err(StakingError::InvalidAmount("Only active stake can be sold".to_string()));
+    }
+    let voter = Pubkey::from_str(voter).map_err(|_| StakingError::InvalidValidator(voter.to_string()))?;
+
+    let signer = crate::signing::active_wallet_signer(hardware_wallet, wallet_info.cloned()).map_err(StakingError::WalletError)?;
+    let user_pubkey_str = signer.get_public_key().await
+        .map_err(|e| StakingError::WalletError(format!("Failed to get public key: {}", e)))?;
+    let user_pubkey = Pubkey::from_str(&user_pubkey_str)
+        .map_err(|_| StakingError::WalletError("Invalid wallet address".to_string()))?;
+
+    let client = LiquidStakingClient::new(rpc_url);
+    let rpc_error = |e: Box<dyn Error>| StakingError::RpcError(e.to_string());
+    let (token, pool) = client
+        .pool_for_validator(&voter)
+        .await
+        .map_err(rpc_error)?
+        .ok_or_else(|| no_pool_for(&voter))?;
+    let before = client.pool_token_balance(&pool, &user_pubkey).await.map_err(rpc_error)?;
+
+    // Step 1: hand the stake to the pool for its tokens
+    let deposit = client
+        .deposit_stake_account(&pool, signer.as_ref(), &stake_account.pubkey, &voter)
+        .await
+        .map_err(|e| StakingError::TransactionFailed(format!("Stake deposit failed: {}", e)))?;
+    println!("Deposited stake into the {} pool: {}", token.symbol, deposit);
+
+    let transaction_client = TransactionClient::new(rpc_url);
+    let mut confirmed = false;
+    for _ in 0..SALE_CONFIRM_ATTEMPTS {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        if let Ok(true) = transaction_client.confirm_transaction(&deposit).await {
+            confirmed = true;
+            break;
+        }
+    }
+    if !confirmed {
+        return Err(StakingError::TransactionFailed(format!(
+            "The stake deposit {} didn't confirm in time; sell the {} from the liquid staking view once it lands",
+            deposit, token.symbol
+        )));
+    }
+
+    // Step 2: sell the tokens it minted
+    let after = client.pool_token_balance(&pool, &user_pubkey).await.map_err(rpc_error)?;
+    let received = after.saturating_sub(before);
+    if received == 0 {
+        return Err(StakingError::TransactionFailed(format!("The {} pool minted no tokens for the stake", token.symbol)));
+    }
+    let (out_lamports, quote) = client.quote_sale(token.mint, received).await.map_err(rpc_error)?;
+    println!("Selling {} {} base units for {} lamports", received, token.symbol, out_lamports);
+    client
+        .sell_quoted(quote, signer.as_ref())
+        .await
+        .map_err(|e| StakingError::TransactionFailed(format!("Sale of {} failed, the tokens stay in your wallet: {}", token.symbol, e)))
+}
+
+/// Seconds to wait for the stake deposit before selling
+const SALE_CONFIRM_ATTEMPTS: usize = 30;
+
+fn no_pool_for(voter: &Pubkey) -> StakingError {
+    StakingError::InvalidValidator(format!(
+        "No listed stake pool takes stake from validator {}; deactivate it instead",
+        voter
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(sol: u64) -> DetailedStakeAccount {
+        DetailedStakeAccount {
+            pubkey: Pubkey::new_unique(),
+            balance: sol * 1_000_000_000 + 2_282_880,
+            rent_exempt_reserve: 2_282_880,
+            state: StakeAccountState::Delegated,
+            validator_name: String::new(),
+            activation_epoch: Some(1),
+            deactivation_epoch: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_unstake() {
+        const SOL: u64 = 1_000_000_000;
+        let accounts = vec![account(5), account(3), account(2)];
+
+        // A whole account that matches
+        let steps = plan_unstake(&accounts, 3 * SOL).unwrap();
+        assert_eq!(steps, vec![UnstakeStep::Deactivate(accounts[1].clone())]);
+
+        // One split off the smallest account that covers it
+        let steps = plan_unstake(&accounts, 4 * SOL).unwrap();
+        assert_eq!(steps, vec![UnstakeStep::Split(accounts[0].clone(), 4 * SOL)]);
+
+        // Whole accounts largest first, the rest split off another
+        let steps = plan_unstake(&accounts, 9 * SOL).unwrap();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0], UnstakeStep::Deactivate(accounts[0].clone()));
+        assert_eq!(steps[1], UnstakeStep::Deactivate(accounts[1].clone()));
+        assert_eq!(steps[2], UnstakeStep::Split(accounts[2].clone(), SOL));
+        assert_eq!(steps.iter().map(UnstakeStep::lamports).sum::<u64>(), 9 * SOL);
+
+        assert!(plan_unstake(&accounts, 11 * SOL).is_err());
+        assert!(plan_unstake(&accounts, 0).is_err());
+    }
+
+    #[test]
+    fn test_instant_sale_deposit_passes_firewall() {
+        use crate::liquid_staking::StakePoolInfo;
+
+        // The deposit the sale sends first, with the tip `send` adds
+        let owner = Pubkey::new_unique();
+        let stake_account = account(5);
+        let pool = StakePoolInfo::test_pool();
+        let mut builder = LiquidStakingClient::stake_deposit_builder(&pool, &owner, &stake_account.pubkey, &Pubkey::new_unique(), 0);
+        for instruction in crate::tips::tip_instructions(&owner, Some(crate::tips::DEFAULT_JITO_TIP_LAMPORTS)).unwrap() {
+            builder.add_instruction(instruction);
+        }
+        let message = VersionedMessage::Legacy(Message::new(builder.instructions(), Some(&owner)));
+        crate::signing::firewall::enforce(&message.serialize(), &owner.to_string()).unwrap();
+    }
+}
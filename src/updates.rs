@@ -0,0 +1,272 @@
+// src/updates.rs
+//! In-app update checks against a signed release manifest
+//!
+//! The release server publishes `{ "manifest": "<json>", "signature": "<base58>" }`
+//! where the signature is an ed25519 signature over the manifest text by the
+//! release key. Nothing in a manifest is trusted before that signature checks
+//! out. Each artifact lists its SHA-256 and its own signature, and a download
+//! must match both before it is installed (desktop) or offered.
+//!
+//! The release key and manifest URL are baked in at build time through
+//! `UNRUGGABLE_RELEASE_KEY` and `UNRUGGABLE_UPDATE_URL`; builds without a
+//! release key never report updates.
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::time::Duration;
+
+const DEFAULT_MANIFEST_URL: &str = "https://github.com/hogyzen12/unruggable-app/releases/latest/download/manifest.json";
+
+/// Version of the running build
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+fn release_key() -> Option<&'static str> {
+    option_env!("UNRUGGABLE_RELEASE_KEY")
+}
+
+fn manifest_url() -> &'static str {
+    option_env!("UNRUGGABLE_UPDATE_URL").unwrap_or(DEFAULT_MANIFEST_URL)
+}
+
+/// `{os}-{arch}` of the running build, e.g. "macos-aarch64"
+pub fn current_platform() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+/// Whether this build can replace its own binary
+pub fn can_self_update() -> bool {
+    cfg!(all(not(target_arch = "wasm32"), not(target_os = "android"), not(target_os = "ios")))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReleaseArtifact {
+    /// `{os}-{arch}` this build is for
+    pub platform: String,
+    pub url: String,
+    /// Hex SHA-256 of the file
+    pub sha256: String,
+    /// Base58 ed25519 signature of the file by the release key
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReleaseManifest {
+    pub version: String,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub changelog: Vec<String>,
+    /// Older builds than this are known to be vulnerable
+    #[serde(default)]
+    pub minimum_safe_version: Option<String>,
+    #[serde(default)]
+    pub artifacts: Vec<ReleaseArtifact>,
+}
+
+impl ReleaseManifest {
+    pub fn artifact_for(&self, platform: &str) -> Option<&ReleaseArtifact> {
+        self.artifacts.iter().find(|a| a.platform == platform)
+    }
+
+    /// The running build is older than the minimum safe version
+    pub fn is_critical(&self) -> bool {
+        self.minimum_safe_version
+            .as_deref()
+            .map(|min| compare_versions(current_version(), min) == Ordering::Less)
+            .unwrap_or(false)
+    }
+}
+
+/// What the release server serves
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedManifest {
+    /// Manifest JSON exactly as signed
+    pub manifest: String,
+    pub signature: String,
+}
+
+fn parse_version(version: &str) -> Vec<u64> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// Compare dotted versions ("1.2.10" > "1.2.9"); pre-release tags are ignored
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a, b) = (parse_version(a), parse_version(b));
+    for i in 0..a.len().max(b.len()) {
+        match a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    Ordering::Equal
+}
+
+fn verify_with_key(public_key: &str, data: &[u8], signature: &str) -> Result<(), String> {
+    let key_bytes: [u8; 32] = bs58::decode(public_key)
+        .into_vec()
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or("Invalid release key")?;
+    let signature: [u8; 64] = bs58::decode(signature)
+        .into_vec()
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or("Invalid signature encoding")?;
+    VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| format!("Invalid release key: {}", e))?
+        .verify(data, &Signature::from_bytes(&signature))
+        .map_err(|_| "Signature does not match the release key".to_string())
+}
+
+/// Check the manifest signature and parse it
+pub fn verify_manifest(signed: &SignedManifest, public_key: &str) -> Result<ReleaseManifest, String> {
+    verify_with_key(public_key, signed.manifest.as_bytes(), &signed.signature)
+        .map_err(|e| format!("Release manifest rejected: {}", e))?;
+    serde_json::from_str(&signed.manifest).map_err(|e| format!("Invalid release manifest: {}", e))
+}
+
+/// Check a downloaded file against its manifest entry
+pub fn verify_artifact(artifact: &ReleaseArtifact, bytes: &[u8], public_key: &str) -> Result<(), String> {
+    let digest = hex::encode(Sha256::digest(bytes));
+    if !digest.eq_ignore_ascii_case(&artifact.sha256) {
+        return Err("Downloaded update does not match the published checksum".to_string());
+    }
+    verify_with_key(public_key, bytes, &artifact.signature)
+        .map_err(|e| format!("Downloaded update rejected: {}", e))
+}
+
+/// The newer release, if the signed manifest has one
+pub async fn check_for_update() -> Result<Option<ReleaseManifest>, String> {
+    let public_key = release_key().ok_or("This build has no release key; update checks are disabled")?;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let signed: SignedManifest = client
+        .get(manifest_url())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch release manifest: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse release manifest: {}", e))?;
+    let manifest = verify_manifest(&signed, public_key)?;
+
+    println!("🆕 Latest release {} (running {})", manifest.version, current_version());
+    Ok((compare_versions(&manifest.version, current_version()) == Ordering::Greater).then_some(manifest))
+}
+
+/// Download this platform's build of `manifest` and verify it
+pub async fn download_update(manifest: &ReleaseManifest) -> Result<Vec<u8>, String> {
+    let public_key = release_key().ok_or("This build has no release key")?;
+    let artifact = manifest
+        .artifact_for(&current_platform())
+        .ok_or_else(|| format!("No {} build in release {}", current_platform(), manifest.version))?;
+    let bytes = reqwest::get(&artifact.url)
+        .await
+        .map_err(|e| format!("Download failed: {}", e))?
+        .bytes()
+        .await
+        .map_err(|e| format!("Download failed: {}", e))?;
+    verify_artifact(artifact, &bytes, public_key)?;
+    println!("✅ Verified update {} ({} bytes)", manifest.version, bytes.len());
+    Ok(bytes.to_vec())
+}
+
+/// Replace the running executable with a verified build; takes effect on
+/// the next start. The previous binary is kept next to it as `.old`.
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android"), not(target_os = "ios")))]
+pub fn apply_update(verified_binary: &[u8]) -> Result<(), String> {
+    let current = std::env::current_exe().map_err(|e| format!("Cannot locate the app binary: {}", e))?;
+    let staged = current.with_extension("new");
+    let backup = current.with_extension("old");
+
+    std::fs::write(&staged, verified_binary).map_err(|e| format!("Failed to write update: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to mark update executable: {}", e))?;
+    }
+    let _ = std::fs::remove_file(&backup);
+    std::fs::rename(&current, &backup).map_err(|e| format!("Failed to move the old binary aside: {}", e))?;
+    if let Err(e) = std::fs::rename(&staged, &current) {
+        let _ = std::fs::rename(&backup, &current);
+        return Err(format!("Failed to install update: {}", e));
+    }
+    println!("✅ Update installed at {}", current.display());
+    Ok(())
+}
+
+#[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+pub fn apply_update(_verified_binary: &[u8]) -> Result<(), String> {
+    Err("Install updates on this device from your app store".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn release_signer() -> (SigningKey, String) {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let public = bs58::encode(key.verifying_key().to_bytes()).into_string();
+        (key, public)
+    }
+
+    fn sign(key: &SigningKey, data: &[u8]) -> String {
+        bs58::encode(key.sign(data).to_bytes()).into_string()
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.2.10", "1.2.9"), Ordering::Greater);
+        assert_eq!(compare_versions("v0.2.0", "0.2"), Ordering::Equal);
+        assert_eq!(compare_versions("0.1.0-beta", "0.1.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_manifest_signature_is_required() {
+        let (key, public) = release_signer();
+        let manifest = r#"{"version":"9.9.9","changelog":["Fixes"]}"#.to_string();
+        let signed = SignedManifest { signature: sign(&key, manifest.as_bytes()), manifest };
+        let parsed = verify_manifest(&signed, &public).unwrap();
+        assert_eq!(parsed.version, "9.9.9");
+        assert_eq!(parsed.changelog, vec!["Fixes".to_string()]);
+
+        let tampered = SignedManifest {
+            manifest: signed.manifest.replace("9.9.9", "9.9.8"),
+            signature: signed.signature.clone(),
+        };
+        assert!(verify_manifest(&tampered, &public).is_err());
+    }
+
+    #[test]
+    fn test_artifact_checksum_and_signature() {
+        let (key, public) = release_signer();
+        let binary = b"new build".to_vec();
+        let artifact = ReleaseArtifact {
+            platform: current_platform(),
+            url: "https://example.invalid/app".to_string(),
+            sha256: hex::encode(Sha256::digest(&binary)),
+            signature: sign(&key, &binary),
+        };
+        assert!(verify_artifact(&artifact, &binary, &public).is_ok());
+        assert!(verify_artifact(&artifact, b"other build", &public).is_err());
+
+        let unsigned = ReleaseArtifact { signature: sign(&key, b"something else"), ..artifact };
+        assert!(verify_artifact(&unsigned, &binary, &public).is_err());
+    }
+}
@@ -1,7 +1,56 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use reqwest::Client;
 
+/// Epochs of vote credits compared for vote performance
+const PERFORMANCE_EPOCHS: usize = 3;
+/// Epochs of commission history read from inflation rewards
+pub const COMMISSION_EPOCHS: u64 = 5;
+
+/// Reliability, yield and stake concentration of a validator from on-chain data
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorAnalytics {
+    /// (epoch, commission %) paid out in recent epochs, oldest first
+    pub commission_history: Vec<(u64, u8)>,
+    pub delinquent: bool,
+    /// Vote credits over recent epochs as a percentage of the best validator's
+    pub vote_performance: f64,
+    /// Estimated yearly yield after commission, percent
+    pub estimated_apy: Option<f64>,
+    /// Share of all active stake, percent
+    pub stake_share: f64,
+    /// Among the fewest validators that together hold a third of all stake
+    pub superminority: bool,
+    /// Yearly yield Jito MEV tips add after the MEV commission, percent
+    #[serde(default)]
+    pub mev_apy: Option<f64>,
+    /// Share of MEV tips the validator keeps, percent; `None` when it
+    /// doesn't run the Jito client
+    #[serde(default)]
+    pub mev_commission: Option<f64>,
+}
+
+impl ValidatorAnalytics {
+    /// Whether the commission went up within the history
+    pub fn commission_raised(&self) -> bool {
+        self.commission_history.windows(2).any(|pair| pair[1].1 > pair[0].1)
+    }
+
+    /// "5% → 5% → 10%"
+    pub fn commission_trend(&self) -> String {
+        self.commission_history
+            .iter()
+            .map(|(_, commission)| format!("{}%", commission))
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+
+    /// Inflation and MEV yield together
+    pub fn total_apy(&self) -> Option<f64> {
+        self.estimated_apy.map(|apy| apy + self.mev_apy.unwrap_or(0.0))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorInfo {
     pub identity: String,
@@ -12,6 +61,32 @@ pub struct ValidatorInfo {
     pub active_stake: f64,
     pub skip_rate: f64,
     pub is_default: bool,
+    #[serde(default)]
+    pub analytics: Option<ValidatorAnalytics>,
+}
+
+impl ValidatorInfo {
+    /// Ranking score: the estimated yield including MEV (or
+    /// commission-adjusted vote performance without one), discounted for adding to the superminority
+    /// or having raised the commission. Delinquent validators rank last.
+    pub fn score(&self) -> f64 {
+        let Some(analytics) = &self.analytics else {
+            return 0.0;
+        };
+        if analytics.delinquent {
+            return -1.0;
+        }
+        let mut score = analytics
+            .total_apy()
+            .unwrap_or(analytics.vote_performance * (100.0 - self.commission) / 100.0);
+        if analytics.superminority {
+            score *= 0.9;
+        }
+        if analytics.commission_raised() {
+            score *= 0.9;
+        }
+        score
+    }
 }
 
 // RPC response structures for getVoteAccounts
@@ -59,6 +134,7 @@ fn get_static_validators() -> Vec<ValidatorInfo> {
             active_stake: 100.0,
             skip_rate: 0.5,
             is_default: true,
+            analytics: None,
         },
         ValidatorInfo {
             identity: "BULKzVM41WAyQZfL34vxqdsYwEYH9mJAJyzRS4xraf8b".to_string(), 
@@ -69,6 +145,7 @@ fn get_static_validators() -> Vec<ValidatorInfo> {
             active_stake: 100.0,
             skip_rate: 0.5,
             is_default: false,
+            analytics: None,
         },
         ValidatorInfo {
             identity: "6xWLi1TDSh65fWsSqE1zdvANTSuVDRMx4ghsGJwgunS8".to_string(),
@@ -79,6 +156,7 @@ fn get_static_validators() -> Vec<ValidatorInfo> {
             active_stake: 253219.0, // From the data you provided
             skip_rate: 1.0, // Very low estimate given 99.99% voting rate
             is_default: false,
+            analytics: None,
         },
         ValidatorInfo {
             identity: "HEL1USMZKAL2odpNBj2oCjffnFGaYwmbGmyewGv1e2TU".to_string(),
@@ -89,6 +167,7 @@ fn get_static_validators() -> Vec<ValidatorInfo> {
             active_stake: 13453011.453622909,
             skip_rate: 2.5, // Static estimate
             is_default: false,
+            analytics: None,
         },
         // Love validator
         ValidatorInfo {
@@ -100,6 +179,7 @@ fn get_static_validators() -> Vec<ValidatorInfo> {
             active_stake: 0.0,
             skip_rate: 2.0, // Static estimate
             is_default: false,
+            analytics: None,
         },
         ValidatorInfo {
             identity: "DRpbCBMxVnDK7maPM5tGv6MvB3v1sRMC86PZ8okm21hy".to_string(),
@@ -110,6 +190,7 @@ fn get_static_validators() -> Vec<ValidatorInfo> {
             active_stake: 13061017.501494104,
             skip_rate: 1.5, // Static estimate - typically very good
             is_default: false,
+            analytics: None,
         },
         // Main Phase Labs node
         ValidatorInfo {
@@ -121,6 +202,7 @@ fn get_static_validators() -> Vec<ValidatorInfo> {
             active_stake: 0.0,
             skip_rate: 3.0, // Static estimate
             is_default: false,
+            analytics: None,
         },        
         ValidatorInfo {
             identity: "radM7PKUpZwJ9bYPAJ7V8FXHeUmH1zim6iaXUKkftP9".to_string(),
@@ -131,6 +213,7 @@ fn get_static_validators() -> Vec<ValidatorInfo> {
             active_stake: 0.0,
             skip_rate: 2.5, // Static estimate
             is_default: false,
+            analytics: None,
         },
         // Institutional Validator for SOC2 secured staking
         //ValidatorInfo {
@@ -205,18 +288,7 @@ async fn fetch_live_validator_data(rpc_url: Option<&str>) -> Result<Vec<Validato
         ],
     };
     
-    let response = client
-        .post(url)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await?;
-    
-    if !response.status().is_success() {
-        return Err(format!("RPC error: {}", response.status()).into());
-    }
-    
-    let json: serde_json::Value = response.json().await?;
+    let json = crate::rpc_pool::post_json(&client, url, &request).await?;
     
     // Check for errors in the response
     if let Some(error) = json.get("error") {
@@ -231,48 +303,174 @@ async fn fetch_live_validator_data(rpc_url: Option<&str>) -> Result<Vec<Validato
         rpc_response.result.delinquent.len()
     );
     
+    // Cluster-wide figures the analytics compare against
+    let all_accounts: Vec<(&VoteAccountInfo, bool)> = rpc_response.result.current.iter().map(|a| (a, false))
+        .chain(rpc_response.result.delinquent.iter().map(|a| (a, true)))
+        .collect();
+    let total_stake: u64 = all_accounts.iter().map(|(a, _)| a.activated_stake).sum();
+    let best_credits = all_accounts.iter().map(|(a, _)| recent_credits(&a.epoch_credits)).max().unwrap_or(0);
+    let stakes: Vec<(&str, u64)> = all_accounts.iter().map(|(a, _)| (a.vote_pubkey.as_str(), a.activated_stake)).collect();
+    let superminority_members = superminority(&stakes);
+    let current_epoch = all_accounts.iter().filter_map(|(a, _)| a.epoch_credits.last().map(|c| c.0)).max().unwrap_or(0);
+    let staking_yield = match fetch_staking_yield(&client, url, total_stake).await {
+        Ok(staking_yield) => Some(staking_yield),
+        Err(e) => {
+            println!("⚠️ Failed to estimate the staking yield: {}", e);
+            None
+        }
+    };
+
     // Create a HashMap for quick lookup of live data by vote account
-    let mut live_data: HashMap<String, VoteAccountInfo> = HashMap::new();
-    
-    // Add both current and delinquent validators to our lookup
-    for vote_account in rpc_response.result.current {
-        live_data.insert(vote_account.vote_pubkey.clone(), vote_account);
-    }
-    for vote_account in rpc_response.result.delinquent {
-        live_data.insert(vote_account.vote_pubkey.clone(), vote_account);
-    }
-    
+    let live_data: HashMap<&str, (&VoteAccountInfo, bool)> = all_accounts
+        .iter()
+        .map(|(a, delinquent)| (a.vote_pubkey.as_str(), (*a, *delinquent)))
+        .collect();
+
     // Get our curated validator list
     let mut validators = get_static_validators();
-    
+    let commission_history = fetch_commission_history(&validators, current_epoch, url).await;
+    let mev = match crate::mev_rewards::fetch_mev_validators().await {
+        Ok(mev) => mev,
+        Err(e) => {
+            println!("⚠️ Failed to load MEV data: {}", e);
+            HashMap::new()
+        }
+    };
+
     println!("🔄 Updating {} curated validators with live data:", validators.len());
-    
-    // Update each validator with ONLY direct RPC data
+
     for validator in &mut validators {
-        if let Some(live_info) = live_data.get(&validator.vote_account) {
-            // Store old values for comparison
-            let old_commission = validator.commission;
-            let old_stake = validator.active_stake;
-            
-            // Update with ONLY direct RPC data - no calculations
+        if let Some((live_info, delinquent)) = live_data.get(validator.vote_account.as_str()) {
             validator.commission = live_info.commission as f64;
             validator.active_stake = live_info.activated_stake as f64 / 1_000_000_000.0; // Convert lamports to SOL
-            // Keep skip_rate as static value from our list (or set to 0 if you want to remove it)
-            
-            //println!("  ✅ {} ({})", validator.name, validator.vote_account);
-            //println!("     Commission: {:.1}% -> {:.1}%", old_commission, validator.commission);
-            //println!("     Active Stake: {:.2} SOL -> {:.2} SOL", old_stake, validator.active_stake);
-            //println!("     Skip Rate: Using static value {:.1}% (no live data available)", validator.skip_rate);
+            // Keep skip_rate as static value from our list
+
+            let vote_performance = if best_credits > 0 {
+                recent_credits(&live_info.epoch_credits) as f64 * 100.0 / best_credits as f64
+            } else {
+                0.0
+            };
+            validator.analytics = Some(ValidatorAnalytics {
+                commission_history: commission_history.get(&validator.vote_account).cloned().unwrap_or_default(),
+                delinquent: *delinquent,
+                vote_performance,
+                estimated_apy: staking_yield.map(|y| y * (100.0 - validator.commission) / 100.0 * vote_performance / 100.0),
+                stake_share: if total_stake > 0 { live_info.activated_stake as f64 * 100.0 / total_stake as f64 } else { 0.0 },
+                superminority: superminority_members.contains(validator.vote_account.as_str()),
+                mev_apy: mev.get(&validator.vote_account).and_then(|m| m.estimated_mev_apy()),
+                mev_commission: mev
+                    .get(&validator.vote_account)
+                    .filter(|m| m.running_jito)
+                    .and_then(|m| m.mev_commission_percent()),
+            });
         } else {
             println!("  ⚠️  {} ({}): No live data found - keeping static values", 
                 validator.name, validator.vote_account);
         }
     }
-    
+
+    rank_validators(&mut validators);
     println!("🎯 Live validator data update completed!");
     Ok(validators)
 }
 
+/// Vote credits earned over the last `PERFORMANCE_EPOCHS` entries of
+/// `epoch_credits` (epoch, credits, previous credits)
+fn recent_credits(epoch_credits: &[(u64, u64, u64)]) -> u64 {
+    epoch_credits
+        .iter()
+        .rev()
+        .take(PERFORMANCE_EPOCHS)
+        .map(|(_, credits, previous)| credits.saturating_sub(*previous))
+        .sum()
+}
+
+/// Vote accounts of the fewest validators that together hold more than a
+/// third of all stake
+fn superminority<'a>(stakes: &[(&'a str, u64)]) -> HashSet<&'a str> {
+    let total: u128 = stakes.iter().map(|(_, stake)| *stake as u128).sum();
+    let mut sorted = stakes.to_vec();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1));
+    let mut members = HashSet::new();
+    let mut held: u128 = 0;
+    for (vote_account, stake) in sorted {
+        if held * 3 > total {
+            break;
+        }
+        held += stake as u128;
+        members.insert(vote_account);
+    }
+    members
+}
+
+/// Order validators best first by `ValidatorInfo::score`
+pub fn rank_validators(validators: &mut [ValidatorInfo]) {
+    validators.sort_by(|a, b| b.score().partial_cmp(&a.score()).unwrap_or(std::cmp::Ordering::Equal));
+}
+
+/// The `count` best-ranked validators to spread stake over. Delinquent
+/// validators are never picked; ones in the superminority or that raised
+/// their commission only fill places no other validator can.
+pub fn spread_candidates(validators: &[ValidatorInfo], count: usize) -> Vec<ValidatorInfo> {
+    let mut ranked: Vec<ValidatorInfo> = validators
+        .iter()
+        .filter(|v| !v.analytics.as_ref().is_some_and(|a| a.delinquent))
+        .cloned()
+        .collect();
+    rank_validators(&mut ranked);
+    let mut seen = HashSet::new();
+    ranked.retain(|v| seen.insert(v.vote_account.clone()));
+    let (preferred, rest): (Vec<_>, Vec<_>) = ranked
+        .into_iter()
+        .partition(|v| v.analytics.as_ref().is_some_and(|a| !a.superminority && !a.commission_raised()));
+    preferred.into_iter().chain(rest).take(count).collect()
+}
+
+/// Yearly percentage paid to stake before commission: the inflation going to
+/// validators spread over the staked share of the supply
+async fn fetch_staking_yield(client: &Client, url: &str, total_stake: u64) -> Result<f64, Box<dyn std::error::Error>> {
+    let inflation = crate::rpc_pool::post_json(client, url, &RpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: 1,
+        method: "getInflationRate".to_string(),
+        params: vec![],
+    }).await?;
+    let supply = crate::rpc_pool::post_json(client, url, &RpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: 1,
+        method: "getSupply".to_string(),
+        params: vec![serde_json::json!({ "excludeNonCirculatingAccountsList": true })],
+    }).await?;
+
+    let validator_rate = inflation["result"]["validator"].as_f64().ok_or("No validator inflation rate")?;
+    let total_supply = supply["result"]["value"]["total"].as_u64().ok_or("No total supply")?;
+    if total_stake == 0 {
+        return Err("No active stake".into());
+    }
+    Ok(validator_rate * total_supply as f64 / total_stake as f64 * 100.0)
+}
+
+/// Commission each validator charged in the last `COMMISSION_EPOCHS`
+/// completed epochs, from its vote account's inflation rewards
+async fn fetch_commission_history(validators: &[ValidatorInfo], current_epoch: u64, url: &str) -> HashMap<String, Vec<(u64, u8)>> {
+    let vote_accounts: Vec<String> = validators.iter().map(|v| v.vote_account.clone()).collect();
+    let mut history: HashMap<String, Vec<(u64, u8)>> = HashMap::new();
+    let first_epoch = current_epoch.saturating_sub(COMMISSION_EPOCHS);
+    for epoch in first_epoch..current_epoch {
+        match crate::rpc::get_inflation_rewards(&vote_accounts, epoch, Some(url)).await {
+            Ok(rewards) => {
+                for (vote_account, reward) in vote_accounts.iter().zip(rewards) {
+                    if let Some(commission) = reward.and_then(|r| r.commission) {
+                        history.entry(vote_account.clone()).or_default().push((epoch, commission));
+                    }
+                }
+            }
+            Err(e) => println!("⚠️ Failed to load commission history for epoch {}: {}", epoch, e),
+        }
+    }
+    history
+}
+
 // Legacy function for backward compatibility - now just calls the async version
 // This can be removed once you update all calling code
 pub fn get_recommended_validators_sync() -> Vec<ValidatorInfo> {
@@ -285,4 +483,54 @@ pub async fn fetch_live_validators(rpc_url: Option<&str>) -> Result<Vec<Validato
     // get_recommended_validators already handles errors internally and returns Vec<ValidatorInfo>
     // It falls back to static data if live data fails, so it never fails
     Ok(get_recommended_validators().await)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(name: &str, commission: f64, analytics: Option<ValidatorAnalytics>) -> ValidatorInfo {
+        ValidatorInfo {
+            identity: name.to_string(),
+            vote_account: name.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            commission,
+            active_stake: 0.0,
+            skip_rate: 0.0,
+            is_default: false,
+            analytics,
+        }
+    }
+
+    #[test]
+    fn test_validator_analytics() {
+        assert_eq!(recent_credits(&[(1, 100, 0), (2, 300, 100), (3, 600, 300), (4, 1000, 600)]), 900);
+
+        let members = superminority(&[("a", 40), ("b", 30), ("c", 20), ("d", 10)]);
+        assert_eq!(members, HashSet::from(["a"]));
+        let members = superminority(&[("a", 30), ("b", 30), ("c", 20), ("d", 20)]);
+        assert_eq!(members, HashSet::from(["a", "b"]));
+
+        let raised = ValidatorAnalytics { commission_history: vec![(1, 5), (2, 10)], ..Default::default() };
+        assert!(raised.commission_raised());
+        assert_eq!(raised.commission_trend(), "5% → 10%");
+
+        let analytics = |apy: f64| ValidatorAnalytics { estimated_apy: Some(apy), ..Default::default() };
+        let mut validators = vec![
+            validator("unknown", 0.0, None),
+            validator("delinquent", 0.0, Some(ValidatorAnalytics { delinquent: true, ..analytics(8.0) })),
+            validator("crowded", 0.0, Some(ValidatorAnalytics { superminority: true, ..analytics(7.0) })),
+            validator("good", 0.0, Some(analytics(6.5))),
+        ];
+        rank_validators(&mut validators);
+        let order: Vec<&str> = validators.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(order, ["good", "crowded", "unknown", "delinquent"]);
+
+        validators.push(validator("fine", 0.0, Some(analytics(6.0))));
+        let picks = spread_candidates(&validators, 3);
+        let order: Vec<&str> = picks.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(order, ["good", "fine", "crowded"]);
+        assert_eq!(spread_candidates(&validators, 10).len(), 4);
+    }
+}
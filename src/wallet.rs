@@ -2,14 +2,23 @@
 use ed25519_dalek::{SigningKey, VerifyingKey, Signer, Signature};
 use rand::{rngs::OsRng, Rng};
 use serde::{Deserialize, Serialize};
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::signer::keypair::keypair_from_seed_and_derivation_path;
 use bs58;
 
+/// Words in a newly generated recovery phrase
+pub const MNEMONIC_WORDS: usize = 12;
+
 /// Persistable wallet info for storage or serialization
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct WalletInfo {
     pub name: String,
     pub address: String,
     pub encrypted_key: String,
+    /// Derived from a recovery phrase with a BIP39 passphrase, which is needed
+    /// along with the phrase to recover it
+    #[serde(default)]
+    pub passphrase_protected: bool,
 }
 
 /// In-memory wallet holding an ed25519 signing key
@@ -55,6 +64,16 @@ impl Wallet {
         }
     }
 
+    /// Derive from a BIP39 recovery phrase and optional passphrase (the "25th
+    /// word") on Solana's default path m/44'/501'/0'/0'. Every passphrase
+    /// gives a different wallet, so none is ever rejected as wrong.
+    pub fn from_mnemonic(phrase: &str, passphrase: &str, name: String) -> Result<Self, String> {
+        let seed = mnemonic_seed(phrase, passphrase)?;
+        let keypair = keypair_from_seed_and_derivation_path(&seed, Some(DerivationPath::new_bip44(Some(0), Some(0))))
+            .map_err(|e| format!("Key derivation failed: {}", e))?;
+        Self::from_private_key(&keypair.to_bytes(), name)
+    }
+
     /// Base58-encoded Solana-style public key
     pub fn get_public_key(&self) -> String {
         let vk = self.signing_key.verifying_key();
@@ -81,12 +100,20 @@ impl Wallet {
             name: self.name.clone(),
             address: self.get_public_key(),
             encrypted_key: self.get_private_key(),
+            passphrase_protected: false,
         }
     }
 
     /// Deserialize from `WalletInfo`
     pub fn from_wallet_info(info: &WalletInfo) -> Result<Self, String> {
-        let bytes = bs58::decode(&info.encrypted_key)
+        if info.encrypted_key.is_empty() {
+            return Err("Wallet key is not stored on this device (passkey or shared wallet)".into());
+        }
+        if crate::storage::is_unreadable_stashed_key(&info.encrypted_key) {
+            return Err("Wallet key could not be read from the platform secret store".into());
+        }
+        let private_key = crate::pin::open_wallet_key(&info.encrypted_key)?;
+        let bytes = bs58::decode(&private_key)
             .into_vec()
             .map_err(|e| format!("Decode error: {}", e))?;
         Self::from_private_key(&bytes, info.name.clone())
@@ -114,3 +141,50 @@ impl Wallet {
         signature.to_bytes().to_vec()
     }
 }
+
+/// A new random BIP39 recovery phrase
+pub fn generate_mnemonic() -> String {
+    bip39::Mnemonic::generate(MNEMONIC_WORDS)
+        .expect("12 words is a valid phrase length")
+        .to_string()
+}
+
+/// Whether an import looks like a recovery phrase rather than a key
+pub fn looks_like_mnemonic(input: &str) -> bool {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    [12, 15, 18, 21, 24].contains(&words.len()) && words.iter().all(|w| w.chars().all(|c| c.is_alphabetic()))
+}
+
+/// BIP39 seed of a recovery phrase and passphrase, before any derivation path
+pub fn mnemonic_seed(phrase: &str, passphrase: &str) -> Result<[u8; 64], String> {
+    let mnemonic = bip39::Mnemonic::parse(normalize_phrase(phrase))
+        .map_err(|e| format!("Invalid recovery phrase: {}", e))?;
+    Ok(mnemonic.to_seed(passphrase))
+}
+
+fn normalize_phrase(phrase: &str) -> String {
+    phrase.split_whitespace().map(|w| w.to_lowercase()).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_from_mnemonic() {
+        let plain = Wallet::from_mnemonic(PHRASE, "", "a".to_string()).unwrap();
+        let again = Wallet::from_mnemonic(&PHRASE.to_uppercase().replace(' ', "  "), "", "b".to_string()).unwrap();
+        let protected = Wallet::from_mnemonic(PHRASE, "hunter2", "c".to_string()).unwrap();
+        // Same address Phantom derives for this phrase
+        assert_eq!(plain.get_public_key(), "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+        assert_eq!(plain.get_public_key(), again.get_public_key());
+        assert_ne!(plain.get_public_key(), protected.get_public_key());
+        assert!(Wallet::from_mnemonic("abandon abandon abandon", "", "d".to_string()).is_err());
+
+        assert!(looks_like_mnemonic(PHRASE));
+        assert!(looks_like_mnemonic(&generate_mnemonic()));
+        assert!(!looks_like_mnemonic(&plain.get_private_key()));
+    }
+}
@@ -0,0 +1,107 @@
+// src/wallet_groups.rs
+//! Order and folders of stored wallets in the wallet list
+//!
+//! Both come from wallet profiles (see `policy`): each wallet's folder and
+//! position. Wallets are listed by folder, those without one first, and
+//! within a folder by position, then in the order they were added.
+
+use crate::policy::WalletProfile;
+use crate::wallet::WalletInfo;
+
+/// Accent colors a wallet can be given
+pub const WALLET_COLORS: [&str; 8] = [
+    "#ef4444", "#f97316", "#eab308", "#22c55e", "#06b6d4", "#6366f1", "#a855f7", "#ec4899",
+];
+
+/// A folder of the wallet list, holding indices into the stored wallets
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletFolder {
+    /// Empty for wallets outside any folder
+    pub name: String,
+    pub indices: Vec<usize>,
+}
+
+/// Group stored wallets into folders in display order
+pub fn organize(wallets: &[WalletInfo], profiles: &[WalletProfile]) -> Vec<WalletFolder> {
+    let profile = |index: usize| profiles.iter().find(|p| p.address == wallets[index].address);
+    let mut order: Vec<usize> = (0..wallets.len()).collect();
+    order.sort_by_key(|&index| (profile(index).and_then(|p| p.position).unwrap_or(u32::MAX), index));
+
+    let mut folders: Vec<WalletFolder> = Vec::new();
+    for index in order {
+        let name = profile(index).map(|p| p.folder.trim().to_string()).unwrap_or_default();
+        match folders.iter_mut().find(|f| f.name.eq_ignore_ascii_case(&name)) {
+            Some(folder) => folder.indices.push(index),
+            None => folders.push(WalletFolder { name, indices: vec![index] }),
+        }
+    }
+    folders.sort_by_key(|f| (!f.name.is_empty(), f.name.to_lowercase()));
+    folders
+}
+
+/// Every wallet's address in display order after moving the wallet at
+/// `index` one place up or down within its folder
+pub fn reordered(wallets: &[WalletInfo], folders: &[WalletFolder], index: usize, up: bool) -> Vec<String> {
+    let mut folders = folders.to_vec();
+    for folder in &mut folders {
+        if let Some(from) = folder.indices.iter().position(|&i| i == index) {
+            let to = if up { from.checked_sub(1) } else { Some(from + 1).filter(|&to| to < folder.indices.len()) };
+            if let Some(to) = to {
+                folder.indices.swap(from, to);
+            }
+        }
+    }
+    folders
+        .iter()
+        .flat_map(|folder| folder.indices.iter().map(|&i| wallets[i].address.clone()))
+        .collect()
+}
+
+/// Folder names in use, for suggestions
+pub fn folder_names(profiles: &[WalletProfile]) -> Vec<String> {
+    let mut names: Vec<String> = profiles
+        .iter()
+        .map(|p| p.folder.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallet(address: &str) -> WalletInfo {
+        WalletInfo {
+            name: address.to_string(),
+            address: address.to_string(),
+            encrypted_key: String::new(),
+            passphrase_protected: false,
+        }
+    }
+
+    fn profile(address: &str, folder: &str, position: Option<u32>) -> WalletProfile {
+        WalletProfile { folder: folder.to_string(), position, ..WalletProfile::new(address) }
+    }
+
+    #[test]
+    fn test_organize() {
+        let wallets = vec![wallet("a"), wallet("b"), wallet("c"), wallet("d")];
+        let profiles = vec![profile("a", "Cold", None), profile("c", "cold", Some(0)), profile("d", "", Some(1))];
+        let folders = organize(&wallets, &profiles);
+        assert_eq!(
+            folders,
+            vec![
+                WalletFolder { name: String::new(), indices: vec![3, 1] },
+                WalletFolder { name: "cold".to_string(), indices: vec![2, 0] },
+            ]
+        );
+
+        assert_eq!(reordered(&wallets, &folders, 0, true), vec!["d", "b", "a", "c"]);
+        // Already last in its folder
+        assert_eq!(reordered(&wallets, &folders, 0, false), vec!["d", "b", "c", "a"]);
+        assert_eq!(folder_names(&profiles), vec!["Cold"]);
+    }
+}
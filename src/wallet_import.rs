@@ -0,0 +1,255 @@
+// src/wallet_import.rs
+//! Importing what other wallets export
+//!
+//! Phantom, Solflare and Backpack export a base58 secret key or a recovery
+//! phrase, the Solana CLI a JSON byte array, web3.js tools a JSON object
+//! holding the secret key, and this app an encrypted keystore (see
+//! `keystore`). A phrase doesn't say which derivation path its
+//! wallet used, so every common path is derived for the first few accounts
+//! and checked on-chain for funds.
+
+use crate::rpc::get_multiple_accounts;
+use crate::keystore::is_encrypted_keystore;
+use crate::shamir::looks_like_shares;
+use crate::wallet::{looks_like_mnemonic, mnemonic_seed, Wallet};
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::signer::keypair::{keypair_from_seed, keypair_from_seed_and_derivation_path};
+
+/// Accounts derived per path when scanning a phrase
+pub const SCAN_ACCOUNTS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Base58Key,
+    ByteArray,
+    JsonKeystore,
+    EncryptedKeystore,
+    RecoveryPhrase,
+    Shares,
+}
+
+impl ImportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Base58Key => "Base58 private key (Phantom, Solflare, Backpack)",
+            Self::ByteArray => "Byte array (Solana CLI keypair file)",
+            Self::JsonKeystore => "JSON keystore",
+            Self::EncryptedKeystore => "Encrypted keystore",
+            Self::RecoveryPhrase => "Recovery phrase",
+            Self::Shares => "SLIP-39 shares",
+        }
+    }
+}
+
+/// Recognize what was pasted into the import field
+pub fn detect_format(input: &str) -> Option<ImportFormat> {
+    let input = input.trim();
+    if input.is_empty() {
+        None
+    } else if looks_like_shares(input) {
+        Some(ImportFormat::Shares)
+    } else if looks_like_mnemonic(input) {
+        Some(ImportFormat::RecoveryPhrase)
+    } else if input.starts_with('{') && is_encrypted_keystore(input) {
+        Some(ImportFormat::EncryptedKeystore)
+    } else if input.starts_with('{') {
+        Some(ImportFormat::JsonKeystore)
+    } else if input.starts_with('[') || input.contains(',') {
+        Some(ImportFormat::ByteArray)
+    } else {
+        bs58::decode(input)
+            .into_vec()
+            .ok()
+            .filter(|bytes| bytes.len() == 32 || bytes.len() == 64)
+            .map(|_| ImportFormat::Base58Key)
+    }
+}
+
+/// Secret key bytes from a JSON object such as `{"publicKey": ..., "secretKey":
+/// [...]}`. The key may be a byte array, a base58 string, or a serialized
+/// `Uint8Array` (`{"0": 12, "1": 34, ...}`).
+pub fn parse_json_keystore(text: &str) -> Result<Vec<u8>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(text.trim()).map_err(|e| format!("Invalid JSON keystore: {}", e))?;
+    let secret = ["secretKey", "secret_key", "privateKey", "private_key"]
+        .iter()
+        .find_map(|field| value.get(field))
+        .ok_or_else(|| "JSON keystore has no secretKey or privateKey field".to_string())?;
+    match secret {
+        serde_json::Value::String(encoded) => bs58::decode(encoded.trim())
+            .into_vec()
+            .map_err(|e| format!("Invalid base58 key in keystore: {}", e)),
+        serde_json::Value::Array(_) => {
+            serde_json::from_value(secret.clone()).map_err(|e| format!("Invalid key bytes in keystore: {}", e))
+        }
+        serde_json::Value::Object(bytes) => {
+            let mut indexed = bytes
+                .iter()
+                .map(|(index, byte)| {
+                    let index: usize = index.parse().map_err(|_| "Invalid key bytes in keystore".to_string())?;
+                    let byte = byte
+                        .as_u64()
+                        .and_then(|b| u8::try_from(b).ok())
+                        .ok_or_else(|| "Invalid key bytes in keystore".to_string())?;
+                    Ok((index, byte))
+                })
+                .collect::<Result<Vec<(usize, u8)>, String>>()?;
+            indexed.sort_unstable();
+            Ok(indexed.into_iter().map(|(_, byte)| byte).collect())
+        }
+        _ => Err("Unsupported key in JSON keystore".to_string()),
+    }
+}
+
+/// Ways wallets turn a recovery phrase into keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DerivationScheme {
+    /// m/44'/501'/n'/0', used by this app
+    Bip44Change,
+    /// m/44'/501'/n'
+    Bip44,
+    /// m/44'/501'
+    Bip44Root,
+    /// The first 32 bytes of the seed, with no path
+    SeedPrefix,
+}
+
+impl DerivationScheme {
+    pub const ALL: [Self; 4] = [Self::Bip44Change, Self::Bip44, Self::Bip44Root, Self::SeedPrefix];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Bip44Change => "Phantom, Solflare, Backpack",
+            Self::Bip44 => "Solflare (older), Ledger",
+            Self::Bip44Root => "Solana CLI with a path",
+            Self::SeedPrefix => "solana-keygen default",
+        }
+    }
+
+    pub fn path(&self, account: u32) -> String {
+        match self {
+            Self::Bip44Change => format!("m/44'/501'/{}'/0'", account),
+            Self::Bip44 => format!("m/44'/501'/{}'", account),
+            Self::Bip44Root => "m/44'/501'".to_string(),
+            Self::SeedPrefix => "no path".to_string(),
+        }
+    }
+
+    /// Whether the scheme has more than one account
+    fn has_accounts(&self) -> bool {
+        matches!(self, Self::Bip44Change | Self::Bip44)
+    }
+
+    fn derive(&self, seed: &[u8], account: u32, name: String) -> Result<Wallet, String> {
+        let keypair = match self {
+            Self::Bip44Change => {
+                keypair_from_seed_and_derivation_path(seed, Some(DerivationPath::new_bip44(Some(account), Some(0))))
+            }
+            Self::Bip44 => keypair_from_seed_and_derivation_path(seed, Some(DerivationPath::new_bip44(Some(account), None))),
+            Self::Bip44Root => keypair_from_seed_and_derivation_path(seed, Some(DerivationPath::new_bip44(None, None))),
+            Self::SeedPrefix => keypair_from_seed(&seed[..32]),
+        }
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+        Wallet::from_private_key(&keypair.to_bytes(), name)
+    }
+}
+
+/// One address a phrase derives to
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivedAccount {
+    pub scheme: DerivationScheme,
+    pub account: u32,
+    pub address: String,
+    /// `None` until checked on-chain; zero for accounts that don't exist
+    pub lamports: Option<u64>,
+}
+
+/// The wallet a phrase derives to on one path
+pub fn wallet_from_phrase(
+    phrase: &str,
+    passphrase: &str,
+    scheme: DerivationScheme,
+    account: u32,
+    name: String,
+) -> Result<Wallet, String> {
+    scheme.derive(&mnemonic_seed(phrase, passphrase)?, account, name)
+}
+
+/// Every address a phrase derives to on the common paths, `accounts` deep
+pub fn derive_accounts(phrase: &str, passphrase: &str, accounts: u32) -> Result<Vec<DerivedAccount>, String> {
+    let seed = mnemonic_seed(phrase, passphrase)?;
+    let mut derived = Vec::new();
+    for scheme in DerivationScheme::ALL {
+        let count = if scheme.has_accounts() { accounts } else { 1 };
+        for account in 0..count {
+            derived.push(DerivedAccount {
+                scheme,
+                account,
+                address: scheme.derive(&seed, account, String::new())?.get_public_key(),
+                lamports: None,
+            });
+        }
+    }
+    Ok(derived)
+}
+
+/// Derive a phrase's addresses and look up their SOL balances
+pub async fn scan_accounts(phrase: &str, passphrase: &str, rpc_url: Option<&str>) -> Result<Vec<DerivedAccount>, String> {
+    let mut derived = derive_accounts(phrase, passphrase, SCAN_ACCOUNTS)?;
+    let addresses: Vec<String> = derived.iter().map(|d| d.address.clone()).collect();
+    let accounts = get_multiple_accounts(&addresses, rpc_url).await?;
+    for (entry, account) in derived.iter_mut().zip(accounts) {
+        entry.lamports = Some(account.map(|a| a.lamports).unwrap_or(0));
+    }
+    Ok(derived)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_detect_format() {
+        let wallet = Wallet::new("a".to_string());
+        assert_eq!(detect_format(&wallet.get_private_key()), Some(ImportFormat::Base58Key));
+        assert_eq!(detect_format(&wallet.get_private_key_only()), Some(ImportFormat::Base58Key));
+        assert_eq!(detect_format("[1, 2, 3]"), Some(ImportFormat::ByteArray));
+        assert_eq!(detect_format(r#"{"secretKey": [1, 2]}"#), Some(ImportFormat::JsonKeystore));
+        assert_eq!(detect_format(r#"{"address": "a", "crypto": {}}"#), Some(ImportFormat::EncryptedKeystore));
+        assert_eq!(detect_format(PHRASE), Some(ImportFormat::RecoveryPhrase));
+        assert_eq!(detect_format(&wallet.get_public_key()[..20]), None);
+        assert_eq!(detect_format("  "), None);
+    }
+
+    #[test]
+    fn test_parse_json_keystore() {
+        let wallet = Wallet::new("a".to_string());
+        let bytes = bs58::decode(wallet.get_private_key()).into_vec().unwrap();
+        let as_array = serde_json::json!({ "publicKey": wallet.get_public_key(), "secretKey": bytes }).to_string();
+        let as_string = serde_json::json!({ "privateKey": wallet.get_private_key() }).to_string();
+        let typed_array: serde_json::Map<String, serde_json::Value> =
+            bytes.iter().enumerate().map(|(i, b)| (i.to_string(), serde_json::json!(b))).collect();
+        let as_object = serde_json::json!({ "secretKey": typed_array }).to_string();
+
+        for text in [as_array, as_string, as_object] {
+            assert_eq!(parse_json_keystore(&text).unwrap(), bytes);
+        }
+        assert!(parse_json_keystore(r#"{"publicKey": "abc"}"#).is_err());
+    }
+
+    #[test]
+    fn test_derive_accounts() {
+        let derived = derive_accounts(PHRASE, "", 3).unwrap();
+        assert_eq!(derived.len(), 3 + 3 + 1 + 1);
+        let default = Wallet::from_mnemonic(PHRASE, "", String::new()).unwrap();
+        assert_eq!(derived[0].address, default.get_public_key());
+        assert_eq!(derived[0].scheme.path(0), "m/44'/501'/0'/0'");
+
+        let second = &derived[4];
+        assert_eq!((second.scheme, second.account), (DerivationScheme::Bip44, 1));
+        let wallet = wallet_from_phrase(PHRASE, "", second.scheme, second.account, String::new()).unwrap();
+        assert_eq!(wallet.get_public_key(), second.address);
+    }
+}